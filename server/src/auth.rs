@@ -1,7 +1,8 @@
 //! Authentication module for password hashing and JWT token management
 //!
 //! This module provides password hashing compatible with SurrealDB's format
-//! and JWT token creation/validation for session management.
+//! (with configurable cost and rehash-on-login detection) and JWT token
+//! creation/validation for session management.
 
 use argon2::{
     Algorithm, Argon2, Params, Version,
@@ -13,6 +14,7 @@ use jsonwebtoken::{
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::config::PasswordHashParams;
 use crate::error::{Error, Result};
 
 /// JWT Claims structure for our tokens
@@ -33,22 +35,36 @@ pub struct Claims {
     /// Defaults to false so tokens minted before this claim existed decode.
     #[serde(default)]
     pub remember: bool,
+    /// Issuer: the deployment that minted the token ([`JwtConfig::issuer`]).
+    /// Optional so tokens minted before this claim existed decode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    /// Audience: the deployment the token is for ([`JwtConfig::audience`]).
+    /// A token carrying another environment's audience is rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
 }
 
-/// Configuration for password hashing (matches SurrealDB's settings)
+/// Configuration for password hashing (Argon2id, SurrealDB-compatible)
 pub struct PasswordConfig;
 
 impl PasswordConfig {
-    /// Create Argon2 hasher with SurrealDB-compatible parameters
+    /// Argon2id hasher at the configured cost (`ARGON2_*`, see
+    /// [`PasswordHashParams`]). The defaults match SurrealDB's
+    /// `crypto::argon2` settings: m=19456, t=2, p=1.
     pub fn argon2() -> Argon2<'static> {
-        // Match SurrealDB's parameters: m=19456, t=2, p=1
+        Self::argon2_with(crate::config::password_hash_params())
+    }
+
+    /// Argon2id hasher at explicit cost parameters.
+    pub fn argon2_with(cost: &PasswordHashParams) -> Argon2<'static> {
         let params = Params::new(
-            19456, // memory cost in KiB (19 MiB)
-            2,     // time cost (iterations)
-            1,     // parallelism
-            None,  // output length (default)
+            cost.memory_kib,
+            cost.iterations,
+            cost.parallelism,
+            None, // output length (default)
         )
-        .expect("Valid Argon2 parameters");
+        .expect("Argon2 parameters validated by PasswordHashParams::from_env");
 
         Argon2::new(
             Algorithm::Argon2id, // SurrealDB uses Argon2id
@@ -74,8 +90,13 @@ pub async fn hash_password(password: &str) -> Result<String> {
 /// Synchronous [`hash_password`]. Blocks the calling thread for the full
 /// Argon2id derivation — never call directly from async code.
 pub fn hash_password_sync(password: &str) -> Result<String> {
+    hash_password_with_sync(password, crate::config::password_hash_params())
+}
+
+/// Synchronous hash at explicit cost parameters (tests, migrations).
+pub fn hash_password_with_sync(password: &str, cost: &PasswordHashParams) -> Result<String> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = PasswordConfig::argon2();
+    let argon2 = PasswordConfig::argon2_with(cost);
 
     let password_hash = argon2
         .hash_password(password.as_bytes(), &salt)
@@ -84,6 +105,28 @@ pub fn hash_password_sync(password: &str) -> Result<String> {
     Ok(password_hash.to_string())
 }
 
+/// True when `hash` should be replaced on the next successful login: it
+/// isn't Argon2id, or any of its embedded cost parameters are below the
+/// configured ones. Unparseable hashes return `false` — they can't have
+/// just verified, so there's nothing safe to upgrade.
+pub fn needs_rehash(hash: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(hash) else {
+        return false;
+    };
+    if parsed.algorithm != Algorithm::Argon2id.ident() {
+        return true;
+    }
+    let Ok(params) = Params::try_from(&parsed) else {
+        return true;
+    };
+    let stored = PasswordHashParams {
+        memory_kib: params.m_cost(),
+        iterations: params.t_cost(),
+        parallelism: params.p_cost(),
+    };
+    stored.is_weaker_than(crate::config::password_hash_params())
+}
+
 /// Verify a password against a SurrealDB-compatible Argon2id hash.
 ///
 /// Verification re-runs the full Argon2id derivation (~50–100 ms CPU), so
@@ -110,6 +153,32 @@ pub fn verify_password_sync(password: &str, hash: &str) -> Result<bool> {
     }
 }
 
+/// Hash of a random throwaway password at the configured cost, used by
+/// [`dummy_verify_password`]. Generated once per process, so it always
+/// carries the same parameters real (fresh) hashes do.
+static DUMMY_HASH: std::sync::LazyLock<String> = std::sync::LazyLock::new(|| {
+    let throwaway = SaltString::generate(&mut OsRng);
+    hash_password_sync(throwaway.as_str()).expect("hashing a throwaway password")
+});
+
+/// Derive [`DUMMY_HASH`] now instead of on the first unknown-user login,
+/// which would otherwise pay for a hash *and* a verify and stand out.
+/// Call once at startup, after the Argon2 cost is configured; blocks for
+/// one full derivation.
+pub fn init_dummy_hash() {
+    std::sync::LazyLock::force(&DUMMY_HASH);
+}
+
+/// Burn the same Argon2id work as [`verify_password`] without a real hash.
+///
+/// Call this on login paths where the account wasn't found, so "unknown
+/// user" and "wrong password" take comparable time and response timing
+/// can't be used to enumerate usernames. The result is always discarded.
+pub async fn dummy_verify_password(password: &str) {
+    let password = password.to_owned();
+    let _ = tokio::task::spawn_blocking(move || verify_password_sync(&password, &DUMMY_HASH)).await;
+}
+
 /// JWT configuration
 pub struct JwtConfig;
 
@@ -140,6 +209,21 @@ impl JwtConfig {
             .parse()
             .unwrap_or(2_592_000)
     }
+
+    /// Value of the `iss` claim on minted tokens, and the only issuer
+    /// [`decode_jwt`] accepts. From `JWT_ISSUER`, defaulting to the
+    /// public base URL (see [`crate::config::public_base_url`]).
+    pub fn issuer() -> String {
+        std::env::var("JWT_ISSUER").unwrap_or_else(|_| crate::config::public_base_url())
+    }
+
+    /// Value of the `aud` claim on minted tokens, and the only audience
+    /// [`decode_jwt`] accepts. From `JWT_AUDIENCE`, defaulting to the public
+    /// base URL, so staging and production never accept each other's
+    /// sessions even if they share a secret.
+    pub fn audience() -> String {
+        std::env::var("JWT_AUDIENCE").unwrap_or_else(|_| crate::config::public_base_url())
+    }
 }
 
 /// Session length in seconds for a login: the standard 12-hour token, or the
@@ -154,6 +238,37 @@ pub fn session_duration(remember: bool) -> u64 {
     }
 }
 
+/// The `auth_token` cookie carrying a session JWT.
+///
+/// A remembered session gets a persistent cookie whose `Max-Age` matches
+/// the token's lifetime ([`session_duration`]), so the two expire together;
+/// otherwise it is a browser-session cookie, dropped when the browser
+/// closes even though the 12-hour token would still verify.
+///
+/// Always `HttpOnly`; `Secure` and `SameSite` come from
+/// [`crate::config::cookie_secure`] and [`crate::config::cookie_same_site`].
+pub fn session_cookie(token: String, remember: bool) -> cookie::Cookie<'static> {
+    let mut builder = cookie::Cookie::build(("auth_token", token))
+        .path("/")
+        .same_site(crate::config::cookie_same_site())
+        .http_only(true)
+        .secure(crate::config::cookie_secure());
+    if remember {
+        builder = builder.max_age(cookie::time::Duration::seconds(
+            session_duration(true) as i64
+        ));
+    }
+    builder.build()
+}
+
+/// A [`session_cookie`] that expires immediately, signing the browser out.
+/// Carries the same attributes so browsers match it to the live cookie.
+pub fn cleared_session_cookie() -> cookie::Cookie<'static> {
+    let mut cookie = session_cookie(String::new(), false);
+    cookie.set_max_age(cookie::time::Duration::ZERO);
+    cookie
+}
+
 /// How old (in seconds) a remembered token must be before the auth middleware
 /// re-issues it. Throttles the sliding refresh to roughly once a day instead
 /// of minting a token on every request.
@@ -219,6 +334,8 @@ fn build_jwt(
         iat: now,
         exp: now + duration_secs,
         remember,
+        iss: Some(JwtConfig::issuer()),
+        aud: Some(JwtConfig::audience()),
     };
 
     let header = Header::new(JwtAlgorithm::HS256);
@@ -233,9 +350,16 @@ fn build_jwt(
 }
 
 /// Decode and validate a JWT token
+///
+/// Besides the signature and `exp`, the `iss` and `aud` claims must match
+/// [`JwtConfig::issuer`] and [`JwtConfig::audience`]. Tokens minted before
+/// those claims existed carry neither and are still accepted until they
+/// expire.
 pub fn decode_jwt(token: &str) -> Result<Claims> {
     let secret = JwtConfig::secret()?;
-    let validation = Validation::new(JwtAlgorithm::HS256);
+    let mut validation = Validation::new(JwtAlgorithm::HS256);
+    validation.set_issuer(&[JwtConfig::issuer()]);
+    validation.set_audience(&[JwtConfig::audience()]);
 
     let token_data = decode::<Claims>(
         token,
@@ -0,0 +1,58 @@
+//! Generated avatar placeholders for people and organizations without an
+//! uploaded image.
+//!
+//! A placeholder is an SVG square showing the entity's initials over a
+//! background color picked from a fixed palette by hashing the entity's id,
+//! so the same person or organization always gets the same color on every
+//! page and every server. The hash is FNV-1a rather than `DefaultHasher`,
+//! whose output isn't guaranteed stable across Rust releases.
+
+use crate::html::escape_attr;
+
+/// Background colors, all dark enough for white initials to stay legible.
+const PALETTE: &[&str] = &[
+    "#4f46e5", "#7c3aed", "#9333ea", "#c026d3", "#db2777", "#e11d48", "#dc2626", "#ea580c",
+    "#b45309", "#4d7c0f", "#15803d", "#047857", "#0f766e", "#0e7490", "#0369a1", "#1d4ed8",
+];
+
+/// Initials for display: first letters of the first and last words, or the
+/// first two letters of a single word, uppercased. `"??"` for a blank name.
+pub fn initials(name: &str) -> String {
+    let parts: Vec<&str> = name.split_whitespace().collect();
+    match parts.as_slice() {
+        [] => "??".to_string(),
+        [single] => single.chars().take(2).collect::<String>().to_uppercase(),
+        [first, .., last] => {
+            let first = first.chars().next().unwrap_or('?');
+            let last = last.chars().next().unwrap_or('?');
+            format!("{}{}", first, last).to_uppercase()
+        }
+    }
+}
+
+/// Stable background color for an entity id.
+///
+/// A table prefix is ignored, so `person:abc` and `abc` share a color.
+pub fn background_color(id: &str) -> &'static str {
+    let key = id.split_once(':').map_or(id, |(_, key)| key);
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    PALETTE[(hash % PALETTE.len() as u64) as usize]
+}
+
+/// Square SVG placeholder showing `name`'s initials on `id`'s color.
+pub fn placeholder_svg(id: &str, name: &str) -> String {
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="128" height="128" viewBox="0 0 128 128" role="img" aria-label="{label}">"#,
+            r#"<rect width="128" height="128" fill="{color}"/>"#,
+            r##"<text x="50%" y="50%" dy=".35em" text-anchor="middle" fill="#ffffff" "##,
+            r#"font-family="system-ui, -apple-system, 'Segoe UI', sans-serif" font-size="52" font-weight="600">{initials}</text>"#,
+            "</svg>"
+        ),
+        label = escape_attr(name),
+        color = background_color(id),
+        initials = escape_attr(&initials(name)),
+    )
+}
@@ -0,0 +1,271 @@
+//! Optional third-party captcha on the anonymous-facing write endpoints.
+//!
+//! The built-in anti-bot layers (honeypot, form token, proof-of-work; see
+//! `routes::auth`) stop naive scripts. Deployments that see real abuse can
+//! add hCaptcha or Cloudflare Turnstile on top:
+//!
+//! | Variable             | Meaning                                              |
+//! |----------------------|------------------------------------------------------|
+//! | `CAPTCHA_PROVIDER`   | `hcaptcha` or `turnstile`                            |
+//! | `CAPTCHA_SITE_KEY`   | public key rendered into the widget                  |
+//! | `CAPTCHA_SECRET_KEY` | server-side key for the provider's `siteverify` call |
+//! | `CAPTCHA_ACTIONS`    | comma list of [`CaptchaAction`]s; default: all       |
+//!
+//! With any of the first three unset the captcha is off: [`widget`] renders
+//! nothing and [`verify`] passes, so local development needs no keys.
+//! Templates call [`widget`] inside the protected form; handlers call
+//! [`verify`] with the token the widget posted. hCaptcha posts it as
+//! `h-captcha-response` and Turnstile as `cf-turnstile-response`; form
+//! structs accept both through `#[serde(alias = ...)]` on one field.
+
+use serde::Deserialize;
+use std::env;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tracing::{error, warn};
+
+use crate::error::{Error, Result};
+use crate::i18n::t;
+
+/// How long to wait on the provider before failing the submission.
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+static HTTP: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(VERIFY_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// A captcha service with a server-side verification endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaProvider {
+    HCaptcha,
+    Turnstile,
+}
+
+impl CaptchaProvider {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "hcaptcha" => Some(Self::HCaptcha),
+            "turnstile" => Some(Self::Turnstile),
+            _ => None,
+        }
+    }
+
+    fn script_url(self) -> &'static str {
+        match self {
+            Self::HCaptcha => "https://js.hcaptcha.com/1/api.js",
+            Self::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/api.js",
+        }
+    }
+
+    fn widget_class(self) -> &'static str {
+        match self {
+            Self::HCaptcha => "h-captcha",
+            Self::Turnstile => "cf-turnstile",
+        }
+    }
+
+    fn verify_url(self) -> &'static str {
+        match self {
+            Self::HCaptcha => "https://api.hcaptcha.com/siteverify",
+            Self::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/siteverify",
+        }
+    }
+
+    /// Origins the widget loads scripts, frames and XHRs from.
+    fn origins(self) -> &'static str {
+        match self {
+            Self::HCaptcha => "https://hcaptcha.com https://*.hcaptcha.com",
+            Self::Turnstile => "https://challenges.cloudflare.com",
+        }
+    }
+}
+
+/// An anonymous-reachable action that can require a captcha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptchaAction {
+    Signup,
+    Contact,
+    JoinRequest,
+}
+
+impl CaptchaAction {
+    pub const ALL: [Self; 3] = [Self::Signup, Self::Contact, Self::JoinRequest];
+
+    /// The name used in `CAPTCHA_ACTIONS`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Signup => "signup",
+            Self::Contact => "contact",
+            Self::JoinRequest => "join_request",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// A fully configured captcha.
+#[derive(Debug, Clone)]
+pub struct CaptchaConfig {
+    pub provider: CaptchaProvider,
+    pub site_key: String,
+    pub secret_key: String,
+    pub actions: Vec<CaptchaAction>,
+}
+
+impl CaptchaConfig {
+    /// Read the `CAPTCHA_*` variables; `None` (captcha off) unless the
+    /// provider and both keys are set. Unknown provider or action names are
+    /// logged and otherwise ignored.
+    pub fn from_env() -> Option<Self> {
+        let var = |name| env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let provider_name = var("CAPTCHA_PROVIDER")?;
+        let Some(provider) = CaptchaProvider::parse(&provider_name) else {
+            warn!(
+                "CAPTCHA_PROVIDER={provider_name:?} is not hcaptcha or turnstile; captcha disabled"
+            );
+            return None;
+        };
+        let site_key = var("CAPTCHA_SITE_KEY")?.trim().to_string();
+        let secret_key = var("CAPTCHA_SECRET_KEY")?.trim().to_string();
+        let actions = match var("CAPTCHA_ACTIONS") {
+            None => CaptchaAction::ALL.to_vec(),
+            Some(list) => parse_actions(&list),
+        };
+        Some(Self {
+            provider,
+            site_key,
+            secret_key,
+            actions,
+        })
+    }
+
+    pub fn protects(&self, action: CaptchaAction) -> bool {
+        self.actions.contains(&action)
+    }
+}
+
+/// Parse a comma-separated `CAPTCHA_ACTIONS` list, skipping unknown names.
+pub fn parse_actions(list: &str) -> Vec<CaptchaAction> {
+    list.split(',')
+        .filter(|name| !name.trim().is_empty())
+        .filter_map(|name| {
+            let action = CaptchaAction::parse(name);
+            if action.is_none() {
+                warn!("CAPTCHA_ACTIONS: unknown action {:?} ignored", name.trim());
+            }
+            action
+        })
+        .collect()
+}
+
+/// The provider's widget (script tag, stamped with the page's CSP `nonce`,
+/// plus placeholder element) for a form guarding `action`, or an empty
+/// string when the captcha is off for it.
+pub fn widget(action: CaptchaAction, nonce: &str) -> String {
+    let Some(config) = CaptchaConfig::from_env().filter(|c| c.protects(action)) else {
+        return String::new();
+    };
+    format!(
+        r#"<script src="{script}" nonce="{nonce}" async defer></script><div class="{class} captcha-widget" data-sitekey="{key}" data-action="{action}"></div>"#,
+        script = config.provider.script_url(),
+        nonce = crate::html::escape_attr(nonce),
+        class = config.provider.widget_class(),
+        key = crate::html::escape_attr(&config.site_key),
+        action = action.as_str(),
+    )
+}
+
+/// Append the configured provider's origins to the `script-src`,
+/// `frame-src`, `style-src` and `connect-src` directives of `policy`, so the
+/// widget can load under the default CSP. Unchanged when the captcha is off.
+pub fn extend_csp(policy: &str) -> String {
+    match CaptchaConfig::from_env() {
+        Some(config) => add_csp_origins(policy, config.provider.origins()),
+        None => policy.to_string(),
+    }
+}
+
+fn add_csp_origins(policy: &str, origins: &str) -> String {
+    policy
+        .split(';')
+        .map(|directive| {
+            let directive = directive.trim();
+            let name = directive.split_whitespace().next().unwrap_or_default();
+            if matches!(
+                name,
+                "script-src" | "frame-src" | "style-src" | "connect-src"
+            ) {
+                format!("{directive} {origins}")
+            } else {
+                directive.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyResponse {
+    success: bool,
+    #[serde(default, rename = "error-codes")]
+    error_codes: Vec<String>,
+}
+
+/// Check the token a protected form posted. Passes when the captcha is off
+/// (or not enabled for `action`); otherwise a missing or rejected token is
+/// an [`Error::Validation`], and an unreachable provider is an
+/// [`Error::ExternalService`] — the submission fails closed either way.
+pub async fn verify(action: CaptchaAction, token: Option<&str>, remote_ip: &str) -> Result<()> {
+    let Some(config) = CaptchaConfig::from_env().filter(|c| c.protects(action)) else {
+        return Ok(());
+    };
+    let Some(token) = token.map(str::trim).filter(|t| !t.is_empty()) else {
+        warn!(action = action.as_str(), ip = %remote_ip, "captcha token missing");
+        return Err(Error::Validation(t("auth.captcha_failed")));
+    };
+
+    let response = HTTP
+        .post(config.provider.verify_url())
+        .form(&[
+            ("secret", config.secret_key.as_str()),
+            ("response", token),
+            ("remoteip", remote_ip),
+            ("sitekey", config.site_key.as_str()),
+        ])
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| {
+            error!(
+                action = action.as_str(),
+                "captcha verification request failed: {e}"
+            );
+            Error::ExternalService("Captcha verification is unavailable".to_string())
+        })?;
+    let result: VerifyResponse = response.json().await.map_err(|e| {
+        error!(
+            action = action.as_str(),
+            "captcha verification response unreadable: {e}"
+        );
+        Error::ExternalService("Captcha verification is unavailable".to_string())
+    })?;
+
+    if result.success {
+        Ok(())
+    } else {
+        warn!(
+            action = action.as_str(),
+            ip = %remote_ip,
+            errors = ?result.error_codes,
+            "captcha rejected"
+        );
+        Err(Error::Validation(t("auth.captcha_failed")))
+    }
+}
@@ -1,16 +1,22 @@
 //! Environment-driven application configuration.
 //!
 //! `main.rs` calls [`Config::from_env`] at startup (loading a `.env` file if
-//! present) to obtain the SurrealDB connection settings and the HTTP listener
-//! address. The module also exposes [`app_url`] — the canonical base URL used
-//! wherever absolute links are built (templates, verification routes, MCP) —
-//! and the lazily-loaded [`SearchWeights`] consumed by the model search
-//! queries and the MCP server's search tools.
+//! present) to obtain the SurrealDB connection settings, the HTTP listener
+//! address and connection tuning, and the Argon2 password-hashing cost. The module also exposes
+//! [`public_base_url`] — the canonical base URL used wherever absolute links
+//! are built (emails, invitations, QR codes, templates, MCP) — the
+//! [`default_currency`]
+//! for new prices, the [`allowed_image_formats`] upload policy, the
+//! [`debug_routes_enabled`]
+//! switch for developer diagnostics, the response [`CompressionConfig`], the `/static` [`StaticCachePolicy`], and the lazily-loaded [`SearchWeights`]
+//! consumed by the model search queries and the MCP server's search tools.
 
 use serde::Deserialize;
 use std::env;
 use std::net::SocketAddr;
+use std::time::Duration;
 use thiserror::Error;
+use tower_http::compression::CompressionLevel;
 
 /// Top-level application configuration, assembled from environment variables
 /// by [`Config::from_env`].
@@ -18,6 +24,13 @@ use thiserror::Error;
 pub struct Config {
     pub database: DatabaseConfig,
     pub server: ServerConfig,
+    pub password_hash: PasswordHashParams,
+    /// ISO 4217 code new prices get when none is given (`DEFAULT_CURRENCY`).
+    pub default_currency: String,
+    /// Image MIME types uploads may use (`UPLOAD_IMAGE_FORMATS`).
+    pub upload_image_formats: Vec<String>,
+    /// Absolute base URL for generated links (`PUBLIC_BASE_URL`).
+    pub public_base_url: String,
 }
 
 /// SurrealDB connection settings, read from the `DB_*` environment variables.
@@ -31,11 +44,97 @@ pub struct DatabaseConfig {
     pub name: String,
 }
 
-/// HTTP listener settings, read from `SERVER_HOST` and `SERVER_PORT`.
+/// HTTP listener settings, read from `SERVER_HOST`, `SERVER_PORT`, and the
+/// [`ServerTuning`] variables.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    pub tuning: ServerTuning,
+}
+
+/// Connection handling for the HTTP listener, applied by
+/// [`crate::server::serve`]. TLS is terminated by the reverse proxy, so
+/// HTTP/2 here is cleartext (h2c) between the proxy and this server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ServerTuning {
+    /// Accept HTTP/2 alongside HTTP/1.1 (`SERVER_HTTP2`)
+    pub http2: bool,
+    /// Concurrent streams per HTTP/2 connection (`SERVER_HTTP2_MAX_STREAMS`)
+    pub http2_max_streams: u32,
+    /// Reuse HTTP/1.1 connections across requests (`SERVER_HTTP1_KEEPALIVE`)
+    pub http1_keep_alive: bool,
+    /// Idle time before TCP keep-alive probes start; `None` leaves them off
+    /// (`SERVER_TCP_KEEPALIVE_SECS`, `0` disables)
+    pub tcp_keepalive: Option<Duration>,
+    /// Send small responses without Nagle batching (`SERVER_TCP_NODELAY`)
+    pub tcp_nodelay: bool,
+    /// Open connections served at once; further clients wait in the accept
+    /// backlog (`SERVER_MAX_CONNECTIONS`, unset or `0` for no limit)
+    pub max_connections: Option<usize>,
+}
+
+impl Default for ServerTuning {
+    fn default() -> Self {
+        Self {
+            http2: true,
+            http2_max_streams: 200,
+            http1_keep_alive: true,
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            tcp_nodelay: true,
+            max_connections: None,
+        }
+    }
+}
+
+impl ServerTuning {
+    /// Reads the `SERVER_*` tuning variables over the defaults.
+    ///
+    /// # Errors
+    /// [`ConfigError::InvalidValue`] when a flag isn't `true`/`false` (or
+    /// `1`/`0`) or a number doesn't parse.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let defaults = Self::default();
+        let keepalive_secs: u64 = env_number(
+            "SERVER_TCP_KEEPALIVE_SECS",
+            defaults.tcp_keepalive.map_or(0, |d| d.as_secs()),
+        )?;
+        let max_connections: usize = env_number("SERVER_MAX_CONNECTIONS", 0)?;
+        Ok(Self {
+            http2: env_flag("SERVER_HTTP2", defaults.http2)?,
+            http2_max_streams: env_number("SERVER_HTTP2_MAX_STREAMS", defaults.http2_max_streams)?,
+            http1_keep_alive: env_flag("SERVER_HTTP1_KEEPALIVE", defaults.http1_keep_alive)?,
+            tcp_keepalive: (keepalive_secs > 0).then(|| Duration::from_secs(keepalive_secs)),
+            tcp_nodelay: env_flag("SERVER_TCP_NODELAY", defaults.tcp_nodelay)?,
+            max_connections: (max_connections > 0).then_some(max_connections),
+        })
+    }
+}
+
+/// A boolean env var (`true`/`false`/`1`/`0`, case-insensitive), or
+/// `default` when unset or blank.
+fn env_flag(name: &str, default: bool) -> Result<bool, ConfigError> {
+    match env::var(name) {
+        Ok(v) if !v.trim().is_empty() => match v.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(ConfigError::InvalidValue(
+                name.to_string(),
+                "must be true or false".to_string(),
+            )),
+        },
+        _ => Ok(default),
+    }
+}
+
+/// A non-negative integer env var, or `default` when unset or blank.
+fn env_number<T: std::str::FromStr>(name: &str, default: T) -> Result<T, ConfigError> {
+    match env::var(name) {
+        Ok(v) if !v.trim().is_empty() => v.trim().parse().map_err(|_| {
+            ConfigError::InvalidValue(name.to_string(), "must be a whole number".to_string())
+        }),
+        _ => Ok(default),
+    }
 }
 
 /// Errors produced when configuration is missing or malformed.
@@ -54,7 +153,9 @@ impl Config {
     /// # Errors
     /// Returns [`ConfigError::MissingEnvVar`] when the database credentials
     /// (`DB_USERNAME`/`DB_USER`, `DB_PASSWORD`/`DB_PASS`) are absent, or
-    /// [`ConfigError::InvalidValue`] when a port fails to parse.
+    /// [`ConfigError::InvalidValue`] when a port, `SERVER_*` tuning, `ARGON2_*`,
+    /// `DEFAULT_CURRENCY`, `UPLOAD_IMAGE_FORMATS`, or `PUBLIC_BASE_URL`
+    /// value fails to parse.
     pub fn from_env() -> Result<Self, ConfigError> {
         // Load .env file if it exists (safe to call multiple times)
         dotenv::dotenv().ok();
@@ -62,8 +163,122 @@ impl Config {
         Ok(Config {
             database: DatabaseConfig::from_env()?,
             server: ServerConfig::from_env()?,
+            password_hash: PasswordHashParams::from_env()?,
+            default_currency: default_currency_from_env()?,
+            upload_image_formats: upload_image_formats_from_env()?,
+            public_base_url: public_base_url_from_env()?,
         })
     }
+
+    /// Make this the process-wide configuration behind the accessors that
+    /// read it ([`allowed_image_formats`], [`public_base_url`]). `main.rs`
+    /// installs the config it validated at boot; the first call wins.
+    /// Before it (tests, tools that never boot the server) those accessors
+    /// return their defaults.
+    pub fn install(&self) {
+        let _ = INSTALLED.set(self.clone());
+    }
+}
+
+/// The configuration installed by [`Config::install`].
+static INSTALLED: std::sync::OnceLock<Config> = std::sync::OnceLock::new();
+
+fn installed() -> Option<&'static Config> {
+    INSTALLED.get()
+}
+
+/// `DEFAULT_CURRENCY` upper-cased, or `USD` when unset.
+///
+/// # Errors
+/// [`ConfigError::InvalidValue`] when set to something other than a
+/// three-letter code.
+fn default_currency_from_env() -> Result<String, ConfigError> {
+    match env::var("DEFAULT_CURRENCY") {
+        Ok(v) if !v.trim().is_empty() => crate::currency::normalize(&v).ok_or_else(|| {
+            ConfigError::InvalidValue(
+                "DEFAULT_CURRENCY".to_string(),
+                "must be a three-letter ISO 4217 code".to_string(),
+            )
+        }),
+        _ => Ok("USD".to_string()),
+    }
+}
+
+/// Process-wide default currency. An invalid `DEFAULT_CURRENCY` is already
+/// rejected by [`Config::from_env`] at boot; here it falls back to USD.
+static DEFAULT_CURRENCY: std::sync::LazyLock<String> = std::sync::LazyLock::new(|| {
+    dotenv::dotenv().ok();
+    default_currency_from_env().unwrap_or_else(|_| "USD".to_string())
+});
+
+/// The ISO 4217 code applied to new prices (location rates, equipment
+/// purchases) when the user doesn't pick one.
+pub fn default_currency() -> &'static str {
+    &DEFAULT_CURRENCY
+}
+
+/// Image MIME types the upload pipeline can handle, and the default for
+/// `UPLOAD_IMAGE_FORMATS`. SVG is only ever accepted for organization
+/// logos, which are rasterized on upload (`services::svg`).
+pub const SUPPORTED_IMAGE_FORMATS: &[&str] =
+    &["image/jpeg", "image/png", "image/webp", "image/svg+xml"];
+
+/// `UPLOAD_IMAGE_FORMATS` (comma-separated MIME types, lower-cased), or all
+/// of [`SUPPORTED_IMAGE_FORMATS`] when unset.
+///
+/// # Errors
+/// [`ConfigError::InvalidValue`] for a type the pipeline can't process
+/// (e.g. `image/heic`), or a list that names none.
+fn upload_image_formats_from_env() -> Result<Vec<String>, ConfigError> {
+    let Ok(raw) = env::var("UPLOAD_IMAGE_FORMATS") else {
+        return Ok(SUPPORTED_IMAGE_FORMATS
+            .iter()
+            .map(|t| t.to_string())
+            .collect());
+    };
+    let mut formats: Vec<String> = Vec::new();
+    for format in raw.split(',').map(|t| t.trim().to_ascii_lowercase()) {
+        if format.is_empty() || formats.contains(&format) {
+            continue;
+        }
+        if !SUPPORTED_IMAGE_FORMATS.contains(&format.as_str()) {
+            return Err(ConfigError::InvalidValue(
+                "UPLOAD_IMAGE_FORMATS".to_string(),
+                format!(
+                    "unsupported image type {format}; supported: {}",
+                    SUPPORTED_IMAGE_FORMATS.join(", ")
+                ),
+            ));
+        }
+        formats.push(format);
+    }
+    if formats.is_empty() {
+        return Err(ConfigError::InvalidValue(
+            "UPLOAD_IMAGE_FORMATS".to_string(),
+            "must list at least one image type".to_string(),
+        ));
+    }
+    Ok(formats)
+}
+
+/// Every supported type, for [`allowed_image_formats`] before a
+/// configuration is installed.
+static DEFAULT_UPLOAD_IMAGE_FORMATS: std::sync::LazyLock<Vec<String>> =
+    std::sync::LazyLock::new(|| {
+        SUPPORTED_IMAGE_FORMATS
+            .iter()
+            .map(|t| t.to_string())
+            .collect()
+    });
+
+/// Image MIME types uploads may use, in configured order: the installed
+/// [`Config::upload_image_formats`], or every supported type before
+/// [`Config::install`].
+pub fn allowed_image_formats() -> &'static [String] {
+    match installed() {
+        Some(config) => &config.upload_image_formats,
+        None => &DEFAULT_UPLOAD_IMAGE_FORMATS,
+    }
 }
 
 impl DatabaseConfig {
@@ -105,14 +320,161 @@ impl DatabaseConfig {
     }
 }
 
-/// Get the application base URL (e.g. "https://slatehub.com").
-/// Reads from APP_URL env var, defaults to "http://localhost:3000".
-/// Returned without a trailing slash.
-pub fn app_url() -> String {
-    env::var("APP_URL")
-        .unwrap_or_else(|_| "http://localhost:3000".to_string())
-        .trim_end_matches('/')
-        .to_string()
+/// Argon2id cost parameters for password hashing, read from
+/// `ARGON2_MEMORY_KIB`, `ARGON2_ITERATIONS`, and `ARGON2_PARALLELISM`.
+///
+/// Defaults match SurrealDB's own `crypto::argon2` settings. Raising them
+/// only affects new hashes; existing ones are upgraded on the next
+/// successful login (see `auth::needs_rehash`). Every hash embeds its own
+/// parameters, so old and new hashes verify side by side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct PasswordHashParams {
+    /// Memory cost in KiB.
+    pub memory_kib: u32,
+    /// Time cost (passes over memory).
+    pub iterations: u32,
+    /// Lanes.
+    pub parallelism: u32,
+}
+
+impl Default for PasswordHashParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19456,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+impl PasswordHashParams {
+    /// Reads the `ARGON2_*` variables, defaulting any that are unset.
+    ///
+    /// # Errors
+    /// Returns [`ConfigError::InvalidValue`] when a value isn't a positive
+    /// integer, or when the memory cost is below Argon2's minimum of
+    /// 8 KiB per lane.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        fn parse_or(var: &str, default: u32) -> Result<u32, ConfigError> {
+            match env::var(var) {
+                Ok(v) => v.trim().parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                    ConfigError::InvalidValue(var.to_string(), "must be a positive integer".into())
+                }),
+                Err(_) => Ok(default),
+            }
+        }
+
+        let defaults = Self::default();
+        let params = Self {
+            memory_kib: parse_or("ARGON2_MEMORY_KIB", defaults.memory_kib)?,
+            iterations: parse_or("ARGON2_ITERATIONS", defaults.iterations)?,
+            parallelism: parse_or("ARGON2_PARALLELISM", defaults.parallelism)?,
+        };
+        if params.memory_kib < 8 * params.parallelism {
+            return Err(ConfigError::InvalidValue(
+                "ARGON2_MEMORY_KIB".to_string(),
+                "must be at least 8 × ARGON2_PARALLELISM".to_string(),
+            ));
+        }
+        Ok(params)
+    }
+
+    /// True when any cost parameter is below `current`'s — i.e. a hash made
+    /// with `self` should be upgraded.
+    pub fn is_weaker_than(&self, current: &Self) -> bool {
+        self.memory_kib < current.memory_kib
+            || self.iterations < current.iterations
+            || self.parallelism < current.parallelism
+    }
+}
+
+/// Process-wide password hashing parameters. Invalid `ARGON2_*` values are
+/// already rejected by [`Config::from_env`] at boot; here they fall back to
+/// the defaults so hashing never panics.
+static PASSWORD_HASH_PARAMS: std::sync::LazyLock<PasswordHashParams> =
+    std::sync::LazyLock::new(|| {
+        dotenv::dotenv().ok();
+        PasswordHashParams::from_env().unwrap_or_default()
+    });
+
+/// Returns the current password hashing parameters, loading them from the
+/// environment on first access.
+pub fn password_hash_params() -> &'static PasswordHashParams {
+    &PASSWORD_HASH_PARAMS
+}
+
+/// Base URL used when neither `PUBLIC_BASE_URL` nor `APP_URL` is set.
+pub const DEFAULT_PUBLIC_BASE_URL: &str = "http://localhost:3000";
+
+/// Validate a base URL: absolute `http`/`https` with a host and nothing
+/// after the path. Returned without a trailing slash.
+///
+/// # Errors
+/// A description of what is wrong with `raw`.
+pub fn parse_public_base_url(raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    let url = url::Url::parse(raw).map_err(|e| format!("must be an absolute URL ({e})"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("must use http or https".to_string());
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err("must include a host".to_string());
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err("must not have a query or fragment".to_string());
+    }
+    Ok(raw.trim_end_matches('/').to_string())
+}
+
+/// The raw base URL setting: `PUBLIC_BASE_URL`, or the older `APP_URL`.
+fn public_base_url_var() -> Option<(&'static str, String)> {
+    ["PUBLIC_BASE_URL", "APP_URL"].into_iter().find_map(|var| {
+        env::var(var)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| (var, v))
+    })
+}
+
+/// The validated base URL, or [`DEFAULT_PUBLIC_BASE_URL`] when unset.
+///
+/// # Errors
+/// [`ConfigError::InvalidValue`] naming the variable when it is set but
+/// not an absolute http(s) URL.
+fn public_base_url_from_env() -> Result<String, ConfigError> {
+    match public_base_url_var() {
+        Some((var, raw)) => {
+            parse_public_base_url(&raw).map_err(|e| ConfigError::InvalidValue(var.to_string(), e))
+        }
+        None => Ok(DEFAULT_PUBLIC_BASE_URL.to_string()),
+    }
+}
+
+/// The absolute base URL (e.g. "https://slatehub.com", no trailing slash)
+/// for every link that leaves the app: emails, invitations, QR codes, OG
+/// tags. The installed [`Config::public_base_url`], validated at boot, or
+/// [`DEFAULT_PUBLIC_BASE_URL`] before [`Config::install`].
+pub fn public_base_url() -> String {
+    installed()
+        .map(|config| config.public_base_url.clone())
+        .unwrap_or_else(|| DEFAULT_PUBLIC_BASE_URL.to_string())
+}
+
+/// Hosts a user-supplied post-login redirect may name besides our own.
+///
+/// [`public_base_url`]'s host plus the comma-separated
+/// `ALLOWED_REDIRECT_HOSTS`, lowercased. Read on every call; see
+/// `response::safe_redirect_target`.
+pub fn allowed_redirect_hosts() -> Vec<String> {
+    let own = url::Url::parse(&public_base_url())
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string));
+    let extra = env::var("ALLOWED_REDIRECT_HOSTS").unwrap_or_default();
+    own.into_iter()
+        .chain(extra.split(',').map(|h| h.trim().to_string()))
+        .filter(|h| !h.is_empty())
+        .map(|h| h.to_ascii_lowercase())
+        .collect()
 }
 
 /// The Meta (Facebook) Pixel id used across the public conversion funnel
@@ -129,6 +491,212 @@ pub fn meta_pixel_id() -> Option<String> {
         .filter(|v| !v.is_empty())
 }
 
+/// Whether cookies we set carry the `Secure` attribute. On unless
+/// `COOKIE_SECURE=false`, which local plain-HTTP development needs because
+/// browsers drop `Secure` cookies on `http://` origins.
+pub fn cookie_secure() -> bool {
+    env::var("COOKIE_SECURE")
+        .map(|v| v.trim() != "false")
+        .unwrap_or(true)
+}
+
+/// `SameSite` policy for the `auth_token` session cookie, from
+/// `COOKIE_SAMESITE`: `lax` (the default) or `strict`. `Strict` also
+/// withholds the session on top-level navigations from other sites, so
+/// users following an external link arrive signed out. Unrecognised values
+/// fall back to `Lax`; `None` is deliberately not offered.
+pub fn cookie_same_site() -> cookie::SameSite {
+    match env::var("COOKIE_SAMESITE") {
+        Ok(v) if v.trim().eq_ignore_ascii_case("strict") => cookie::SameSite::Strict,
+        _ => cookie::SameSite::Lax,
+    }
+}
+
+/// Baseline `Content-Security-Policy` sent with every response.
+///
+/// Tuned to what the templates load: inline `<script>` blocks stamped with
+/// the request's nonce (`'nonce-{nonce}'`; browsers that understand it
+/// ignore the `'unsafe-inline'` kept for those that don't), inline `style`
+/// blocks, Datastar's expression evaluation (`'unsafe-eval'`), the Datastar
+/// bundle from jsDelivr, the Meta Pixel, profile reel embeds (see
+/// `video_platforms::embed_url`), and media images served from the S3
+/// public URL. `frame-ancestors 'none'` mirrors `X-Frame-Options: DENY`.
+pub const DEFAULT_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; \
+    script-src 'self' 'nonce-{nonce}' 'unsafe-inline' 'unsafe-eval' https://cdn.jsdelivr.net https://connect.facebook.net; \
+    style-src 'self' 'unsafe-inline'; \
+    img-src 'self' data: blob: https:; \
+    media-src 'self' blob: https:; \
+    font-src 'self' data:; \
+    connect-src 'self' https://www.facebook.com https://connect.facebook.net; \
+    frame-src https://www.youtube.com https://player.vimeo.com https://www.tiktok.com https://www.dailymotion.com; \
+    object-src 'none'; \
+    base-uri 'self'; \
+    frame-ancestors 'none'";
+
+/// The `Content-Security-Policy` header value. `CONTENT_SECURITY_POLICY`
+/// replaces [`DEFAULT_CONTENT_SECURITY_POLICY`] wholesale (e.g. to allow a
+/// CDN serving `/static`); set it to an empty string to send no CSP at all.
+/// The default gains the captcha provider's origins when one is configured
+/// ([`crate::captcha::extend_csp`]); a custom policy must list them itself.
+/// Every `{nonce}` in it is replaced with the request's nonce by
+/// [`crate::middleware::csp`].
+pub fn content_security_policy() -> Option<String> {
+    match env::var("CONTENT_SECURITY_POLICY") {
+        Ok(v) if v.trim().is_empty() => None,
+        Ok(v) => Some(v.trim().to_string()),
+        Err(_) => Some(crate::captcha::extend_csp(DEFAULT_CONTENT_SECURITY_POLICY)),
+    }
+}
+
+/// Whether developer diagnostic routes (e.g. `/orgs/test-types`) are
+/// served. Read from `DEBUG_ROUTES` on every call; only `true` (any case)
+/// enables them, so production deployments that never set it get 404s.
+pub fn debug_routes_enabled() -> bool {
+    env::var("DEBUG_ROUTES")
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Whether an owner's equipment serial numbers must be unique. Read from
+/// `EQUIPMENT_UNIQUE_SERIALS` on every call; only `true` (any case) turns
+/// duplicates into a validation error. Otherwise they are allowed, since
+/// some gear legitimately shares serial patterns, and only flagged.
+pub fn unique_equipment_serials() -> bool {
+    env::var("EQUIPMENT_UNIQUE_SERIALS")
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Response-compression settings for the `CompressionLayer` in
+/// [`crate::routes::app`], read by [`CompressionConfig::from_env`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// Encoder effort: `fastest`, `default`, `best`, or an algorithm-specific
+    /// number (gzip 0-9, brotli 0-11)
+    pub level: CompressionLevel,
+    /// Responses with a known `Content-Length` below this many bytes go out
+    /// uncompressed; see `routes::compression_layer` for how 1 KB was chosen
+    pub min_size: u16,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: CompressionLevel::Default,
+            min_size: 1024,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// `COMPRESSION_LEVEL` and `COMPRESSION_MIN_SIZE`; unset or unparseable
+    /// values fall back to the defaults with a warning.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let level = match env::var("COMPRESSION_LEVEL") {
+            Ok(v) => parse_compression_level(&v).unwrap_or_else(|| {
+                tracing::warn!("Ignoring invalid COMPRESSION_LEVEL={:?}", v);
+                defaults.level
+            }),
+            Err(_) => defaults.level,
+        };
+        let min_size = match env::var("COMPRESSION_MIN_SIZE") {
+            Ok(v) => v.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!("Ignoring invalid COMPRESSION_MIN_SIZE={:?}", v);
+                defaults.min_size
+            }),
+            Err(_) => defaults.min_size,
+        };
+        Self { level, min_size }
+    }
+}
+
+/// `Cache-Control` policy for `/static`, applied in [`crate::routes::app`]
+/// and read by [`StaticCachePolicy::from_env`].
+///
+/// Versioned assets — a `?v=` cache-buster or a content hash in the file
+/// name (`main.3f9a1c2e.css`) — change URL whenever their bytes change, so
+/// they are cached as immutable. Everything else (images and scripts linked
+/// by bare path) gets a short lifetime and revalidates via `Last-Modified`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticCachePolicy {
+    /// Seconds versioned assets stay cached (`STATIC_CACHE_VERSIONED_MAX_AGE`)
+    pub versioned_max_age: u32,
+    /// Seconds other assets stay cached (`STATIC_CACHE_MAX_AGE`)
+    pub max_age: u32,
+}
+
+impl Default for StaticCachePolicy {
+    fn default() -> Self {
+        Self {
+            versioned_max_age: 31_536_000,
+            max_age: 3600,
+        }
+    }
+}
+
+impl StaticCachePolicy {
+    /// `STATIC_CACHE_VERSIONED_MAX_AGE` and `STATIC_CACHE_MAX_AGE`; unset or
+    /// unparseable values fall back to the defaults with a warning.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let seconds = |name: &str, default: u32| match env::var(name) {
+            Ok(v) => v.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!("Ignoring invalid {}={:?}", name, v);
+                default
+            }),
+            Err(_) => default,
+        };
+        Self {
+            versioned_max_age: seconds(
+                "STATIC_CACHE_VERSIONED_MAX_AGE",
+                defaults.versioned_max_age,
+            ),
+            max_age: seconds("STATIC_CACHE_MAX_AGE", defaults.max_age),
+        }
+    }
+
+    /// The `Cache-Control` value for a static file at `path` requested with
+    /// `query`.
+    pub fn cache_control(&self, path: &str, query: Option<&str>) -> String {
+        if is_versioned_asset(path, query) {
+            format!("public, max-age={}, immutable", self.versioned_max_age)
+        } else {
+            format!("public, max-age={}", self.max_age)
+        }
+    }
+}
+
+/// Whether the URL pins the asset's content: a non-empty `v` query
+/// parameter, or a file name with a hex hash segment of 8+ characters
+/// before the extension (`app.0123abcd.js`).
+fn is_versioned_asset(path: &str, query: Option<&str>) -> bool {
+    let busted = query.is_some_and(|q| {
+        q.split('&')
+            .any(|pair| pair.strip_prefix("v=").is_some_and(|v| !v.is_empty()))
+    });
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let segments: Vec<&str> = file_name.split('.').collect();
+    let hashed = segments.len() >= 3
+        && segments[1..segments.len() - 1]
+            .iter()
+            .any(|s| s.len() >= 8 && s.bytes().all(|b| b.is_ascii_hexdigit()));
+    busted || hashed
+}
+
+fn parse_compression_level(value: &str) -> Option<CompressionLevel> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("fastest") {
+        Some(CompressionLevel::Fastest)
+    } else if value.eq_ignore_ascii_case("default") {
+        Some(CompressionLevel::Default)
+    } else if value.eq_ignore_ascii_case("best") {
+        Some(CompressionLevel::Best)
+    } else {
+        value.parse().ok().map(CompressionLevel::Precise)
+    }
+}
+
 /// Search scoring weights — configurable via env vars.
 ///
 /// Consumed by the model search queries (people, jobs, organizations,
@@ -231,6 +799,7 @@ impl ServerConfig {
                         "must be a valid port number".to_string(),
                     )
                 })?,
+            tuning: ServerTuning::from_env()?,
         })
     }
 
@@ -0,0 +1,99 @@
+//! ISO 4217 currency codes and display formatting for stored amounts.
+//!
+//! Amounts live in the database as a decimal plus a three-letter code (see
+//! `location_rate.currency`); nothing here converts between currencies.
+//! [`format_amount`] renders one for humans — symbol placement and the
+//! number of decimals follow the currency, grouping is always `1,234.50`.
+//! New rows default to [`crate::config::default_currency`].
+
+use crate::error::Error;
+
+/// Shown when a submitted currency isn't a three-letter code.
+pub const INVALID_CODE_MESSAGE: &str = "Currency must be a three-letter code like USD";
+
+/// Upper-case `code` if it looks like an ISO 4217 code (three ASCII
+/// letters); `None` otherwise. Doesn't check the code is actually issued.
+pub fn normalize(code: &str) -> Option<String> {
+    let code = code.trim();
+    (code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| code.to_ascii_uppercase())
+}
+
+/// A submitted currency code upper-cased, or the configured default when
+/// blank.
+///
+/// # Errors
+/// `Error::ValidationFields` on `field` for anything that isn't a
+/// three-letter code.
+pub fn resolve(code: Option<&str>, field: &str) -> Result<String, Error> {
+    match code.map(str::trim) {
+        Some(code) if !code.is_empty() => {
+            normalize(code).ok_or_else(|| Error::invalid_field(field, INVALID_CODE_MESSAGE))
+        }
+        _ => Ok(crate::config::default_currency().to_string()),
+    }
+}
+
+/// Digits after the decimal point in the currency's minor unit.
+pub fn minor_digits(code: &str) -> usize {
+    match code.to_ascii_uppercase().as_str() {
+        "JPY" | "KRW" | "ISK" | "CLP" | "VND" | "UGX" | "XAF" | "XOF" => 0,
+        "BHD" | "JOD" | "KWD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
+
+/// `(symbol, goes_after_amount)` for currencies with a well-known sign.
+fn symbol(code: &str) -> Option<(&'static str, bool)> {
+    Some(match code {
+        "USD" => ("$", false),
+        "CAD" => ("CA$", false),
+        "AUD" => ("A$", false),
+        "NZD" => ("NZ$", false),
+        "MXN" => ("MX$", false),
+        "GBP" => ("£", false),
+        "JPY" => ("¥", false),
+        "INR" => ("₹", false),
+        "KRW" => ("₩", false),
+        "EUR" => ("€", true),
+        "SEK" => ("kr", true),
+        "NOK" => ("kr", true),
+        "DKK" => ("kr", true),
+        "PLN" => ("zł", true),
+        _ => return None,
+    })
+}
+
+/// Render `amount` in `currency` for display: `$1,250.00`, `1,250.00 €`,
+/// `¥125,000`. Codes without a known symbol are shown as a prefix
+/// (`CHF 1,250.00`).
+pub fn format_amount(amount: f64, currency: &str) -> String {
+    let code = currency.trim().to_ascii_uppercase();
+    let number = group_thousands(&format!("{:.*}", minor_digits(&code), amount.abs()));
+    let sign = if amount < 0.0 { "-" } else { "" };
+    match symbol(&code) {
+        Some((symbol, false)) => format!("{sign}{symbol}{number}"),
+        Some((symbol, true)) => format!("{sign}{number} {symbol}"),
+        None => format!("{sign}{code} {number}"),
+    }
+}
+
+/// Insert `,` every three digits of the integer part of a formatted number.
+fn group_thousands(number: &str) -> String {
+    let (int, frac) = match number.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (number, None),
+    };
+    let mut grouped = String::with_capacity(number.len() + int.len() / 3);
+    for (i, digit) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if let Some(frac) = frac {
+        grouped.push('.');
+        grouped.push_str(frac);
+    }
+    grouped
+}
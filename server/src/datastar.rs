@@ -11,9 +11,21 @@
 //! interpolated into [`patch_elements`] must already be escaped — see
 //! [`crate::html::escape_html`].
 
-use axum::http::header;
+use axum::http::{HeaderMap, header};
 use axum::response::{IntoResponse, Response};
 
+/// Whether the request came from a Datastar action, which sends
+/// `Datastar-Request: true` with every `@get`/`@post`.
+///
+/// Lets a handler shared with a plain `<form>` post answer Datastar with
+/// patches for just the changed fragment, while browsers without JS still
+/// get the full-page redirect.
+pub fn is_datastar_request(headers: &HeaderMap) -> bool {
+    headers
+        .get("datastar-request")
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"true"))
+}
+
 /// Render one `datastar-patch-elements` SSE event.
 ///
 /// `selector` is a CSS selector for the patch target, `mode` a Datastar
@@ -4,22 +4,289 @@
 //! [`DB`] at boot (and the test harness points it at the test container),
 //! after which models and services issue queries through `DB.query(...)`
 //! directly. The SDK multiplexes concurrent queries over the single
-//! connection, so no pool is needed.
+//! connection, so no pool is needed. Every `DB.query(...)` is bounded by
+//! `DB_QUERY_TIMEOUT_SECS` ([`query_timeout`]), so a stuck query fails the
+//! request instead of holding it open. The SDK's other builders reached
+//! through `Deref` (`DB.select(...)`, `DB.update(...)`, …) get the same
+//! bound when awaited through [`Database::timed`], and [`transaction`]
+//! applies it to starting, running and committing the transaction. Live
+//! queries (`DB.select(...).live()`) stay open by design and are unbounded.
+//!
+//! Multiplexing has no back-pressure of its own: under a burst every handler
+//! puts its query on the one socket and they all slow down together. So at
+//! most `DB_MAX_CONCURRENT_QUERIES` ([`max_concurrent_queries`], default 64)
+//! `DB.query(...)` calls are in flight at once; the rest queue for a slot for
+//! up to `DB_QUERY_QUEUE_TIMEOUT_MS` ([`query_queue_timeout`]) and then fail
+//! with a 503 without reaching SurrealDB. The
+//! tradeoff: a burst past the limit sheds some requests quickly instead of
+//! making every request slow, and the limit also caps throughput when the
+//! database itself has headroom, so raise it (or set 0 to disable) on a
+//! larger SurrealDB. The SDK offers no connection pool for the WebSocket
+//! engine, so the guard limits concurrency rather than adding connections.
+//! Statements inside [`transaction`] and the health ping bypass the guard:
+//! a transaction already holds the connection's attention, and the ping
+//! must not report an outage because the queue is full. A turned-away
+//! query becomes a 503 however its caller wrapped the error: the guard
+//! flags the request ([`track_busy_queries`]), and `middleware::db_gate`
+//! swaps the handler's 5xx for [`busy_error`] with `Retry-After`.
+//!
+//! The SDK reopens the socket by itself when SurrealDB restarts, but a
+//! restarted server may not accept the replayed session. The monitor started
+//! by [`spawn_health_monitor`] pings the database every
+//! `DB_HEALTH_CHECK_INTERVAL_SECS` (default 10, `0` disables it) and, when a
+//! ping fails, signs in and selects the namespace/database again from the
+//! boot config ([`authenticate`]). Until that succeeds [`is_available`] is
+//! false and `middleware::db_gate` answers requests with a 503 instead of
+//! letting every query fail.
 
+use crate::config::DatabaseConfig;
+use crate::error::Error;
 use crate::log_db_error;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::future::IntoFuture;
+use std::pin::Pin;
 use std::sync::LazyLock;
-use surrealdb::{Surreal, engine::remote::ws::Client};
-use tracing::{debug, info, instrument};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use surrealdb::IndexedResults;
+use surrealdb::method::{IntoVariables, Query, Transaction};
+use surrealdb::types::QueryError;
+use surrealdb::{Surreal, engine::remote::ws::Client, opt::auth::Root};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::{debug, error, info, instrument, warn};
 
 /// Global SurrealDB handle. Unconnected until `main` (or a test's
 /// `setup_test_db`) calls `DB.connect(...)` + `signin` + `use_ns/use_db`;
 /// queries issued before that return a connection error rather than panic.
-pub static DB: LazyLock<Surreal<Client>> = LazyLock::new(|| {
+pub static DB: LazyLock<Database> = LazyLock::new(|| {
     debug!("Initializing database client");
-    Surreal::init()
+    Database(Surreal::init())
 });
 
-/// Ensures the database client is initialized and ready
+/// The client behind [`DB`]. Derefs to `Surreal<Client>` for everything
+/// except [`query`](Self::query), which bounds each query by
+/// [`query_timeout`]; wrap the SDK's other builders in
+/// [`timed`](Self::timed) for the same bound.
+#[derive(Debug, Clone)]
+pub struct Database(Surreal<Client>);
+
+impl std::ops::Deref for Database {
+    type Target = Surreal<Client>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Database {
+    /// Like `Surreal::query`, but awaiting it first waits for one of the
+    /// [`max_concurrent_queries`] slots, then fails with a "query timed out"
+    /// error (carrying the request ID, see
+    /// [`crate::middleware::request_id::current`]) once [`query_timeout`]
+    /// passes. The timeout only abandons the wait: SurrealDB may still
+    /// finish the statement.
+    pub fn query<'r>(&'r self, query: impl Into<Cow<'r, str>>) -> TimedQuery<'r> {
+        TimedQuery(self.0.query(query))
+    }
+
+    /// Await one of the SDK's other builders (`DB.select(id)`,
+    /// `DB.update(id).content(...)`, `DB.delete(id)`, …) under the same
+    /// query slot and [`query_timeout`] as [`query`](Self::query):
+    /// `DB.timed(DB.select(id)).await`. Not for live queries, which stay
+    /// open on purpose.
+    pub async fn timed<T>(
+        &self,
+        operation: impl IntoFuture<Output = Result<T, surrealdb::Error>>,
+    ) -> Result<T, surrealdb::Error> {
+        bounded(operation.into_future()).await
+    }
+}
+
+/// A query on [`DB`] that gives up after [`query_timeout`]. Build it like
+/// the SDK's `Query` (`.bind(...)`, `.query(...)`), then `.await` it.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TimedQuery<'r>(Query<'r, Client>);
+
+impl<'r> TimedQuery<'r> {
+    /// Bind variables to the query (see `surrealdb::method::Query::bind`).
+    pub fn bind(self, vars: impl IntoVariables) -> Self {
+        Self(self.0.bind(vars))
+    }
+
+    /// Chain another statement onto the query.
+    pub fn query(self, query: impl Into<Cow<'r, str>>) -> Self {
+        Self(self.0.query(query))
+    }
+}
+
+impl<'r> IntoFuture for TimedQuery<'r> {
+    type Output = Result<IndexedResults, surrealdb::Error>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'r>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(bounded(self.0.into_future()))
+    }
+}
+
+/// Wait for a query slot, then run `operation` for at most
+/// [`query_timeout`].
+async fn bounded<T>(
+    operation: impl Future<Output = Result<T, surrealdb::Error>>,
+) -> Result<T, surrealdb::Error> {
+    let _permit = acquire_query_slot().await?;
+    within_query_timeout(operation).await
+}
+
+/// Run `operation`, failing with a "query timed out" error once
+/// [`query_timeout`] passes.
+async fn within_query_timeout<T>(
+    operation: impl Future<Output = Result<T, surrealdb::Error>>,
+) -> Result<T, surrealdb::Error> {
+    let Some(limit) = query_timeout() else {
+        return operation.await;
+    };
+    tokio::time::timeout(limit, operation)
+        .await
+        .unwrap_or_else(|_| Err(timed_out_error(limit)))
+}
+
+/// The error an operation fails with after running for `limit`.
+fn timed_out_error(limit: Duration) -> surrealdb::Error {
+    let message = timed_out_message(limit);
+    warn!("{}", message);
+    surrealdb::Error::query(message, QueryError::TimedOut { duration: limit })
+}
+
+/// Start of the message of the error a query fails with when no slot frees
+/// up within [`query_queue_timeout`].
+const BUSY_MESSAGE: &str = "database busy";
+
+static QUERY_SLOTS: LazyLock<Option<Semaphore>> =
+    LazyLock::new(|| max_concurrent_queries().map(Semaphore::new));
+
+/// Wait for a free query slot, or fail with a [`is_busy_error`] error once
+/// [`query_queue_timeout`] passes. `None` when the guard is disabled.
+async fn acquire_query_slot() -> Result<Option<SemaphorePermit<'static>>, surrealdb::Error> {
+    let Some(slots) = QUERY_SLOTS.as_ref() else {
+        return Ok(None);
+    };
+    if let Ok(permit) = slots.try_acquire() {
+        return Ok(Some(permit));
+    }
+
+    let acquire = slots.acquire();
+    let acquired = match query_queue_timeout() {
+        Some(limit) => tokio::time::timeout(limit, acquire).await.ok(),
+        None => Some(acquire.await),
+    };
+    match acquired {
+        Some(permit) => Ok(Some(permit.expect("query slot semaphore is never closed"))),
+        None => {
+            let message = busy_message();
+            warn!("{}", message);
+            let _ = TURNED_AWAY.try_with(|flag| flag.set(true));
+            Err(surrealdb::Error::query(message, QueryError::NotExecuted))
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Set when [`acquire_query_slot`] turns a query away during a
+    /// [`track_busy_queries`] scope.
+    static TURNED_AWAY: Cell<bool>;
+}
+
+/// Run `f`, also reporting whether any `DB.query(...)` it awaited was
+/// turned away because every slot stayed busy. Handlers mostly wrap query
+/// errors in `Error::Database` with their own message, which loses the
+/// busy case; the request-level flag keeps it.
+pub async fn track_busy_queries<F: Future>(f: F) -> (F::Output, bool) {
+    TURNED_AWAY
+        .scope(Cell::new(false), async move {
+            let output = f.await;
+            (output, TURNED_AWAY.with(Cell::get))
+        })
+        .await
+}
+
+/// The 503 a request gets when its queries were turned away.
+pub fn busy_error() -> Error {
+    Error::Unavailable {
+        message: "SlateHub is busy right now. Please try again in a moment.".to_string(),
+        retry_after: Duration::from_secs(1),
+    }
+}
+
+/// "database busy: all 64 query slots in use (request 01J…)".
+fn busy_message() -> String {
+    let limit = max_concurrent_queries().unwrap_or_default();
+    match crate::middleware::request_id::current() {
+        Some(id) => format!(
+            "{}: all {} query slots in use (request {})",
+            BUSY_MESSAGE, limit, id
+        ),
+        None => format!("{}: all {} query slots in use", BUSY_MESSAGE, limit),
+    }
+}
+
+/// Whether `err` is a query turned away because every slot stayed busy for
+/// [`query_queue_timeout`]; it never reached SurrealDB, so retrying is
+/// safe. [`Error`] maps these to a 503.
+pub fn is_busy_error(err: &surrealdb::Error) -> bool {
+    matches!(err.query_details(), Some(QueryError::NotExecuted))
+        && err.message().starts_with(BUSY_MESSAGE)
+}
+
+/// "query timed out after 30s (request 01J…)" — the request part only when
+/// the query ran inside a request.
+fn timed_out_message(limit: Duration) -> String {
+    match crate::middleware::request_id::current() {
+        Some(id) => format!("query timed out after {:?} (request {})", limit, id),
+        None => format!("query timed out after {:?}", limit),
+    }
+}
+
+/// `DB_QUERY_TIMEOUT_SECS` (default 30), read once; `None` when set to 0.
+pub fn query_timeout() -> Option<Duration> {
+    static TIMEOUT: LazyLock<Option<Duration>> = LazyLock::new(|| {
+        let secs = std::env::var("DB_QUERY_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(30);
+        (secs > 0).then(|| Duration::from_secs(secs))
+    });
+    *TIMEOUT
+}
+
+/// `DB_MAX_CONCURRENT_QUERIES` (default 64), read once; `None` when set to 0.
+pub fn max_concurrent_queries() -> Option<usize> {
+    static LIMIT: LazyLock<Option<usize>> = LazyLock::new(|| {
+        let limit = std::env::var("DB_MAX_CONCURRENT_QUERIES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(64);
+        (limit > 0).then_some(limit)
+    });
+    *LIMIT
+}
+
+/// `DB_QUERY_QUEUE_TIMEOUT_MS` (default 2000), read once: how long a query
+/// waits for a slot. `None` when set to 0, waiting as long as it takes.
+pub fn query_queue_timeout() -> Option<Duration> {
+    static TIMEOUT: LazyLock<Option<Duration>> = LazyLock::new(|| {
+        let millis = std::env::var("DB_QUERY_QUEUE_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(2000);
+        (millis > 0).then(|| Duration::from_millis(millis))
+    });
+    *TIMEOUT
+}
+
+/// Ensures the database client is initialized and ready, that the lookup
+/// rows equipment records point at exist, and warns about any
+/// [`REQUIRED_INDEXES`] the database lacks
 pub async fn ensure_db_initialized() -> Result<(), surrealdb::Error> {
     // Force initialization of the LazyLock if not already done
     let _ = &*DB;
@@ -27,17 +294,260 @@ pub async fn ensure_db_initialized() -> Result<(), surrealdb::Error> {
     // Verify we can perform a basic operation
     debug!("Verifying database connection is ready");
     match DB.query("INFO FOR NS").await {
-        Ok(_) => {
-            info!("Database connection verified and ready");
-            Ok(())
-        }
+        Ok(_) => info!("Database connection verified and ready"),
         Err(e) => {
             log_db_error!(
                 format!("{:?}", e),
                 "Database connection verification failed"
             );
-            Err(e)
+            return Err(e);
+        }
+    }
+
+    seed_equipment_lookups().await?;
+    warn_missing_indexes().await;
+    Ok(())
+}
+
+/// Default equipment categories, `(name, description)`. Mirrors the seed
+/// in `db/schema.surql`.
+pub const EQUIPMENT_CATEGORIES: &[(&str, &str)] = &[
+    ("camera", "Cameras and camera bodies"),
+    ("lens", "Camera lenses"),
+    ("lighting", "Lighting equipment"),
+    ("audio", "Audio recording equipment"),
+    ("grip", "Grip and rigging equipment"),
+    ("computer", "Computers and tablets"),
+    ("storage", "Storage devices and media"),
+    ("wireless", "Wireless transmission equipment"),
+    ("monitor", "Monitors and displays"),
+    ("tripod", "Tripods and support equipment"),
+    ("accessory", "Miscellaneous accessories"),
+    ("other", "Other equipment"),
+];
+
+/// Default equipment conditions, `(name, description)`. Mirrors the seed
+/// in `db/schema.surql`.
+pub const EQUIPMENT_CONDITIONS: &[(&str, &str)] = &[
+    ("new", "Brand new, unused"),
+    ("excellent", "Like new, minimal wear"),
+    ("good", "Normal wear, fully functional"),
+    ("fair", "Some wear, functional but may need maintenance"),
+    ("poor", "Significant wear, needs repair"),
+    ("damaged", "Damaged, needs repair before use"),
+    ("broken", "Not functional, needs repair or replacement"),
+];
+
+/// Create any default category or condition whose name is missing, with a
+/// generated id like the seed in `db/schema.surql`. Rows that already
+/// exist are left alone, so this is safe on every boot.
+async fn seed_equipment_lookups() -> Result<(), surrealdb::Error> {
+    let rows = |defaults: &[(&str, &str)]| {
+        defaults
+            .iter()
+            .map(|(name, description)| {
+                serde_json::json!({ "name": name, "description": description })
+            })
+            .collect::<Vec<_>>()
+    };
+    DB.query(
+        "FOR $row IN $categories {
+            IF array::len((SELECT VALUE id FROM equipment_category WHERE name = $row.name)) = 0 {
+                CREATE equipment_category CONTENT $row;
+            };
+        };
+        FOR $row IN $conditions {
+            IF array::len((SELECT VALUE id FROM equipment_condition WHERE name = $row.name)) = 0 {
+                CREATE equipment_condition CONTENT $row;
+            };
+        };",
+    )
+    .bind(("categories", rows(EQUIPMENT_CATEGORIES)))
+    .bind(("conditions", rows(EQUIPMENT_CONDITIONS)))
+    .await?
+    .check()?;
+    debug!("Equipment categories and conditions seeded");
+    Ok(())
+}
+
+/// Indexes the hot model queries depend on, `(table, index)`: logins by
+/// email/username, org pages by slug, QR scans, owner inventories, the
+/// active-rental lookup behind every checkout, and the public location
+/// listing. All are defined in `db/schema.surql` and (idempotently) in
+/// migrations 028 and 029. Keep entries for the same table adjacent.
+pub const REQUIRED_INDEXES: &[(&str, &str)] = &[
+    ("person", "person_email_unique"),
+    ("person", "person_username_unique"),
+    ("organization", "idx_organization_slug"),
+    ("equipment", "idx_equipment_qr"),
+    ("equipment", "idx_equipment_owner_person"),
+    ("equipment", "idx_equipment_owner_org"),
+    ("equipment_rental", "idx_rental_equipment_active"),
+    ("equipment_rental", "idx_rental_kit_active"),
+    ("location", "idx_location_public"),
+];
+
+/// The [`REQUIRED_INDEXES`] on `table` that an `INFO FOR TABLE` result
+/// doesn't list.
+pub fn indexes_missing_from(table: &str, info: &serde_json::Value) -> Vec<&'static str> {
+    REQUIRED_INDEXES
+        .iter()
+        .filter(|(t, index)| *t == table && info["indexes"].get(*index).is_none())
+        .map(|(_, index)| *index)
+        .collect()
+}
+
+/// The [`REQUIRED_INDEXES`] absent from the connected database, as
+/// `(table, index)`. A table that can't be inspected counts as missing all
+/// of its indexes.
+pub async fn missing_indexes() -> Result<Vec<(&'static str, &'static str)>, surrealdb::Error> {
+    let mut tables: Vec<&'static str> = REQUIRED_INDEXES.iter().map(|(t, _)| *t).collect();
+    tables.dedup();
+    // Table names are the constants above, never user input
+    let statements: Vec<String> = tables
+        .iter()
+        .map(|t| format!("INFO FOR TABLE {};", t))
+        .collect();
+    let mut response = DB.query(statements.join("\n")).await?;
+
+    let mut missing = Vec::new();
+    for (i, table) in tables.iter().enumerate() {
+        let info = response
+            .take::<Option<serde_json::Value>>(i)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        missing.extend(
+            indexes_missing_from(table, &info)
+                .into_iter()
+                .map(|index| (*table, index)),
+        );
+    }
+    Ok(missing)
+}
+
+/// Log each missing required index. Never fails boot: a missing index
+/// makes queries slow (full table scans), not wrong.
+async fn warn_missing_indexes() {
+    match missing_indexes().await {
+        Ok(missing) if missing.is_empty() => debug!("All required indexes present"),
+        Ok(missing) => {
+            for (table, index) in missing {
+                warn!(
+                    table,
+                    index,
+                    "Required index is missing; queries will scan the table. Apply the pending db/migrations"
+                );
+            }
         }
+        Err(e) => warn!("Could not verify required indexes: {}", e),
+    }
+}
+
+/// Sign in as the configured root user and select the configured namespace
+/// and database — at boot, and again whenever the monitor reconnects.
+pub async fn authenticate(config: &DatabaseConfig) -> Result<(), surrealdb::Error> {
+    DB.signin(Root {
+        username: config.username.clone(),
+        password: config.password.clone(),
+    })
+    .await?;
+    DB.use_ns(&config.namespace).use_db(&config.name).await?;
+    Ok(())
+}
+
+/// Whether the database is currently reachable. Only the health monitor
+/// clears this, so it stays true in tests and before the monitor starts.
+pub fn is_available() -> bool {
+    AVAILABLE.load(Ordering::Relaxed)
+}
+
+static AVAILABLE: AtomicBool = AtomicBool::new(true);
+
+/// Longest a single ping may take before it counts as a failure.
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reconnect attempts back off from one second up to this.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// A query that fails unless the connection is up, authenticated, and has
+/// a database selected.
+async fn ping() -> Result<(), String> {
+    // Straight to the client: a full query queue isn't an outage
+    match tokio::time::timeout(PING_TIMEOUT, DB.0.query("INFO FOR DB")).await {
+        Ok(Ok(response)) => response.check().map(|_| ()).map_err(|e| e.to_string()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("no response within {:?}", PING_TIMEOUT)),
+    }
+}
+
+/// `DB_HEALTH_CHECK_INTERVAL_SECS`, or `None` when set to 0.
+pub fn health_check_interval() -> Option<Duration> {
+    let secs = std::env::var("DB_HEALTH_CHECK_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(10);
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Start the background task that pings [`DB`] and restores the session
+/// after an outage (see the module docs). Call once, after the boot-time
+/// connect and [`authenticate`] succeed.
+pub fn spawn_health_monitor(config: DatabaseConfig) {
+    let Some(interval) = health_check_interval() else {
+        info!("Database health monitor disabled (DB_HEALTH_CHECK_INTERVAL_SECS=0)");
+        return;
+    };
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            if let Err(e) = ping().await {
+                warn!("Database health check failed: {}", e);
+                reconnect(&config).await;
+            }
+        }
+    });
+}
+
+/// Re-authenticate until a ping succeeds. The first attempt is immediate
+/// and requests are only turned away once it has failed too, so a single
+/// dropped ping doesn't cause 503s.
+async fn reconnect(config: &DatabaseConfig) {
+    let started = Instant::now();
+    let mut delay = Duration::from_secs(1);
+    let mut attempt = 1u32;
+    loop {
+        let result = match authenticate(config).await {
+            Ok(()) => ping().await,
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(()) => {
+                if !AVAILABLE.swap(true, Ordering::Relaxed) {
+                    info!(
+                        "Database connection restored after {:?} ({} attempts)",
+                        started.elapsed(),
+                        attempt
+                    );
+                } else {
+                    info!("Database session re-established");
+                }
+                return;
+            }
+            Err(e) => {
+                if AVAILABLE.swap(false, Ordering::Relaxed) {
+                    error!(
+                        "Database unavailable, serving 503s while reconnecting: {}",
+                        e
+                    );
+                } else {
+                    debug!("Database reconnect attempt {} failed: {}", attempt, e);
+                }
+            }
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        attempt += 1;
     }
 }
 
@@ -59,3 +569,49 @@ where
         }
     }
 }
+
+/// An open transaction on [`DB`]; issue statements with `tx.query(...)`.
+pub type Tx = Transaction<Client>;
+
+/// Run `f` inside a transaction: committed if it returns `Ok`, cancelled if
+/// it returns `Err` or runs past [`query_timeout`]. Starting and committing
+/// the transaction are bounded by the same timeout.
+///
+/// Replaces hand-written `BEGIN TRANSACTION` / `COMMIT TRANSACTION` sent as
+/// separate queries, where an early `?` between them left the transaction
+/// open on the shared connection. A failed rollback is logged and the
+/// original error returned.
+///
+/// ```ignore
+/// db::transaction(async |tx| {
+///     tx.query("DELETE $id").bind(("id", id)).await?.check()?;
+///     Ok(())
+/// })
+/// .await?;
+/// ```
+pub async fn transaction<T>(f: impl AsyncFnOnce(&Tx) -> Result<T, Error>) -> Result<T, Error> {
+    let tx = within_query_timeout(DB.0.clone().begin().into_future())
+        .await
+        .map_err(|e| Error::Database(format!("Failed to start transaction: {}", e)))?;
+
+    let outcome = match query_timeout() {
+        Some(limit) => tokio::time::timeout(limit, f(&tx))
+            .await
+            .unwrap_or_else(|_| Err(timed_out_error(limit).into())),
+        None => f(&tx).await,
+    };
+    match outcome {
+        Ok(value) => {
+            within_query_timeout(tx.commit().into_future())
+                .await
+                .map_err(|e| Error::Database(format!("Failed to commit transaction: {}", e)))?;
+            Ok(value)
+        }
+        Err(e) => {
+            if let Err(cancel_err) = tx.cancel().await {
+                warn!("Failed to cancel transaction: {}", cancel_err);
+            }
+            Err(e)
+        }
+    }
+}
@@ -11,14 +11,23 @@
 //! Server-side variants (`Database`, `Template`, `Internal`,
 //! `ExternalService`) log on conversion/response and deliberately return a
 //! generic message — internals never leak to clients. Client-side variants
-//! (`BadRequest`, `Conflict`, `Validation`) surface their message verbatim.
+//! (`BadRequest`, `Conflict`, `Validation`, `ValidationFields`,
+//! `RateLimited`, `Unavailable`) surface their message verbatim; `ValidationFields` also
+//! adds a `fields` object (field → messages) to the JSON body so API
+//! clients can mark each input, `RateLimited` adds `retry_after` plus
+//! the [`crate::rate_limit`] headers, and `Unavailable` adds `retry_after`
+//! plus a `Retry-After` header.
 
 use crate::log_colored_error;
 use crate::log_db_error;
+use crate::rate_limit::RateLimitStatus;
+use crate::validation::ValidationErrors;
 use axum::Json;
-use axum::http::{HeaderValue, StatusCode};
+use axum::http::{HeaderValue, StatusCode, header};
 use axum::response::{IntoResponse, Response};
 use serde_json::json;
+use std::borrow::Cow;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Application-wide error; maps 1:1 onto an HTTP status (see module docs).
@@ -61,6 +70,29 @@ pub enum Error {
     #[error("validation error: {0}")]
     Validation(String),
 
+    /// Per-field form/input failures → 422. Like `Validation`, but the JSON
+    /// body carries a `fields` map so clients can attach each message to
+    /// its input. Use `Validation` for errors not tied to a field.
+    #[error("validation error: {0}")]
+    ValidationFields(ValidationErrors),
+
+    /// A [`crate::rate_limit::RateLimiter`] tripped → 429. Shown, with
+    /// `Retry-After` and `X-RateLimit-*` headers from `status`.
+    #[error("rate limited: {message}")]
+    RateLimited {
+        message: String,
+        status: RateLimitStatus,
+    },
+
+    /// A dependency is down but expected back shortly (the database while
+    /// [`crate::db`] reconnects, or every query slot busy) → 503. Shown,
+    /// with `Retry-After`.
+    #[error("service unavailable: {message}")]
+    Unavailable {
+        message: String,
+        retry_after: Duration,
+    },
+
     /// Upstream (S3, Stripe, Listmonk, LLM …) failure → 502. Logged.
     #[error("external service error: {0}")]
     ExternalService(String),
@@ -68,6 +100,7 @@ pub enum Error {
 
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
+        let summary = self.form_message().map(|m| m.into_owned());
         let (status, error_message, custom_message) = match &self {
             Error::Database(msg) => {
                 log_db_error!(msg);
@@ -103,6 +136,21 @@ impl IntoResponse for Error {
                 msg.as_str(),
                 Some(msg.clone()),
             ),
+            Error::ValidationFields(_) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                summary.as_deref().unwrap_or("Validation error"),
+                summary.clone(),
+            ),
+            Error::RateLimited { message, .. } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                message.as_str(),
+                Some(message.clone()),
+            ),
+            Error::Unavailable { message, .. } => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                message.as_str(),
+                Some(message.clone()),
+            ),
             Error::ExternalService(msg) => {
                 log_colored_error!("network", format!("External service error: {}", msg));
                 (StatusCode::BAD_GATEWAY, "External service error", None)
@@ -110,11 +158,20 @@ impl IntoResponse for Error {
         };
 
         // Create a JSON response with error details
-        let body = json!({
+        let mut body = json!({
             "error": error_message,
             "status": status.as_u16(),
             "timestamp": chrono::Utc::now().to_rfc3339(),
         });
+        if let Error::ValidationFields(errors) = &self {
+            body["fields"] = json!(errors.by_field());
+        }
+        if let Error::RateLimited { status, .. } = &self {
+            body["retry_after"] = json!(status.retry_after_secs());
+        }
+        if let Error::Unavailable { retry_after, .. } = &self {
+            body["retry_after"] = json!(retry_after.as_secs());
+        }
 
         // Add a special header to indicate this is an error that could be converted to HTML
         // The middleware will check for this header and the Accept header to determine
@@ -131,6 +188,15 @@ impl IntoResponse for Error {
                 HeaderValue::from_str(&custom_msg).unwrap_or_else(|_| HeaderValue::from_static("")),
             );
         }
+        if let Error::RateLimited { status, .. } = &self {
+            status.apply_headers(response.headers_mut());
+        }
+        if let Error::Unavailable { retry_after, .. } = &self {
+            response.headers_mut().insert(
+                header::RETRY_AFTER,
+                HeaderValue::from(retry_after.as_secs()),
+            );
+        }
         response
     }
 }
@@ -138,6 +204,9 @@ impl IntoResponse for Error {
 // Conversion from surrealdb errors
 impl From<surrealdb::Error> for Error {
     fn from(err: surrealdb::Error) -> Self {
+        if crate::db::is_busy_error(&err) {
+            return crate::db::busy_error();
+        }
         log_db_error!(format!("{:?}", err), "SurrealDB operation failed");
         Self::Database(err.to_string())
     }
@@ -193,6 +262,13 @@ impl Error {
         Self::Validation(msg.into())
     }
 
+    /// A single-field `ValidationFields` error.
+    pub fn invalid_field<S: Into<String>>(field: &str, msg: S) -> Self {
+        let mut errors = ValidationErrors::new();
+        errors.add(field, msg);
+        Self::ValidationFields(errors)
+    }
+
     pub fn external_service<S: Into<String>>(msg: S) -> Self {
         Self::ExternalService(msg.into())
     }
@@ -201,6 +277,20 @@ impl Error {
         Self::Internal(msg.into())
     }
 
+    /// The message to show inline on a re-rendered form, for errors the
+    /// user can fix by editing their input (validation, conflict, bad
+    /// request). `None` for everything else, which should propagate as an
+    /// error page.
+    pub fn form_message(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Error::Validation(msg) | Error::Conflict(msg) | Error::BadRequest(msg) => {
+                Some(Cow::Borrowed(msg))
+            }
+            Error::ValidationFields(errors) => Some(Cow::Owned(errors.to_string())),
+            _ => None,
+        }
+    }
+
     /// Parse form validation errors and return a user-friendly message
     pub fn parse_form_validation_error<S: AsRef<str>>(error_msg: S) -> Self {
         let msg = error_msg.as_ref();
@@ -0,0 +1,556 @@
+//! Translated user-facing strings.
+//!
+//! Messages live in per-locale catalogs keyed by dotted ids
+//! (`auth.invalid_credentials`, `validation.required`). English is the
+//! complete reference catalog; a key missing from another locale falls back
+//! to English, and a key missing everywhere renders as the key itself so the
+//! gap is visible rather than blank.
+//!
+//! The request's locale is chosen by [`crate::middleware::locale`] — the
+//! `lang` preference cookie first, then `Accept-Language` — and kept in a
+//! task-local for the rest of the request, so handlers call [`t`] / [`tf`]
+//! and templates use the `t` filter without threading a locale through.
+//! Outside a request (background jobs, tests) [`current`] is English.
+//!
+//! Placeholders are `{name}`, filled by [`tf`] from `(name, value)` pairs.
+//!
+//! Times are stored in UTC and shown in the request's timezone
+//! ([`current_timezone`]): the person's saved preference, else the `tz`
+//! cookie the browser reports, else UTC. [`local_datetime`] and
+//! [`local_date`] (the `local_datetime` / `local_date` template filters)
+//! do the conversion.
+
+use std::fmt::{self, Display};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+/// Name of the cookie holding a visitor's explicit language choice.
+pub const LANG_COOKIE: &str = "lang";
+
+/// Name of the cookie the browser reports its IANA timezone in.
+pub const TZ_COOKIE: &str = "tz";
+
+/// A locale with a message catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Fr,
+    De,
+}
+
+impl Locale {
+    /// Every supported locale, English first.
+    pub const ALL: [Locale; 4] = [Locale::En, Locale::Es, Locale::Fr, Locale::De];
+
+    /// The primary language subtag, as sent in `Content-Language`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+            Locale::De => "de",
+        }
+    }
+
+    /// The language's own name, for the language picker.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Es => "Español",
+            Locale::Fr => "Français",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    /// The locale for a language tag, matching on its primary subtag
+    /// (`fr-CA` → French); `None` for unsupported languages.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?;
+        Self::ALL
+            .into_iter()
+            .find(|locale| primary.eq_ignore_ascii_case(locale.code()))
+    }
+
+    /// The best supported locale for an `Accept-Language` header: the
+    /// highest-`q` supported language, earlier entries winning ties.
+    /// English when the header is missing or names nothing supported.
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let Some(header) = accept_language else {
+            return Self::default();
+        };
+        let mut best: Option<(Self, f32)> = None;
+        for entry in header.split(',') {
+            let mut parts = entry.split(';');
+            let Some(locale) = parts.next().and_then(Self::from_tag) else {
+                continue;
+            };
+            let q = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())
+                .unwrap_or(0.0);
+            if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) {
+                best = Some((locale, q));
+            }
+        }
+        best.map_or_else(Self::default, |(locale, _)| locale)
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Es => ES,
+            Locale::Fr => FR,
+            Locale::De => DE,
+        }
+    }
+
+    /// The message for `key` in this locale, falling back to English and
+    /// then to the key itself.
+    pub fn message(self, key: &str) -> &str {
+        let find = |catalog: &'static [(&'static str, &'static str)]| {
+            catalog.iter().find(|(k, _)| *k == key).map(|(_, m)| *m)
+        };
+        find(self.catalog()).or_else(|| find(EN)).unwrap_or(key)
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: Locale;
+    static TIMEZONE: Tz;
+}
+
+/// The locale of the request being handled; English outside a request.
+pub fn current() -> Locale {
+    CURRENT.try_with(|locale| *locale).unwrap_or_default()
+}
+
+/// Run `f` with `locale` as the [`current`] locale.
+pub async fn scope<F: Future>(locale: Locale, f: F) -> F::Output {
+    CURRENT.scope(locale, f).await
+}
+
+/// The message for `key` in the current locale.
+pub fn t(key: &str) -> String {
+    current().message(key).to_string()
+}
+
+/// [`t`] with each `{name}` placeholder replaced by its value.
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = current().message(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    message
+}
+
+/// An IANA timezone name (`Europe/Berlin`), or `None` if unknown.
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+/// The timezone of the request being handled; UTC outside a request.
+pub fn current_timezone() -> Tz {
+    TIMEZONE.try_with(|tz| *tz).unwrap_or(Tz::UTC)
+}
+
+/// Run `f` with `tz` as the [`current_timezone`].
+pub async fn scope_timezone<F: Future>(tz: Tz, f: F) -> F::Output {
+    TIMEZONE.scope(tz, f).await
+}
+
+/// An instant in the current timezone, e.g. "March 5, 2026 at 3:04 PM CET".
+pub fn local_datetime(at: &DateTime<Utc>) -> String {
+    at.with_timezone(&current_timezone())
+        .format("%B %-d, %Y at %-I:%M %p %Z")
+        .to_string()
+}
+
+/// The calendar date of an instant in the current timezone, e.g.
+/// "March 5, 2026". Not for date-only values stored as UTC midnight
+/// (purchase or due dates) — those would shift a day west of UTC.
+pub fn local_date(at: &DateTime<Utc>) -> String {
+    at.with_timezone(&current_timezone())
+        .format("%B %-d, %Y")
+        .to_string()
+}
+
+/// How long ago (or until) `at` is, in the current locale: "3 hours ago",
+/// "in 2 days", "just now".
+pub fn time_ago(at: &DateTime<Utc>) -> String {
+    relative_time(at, &Utc::now())
+}
+
+/// [`time_ago`] measured from `now` rather than the clock.
+pub fn relative_time(at: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let seconds = (*at - *now).num_seconds();
+    let elapsed = seconds.unsigned_abs();
+    if elapsed < 45 {
+        return t("time.just_now");
+    }
+    let minutes = (elapsed + 30) / 60;
+    let hours = (elapsed + 1800) / 3600;
+    let days = (elapsed + 43_200) / 86_400;
+    let (unit, n) = match () {
+        _ if minutes < 60 => ("minute", minutes),
+        _ if hours < 24 => ("hour", hours),
+        _ if days < 7 => ("day", days),
+        _ if days < 30 => ("week", days / 7),
+        _ if days < 365 => ("month", (days / 30).max(1)),
+        _ => ("year", days / 365),
+    };
+    let form = if n == 1 { "one" } else { "other" };
+    let span = tf(&format!("time.{unit}.{form}"), &[("n", &n)]);
+    if seconds < 0 {
+        tf("time.ago", &[("time", &span)])
+    } else {
+        tf("time.in", &[("time", &span)])
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("language.label", "Language"),
+    ("language.submit", "Change"),
+    (
+        "auth.too_many_signups",
+        "Too many signup attempts. Please try again later.",
+    ),
+    (
+        "auth.too_many_logins",
+        "Too many sign-in attempts. Please try again later.",
+    ),
+    ("auth.signup_failed", "Signup failed. Please try again."),
+    (
+        "auth.verification_failed",
+        "Verification failed. Please reload and try again.",
+    ),
+    (
+        "auth.captcha_failed",
+        "Please complete the captcha and try again.",
+    ),
+    ("auth.invalid_credentials", "Invalid email or password"),
+    (
+        "auth.email_not_verified",
+        "Your email address has not been verified. Please check your email for the verification code.",
+    ),
+    (
+        "auth.verification_code_sent",
+        "A new verification code has been sent. Please check your email.",
+    ),
+    (
+        "auth.invalid_verification_code",
+        "Invalid or expired verification code",
+    ),
+    (
+        "auth.reset_code_sent",
+        "If an account exists for {email}, a password reset code has been sent.",
+    ),
+    ("auth.passwords_mismatch", "Passwords do not match"),
+    ("auth.invalid_reset_code", "Invalid or expired reset code"),
+    (
+        "auth.too_many_verification_emails",
+        "Too many verification emails requested. Please wait a few minutes and try again.",
+    ),
+    ("validation.required", "{label} is required"),
+    (
+        "validation.max_len",
+        "{label} must be at most {max} characters",
+    ),
+    ("validation.email", "{label} must be a valid email address"),
+    (
+        "validation.year",
+        "{label} must be a year between {min} and {max}",
+    ),
+    (
+        "validation.integer_at_least",
+        "{label} must be a whole number of at least {min}",
+    ),
+    ("time.just_now", "just now"),
+    ("time.ago", "{time} ago"),
+    ("time.in", "in {time}"),
+    ("time.minute.one", "a minute"),
+    ("time.minute.other", "{n} minutes"),
+    ("time.hour.one", "an hour"),
+    ("time.hour.other", "{n} hours"),
+    ("time.day.one", "a day"),
+    ("time.day.other", "{n} days"),
+    ("time.week.one", "a week"),
+    ("time.week.other", "{n} weeks"),
+    ("time.month.one", "a month"),
+    ("time.month.other", "{n} months"),
+    ("time.year.one", "a year"),
+    ("time.year.other", "{n} years"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("language.label", "Idioma"),
+    ("language.submit", "Cambiar"),
+    (
+        "auth.too_many_signups",
+        "Demasiados intentos de registro. Inténtalo de nuevo más tarde.",
+    ),
+    (
+        "auth.too_many_logins",
+        "Demasiados intentos de inicio de sesión. Inténtalo de nuevo más tarde.",
+    ),
+    (
+        "auth.signup_failed",
+        "No se pudo completar el registro. Inténtalo de nuevo.",
+    ),
+    (
+        "auth.verification_failed",
+        "La verificación falló. Recarga la página e inténtalo de nuevo.",
+    ),
+    (
+        "auth.captcha_failed",
+        "Completa el captcha e inténtalo de nuevo.",
+    ),
+    (
+        "auth.invalid_credentials",
+        "Correo electrónico o contraseña no válidos",
+    ),
+    (
+        "auth.email_not_verified",
+        "Tu dirección de correo electrónico no ha sido verificada. Revisa tu correo para obtener el código de verificación.",
+    ),
+    (
+        "auth.verification_code_sent",
+        "Se ha enviado un nuevo código de verificación. Revisa tu correo electrónico.",
+    ),
+    (
+        "auth.invalid_verification_code",
+        "Código de verificación no válido o caducado",
+    ),
+    (
+        "auth.reset_code_sent",
+        "Si existe una cuenta para {email}, se ha enviado un código para restablecer la contraseña.",
+    ),
+    ("auth.passwords_mismatch", "Las contraseñas no coinciden"),
+    (
+        "auth.invalid_reset_code",
+        "Código de restablecimiento no válido o caducado",
+    ),
+    (
+        "auth.too_many_verification_emails",
+        "Se han solicitado demasiados correos de verificación. Espera unos minutos e inténtalo de nuevo.",
+    ),
+    ("validation.required", "{label} es obligatorio"),
+    (
+        "validation.max_len",
+        "{label} debe tener como máximo {max} caracteres",
+    ),
+    (
+        "validation.email",
+        "{label} debe ser una dirección de correo electrónico válida",
+    ),
+    (
+        "validation.year",
+        "{label} debe ser un año entre {min} y {max}",
+    ),
+    (
+        "validation.integer_at_least",
+        "{label} debe ser un número entero mayor o igual que {min}",
+    ),
+    ("time.just_now", "justo ahora"),
+    ("time.ago", "hace {time}"),
+    ("time.in", "dentro de {time}"),
+    ("time.minute.one", "un minuto"),
+    ("time.minute.other", "{n} minutos"),
+    ("time.hour.one", "una hora"),
+    ("time.hour.other", "{n} horas"),
+    ("time.day.one", "un día"),
+    ("time.day.other", "{n} días"),
+    ("time.week.one", "una semana"),
+    ("time.week.other", "{n} semanas"),
+    ("time.month.one", "un mes"),
+    ("time.month.other", "{n} meses"),
+    ("time.year.one", "un año"),
+    ("time.year.other", "{n} años"),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("language.label", "Langue"),
+    ("language.submit", "Changer"),
+    (
+        "auth.too_many_signups",
+        "Trop de tentatives d'inscription. Veuillez réessayer plus tard.",
+    ),
+    (
+        "auth.too_many_logins",
+        "Trop de tentatives de connexion. Veuillez réessayer plus tard.",
+    ),
+    (
+        "auth.signup_failed",
+        "L'inscription a échoué. Veuillez réessayer.",
+    ),
+    (
+        "auth.verification_failed",
+        "La vérification a échoué. Veuillez recharger la page et réessayer.",
+    ),
+    (
+        "auth.captcha_failed",
+        "Veuillez compléter le captcha et réessayer.",
+    ),
+    (
+        "auth.invalid_credentials",
+        "Adresse e-mail ou mot de passe incorrect",
+    ),
+    (
+        "auth.email_not_verified",
+        "Votre adresse e-mail n'a pas été vérifiée. Consultez vos e-mails pour obtenir le code de vérification.",
+    ),
+    (
+        "auth.verification_code_sent",
+        "Un nouveau code de vérification a été envoyé. Veuillez consulter vos e-mails.",
+    ),
+    (
+        "auth.invalid_verification_code",
+        "Code de vérification invalide ou expiré",
+    ),
+    (
+        "auth.reset_code_sent",
+        "Si un compte existe pour {email}, un code de réinitialisation du mot de passe a été envoyé.",
+    ),
+    (
+        "auth.passwords_mismatch",
+        "Les mots de passe ne correspondent pas",
+    ),
+    (
+        "auth.invalid_reset_code",
+        "Code de réinitialisation invalide ou expiré",
+    ),
+    (
+        "auth.too_many_verification_emails",
+        "Trop d'e-mails de vérification demandés. Veuillez patienter quelques minutes et réessayer.",
+    ),
+    ("validation.required", "{label} est obligatoire"),
+    (
+        "validation.max_len",
+        "{label} doit comporter au maximum {max} caractères",
+    ),
+    (
+        "validation.email",
+        "{label} doit être une adresse e-mail valide",
+    ),
+    (
+        "validation.year",
+        "{label} doit être une année comprise entre {min} et {max}",
+    ),
+    (
+        "validation.integer_at_least",
+        "{label} doit être un nombre entier supérieur ou égal à {min}",
+    ),
+    ("time.just_now", "à l'instant"),
+    ("time.ago", "il y a {time}"),
+    ("time.in", "dans {time}"),
+    ("time.minute.one", "une minute"),
+    ("time.minute.other", "{n} minutes"),
+    ("time.hour.one", "une heure"),
+    ("time.hour.other", "{n} heures"),
+    ("time.day.one", "un jour"),
+    ("time.day.other", "{n} jours"),
+    ("time.week.one", "une semaine"),
+    ("time.week.other", "{n} semaines"),
+    ("time.month.one", "un mois"),
+    ("time.month.other", "{n} mois"),
+    ("time.year.one", "un an"),
+    ("time.year.other", "{n} ans"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("language.label", "Sprache"),
+    ("language.submit", "Ändern"),
+    (
+        "auth.too_many_signups",
+        "Zu viele Registrierungsversuche. Bitte versuche es später erneut.",
+    ),
+    (
+        "auth.too_many_logins",
+        "Zu viele Anmeldeversuche. Bitte versuche es später erneut.",
+    ),
+    (
+        "auth.signup_failed",
+        "Die Registrierung ist fehlgeschlagen. Bitte versuche es erneut.",
+    ),
+    (
+        "auth.verification_failed",
+        "Die Überprüfung ist fehlgeschlagen. Bitte lade die Seite neu und versuche es erneut.",
+    ),
+    (
+        "auth.captcha_failed",
+        "Bitte löse das Captcha und versuche es erneut.",
+    ),
+    (
+        "auth.invalid_credentials",
+        "Ungültige E-Mail-Adresse oder ungültiges Passwort",
+    ),
+    (
+        "auth.email_not_verified",
+        "Deine E-Mail-Adresse wurde noch nicht bestätigt. Bitte sieh in deinem Postfach nach dem Bestätigungscode.",
+    ),
+    (
+        "auth.verification_code_sent",
+        "Ein neuer Bestätigungscode wurde gesendet. Bitte sieh in deinem Postfach nach.",
+    ),
+    (
+        "auth.invalid_verification_code",
+        "Ungültiger oder abgelaufener Bestätigungscode",
+    ),
+    (
+        "auth.reset_code_sent",
+        "Falls ein Konto für {email} existiert, wurde ein Code zum Zurücksetzen des Passworts gesendet.",
+    ),
+    (
+        "auth.passwords_mismatch",
+        "Die Passwörter stimmen nicht überein",
+    ),
+    (
+        "auth.invalid_reset_code",
+        "Ungültiger oder abgelaufener Code zum Zurücksetzen",
+    ),
+    (
+        "auth.too_many_verification_emails",
+        "Zu viele Bestätigungs-E-Mails angefordert. Bitte warte ein paar Minuten und versuche es erneut.",
+    ),
+    ("validation.required", "{label} ist erforderlich"),
+    (
+        "validation.max_len",
+        "{label} darf höchstens {max} Zeichen lang sein",
+    ),
+    (
+        "validation.email",
+        "{label} muss eine gültige E-Mail-Adresse sein",
+    ),
+    (
+        "validation.year",
+        "{label} muss ein Jahr zwischen {min} und {max} sein",
+    ),
+    (
+        "validation.integer_at_least",
+        "{label} muss eine ganze Zahl von mindestens {min} sein",
+    ),
+    // "vor" and "in" both take the dative, so units are declined for it.
+    ("time.just_now", "gerade eben"),
+    ("time.ago", "vor {time}"),
+    ("time.in", "in {time}"),
+    ("time.minute.one", "einer Minute"),
+    ("time.minute.other", "{n} Minuten"),
+    ("time.hour.one", "einer Stunde"),
+    ("time.hour.other", "{n} Stunden"),
+    ("time.day.one", "einem Tag"),
+    ("time.day.other", "{n} Tagen"),
+    ("time.week.one", "einer Woche"),
+    ("time.week.other", "{n} Wochen"),
+    ("time.month.one", "einem Monat"),
+    ("time.month.other", "{n} Monaten"),
+    ("time.year.one", "einem Jahr"),
+    ("time.year.other", "{n} Jahren"),
+];
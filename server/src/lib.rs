@@ -18,29 +18,45 @@
 //!
 //! Shared plumbing: [`error`] (the crate-wide `Error`/`Result`), [`db`] (the
 //! global SurrealDB handle), [`auth`] (JWT + password hashing), [`config`],
-//! [`datastar`]/[`html`]/[`text`] (fragment + formatting helpers).
+//! [`server`] (the tuned HTTP accept loop),
+//! [`datastar`]/[`html`]/[`text`]/[`currency`] (fragment + formatting
+//! helpers), [`util`] (slug generation), [`avatar`] (generated initials placeholders),
+//! [`validation`] (collect-all form field checks), [`i18n`] (translated
+//! message catalogs), [`pagination`] (the
+//! shared `Page`/`PageQuery` types for list endpoints), [`rate_limit`]
+//! (sliding-window limiters and their 429 headers).
 
 pub mod aristotle;
 pub mod auth;
+pub mod avatar;
+pub mod captcha;
 pub mod config;
+pub mod country;
+pub mod currency;
 pub mod datastar;
 pub mod db;
 pub mod error;
 pub mod html;
+pub mod i18n;
 pub mod logging;
 pub mod markdown;
 pub mod mcp;
 pub mod middleware;
 pub mod models;
+pub mod pagination;
+pub mod rate_limit;
 pub mod record_id_ext;
 pub mod response;
 pub mod routes;
 pub mod serde_utils;
+pub mod server;
 pub mod services;
 pub mod social_platforms;
 pub mod stats;
 pub mod templates;
 pub mod text;
+pub mod util;
+pub mod validation;
 pub mod verification_limits;
 pub mod version;
 pub mod video_platforms;
@@ -3,7 +3,7 @@ use slatehub::db::{DB, ensure_db_initialized};
 use slatehub::services::embedding::init_embedding_service;
 use slatehub::services::oidc_keys::ensure_signing_key;
 use slatehub::services::s3::init_s3;
-use surrealdb::{engine::remote::ws::Ws, opt::auth::Root};
+use surrealdb::engine::remote::ws::Ws;
 use tracing::{debug, error, info};
 
 #[tokio::main]
@@ -29,6 +29,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = match Config::from_env() {
         Ok(cfg) => {
             info!("Configuration loaded successfully");
+            cfg.install();
             cfg
         }
         Err(e) => {
@@ -37,6 +38,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    // Pre-compute the login timing-equalization hash so the first
+    // unknown-user sign-in isn't slower than the rest
+    if let Err(e) = tokio::task::spawn_blocking(slatehub::auth::init_dummy_hash).await {
+        error!("Failed to prepare dummy password hash: {}", e);
+        return Err(e.into());
+    }
+
     // Connect to database using configuration
     let db_url = config.database.connection_url();
 
@@ -82,38 +90,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Sign in to database using configured credentials
-    debug!("Authenticating with database");
-    match DB
-        .signin(Root {
-            username: config.database.username.clone(),
-            password: config.database.password.clone(),
-        })
-        .await
-    {
-        Ok(_) => info!("Database authentication successful"),
-        Err(e) => {
-            error!("Database authentication failed: {}", e);
-            return Err(e.into());
-        }
-    }
-
-    // Use configured namespace and database
+    // Sign in and select the configured namespace/database
     debug!(
-        "Setting namespace: {} and database: {}",
+        "Authenticating with database; namespace: {}, database: {}",
         config.database.namespace, config.database.name
     );
-    match DB
-        .use_ns(&config.database.namespace)
-        .use_db(&config.database.name)
-        .await
-    {
+    match slatehub::db::authenticate(&config.database).await {
         Ok(_) => info!(
-            "Using namespace: {} and database: {}",
+            "Database authentication successful; using namespace: {} and database: {}",
             config.database.namespace, config.database.name
         ),
         Err(e) => {
-            error!("Failed to set namespace/database: {}", e);
+            error!("Database authentication failed: {}", e);
             return Err(e.into());
         }
     }
@@ -128,6 +116,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Ping the database periodically and re-authenticate after an outage
+    slatehub::db::spawn_health_monitor(config.database.clone());
+
     // Ensure an OIDC signing key exists (generates one on first boot)
     debug!("Ensuring OIDC signing key");
     if let Err(e) = ensure_signing_key().await {
@@ -164,6 +155,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Geocode profile locations saved before structured locations existed.
+    // Paced at one lookup per second, so it runs in the background.
+    tokio::spawn(slatehub::services::geocoding::backfill_profile_locations());
+    // Same for filming locations, after parsing their structured address.
+    tokio::spawn(slatehub::services::geocoding::backfill_location_coordinates());
+
+    // Equipment bought before per-item currencies gets the configured default
+    match slatehub::models::equipment::EquipmentModel::backfill_purchase_currency().await {
+        Ok(0) => {}
+        Ok(n) => info!("Backfilled purchase currency on {} equipment items", n),
+        Err(e) => error!("Failed to backfill equipment purchase currency: {}", e),
+    }
+
+    // Organization service tags saved before normalization get the
+    // canonical spelling the service filter matches on
+    match slatehub::models::organization::OrganizationModel::new()
+        .backfill_service_tags()
+        .await
+    {
+        Ok(0) => {}
+        Ok(n) => info!("Normalized service tags on {} organizations", n),
+        Err(e) => error!("Failed to normalize organization service tags: {}", e),
+    }
+
     // Log Listmonk wiring status (no init needed — service is built per-call from env)
     slatehub::services::listmonk::log_status();
 
@@ -224,6 +239,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
+    // Periodically reconcile uploads against the DB: objects nothing
+    // references any more (replaced avatars, logos, posters) are logged, or
+    // deleted once STORAGE_CLEANUP_DRY_RUN=false.
+    tokio::spawn(async {
+        use slatehub::services::storage_cleanup::{self, StorageCleanupConfig};
+        let cfg = StorageCleanupConfig::from_env();
+        if cfg.interval_hours == 0 {
+            info!("Storage cleanup disabled (STORAGE_CLEANUP_INTERVAL_HOURS=0)");
+            return;
+        }
+        let interval = std::time::Duration::from_secs(u64::from(cfg.interval_hours) * 3600);
+        loop {
+            tokio::time::sleep(interval).await;
+            info!("Running orphaned storage sweep");
+            storage_cleanup::run(&cfg).await;
+        }
+    });
+
     // Start live notification stream
     info!("Starting notification live stream");
     slatehub::services::notification_stream::init().await;
@@ -250,15 +283,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("SlateHub server is ready to accept connections");
 
-    // Run the server. `into_make_service_with_connect_info` exposes the socket
-    // peer address to handlers (via `ConnectInfo<SocketAddr>`) so signup IP
-    // resolution has a real fallback when proxy headers are absent.
-    match axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
-    )
-    .await
-    {
+    // Run the server. Handlers still get the socket peer address (via
+    // `ConnectInfo<SocketAddr>`) so signup IP resolution has a real fallback
+    // when proxy headers are absent.
+    let tuning = config.server.tuning;
+    info!(
+        http2 = tuning.http2,
+        http1_keep_alive = tuning.http1_keep_alive,
+        tcp_keepalive_secs = tuning.tcp_keepalive.map(|d| d.as_secs()),
+        max_connections = tuning.max_connections,
+        "Connection tuning"
+    );
+    match slatehub::server::serve(listener, app, tuning).await {
         Ok(_) => {
             info!("Server shutdown gracefully");
             Ok(())
@@ -10,6 +10,7 @@ use std::time::Duration;
 
 use crate::config::mcp_search_weights;
 use crate::db::DB;
+use crate::models::person::AvailabilityStatus;
 use crate::services::embedding::generate_embedding_async;
 use crate::services::search::{
     SearchParams, search_jobs as svc_search_jobs, search_locations as svc_search_locations,
@@ -134,7 +135,7 @@ impl SlateHubMcp {
     pub fn new() -> Self {
         Self {
             tool_router: Self::tool_router(),
-            app_url: crate::config::app_url(),
+            app_url: crate::config::public_base_url(),
         }
     }
 }
@@ -262,11 +263,11 @@ impl ServerHandler for SlateHubMcp {
                     .with_mime_type("image/png")
                     .with_sizes(vec!["180x180".to_string()]),
             ]);
-        server_impl.website_url = Some("https://slatehub.com".to_string());
+        server_impl.website_url = Some(self.app_url.clone());
 
         ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
             .with_server_info(server_impl)
-            .with_instructions(concat!(
+            .with_instructions(format!(concat!(
                 "SlateHub MCP Server — read-only access to a creative networking platform for the film, TV, ",
                 "and content creation industry. You have access to profiles of actors, crew, filmmakers, ",
                 "and other creative professionals, plus productions, organizations, filming locations, and jobs.\n\n",
@@ -291,9 +292,9 @@ impl ServerHandler for SlateHubMcp {
                 "- Natural language works: 'female cinematographers in Berlin who speak German'\n",
                 "- Physical attribute filters: 'tall athletic male actors ages 25-35 with brown hair'\n",
                 "- The 'skill' parameter matches against headline and skills array\n",
-                "- All profile URLs follow the pattern: https://slatehub.com/username\n",
-                "- Photo URLs are relative paths starting with /api/media/ — prepend https://slatehub.com to make them absolute",
-            ))
+                "- All profile URLs follow the pattern: {base}/username\n",
+                "- Photo URLs are relative paths starting with /api/media/ — prepend {base} to make them absolute",
+            ), base = self.app_url))
     }
 }
 
@@ -365,6 +366,9 @@ impl SlateHubMcp {
             {
                 out.push_str(&format!("  Summary: {}\n", embedding_text));
             }
+            if let Some(m) = &r.matched {
+                out.push_str(&format!("  Matched {}: {}\n", m.field, m.snippet));
+            }
             out.push('\n');
         }
         Ok(out)
@@ -425,6 +429,9 @@ impl SlateHubMcp {
                 out.push_str(&format!("  Description: {}\n", desc));
             }
             out.push_str(&format!("  URL: {}/productions/{}\n", self.app_url, r.slug));
+            if let Some(m) = &r.matched {
+                out.push_str(&format!("  Matched {}: {}\n", m.field, m.snippet));
+            }
             out.push('\n');
         }
         Ok(out)
@@ -486,6 +493,9 @@ impl SlateHubMcp {
                 out.push_str(&format!("  Description: {}\n", desc));
             }
             out.push_str(&format!("  URL: {}/orgs/{}\n", self.app_url, r.slug));
+            if let Some(m) = &r.matched {
+                out.push_str(&format!("  Matched {}: {}\n", m.field, m.snippet));
+            }
             out.push('\n');
         }
         Ok(out)
@@ -555,6 +565,9 @@ impl SlateHubMcp {
                 out.push_str(&format!("  Description: {}\n", desc));
             }
             out.push_str(&format!("  URL: {}/locations/{}\n", self.app_url, r.key));
+            if let Some(m) = &r.matched {
+                out.push_str(&format!("  Matched {}: {}\n", m.field, m.snippet));
+            }
             out.push('\n');
         }
         Ok(out)
@@ -632,6 +645,9 @@ impl SlateHubMcp {
             // Extract key from id (format: "job_posting:key")
             let key = r.id.strip_prefix("job_posting:").unwrap_or(&r.id);
             out.push_str(&format!("  URL: {}/jobs/{}\n", self.app_url, key));
+            if let Some(m) = &r.matched {
+                out.push_str(&format!("  Matched {}: {}\n", m.field, m.snippet));
+            }
             out.push('\n');
         }
         Ok(out)
@@ -664,6 +680,7 @@ impl SlateHubMcp {
                     profile.languages AS languages,
                     profile.unions AS unions,
                     profile.availability AS availability,
+                    profile.availability_status AS availability_status,
                     profile.website AS website,
                     profile.reels AS reels,
                     profile.social_links AS social_links,
@@ -696,8 +713,17 @@ impl SlateHubMcp {
         if let Some(loc) = row["location"].as_str().filter(|s| !s.is_empty()) {
             out.push_str(&format!("Location: {}\n", loc));
         }
-        if let Some(avail) = row["availability"].as_str().filter(|s| !s.is_empty()) {
-            out.push_str(&format!("Availability: {}\n", avail));
+        let status = row["availability_status"]
+            .as_str()
+            .and_then(AvailabilityStatus::parse);
+        let note = row["availability"].as_str().filter(|s| !s.is_empty());
+        match (status, note) {
+            (Some(status), Some(note)) => {
+                out.push_str(&format!("Availability: {} ({})\n", status.label(), note))
+            }
+            (Some(status), None) => out.push_str(&format!("Availability: {}\n", status.label())),
+            (None, Some(note)) => out.push_str(&format!("Availability: {}\n", note)),
+            (None, None) => {}
         }
         if let Some(nat) = row["nationality"].as_str().filter(|s| !s.is_empty()) {
             out.push_str(&format!("Nationality: {}\n", nat));
@@ -9,7 +9,7 @@
 //! `Arc<CurrentUser>` (an alias for [`SessionUser`]) into the request
 //! extensions. A missing or invalid token never fails the request here; the
 //! request simply continues anonymously, and enforcement is left to the
-//! [`AuthenticatedUser`] extractor and individual handlers.
+//! [`AuthenticatedUser`] / [`AdminUser`] extractors and individual handlers.
 
 use axum::{
     extract::{FromRequestParts, Request},
@@ -26,6 +26,7 @@ use crate::{
     error::Error,
     models::person::{Person, SessionUser},
     record_id_ext::RecordIdExt,
+    templates::User,
 };
 use surrealdb::types::RecordId;
 
@@ -37,8 +38,10 @@ pub type CurrentUser = SessionUser;
 ///
 /// The token is read from the `Authorization: Bearer` header first (API
 /// clients such as the Chrome extension), falling back to the `auth_token`
-/// cookie. When the token decodes and its `sub` claim resolves to an
-/// existing person, an `Arc<CurrentUser>` is inserted into the request
+/// cookie. [`auth::decode_jwt`] checks the signature, expiry, issuer and
+/// audience, so a token minted for another environment is treated like
+/// any other invalid token. When the token decodes and its `sub` claim
+/// resolves to an existing person, an `Arc<CurrentUser>` is inserted into the request
 /// extensions for downstream middleware and handlers.
 ///
 /// # Errors
@@ -192,19 +195,7 @@ pub async fn auth_middleware(
 /// attributes as the login cookie, with `Max-Age` matching the 30-day JWT so
 /// cookie and token expire together.
 fn remembered_session_cookie(token: String) -> String {
-    let secure = std::env::var("COOKIE_SECURE")
-        .map(|v| v != "false")
-        .unwrap_or(true);
-    let cookie = cookie::Cookie::build(("auth_token", token))
-        .path("/")
-        .same_site(cookie::SameSite::Lax)
-        .http_only(true)
-        .secure(secure)
-        .max_age(cookie::time::Duration::seconds(
-            auth::session_duration(true) as i64,
-        ))
-        .build();
-    cookie.to_string()
+    auth::session_cookie(token, true).to_string()
 }
 
 /// `Set-Cookie` value that expires the `auth_token` cookie (matching the
@@ -212,13 +203,7 @@ fn remembered_session_cookie(token: String) -> String {
 /// holding a session for a deleted account, instead of letting it re-send the
 /// dead token on every request.
 fn stale_session_clear_cookie() -> String {
-    let secure = std::env::var("COOKIE_SECURE")
-        .map(|v| v != "false")
-        .unwrap_or(true);
-    format!(
-        "auth_token=; Path=/; Max-Age=0; HttpOnly; SameSite=Lax{}",
-        if secure { "; Secure" } else { "" }
-    )
+    auth::cleared_session_cookie().to_string()
 }
 
 /// Extract user information from the JWT-sub claim using the Person model.
@@ -311,3 +296,42 @@ where
             .ok_or(Error::Unauthorized)
     }
 }
+
+/// Extractor for platform-admin-only handlers (`person.is_admin = true`).
+///
+/// Runs the [`AuthenticatedUser`] check, then loads the template [`User`]
+/// (which carries `is_admin`, read fresh from the DB) so admin pages can
+/// hand it straight to `BaseContext::with_user` without a second lookup.
+///
+/// # Errors
+///
+/// [`Error::Unauthorized`] with no signed-in user; [`Error::Forbidden`]
+/// when the user isn't a platform admin (or the flag can't be read).
+pub struct AdminUser {
+    pub user: Arc<CurrentUser>,
+    pub template_user: User,
+}
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthenticatedUser(user) = AuthenticatedUser::from_request_parts(parts, state).await?;
+        let template_user = User::from_session_user(&user).await;
+        if !template_user.is_admin {
+            warn!(
+                "Non-admin {} denied access to {}",
+                user.username,
+                parts.uri.path()
+            );
+            return Err(Error::Forbidden);
+        }
+        Ok(AdminUser {
+            user,
+            template_user,
+        })
+    }
+}
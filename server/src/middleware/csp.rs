@@ -0,0 +1,99 @@
+//! Per-request Content-Security-Policy nonces.
+//!
+//! [`csp_middleware`] mints a fresh random nonce for every request and keeps
+//! it in scope while the handler (and the error-page renderer) runs, where
+//! [`crate::templates::BaseContext`] picks it up through [`nonce`]. Templates
+//! stamp it on inline tags from their context:
+//!
+//! ```html
+//! <script nonce="{{ nonce }}">…</script>
+//! ```
+//!
+//! On the way out it sets the `Content-Security-Policy` header from the
+//! configured policy ([`crate::config::content_security_policy`]),
+//! replacing every [`NONCE_PLACEHOLDER`] with the nonce. The default's
+//! `script-src 'self' 'nonce-{nonce}'` therefore admits exactly the stamped
+//! inline scripts of that response. Inline `on*` attributes are blocked
+//! along with unstamped scripts, so templates write them as
+//! `data-handler-*` attributes, run by `static/js/inline-handlers.js`.
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use base64::Engine;
+use rand::RngCore;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Text in a configured policy that is replaced by the request's nonce.
+pub const NONCE_PLACEHOLDER: &str = "{nonce}";
+
+tokio::task_local! {
+    static NONCE: String;
+}
+
+/// The current request's CSP nonce, or an empty string outside a request
+/// (background jobs, emails, tests rendering templates directly).
+pub fn nonce() -> String {
+    NONCE.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// 128 bits from the OS-seeded thread RNG, base64-encoded.
+fn generate_nonce() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// The configured policy, read and validated once when the router is built.
+#[derive(Clone, Debug)]
+pub struct CspPolicy(Option<Arc<str>>);
+
+impl CspPolicy {
+    /// Load [`crate::config::content_security_policy`]. A policy that is not
+    /// a valid header value (even with a nonce substituted) falls back to
+    /// the default rather than serving pages without a policy.
+    pub fn from_config() -> Self {
+        let policy = crate::config::content_security_policy().map(|csp| {
+            let sample = csp.replace(NONCE_PLACEHOLDER, &generate_nonce());
+            match HeaderValue::from_str(&sample) {
+                Ok(_) => csp,
+                Err(e) => {
+                    warn!(
+                        "CONTENT_SECURITY_POLICY is not a valid header value ({e}); using the default"
+                    );
+                    crate::config::DEFAULT_CONTENT_SECURITY_POLICY.to_string()
+                }
+            }
+        });
+        Self(policy.map(Arc::from))
+    }
+}
+
+/// Mint the request's nonce, run the rest of the stack with it in scope,
+/// and set the `Content-Security-Policy` header unless the handler already
+/// chose its own.
+pub async fn csp_middleware(
+    State(policy): State<CspPolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let nonce = generate_nonce();
+
+    let mut response = NONCE.scope(nonce.clone(), next.run(request)).await;
+
+    if let Some(policy) = &policy.0
+        && !response
+            .headers()
+            .contains_key(header::CONTENT_SECURITY_POLICY)
+        && let Ok(value) = HeaderValue::from_str(&policy.replace(NONCE_PLACEHOLDER, &nonce))
+    {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_SECURITY_POLICY, value);
+    }
+    response
+}
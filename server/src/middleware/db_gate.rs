@@ -0,0 +1,52 @@
+//! Database-outage gate.
+//!
+//! [`db_gate_middleware`] sits just outside the auth middleware in the stack
+//! built by [`crate::routes::app`], so it runs before the first query a
+//! request would make (the auth middleware's person lookup). While the
+//! health monitor in [`crate::db`] is reconnecting it answers with a 503
+//! and `Retry-After` instead of letting the request fail deeper in. Static
+//! assets and `/healthcheck` (which reports the database itself) always
+//! pass through. It also turns a server error into a 503 with
+//! `Retry-After` when one of the request's queries was turned away by the
+//! query-slot guard ([`db::track_busy_queries`]), since handlers usually
+//! report query failures as plain database errors. It inserts nothing
+//! into the request extensions.
+
+use axum::{
+    extract::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+
+use crate::{db, error::Error};
+
+/// How long clients are told to wait while the database reconnects.
+const RETRY_AFTER: Duration = Duration::from_secs(5);
+
+/// Turn requests away with a 503 while [`db::is_available`] is false, and
+/// answer a 503 for requests that failed because the query slots were busy.
+pub async fn db_gate_middleware(request: Request, next: Next) -> Response {
+    if bypasses_gate(request.uri().path()) {
+        return next.run(request).await;
+    }
+    if db::is_available() {
+        let (response, turned_away) = db::track_busy_queries(next.run(request)).await;
+        if turned_away && response.status().is_server_error() {
+            return db::busy_error().into_response();
+        }
+        return response;
+    }
+    Error::Unavailable {
+        message: "SlateHub is briefly unavailable while it reconnects to its database. \
+                  Please try again in a few seconds."
+            .to_string(),
+        retry_after: RETRY_AFTER,
+    }
+    .into_response()
+}
+
+/// Paths that work (or report status) without the database.
+fn bypasses_gate(path: &str) -> bool {
+    path == "/healthcheck" || path.starts_with("/static/")
+}
@@ -1,7 +1,7 @@
 //! Error-to-response conversion middleware.
 //!
 //! [`error_response_middleware`] sits between the request-ID/header layers
-//! and the auth middleware in the stack built by [`crate::routes::app`]. It
+//! and the database gate in the stack built by [`crate::routes::app`]. It
 //! inserts nothing into the request extensions; on the way in it reads the
 //! [`RequestId`](super::RequestId) (via [`RequestIdExt`]) for log
 //! correlation. After the inner service responds, every 4xx/5xx response is
@@ -22,7 +22,7 @@ use axum::{
 use serde_json::json;
 use tracing::{debug, error, info};
 
-use crate::{error::Error, middleware::RequestIdExt};
+use crate::{error::Error, middleware::RequestIdExt, rate_limit::RateLimitStatus};
 use crate::{log_colored_error, log_db_error};
 
 /// Check if the client accepts HTML responses
@@ -40,13 +40,15 @@ fn accepts_html(headers: &HeaderMap) -> bool {
 /// the internal detail for database, template, internal, and
 /// external-service errors), then renders either a styled HTML error page
 /// (when the client accepts `text/html`) or a JSON body containing the
-/// message, status, request ID, and timestamp.
+/// message, status, request ID, and timestamp — plus a `fields` object
+/// (field → messages) for [`Error::ValidationFields`].
 pub fn create_error_response(
     error: &Error,
     headers: &HeaderMap,
     request_path: Option<String>,
     request_id: Option<String>,
 ) -> Response {
+    let summary = error.form_message().map(|m| m.into_owned());
     let (status, error_message, custom_message) = match error {
         Error::Database(msg) => {
             log_db_error!(msg);
@@ -82,13 +84,28 @@ pub fn create_error_response(
             msg.as_str(),
             Some(msg.clone()),
         ),
+        Error::ValidationFields(_) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            summary.as_deref().unwrap_or("Validation error"),
+            summary.clone(),
+        ),
+        Error::RateLimited { message, .. } => (
+            StatusCode::TOO_MANY_REQUESTS,
+            message.as_str(),
+            Some(message.clone()),
+        ),
+        Error::Unavailable { message, .. } => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            message.as_str(),
+            Some(message.clone()),
+        ),
         Error::ExternalService(msg) => {
             log_colored_error!("network", format!("External service error: {}", msg));
             (StatusCode::BAD_GATEWAY, "External service error", None)
         }
     };
 
-    if accepts_html(headers) {
+    let mut response = if accepts_html(headers) {
         render_html_error(
             status,
             error_message,
@@ -97,8 +114,22 @@ pub fn create_error_response(
             request_id,
         )
     } else {
-        render_json_error(status, error_message, request_id)
+        let fields = match error {
+            Error::ValidationFields(errors) => Some(json!(errors.by_field())),
+            _ => None,
+        };
+        render_json_error(status, error_message, fields, request_id)
+    };
+    if let Error::RateLimited { status, .. } = error {
+        status.apply_headers(response.headers_mut());
     }
+    if let Error::Unavailable { retry_after, .. } = error {
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            header::HeaderValue::from(retry_after.as_secs()),
+        );
+    }
+    response
 }
 
 /// Render an HTML error page
@@ -138,6 +169,11 @@ fn render_html_error(
             "422".to_string(),
             custom_message.unwrap_or_else(|| "The information you provided couldn't be processed. Please check your input and try again.".to_string()),
         ),
+        StatusCode::TOO_MANY_REQUESTS => (
+            "Too Many Requests",
+            "429".to_string(),
+            custom_message.unwrap_or_else(|| "You're doing that too often. Please wait a moment and try again.".to_string()),
+        ),
         StatusCode::BAD_REQUEST => (
             "Bad Request",
             "400".to_string(),
@@ -199,14 +235,18 @@ fn render_html_error(
 fn render_json_error(
     status: StatusCode,
     error_message: &str,
+    fields: Option<serde_json::Value>,
     request_id: Option<String>,
 ) -> Response {
-    let body = json!({
+    let mut body = json!({
         "error": error_message,
         "status": status.as_u16(),
         "request_id": request_id,
         "timestamp": chrono::Utc::now().to_rfc3339(),
     });
+    if let Some(fields) = fields {
+        body["fields"] = fields;
+    }
 
     (status, Json(body)).into_response()
 }
@@ -351,6 +391,26 @@ pub async fn error_response_middleware(req: Request, next: Next) -> Response {
                             Error::Validation("Validation error".to_string())
                         }
                     }
+                    StatusCode::TOO_MANY_REQUESTS => Error::RateLimited {
+                        message: custom_message
+                            .clone()
+                            .unwrap_or_else(|| "Too many requests".to_string()),
+                        status: RateLimitStatus::from_headers(response.headers())
+                            .unwrap_or_default(),
+                    },
+                    StatusCode::SERVICE_UNAVAILABLE => Error::Unavailable {
+                        message: custom_message
+                            .clone()
+                            .unwrap_or_else(|| "Service unavailable".to_string()),
+                        retry_after: std::time::Duration::from_secs(
+                            response
+                                .headers()
+                                .get(header::RETRY_AFTER)
+                                .and_then(|v| v.to_str().ok())
+                                .and_then(|v| v.parse().ok())
+                                .unwrap_or(0),
+                        ),
+                    },
                     StatusCode::BAD_GATEWAY => {
                         Error::ExternalService("External service error".to_string())
                     }
@@ -0,0 +1,78 @@
+//! Picks the language each request is answered in.
+//!
+//! A `lang` cookie (set from the language picker, see `POST /language`)
+//! is an explicit choice and wins; otherwise the `Accept-Language` header
+//! is negotiated against the supported locales ([`Locale::negotiate`]).
+//! The result goes into the request extensions and, for handlers and
+//! templates, into [`i18n::current`]; the response says which one was
+//! used in `Content-Language`.
+//!
+//! [`timezone_middleware`] runs later, inside auth, because the signed-in
+//! person's saved timezone beats the browser's `tz` cookie.
+
+use axum::{
+    extract::Request,
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::extract::cookie::CookieJar;
+
+use std::sync::Arc;
+
+use chrono_tz::Tz;
+
+use crate::i18n::{self, LANG_COOKIE, Locale, TZ_COOKIE};
+use crate::middleware::CurrentUser;
+
+/// The locale for a request's cookies and headers.
+pub fn resolve(request: &Request) -> Locale {
+    CookieJar::from_headers(request.headers())
+        .get(LANG_COOKIE)
+        .and_then(|cookie| Locale::from_tag(cookie.value()))
+        .unwrap_or_else(|| {
+            Locale::negotiate(
+                request
+                    .headers()
+                    .get(header::ACCEPT_LANGUAGE)
+                    .and_then(|v| v.to_str().ok()),
+            )
+        })
+}
+
+/// Resolve the locale, run the rest of the stack with it in scope, and set
+/// `Content-Language` unless the handler already did.
+pub async fn locale_middleware(mut request: Request, next: Next) -> Response {
+    let locale = resolve(&request);
+    request.extensions_mut().insert(locale);
+
+    let mut response = i18n::scope(locale, next.run(request)).await;
+    response
+        .headers_mut()
+        .entry(header::CONTENT_LANGUAGE)
+        .or_insert(HeaderValue::from_static(locale.code()));
+    response
+}
+
+/// The timezone for a request: the signed-in person's preference, then the
+/// browser's `tz` cookie, then UTC.
+pub fn resolve_timezone(request: &Request) -> Tz {
+    request
+        .extensions()
+        .get::<Arc<CurrentUser>>()
+        .and_then(|user| user.timezone.as_deref())
+        .and_then(i18n::parse_timezone)
+        .or_else(|| {
+            CookieJar::from_headers(request.headers())
+                .get(TZ_COOKIE)
+                .and_then(|cookie| i18n::parse_timezone(cookie.value()))
+        })
+        .unwrap_or(Tz::UTC)
+}
+
+/// Run the rest of the stack with the request's timezone in scope for
+/// [`i18n::local_datetime`].
+pub async fn timezone_middleware(request: Request, next: Next) -> Response {
+    let tz = resolve_timezone(&request);
+    i18n::scope_timezone(tz, next.run(request)).await
+}
@@ -14,18 +14,35 @@
 //!    `CorsLayer` (Chrome-extension origins), and four `SetResponseHeaderLayer`s
 //!    for security headers. These add spans and response headers but insert
 //!    nothing into the request extensions.
-//! 3. [`error_response_middleware`] — reads the [`RequestId`] for log
+//! 3. [`csp_middleware`] — mints a per-request nonce and keeps it in scope
+//!    for [`crate::templates::BaseContext`] ([`csp::nonce`]), then sets the
+//!    `Content-Security-Policy` header.
+//! 4. [`locale_middleware`] — picks the response language from the `lang`
+//!    cookie or `Accept-Language`, inserts [`crate::i18n::Locale`] into the
+//!    request extensions and keeps it in scope for [`crate::i18n::t`], then
+//!    sets `Content-Language`.
+//! 5. [`error_response_middleware`] — reads the [`RequestId`] for log
 //!    correlation on the way in; on the way out it logs 4xx/5xx responses and
 //!    rewrites those carrying an `X-Error-Message` header into full HTML
 //!    error pages for clients that accept `text/html`.
-//! 4. [`auth_middleware`] — decodes the JWT from the `Authorization: Bearer`
+//! 6. [`db_gate_middleware`] — while [`crate::db`]'s health monitor is
+//!    reconnecting to the database, answers with a 503 and `Retry-After`
+//!    (static assets and `/healthcheck` excepted) instead of running the
+//!    rest of the stack.
+//! 7. [`read_only_middleware`] — in read-only maintenance mode, answers
+//!    POST/PUT/PATCH/DELETE requests (bar the admin toggle, backup, and
+//!    sign-in) with a 503 and `Retry-After`; reads pass through.
+//! 8. [`auth_middleware`] — decodes the JWT from the `Authorization: Bearer`
 //!    header or the `auth_token` cookie and, when it resolves to a known
 //!    person, inserts `Arc<CurrentUser>` into the request extensions. It
 //!    never rejects a request itself.
-//! 5. [`activity::activity_middleware`] — reads the `Arc<CurrentUser>`
-//!    extension and, after the handler responds, records a `page_view`
-//!    activity event for successful GET requests to user-facing pages.
-//! 6. `DefaultBodyLimit` (50 MB) and the route handler.
+//! 9. [`timezone_middleware`] — reads the `Arc<CurrentUser>` extension's
+//!    timezone preference (else the `tz` cookie, else UTC) and keeps it in
+//!    scope for [`crate::i18n::local_datetime`].
+//! 10. [`activity::activity_middleware`] — reads the `Arc<CurrentUser>`
+//!     extension and, after the handler responds, records a `page_view`
+//!     activity event for successful GET requests to user-facing pages.
+//! 11. `DefaultBodyLimit` (50 MB) and the route handler.
 //!
 //! Responses unwind through the same layers in reverse order.
 //!
@@ -36,11 +53,19 @@
 
 pub mod activity;
 pub mod auth;
+pub mod csp;
+pub mod db_gate;
 pub mod error_handler;
+pub mod locale;
 pub mod logging;
+pub mod read_only;
 pub mod request_id;
 
-pub use auth::{AuthenticatedUser, CurrentUser, UserExtractor, auth_middleware};
+pub use auth::{AdminUser, AuthenticatedUser, CurrentUser, UserExtractor, auth_middleware};
+pub use csp::{CspPolicy, csp_middleware};
+pub use db_gate::db_gate_middleware;
 pub use error_handler::{ErrorWithContext, ResultExt, error_response_middleware};
+pub use locale::{locale_middleware, timezone_middleware};
 pub use logging::{filtered_logging_middleware, logging_middleware};
+pub use read_only::read_only_middleware;
 pub use request_id::{RequestId, RequestIdExt, request_id_middleware};
@@ -0,0 +1,73 @@
+//! Read-only maintenance mode.
+//!
+//! While [`is_read_only`] is true, [`read_only_middleware`] answers
+//! state-changing requests (POST, PUT, PATCH, DELETE) with a 503 and
+//! `Retry-After`, and lets everything else through — the site stays
+//! browsable during a migration or backup without taking writes that could
+//! be lost or conflict. It is seeded from `READ_ONLY_MODE` on first use and
+//! flipped at runtime from the admin dashboard (`POST /admin/read-only`),
+//! which, with sign-in and the admin backup, stays exempt so the mode can
+//! always be turned off again. Layered with the database gate, outside the
+//! auth middleware; it inserts nothing into the request extensions.
+
+use axum::{
+    extract::Request,
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::error::Error;
+
+/// How long clients are told to wait before retrying a rejected write.
+const RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// POST endpoints that keep working in read-only mode.
+const EXEMPT_PATHS: &[&str] = &["/admin/read-only", "/admin/backup", "/login", "/logout"];
+
+static READ_ONLY: LazyLock<AtomicBool> = LazyLock::new(|| {
+    let enabled = std::env::var("READ_ONLY_MODE")
+        .map(|v| v.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if enabled {
+        warn!("Starting in read-only mode (READ_ONLY_MODE=true)");
+    }
+    AtomicBool::new(enabled)
+});
+
+/// Whether writes are currently being rejected.
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// Turn read-only mode on or off until the next restart (which goes back
+/// to `READ_ONLY_MODE`).
+pub fn set_read_only(enabled: bool) {
+    READ_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+/// Reject state-changing requests with a 503 while [`is_read_only`].
+pub async fn read_only_middleware(request: Request, next: Next) -> Response {
+    if is_read_only() && is_write(request.method()) && !EXEMPT_PATHS.contains(&request.uri().path())
+    {
+        return Error::Unavailable {
+            message: "SlateHub is in read-only mode for maintenance. You can keep browsing; \
+                      changes can be saved again shortly."
+                .to_string(),
+            retry_after: RETRY_AFTER,
+        }
+        .into_response();
+    }
+    next.run(request).await
+}
+
+fn is_write(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
@@ -7,7 +7,8 @@
 //! ULID, inserts a [`RequestId`] into the request extensions, wraps the rest
 //! of the stack in a tracing span carrying the ID, and echoes the ID back in
 //! the `X-Request-Id` response header. Downstream code reads the ID through
-//! the [`RequestIdExt`] trait.
+//! the [`RequestIdExt`] trait, or [`current`] where there is no request at
+//! hand (database errors raised deep in a model).
 
 use crate::logging::format_http_status;
 use axum::{
@@ -19,6 +20,16 @@ use axum::{
 use tracing::{Instrument, info_span};
 use ulid::Ulid;
 
+tokio::task_local! {
+    static CURRENT: String;
+}
+
+/// The ID of the request being handled on this task, if any (`None` in
+/// background jobs and tests that call models directly).
+pub fn current() -> Option<String> {
+    CURRENT.try_with(Clone::clone).ok()
+}
+
 /// Extension type carrying the unique ID assigned to a request.
 #[derive(Clone, Debug)]
 pub struct RequestId(pub String);
@@ -91,7 +102,9 @@ pub async fn request_id_middleware(mut request: Request<Body>, next: Next) -> Re
     drop(_enter);
 
     // Process the request within the span
-    let mut response = next.run(request).instrument(span.clone()).await;
+    let mut response = CURRENT
+        .scope(id_str.clone(), next.run(request).instrument(span.clone()))
+        .await;
 
     // Add the request ID to the response headers for debugging
     // This helps with tracing requests through multiple services
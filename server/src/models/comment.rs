@@ -0,0 +1,180 @@
+//! Comments on productions (and, via the polymorphic `target`, any record).
+//!
+//! Owns the `comment` table. Called by `routes/comments.rs` (create / edit /
+//! delete / paginated JSON list) and `routes/productions.rs` (first page
+//! rendered inline on the production page). Cascades: `ProductionModel::delete`
+//! removes a production's comments, `Person::delete_with_cascade` removes a
+//! person's; deleting a comment also drops its `likes` edges.
+
+use crate::{db::DB, error::Error, record_id_ext::RecordIdExt};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, SurrealValue};
+use tracing::debug;
+
+/// Maximum comment length in characters (mirrors the schema ASSERT).
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+
+/// One `comment` row as stored.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct Comment {
+    pub id: RecordId,
+    pub target: RecordId,
+    pub author: RecordId,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    /// NONE until the author edits the body.
+    #[serde(default)]
+    #[surreal(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A comment joined with its author's display fields; ids are cast to
+/// `<string>` in the query ("comment:key", "person:key").
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct CommentView {
+    pub id: String,
+    pub body: String,
+    pub author_id: String,
+    pub author_username: String,
+    #[serde(default)]
+    #[surreal(default)]
+    pub author_name: Option<String>,
+    #[serde(default)]
+    #[surreal(default)]
+    pub author_avatar: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    #[surreal(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, SurrealValue)]
+struct CountResult {
+    count: u64,
+}
+
+/// Query/mutation surface for the `comment` table.
+pub struct CommentModel;
+
+impl CommentModel {
+    /// Trim a submitted body and enforce the non-empty / max-length rules.
+    /// Length is counted in characters, matching SurrealDB's `string::len`.
+    pub fn normalize_body(raw: &str) -> Result<String, Error> {
+        let body = raw.trim();
+        if body.is_empty() {
+            return Err(Error::validation("Comment cannot be empty"));
+        }
+        if body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(Error::validation(format!(
+                "Comment must be {} characters or fewer",
+                MAX_COMMENT_LENGTH
+            )));
+        }
+        Ok(body.to_string())
+    }
+
+    /// Create a comment on `target` by `author`. The body is normalized
+    /// first, so callers can pass raw form input.
+    pub async fn create(
+        target: &RecordId,
+        author: &RecordId,
+        body: &str,
+    ) -> Result<Comment, Error> {
+        let body = Self::normalize_body(body)?;
+        debug!(
+            "Creating comment on {} by {}",
+            target.display(),
+            author.display()
+        );
+
+        let comment: Option<Comment> = DB
+            .query(
+                "CREATE comment CONTENT {
+                    target: $target,
+                    author: $author,
+                    body: $body
+                }",
+            )
+            .bind(("target", target.clone()))
+            .bind(("author", author.clone()))
+            .bind(("body", body))
+            .await?
+            .take(0)?;
+
+        comment.ok_or_else(|| Error::Database("Failed to create comment".to_string()))
+    }
+
+    /// Fetch one comment by id.
+    pub async fn get(id: &RecordId) -> Result<Comment, Error> {
+        let comment: Option<Comment> = DB
+            .query("SELECT * FROM ONLY $id")
+            .bind(("id", id.clone()))
+            .await?
+            .take(0)?;
+
+        comment.ok_or(Error::NotFound)
+    }
+
+    /// Page through a target's comments, oldest first so threads read
+    /// top-to-bottom.
+    pub async fn list_for_target(
+        target: &RecordId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<CommentView>, Error> {
+        let comments: Vec<CommentView> = DB
+            .query(
+                "SELECT <string> id AS id, body, <string> author AS author_id,
+                        author.username AS author_username, author.name AS author_name,
+                        author.profile.avatar AS author_avatar, created_at, updated_at
+                 FROM comment WHERE target = $target
+                 ORDER BY created_at ASC LIMIT $limit START $offset",
+            )
+            .bind(("target", target.clone()))
+            .bind(("limit", limit as i64))
+            .bind(("offset", offset as i64))
+            .await?
+            .take(0)?;
+
+        Ok(comments)
+    }
+
+    /// Count a target's comments (`GROUP ALL` so the aggregate is one row).
+    pub async fn count_for_target(target: &RecordId) -> Result<u64, Error> {
+        let result: Option<CountResult> = DB
+            .query("SELECT count() AS count FROM comment WHERE target = $target GROUP ALL")
+            .bind(("target", target.clone()))
+            .await?
+            .take(0)?;
+
+        Ok(result.map(|r| r.count).unwrap_or(0))
+    }
+
+    /// Replace a comment's body and stamp `updated_at`. Authorization is
+    /// the caller's job (only the author may edit).
+    pub async fn update_body(id: &RecordId, body: &str) -> Result<Comment, Error> {
+        let body = Self::normalize_body(body)?;
+        debug!("Editing comment {}", id.display());
+
+        let comment: Option<Comment> = DB
+            .query("UPDATE ONLY $id SET body = $body, updated_at = time::now() RETURN AFTER")
+            .bind(("id", id.clone()))
+            .bind(("body", body))
+            .await?
+            .take(0)?;
+
+        comment.ok_or(Error::NotFound)
+    }
+
+    /// Delete one comment along with the likes it received.
+    pub async fn delete(id: &RecordId) -> Result<(), Error> {
+        debug!("Deleting comment {}", id.display());
+
+        DB.query("DELETE likes WHERE out = $id; DELETE $id")
+            .bind(("id", id.clone()))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -5,13 +5,18 @@
 //! bundling into kits, and check-out/check-in state. Called from
 //! `routes::equipment`.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use surrealdb::types::{RecordId, SurrealValue};
 use tracing::{debug, error};
 use uuid::Uuid;
 
-use crate::{db::DB, error::Error};
+use crate::{
+    db::DB,
+    error::Error,
+    pagination::{Page, PageQuery},
+    record_id_ext::{RecordIdExt, normalize_record_id},
+};
 
 // ============================
 // Data Structures
@@ -31,6 +36,57 @@ pub struct EquipmentCondition {
     pub description: Option<String>,
 }
 
+/// Allowlisted orderings for equipment listings. The `ORDER BY` clause
+/// comes from here, never from the request, so `?sort=` can't inject SQL.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EquipmentSort {
+    #[default]
+    CreatedAt,
+    Name,
+    Availability,
+    PurchasePrice,
+}
+
+impl EquipmentSort {
+    pub const ALL: [Self; 4] = [
+        Self::CreatedAt,
+        Self::Name,
+        Self::Availability,
+        Self::PurchasePrice,
+    ];
+
+    /// The `?sort=` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::Name => "name",
+            Self::Availability => "availability",
+            Self::PurchasePrice => "purchase_price",
+        }
+    }
+
+    fn order_by(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at DESC",
+            Self::Name => "name ASC",
+            Self::Availability => "is_available DESC, name ASC",
+            Self::PurchasePrice => "purchase_price DESC, name ASC",
+        }
+    }
+}
+
+/// `Error::BadRequest` for anything outside the allowlist.
+impl std::str::FromStr for EquipmentSort {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.as_str() == s)
+            .ok_or_else(|| Error::BadRequest(format!("Unknown equipment sort: {s}")))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
 pub struct Equipment {
     pub id: RecordId,
@@ -42,6 +98,10 @@ pub struct Equipment {
     pub description: Option<String>,
     pub purchase_date: Option<DateTime<Utc>>,
     pub purchase_price: Option<f64>,
+    /// ISO 4217 code for `purchase_price`; unset only on rows that predate
+    /// migration 019.
+    #[serde(default)]
+    pub purchase_currency: Option<String>,
     pub condition: EquipmentCondition,
     pub notes: Option<String>,
     pub qr_code: Option<String>,
@@ -56,6 +116,19 @@ pub struct Equipment {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Equipment {
+    /// `purchase_price` formatted in its currency (the configured default
+    /// for rows that predate per-item currencies).
+    pub fn formatted_purchase_price(&self) -> Option<String> {
+        let currency = self
+            .purchase_currency
+            .as_deref()
+            .unwrap_or_else(|| crate::config::default_currency());
+        self.purchase_price
+            .map(|price| crate::currency::format_amount(price, currency))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
 pub struct EquipmentKit {
     pub id: RecordId,
@@ -94,6 +167,19 @@ pub struct EquipmentRental {
     pub updated_at: DateTime<Utc>,
 }
 
+impl EquipmentRental {
+    /// Whether this rental has the gear out on `date`. Only open rentals
+    /// count; one with no expected return date is treated as out
+    /// indefinitely from checkout.
+    pub fn covers_date(&self, date: NaiveDate) -> bool {
+        self.is_active
+            && self.checkout_date.date_naive() <= date
+            && self
+                .expected_return_date
+                .is_none_or(|due| date <= due.date_naive())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EquipmentWithKit {
     pub equipment: Equipment,
@@ -110,6 +196,8 @@ pub struct CreateEquipmentData {
     pub description: Option<String>,
     pub purchase_date: Option<DateTime<Utc>>,
     pub purchase_price: Option<f64>,
+    /// Blank → the configured default currency.
+    pub purchase_currency: Option<String>,
     pub condition: String,
     pub notes: Option<String>,
     pub owner_type: String,
@@ -130,6 +218,8 @@ pub struct UpdateEquipmentData {
     pub description: Option<String>,
     pub purchase_date: Option<DateTime<Utc>>,
     pub purchase_price: Option<f64>,
+    /// Blank → the configured default currency.
+    pub purchase_currency: Option<String>,
     pub condition: String,
     pub notes: Option<String>,
     pub current_location: Option<String>,
@@ -167,6 +257,220 @@ pub struct CheckoutData {
     pub condition: String,
     pub notes: Option<String>,
     pub checkout_by: String,
+    /// Check a kit out even when some of its items are unavailable. Without
+    /// this, an incomplete kit is rejected with the missing items listed.
+    pub allow_partial: bool,
+}
+
+/// Items sharing one serial number under the same owner.
+#[derive(Debug, Clone)]
+pub struct SerialDuplicate {
+    pub serial_number: String,
+    pub items: Vec<Equipment>,
+}
+
+/// Group `equipment` (one owner's items) by serial number, keeping only
+/// serials used more than once, ordered by serial. Blank serials are
+/// ignored.
+pub fn serial_duplicates(equipment: &[Equipment]) -> Vec<SerialDuplicate> {
+    let mut by_serial: std::collections::BTreeMap<&str, Vec<Equipment>> = Default::default();
+    for item in equipment {
+        if let Some(serial) = item.serial_number.as_deref().map(str::trim)
+            && !serial.is_empty()
+        {
+            by_serial.entry(serial).or_default().push(item.clone());
+        }
+    }
+    by_serial
+        .into_iter()
+        .filter(|(_, items)| items.len() > 1)
+        .map(|(serial, items)| SerialDuplicate {
+            serial_number: serial.to_string(),
+            items,
+        })
+        .collect()
+}
+
+/// Outcome of [`EquipmentModel::lookup`].
+#[derive(Debug, Clone)]
+pub enum EquipmentLookup {
+    Item(Box<Equipment>),
+    Kit(Box<EquipmentKit>),
+    /// Several items matched; the caller should let the user pick.
+    Candidates(Vec<Equipment>),
+    NotFound,
+}
+
+/// A kit's items split by whether they can go out with it right now.
+#[derive(Debug, Clone, Default)]
+pub struct KitCompleteness {
+    pub available: Vec<Equipment>,
+    pub unavailable: Vec<Equipment>,
+}
+
+impl KitCompleteness {
+    pub fn from_items(items: Vec<Equipment>) -> Self {
+        let (available, unavailable) = items.into_iter().partition(|item| item.is_available);
+        Self {
+            available,
+            unavailable,
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.unavailable.is_empty()
+    }
+
+    /// Names of the unavailable items, comma-separated, for messages and
+    /// rental notes.
+    pub fn unavailable_names(&self) -> String {
+        self.unavailable
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Condition names (lower-cased) that put an item in the maintenance
+/// bucket of [`OwnerDashboard`]: the seeded "needs repair" conditions.
+pub const MAINTENANCE_CONDITIONS: [&str; 3] = ["poor", "damaged", "broken"];
+
+/// How many days ahead [`OwnerDashboard::upcoming_returns`] looks.
+pub const UPCOMING_RETURN_DAYS: i64 = 7;
+
+/// One-glance status of an owner's gear, from
+/// [`EquipmentModel::owner_dashboard`]. The three status counts partition
+/// the owner's items: checked out is `rented`, otherwise a
+/// [`MAINTENANCE_CONDITIONS`] condition is `maintenance`, else `available`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OwnerDashboard {
+    pub available: u64,
+    pub rented: u64,
+    pub maintenance: u64,
+    /// Active rentals past their expected return date.
+    pub overdue_rentals: u64,
+    /// Active rentals due back within [`UPCOMING_RETURN_DAYS`], soonest
+    /// first.
+    pub upcoming_returns: Vec<UpcomingReturn>,
+}
+
+impl OwnerDashboard {
+    pub fn total(&self) -> u64 {
+        self.available + self.rented + self.maintenance
+    }
+}
+
+/// An active rental due back soon.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct UpcomingReturn {
+    pub rental_id: RecordId,
+    /// The rented item's or kit's name.
+    pub item_name: String,
+    pub expected_return_date: DateTime<Utc>,
+}
+
+/// Returns within this long after the expected return date still count
+/// as on time; expected dates are stored as midnight of the chosen day.
+pub const RETURN_GRACE_HOURS: i64 = 24;
+
+/// The dates of one rental that [`RenterHistory`] is computed from.
+#[derive(Debug, Clone, Deserialize, SurrealValue)]
+pub struct RentalTiming {
+    pub expected_return_date: Option<DateTime<Utc>>,
+    pub actual_return_date: Option<DateTime<Utc>>,
+    pub is_active: bool,
+}
+
+/// How a renter has returned gear across every owner, from
+/// [`EquipmentModel::renter_history`]. Only rentals with an expected
+/// return date count towards on time / late.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RenterHistory {
+    pub total_rentals: u64,
+    pub on_time_returns: u64,
+    pub late_returns: u64,
+    /// Active rentals already past their expected return date.
+    pub currently_overdue: u64,
+    /// Mean hours past the expected date over late returns.
+    pub average_lateness_hours: Option<f64>,
+}
+
+/// Coarse reading of a [`RenterHistory`] for the checkout page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reliability {
+    /// No returns with a due date yet.
+    New,
+    Reliable,
+    Mixed,
+    Unreliable,
+}
+
+impl Reliability {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Reliable => "reliable",
+            Self::Mixed => "mixed",
+            Self::Unreliable => "unreliable",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::New => "No history yet",
+            Self::Reliable => "Returns on time",
+            Self::Mixed => "Sometimes late",
+            Self::Unreliable => "Often late",
+        }
+    }
+}
+
+impl RenterHistory {
+    /// Tally `rentals` as of `now`.
+    pub fn from_rentals(rentals: &[RentalTiming], now: DateTime<Utc>) -> Self {
+        let grace = chrono::Duration::hours(RETURN_GRACE_HOURS);
+        let mut history = Self {
+            total_rentals: rentals.len() as u64,
+            ..Self::default()
+        };
+        let mut lateness_hours = 0.0;
+        for rental in rentals {
+            let Some(due) = rental.expected_return_date else {
+                continue;
+            };
+            match rental.actual_return_date {
+                Some(returned) if returned > due + grace => {
+                    history.late_returns += 1;
+                    lateness_hours += (returned - due).num_minutes() as f64 / 60.0;
+                }
+                Some(_) => history.on_time_returns += 1,
+                None if rental.is_active && now > due + grace => history.currently_overdue += 1,
+                None => {}
+            }
+        }
+        if history.late_returns > 0 {
+            history.average_lateness_hours = Some(lateness_hours / history.late_returns as f64);
+        }
+        history
+    }
+
+    /// Share of due-dated rentals returned on time, counting ones still
+    /// overdue as late: 90% or better is reliable, 60% or better mixed.
+    pub fn reliability(&self) -> Reliability {
+        let judged = self.on_time_returns + self.late_returns + self.currently_overdue;
+        if judged == 0 {
+            return Reliability::New;
+        }
+        let on_time = self.on_time_returns as f64 / judged as f64;
+        if on_time >= 0.9 {
+            Reliability::Reliable
+        } else if on_time >= 0.6 {
+            Reliability::Mixed
+        } else {
+            Reliability::Unreliable
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -188,6 +492,22 @@ impl EquipmentModel {
     pub async fn create_equipment(data: CreateEquipmentData) -> Result<Equipment, Error> {
         debug!("Creating new equipment: {:?}", data);
 
+        let purchase_currency =
+            crate::currency::resolve(data.purchase_currency.as_deref(), "purchase_currency")?;
+        if let Some(owner_id) = data
+            .owner_person
+            .as_deref()
+            .or(data.owner_organization.as_deref())
+        {
+            ensure_serial_unique(
+                &data.owner_type,
+                owner_id,
+                data.serial_number.as_deref(),
+                None,
+            )
+            .await?;
+        }
+
         // Generate QR code identifier
         let qr_code = format!("EQ-{}", Uuid::new_v4());
 
@@ -201,6 +521,7 @@ impl EquipmentModel {
                 description: $description,
                 purchase_date: IF $purchase_date THEN <datetime>$purchase_date ELSE NONE END,
                 purchase_price: $purchase_price,
+                purchase_currency: $purchase_currency,
                 condition: type::record('equipment_condition', $condition),
                 notes: $notes,
                 qr_code: $qr_code,
@@ -229,6 +550,7 @@ impl EquipmentModel {
                 data.purchase_date.map(|dt| dt.to_rfc3339()),
             ))
             .bind(("purchase_price", data.purchase_price))
+            .bind(("purchase_currency", purchase_currency))
             .bind(("condition", data.condition.clone()))
             .bind(("notes", data.notes.clone()))
             .bind(("qr_code", qr_code.clone()))
@@ -279,6 +601,25 @@ impl EquipmentModel {
     pub async fn update_equipment(id: &str, data: UpdateEquipmentData) -> Result<Equipment, Error> {
         debug!("Updating equipment {}: {:?}", id, data);
 
+        let purchase_currency =
+            crate::currency::resolve(data.purchase_currency.as_deref(), "purchase_currency")?;
+        if crate::config::unique_equipment_serials() {
+            let existing = Self::get_equipment(id).await?;
+            if let Some(owner) = existing
+                .owner_person
+                .as_ref()
+                .or(existing.owner_organization.as_ref())
+            {
+                ensure_serial_unique(
+                    &existing.owner_type,
+                    &owner.key_string(),
+                    data.serial_number.as_deref(),
+                    Some(&existing.id),
+                )
+                .await?;
+            }
+        }
+
         let query = r#"
             UPDATE type::record('equipment', $id) SET
                 name = $name,
@@ -289,6 +630,7 @@ impl EquipmentModel {
                 description = $description,
                 purchase_date = IF $purchase_date THEN <datetime>$purchase_date ELSE NONE END,
                 purchase_price = $purchase_price,
+                purchase_currency = $purchase_currency,
                 condition = type::record('equipment_condition', $condition),
                 notes = $notes,
                 current_location = $current_location,
@@ -310,6 +652,7 @@ impl EquipmentModel {
                 data.purchase_date.map(|dt| dt.to_rfc3339()),
             ))
             .bind(("purchase_price", data.purchase_price))
+            .bind(("purchase_currency", purchase_currency))
             .bind(("condition", data.condition.clone()))
             .bind(("notes", data.notes.clone()))
             .bind(("current_location", data.current_location.clone()))
@@ -353,27 +696,35 @@ impl EquipmentModel {
         Ok(())
     }
 
+    /// Give items saved before migration 019 the configured default
+    /// currency. Returns how many rows were updated.
+    pub async fn backfill_purchase_currency() -> Result<usize, Error> {
+        let updated: Vec<RecordId> = DB
+            .query(
+                "UPDATE equipment SET purchase_currency = $currency
+                 WHERE purchase_currency IS NONE RETURN VALUE id",
+            )
+            .bind(("currency", crate::config::default_currency().to_string()))
+            .await?
+            .take(0)?;
+        Ok(updated.len())
+    }
+
     pub async fn list_equipment_for_owner(
         owner_type: &str,
         owner_id: &str,
+        sort: EquipmentSort,
     ) -> Result<Vec<Equipment>, Error> {
-        debug!("Listing equipment for {} owner: {}", owner_type, owner_id);
+        debug!(
+            "Listing equipment for {} owner: {} (sort: {:?})",
+            owner_type, owner_id, sort
+        );
 
-        let query = if owner_type == "person" {
-            r#"
-                SELECT * FROM equipment
-                WHERE owner_person = type::record('person', $owner_id)
-                ORDER BY created_at DESC
-                FETCH category, condition, parent_kit;
-            "#
-        } else {
-            r#"
-                SELECT * FROM equipment
-                WHERE owner_organization = type::record('organization', $owner_id)
-                ORDER BY created_at DESC
-                FETCH category, condition, parent_kit;
-            "#
-        };
+        let query = format!(
+            "SELECT * FROM equipment WHERE {} ORDER BY {} FETCH category, condition, parent_kit;",
+            equipment_owner_condition(owner_type),
+            sort.order_by()
+        );
 
         let mut result = DB
             .query(query)
@@ -392,6 +743,201 @@ impl EquipmentModel {
         Ok(equipment)
     }
 
+    /// Items `person_id` can book: their own, and those of organizations
+    /// they're an accepted member of. By name.
+    pub async fn list_for_member(person_id: &str) -> Result<Vec<Equipment>, Error> {
+        debug!("Listing equipment available to member: {}", person_id);
+
+        let query = r#"
+            LET $person = type::record('person', $person_id);
+            LET $orgs = (SELECT VALUE out FROM member_of
+                WHERE in = $person AND invitation_status = 'accepted');
+            SELECT * FROM equipment
+            WHERE owner_person = $person OR owner_organization INSIDE $orgs
+            ORDER BY name ASC
+            FETCH category, condition, parent_kit;
+        "#;
+
+        let mut result = DB
+            .query(query)
+            .bind(("person_id", person_id.to_string()))
+            .await
+            .map_err(|e| {
+                error!("Failed to list member equipment: {:?}", e);
+                Error::Database(e.to_string())
+            })?;
+
+        let equipment: Vec<Equipment> = result.take(2).map_err(|e| {
+            error!("Failed to parse member equipment: {:?}", e);
+            Error::Database(e.to_string())
+        })?;
+
+        Ok(equipment)
+    }
+
+    /// The owner's items whose serial number is `serial` (trimmed), oldest
+    /// first. Empty for a blank serial.
+    pub async fn find_by_serial(
+        owner_type: &str,
+        owner_id: &str,
+        serial: &str,
+    ) -> Result<Vec<Equipment>, Error> {
+        let serial = serial.trim();
+        if serial.is_empty() {
+            return Ok(Vec::new());
+        }
+        debug!(
+            "Finding {} owner {}'s equipment with serial {}",
+            owner_type, owner_id, serial
+        );
+
+        let query = format!(
+            "SELECT * FROM equipment WHERE {} AND serial_number = $serial \
+             ORDER BY created_at FETCH category, condition;",
+            equipment_owner_condition(owner_type),
+        );
+
+        let mut result = DB
+            .query(query)
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("serial", serial.to_string()))
+            .await
+            .map_err(|e| {
+                error!("Failed to find equipment by serial: {:?}", e);
+                Error::Database(e.to_string())
+            })?;
+
+        let equipment: Vec<Equipment> = result.take(0).map_err(|e| {
+            error!("Failed to parse equipment by serial: {:?}", e);
+            Error::Database(e.to_string())
+        })?;
+
+        Ok(equipment)
+    }
+
+    /// One page of an owner's equipment in `sort` order.
+    pub async fn page_equipment_for_owner(
+        owner_type: &str,
+        owner_id: &str,
+        sort: EquipmentSort,
+        page: PageQuery,
+    ) -> Result<Page<Equipment>, Error> {
+        debug!(
+            "Paging equipment for {} owner: {} ({:?}, {:?})",
+            owner_type, owner_id, sort, page
+        );
+
+        let condition = equipment_owner_condition(owner_type);
+        let order = sort.order_by();
+        let mut result = DB
+            .query(format!(
+                "SELECT * FROM equipment WHERE {condition} ORDER BY {order} LIMIT $limit START $offset FETCH category, condition, parent_kit"
+            ))
+            .query(format!(
+                "SELECT VALUE count() FROM equipment WHERE {condition} GROUP ALL"
+            ))
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("limit", page.limit()))
+            .bind(("offset", page.offset()))
+            .await
+            .map_err(|e| {
+                error!("Failed to page equipment: {:?}", e);
+                Error::Database(e.to_string())
+            })?;
+
+        let items: Vec<Equipment> = result.take(0)?;
+        let total: Option<u64> = result.take(1)?;
+        Ok(Page::new(items, total.unwrap_or(0), page))
+    }
+
+    /// Status counts, overdue rentals, and returns due in the next
+    /// [`UPCOMING_RETURN_DAYS`] for one owner, in a single query batch.
+    pub async fn owner_dashboard(
+        owner_type: &str,
+        owner_id: &str,
+    ) -> Result<OwnerDashboard, Error> {
+        debug!(
+            "Building equipment dashboard for {} owner: {}",
+            owner_type, owner_id
+        );
+
+        let owned = equipment_owner_condition(owner_type);
+        let rented = rental_owner_condition(owner_type);
+        let repair = "string::lowercase(condition.name)";
+        let mut result = DB
+            .query(format!(
+                "SELECT VALUE count() FROM equipment WHERE {owned} AND is_available = true AND {repair} NOTINSIDE $maintenance GROUP ALL"
+            ))
+            .query(format!(
+                "SELECT VALUE count() FROM equipment WHERE {owned} AND is_available = false GROUP ALL"
+            ))
+            .query(format!(
+                "SELECT VALUE count() FROM equipment WHERE {owned} AND is_available = true AND {repair} INSIDE $maintenance GROUP ALL"
+            ))
+            .query(format!(
+                "SELECT VALUE count() FROM equipment_rental WHERE is_active = true AND {rented} AND expected_return_date < time::now() GROUP ALL"
+            ))
+            .query(format!(
+                "SELECT id AS rental_id, (equipment_id.name ?? kit_id.name) AS item_name, expected_return_date \
+                 FROM equipment_rental WHERE is_active = true AND {rented} \
+                 AND expected_return_date >= time::now() AND expected_return_date <= time::now() + duration::from::days($days) \
+                 ORDER BY expected_return_date"
+            ))
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("maintenance", MAINTENANCE_CONDITIONS.to_vec()))
+            .bind(("days", UPCOMING_RETURN_DAYS))
+            .await
+            .map_err(|e| {
+                error!("Failed to build equipment dashboard: {:?}", e);
+                Error::Database(e.to_string())
+            })?;
+
+        let available: Option<u64> = result.take(0)?;
+        let rented: Option<u64> = result.take(1)?;
+        let maintenance: Option<u64> = result.take(2)?;
+        let overdue_rentals: Option<u64> = result.take(3)?;
+        let upcoming_returns: Vec<UpcomingReturn> = result.take(4)?;
+        Ok(OwnerDashboard {
+            available: available.unwrap_or(0),
+            rented: rented.unwrap_or(0),
+            maintenance: maintenance.unwrap_or(0),
+            overdue_rentals: overdue_rentals.unwrap_or(0),
+            upcoming_returns,
+        })
+    }
+
+    /// Return record of a person or organization across all rentals on
+    /// the platform. Callers show it only to owners checking gear out.
+    pub async fn renter_history(
+        renter_type: &str,
+        renter_id: &str,
+    ) -> Result<RenterHistory, Error> {
+        debug!(
+            "Building rental history for {} renter: {}",
+            renter_type, renter_id
+        );
+
+        let (field, table) = if renter_type == "organization" {
+            ("renter_organization", "organization")
+        } else {
+            ("renter_person", "person")
+        };
+        let mut result = DB
+            .query(format!(
+                "SELECT expected_return_date, actual_return_date, is_active \
+                 FROM equipment_rental WHERE {field} = $renter"
+            ))
+            .bind(("renter", normalize_record_id(renter_id, table)))
+            .await
+            .map_err(|e| {
+                error!("Failed to load renter history: {:?}", e);
+                Error::Database(e.to_string())
+            })?;
+
+        let rentals: Vec<RentalTiming> = result.take(0)?;
+        Ok(RenterHistory::from_rentals(&rentals, Utc::now()))
+    }
+
     // Kit Operations
 
     pub async fn create_kit(data: CreateKitData) -> Result<EquipmentKit, Error> {
@@ -505,6 +1051,13 @@ impl EquipmentModel {
         Ok(items)
     }
 
+    /// Which of a kit's items are available to go out with it.
+    pub async fn kit_completeness(kit_id: &str) -> Result<KitCompleteness, Error> {
+        Ok(KitCompleteness::from_items(
+            Self::get_kit_items(kit_id).await?,
+        ))
+    }
+
     pub async fn update_kit(id: &str, data: UpdateKitData) -> Result<EquipmentKit, Error> {
         debug!("Updating kit {}: {:?}", id, data);
 
@@ -640,7 +1193,7 @@ impl EquipmentModel {
 
     // Rental Operations
 
-    pub async fn checkout_equipment(data: CheckoutData) -> Result<EquipmentRental, Error> {
+    pub async fn checkout_equipment(mut data: CheckoutData) -> Result<EquipmentRental, Error> {
         debug!("Checking out equipment: {:?}", data);
 
         // Verify equipment or kit is available
@@ -660,6 +1213,23 @@ impl EquipmentModel {
                     "Kit is not available for checkout".to_string(),
                 ));
             }
+
+            // Items out on their own rentals can't go with the kit; make the
+            // caller confirm a partial checkout and record what was missing.
+            let completeness = Self::kit_completeness(kit_id).await?;
+            if !completeness.is_complete() {
+                let missing = completeness.unavailable_names();
+                if !data.allow_partial {
+                    return Err(Error::Validation(format!(
+                        "Kit is incomplete; unavailable items: {missing}"
+                    )));
+                }
+                let note = format!("Checked out without: {missing}");
+                data.notes = Some(match data.notes.take() {
+                    Some(notes) if !notes.trim().is_empty() => format!("{notes}\n{note}"),
+                    _ => note,
+                });
+            }
         }
 
         let query = r#"
@@ -738,71 +1308,43 @@ impl EquipmentModel {
         rental.ok_or(Error::NotFound)
     }
 
+    /// Check in one rental as `data.return_by`, who must own its equipment
+    /// or kit (or belong to the owning organization).
+    ///
+    /// # Errors
+    /// `Error::NotFound` for an unknown rental, `Error::Unauthorized` for
+    /// someone else's, `Error::Validation` if it was already returned.
     pub async fn checkin_equipment(
         rental_id: &str,
         data: CheckinData,
     ) -> Result<EquipmentRental, Error> {
         debug!("Checking in rental {}: {:?}", rental_id, data);
 
-        let query = r#"
-            BEGIN TRANSACTION;
-
-            -- Get the rental
-            LET $rental = SELECT * FROM type::record('equipment_rental', $rental_id);
-
-            -- Update rental record
-            LET $updated_rental = UPDATE type::record('equipment_rental', $rental_id) SET
-                actual_return_date = time::now(),
-                return_condition = type::record('equipment_condition', $return_condition),
-                return_notes = $return_notes,
-                return_by = type::record('person', $return_by),
-                is_active = false,
-                updated_at = time::now();
-
-            -- Update equipment availability
-            IF $rental.equipment_id THEN
-                UPDATE $rental.equipment_id SET
-                    is_available = true,
-                    updated_at = time::now()
-            END;
-
-            -- Update kit availability (and all its items)
-            IF $rental.kit_id THEN {
-                UPDATE $rental.kit_id SET
-                    is_available = true,
-                    updated_at = time::now();
-
-                UPDATE equipment SET
-                    is_available = true,
-                    updated_at = time::now()
-                WHERE parent_kit = $rental.kit_id;
-            } END;
-
-            RETURN $updated_rental FETCH checkout_condition, return_condition;
-
-            COMMIT TRANSACTION;
-        "#;
-
-        let mut result = DB
-            .query(query)
-            .bind(("rental_id", rental_id.to_string()))
-            .bind(("return_condition", data.return_condition.clone()))
-            .bind(("return_notes", data.return_notes.clone()))
-            .bind(("return_by", data.return_by.clone()))
-            .await
-            .map_err(|e| {
-                error!("Failed to checkin equipment: {:?}", e);
-                Error::Database(e.to_string())
-            })?;
+        let rental_id = normalize_record_id(rental_id, "equipment_rental");
+        crate::db::transaction(async |tx| checkin_rental(tx, &rental_id, &data).await).await
+    }
 
-        let rental: Option<EquipmentRental> = result.take("updated_rental").map_err(|e| {
-            error!("Failed to parse rental: {:?}", e);
-            Error::Database(e.to_string())
-        })?;
+    /// Check in every rental in `rental_ids` with the same return condition
+    /// and notes, in one transaction: either all are returned or none are.
+    /// Each rental is re-checked inside the transaction, with the same
+    /// rules and errors as [`Self::checkin_equipment`].
+    pub async fn checkin_rentals(rental_ids: &[RecordId], data: &CheckinData) -> Result<(), Error> {
+        debug!("Bulk checking in {} rentals: {:?}", rental_ids.len(), data);
 
-        rental.ok_or(Error::NotFound)
+        crate::db::transaction(async |tx| {
+            for rental_id in rental_ids {
+                checkin_rental(tx, rental_id, data).await?;
+            }
+            Ok(())
+        })
+        .await
     }
 
+    /// Open rentals of one item, newest first. Runs as the guard on every
+    /// checkout and delete, so it must stay an index lookup: the id is bound
+    /// as a record (not built with `type::record` in the query) so both
+    /// conditions are plain equalities the planner can serve from
+    /// `idx_rental_equipment_active` (`equipment_id`, `is_active`).
     pub async fn get_active_rentals_for_equipment(
         equipment_id: &str,
     ) -> Result<Vec<EquipmentRental>, Error> {
@@ -810,7 +1352,7 @@ impl EquipmentModel {
 
         let query = r#"
             SELECT * FROM equipment_rental
-            WHERE equipment_id = type::record('equipment', $equipment_id)
+            WHERE equipment_id = $equipment
             AND is_active = true
             ORDER BY checkout_date DESC
             FETCH checkout_condition, return_condition;
@@ -818,7 +1360,7 @@ impl EquipmentModel {
 
         let mut result = DB
             .query(query)
-            .bind(("equipment_id", equipment_id.to_string()))
+            .bind(("equipment", RecordId::new("equipment", equipment_id)))
             .await
             .map_err(|e| {
                 error!("Failed to get rentals: {:?}", e);
@@ -833,12 +1375,15 @@ impl EquipmentModel {
         Ok(rentals)
     }
 
+    /// Open rentals of one kit, newest first. The kit counterpart of
+    /// [`Self::get_active_rentals_for_equipment`], served by
+    /// `idx_rental_kit_active` (`kit_id`, `is_active`).
     pub async fn get_active_rentals_for_kit(kit_id: &str) -> Result<Vec<EquipmentRental>, Error> {
         debug!("Getting active rentals for kit: {}", kit_id);
 
         let query = r#"
             SELECT * FROM equipment_rental
-            WHERE kit_id = type::record('equipment_kit', $kit_id)
+            WHERE kit_id = $kit
             AND is_active = true
             ORDER BY checkout_date DESC
             FETCH checkout_condition, return_condition;
@@ -846,7 +1391,7 @@ impl EquipmentModel {
 
         let mut result = DB
             .query(query)
-            .bind(("kit_id", kit_id.to_string()))
+            .bind(("kit", RecordId::new("equipment_kit", kit_id)))
             .await
             .map_err(|e| {
                 error!("Failed to get rentals: {:?}", e);
@@ -861,6 +1406,18 @@ impl EquipmentModel {
         Ok(rentals)
     }
 
+    /// Whether the item is free on `date` — a shoot day, typically. Blocked
+    /// by an open rental of the item itself or of the kit it belongs to
+    /// whose checkout..expected-return window includes the date.
+    pub async fn available_on(equipment_id: &str, date: NaiveDate) -> Result<bool, Error> {
+        let equipment = Self::get_equipment(equipment_id).await?;
+        let mut rentals = Self::get_active_rentals_for_equipment(equipment_id).await?;
+        if let Some(kit) = &equipment.parent_kit {
+            rentals.extend(Self::get_active_rentals_for_kit(&kit.key_string()).await?);
+        }
+        Ok(!rentals.iter().any(|rental| rental.covers_date(date)))
+    }
+
     // Helper Methods
 
     pub async fn get_all_categories() -> Result<Vec<EquipmentCategory>, Error> {
@@ -1035,4 +1592,232 @@ impl EquipmentModel {
 
         kit.ok_or(Error::NotFound)
     }
+
+    /// The owner's items whose name contains `term`, case-insensitively,
+    /// by name. Capped at [`NAME_SEARCH_LIMIT`].
+    pub async fn search_by_name(
+        owner_type: &str,
+        owner_id: &str,
+        term: &str,
+    ) -> Result<Vec<Equipment>, Error> {
+        debug!(
+            "Searching {} owner {}'s equipment for {:?}",
+            owner_type, owner_id, term
+        );
+
+        let query = format!(
+            "SELECT * FROM equipment WHERE {} \
+             AND string::lowercase(name) CONTAINS string::lowercase($term) \
+             ORDER BY name LIMIT {NAME_SEARCH_LIMIT} FETCH category, condition;",
+            equipment_owner_condition(owner_type),
+        );
+
+        let mut result = DB
+            .query(query)
+            .bind(("owner_id", owner_id.to_string()))
+            .bind(("term", term.to_string()))
+            .await
+            .map_err(|e| {
+                error!("Failed to search equipment: {:?}", e);
+                Error::Database(e.to_string())
+            })?;
+
+        let equipment: Vec<Equipment> = result.take(0).map_err(|e| {
+            error!("Failed to parse equipment search: {:?}", e);
+            Error::Database(e.to_string())
+        })?;
+
+        Ok(equipment)
+    }
+
+    /// Resolve a scanned or typed `q`, trying in order: an exact equipment
+    /// or kit QR code (any owner, as detail pages are public), then the
+    /// owner's serial numbers, then the owner's item names. The first stage
+    /// with any match decides the outcome.
+    pub async fn lookup(
+        owner_type: &str,
+        owner_id: &str,
+        q: &str,
+    ) -> Result<EquipmentLookup, Error> {
+        let q = q.trim();
+        if q.is_empty() {
+            return Ok(EquipmentLookup::NotFound);
+        }
+
+        match Self::get_equipment_by_qr(q).await {
+            Ok(item) => return Ok(EquipmentLookup::Item(Box::new(item))),
+            Err(Error::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+        match Self::get_kit_by_qr(q).await {
+            Ok(kit) => return Ok(EquipmentLookup::Kit(Box::new(kit))),
+            Err(Error::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+
+        let by_serial = Self::find_by_serial(owner_type, owner_id, q).await?;
+        let mut matches = if by_serial.is_empty() {
+            Self::search_by_name(owner_type, owner_id, q).await?
+        } else {
+            by_serial
+        };
+
+        Ok(match matches.len() {
+            0 => EquipmentLookup::NotFound,
+            1 => EquipmentLookup::Item(Box::new(matches.remove(0))),
+            _ => EquipmentLookup::Candidates(matches),
+        })
+    }
+}
+
+/// Most items [`EquipmentModel::search_by_name`] returns.
+pub const NAME_SEARCH_LIMIT: usize = 50;
+
+/// Whether `$rental_id` exists, and whether `$return_by` owns its
+/// equipment or kit: the owning person, or any member of the owning
+/// organization (the same rule as the equipment routes).
+const CHECKIN_GUARD: &str = r#"
+    LET $item = $rental_id.equipment_id ?? $rental_id.kit_id;
+    SELECT
+        $item.owner_person = $return_by
+            OR $return_by INSIDE (SELECT VALUE in FROM member_of
+                WHERE out = $item.owner_organization) AS may_return
+    FROM ONLY $rental_id;
+"#;
+
+/// Return one rental and free its equipment or kit (and the kit's items).
+/// The `is_active` guard on the rental update claims it, so of two
+/// concurrent check-ins only one frees anything; the other returns `NONE`.
+const CHECKIN_RENTAL: &str = r#"
+    LET $rental = (UPDATE $rental_id SET
+        actual_return_date = time::now(),
+        return_condition = type::record('equipment_condition', $return_condition),
+        return_notes = $return_notes,
+        return_by = $return_by,
+        is_active = false,
+        updated_at = time::now()
+    WHERE is_active = true RETURN BEFORE)[0];
+
+    IF $rental.equipment_id THEN
+        UPDATE $rental.equipment_id SET
+            is_available = true,
+            updated_at = time::now()
+    END;
+
+    IF $rental.kit_id THEN {
+        UPDATE $rental.kit_id SET
+            is_available = true,
+            updated_at = time::now();
+
+        -- Items still out on their own rentals stay unavailable
+        UPDATE equipment SET
+            is_available = true,
+            updated_at = time::now()
+        WHERE parent_kit = $rental.kit_id
+        AND id NOTINSIDE (SELECT VALUE equipment_id FROM equipment_rental
+            WHERE is_active = true AND equipment_id != NONE);
+    } END;
+
+    RETURN IF $rental THEN
+        (SELECT * FROM ONLY $rental_id FETCH checkout_condition, return_condition)
+    END;
+"#;
+
+/// Check in `rental_id` inside `tx`; see [`EquipmentModel::checkin_equipment`].
+async fn checkin_rental(
+    tx: &crate::db::Tx,
+    rental_id: &RecordId,
+    data: &CheckinData,
+) -> Result<EquipmentRental, Error> {
+    #[derive(Debug, Deserialize, SurrealValue)]
+    struct Guard {
+        may_return: bool,
+    }
+
+    let return_by = normalize_record_id(&data.return_by, "person");
+    let guard: Option<Guard> = tx
+        .query(CHECKIN_GUARD)
+        .bind(("rental_id", rental_id.clone()))
+        .bind(("return_by", return_by.clone()))
+        .await
+        .and_then(|mut r| r.take(1))
+        .map_err(|e| {
+            error!("Failed to load rental {}: {:?}", rental_id.display(), e);
+            Error::Database(e.to_string())
+        })?;
+    match guard {
+        None => return Err(Error::NotFound),
+        Some(guard) if !guard.may_return => return Err(Error::Unauthorized),
+        Some(_) => {}
+    }
+
+    let rental: Option<EquipmentRental> = tx
+        .query(CHECKIN_RENTAL)
+        .bind(("rental_id", rental_id.clone()))
+        .bind(("return_condition", data.return_condition.clone()))
+        .bind(("return_notes", data.return_notes.clone()))
+        .bind(("return_by", return_by))
+        .await
+        .and_then(|mut r| {
+            let last = r.num_statements() - 1;
+            r.take(last)
+        })
+        .map_err(|e| {
+            error!("Failed to check in rental {}: {:?}", rental_id.display(), e);
+            Error::Database(e.to_string())
+        })?;
+    rental.ok_or_else(|| Error::Validation("Rental has already been returned".to_string()))
+}
+
+/// With `EQUIPMENT_UNIQUE_SERIALS` on, reject a serial number the owner
+/// already uses on another item (`except` being the item under edit).
+/// Off by default, where duplicates are only warned about.
+///
+/// # Errors
+/// `Error::ValidationFields` on `serial_number` for a duplicate.
+async fn ensure_serial_unique(
+    owner_type: &str,
+    owner_id: &str,
+    serial: Option<&str>,
+    except: Option<&RecordId>,
+) -> Result<(), Error> {
+    let Some(serial) = serial else {
+        return Ok(());
+    };
+    if !crate::config::unique_equipment_serials() {
+        return Ok(());
+    }
+    let taken = EquipmentModel::find_by_serial(owner_type, owner_id, serial)
+        .await?
+        .into_iter()
+        .any(|item| Some(&item.id) != except);
+    if taken {
+        return Err(Error::invalid_field(
+            "serial_number",
+            "Another item you own already has this serial number",
+        ));
+    }
+    Ok(())
+}
+
+/// `WHERE` clause matching rows owned by `$owner_id`; anything other than
+/// `"person"` is treated as an organization owner.
+fn equipment_owner_condition(owner_type: &str) -> &'static str {
+    if owner_type == "person" {
+        "owner_person = type::record('person', $owner_id)"
+    } else {
+        "owner_organization = type::record('organization', $owner_id)"
+    }
+}
+
+/// [`equipment_owner_condition`] for `equipment_rental` rows, matching the
+/// owner of the rented item or kit.
+fn rental_owner_condition(owner_type: &str) -> &'static str {
+    if owner_type == "person" {
+        "(equipment_id.owner_person = type::record('person', $owner_id) \
+         OR kit_id.owner_person = type::record('person', $owner_id))"
+    } else {
+        "(equipment_id.owner_organization = type::record('organization', $owner_id) \
+         OR kit_id.owner_organization = type::record('organization', $owner_id))"
+    }
 }
@@ -81,14 +81,9 @@ pub struct InvolvementWithPerson {
 /// Query/mutation surface for `involvement` credit edges.
 pub struct InvolvementModel;
 
-/// Parse a "table:key" string into a RecordId
+/// Parse a "table:key" (or bare person key) string into a RecordId
 fn to_record_id(id: &str) -> RecordId {
-    if id.contains(':') {
-        let parts: Vec<&str> = id.splitn(2, ':').collect();
-        RecordId::new(parts[0], parts[1])
-    } else {
-        RecordId::new("person", id)
-    }
+    crate::record_id_ext::normalize_record_id(id, "person")
 }
 
 impl InvolvementModel {
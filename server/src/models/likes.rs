@@ -1,17 +1,29 @@
 //! Likes/favorites: the `likes` graph edge.
 //!
-//! Owns the `likes` RELATION (person -> person|location). Called by
-//! `routes/likes.rs` (toggle), `routes/search.rs` and
-//! `routes/public_profiles.rs` (liked-state badges), and
-//! `routes/locations.rs`. Reads traverse `person->likes->target` with the
-//! person id formatted into the query — bind params don't work in the graph
-//! traversal FROM position — so the id's table is validated first.
+//! Owns the `likes` RELATION (person -> person|location|production|comment|
+//! media). Called by `routes/likes.rs` (toggle), `routes/search.rs` and
+//! `routes/public_profiles.rs` (liked-state badges), `routes/locations.rs`,
+//! and `routes/productions.rs` (production + comment like counts). Reads
+//! traverse `person->likes->target` with the person id formatted into the
+//! query — bind params don't work in the graph traversal FROM position — so
+//! the id's table is validated first. Duplicate likes are impossible: the
+//! `in, out` UNIQUE index backs the existence check in [`LikesModel::like`].
 
 use crate::{db::DB, error::Error, record_id_ext::RecordIdExt};
 use serde::{Deserialize, Serialize};
-use surrealdb::types::RecordId;
+use std::collections::HashMap;
+use surrealdb::types::{RecordId, SurrealValue};
 use tracing::debug;
 
+/// Tables a person may like (mirrors the `likes` relation's `TO` clause).
+pub const LIKEABLE_TABLES: &[&str] = &["person", "location", "production", "comment", "media"];
+
+#[derive(Debug, Deserialize, SurrealValue)]
+struct TargetCount {
+    target: String,
+    count: u64,
+}
+
 /// Query/mutation surface for `likes` edges.
 pub struct LikesModel;
 
@@ -47,40 +59,98 @@ impl LikesModel {
         Ok(())
     }
 
-    /// Toggle a like. Returns true if now liked, false if unliked.
-    pub async fn toggle(person_id: &RecordId, target_id: &RecordId) -> Result<bool, Error> {
+    /// Reject targets outside [`LIKEABLE_TABLES`] before they reach the
+    /// relation (the schema would refuse them anyway, with a worse error).
+    pub fn validate_target_id(target_id: &RecordId) -> Result<(), Error> {
+        let table = target_id.table.to_string();
+        if !LIKEABLE_TABLES.contains(&table.as_str()) {
+            return Err(Error::BadRequest(format!("Cannot like a {} record", table)));
+        }
+        Ok(())
+    }
+
+    /// Like a target. Idempotent: liking twice leaves a single edge.
+    pub async fn like(person_id: &RecordId, target_id: &RecordId) -> Result<(), Error> {
+        Self::validate_target_id(target_id)?;
+        if Self::has_liked(person_id, target_id).await? {
+            return Ok(());
+        }
+        debug!("Liking: {} -> {}", person_id.display(), target_id.display());
+
+        let query = "RELATE $person_id -> likes -> $target_id SET created_at = time::now()";
+        DB.query(query)
+            .bind(("person_id", person_id.clone()))
+            .bind(("target_id", target_id.clone()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to create like: {}", e)))?;
+        Ok(())
+    }
+
+    /// Remove a like. A no-op if the person hadn't liked the target.
+    pub async fn unlike(person_id: &RecordId, target_id: &RecordId) -> Result<(), Error> {
         debug!(
-            "Toggling like: {} -> {}",
+            "Unliking: {} -> {}",
             person_id.display(),
             target_id.display()
         );
 
-        // Check if like already exists
-        let exists = Self::is_liked(person_id, target_id).await?;
-
-        if exists {
-            // Delete the like
-            let query = "DELETE likes WHERE in = $person_id AND out = $target_id";
-            DB.query(query)
-                .bind(("person_id", person_id.clone()))
-                .bind(("target_id", target_id.clone()))
-                .await
-                .map_err(|e| Error::Database(format!("Failed to delete like: {}", e)))?;
+        let query = "DELETE likes WHERE in = $person_id AND out = $target_id";
+        DB.query(query)
+            .bind(("person_id", person_id.clone()))
+            .bind(("target_id", target_id.clone()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to delete like: {}", e)))?;
+        Ok(())
+    }
+
+    /// Toggle a like. Returns true if now liked, false if unliked.
+    pub async fn toggle(person_id: &RecordId, target_id: &RecordId) -> Result<bool, Error> {
+        if Self::has_liked(person_id, target_id).await? {
+            Self::unlike(person_id, target_id).await?;
             Ok(false)
         } else {
-            // Create the like
-            let query = "RELATE $person_id -> likes -> $target_id SET created_at = time::now()";
-            DB.query(query)
-                .bind(("person_id", person_id.clone()))
-                .bind(("target_id", target_id.clone()))
-                .await
-                .map_err(|e| Error::Database(format!("Failed to create like: {}", e)))?;
+            Self::like(person_id, target_id).await?;
             Ok(true)
         }
     }
 
+    /// Number of likes a target has received.
+    pub async fn count(target_id: &RecordId) -> Result<u64, Error> {
+        let query = "SELECT count() AS count FROM likes WHERE out = $target_id GROUP ALL";
+        let mut result = DB
+            .query(query)
+            .bind(("target_id", target_id.clone()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to count likes: {}", e)))?;
+
+        let row: Option<serde_json::Value> = result.take(0)?;
+        Ok(row
+            .and_then(|v| v.get("count").and_then(|c| c.as_u64()))
+            .unwrap_or(0))
+    }
+
+    /// Like counts for many targets in one query, keyed by raw "table:key"
+    /// id. Targets with no likes are absent from the map.
+    pub async fn count_many(target_ids: &[RecordId]) -> Result<HashMap<String, u64>, Error> {
+        if target_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let query = "SELECT <string> out AS target, count() AS count FROM likes \
+                     WHERE out IN $target_ids GROUP BY target";
+        let rows: Vec<TargetCount> = DB
+            .query(query)
+            .bind(("target_ids", target_ids.to_vec()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to count likes: {}", e)))?
+            .take(0)
+            .unwrap_or_default();
+
+        Ok(rows.into_iter().map(|r| (r.target, r.count)).collect())
+    }
+
     /// Check if a person has liked a target
-    pub async fn is_liked(person_id: &RecordId, target_id: &RecordId) -> Result<bool, Error> {
+    pub async fn has_liked(person_id: &RecordId, target_id: &RecordId) -> Result<bool, Error> {
         let query = "SELECT count() AS count FROM likes WHERE in = $person_id AND out = $target_id";
         let mut result = DB
             .query(query)
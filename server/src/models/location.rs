@@ -1,15 +1,21 @@
 //! Filming-location records and their rental rates.
 //!
-//! Owns the `location` table (whose `created_by` may be a person or an
-//! organization) and the `location_rate` table. Called by
+//! Owns the `location` table and the `location_rate` table. A location is
+//! owned by a person or an organization ([`LocationOwner`], the same
+//! `owner_type` / `owner_person` / `owner_organization` shape as equipment);
+//! organization-owned locations are editable by the org's owners and admins. Called by
 //! `routes/locations.rs` for CRUD, browse, and search, and by
 //! `routes/media.rs` for photo management; `list()` powers the public browse
 //! page with optional keyword + vector-similarity scoring.
 
 use crate::db::DB;
 use crate::error::Error;
-use crate::record_id_ext::RecordIdExt;
+use crate::models::media::DELETE_TARGET_MEDIA;
+use crate::models::person::GeoLocation;
+use crate::pagination::{Page, PageQuery};
+use crate::record_id_ext::{RecordIdExt, normalize_record_id};
 use crate::services::embedding::build_location_embedding_text;
+use crate::services::geocoding;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use surrealdb::types::{RecordId, SurrealValue};
@@ -29,6 +35,9 @@ pub struct LocationPhoto {
 pub struct Location {
     pub id: RecordId,
     pub name: String,
+    /// Derived from `name`; unset on rows created before migration 018.
+    #[serde(default)]
+    pub slug: Option<String>,
     pub address: String,
     pub city: String,
     pub state: String,
@@ -48,7 +57,127 @@ pub struct Location {
     pub photos: Vec<LocationPhoto>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Who listed the location. Ownership is [`Location::owner`].
     pub created_by: RecordId,
+    /// "person" | "organization"; unset on rows created before migration 026.
+    #[serde(default)]
+    #[surreal(default)]
+    pub owner_type: String,
+    #[serde(default)]
+    #[surreal(default)]
+    pub owner_person: Option<RecordId>,
+    #[serde(default)]
+    #[surreal(default)]
+    pub owner_organization: Option<RecordId>,
+    /// Structured address ([`AddressComponents`]); `address`, `state` and
+    /// `country` stay the display strings. Unset on rows created before
+    /// migration 027 until the startup backfill parses them.
+    #[serde(default)]
+    #[surreal(default)]
+    pub street: Option<String>,
+    #[serde(default)]
+    #[surreal(default)]
+    pub unit: Option<String>,
+    /// ISO 3166-2 subdivision code without the country prefix ("CA").
+    #[serde(default)]
+    #[surreal(default)]
+    pub region_code: Option<String>,
+    /// ISO 3166-1 alpha-2, upper-case.
+    #[serde(default)]
+    #[surreal(default)]
+    pub country_code: Option<String>,
+    /// Geocoded from the address when a geocoder is configured.
+    #[serde(default)]
+    #[surreal(default)]
+    pub geo: Option<GeoLocation>,
+}
+
+/// Who owns a location and so decides who may edit it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocationOwner {
+    Person(RecordId),
+    Organization(RecordId),
+}
+
+impl Location {
+    /// The owner from the ownership fields, falling back to `created_by`
+    /// for rows migration 026 hasn't backfilled.
+    pub fn owner(&self) -> LocationOwner {
+        match (&self.owner_organization, &self.owner_person) {
+            (Some(org), _) if self.owner_type != "person" => {
+                LocationOwner::Organization(org.clone())
+            }
+            (_, Some(person)) => LocationOwner::Person(person.clone()),
+            _ if self.created_by.table.as_str() == "organization" => {
+                LocationOwner::Organization(self.created_by.clone())
+            }
+            _ => LocationOwner::Person(self.created_by.clone()),
+        }
+    }
+
+    /// Free-text geocoder query for this location's address.
+    pub fn geocode_query(&self) -> String {
+        geocode_query(
+            &self.address,
+            &self.components(),
+            &self.city,
+            &self.state,
+            self.postal_code.as_deref(),
+            &self.country,
+        )
+    }
+
+    fn components(&self) -> AddressComponents {
+        AddressComponents {
+            street: self.street.clone(),
+            unit: self.unit.clone(),
+            region_code: self.region_code.clone(),
+            country_code: self.country_code.clone(),
+        }
+    }
+}
+
+/// Allowlisted orderings for location listings; `?sort=` values outside
+/// this set are rejected rather than reaching the `ORDER BY` clause.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LocationSort {
+    #[default]
+    Recent,
+    Name,
+    City,
+}
+
+impl LocationSort {
+    pub const ALL: [Self; 3] = [Self::Recent, Self::Name, Self::City];
+
+    /// The `?sort=` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Recent => "recent",
+            Self::Name => "name",
+            Self::City => "city",
+        }
+    }
+
+    fn order_by(self) -> &'static str {
+        match self {
+            Self::Recent => "created_at DESC",
+            Self::Name => "name ASC",
+            Self::City => "city ASC, name ASC",
+        }
+    }
+}
+
+/// `Error::BadRequest` for anything outside the allowlist.
+impl std::str::FromStr for LocationSort {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.as_str() == s)
+            .ok_or_else(|| Error::BadRequest(format!("Unknown location sort: {s}")))
+    }
 }
 
 /// Data required to create a new location
@@ -69,6 +198,20 @@ pub struct CreateLocationData {
     pub restrictions: Option<Vec<String>>,
     pub parking_info: Option<String>,
     pub max_capacity: Option<i32>,
+    /// List the location under this organization (`organization:…`)
+    /// instead of the creator; the caller checks the creator may do so.
+    #[serde(default)]
+    pub owner_organization: Option<String>,
+    /// Structured address; whatever is left out is derived from `address`
+    /// and `country` (see [`AddressComponents::resolve`]).
+    #[serde(default)]
+    pub street: Option<String>,
+    #[serde(default)]
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub region_code: Option<String>,
+    #[serde(default)]
+    pub country_code: Option<String>,
 }
 
 /// Data for updating an existing location
@@ -89,6 +232,14 @@ pub struct UpdateLocationData {
     pub restrictions: Option<Vec<String>>,
     pub parking_info: Option<String>,
     pub max_capacity: Option<i32>,
+    #[serde(default)]
+    pub street: Option<String>,
+    #[serde(default)]
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub region_code: Option<String>,
+    #[serde(default)]
+    pub country_code: Option<String>,
 }
 
 /// Location rate information
@@ -118,6 +269,188 @@ pub struct CreateRateData {
     pub description: Option<String>,
 }
 
+/// Street-type words folded to one spelling when comparing addresses.
+const ADDRESS_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("street", "st"),
+    ("avenue", "ave"),
+    ("road", "rd"),
+    ("boulevard", "blvd"),
+    ("drive", "dr"),
+    ("lane", "ln"),
+    ("court", "ct"),
+    ("place", "pl"),
+    ("suite", "ste"),
+    ("north", "n"),
+    ("south", "s"),
+    ("east", "e"),
+    ("west", "w"),
+];
+
+/// Lowercased words of `text` with punctuation dropped and street types
+/// abbreviated, so "12 Main Street." and "12 main st" compare equal.
+pub fn normalize_location_text(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            ADDRESS_ABBREVIATIONS
+                .iter()
+                .find(|(long, _)| *long == word)
+                .map_or(word, |(_, short)| short)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a new name+address very likely describes `existing`: the same
+/// normalized address, and names that match or where one contains the
+/// other ("Main Stage" vs "Main Stage Studios").
+pub fn is_probable_duplicate(existing: &Location, name: &str, address: &str) -> bool {
+    if normalize_location_text(&existing.address) != normalize_location_text(address) {
+        return false;
+    }
+    let a = normalize_location_text(&existing.name);
+    let b = normalize_location_text(name);
+    !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+}
+
+/// Words that start the unit part of a street line ("Suite 200").
+const UNIT_MARKERS: &[&str] = &[
+    "apt",
+    "apartment",
+    "unit",
+    "suite",
+    "ste",
+    "fl",
+    "floor",
+    "bldg",
+    "building",
+    "rm",
+    "room",
+];
+
+/// Structured parts of a location's address, stored next to the display
+/// strings for search, geocoding and distance queries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressComponents {
+    pub street: Option<String>,
+    pub unit: Option<String>,
+    pub region_code: Option<String>,
+    pub country_code: Option<String>,
+}
+
+impl AddressComponents {
+    /// Best-effort parse of the free-text `address` and `country`: the
+    /// first comma-separated part is the street line, a unit is split off
+    /// it (or taken from the next part) at a marker like "Suite" or "#",
+    /// and the country maps to its code by code, name or common alias.
+    /// The region is never guessed.
+    pub fn parse(address: &str, country: &str) -> Self {
+        let mut parts = address.split(',').map(str::trim).filter(|p| !p.is_empty());
+        let first = parts.next().unwrap_or_default();
+        let words: Vec<&str> = first.split_whitespace().collect();
+        // A marker needs something after it ("Suite 4", "#4"), so street
+        // names like "Unit Rd" stay whole.
+        let split = (1..words.len()).find(|&i| {
+            is_unit_marker(words[i])
+                && (i + 1 < words.len() || (words[i].starts_with('#') && words[i].len() > 1))
+        });
+        let (street, mut unit) = match split {
+            Some(i) => (words[..i].join(" "), Some(words[i..].join(" "))),
+            None => (words.join(" "), None),
+        };
+        if unit.is_none() {
+            unit = parts
+                .next()
+                .filter(|p| p.split_whitespace().next().is_some_and(is_unit_marker))
+                .map(str::to_string);
+        }
+        Self {
+            street: Some(street).filter(|s| !s.is_empty()),
+            unit,
+            region_code: None,
+            country_code: crate::country::code_for(country).map(str::to_string),
+        }
+    }
+
+    /// Components for a save: `explicit` values are validated and win,
+    /// blanks are dropped, and missing parts fall back to [`Self::parse`].
+    /// An unassigned country code or a malformed region code is a field
+    /// error.
+    pub fn resolve(address: &str, country: &str, explicit: Self) -> Result<Self, Error> {
+        let blank = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let parsed = Self::parse(address, country);
+
+        let country_code = match blank(explicit.country_code) {
+            Some(code) => Some(crate::country::normalize(&code).ok_or_else(|| {
+                Error::invalid_field("country_code", format!("Unknown country code: {code}"))
+            })?),
+            None => parsed.country_code,
+        };
+        let region_code = blank(explicit.region_code)
+            .map(|code| {
+                normalize_region_code(&code, country_code.as_deref()).ok_or_else(|| {
+                    Error::invalid_field("region_code", format!("Invalid region code: {code}"))
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            street: blank(explicit.street).or(parsed.street),
+            unit: blank(explicit.unit).or(parsed.unit),
+            region_code,
+            country_code,
+        })
+    }
+}
+
+fn is_unit_marker(word: &str) -> bool {
+    let word = word.trim_end_matches('.').to_lowercase();
+    word.starts_with('#') || UNIT_MARKERS.contains(&word.as_str())
+}
+
+/// Upper-case ISO 3166-2 subdivision code (1–3 letters or digits),
+/// accepting and dropping a `CC-` prefix that matches `country_code`.
+fn normalize_region_code(code: &str, country_code: Option<&str>) -> Option<String> {
+    let code = code.trim().to_ascii_uppercase();
+    let code = match code.split_once('-') {
+        Some((prefix, rest)) if Some(prefix) == country_code => rest.to_string(),
+        Some(_) => return None,
+        None => code,
+    };
+    (!code.is_empty() && code.len() <= 3 && code.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then_some(code)
+}
+
+/// Free-text query for the geocoder, most specific part first, preferring
+/// the structured components over the display strings.
+fn geocode_query(
+    address: &str,
+    components: &AddressComponents,
+    city: &str,
+    state: &str,
+    postal_code: Option<&str>,
+    country: &str,
+) -> String {
+    let country = components
+        .country_code
+        .as_deref()
+        .and_then(crate::country::name)
+        .unwrap_or(country);
+    [
+        components.street.as_deref().unwrap_or(address),
+        city,
+        state,
+        postal_code.unwrap_or_default(),
+        country,
+    ]
+    .iter()
+    .map(|part| part.trim())
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
 /// Location model for database operations
 pub struct LocationModel;
 
@@ -128,6 +461,34 @@ impl LocationModel {
 
         let creator_id =
             RecordId::parse_simple(creator_id).map_err(|e| Error::BadRequest(e.to_string()))?;
+        let owner_organization = data
+            .owner_organization
+            .as_deref()
+            .map(|id| RecordId::parse_simple(id).map_err(|e| Error::BadRequest(e.to_string())))
+            .transpose()?;
+        let (owner_type, owner_person) = match &owner_organization {
+            Some(_) => ("organization", None),
+            None => ("person", Some(creator_id.clone())),
+        };
+        let components = AddressComponents::resolve(
+            &data.address,
+            &data.country,
+            AddressComponents {
+                street: data.street,
+                unit: data.unit,
+                region_code: data.region_code,
+                country_code: data.country_code,
+            },
+        )?;
+        let geo = geocoding::geocode(&geocode_query(
+            &data.address,
+            &components,
+            &data.city,
+            &data.state,
+            data.postal_code.as_deref(),
+            &data.country,
+        ))
+        .await;
 
         // Build embedding text for background update
         let embedding_text = build_location_embedding_text(
@@ -142,10 +503,13 @@ impl LocationModel {
             data.parking_info.as_deref(),
         );
 
+        let slug = generate_slug(&data.name, None).await?;
+
         // Create the location (embedding generated in background)
         let query = r#"
             CREATE location CONTENT {
                 name: $name,
+                slug: $slug,
                 address: $address,
                 city: $city,
                 state: $state,
@@ -160,13 +524,22 @@ impl LocationModel {
                 restrictions: $restrictions,
                 parking_info: $parking_info,
                 max_capacity: $max_capacity,
-                created_by: $created_by
+                created_by: $created_by,
+                owner_type: $owner_type,
+                owner_person: $owner_person,
+                owner_organization: $owner_organization,
+                street: $street,
+                unit: $unit,
+                region_code: $region_code,
+                country_code: $country_code,
+                geo: $geo
             } RETURN *;
         "#;
 
         let mut result = DB
             .query(query)
             .bind(("name", data.name))
+            .bind(("slug", slug))
             .bind(("address", data.address))
             .bind(("city", data.city))
             .bind(("state", data.state))
@@ -182,6 +555,14 @@ impl LocationModel {
             .bind(("parking_info", data.parking_info))
             .bind(("max_capacity", data.max_capacity))
             .bind(("created_by", creator_id))
+            .bind(("owner_type", owner_type))
+            .bind(("owner_person", owner_person))
+            .bind(("owner_organization", owner_organization))
+            .bind(("street", components.street))
+            .bind(("unit", components.unit))
+            .bind(("region_code", components.region_code))
+            .bind(("country_code", components.country_code))
+            .bind(("geo", geo))
             .await
             .map_err(|e| Error::Database(format!("Failed to create location: {}", e)))?;
 
@@ -211,7 +592,25 @@ impl LocationModel {
         location.ok_or(Error::NotFound)
     }
 
-    /// List locations with optional filters
+    /// One page of public locations, newest first.
+    pub async fn list_public_page(page: PageQuery) -> Result<Page<Location>, Error> {
+        debug!("Paging public locations: {:?}", page);
+
+        let mut result = DB
+            .query("SELECT * FROM location WHERE is_public = true ORDER BY created_at DESC LIMIT $limit START $offset")
+            .query("SELECT VALUE count() FROM location WHERE is_public = true GROUP ALL")
+            .bind(("limit", page.limit()))
+            .bind(("offset", page.offset()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to page locations: {}", e)))?;
+
+        let items: Vec<Location> = result.take(0)?;
+        let total: Option<u64> = result.take(1)?;
+        Ok(Page::new(items, total.unwrap_or(0), page))
+    }
+
+    /// List locations with optional filters. A text/vector filter orders by
+    /// relevance; otherwise `sort` applies.
     #[allow(clippy::too_many_arguments)]
     pub async fn list(
         limit: Option<usize>,
@@ -220,7 +619,7 @@ impl LocationModel {
         creator_id: Option<&str>,
         filter: Option<&str>,
         query_embedding: Option<Vec<f32>>,
-        sort: Option<&str>,
+        sort: LocationSort,
         offset: usize,
     ) -> Result<Vec<Location>, Error> {
         debug!(
@@ -287,12 +686,7 @@ impl LocationModel {
         if filter.is_some() || has_embedding {
             query.push_str(" ORDER BY _score DESC, created_at DESC");
         } else {
-            let order_clause = match sort {
-                Some("name") => " ORDER BY name ASC",
-                Some("city") => " ORDER BY city ASC",
-                _ => " ORDER BY created_at DESC",
-            };
-            query.push_str(order_clause);
+            query.push_str(&format!(" ORDER BY {}", sort.order_by()));
         }
 
         if let Some(limit) = limit {
@@ -341,7 +735,9 @@ impl LocationModel {
         let mut update_fields = Vec::new();
 
         if data.name.is_some() {
+            // The slug follows the name
             update_fields.push("name = $name");
+            update_fields.push("slug = $slug");
         }
         if data.address.is_some() {
             update_fields.push("address = $address");
@@ -386,6 +782,64 @@ impl LocationModel {
             update_fields.push("max_capacity = $max_capacity");
         }
 
+        // Any change to the address re-derives the structured parts and
+        // the coordinates from the merged values.
+        let address_changed = data.address.is_some()
+            || data.city.is_some()
+            || data.state.is_some()
+            || data.country.is_some()
+            || data.postal_code.is_some()
+            || data.street.is_some()
+            || data.unit.is_some()
+            || data.region_code.is_some()
+            || data.country_code.is_some();
+        let located = if address_changed {
+            let address = data.address.as_ref().unwrap_or(&current.address);
+            let country = data.country.as_ref().unwrap_or(&current.country);
+            // Keep stored parts unless their source string changed.
+            let kept = current.components();
+            let components = AddressComponents::resolve(
+                address,
+                country,
+                AddressComponents {
+                    street: data
+                        .street
+                        .clone()
+                        .or(kept.street.filter(|_| data.address.is_none())),
+                    unit: data
+                        .unit
+                        .clone()
+                        .or(kept.unit.filter(|_| data.address.is_none())),
+                    region_code: data.region_code.clone().or(kept.region_code),
+                    country_code: data
+                        .country_code
+                        .clone()
+                        .or(kept.country_code.filter(|_| data.country.is_none())),
+                },
+            )?;
+            let geo = geocoding::geocode(&geocode_query(
+                address,
+                &components,
+                data.city.as_ref().unwrap_or(&current.city),
+                data.state.as_ref().unwrap_or(&current.state),
+                data.postal_code
+                    .as_deref()
+                    .or(current.postal_code.as_deref()),
+                country,
+            ))
+            .await;
+            update_fields.extend([
+                "street = $street",
+                "unit = $unit",
+                "region_code = $region_code",
+                "country_code = $country_code",
+                "geo = $geo",
+            ]);
+            Some((components, geo))
+        } else {
+            None
+        };
+
         if update_fields.is_empty() {
             return Self::get(location_id).await;
         }
@@ -431,10 +885,8 @@ impl LocationModel {
         let mut db_query = DB.query(&query).bind(("location_id", location_id.clone()));
 
         if let Some(name) = data.name {
-            // Also update slug if name changes
-            let slug = crate::text::slugify(&name);
+            let slug = generate_slug(&name, Some(location_id)).await?;
             db_query = db_query.bind(("name", name));
-            update_fields.push("slug = $slug");
             db_query = db_query.bind(("slug", slug));
         }
 
@@ -480,6 +932,14 @@ impl LocationModel {
         if let Some(max_capacity) = data.max_capacity {
             db_query = db_query.bind(("max_capacity", max_capacity));
         }
+        if let Some((components, geo)) = located {
+            db_query = db_query
+                .bind(("street", components.street))
+                .bind(("unit", components.unit))
+                .bind(("region_code", components.region_code))
+                .bind(("country_code", components.country_code))
+                .bind(("geo", geo));
+        }
 
         let mut result = db_query
             .await
@@ -495,7 +955,8 @@ impl LocationModel {
     }
 
     /// Delete a location (and any `rate` rows referencing it) in a single
-    /// transaction.
+    /// transaction; if either statement fails both are rolled back and the
+    /// connection is left outside any transaction.
     ///
     /// Note: the cascade targets a table literally named `rate`; rates are
     /// stored in `location_rate`, so this statement is a no-op cleanup kept
@@ -503,37 +964,37 @@ impl LocationModel {
     pub async fn delete(location_id: &RecordId) -> Result<(), Error> {
         debug!("Deleting location: {}", location_id.display());
 
-        // Start transaction
-        DB.query("BEGIN TRANSACTION")
-            .await
-            .map_err(|e| Error::Database(format!("Failed to start transaction: {}", e)))?;
-
-        // Delete all rates associated with this location
-        DB.query("DELETE rate WHERE location = $location_id")
-            .bind(("location_id", location_id.clone()))
-            .await
-            .map_err(|e| Error::Database(format!("Failed to delete rates: {}", e)))?;
-
-        // Delete the location
-        DB.query("DELETE $location_id")
-            .bind(("location_id", location_id.clone()))
-            .await
-            .map_err(|e| Error::Database(format!("Failed to delete location: {}", e)))?;
-
-        // Commit transaction
-        DB.query("COMMIT TRANSACTION")
-            .await
-            .map_err(|e| Error::Database(format!("Failed to commit transaction: {}", e)))?;
-
-        Ok(())
+        crate::db::transaction(async |tx| {
+            // Delete all rates associated with this location
+            tx.query("DELETE location_rate WHERE location = $location_id")
+                .bind(("location_id", location_id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete rates: {}", e)))?;
+
+            // Delete its photos' media rows
+            tx.query(DELETE_TARGET_MEDIA)
+                .bind(("target", location_id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete media: {}", e)))?;
+
+            // Delete the location
+            tx.query("DELETE $location_id")
+                .bind(("location_id", location_id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete location: {}", e)))?;
+
+            Ok(())
+        })
+        .await
     }
 
-    /// Check if a person can edit a location (only the creator can).
-    ///
-    /// Compares the location's `created_by` record id, rendered as a raw
-    /// string, against `person_id` — so it only matches when the location was
-    /// created by that person directly (organization-created locations never
-    /// match a person id).
+    /// Check if a person can edit a location: its owning person (matched
+    /// as `"person:x"` or bare `"x"`, via [`RecordIdExt::matches_id`]), or
+    /// an owner/admin of its owning organization. A missing location is
+    /// `false`.
     pub async fn can_edit(location_id: &RecordId, person_id: &str) -> Result<bool, Error> {
         debug!(
             "Checking edit permission for {} on location {}",
@@ -541,56 +1002,111 @@ impl LocationModel {
             location_id.display()
         );
 
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct CreatedByResult {
-            created_by: RecordId,
+        let location = match Self::get(location_id).await {
+            Ok(location) => location,
+            Err(Error::NotFound) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        match location.owner() {
+            LocationOwner::Person(owner) => Ok(owner.matches_id(person_id, "person")),
+            LocationOwner::Organization(org) => {
+                let role = crate::models::organization::OrganizationModel::new()
+                    .get_member_role(&org.to_raw_string(), person_id)
+                    .await?;
+                Ok(matches!(role.as_deref(), Some("owner") | Some("admin")))
+            }
         }
+    }
 
-        let query = r#"
-            SELECT created_by FROM location WHERE id = $location_id
-        "#;
-
-        let mut result = DB
+    /// An existing location of `owner` that a new `name` + `address`
+    /// probably duplicates ([`is_probable_duplicate`]), for the create form
+    /// to offer instead of listing the same place twice.
+    pub async fn find_duplicate(
+        owner: &LocationOwner,
+        name: &str,
+        address: &str,
+    ) -> Result<Option<Location>, Error> {
+        let (field, owner_id) = match owner {
+            LocationOwner::Person(id) => ("owner_person", id),
+            LocationOwner::Organization(id) => ("owner_organization", id),
+        };
+        // Rows without ownership fields (pre-026) are matched on `created_by`.
+        let query = format!(
+            "SELECT * FROM location WHERE {field} = $owner \
+             OR (owner_person = NONE AND owner_organization = NONE AND created_by = $owner)"
+        );
+        let candidates: Vec<Location> = DB
             .query(query)
-            .bind(("location_id", location_id.clone()))
+            .bind(("owner", owner_id.clone()))
             .await
-            .map_err(|e| Error::Database(format!("Failed to check permissions: {}", e)))?;
+            .map_err(|e| Error::Database(format!("Failed to check for duplicates: {}", e)))?
+            .take(0)?;
+        Ok(candidates
+            .into_iter()
+            .find(|loc| is_probable_duplicate(loc, name, address)))
+    }
 
-        let location: Option<CreatedByResult> = result.take(0)?;
-        if let Some(loc) = location {
-            let created_by_str = loc.created_by.to_raw_string();
-            return Ok(created_by_str == person_id);
+    /// Whether `viewer_id` may see `location`. Public locations are visible
+    /// to everyone; a private one only to its owner or, for organization-owned
+    /// locations, to that organization's accepted members — the same rule as
+    /// the org profile.
+    pub async fn can_view(location: &Location, viewer_id: Option<&str>) -> Result<bool, Error> {
+        if location.is_public {
+            return Ok(true);
+        }
+        let Some(viewer_id) = viewer_id else {
+            return Ok(false);
+        };
+        match location.owner() {
+            LocationOwner::Person(owner) => Ok(owner.matches_id(viewer_id, "person")),
+            LocationOwner::Organization(org) => {
+                Ok(crate::models::organization::OrganizationModel::new()
+                    .get_member_role(&org.to_raw_string(), viewer_id)
+                    .await?
+                    .is_some())
+            }
         }
-        Ok(false)
     }
 
-    /// Get locations created by a specific user or organization
-    pub async fn get_by_creator(creator_id: &str) -> Result<Vec<Location>, Error> {
-        debug!("Fetching locations for creator: {}", creator_id);
+    /// Locations `person_id` can book: the ones they own, and those of
+    /// organizations they're an accepted member of — the owner rule of
+    /// [`Location::owner`], including its `created_by` fallback for rows
+    /// without ownership fields. Newest first.
+    pub async fn get_for_person(person_id: &str) -> Result<Vec<Location>, Error> {
+        debug!("Fetching locations owned by or through: {}", person_id);
 
         let query = r#"
+            LET $orgs = (SELECT VALUE out FROM member_of
+                WHERE in = $person AND invitation_status = 'accepted');
             SELECT * FROM location
-            WHERE created_by = $creator_id
-            ORDER BY created_at DESC
+            WHERE (owner_type != 'person' AND owner_organization INSIDE $orgs)
+                OR (owner_person = $person
+                    AND (owner_type = 'person' OR owner_organization = NONE))
+                OR (owner_person = NONE AND owner_organization = NONE
+                    AND (created_by = $person OR created_by INSIDE $orgs))
+            ORDER BY created_at DESC;
         "#;
 
         let mut result = DB
             .query(query)
-            .bind(("creator_id", creator_id.to_string()))
+            .bind(("person", normalize_record_id(person_id, "person")))
             .await
-            .map_err(|e| Error::Database(format!("Failed to fetch creator locations: {}", e)))?;
+            .map_err(|e| Error::Database(format!("Failed to fetch owned locations: {}", e)))?;
 
-        let locations: Vec<Location> = result.take(0)?;
+        let locations: Vec<Location> = result.take(1)?;
         Ok(locations)
     }
 
-    /// Add a rate to a location
+    /// Add a rate to a location. A blank currency gets the configured
+    /// default; anything else must be a three-letter code.
     pub async fn add_rate(
         location_id: &RecordId,
         data: CreateRateData,
     ) -> Result<LocationRate, Error> {
         debug!("Adding rate to location: {}", location_id.display());
 
+        let currency = crate::currency::resolve(data.currency.as_deref(), "currency")?;
+
         let query = r#"
             CREATE location_rate CONTENT {
                 location: $location,
@@ -604,13 +1120,10 @@ impl LocationModel {
 
         let mut result = DB
             .query(query)
-            .bind(("location_id", location_id.clone()))
+            .bind(("location", location_id.clone()))
             .bind(("rate_type", data.rate_type))
             .bind(("amount", data.amount))
-            .bind((
-                "currency",
-                data.currency.unwrap_or_else(|| "USD".to_string()),
-            ))
+            .bind(("currency", currency))
             .bind(("minimum_duration", data.minimum_duration))
             .bind(("description", data.description))
             .await
@@ -673,6 +1186,7 @@ impl LocationModel {
                 OR string::lowercase(city) CONTAINS string::lowercase($keyword)
                 OR string::lowercase(state) CONTAINS string::lowercase($keyword)
                 OR string::lowercase(description ?? '') CONTAINS string::lowercase($keyword)
+                OR country_code = string::uppercase($keyword)
             )
             ORDER BY created_at DESC
             LIMIT $limit
@@ -688,4 +1202,104 @@ impl LocationModel {
         let locations: Vec<Location> = result.take(0)?;
         Ok(locations)
     }
+
+    /// Public geocoded locations within `radius_km` of a point, nearest
+    /// first, with their distance in kilometres; `country_code` narrows to
+    /// one country.
+    pub async fn find_near(
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+        country_code: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<(Location, f64)>, Error> {
+        // The bounding box narrows the scan; exact distances are checked below.
+        let (min_lat, max_lat, min_lng, max_lng) = geocoding::bounding_box(lat, lng, radius_km);
+        let mut sql = "SELECT * FROM location
+             WHERE is_public = true
+               AND geo.lat >= $min_lat AND geo.lat <= $max_lat
+               AND geo.lng >= $min_lng AND geo.lng <= $max_lng"
+            .to_string();
+        if country_code.is_some() {
+            sql.push_str(" AND country_code = $country_code");
+        }
+        let locations: Vec<Location> = DB
+            .query(sql)
+            .bind(("min_lat", min_lat))
+            .bind(("max_lat", max_lat))
+            .bind(("min_lng", min_lng))
+            .bind(("max_lng", max_lng))
+            .bind(("country_code", country_code.map(str::to_string)))
+            .await?
+            .take(0)?;
+
+        let mut nearby: Vec<(Location, f64)> = locations
+            .into_iter()
+            .filter_map(|location| {
+                let geo = location.geo.as_ref()?;
+                let distance = geocoding::distance_km(lat, lng, geo.lat, geo.lng);
+                (distance <= radius_km).then_some((location, distance))
+            })
+            .collect();
+        nearby.sort_by(|a, b| a.1.total_cmp(&b.1));
+        nearby.truncate(limit);
+        Ok(nearby)
+    }
+
+    /// Fill the structured address of rows saved before migration 027 by
+    /// parsing their display strings ([`AddressComponents::parse`]). Rows
+    /// nothing can be parsed from are left alone. Returns how many rows
+    /// were updated.
+    pub async fn backfill_address_components() -> Result<usize, Error> {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct PendingRow {
+            id: RecordId,
+            address: String,
+            country: String,
+        }
+
+        let rows: Vec<PendingRow> = DB
+            .query(
+                "SELECT id, address, country FROM location
+                 WHERE street IS NONE AND unit IS NONE AND country_code IS NONE",
+            )
+            .await?
+            .take(0)?;
+
+        let mut updated = 0;
+        for row in rows {
+            let parsed = AddressComponents::parse(&row.address, &row.country);
+            if parsed == AddressComponents::default() {
+                continue;
+            }
+            DB.query(
+                "UPDATE $id SET street = $street, unit = $unit, country_code = $country_code
+                 WHERE street IS NONE AND unit IS NONE AND country_code IS NONE",
+            )
+            .bind(("id", row.id))
+            .bind(("street", parsed.street))
+            .bind(("unit", parsed.unit))
+            .bind(("country_code", parsed.country_code))
+            .await?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+}
+
+/// Generate an unused slug from a location name.
+///
+/// See [`crate::util::slug::slugify_unique`]; `own_id` is skipped so
+/// renaming a location doesn't collide with itself.
+async fn generate_slug(name: &str, own_id: Option<&RecordId>) -> Result<String, Error> {
+    crate::util::slug::slugify_unique(name, |candidate| async move {
+        let taken: Option<RecordId> = DB
+            .query("SELECT VALUE id FROM location WHERE slug = $slug AND id != $own_id LIMIT 1")
+            .bind(("slug", candidate))
+            .bind(("own_id", own_id.cloned()))
+            .await?
+            .take(0)?;
+        Ok(taken.is_some())
+    })
+    .await
 }
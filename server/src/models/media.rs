@@ -2,179 +2,389 @@
 //!
 //! Owns the `media` table: one row of metadata per object stored in the
 //! S3-compatible bucket (the bytes themselves live in object storage, keyed
-//! by `object_key`). Called by `routes/media.rs` upload/delete handlers and
-//! by profile/resume management code.
+//! by `object_key`). `routes/media.rs` records a row for every upload and
+//! forgets it when the file is removed; a file replaced in a single-image
+//! slot is only detached from its target and stays in the uploader's
+//! library until deleted there. The summed `size` per `owner` is that
+//! owner's storage usage, checked against the configured quota before each
+//! new upload.
 
 use crate::db::DB;
 use crate::error::{Error, Result};
+use crate::pagination::{Page, PageQuery};
+use crate::record_id_ext::RecordIdExt;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::env;
 use surrealdb::types::{RecordId, SurrealValue};
 use tracing::{debug, info};
-use ulid::Ulid;
+
+/// Upload slots (`media.media_type`). Single-image slots detach their
+/// previous file; gallery slots accumulate.
+pub mod kind {
+    pub const AVATAR: &str = "avatar";
+    pub const PROFILE_PHOTO: &str = "profile_photo";
+    pub const ORG_LOGO: &str = "org_logo";
+    pub const LOCATION_PROFILE_PHOTO: &str = "location_profile_photo";
+    pub const LOCATION_PHOTO: &str = "location_photo";
+    pub const PRODUCTION_HEADER: &str = "production_header";
+    pub const PRODUCTION_POSTER: &str = "production_poster";
+    pub const PRODUCTION_PHOTO: &str = "production_photo";
+
+    pub const ALL: [&str; 8] = [
+        AVATAR,
+        PROFILE_PHOTO,
+        ORG_LOGO,
+        LOCATION_PROFILE_PHOTO,
+        LOCATION_PHOTO,
+        PRODUCTION_HEADER,
+        PRODUCTION_POSTER,
+        PRODUCTION_PHOTO,
+    ];
+}
+
+/// Drops every `media` row (and likes on them) attached to `$target`, for
+/// the owner deletes to run inside their transaction. The S3 objects are
+/// left for the orphaned-file cleanup.
+pub const DELETE_TARGET_MEDIA: &str = "
+    DELETE likes WHERE out IN (SELECT VALUE id FROM media WHERE target = $target);
+    DELETE media WHERE target = $target;
+";
+
+/// Default per-person storage quota in MB (`STORAGE_QUOTA_MB`).
+const DEFAULT_PERSON_QUOTA_MB: u64 = 500;
+/// Default per-organization storage quota in MB (`ORG_STORAGE_QUOTA_MB`).
+const DEFAULT_ORG_QUOTA_MB: u64 = 2000;
 
 /// Media record structure
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
 pub struct Media {
     /// The unique identifier for the media record
     pub id: RecordId,
-    /// Type of media (e.g., "profile_image", "reel", "resume")
-    pub media_type: String,
-    /// Original filename
-    pub filename: String,
+    /// Display name (original filename, or the object's file name)
+    pub name: String,
+    /// Public URL (the `/api/media/{key}` proxy path)
+    pub uri: String,
     /// MIME type (e.g., "image/jpeg")
     pub mime_type: String,
-    /// File size in bytes
+    pub caption: Option<String>,
+    pub is_public: bool,
+    /// Upload slot, one of [`kind`]
+    pub media_type: Option<String>,
+    /// Person or organization whose quota this counts against
+    pub owner: Option<RecordId>,
+    /// Person who uploaded it
+    pub uploaded_by: Option<RecordId>,
+    /// Record the file is attached to (profile, location, production …)
+    pub target: Option<RecordId>,
+    /// S3 object key of the main file
+    pub object_key: Option<String>,
+    /// S3 object key of the generated thumbnail, if any
+    pub thumbnail_key: Option<String>,
+    /// Public URL of the thumbnail
+    pub thumbnail_uri: Option<String>,
+    /// Bytes stored, thumbnail included
     pub size: i64,
-    /// S3 bucket name
-    pub bucket: String,
-    /// S3 object key/path
-    pub object_key: String,
-    /// Public URL if available
-    pub url: Option<String>,
-    /// Thumbnail URL if applicable
-    pub thumbnail_url: Option<String>,
-    /// Image dimensions if applicable
-    pub dimensions: Option<MediaDimensions>,
-    /// Upload timestamp
-    pub uploaded_at: String,
-    /// Owner of the media (person record ID)
+    /// Pixel dimensions of the main file (`None` for SVG)
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Input for recording a new upload
+#[derive(Debug, Clone)]
+pub struct NewMedia {
+    pub owner: RecordId,
     pub uploaded_by: RecordId,
+    pub target: RecordId,
+    /// One of [`kind`]
+    pub media_type: &'static str,
+    pub name: String,
+    pub mime_type: String,
+    pub object_key: String,
+    pub thumbnail_key: Option<String>,
+    pub size: u64,
+    /// Pixel dimensions of the main file, when known
+    pub dimensions: Option<(u32, u32)>,
 }
 
-/// Media dimensions for images/videos
-#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
-pub struct MediaDimensions {
-    pub width: u32,
-    pub height: u32,
+/// An owner's storage usage against its quota
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageUsage {
+    pub owner: String,
+    pub used_bytes: u64,
+    /// `None` when uploads are unlimited
+    pub quota_bytes: Option<u64>,
+    pub files: u64,
 }
 
-/// Input for creating a new media record
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CreateMediaInput {
-    /// Type of media (e.g., "profile_image", "reel", "resume"); free-form,
-    /// no schema ASSERT.
-    pub media_type: String,
-    pub filename: String,
-    pub mime_type: String,
-    pub size: i64,
-    pub bucket: String,
-    pub object_key: String,
-    pub url: Option<String>,
-    pub dimensions: Option<MediaDimensions>,
-    pub uploaded_by: String, // Person ID as string
+impl StorageUsage {
+    pub fn remaining_bytes(&self) -> Option<u64> {
+        self.quota_bytes
+            .map(|quota| quota.saturating_sub(self.used_bytes))
+    }
 }
 
-impl Media {
-    /// Create a new media record in the database and return the full record ID
-    pub async fn create(input: CreateMediaInput) -> Result<String> {
-        debug!("Creating media record for file: {}", input.filename);
+/// The storage quota for `owner` in bytes, or `None` for unlimited.
+/// Read from `STORAGE_QUOTA_MB` (people) and `ORG_STORAGE_QUOTA_MB`
+/// (organizations) on every call; `0` disables the quota.
+pub fn quota_bytes(owner: &RecordId) -> Option<u64> {
+    let (var, default) = if owner.table.as_str() == "organization" {
+        ("ORG_STORAGE_QUOTA_MB", DEFAULT_ORG_QUOTA_MB)
+    } else {
+        ("STORAGE_QUOTA_MB", DEFAULT_PERSON_QUOTA_MB)
+    };
+    let mb = env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(default);
+    (mb > 0).then(|| mb * 1024 * 1024)
+}
 
-        // Generate our own ID to avoid deserialization issues
-        let id_part = Ulid::new().to_string();
-        let media_id = format!("media:{}", id_part);
+/// Human-readable byte count, e.g. "12.5 MB".
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["bytes", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
 
-        // Ensure uploaded_by is the full record ID like "person:id"
-        let uploaded_by_record = if input.uploaded_by.starts_with("person:") {
-            input.uploaded_by.clone()
-        } else {
-            format!("person:{}", input.uploaded_by)
-        };
+impl Media {
+    /// Record an upload. For single-image slots (`replace`), the file
+    /// previously in the same slot on `target` is detached first
+    /// ([`Self::detach`]); it stays in the library, and in the quota.
+    pub async fn record(new: NewMedia, replace: bool) -> Result<RecordId> {
+        debug!("Recording {} upload: {}", new.media_type, new.object_key);
 
-        // Create the media record using the SDK's create method
-        #[derive(serde::Serialize, serde::Deserialize, SurrealValue)]
-        struct MediaData {
-            media_type: String,
-            filename: String,
-            mime_type: String,
-            size: i64,
-            bucket: String,
-            object_key: String,
-            url: Option<String>,
-            dimensions: Option<MediaDimensions>,
-            uploaded_at: String,
-            uploaded_by: String,
+        if replace {
+            Self::detach(&new.target, new.media_type).await?;
         }
 
-        let data = MediaData {
-            media_type: input.media_type,
-            filename: input.filename,
-            mime_type: input.mime_type,
-            size: input.size,
-            bucket: input.bucket,
-            object_key: input.object_key,
-            url: input.url,
-            dimensions: input.dimensions,
-            uploaded_at: chrono::Utc::now().to_rfc3339(),
-            uploaded_by: uploaded_by_record,
+        let uri = format!("/api/media/{}", new.object_key);
+        let thumbnail_uri = new
+            .thumbnail_key
+            .as_ref()
+            .map(|key| format!("/api/media/{}", key));
+        let mut response = DB
+            .query(
+                "CREATE media CONTENT {
+                    name: $name, uri: $uri, mime_type: $mime_type, is_public: true,
+                    media_type: $media_type, owner: $owner, uploaded_by: $uploaded_by,
+                    target: $target, object_key: $object_key, thumbnail_key: $thumbnail_key,
+                    thumbnail_uri: $thumbnail_uri, size: $size, width: $width, height: $height
+                } RETURN VALUE id",
+            )
+            .bind(("name", new.name))
+            .bind(("uri", uri))
+            .bind(("mime_type", new.mime_type))
+            .bind(("media_type", new.media_type))
+            .bind(("owner", new.owner))
+            .bind(("uploaded_by", new.uploaded_by))
+            .bind(("target", new.target))
+            .bind(("object_key", new.object_key))
+            .bind(("thumbnail_key", new.thumbnail_key))
+            .bind(("thumbnail_uri", thumbnail_uri))
+            .bind(("size", new.size as i64))
+            .bind(("width", new.dimensions.map(|(w, _)| w as i64)))
+            .bind(("height", new.dimensions.map(|(_, h)| h as i64)))
+            .await?;
+
+        let id: Option<RecordId> = response.take(0)?;
+        let id = id.ok_or_else(|| Error::database("Failed to create media record"))?;
+        info!("Created media record {}", id.display());
+        Ok(id)
+    }
+
+    /// Remove the media rows for a slot on `target` — all of them, or only
+    /// the one served at `uri` (gallery photos). The S3 objects are left
+    /// for the orphaned-file cleanup.
+    pub async fn forget(target: &RecordId, media_type: &str, uri: Option<&str>) -> Result<()> {
+        let sql = if uri.is_some() {
+            "DELETE media WHERE target = $target AND media_type = $media_type AND uri = $uri"
+        } else {
+            "DELETE media WHERE target = $target AND media_type = $media_type"
         };
+        DB.query(sql)
+            .bind(("target", target.clone()))
+            .bind(("media_type", media_type.to_string()))
+            .bind(("uri", uri.map(str::to_string)))
+            .await?
+            .check()?;
+        Ok(())
+    }
 
-        // Use the SDK's create method with a specific ID
-        let _result: Option<serde_json::Value> = DB
-            .create(RecordId::new("media", id_part.clone()))
-            .content(data)
-            .await
-            .map_err(|e| Error::database(format!("Failed to create media record: {}", e)))?;
+    /// Unlink a slot's files from `target` without deleting them, so a
+    /// replaced headshot or logo can still be found in the library.
+    pub async fn detach(target: &RecordId, media_type: &str) -> Result<()> {
+        DB.query(
+            "UPDATE media SET target = NONE
+             WHERE target = $target AND media_type = $media_type RETURN NONE",
+        )
+        .bind(("target", target.clone()))
+        .bind(("media_type", media_type.to_string()))
+        .await?
+        .check()?;
+        Ok(())
+    }
 
-        info!("Created media record with ID: {}", media_id);
-        Ok(media_id)
+    /// Delete a library file no longer attached to anything. Attached files
+    /// are refused with [`Error::Conflict`]; remove them from their profile,
+    /// location or production instead. The S3 objects are left for the
+    /// orphaned-file cleanup.
+    pub async fn delete_detached(id: &RecordId) -> Result<()> {
+        let deleted: Vec<Self> = DB
+            .query(
+                "DELETE likes WHERE out = $id AND $id.target = NONE;
+                 DELETE $id WHERE target = NONE RETURN BEFORE",
+            )
+            .bind(("id", id.clone()))
+            .await?
+            .take(1)?;
+        if deleted.is_empty() {
+            return Err(Error::Conflict(
+                "This file is still in use; remove it where it's shown first".to_string(),
+            ));
+        }
+        Ok(())
     }
 
-    /// Find a media record by ID
-    pub async fn find_by_id(id: &str) -> Result<Option<Self>> {
-        debug!("Finding media by ID: {}", id);
+    /// `owner`'s current storage usage and quota.
+    pub async fn storage_usage(owner: &RecordId) -> Result<StorageUsage> {
+        #[derive(Deserialize, SurrealValue)]
+        struct UsageRow {
+            used: i64,
+            files: i64,
+        }
 
-        let sql = "SELECT * FROM media WHERE id = type::record('media', $id)";
+        let row: Option<UsageRow> = DB
+            .query(
+                "SELECT math::sum(size) AS used, count() AS files FROM media
+                 WHERE owner = $owner GROUP ALL",
+            )
+            .bind(("owner", owner.clone()))
+            .await?
+            .take(0)?;
 
-        let mut response = DB.query(sql).bind(("id", id.to_string())).await?;
+        let (used, files) = row.map(|r| (r.used, r.files)).unwrap_or((0, 0));
+        Ok(StorageUsage {
+            owner: owner.to_raw_string(),
+            used_bytes: used.max(0) as u64,
+            quota_bytes: quota_bytes(owner),
+            files: files.max(0) as u64,
+        })
+    }
 
-        let media: Vec<Self> = response.take(0)?;
-        Ok(media.into_iter().next())
+    /// Fail with [`Error::Validation`] (quoting current usage) if storing
+    /// `incoming` more bytes would take `owner` over its quota.
+    pub async fn ensure_quota(owner: &RecordId, incoming: u64) -> Result<()> {
+        let usage = Self::storage_usage(owner).await?;
+        if let Some(quota) = usage.quota_bytes
+            && usage.used_bytes + incoming > quota
+        {
+            return Err(Error::Validation(format!(
+                "Storage quota exceeded: {} of {} used, and this upload needs {}. Delete some files to make room.",
+                format_bytes(usage.used_bytes),
+                format_bytes(quota),
+                format_bytes(incoming)
+            )));
+        }
+        Ok(())
     }
 
-    /// Delete a media record and its S3 object
-    pub async fn delete(id: &str) -> Result<()> {
-        debug!("Deleting media record: {}", id);
+    /// One page of what `uploader` has uploaded, newest first, optionally
+    /// limited to one [`kind`].
+    pub async fn library(
+        uploader: &RecordId,
+        media_type: Option<&str>,
+        page: PageQuery,
+    ) -> Result<Page<Self>> {
+        debug!(
+            "Paging media library for {} ({:?}, {:?})",
+            uploader.display(),
+            media_type,
+            page
+        );
 
-        // TODO: Delete the actual file from S3
-        // This will require the S3 client to be passed in or available globally
+        let condition = if media_type.is_some() {
+            "uploaded_by = $uploader AND media_type = $media_type"
+        } else {
+            "uploaded_by = $uploader"
+        };
+        let mut result = DB
+            .query(format!(
+                "SELECT * FROM media WHERE {condition} ORDER BY created_at DESC LIMIT $limit START $offset"
+            ))
+            .query(format!(
+                "SELECT VALUE count() FROM media WHERE {condition} GROUP ALL"
+            ))
+            .bind(("uploader", uploader.clone()))
+            .bind(("media_type", media_type.map(str::to_string)))
+            .bind(("limit", page.limit()))
+            .bind(("offset", page.offset()))
+            .await?;
 
-        let sql = "DELETE type::record('media', $id)";
+        let items: Vec<Self> = result.take(0)?;
+        let total: Option<u64> = result.take(1)?;
+        Ok(Page::new(items, total.unwrap_or(0), page))
+    }
 
-        DB.query(sql).bind(("id", id.to_string())).await?;
+    /// Every upload counted against `owner` or made by them, oldest first —
+    /// the set a bulk thumbnail rebuild walks.
+    pub async fn list_for_owner(owner: &RecordId) -> Result<Vec<Self>> {
+        let media: Vec<Self> = DB
+            .query(
+                "SELECT * FROM media WHERE owner = $owner OR uploaded_by = $owner
+                 ORDER BY created_at",
+            )
+            .bind(("owner", owner.clone()))
+            .await?
+            .take(0)?;
+        Ok(media)
+    }
 
-        info!("Media record {} deleted", id);
-        Ok(())
+    /// Whether `person` may manage this upload: they made it, or it counts
+    /// against their quota.
+    pub fn is_managed_by(&self, person: &RecordId) -> bool {
+        self.uploaded_by.as_ref() == Some(person) || self.owner.as_ref() == Some(person)
     }
 
-    /// Get all media for a person
-    pub async fn get_person_media(person_id: &str, media_type: Option<&str>) -> Result<Vec<Self>> {
-        debug!("Getting media for person: {}", person_id);
+    /// Record a new stored size after a file was rewritten in place.
+    pub async fn set_size(id: &RecordId, size: u64) -> Result<()> {
+        DB.query("UPDATE $id SET size = $size RETURN NONE")
+            .bind(("id", id.clone()))
+            .bind(("size", size as i64))
+            .await?
+            .check()?;
+        Ok(())
+    }
 
-        let sql = if let Some(_mt) = media_type {
-            "SELECT * FROM media WHERE uploaded_by = type::record('person', $person_id) AND media_type = $media_type ORDER BY uploaded_at DESC"
-        } else {
-            "SELECT * FROM media WHERE uploaded_by = type::record('person', $person_id) ORDER BY uploaded_at DESC"
-        };
+    /// Find a media record by ID
+    pub async fn find_by_id(id: &str) -> Result<Option<Self>> {
+        debug!("Finding media by ID: {}", id);
 
-        let mut query = DB.query(sql).bind(("person_id", person_id.to_string()));
+        let sql = "SELECT * FROM media WHERE id = type::record('media', $id)";
 
-        if let Some(mt) = media_type {
-            query = query.bind(("media_type", mt.to_string()));
-        }
+        let mut response = DB.query(sql).bind(("id", id.to_string())).await?;
 
-        let mut response = query.await?;
         let media: Vec<Self> = response.take(0)?;
+        Ok(media.into_iter().next())
+    }
 
-        Ok(media)
+    /// The row recorded for the file served at `uri` (`/api/media/...`).
+    pub async fn find_by_uri(uri: &str) -> Result<Option<Self>> {
+        let mut response = DB
+            .query("SELECT * FROM media WHERE uri = $uri LIMIT 1")
+            .bind(("uri", uri.to_string()))
+            .await?;
+        let media: Vec<Self> = response.take(0)?;
+        Ok(media.into_iter().next())
     }
 }
-
-// TODO: Future enhancements
-// - Add image processing (resize, optimize)
-// - Generate thumbnails automatically
-// - Add virus scanning
-// - Add file type validation
-// - Add storage quota management
-// - Add CDN URL generation
-// - Add batch upload support
-// - Add media metadata extraction (EXIF, etc.)
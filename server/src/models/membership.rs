@@ -2,7 +2,13 @@
 //!
 //! This module handles the graph relationships between people and organizations,
 //! including roles, permissions, and invitation management.
+//!
+//! Every membership is a `person->member_of->organization` edge — the one
+//! relation for org membership (productions reuse `member_of` with a
+//! production as `out`). There is no separate `membership` or
+//! `organization_members` table; `OrganizationModel` reads the same edges.
 
+use crate::record_id_ext::normalize_record_id;
 use crate::{db::DB, error::Error};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -71,7 +77,7 @@ impl MembershipRole {
 }
 
 /// Permissions that can be granted to members
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum Permission {
     // Organization management
@@ -93,6 +99,88 @@ pub enum Permission {
     PublishContent,
 }
 
+impl Permission {
+    pub const ALL: [Permission; 10] = [
+        Permission::UpdateOrganization,
+        Permission::DeleteOrganization,
+        Permission::InviteMembers,
+        Permission::RemoveMembers,
+        Permission::UpdateMemberRoles,
+        Permission::CreateProjects,
+        Permission::UpdateProjects,
+        Permission::DeleteProjects,
+        Permission::ManageContent,
+        Permission::PublishContent,
+    ];
+
+    /// The snake_case string stored in `member_of.permissions`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::UpdateOrganization => "update_organization",
+            Permission::DeleteOrganization => "delete_organization",
+            Permission::InviteMembers => "invite_members",
+            Permission::RemoveMembers => "remove_members",
+            Permission::UpdateMemberRoles => "update_member_roles",
+            Permission::CreateProjects => "create_projects",
+            Permission::UpdateProjects => "update_projects",
+            Permission::DeleteProjects => "delete_projects",
+            Permission::ManageContent => "manage_content",
+            Permission::PublishContent => "publish_content",
+        }
+    }
+
+    /// Parse a stored value; unknown strings yield `None` so stale grants
+    /// in old rows are ignored rather than failing the lookup.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == s)
+    }
+}
+
+/// What a person may do in one organization, computed by
+/// [`MembershipModel::permissions_for`]. Templates gate controls with
+/// `permissions.contains(Permission::InviteMembers)` instead of comparing
+/// role strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionSet(Vec<Permission>);
+
+impl PermissionSet {
+    /// Every permission (organization owners).
+    pub fn all() -> Self {
+        Self(Permission::ALL.to_vec())
+    }
+
+    /// Effective permissions for an accepted membership: owners get
+    /// everything; anyone else gets their role's defaults plus any extra
+    /// grants stored on the edge.
+    pub fn for_membership(role: &str, stored: &[String]) -> Self {
+        let Ok(role) = MembershipRole::from_str(role) else {
+            return Self::default();
+        };
+        if role == MembershipRole::Owner {
+            return Self::all();
+        }
+        let mut permissions = MembershipModel::get_default_permissions(&role);
+        for extra in stored.iter().filter_map(|s| Permission::parse(s)) {
+            if !permissions.contains(&extra) {
+                permissions.push(extra);
+            }
+        }
+        Self(permissions)
+    }
+
+    pub fn contains(&self, permission: Permission) -> bool {
+        self.0.contains(&permission)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Permission> + '_ {
+        self.0.iter().copied()
+    }
+}
+
 /// Status of a membership invitation
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -292,10 +380,10 @@ impl MembershipModel {
             person_id, org_id
         );
 
-        let person_id: RecordId =
-            RecordId::parse_simple(person_id).map_err(|e| Error::BadRequest(e.to_string()))?;
-        let org_id: RecordId =
-            RecordId::parse_simple(org_id).map_err(|e| Error::BadRequest(e.to_string()))?;
+        // Callers pass both "person:x" and bare "x"; normalize so every
+        // membership/permission check resolves the same edge.
+        let person_id = normalize_record_id(person_id, "person");
+        let org_id = normalize_record_id(org_id, "organization");
 
         let query = "SELECT
                         id,
@@ -494,23 +582,27 @@ impl MembershipModel {
         org_id: &str,
         permission: Permission,
     ) -> Result<bool, Error> {
-        let membership = self.find_by_person_and_org(person_id, org_id).await?;
+        Ok(self
+            .permissions_for(person_id, org_id)
+            .await?
+            .contains(permission))
+    }
 
-        if let Some(membership) = membership {
-            // Owners have all permissions
-            if membership.role == "owner" {
-                return Ok(true);
+    /// The typed permission set a person holds in an organization. Empty
+    /// unless the membership is accepted (pending invites and join
+    /// requests grant nothing).
+    pub async fn permissions_for(
+        &self,
+        person_id: &str,
+        org_id: &str,
+    ) -> Result<PermissionSet, Error> {
+        let membership = self.find_by_person_and_org(person_id, org_id).await?;
+        Ok(match membership {
+            Some(m) if m.invitation_status == InvitationStatus::Accepted.as_str() => {
+                PermissionSet::for_membership(&m.role, &m.permissions)
             }
-
-            // Check specific permissions
-            let perm_str = serde_json::to_string(&permission)
-                .unwrap_or_default()
-                .trim_matches('"')
-                .to_string();
-            Ok(membership.permissions.contains(&perm_str))
-        } else {
-            Ok(false)
-        }
+            _ => PermissionSet::default(),
+        })
     }
 
     /// Get default permissions for a role
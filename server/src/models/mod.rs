@@ -9,6 +9,7 @@
 
 pub mod activity;
 pub mod analytics;
+pub mod comment;
 pub mod consent_grant;
 pub mod equipment;
 pub mod involvement;
@@ -25,5 +26,6 @@ pub mod organization;
 pub mod pending_invitation;
 pub mod person;
 pub mod production;
+pub mod schedule;
 pub mod script;
 pub mod system;
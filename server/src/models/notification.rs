@@ -2,9 +2,11 @@
 //!
 //! Owns the `notification` table. Rows are created by whatever flow needs to
 //! notify someone — invitations (`services/invitation.rs`), membership and
-//! production routes, messages, job applications, webhooks — and read/managed
-//! by `routes/notifications.rs` plus the unread-count badge in
-//! `templates.rs`.
+//! production routes, comments, messages, job applications, webhooks — and
+//! read/managed by `routes/notifications.rs` (HTML page + `/api/notifications`
+//! JSON) plus the unread-count badge in `templates.rs`. Rows persist, so the
+//! live SSE badge stream (`services/notification_stream.rs`) is only a push
+//! channel: anything missed while offline is still here on next load.
 
 use crate::{db::DB, error::Error};
 use chrono::{DateTime, Utc};
@@ -19,7 +21,7 @@ pub struct Notification {
     pub person_id: RecordId,
     /// One of "invitation" | "invitation_accepted" | "member_joined" |
     /// "general" | "message" | "job_application" | "application_update" |
-    /// "join_request" (schema ASSERT on `notification.notification_type`).
+    /// "join_request" | "comment" (schema ASSERT on `notification.notification_type`).
     pub notification_type: String,
     pub title: String,
     pub message: String,
@@ -127,6 +129,35 @@ impl NotificationModel {
         Ok(notifications)
     }
 
+    /// Page through a person's notifications, newest first, optionally
+    /// restricted to unread ones (backs the `/api/notifications` list).
+    pub async fn list(
+        &self,
+        person_id: &str,
+        unread_only: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Notification>, Error> {
+        let person_id =
+            RecordId::parse_simple(person_id).map_err(|e| Error::BadRequest(e.to_string()))?;
+
+        let query = if unread_only {
+            "SELECT * FROM notification WHERE person_id = $person_id AND read = false ORDER BY created_at DESC LIMIT $limit START $offset"
+        } else {
+            "SELECT * FROM notification WHERE person_id = $person_id ORDER BY created_at DESC LIMIT $limit START $offset"
+        };
+
+        let notifications: Vec<Notification> = DB
+            .query(query)
+            .bind(("person_id", person_id))
+            .bind(("limit", limit))
+            .bind(("offset", offset))
+            .await?
+            .take(0)?;
+
+        Ok(notifications)
+    }
+
     /// Mark one notification read; the `WHERE person_id = $person_id` guard
     /// makes it a no-op unless the caller owns it.
     pub async fn mark_read(&self, id: &str, person_id: &str) -> Result<(), Error> {
@@ -5,6 +5,10 @@
 //! the embedding text used by semantic search. Called from
 //! `routes::organizations` and `routes::org_settings`.
 
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use surrealdb::types::{RecordId, SurrealValue};
@@ -13,11 +17,44 @@ use tracing::{debug, error, warn};
 use crate::{
     db::DB,
     error::Error,
+    models::media::DELETE_TARGET_MEDIA,
     models::membership::{MembershipModel, MembershipRole},
+    pagination::{Page, PageQuery},
     record_id_ext::RecordIdExt,
     services::embedding::build_organization_embedding_text,
 };
 
+/// How long [`OrganizationModel::get_organization_types`] reuses its list.
+/// The admin create/rename/delete methods drop the cache immediately, so
+/// this only bounds staleness after edits made straight in the database.
+pub const ORGANIZATION_TYPES_TTL: Duration = Duration::from_secs(600);
+
+/// How long the reserved-name set behind
+/// [`OrganizationModel::check_slug_availability`] is reused. The table is
+/// only seeded by migrations, so nothing in-process invalidates it.
+pub const RESERVED_NAMES_TTL: Duration = Duration::from_secs(600);
+
+/// Organization filter admitting public organizations plus the private ones
+/// `$viewer` (a person record) is an accepted member of.
+pub const VISIBLE_TO_VIEWER: &str = "(public = true OR id IN (SELECT VALUE out FROM member_of WHERE in = $viewer AND invitation_status = 'accepted'))";
+
+type CachedOrganizationTypes = Option<(Instant, Arc<Vec<(String, String)>>)>;
+type CachedReservedNames = Option<(Instant, Arc<HashSet<String>>)>;
+
+static ORGANIZATION_TYPES: LazyLock<Mutex<CachedOrganizationTypes>> =
+    LazyLock::new(|| Mutex::new(None));
+static RESERVED_NAMES: LazyLock<Mutex<CachedReservedNames>> = LazyLock::new(|| Mutex::new(None));
+
+/// Drop the cached organization-type list so the next read reloads it.
+pub fn invalidate_organization_types() {
+    *ORGANIZATION_TYPES.lock().unwrap() = None;
+}
+
+/// Drop the cached reserved-name set so the next slug check reloads it.
+pub fn invalidate_reserved_names() {
+    *RESERVED_NAMES.lock().unwrap() = None;
+}
+
 // ============================
 // Data Structures
 // ============================
@@ -36,6 +73,37 @@ pub struct OrganizationType {
     pub name: String,
 }
 
+/// An organization type plus how many organizations reference it; the
+/// admin taxonomy page uses `usage` to decide whether delete is allowed.
+#[derive(Debug, Clone, Deserialize, SurrealValue)]
+pub struct OrganizationTypeUsage {
+    pub id: RecordId,
+    pub name: String,
+    pub usage: u64,
+}
+
+/// Longest accepted organization type name.
+pub const ORGANIZATION_TYPE_NAME_MAX: usize = 80;
+
+/// Trim and validate an organization type name for create/rename.
+///
+/// # Errors
+/// `Error::Validation` when blank or longer than
+/// [`ORGANIZATION_TYPE_NAME_MAX`] characters.
+pub fn normalize_organization_type_name(name: &str) -> Result<String, Error> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err(Error::validation("Organization type name is required"));
+    }
+    if name.chars().count() > ORGANIZATION_TYPE_NAME_MAX {
+        return Err(Error::validation(format!(
+            "Organization type name must be at most {} characters",
+            ORGANIZATION_TYPE_NAME_MAX
+        )));
+    }
+    Ok(name)
+}
+
 /// Organization entity with all RecordId references properly typed
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
 pub struct Organization {
@@ -111,6 +179,97 @@ pub struct UpdateOrganizationData {
     pub allow_join_requests: bool,
 }
 
+// ============================
+// Service Tags
+// ============================
+
+/// Spellings folded onto one canonical service tag, keyed by the
+/// hyphen-free form [`normalize_service`] compares on.
+const SERVICE_SYNONYMS: &[(&str, &str)] = &[
+    ("post production", "post-production"),
+    ("postproduction", "post-production"),
+    ("post", "post-production"),
+    ("pre production", "pre-production"),
+    ("preproduction", "pre-production"),
+    ("vfx", "visual effects"),
+    ("visual fx", "visual effects"),
+    ("colour grading", "color grading"),
+    ("color correction", "color grading"),
+    ("colour correction", "color grading"),
+    ("grading", "color grading"),
+    ("mograph", "motion graphics"),
+    ("equipment hire", "equipment rental"),
+    ("gear rental", "equipment rental"),
+    ("gear hire", "equipment rental"),
+    ("sound design and mixing", "sound design"),
+    ("audio post", "audio post-production"),
+    ("audio post production", "audio post-production"),
+];
+
+/// One service tag in canonical form: trimmed, lowercased, whitespace
+/// collapsed, and mapped through [`SERVICE_SYNONYMS`] so "Post Production"
+/// and "post-production" are the same facet. `None` for a blank tag.
+pub fn normalize_service(raw: &str) -> Option<String> {
+    let tag = raw
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if tag.is_empty() {
+        return None;
+    }
+    let key = tag.replace(['-', '_'], " ");
+    let key = key.split_whitespace().collect::<Vec<_>>().join(" ");
+    let canonical = SERVICE_SYNONYMS
+        .iter()
+        .find(|(synonym, _)| *synonym == key)
+        .map_or(tag, |(_, canonical)| canonical.to_string());
+    Some(canonical)
+}
+
+/// Normalize a list of service tags, dropping blanks and duplicates while
+/// keeping the owner's order.
+pub fn normalize_services(raw: &[String]) -> Vec<String> {
+    let mut services: Vec<String> = Vec::new();
+    for tag in raw.iter().filter_map(|s| normalize_service(s)) {
+        if !services.contains(&tag) {
+            services.push(tag);
+        }
+    }
+    services
+}
+
+/// Check `founded_year` (between [`crate::validation::MIN_YEAR`] and this
+/// year) and `employees_count` (not negative), with the same field-level
+/// messages the org forms show. Enforced by `create` and `update` so no
+/// caller can store a founded year of 9999.
+pub fn validate_org_numbers(
+    founded_year: Option<i32>,
+    employees_count: Option<i32>,
+) -> Result<(), Error> {
+    let mut errors = crate::validation::ValidationErrors::new();
+    if let Some(year) = founded_year {
+        errors.year("founded_year", "Founded year", &year.to_string());
+    }
+    if let Some(count) = employees_count {
+        errors.integer_at_least(
+            "employees_count",
+            "Number of employees",
+            &count.to_string(),
+            0,
+        );
+    }
+    errors.into_result().map_err(Error::ValidationFields)
+}
+
+/// A service tag and how many public organizations offer it, for the
+/// directory's service filter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServiceFacet {
+    pub name: String,
+    pub count: usize,
+}
+
 // ============================
 // Model Implementation
 // ============================
@@ -140,10 +299,12 @@ impl OrganizationModel {
     /// Create a new organization with the creator as owner
     pub async fn create(
         &self,
-        data: CreateOrganizationData,
+        mut data: CreateOrganizationData,
         created_by: &str,
     ) -> Result<Organization, Error> {
         debug!("Creating organization with slug: {}", data.slug);
+        validate_org_numbers(data.founded_year, data.employees_count)?;
+        data.services = normalize_services(&data.services);
 
         let org_type_id: RecordId =
             RecordId::parse_simple(&data.org_type).map_err(|e| Error::BadRequest(e.to_string()))?;
@@ -154,7 +315,8 @@ impl OrganizationModel {
         let (available, reason) = self.check_slug_availability(&data.slug).await?;
         if !available {
             error!("Slug '{}' is not available: {:?}", data.slug, reason);
-            return Err(Error::validation(
+            return Err(Error::invalid_field(
+                "slug",
                 reason.unwrap_or("Slug not available".to_string()),
             ));
         }
@@ -167,10 +329,10 @@ impl OrganizationModel {
         let type_exists = self.validate_organization_type(&org_type_id).await?;
         if !type_exists {
             error!("Organization type '{}' does not exist", data.org_type);
-            return Err(Error::validation(format!(
-                "Invalid organization type: {}",
-                data.org_type
-            )));
+            return Err(Error::invalid_field(
+                "org_type",
+                format!("Invalid organization type: {}", data.org_type),
+            ));
         }
         debug!("Organization type '{}' is valid", org_type_id.display());
 
@@ -181,19 +343,11 @@ impl OrganizationModel {
             org_type_id.display()
         );
 
-        // Get default owner permissions as strings (must match snake_case serialization)
-        let owner_permissions = vec![
-            "update_organization".to_string(),
-            "delete_organization".to_string(),
-            "invite_members".to_string(),
-            "remove_members".to_string(),
-            "update_member_roles".to_string(),
-            "create_projects".to_string(),
-            "update_projects".to_string(),
-            "delete_projects".to_string(),
-            "manage_content".to_string(),
-            "publish_content".to_string(),
-        ];
+        let owner_permissions: Vec<String> =
+            MembershipModel::get_default_permissions(&MembershipRole::Owner)
+                .iter()
+                .map(|p| p.as_str().to_string())
+                .collect();
 
         // Transaction creates the org and owner membership atomically
         let transaction_query = r#"
@@ -304,18 +458,51 @@ impl OrganizationModel {
         result.ok_or(Error::NotFound)
     }
 
-    /// Search organizations with filters
+    /// One page of public organizations, in the same order as the
+    /// unfiltered [`Self::search`] (verified first, then newest).
+    pub async fn list_public_page(&self, page: PageQuery) -> Result<Page<Organization>, Error> {
+        debug!("Paging public organizations: {:?}", page);
+
+        let mut result = DB
+            .query("SELECT *, type.* FROM organization WHERE public = true ORDER BY verified DESC, created_at DESC LIMIT $limit START $offset")
+            .query("SELECT VALUE count() FROM organization WHERE public = true GROUP ALL")
+            .bind(("limit", page.limit()))
+            .bind(("offset", page.offset()))
+            .await?;
+
+        let items: Vec<Organization> = result.take(0)?;
+        let total: Option<u64> = result.take(1)?;
+        Ok(Page::new(items, total.unwrap_or(0), page))
+    }
+
+    /// Search organizations with filters.
+    ///
+    /// Anonymous viewers (`viewer_id` of `None`) only match public
+    /// organizations; a signed-in viewer additionally sees the private ones
+    /// they are an accepted member of ([`VISIBLE_TO_VIEWER`]) — never anyone
+    /// else's. `service` is normalized like saved tags (see
+    /// [`normalize_service`]).
+    #[allow(clippy::too_many_arguments)]
     pub async fn search(
         &self,
         query: Option<&str>,
         org_type: Option<&str>,
         location: Option<&str>,
+        service: Option<&str>,
         query_embedding: Option<Vec<f32>>,
+        viewer_id: Option<&str>,
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Organization>, Error> {
         debug!("Searching organizations with filters");
 
+        let viewer = match viewer_id {
+            Some(id) => {
+                Some(RecordId::parse_simple(id).map_err(|e| Error::BadRequest(e.to_string()))?)
+            }
+            None => None,
+        };
+
         let has_embedding = query_embedding.is_some();
         let empty_emb: Vec<f32> = vec![];
 
@@ -362,6 +549,17 @@ impl OrganizationModel {
             conditions.push("(string::lowercase(location ?? '') CONTAINS string::lowercase($location) OR string::lowercase(embedding_text ?? '') CONTAINS string::lowercase($location))".to_string());
         }
 
+        let service = service.and_then(normalize_service);
+        if service.is_some() {
+            conditions.push("services CONTAINS $service".to_string());
+        }
+
+        if viewer.is_some() {
+            conditions.push(VISIBLE_TO_VIEWER.to_string());
+        } else {
+            conditions.push("public = true".to_string());
+        }
+
         if !conditions.is_empty() {
             sql.push_str(" WHERE ");
             sql.push_str(&conditions.join(" AND "));
@@ -394,6 +592,12 @@ impl OrganizationModel {
         if let Some(loc) = location {
             result = result.bind(("location", loc.to_string()));
         }
+        if let Some(service) = service {
+            result = result.bind(("service", service));
+        }
+        if let Some(viewer) = viewer {
+            result = result.bind(("viewer", viewer));
+        }
 
         let organizations: Vec<Organization> = result.await?.take(0).unwrap_or_default();
 
@@ -401,8 +605,10 @@ impl OrganizationModel {
     }
 
     /// Update an existing organization
-    pub async fn update(&self, id: &str, data: UpdateOrganizationData) -> Result<(), Error> {
+    pub async fn update(&self, id: &str, mut data: UpdateOrganizationData) -> Result<(), Error> {
         debug!("Updating organization: {}", id);
+        validate_org_numbers(data.founded_year, data.employees_count)?;
+        data.services = normalize_services(&data.services);
         let id: RecordId =
             RecordId::parse_simple(id).map_err(|e| Error::BadRequest(e.to_string()))?;
         let org_type_id: RecordId =
@@ -462,23 +668,31 @@ impl OrganizationModel {
         let id: RecordId =
             RecordId::parse_simple(id).map_err(|e| Error::BadRequest(e.to_string()))?;
 
-        // Delete all memberships first
-        let _: Vec<()> = DB
-            .query("DELETE member_of WHERE out = $id")
-            .bind(("id", id.clone()))
-            .await?
-            .take(0)
-            .unwrap_or_default();
+        crate::db::transaction(async |tx| {
+            // Delete all memberships first
+            tx.query("DELETE member_of WHERE out = $id")
+                .bind(("id", id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete memberships: {}", e)))?;
 
-        // Delete the organization
-        let _: Vec<()> = DB
-            .query("DELETE $id")
-            .bind(("id", id))
-            .await?
-            .take(0)
-            .unwrap_or_default();
+            // Delete its logo's media rows
+            tx.query(DELETE_TARGET_MEDIA)
+                .bind(("target", id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete media: {}", e)))?;
 
-        Ok(())
+            // Delete the organization
+            tx.query("DELETE $id")
+                .bind(("id", id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete organization: {}", e)))?;
+
+            Ok(())
+        })
+        .await
     }
 
     /// Add a member to an organization
@@ -702,6 +916,17 @@ impl OrganizationModel {
         Ok(())
     }
 
+    /// Set (or with `None`, clear) the logo URL of the organization at `slug`.
+    pub async fn set_logo(&self, slug: &str, logo: Option<String>) -> Result<(), Error> {
+        debug!("Setting logo for organization {}: {:?}", slug, logo);
+        DB.query("UPDATE organization SET logo = $logo WHERE slug = $slug RETURN NONE")
+            .bind(("logo", logo))
+            .bind(("slug", slug.to_string()))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
     /// Check if a slug is available
     pub async fn check_slug_availability(
         &self,
@@ -721,23 +946,110 @@ impl OrganizationModel {
             return Ok((false, Some("This name is already taken".to_string())));
         }
 
-        // Check against reserved names
-        let reserved_check: Vec<serde_json::Value> = DB
-            .query("SELECT name FROM reserved_names WHERE name = $name")
-            .bind(("name", slug.to_string()))
+        if Self::reserved_names().await?.contains(slug) {
+            return Ok((false, Some("This name is reserved".to_string())));
+        }
+
+        Ok((true, None))
+    }
+
+    /// Every name in `reserved_names`, reused for [`RESERVED_NAMES_TTL`].
+    async fn reserved_names() -> Result<Arc<HashSet<String>>, Error> {
+        if let Some((fetched, names)) = RESERVED_NAMES.lock().unwrap().as_ref()
+            && fetched.elapsed() < RESERVED_NAMES_TTL
+        {
+            return Ok(Arc::clone(names));
+        }
+
+        let names: Vec<String> = DB
+            .query("SELECT VALUE name FROM reserved_names")
+            .await?
+            .take(0)?;
+        let names = Arc::new(names.into_iter().collect::<HashSet<_>>());
+        *RESERVED_NAMES.lock().unwrap() = Some((Instant::now(), Arc::clone(&names)));
+        Ok(names)
+    }
+
+    /// Derive an available slug from an organization name.
+    ///
+    /// Slugifies `name` and appends `-2`, `-3`, … past any taken or reserved
+    /// slug (see [`crate::util::slug::slugify_unique`]).
+    pub async fn suggest_slug(&self, name: &str) -> Result<String, Error> {
+        crate::util::slug::slugify_unique(name, |candidate| async move {
+            let (available, _) = self.check_slug_availability(&candidate).await?;
+            Ok(!available)
+        })
+        .await
+    }
+
+    /// Rewrite the service tags of organizations saved before tags were
+    /// normalized, so the `services CONTAINS $service` filter in
+    /// [`search`](Self::search) matches them. Returns how many rows
+    /// changed; rows already in canonical form are left alone.
+    pub async fn backfill_service_tags(&self) -> Result<usize, Error> {
+        #[derive(Deserialize, SurrealValue)]
+        struct Row {
+            id: RecordId,
+            services: Vec<String>,
+        }
+
+        let rows: Vec<Row> = DB
+            .query("SELECT id, services FROM organization WHERE array::len(services ?? []) > 0")
+            .await?
+            .take(0)?;
+        let changed: Vec<(RecordId, Vec<String>)> = rows
+            .into_iter()
+            .filter_map(|row| {
+                let services = normalize_services(&row.services);
+                (services != row.services).then_some((row.id, services))
+            })
+            .collect();
+
+        for (id, services) in &changed {
+            DB.query("UPDATE $id SET services = $services")
+                .bind(("id", id.clone()))
+                .bind(("services", services.clone()))
+                .await?
+                .check()?;
+        }
+        Ok(changed.len())
+    }
+
+    /// Distinct service tags across public organizations, most offered
+    /// first (ties alphabetical). Tags are normalized again here so rows
+    /// saved before normalization still fold into one facet.
+    pub async fn service_facets(&self) -> Result<Vec<ServiceFacet>, Error> {
+        let rows: Vec<Vec<String>> = DB
+            .query("SELECT VALUE services FROM organization WHERE public = true AND array::len(services ?? []) > 0")
             .await?
             .take(0)
             .unwrap_or_default();
 
-        if !reserved_check.is_empty() {
-            return Ok((false, Some("This name is reserved".to_string())));
+        let mut counts: std::collections::HashMap<String, usize> = Default::default();
+        for services in rows {
+            for tag in normalize_services(&services) {
+                *counts.entry(tag).or_default() += 1;
+            }
         }
-
-        Ok((true, None))
+        let mut facets: Vec<ServiceFacet> = counts
+            .into_iter()
+            .map(|(name, count)| ServiceFacet { name, count })
+            .collect();
+        facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        Ok(facets)
     }
 
-    /// Get all organization types with ID and name
+    /// Get all organization types with ID and name, reused for
+    /// [`ORGANIZATION_TYPES_TTL`] since every org create/edit/list page
+    /// renders them. An empty result isn't cached, so a database seeded
+    /// after boot shows up on the next request.
     pub async fn get_organization_types(&self) -> Result<Vec<(String, String)>, Error> {
+        if let Some((fetched, types)) = ORGANIZATION_TYPES.lock().unwrap().as_ref()
+            && fetched.elapsed() < ORGANIZATION_TYPES_TTL
+        {
+            return Ok(types.as_ref().clone());
+        }
+
         debug!("Fetching organization types from database");
 
         // Define a struct to match the query result
@@ -755,8 +1067,6 @@ impl OrganizationModel {
         // Extract as structured records
         let records: Vec<OrgTypeRecord> = response.take(0)?;
 
-        debug!("Fetched {} organization types", records.len());
-
         // Convert to tuples with full RecordId strings
         let types: Vec<(String, String)> = records
             .into_iter()
@@ -768,12 +1078,141 @@ impl OrganizationModel {
                 "No organization types found - database may need initialization with 'make db-init'"
             );
         } else {
-            debug!("Successfully loaded {} organization types", types.len());
+            debug!("Loaded {} organization types", types.len());
+            *ORGANIZATION_TYPES.lock().unwrap() = Some((Instant::now(), Arc::new(types.clone())));
         }
 
         Ok(types)
     }
 
+    /// All organization types with their usage counts, for admin management.
+    pub async fn get_organization_types_with_usage(
+        &self,
+    ) -> Result<Vec<OrganizationTypeUsage>, Error> {
+        let types: Vec<OrganizationTypeUsage> = DB
+            .query(
+                "SELECT id, name,
+                    count(SELECT VALUE id FROM organization WHERE type = $parent.id) AS usage
+                 FROM organization_type ORDER BY name",
+            )
+            .await?
+            .take(0)?;
+        Ok(types)
+    }
+
+    /// Create an organization type (platform admins only — callers check).
+    ///
+    /// # Errors
+    /// `Error::Validation` for a bad name; `Error::Conflict` when a type
+    /// with the same name (case-insensitive) already exists.
+    pub async fn create_organization_type(&self, name: &str) -> Result<OrganizationType, Error> {
+        let name = normalize_organization_type_name(name)?;
+        self.ensure_organization_type_name_free(&name, None).await?;
+
+        let created: Option<OrganizationType> = DB
+            .query("CREATE ONLY organization_type SET name = $name RETURN id, name")
+            .bind(("name", name))
+            .await?
+            .take(0)?;
+        let created =
+            created.ok_or_else(|| Error::Internal("Failed to create organization type".into()))?;
+        invalidate_organization_types();
+        Ok(created)
+    }
+
+    /// Rename an organization type. Organizations reference the type by
+    /// record id, so they pick up the new name without further writes
+    /// (search embeddings refresh on the org's next edit or rebuild).
+    ///
+    /// # Errors
+    /// `Error::NotFound` for an unknown id; otherwise as
+    /// [`Self::create_organization_type`].
+    pub async fn rename_organization_type(&self, id: &RecordId, name: &str) -> Result<(), Error> {
+        let name = normalize_organization_type_name(name)?;
+        self.ensure_organization_type_name_free(&name, Some(id))
+            .await?;
+
+        let updated: Option<OrganizationType> = DB
+            .query("UPDATE ONLY $id SET name = $name RETURN id, name")
+            .bind(("id", id.clone()))
+            .bind(("name", name))
+            .await?
+            .take(0)?;
+        updated.ok_or(Error::NotFound)?;
+        invalidate_organization_types();
+        Ok(())
+    }
+
+    /// Delete an organization type that no organization uses.
+    ///
+    /// # Errors
+    /// `Error::Conflict` while any organization still references the type
+    /// (reassign those orgs first); `Error::NotFound` for an unknown id.
+    pub async fn delete_organization_type(&self, id: &RecordId) -> Result<(), Error> {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct Count {
+            count: u64,
+        }
+
+        // Check and delete in one transaction so an organization created
+        // in between can't be left pointing at the deleted type.
+        crate::db::transaction(async |tx| {
+            let mut response = tx
+                .query("SELECT VALUE id FROM ONLY $id")
+                .query("SELECT count() AS count FROM organization WHERE type = $id GROUP ALL")
+                .bind(("id", id.clone()))
+                .await?;
+            let existing: Option<RecordId> = response.take(0)?;
+            if existing.is_none() {
+                return Err(Error::NotFound);
+            }
+            let in_use: Option<Count> = response.take(1)?;
+            let in_use = in_use.map(|c| c.count).unwrap_or(0);
+            if in_use > 0 {
+                return Err(Error::Conflict(format!(
+                    "This type is still used by {} organization{}",
+                    in_use,
+                    if in_use == 1 { "" } else { "s" }
+                )));
+            }
+
+            tx.query("DELETE $id")
+                .bind(("id", id.clone()))
+                .await
+                .and_then(|r| r.check())?;
+            Ok(())
+        })
+        .await?;
+        invalidate_organization_types();
+        Ok(())
+    }
+
+    /// `Error::Conflict` when another type (other than `except`) already
+    /// has `name`, compared case-insensitively.
+    async fn ensure_organization_type_name_free(
+        &self,
+        name: &str,
+        except: Option<&RecordId>,
+    ) -> Result<(), Error> {
+        let clash: Option<RecordId> = DB
+            .query(
+                "SELECT VALUE id FROM organization_type
+                 WHERE string::lowercase(name) = string::lowercase($name) AND id != $except
+                 LIMIT 1",
+            )
+            .bind(("name", name.to_string()))
+            .bind(("except", except.cloned()))
+            .await?
+            .take(0)?;
+        if clash.is_some() {
+            return Err(Error::Conflict(format!(
+                "An organization type named \"{}\" already exists",
+                name
+            )));
+        }
+        Ok(())
+    }
+
     /// Find a user by username or email
     pub async fn find_user_by_username_or_email(&self, identifier: &str) -> Result<String, Error> {
         debug!("Finding user by identifier: {}", identifier);
@@ -798,7 +1237,7 @@ impl OrganizationModel {
     pub async fn get_user_organizations(
         &self,
         user_id: &str,
-    ) -> Result<Vec<(Organization, String, String)>, Error> {
+    ) -> Result<Vec<(Organization, String, DateTime<Utc>)>, Error> {
         debug!("=== Starting get_user_organizations ===");
         debug!("Fetching organizations for user_id: '{}'", user_id);
 
@@ -897,7 +1336,7 @@ impl OrganizationModel {
                     "Successfully fetched organization: {} ({})",
                     org.name, org.slug
                 );
-                result.push((org, rel.role, rel.joined_at.to_rfc3339()));
+                result.push((org, rel.role, rel.joined_at));
             } else {
                 warn!(
                     "Organization {} not found in database",
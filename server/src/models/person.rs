@@ -7,12 +7,15 @@
 use crate::auth;
 use crate::db::DB;
 use crate::error::{Error, Result};
+use crate::pagination::{Page, PageQuery};
 use crate::record_id_ext::RecordIdExt;
 use crate::services::embedding::build_person_embedding_text;
+use crate::services::geocoding;
 use crate::{db_span, log_error};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use surrealdb::types::{RecordId, SurrealValue};
 use tracing::{debug, error, info, warn};
 
@@ -121,6 +124,40 @@ pub fn validate_username(username: &str) -> Result<String> {
     Ok(username)
 }
 
+// -----------------------------------------------------------------------------
+// Skill Aggregation
+// -----------------------------------------------------------------------------
+
+/// How many distinct skills [`Person::known_skills`] keeps.
+pub const KNOWN_SKILLS_LIMIT: usize = 1000;
+
+/// How long [`Person::known_skills`] reuses an aggregation.
+pub const KNOWN_SKILLS_TTL: Duration = Duration::from_secs(120);
+
+/// The known-skill list and when it was aggregated.
+type CachedSkills = Option<(Instant, Arc<Vec<(String, u64)>>)>;
+
+static KNOWN_SKILLS: LazyLock<Mutex<CachedSkills>> = LazyLock::new(|| Mutex::new(None));
+
+/// Up to `limit` of `skills` where the whole skill or any word in it starts
+/// with `prefix` (case-insensitive), keeping their most-used-first order.
+/// An empty prefix matches everything.
+pub fn skills_matching(skills: &[(String, u64)], prefix: &str, limit: usize) -> Vec<(String, u64)> {
+    let prefix = prefix.trim().to_lowercase();
+    skills
+        .iter()
+        .filter(|(skill, _)| {
+            let skill = skill.to_lowercase();
+            skill.starts_with(&prefix)
+                || skill
+                    .split(|c: char| !c.is_alphanumeric())
+                    .any(|word| word.starts_with(&prefix))
+        })
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
 // -----------------------------------------------------------------------------
 // Core Person Model
 // -----------------------------------------------------------------------------
@@ -148,6 +185,11 @@ pub struct Person {
     #[serde(default = "default_messaging_preference")]
     #[surreal(default = "default_messaging_preference")]
     pub messaging_preference: String,
+    /// IANA timezone dates are shown in (e.g. "Europe/Berlin"); `None`
+    /// falls back to the browser's `tz` cookie, then UTC.
+    #[serde(default)]
+    #[surreal(default)]
+    pub timezone: Option<String>,
 }
 
 fn default_verification_status() -> String {
@@ -158,6 +200,103 @@ fn default_messaging_preference() -> String {
     "anyone".to_string()
 }
 
+/// What a given viewer is allowed to see of a profile
+/// (see [`Person::profile_access`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileAccess {
+    /// The owner: every field, including contact details.
+    Owner,
+    /// Anyone else viewing a public profile: contact details only when the
+    /// owner opted in, and none of the owner's `private_fields`.
+    Visitor,
+    /// Anyone else viewing a private profile: name and avatar only.
+    Private,
+}
+
+/// Profile fields an owner can individually hide from other viewers, as
+/// `(key stored in profile.private_fields, settings label)`.
+pub const PRIVATE_FIELD_OPTIONS: &[(&str, &str)] = &[
+    ("birthday", "Birthday"),
+    ("gender", "Gender"),
+    ("measurements", "Height, weight & body type"),
+    ("ethnicity", "Ethnicity"),
+    ("nationality", "Nationality"),
+];
+
+/// Whether a person is looking for work, stored normalized in
+/// `profile.availability_status` next to the free-text
+/// `profile.availability` note ("From June", "Weekends only").
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AvailabilityStatus {
+    Available,
+    OpenToOffers,
+    Busy,
+    NotLooking,
+}
+
+impl AvailabilityStatus {
+    pub const ALL: [AvailabilityStatus; 4] = [
+        AvailabilityStatus::Available,
+        AvailabilityStatus::OpenToOffers,
+        AvailabilityStatus::Busy,
+        AvailabilityStatus::NotLooking,
+    ];
+
+    /// The snake_case string stored in `profile.availability_status` and
+    /// used as the `?availability=` filter value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AvailabilityStatus::Available => "available",
+            AvailabilityStatus::OpenToOffers => "open_to_offers",
+            AvailabilityStatus::Busy => "busy",
+            AvailabilityStatus::NotLooking => "not_looking",
+        }
+    }
+
+    /// Display label for forms, filters, and profiles.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AvailabilityStatus::Available => "Available for work",
+            AvailabilityStatus::OpenToOffers => "Open to offers",
+            AvailabilityStatus::Busy => "Currently busy",
+            AvailabilityStatus::NotLooking => "Not looking",
+        }
+    }
+
+    /// Parse a stored value; unknown strings yield `None`.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+
+    /// Best-effort status for a free-text availability note, for values
+    /// written before the status existed. Negations are checked first so
+    /// "not available" isn't read as available. Mirrored by migration 020.
+    pub fn from_free_text(note: &str) -> Option<Self> {
+        let note = note.trim().to_lowercase().replace(['_', '-'], " ");
+        let has = |words: &[&str]| words.iter().any(|w| note.contains(w));
+
+        if has(&["not available", "unavailable", "not looking", "retired"]) {
+            Some(AvailabilityStatus::NotLooking)
+        } else if has(&["busy", "booked", "on set", "on a project", "working on"]) {
+            Some(AvailabilityStatus::Busy)
+        } else if has(&["open to", "offers", "considering"]) {
+            Some(AvailabilityStatus::OpenToOffers)
+        } else if has(&[
+            "available",
+            "free",
+            "immediately",
+            "looking",
+            "full time",
+            "part time",
+        ]) {
+            Some(AvailabilityStatus::Available)
+        } else {
+            None
+        }
+    }
+}
+
 /// Represents the detailed profile of a person.
 /// Corresponds to the flexible `profile` object in the `person` table.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, SurrealValue)]
@@ -168,10 +307,20 @@ pub struct Profile {
     pub avatar: Option<String>, // Direct URL to profile image
     pub headline: Option<String>,
     pub bio: Option<String>,
+    /// Display location as the person wrote it.
     pub location: Option<String>,
+    /// Structured, geocoded form of `location`, when it could be resolved.
+    pub geo: Option<GeoLocation>,
     pub website: Option<String>,
     pub phone: Option<String>,
+    /// Show email and phone to other viewers (the account-settings
+    /// "contact visibility" toggle).
     pub is_public: bool,
+    /// Hide the whole profile from everyone but the owner.
+    pub is_private: bool,
+    /// Optional fields hidden from other viewers; keys from
+    /// [`PRIVATE_FIELD_OPTIONS`].
+    pub private_fields: Vec<String>,
 
     // Physical Attributes
     pub height_mm: Option<i32>,
@@ -191,7 +340,11 @@ pub struct Profile {
     pub skills: Vec<String>,
     pub unions: Vec<String>,
     pub languages: Vec<String>,
+    /// Free-text availability note; the filterable status is
+    /// `availability_status`.
     pub availability: Option<String>,
+    /// An [`AvailabilityStatus`] string.
+    pub availability_status: Option<String>,
     pub education: Vec<Education>,
     pub awards: Vec<Award>,
 
@@ -203,10 +356,33 @@ pub struct Profile {
     pub social_links: Vec<SocialLink>,
 }
 
+impl Profile {
+    /// The parsed `availability_status`; `None` when unset or unrecognised.
+    pub fn availability_status(&self) -> Option<AvailabilityStatus> {
+        self.availability_status
+            .as_deref()
+            .and_then(AvailabilityStatus::parse)
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Nested Profile Structs
 // -----------------------------------------------------------------------------
 
+/// Structured form of `profile.location`, filled by
+/// [`crate::services::geocoding`] whenever the display string changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, SurrealValue)]
+pub struct GeoLocation {
+    pub city: Option<String>,
+    /// State, province, or similar first-level subdivision.
+    pub region: Option<String>,
+    pub country: Option<String>,
+    /// ISO 3166-1 alpha-2, lower-case.
+    pub country_code: Option<String>,
+    pub lat: f64,
+    pub lng: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
 pub struct AgeRange {
     pub min: i32,
@@ -270,7 +446,7 @@ impl Person {
     /// `None` if not found, or an `Error` if the database operation fails.
     pub async fn get(id: &RecordId) -> Result<Option<Self>> {
         let _span = db_span!("Person::get", id.to_raw_string()).entered();
-        match DB.select(id).await {
+        match DB.timed(DB.select(id)).await {
             Ok(person) => Ok(person),
             Err(e) => {
                 log_error!(e, "Failed to get person");
@@ -286,7 +462,7 @@ impl Person {
     /// Returns an `Error` if the update operation fails.
     pub async fn update(&self) -> Result<Option<Self>> {
         let _span = db_span!("Person::update", self.id.to_raw_string()).entered();
-        match DB.update(&self.id).content(self.clone()).await {
+        match DB.timed(DB.update(&self.id).content(self.clone())).await {
             Ok(person) => Ok(person),
             Err(e) => {
                 log_error!(e, "Failed to update person");
@@ -302,7 +478,7 @@ impl Person {
     /// Returns an `Error` if the deletion fails.
     pub async fn delete(&self) -> Result<Option<Self>> {
         let _span = db_span!("Person::delete", self.id.to_raw_string()).entered();
-        match DB.delete(&self.id).await {
+        match DB.timed(DB.delete(&self.id)).await {
             Ok(person) => Ok(person),
             Err(e) => {
                 log_error!(e, "Failed to delete person");
@@ -434,7 +610,50 @@ impl Person {
             .await?;
 
         let persons: Vec<Person> = response.take(0)?;
-        Ok(persons.into_iter().next())
+        let person = persons.into_iter().next();
+
+        if let Some(person) = &person {
+            let stored: Option<String> = DB
+                .query("SELECT VALUE password FROM ONLY $id")
+                .bind(("id", person.id.clone()))
+                .await?
+                .take(0)?;
+            if let Some(stored) = stored {
+                Self::upgrade_password_hash(&person.id, password, &stored).await;
+            }
+        }
+
+        Ok(person)
+    }
+
+    /// Re-hash `password` at the current Argon2 cost when the just-verified
+    /// `stored_hash` used weaker parameters (see [`auth::needs_rehash`]).
+    /// Best-effort: a failure is logged and the login proceeds on the old
+    /// hash, which keeps verifying.
+    async fn upgrade_password_hash(id: &RecordId, password: &str, stored_hash: &str) {
+        if !auth::needs_rehash(stored_hash) {
+            return;
+        }
+        let new_hash = match auth::hash_password(password).await {
+            Ok(hash) => hash,
+            Err(e) => {
+                error!("Failed to re-hash password for {}: {}", id.display(), e);
+                return;
+            }
+        };
+        match DB
+            .query("UPDATE $id SET password = $password")
+            .bind(("id", id.clone()))
+            .bind(("password", new_hash))
+            .await
+        {
+            Ok(_) => debug!("Upgraded password hash for {}", id.display()),
+            Err(e) => error!(
+                "Failed to store re-hashed password for {}: {}",
+                id.display(),
+                e
+            ),
+        }
     }
 
     /// Retrieves all persons from the database.
@@ -449,24 +668,22 @@ impl Person {
         Ok(persons)
     }
 
-    /// Retrieves persons with pagination.
-    ///
-    /// # Arguments
-    /// * `limit` - Maximum number of records to return
-    /// * `offset` - Number of records to skip
+    /// Retrieves one page of persons, oldest first.
     ///
     /// # Returns
-    /// A `Result` containing a `Vec<Person>` with the requested page of records.
-    pub async fn get_paginated(limit: usize, offset: usize) -> Result<Vec<Self>> {
-        let sql = "SELECT * OMIT embedding, embedding_text FROM person LIMIT $limit START $offset";
+    /// A `Result` containing a [`Page`] with the requested records and the
+    /// total person count.
+    pub async fn get_page(page: PageQuery) -> Result<Page<Self>> {
         let mut response = DB
-            .query(sql)
-            .bind(("limit", limit))
-            .bind(("offset", offset))
+            .query("SELECT * OMIT embedding, embedding_text FROM person ORDER BY created_at ASC LIMIT $limit START $offset")
+            .query("SELECT VALUE count() FROM person GROUP ALL")
+            .bind(("limit", page.limit()))
+            .bind(("offset", page.offset()))
             .await?;
 
         let persons: Vec<Person> = response.take(0)?;
-        Ok(persons)
+        let total: Option<u64> = response.take(1)?;
+        Ok(Page::new(persons, total.unwrap_or(0), page))
     }
 
     /// Searches for persons by skill.
@@ -486,22 +703,214 @@ impl Person {
 
     /// Searches for persons by location.
     ///
+    /// Matches the structured `profile.geo` fields (city, region, country,
+    /// or country code equal to the query, case-insensitive) or the display
+    /// string containing it. When a geocoder is configured the query is
+    /// resolved too, so "LA" also finds profiles geocoded to Los Angeles.
+    ///
     /// # Arguments
     /// * `location` - The location to search for
     ///
     /// # Returns
     /// A `Result` containing a `Vec<Person>` with matching records.
     pub async fn find_by_location(location: &str) -> Result<Vec<Self>> {
-        let sql = "SELECT * OMIT embedding, embedding_text FROM person WHERE profile.location CONTAINS $location";
-        let mut response = DB
+        let query = location.trim().to_lowercase();
+        let mut conditions = vec![
+            "string::lowercase(profile.location ?? '') CONTAINS $query",
+            "string::lowercase(profile.geo.city ?? '') = $query",
+            "string::lowercase(profile.geo.region ?? '') = $query",
+            "string::lowercase(profile.geo.country ?? '') = $query",
+            "profile.geo.country_code = $query",
+        ];
+
+        // Match the resolved place at its most specific level.
+        let place = geocoding::geocode(location).await;
+        if let Some(place) = &place {
+            if place.city.is_some() {
+                conditions.push(
+                    "(profile.geo.city = $city AND profile.geo.country_code = $country_code)",
+                );
+            } else if place.region.is_some() {
+                conditions.push(
+                    "(profile.geo.region = $region AND profile.geo.country_code = $country_code)",
+                );
+            } else if place.country_code.is_some() {
+                conditions.push("profile.geo.country_code = $country_code");
+            }
+        }
+
+        let sql = format!(
+            "SELECT * OMIT embedding, embedding_text FROM person WHERE {}",
+            conditions.join(" OR ")
+        );
+        let mut query = DB.query(sql).bind(("query", query));
+        if let Some(place) = place {
+            query = query
+                .bind(("city", place.city))
+                .bind(("region", place.region))
+                .bind(("country_code", place.country_code));
+        }
+
+        let persons: Vec<Person> = query.await?.take(0)?;
+        Ok(persons)
+    }
+
+    /// Public profiles geocoded within `radius_km` of a point (crew near a
+    /// shoot), nearest first, with their distance in kilometres.
+    ///
+    /// # Arguments
+    /// * `lat` / `lng` - The centre, in degrees
+    /// * `radius_km` - The search radius
+    /// * `limit` - The maximum number of people to return
+    pub async fn find_near(
+        lat: f64,
+        lng: f64,
+        radius_km: f64,
+        limit: usize,
+    ) -> Result<Vec<(Self, f64)>> {
+        // The bounding box narrows the scan; exact distances are checked below.
+        let (min_lat, max_lat, min_lng, max_lng) = geocoding::bounding_box(lat, lng, radius_km);
+        let sql = "SELECT * OMIT embedding, embedding_text FROM person
+             WHERE verification_status != 'unverified'
+               AND profile.is_private != true
+               AND profile.geo.lat >= $min_lat AND profile.geo.lat <= $max_lat
+               AND profile.geo.lng >= $min_lng AND profile.geo.lng <= $max_lng";
+        let persons: Vec<Person> = DB
             .query(sql)
-            .bind(("location", location.to_string()))
-            .await?;
+            .bind(("min_lat", min_lat))
+            .bind(("max_lat", max_lat))
+            .bind(("min_lng", min_lng))
+            .bind(("max_lng", max_lng))
+            .await?
+            .take(0)?;
 
-        let persons: Vec<Person> = response.take(0)?;
+        let mut nearby: Vec<(Self, f64)> = persons
+            .into_iter()
+            .filter_map(|person| {
+                let geo = person.profile.as_ref()?.geo.as_ref()?;
+                let distance = geocoding::distance_km(lat, lng, geo.lat, geo.lng);
+                (distance <= radius_km).then_some((person, distance))
+            })
+            .collect();
+        nearby.sort_by(|a, b| a.1.total_cmp(&b.1));
+        nearby.truncate(limit);
+        Ok(nearby)
+    }
+
+    /// Retrieves one page of the public people directory: verified,
+    /// non-private profiles with at least a name, headline, or bio,
+    /// identity-verified first and then newest first.
+    ///
+    /// # Arguments
+    /// * `skill` - Only include people listing this exact skill
+    /// * `location` - Only include people whose location contains this text
+    ///   (case-insensitive)
+    /// * `availability` - Only include people with this availability status
+    /// * `limit` / `offset` - The page window
+    pub async fn directory_page(
+        skill: Option<&str>,
+        location: Option<&str>,
+        availability: Option<AvailabilityStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Self>> {
+        let mut conditions = vec![
+            "verification_status != 'unverified'",
+            "profile.is_private != true",
+            "(profile.name IS NOT NULL OR profile.headline IS NOT NULL OR profile.bio IS NOT NULL)",
+        ];
+        if skill.is_some() {
+            conditions.push("profile.skills CONTAINS $skill");
+        }
+        if location.is_some() {
+            conditions.push(
+                "string::lowercase(profile.location ?? '') CONTAINS string::lowercase($location)",
+            );
+        }
+        if availability.is_some() {
+            conditions.push("profile.availability_status = $availability");
+        }
+
+        let sql = format!(
+            "SELECT *, verification_status = 'identity' AS _vord OMIT embedding, embedding_text FROM person
+             WHERE {}
+             ORDER BY _vord DESC, created_at DESC
+             LIMIT $limit START $offset",
+            conditions.join(" AND ")
+        );
+
+        let mut query = DB
+            .query(sql)
+            .bind(("limit", limit as i64))
+            .bind(("offset", offset as i64));
+        if let Some(skill) = skill {
+            query = query.bind(("skill", skill.to_string()));
+        }
+        if let Some(location) = location {
+            query = query.bind(("location", location.to_string()));
+        }
+        if let Some(availability) = availability {
+            query = query.bind(("availability", availability.as_str().to_string()));
+        }
+
+        let persons: Vec<Person> = query.await?.take(0)?;
         Ok(persons)
     }
 
+    /// Skills listed on public profiles with the number of people listing
+    /// each, most used first (ties alphabetical).
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum number of skills to return
+    pub async fn distinct_skills(limit: usize) -> Result<Vec<(String, u64)>> {
+        #[derive(Deserialize, SurrealValue)]
+        struct SkillCount {
+            skill: String,
+            uses: u64,
+        }
+
+        let sql = "SELECT skill, count() AS uses FROM (
+                SELECT profile.skills AS skill FROM person
+                WHERE verification_status != 'unverified' AND profile.is_private != true
+                SPLIT skill
+            )
+            WHERE skill IS NOT NONE AND skill != ''
+            GROUP BY skill
+            ORDER BY uses DESC, skill ASC
+            LIMIT $limit";
+        let rows: Vec<SkillCount> = DB.query(sql).bind(("limit", limit as i64)).await?.take(0)?;
+        Ok(rows.into_iter().map(|r| (r.skill, r.uses)).collect())
+    }
+
+    /// [`Person::distinct_skills`] (up to [`KNOWN_SKILLS_LIMIT`]), reused
+    /// for [`KNOWN_SKILLS_TTL`] so typeahead keystrokes and page renders
+    /// don't each re-aggregate every profile. A failed refresh keeps
+    /// serving the stale list (or nothing, before the first success).
+    pub async fn known_skills() -> Arc<Vec<(String, u64)>> {
+        if let Some((fetched, skills)) = KNOWN_SKILLS.lock().unwrap().as_ref()
+            && fetched.elapsed() < KNOWN_SKILLS_TTL
+        {
+            return Arc::clone(skills);
+        }
+
+        match Self::distinct_skills(KNOWN_SKILLS_LIMIT).await {
+            Ok(skills) => {
+                let skills = Arc::new(skills);
+                *KNOWN_SKILLS.lock().unwrap() = Some((Instant::now(), Arc::clone(&skills)));
+                skills
+            }
+            Err(e) => {
+                error!("Failed to aggregate known skills: {}", e);
+                KNOWN_SKILLS
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(|(_, skills)| Arc::clone(skills))
+                    .unwrap_or_default()
+            }
+        }
+    }
+
     /// Creates a simplified version of the Person for session/auth purposes.
     /// This excludes sensitive data like password and detailed profile info.
     pub fn to_session_user(&self) -> SessionUser {
@@ -514,6 +923,7 @@ impl Person {
                 .as_ref()
                 .and_then(|p| p.name.clone())
                 .unwrap_or_else(|| self.username.clone()),
+            timezone: self.timezone.clone(),
         }
     }
 
@@ -525,26 +935,7 @@ impl Person {
             .or_else(|| self.profile.as_ref().and_then(|p| p.name.clone()))
             .unwrap_or_else(|| self.username.clone());
 
-        let parts: Vec<&str> = display_name.split_whitespace().collect();
-
-        if parts.len() >= 2 {
-            // Use first letter of first and last name
-            let first = parts[0].chars().next().unwrap_or('?');
-            let last = parts[parts.len() - 1].chars().next().unwrap_or('?');
-            format!("{}{}", first, last).to_uppercase()
-        } else if !parts.is_empty() {
-            // Use first two letters of single name
-            let chars: Vec<char> = parts[0].chars().take(2).collect();
-            if chars.len() == 2 {
-                format!("{}{}", chars[0], chars[1]).to_uppercase()
-            } else if chars.len() == 1 {
-                format!("{}", chars[0]).to_uppercase()
-            } else {
-                "??".to_string()
-            }
-        } else {
-            "??".to_string()
-        }
+        crate::avatar::initials(&display_name)
     }
 
     /// Get the avatar URL if one exists
@@ -558,7 +949,7 @@ impl Person {
             if url.starts_with("http://") || url.starts_with("https://") {
                 url
             } else {
-                format!("{}{}", crate::config::app_url(), url)
+                format!("{}{}", crate::config::public_base_url(), url)
             }
         })
     }
@@ -572,6 +963,27 @@ impl Person {
             .unwrap_or_else(|| self.username.clone())
     }
 
+    /// How much of this profile a viewer may see: everything for the owner,
+    /// nothing beyond name/avatar when the profile is private, otherwise the
+    /// public view with contact details and `private_fields` stripped.
+    pub fn profile_access(&self, is_owner: bool) -> ProfileAccess {
+        if is_owner {
+            ProfileAccess::Owner
+        } else if self.profile.as_ref().is_some_and(|p| p.is_private) {
+            ProfileAccess::Private
+        } else {
+            ProfileAccess::Visitor
+        }
+    }
+
+    /// Optional fields the owner hides from visitors.
+    pub fn private_fields(&self) -> &[String] {
+        self.profile
+            .as_ref()
+            .map(|p| p.private_fields.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Updates a user's profile information.
     ///
     /// # Arguments
@@ -583,7 +995,9 @@ impl Person {
     /// * `website` - Optional new website
     /// * `skills` - Optional comma-separated list of skills
     /// * `languages` - Optional comma-separated list of languages
-    /// * `availability` - Optional availability status
+    /// * `availability` - Optional free-text availability note
+    /// * `availability_status` - Optional [`AvailabilityStatus`] value; when
+    ///   absent but a note is given, the status is inferred from the note
     ///
     /// # Returns
     /// * `Result<Option<Person>>` - The updated person record if successful
@@ -598,6 +1012,7 @@ impl Person {
         skills: Option<String>,
         languages: Option<String>,
         availability: Option<String>,
+        availability_status: Option<String>,
         social_links: Option<Vec<SocialLink>>,
         reels: Option<Vec<Reel>>,
         photos: Option<Vec<Photo>>,
@@ -624,16 +1039,13 @@ impl Person {
             Some(p) => {
                 // Verify ownership: The fetched person's ID must match the requested user_id
                 // This is implicit since find_by_id already filters by user_id, but we can be explicit
-                if p.id.to_raw_string() != user_id && !user_id.starts_with("person:") {
-                    // Also check if user_id is missing the "person:" prefix
-                    let full_id = format!("person:{}", user_id);
-                    if p.id.to_raw_string() != full_id {
-                        error!(
-                            "User {} attempted to update profile for different user",
-                            user_id
-                        );
-                        return Err(Error::Forbidden);
-                    }
+                // user_id may arrive with or without the "person:" prefix
+                if !p.id.matches_id(user_id, "person") {
+                    error!(
+                        "User {} attempted to update profile for different user",
+                        user_id
+                    );
+                    return Err(Error::Forbidden);
                 }
                 p
             }
@@ -667,9 +1079,12 @@ impl Person {
                 headline: None,
                 bio: None,
                 location: None,
+                geo: None,
                 website: None,
                 phone: None,
                 is_public: false,
+                is_private: false,
+                private_fields: Vec::new(),
                 height_mm: None,
                 weight_kg: None,
                 body_type: None,
@@ -686,6 +1101,7 @@ impl Person {
                 unions: Vec::new(),
                 languages: Vec::new(),
                 availability: None,
+                availability_status: None,
                 education: Vec::new(),
                 awards: Vec::new(),
                 reels: Vec::new(),
@@ -714,7 +1130,14 @@ impl Person {
                 profile.bio = if b.is_empty() { None } else { Some(b) };
             }
             if let Some(l) = location {
-                profile.location = if l.is_empty() { None } else { Some(l) };
+                let l = if l.is_empty() { None } else { Some(l) };
+                if l != profile.location || profile.geo.is_none() {
+                    profile.geo = match &l {
+                        Some(l) => geocoding::geocode(l).await,
+                        None => None,
+                    };
+                }
+                profile.location = l;
             }
             if let Some(w) = website {
                 profile.website = if w.is_empty() { None } else { Some(w) };
@@ -739,6 +1162,23 @@ impl Person {
                         .collect()
                 };
             }
+            match availability_status.as_deref().map(str::trim) {
+                Some("") => profile.availability_status = None,
+                Some(status) => {
+                    let status = AvailabilityStatus::parse(status).ok_or_else(|| {
+                        Error::Validation(format!("Unknown availability status: {status}"))
+                    })?;
+                    profile.availability_status = Some(status.as_str().to_string());
+                }
+                None => {
+                    if let Some(status) = availability
+                        .as_deref()
+                        .and_then(AvailabilityStatus::from_free_text)
+                    {
+                        profile.availability_status = Some(status.as_str().to_string());
+                    }
+                }
+            }
             if let Some(a) = availability {
                 profile.availability = if a.is_empty() { None } else { Some(a) };
             }
@@ -1046,13 +1486,25 @@ impl Person {
         }
 
         let persons: Vec<PersonWithPassword> = response.take(0)?;
-        let person_with_password = persons.into_iter().next().ok_or(Error::Unauthorized)?;
+        let Some(person_with_password) = persons.into_iter().next() else {
+            // Run a throwaway verification so an unknown identifier costs the
+            // same Argon2 time as a wrong password (no enumeration oracle).
+            auth::dummy_verify_password(&password).await;
+            debug!("Sign-in attempt for unknown identifier");
+            return Err(Error::Unauthorized);
+        };
 
         // Verify the password
         if !auth::verify_password(&password, &person_with_password.password).await? {
             debug!("Invalid password for user: {}", identifier);
             return Err(Error::Unauthorized);
         }
+        Self::upgrade_password_hash(
+            &person_with_password.id,
+            &password,
+            &person_with_password.password,
+        )
+        .await;
 
         // Check email verification status
         if person_with_password.verification_status == "unverified" {
@@ -1219,7 +1671,7 @@ impl Person {
     /// GDPR-compliant cascade delete. Scrubs every reference to this person:
     /// messages they sent + conversations they participated in, notifications
     /// (both received and triggered by their messages), media (DB rows + S3
-    /// objects), likes, job applications, OIDC consents/tokens, verification
+    /// objects), likes, comments, job applications, OIDC consents/tokens, verification
     /// codes, invitations they sent, profile-view rows, activity events,
     /// production scripts (+ S3 PDFs), locations/jobs they created,
     /// person-owned equipment + rentals, security events, involvements,
@@ -1297,6 +1749,8 @@ impl Person {
             DELETE conversation WHERE participant_a = $pid OR participant_b = $pid;
             DELETE notification WHERE person_id = $pid;
             DELETE FROM likes WHERE in = $pid OR out = $pid;
+            DELETE FROM likes WHERE out IN (SELECT VALUE id FROM comment WHERE author = $pid);
+            DELETE comment WHERE author = $pid;
             DELETE FROM application WHERE in = $pid;
             DELETE FROM consent_grant WHERE in = $pid;
             DELETE access_token WHERE person = $pid;
@@ -1315,6 +1769,7 @@ impl Person {
             DELETE equipment_kit WHERE owner_person = $pid;
             DELETE equipment WHERE owner_person = $pid;
             DELETE security_event WHERE subject = $pid;
+            DELETE FROM likes WHERE out IN (SELECT VALUE id FROM media WHERE uploaded_by = $pid);
             DELETE media WHERE uploaded_by = $pid;
             DELETE call_time WHERE person = $pid;
             UPDATE call_sheet SET generated_by = NONE WHERE generated_by = $pid;
@@ -1345,6 +1800,58 @@ impl Person {
         info!(person = %pid_str, "GDPR cascade delete complete");
         Ok(())
     }
+
+    /// Everything SlateHub holds about a person, for GDPR data portability
+    /// (the counterpart to [`Person::delete_with_cascade`]).
+    ///
+    /// Covers the person record and profile, memberships, credits, owned
+    /// equipment, kits and locations, follows, and referenced media. The
+    /// password hash and derived search embeddings are left out, as is
+    /// anything private to other users: follows list only the followed
+    /// record, and followers are reported as a count.
+    pub async fn export_data(person_id: &RecordId) -> Result<serde_json::Value> {
+        let sql = "
+            SELECT * OMIT password, embedding, embedding_text FROM ONLY $pid;
+            SELECT id, out AS target, record::tb(out) AS target_type,
+                   out.name ?? out.title AS target_name, role, production_roles,
+                   permissions, invitation_status, joined_at, request_note
+            FROM member_of WHERE in = $pid ORDER BY joined_at;
+            SELECT * OMIT in FROM involvement WHERE in = $pid ORDER BY timestamp;
+            SELECT * FROM equipment WHERE owner_person = $pid ORDER BY created_at;
+            SELECT * FROM equipment_kit WHERE owner_person = $pid;
+            SELECT * OMIT embedding, embedding_text FROM location WHERE created_by = $pid ORDER BY created_at;
+            SELECT out AS target, record::tb(out) AS target_type, created_at
+            FROM likes WHERE in = $pid ORDER BY created_at;
+            RETURN count((SELECT VALUE id FROM likes WHERE out = $pid));
+            SELECT * FROM media
+            WHERE id INSIDE array::concat($pid.profile.media_other ?? [], [$pid.profile.resume]);
+        ";
+
+        let mut response = DB.query(sql).bind(("pid", person_id.clone())).await?;
+        let person: Option<serde_json::Value> = response.take(0)?;
+        let person = person.ok_or(Error::NotFound)?;
+        let memberships: Vec<serde_json::Value> = response.take(1)?;
+        let credits: Vec<serde_json::Value> = response.take(2)?;
+        let equipment: Vec<serde_json::Value> = response.take(3)?;
+        let equipment_kits: Vec<serde_json::Value> = response.take(4)?;
+        let locations: Vec<serde_json::Value> = response.take(5)?;
+        let follows: Vec<serde_json::Value> = response.take(6)?;
+        let follower_count: Option<u64> = response.take(7)?;
+        let media: Vec<serde_json::Value> = response.take(8)?;
+
+        Ok(serde_json::json!({
+            "exported_at": chrono::Utc::now().to_rfc3339(),
+            "person": person,
+            "memberships": memberships,
+            "credits": credits,
+            "equipment": equipment,
+            "equipment_kits": equipment_kits,
+            "locations": locations,
+            "follows": follows,
+            "follower_count": follower_count.unwrap_or(0),
+            "media": media,
+        }))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -1364,6 +1871,9 @@ pub struct SessionUser {
     pub username: String,
     pub email: String,
     pub name: String,
+    /// The person's timezone preference, see [`Person::timezone`].
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 impl SessionUser {
@@ -1399,6 +1909,10 @@ pub struct CreateUser {
     /// Proof-of-Work solution
     #[serde(default)]
     pub pow_solution: Option<String>,
+    /// hCaptcha/Turnstile token, when a captcha is configured
+    /// (see [`crate::captcha`]).
+    #[serde(default, alias = "h-captcha-response", alias = "cf-turnstile-response")]
+    pub captcha_token: Option<String>,
     /// Landing-page campaign id carried through from `/a/{campaign}` → `/signup`
     /// as a hidden field. Attribution only — never affects account creation.
     #[serde(default)]
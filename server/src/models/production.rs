@@ -1,21 +1,25 @@
 //! Production records and their membership/credit graph.
 //!
 //! Owns the `production` table plus its `member_of` edges (ownership and
-//! membership), and reads the reference tables `production_type`,
-//! `production_status`, `budget_level`, `production_tier`, and `role` for
-//! dropdown values. Credit edges are delegated to [`crate::models::involvement`].
-//! Called by the production routes (`routes/productions.rs`,
-//! `routes/productions_manage.rs`), `routes/api.rs`, `routes/media.rs`,
-//! `routes/auth.rs`, and `services/invitation.rs`.
+//! membership) and `production_status_change` history, and reads the
+//! reference tables `production_type`, `production_status`, `budget_level`,
+//! `production_tier`, and `role` for dropdown values. Credit edges are
+//! delegated to [`crate::models::involvement`]. Called by the production
+//! routes (`routes/productions.rs`, `routes/productions_manage.rs`),
+//! `routes/api.rs`, `routes/media.rs`, `routes/auth.rs`, and
+//! `services/invitation.rs`.
 
 use crate::db::DB;
 use crate::error::Error;
-use crate::record_id_ext::RecordIdExt;
+use crate::models::media::DELETE_TARGET_MEDIA;
+use crate::models::notification::NotificationModel;
+use crate::record_id_ext::{RecordIdExt, normalize_record_id};
+use crate::services::activity::log_activity;
 use crate::services::embedding::build_production_embedding_text;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use surrealdb::types::{RecordId, SurrealValue};
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// A production photo (gallery item)
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
@@ -183,6 +187,39 @@ pub struct ProductionMember {
     pub is_verified: bool, // Whether org is verified (gold checkmark)
 }
 
+/// One row of a production's exported crew list
+/// (see [`ProductionModel::crew_list`]). Contact fields are already
+/// redacted: they are only filled when the member has opted to show them.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct CrewListEntry {
+    pub name: String,
+    /// Credited production roles, e.g. ["Gaffer"].
+    #[serde(default)]
+    #[surreal(default)]
+    pub production_roles: Vec<String>,
+    /// Departments of those roles, from the `role` → `department` links.
+    #[serde(default)]
+    #[surreal(default)]
+    pub departments: Vec<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+/// A public person suggested as crew for a production
+/// (see [`ProductionModel::recommend_crew`]).
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct CrewRecommendation {
+    pub id: String,
+    pub username: String,
+    pub name: Option<String>,
+    pub headline: Option<String>,
+    pub avatar: Option<String>,
+    pub location: Option<String>,
+    /// The person's skills that overlap the required ones, as they wrote them.
+    pub matched_skills: Vec<String>,
+    pub match_count: u64,
+}
+
 /// The canonical six-phase production lifecycle.
 ///
 /// A production's stored `status` string (sourced from the
@@ -251,6 +288,30 @@ impl LifecyclePhase {
         Self::ORDERED.iter().position(|p| *p == self)
     }
 
+    /// Phases a production may move to from this one: the next phase, a
+    /// step back (reshoots, a re-cut), or `Canceled`. Post-production may
+    /// go straight to `Released` for self-distributed work, a release is
+    /// final (short of stepping back into distribution), and a canceled
+    /// project can only be revived into `Development`.
+    pub fn next_phases(self) -> &'static [LifecyclePhase] {
+        use LifecyclePhase::*;
+        match self {
+            Development => &[PreProduction, Canceled],
+            PreProduction => &[Production, Development, Canceled],
+            Production => &[PostProduction, PreProduction, Canceled],
+            PostProduction => &[MarketingDistribution, Released, Production, Canceled],
+            MarketingDistribution => &[Released, PostProduction, Canceled],
+            Released => &[MarketingDistribution],
+            Canceled => &[Development],
+        }
+    }
+
+    /// Whether a production in this phase may move to `to`. Staying in the
+    /// same phase (e.g. `Completed` → `Festival`) is always allowed.
+    pub fn can_move_to(self, to: LifecyclePhase) -> bool {
+        self == to || self.next_phases().contains(&to)
+    }
+
     /// Human-readable label for the phase.
     pub fn label(self) -> &'static str {
         match self {
@@ -336,6 +397,62 @@ impl LifecycleView {
     }
 }
 
+/// Check that a production's status may change from `from` to `to`
+/// (stored status strings, compared by [`LifecyclePhase`]).
+///
+/// # Errors
+/// `Error::Validation` naming both statuses for a move
+/// [`LifecyclePhase::can_move_to`] rejects.
+pub fn validate_status_transition(from: &str, to: &str) -> Result<(), Error> {
+    let (from_phase, to_phase) = (
+        LifecyclePhase::from_status(from),
+        LifecyclePhase::from_status(to),
+    );
+    if from_phase.can_move_to(to_phase) {
+        return Ok(());
+    }
+    Err(Error::Validation(format!(
+        "A production can't move from {} to {}. From {} it can move to: {}.",
+        from,
+        to,
+        from_phase.label(),
+        from_phase
+            .next_phases()
+            .iter()
+            .map(|phase| phase.label())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
+
+/// The subset of `statuses` a production currently at `current` may be
+/// set to, in their original order — what the edit form offers. `current`
+/// itself is always included (first, when it isn't one of `statuses`), so
+/// saving the form without touching the status never fails.
+pub fn allowed_statuses(current: &str, statuses: &[String]) -> Vec<String> {
+    let phase = LifecyclePhase::from_status(current);
+    let mut allowed: Vec<String> = statuses
+        .iter()
+        .filter(|status| phase.can_move_to(LifecyclePhase::from_status(status)))
+        .cloned()
+        .collect();
+    if !allowed.iter().any(|status| status == current) {
+        allowed.insert(0, current.to_string());
+    }
+    allowed
+}
+
+/// One recorded change of a production's status (see
+/// [`ProductionModel::record_status_change`]).
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct StatusChange {
+    pub from_status: String,
+    pub to_status: String,
+    /// Who made the change.
+    pub changed_by: Option<RecordId>,
+    pub changed_at: DateTime<Utc>,
+}
+
 /// Per-stage counters for the management Overview dashboard.
 ///
 /// Zero values are meaningful: the dashboard shows "not started" states
@@ -447,7 +564,7 @@ impl ProductionModel {
         );
 
         // Generate slug from title
-        let slug = crate::text::slugify(&data.title);
+        let slug = generate_slug(&data.title).await?;
 
         // Start a transaction
         let _response = DB
@@ -695,6 +812,12 @@ impl ProductionModel {
         // Fetch current production to merge with updates for embedding
         let current = Self::get(production_id).await?;
 
+        if let Some(status) = &data.status
+            && *status != current.status
+        {
+            validate_status_transition(&current.status, status)?;
+        }
+
         let mut update_fields = Vec::new();
 
         if data.title.is_some() {
@@ -800,42 +923,122 @@ impl ProductionModel {
         Ok(production)
     }
 
+    /// Record that `production` moved from status `from` to its current
+    /// status: appends to its status history, logs a
+    /// `production_status_change` activity event, and notifies the other
+    /// accepted person members. Call after [`Self::update`] changed the
+    /// status; notification failures are logged, not returned. A failed
+    /// history insert is returned, but the status itself is already saved,
+    /// so callers should log it rather than fail the request.
+    pub async fn record_status_change(
+        production: &Production,
+        from: &str,
+        changed_by: &str,
+    ) -> Result<(), Error> {
+        let person = normalize_record_id(changed_by, "person");
+        let mut response = DB
+            .query(
+                "CREATE production_status_change SET production = $production, \
+                 from_status = $from, to_status = $to, changed_by = $person RETURN NONE",
+            )
+            .query(
+                "SELECT VALUE <string> in FROM member_of WHERE out = $production \
+                 AND invitation_status = 'accepted' AND record::tb(in) = 'person' AND in != $person",
+            )
+            .bind(("production", production.id.clone()))
+            .bind(("from", from.to_string()))
+            .bind(("to", production.status.clone()))
+            .bind(("person", person.clone()))
+            .await?
+            .check()?;
+        let members: Vec<String> = response.take(1)?;
+
+        let link = format!("/productions/{}", production.slug);
+        log_activity(
+            Some(&person.to_raw_string()),
+            "production_status_change",
+            &link,
+        );
+
+        let title = format!("{} moved to {}", production.title, production.status);
+        let message = format!(
+            "{} is now in {} (was {}).",
+            production.title, production.status, from
+        );
+        let related_id = production.id.to_raw_string();
+        let notifications = NotificationModel::new();
+        for member in members {
+            if let Err(e) = notifications
+                .create(
+                    &member,
+                    "general",
+                    &title,
+                    &message,
+                    Some(&link),
+                    Some(&related_id),
+                )
+                .await
+            {
+                warn!("Failed to notify {} of status change: {}", member, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// A production's recorded status changes, newest first.
+    pub async fn status_history(production_id: &RecordId) -> Result<Vec<StatusChange>, Error> {
+        let history: Vec<StatusChange> = DB
+            .query(
+                "SELECT from_status, to_status, changed_by, changed_at \
+                 FROM production_status_change WHERE production = $production \
+                 ORDER BY changed_at DESC",
+            )
+            .bind(("production", production_id.clone()))
+            .await?
+            .take(0)?;
+        Ok(history)
+    }
+
     /// Delete a production
     pub async fn delete(production_id: &RecordId) -> Result<(), Error> {
         debug!("Deleting production: {}", production_id.display());
 
-        // Start transaction
-        DB.query("BEGIN TRANSACTION")
+        crate::db::transaction(async |tx| {
+            // Delete all member_of and involvement relations to this production
+            tx.query("DELETE member_of WHERE out = $id; DELETE involvement WHERE out = $id")
+                .bind(("id", production_id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete relations: {}", e)))?;
+
+            // Delete likes on this production and on its comments, then the comments
+            tx.query(
+                "DELETE likes WHERE out = $id OR out IN (SELECT VALUE id FROM comment WHERE target = $id); \
+                 DELETE comment WHERE target = $id; \
+                 DELETE production_status_change WHERE production = $id",
+            )
+            .bind(("id", production_id.clone()))
             .await
-            .map_err(|e| Error::Database(format!("Failed to start transaction: {}", e)))?;
-
-        // Delete all member_of relations to this production
-        DB.query(format!(
-            "DELETE member_of WHERE out = {}",
-            production_id.display()
-        ))
-        .await
-        .map_err(|e| Error::Database(format!("Failed to delete member relations: {}", e)))?;
-
-        // Delete all involvement relations to this production
-        DB.query(format!(
-            "DELETE involvement WHERE out = {}",
-            production_id.display()
-        ))
+            .and_then(|r| r.check())
+            .map_err(|e| Error::Database(format!("Failed to delete comments and likes: {}", e)))?;
+
+            // Delete its images' media rows
+            tx.query(DELETE_TARGET_MEDIA)
+                .bind(("target", production_id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete media: {}", e)))?;
+
+            // Delete the production
+            tx.query("DELETE $id")
+                .bind(("id", production_id.clone()))
+                .await
+                .and_then(|r| r.check())
+                .map_err(|e| Error::Database(format!("Failed to delete production: {}", e)))?;
+
+            Ok(())
+        })
         .await
-        .map_err(|e| Error::Database(format!("Failed to delete involvement relations: {}", e)))?;
-
-        // Delete the production
-        DB.query(format!("DELETE {}", production_id.display()))
-            .await
-            .map_err(|e| Error::Database(format!("Failed to delete production: {}", e)))?;
-
-        // Commit transaction
-        DB.query("COMMIT TRANSACTION")
-            .await
-            .map_err(|e| Error::Database(format!("Failed to commit transaction: {}", e)))?;
-
-        Ok(())
     }
 
     /// Aggregate counters for the management-workspace Overview dashboard.
@@ -978,6 +1181,124 @@ impl ProductionModel {
         Ok(members)
     }
 
+    /// Accepted members of a production as a crew list, grouped by their
+    /// first department (members without one last), then by name. Contact
+    /// details follow the profile rules: a person's email and phone only
+    /// when their profile's `is_public` contact opt-in is set and the
+    /// profile isn't private, an organization's contact email and phone
+    /// only when it is public.
+    pub async fn crew_list(production_id: &RecordId) -> Result<Vec<CrewListEntry>, Error> {
+        debug!(
+            "Building crew list for production: {}",
+            production_id.display()
+        );
+
+        let query = "
+            SELECT
+                in.name ?? in.username ?? '' AS name,
+                production_roles ?? [] AS production_roles,
+                array::distinct(
+                    (SELECT VALUE department.name FROM role
+                     WHERE name IN ($parent.production_roles ?? []) AND department != NONE)
+                ) AS departments,
+                IF <string> type::table(in) = 'person' THEN
+                    (IF in.profile.is_public = true AND in.profile.is_private != true
+                     THEN in.email ELSE NONE END)
+                ELSE
+                    (IF in.public = true THEN in.contact_email ELSE NONE END)
+                END AS email,
+                IF <string> type::table(in) = 'person' THEN
+                    (IF in.profile.is_public = true AND in.profile.is_private != true
+                     THEN in.profile.phone ELSE NONE END)
+                ELSE
+                    (IF in.public = true THEN in.phone ELSE NONE END)
+                END AS phone
+            FROM member_of
+            WHERE out = $production AND invitation_status = 'accepted'
+            ORDER BY name ASC";
+
+        let mut crew: Vec<CrewListEntry> = DB
+            .query(query)
+            .bind(("production", production_id.clone()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to fetch crew list: {}", e)))?
+            .take(0)?;
+
+        // Stable: names stay alphabetical within each department.
+        crew.sort_by(
+            |a, b| match (a.departments.first(), b.departments.first()) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            },
+        );
+        Ok(crew)
+    }
+
+    /// Suggest public people whose skills overlap `required_skills`
+    /// (case-insensitive), most matches first. Among equal matches, people
+    /// whose location contains the production's location rank first, then
+    /// identity-verified people. Anyone already attached to the production
+    /// (a `member_of` edge in any state, or an `involvement` credit) is left
+    /// out, as are private and unverified profiles.
+    pub async fn recommend_crew(
+        production_id: &RecordId,
+        required_skills: &[String],
+        limit: usize,
+    ) -> Result<Vec<CrewRecommendation>, Error> {
+        let skills: Vec<String> = required_skills
+            .iter()
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if skills.is_empty() || limit == 0 {
+            return Ok(vec![]);
+        }
+        debug!(
+            "Recommending crew for production {} with skills {:?}",
+            production_id.display(),
+            skills
+        );
+
+        let query = "
+            LET $attached = array::union(
+                (SELECT VALUE in FROM member_of WHERE out = $production),
+                (SELECT VALUE in FROM involvement WHERE out = $production)
+            );
+            LET $near = string::lowercase((SELECT VALUE location FROM ONLY $production) ?? '');
+            SELECT *, array::len(matched_skills) AS match_count FROM (
+                SELECT
+                    <string> id AS id,
+                    username,
+                    profile.name AS name,
+                    profile.headline AS headline,
+                    profile.avatar AS avatar,
+                    profile.location AS location,
+                    array::filter(profile.skills ?? [], |$s| string::lowercase($s) INSIDE $skills) AS matched_skills,
+                    ($near != '' AND string::lowercase(profile.location ?? '') CONTAINS $near) AS _near,
+                    verification_status = 'identity' AS _vord
+                FROM person
+                WHERE verification_status != 'unverified'
+                  AND profile.is_private != true
+                  AND id NOTINSIDE $attached
+            )
+            WHERE array::len(matched_skills) > 0
+            ORDER BY match_count DESC, _near DESC, _vord DESC, username ASC
+            LIMIT $limit";
+
+        let mut result = DB
+            .query(query)
+            .bind(("production", production_id.clone()))
+            .bind(("skills", skills))
+            .bind(("limit", limit as i64))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to recommend crew: {e}")))?;
+
+        let recommendations: Vec<CrewRecommendation> = result.take(2)?;
+        Ok(recommendations)
+    }
+
     /// Check if a user or organization is a member of a production
     pub async fn is_member(production_id: &RecordId, member_id: &str) -> Result<bool, Error> {
         let member_rid = validate_record_id_str(member_id)?;
@@ -1007,6 +1328,27 @@ impl ProductionModel {
         Ok(false)
     }
 
+    /// Whether `member_id` has an accepted `member_of` edge to the production
+    /// — a pending invitation or join request doesn't count.
+    pub async fn is_accepted_member(
+        production_id: &RecordId,
+        member_id: &str,
+    ) -> Result<bool, Error> {
+        let member_rid = validate_record_id_str(member_id)?;
+        let mut result = DB
+            .query(
+                "SELECT VALUE count() FROM member_of \
+                 WHERE in = $member AND out = $production AND invitation_status = 'accepted' \
+                 GROUP ALL",
+            )
+            .bind(("member", member_rid))
+            .bind(("production", production_id.clone()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to check membership: {e}")))?;
+        let count: Option<i64> = result.take(0)?;
+        Ok(count.unwrap_or(0) > 0)
+    }
+
     /// Return the user's role on a production (`owner` / `admin` / `member`) if
     /// they have a direct `member_of` edge to it, otherwise `None`.
     /// Unlike [`can_edit`], this does NOT walk through org memberships — it's
@@ -1444,7 +1786,7 @@ impl ProductionModel {
         };
 
         // Generate slug from title
-        let slug = generate_slug(&title);
+        let slug = generate_slug(&title).await?;
 
         // Build embedding text for background update
         let embedding_text = build_production_embedding_text(
@@ -1512,7 +1854,7 @@ impl ProductionModel {
             title, year, source
         );
 
-        let slug = generate_slug(title);
+        let slug = generate_slug(title).await?;
         let release_date = year.map(|y| format!("{}-01-01", y));
 
         let embedding_text = build_production_embedding_text(
@@ -1600,6 +1942,31 @@ impl ProductionModel {
         Ok(false)
     }
 
+    /// Person ids ("person:key") of everyone who owns a production: direct
+    /// person owners plus the owners of any organization that owns it.
+    pub async fn get_owner_person_ids(production_id: &RecordId) -> Result<Vec<String>, Error> {
+        let owners: Vec<String> = DB
+            .query("SELECT VALUE <string> in FROM member_of WHERE out = $production AND role = 'owner'")
+            .bind(("production", production_id.clone()))
+            .await
+            .map_err(|e| Error::Database(format!("Failed to get production owners: {}", e)))?
+            .take(0)
+            .unwrap_or_default();
+
+        let org_model = crate::models::organization::OrganizationModel::new();
+        let mut person_ids = Vec::new();
+        for owner in owners {
+            if owner.starts_with("organization:") {
+                person_ids.extend(org_model.get_org_owners(&owner).await.unwrap_or_default());
+            } else {
+                person_ids.push(owner);
+            }
+        }
+        person_ids.sort();
+        person_ids.dedup();
+        Ok(person_ids)
+    }
+
     /// Claim an unclaimed production — creates owner member_of edge and promotes self-asserted credits
     pub async fn claim(production_id: &RecordId, claimer_id: &str) -> Result<(), Error> {
         let claimer_rid = validate_record_id_str(claimer_id)?;
@@ -1634,10 +2001,19 @@ impl ProductionModel {
     }
 }
 
-/// Generate a URL-friendly slug from a title.
+/// Generate an unused URL-friendly slug from a title.
 ///
-/// Thin wrapper over the canonical [`crate::text::slugify`], kept so the
-/// TMDB/scrape creation paths read naturally.
-fn generate_slug(title: &str) -> String {
-    crate::text::slugify(title)
+/// [`crate::util::slug::slugify_unique`] against the production table
+/// (`idx_production_slug` is UNIQUE, so a second "Untitled" would otherwise
+/// fail to insert).
+async fn generate_slug(title: &str) -> Result<String, Error> {
+    crate::util::slug::slugify_unique(title, |candidate| async move {
+        let taken: Option<RecordId> = DB
+            .query("SELECT VALUE id FROM production WHERE slug = $slug LIMIT 1")
+            .bind(("slug", candidate))
+            .await?
+            .take(0)?;
+        Ok(taken.is_some())
+    })
+    .await
 }
@@ -0,0 +1,364 @@
+//! Shoot days: a production's shooting schedule.
+//!
+//! Owns the `schedule_day` table (migration 012). Every statement is scoped
+//! to the production as well as the day id, so a member of one production
+//! can't reach another's days by guessing keys. Days are stored at midnight
+//! UTC of the calendar date; the general call is that date plus the entered
+//! wall-clock time, also as UTC — no timezone conversion, a call time is
+//! read as local to the set. The `schedule_scene`, `call_time`, and
+//! `call_sheet` rows hang off a day; call sheets are generated per day.
+//! A day also lists the equipment booked for it (migration 031); booking
+//! checks the item isn't out on a rental that covers the date.
+//! Called from the Schedule and Call Sheets tabs in
+//! `routes::productions_manage`.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, SurrealValue};
+use tracing::debug;
+
+use crate::{
+    db::DB,
+    error::Error,
+    models::equipment::EquipmentModel,
+    record_id_ext::{RecordIdExt, normalize_record_id},
+};
+
+/// Values accepted by the `schedule_day.status` ASSERT, in workflow order.
+pub const SHOOT_DAY_STATUSES: [&str; 5] =
+    ["planned", "confirmed", "shooting", "wrapped", "canceled"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct ShootDay {
+    pub id: RecordId,
+    pub date: DateTime<Utc>,
+    pub general_call: Option<DateTime<Utc>>,
+    pub location: Option<RecordId>,
+    /// `location.name`, followed through the record link.
+    pub location_name: Option<String>,
+    pub location_label: Option<String>,
+    pub notes: Option<String>,
+    pub status: String,
+    /// Equipment booked for the day, by name.
+    #[serde(default)]
+    pub equipment: Vec<ShootDayEquipment>,
+}
+
+/// An item booked for a shoot day.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct ShootDayEquipment {
+    pub id: RecordId,
+    pub name: String,
+}
+
+impl ShootDay {
+    /// The calendar date of the shoot.
+    pub fn day(&self) -> NaiveDate {
+        self.date.date_naive()
+    }
+
+    /// Wall-clock general call, if one is set.
+    pub fn call_time(&self) -> Option<NaiveTime> {
+        self.general_call.map(|call| call.time())
+    }
+
+    /// Where the day shoots: the linked location's name, else the free-text
+    /// label.
+    pub fn place(&self) -> Option<&str> {
+        self.location_name
+            .as_deref()
+            .or(self.location_label.as_deref())
+    }
+}
+
+/// Raw shoot-day form fields, as posted by the Schedule tab.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ShootDayForm {
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub call_time: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub location_label: String,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub status: String,
+}
+
+/// A validated shoot day, ready to write.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShootDayInput {
+    pub date: NaiveDate,
+    pub call_time: Option<NaiveTime>,
+    pub location: Option<RecordId>,
+    pub location_label: Option<String>,
+    pub notes: Option<String>,
+    pub status: String,
+}
+
+impl ShootDayInput {
+    /// Validate a submitted form. The date is required (`YYYY-MM-DD`); a
+    /// blank call time, location, label, or notes is left unset; a blank
+    /// status means `planned`. The location is only shape-checked here —
+    /// whether the member may use it is the caller's call.
+    pub fn parse(form: &ShootDayForm) -> Result<Self, Error> {
+        let date = NaiveDate::parse_from_str(form.date.trim(), "%Y-%m-%d")
+            .map_err(|_| Error::Validation("A valid shoot date is required".to_string()))?;
+
+        let call_time = match form.call_time.trim() {
+            "" => None,
+            time => Some(
+                NaiveTime::parse_from_str(time, "%H:%M")
+                    .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+                    .map_err(|_| Error::Validation("Call time must be HH:MM".to_string()))?,
+            ),
+        };
+
+        let location = match form.location.trim() {
+            "" => None,
+            id => {
+                let rid = normalize_record_id(id, "location");
+                if rid.table.as_str() != "location" {
+                    return Err(Error::Validation("Unknown location".to_string()));
+                }
+                Some(rid)
+            }
+        };
+
+        let status = match form.status.trim() {
+            "" => "planned",
+            status => SHOOT_DAY_STATUSES
+                .into_iter()
+                .find(|s| *s == status)
+                .ok_or_else(|| Error::Validation(format!("Unknown shoot day status: {status}")))?,
+        };
+
+        Ok(Self {
+            date,
+            call_time,
+            location,
+            location_label: non_blank(&form.location_label),
+            notes: non_blank(&form.notes),
+            status: status.to_string(),
+        })
+    }
+
+    /// The stored `date`: midnight UTC of the shoot date.
+    pub fn date_value(&self) -> DateTime<Utc> {
+        self.date.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// The stored `general_call`: the shoot date at the call time.
+    pub fn general_call_value(&self) -> Option<DateTime<Utc>> {
+        self.call_time
+            .map(|time| self.date.and_time(time).and_utc())
+    }
+}
+
+fn non_blank(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+const SHOOT_DAY_FIELDS: &str = "id, date, general_call, location, location.name AS location_name, \
+     location_label, notes, status, \
+     (SELECT id, name FROM $parent.equipment ORDER BY name) AS equipment";
+
+pub struct ScheduleModel;
+
+impl ScheduleModel {
+    /// Every shoot day of a production, earliest first; same-date days are
+    /// ordered by call time.
+    pub async fn list(production_id: &RecordId) -> Result<Vec<ShootDay>, Error> {
+        debug!("Listing shoot days for production {:?}", production_id);
+
+        let days: Vec<ShootDay> = DB
+            .query(format!(
+                "SELECT {SHOOT_DAY_FIELDS} FROM schedule_day \
+                 WHERE production = $prod \
+                 ORDER BY date ASC, general_call ASC"
+            ))
+            .bind(("prod", production_id.clone()))
+            .await?
+            .take(0)?;
+        Ok(days)
+    }
+
+    /// One shoot day, if it belongs to `production_id`.
+    pub async fn get(production_id: &RecordId, day_id: &RecordId) -> Result<ShootDay, Error> {
+        let day: Option<ShootDay> = DB
+            .query(format!(
+                "SELECT {SHOOT_DAY_FIELDS} FROM $day WHERE production = $prod"
+            ))
+            .bind(("day", day_id.clone()))
+            .bind(("prod", production_id.clone()))
+            .await?
+            .take(0)?;
+        day.ok_or(Error::NotFound)
+    }
+
+    pub async fn create(
+        production_id: &RecordId,
+        input: &ShootDayInput,
+    ) -> Result<RecordId, Error> {
+        debug!(
+            "Creating shoot day {} for production {:?}",
+            input.date, production_id
+        );
+
+        #[derive(Deserialize, SurrealValue)]
+        struct Created {
+            id: RecordId,
+        }
+        let created: Option<Created> = DB
+            .query(
+                "CREATE ONLY schedule_day CONTENT {
+                    production: $prod,
+                    date: $date,
+                    general_call: $general_call,
+                    location: $location,
+                    location_label: $location_label,
+                    notes: $notes,
+                    status: $status
+                } RETURN id",
+            )
+            .bind(("prod", production_id.clone()))
+            .bind(("date", input.date_value()))
+            .bind(("general_call", input.general_call_value()))
+            .bind(("location", input.location.clone()))
+            .bind(("location_label", input.location_label.clone()))
+            .bind(("notes", input.notes.clone()))
+            .bind(("status", input.status.clone()))
+            .await?
+            .take(0)?;
+
+        created
+            .map(|row| row.id)
+            .ok_or_else(|| Error::Internal("Failed to create shoot day".to_string()))
+    }
+
+    pub async fn update(
+        production_id: &RecordId,
+        day_id: &RecordId,
+        input: &ShootDayInput,
+    ) -> Result<(), Error> {
+        debug!("Updating shoot day {:?}", day_id);
+
+        #[derive(Deserialize, SurrealValue)]
+        struct Updated {
+            #[allow(dead_code)]
+            id: RecordId,
+        }
+        let updated: Vec<Updated> = DB
+            .query(
+                "UPDATE $day SET
+                    date = $date,
+                    general_call = $general_call,
+                    location = $location,
+                    location_label = $location_label,
+                    notes = $notes,
+                    status = $status
+                 WHERE production = $prod
+                 RETURN id",
+            )
+            .bind(("day", day_id.clone()))
+            .bind(("prod", production_id.clone()))
+            .bind(("date", input.date_value()))
+            .bind(("general_call", input.general_call_value()))
+            .bind(("location", input.location.clone()))
+            .bind(("location_label", input.location_label.clone()))
+            .bind(("notes", input.notes.clone()))
+            .bind(("status", input.status.clone()))
+            .await?
+            .take(0)?;
+
+        if updated.is_empty() {
+            return Err(Error::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Book an item for a shoot day. Refused with a conflict while an open
+    /// rental of the item, or of its kit, covers the date
+    /// ([`EquipmentModel::available_on`]). Booking an item twice is a no-op.
+    pub async fn attach_equipment(
+        production_id: &RecordId,
+        day_id: &RecordId,
+        equipment_id: &RecordId,
+    ) -> Result<(), Error> {
+        debug!("Booking {:?} for shoot day {:?}", equipment_id, day_id);
+
+        let day = Self::get(production_id, day_id).await?;
+        if !EquipmentModel::available_on(&equipment_id.key_string(), day.day()).await? {
+            return Err(Error::Conflict(format!(
+                "That item is checked out on {}",
+                day.day().format("%b %-d %Y")
+            )));
+        }
+
+        DB.query(
+            "UPDATE $day SET equipment = array::union(equipment ?? [], [$equipment]) \
+             WHERE production = $prod",
+        )
+        .bind(("day", day_id.clone()))
+        .bind(("prod", production_id.clone()))
+        .bind(("equipment", equipment_id.clone()))
+        .await?
+        .check()?;
+        Ok(())
+    }
+
+    /// Drop an item from a shoot day's bookings.
+    pub async fn detach_equipment(
+        production_id: &RecordId,
+        day_id: &RecordId,
+        equipment_id: &RecordId,
+    ) -> Result<(), Error> {
+        debug!("Unbooking {:?} from shoot day {:?}", equipment_id, day_id);
+
+        Self::get(production_id, day_id).await?;
+        DB.query("UPDATE $day SET equipment -= $equipment WHERE production = $prod")
+            .bind(("day", day_id.clone()))
+            .bind(("prod", production_id.clone()))
+            .bind(("equipment", equipment_id.clone()))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    /// Delete a shoot day with its scene placements and call times. Refused
+    /// once a call sheet has been generated for the day: the sheet is the
+    /// record of what crew were sent.
+    pub async fn delete(production_id: &RecordId, day_id: &RecordId) -> Result<(), Error> {
+        debug!("Deleting shoot day {:?}", day_id);
+
+        Self::get(production_id, day_id).await?;
+
+        let sheets: Option<i64> = DB
+            .query("SELECT VALUE count() FROM call_sheet WHERE schedule_day = $day GROUP ALL")
+            .bind(("day", day_id.clone()))
+            .await?
+            .take(0)?;
+        if sheets.unwrap_or(0) > 0 {
+            return Err(Error::Conflict(
+                "This shoot day already has call sheets and can't be deleted".to_string(),
+            ));
+        }
+
+        crate::db::transaction(async |tx| {
+            tx.query(
+                "DELETE schedule_scene WHERE schedule_day = $day;
+                 DELETE call_time WHERE schedule_day = $day;
+                 DELETE $day",
+            )
+            .bind(("day", day_id.clone()))
+            .await?
+            .check()?;
+            Ok(())
+        })
+        .await
+    }
+}
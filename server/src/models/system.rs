@@ -6,8 +6,18 @@
 use crate::db::DB;
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use surrealdb::types::SurrealValue;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+/// How long [`System::cached_platform_metrics`] reuses a computed snapshot;
+/// the aggregates scan whole tables and the storage figure lists the bucket.
+pub const PLATFORM_METRICS_TTL: Duration = Duration::from_secs(60);
+
+type CachedMetrics = Option<(Instant, Arc<PlatformMetrics>)>;
+
+static PLATFORM_METRICS: LazyLock<Mutex<CachedMetrics>> = LazyLock::new(|| Mutex::new(None));
 
 /// System information and status
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +46,41 @@ pub struct HealthStatus {
     pub timestamp: String,
 }
 
+/// Platform-wide counts for the operations dashboard
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformMetrics {
+    pub users: UserCounts,
+    pub organizations_by_type: Vec<GroupCount>,
+    pub productions_by_status: Vec<GroupCount>,
+    pub equipment_items: u64,
+    pub active_rentals: u64,
+    /// `None` when object storage is unavailable
+    pub storage: Option<StorageUsage>,
+    pub generated_at: String,
+}
+
+/// Person counts split by email/identity verification
+#[derive(Debug, Clone, Serialize)]
+pub struct UserCounts {
+    pub total: u64,
+    pub verified: u64,
+    pub unverified: u64,
+}
+
+/// One bucket of a grouped count, e.g. productions with status "Filming"
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue)]
+pub struct GroupCount {
+    pub key: String,
+    pub count: u64,
+}
+
+/// Object storage footprint, from listing the bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageUsage {
+    pub objects: u64,
+    pub bytes: u64,
+}
+
 /// System model for system-level operations
 pub struct System;
 
@@ -209,4 +254,78 @@ impl System {
         let results: Vec<CountResult> = response.take(0)?;
         Ok(results.first().map(|r| r.count).unwrap_or(0))
     }
+
+    /// Compute platform metrics with aggregate queries
+    ///
+    /// # Returns
+    /// A `Result` containing the `PlatformMetrics`
+    pub async fn platform_metrics() -> Result<PlatformMetrics> {
+        debug!("Computing platform metrics");
+
+        let sql = "
+            SELECT count() AS count FROM person GROUP ALL;
+            SELECT count() AS count FROM person WHERE verification_status != 'unverified' GROUP ALL;
+            SELECT type.name ?? 'Unknown' AS key, count() AS count FROM organization
+                GROUP BY key ORDER BY count DESC;
+            SELECT status ?? 'Unknown' AS key, count() AS count FROM production
+                GROUP BY key ORDER BY count DESC;
+            SELECT count() AS count FROM equipment GROUP ALL;
+            SELECT count() AS count FROM equipment_rental WHERE is_active = true GROUP ALL;
+        ";
+        let mut response = DB.query(sql).await?;
+
+        #[derive(Deserialize, SurrealValue)]
+        struct CountResult {
+            count: u64,
+        }
+        let mut count = |index: usize| -> Result<u64> {
+            let row: Option<CountResult> = response.take(index)?;
+            Ok(row.map(|r| r.count).unwrap_or(0))
+        };
+        let total = count(0)?;
+        let verified = count(1)?;
+        let equipment_items = count(4)?;
+        let active_rentals = count(5)?;
+        let organizations_by_type: Vec<GroupCount> = response.take(2)?;
+        let productions_by_status: Vec<GroupCount> = response.take(3)?;
+
+        let storage = match crate::services::s3::s3() {
+            Ok(s3) => match s3.usage().await {
+                Ok((objects, bytes)) => Some(StorageUsage { objects, bytes }),
+                Err(e) => {
+                    warn!("Failed to measure storage usage: {}", e);
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+
+        Ok(PlatformMetrics {
+            users: UserCounts {
+                total,
+                verified,
+                unverified: total.saturating_sub(verified),
+            },
+            organizations_by_type,
+            productions_by_status,
+            equipment_items,
+            active_rentals,
+            storage,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// [`System::platform_metrics`], reused for [`PLATFORM_METRICS_TTL`] so
+    /// dashboard refreshes don't re-run the aggregation each time
+    pub async fn cached_platform_metrics() -> Result<Arc<PlatformMetrics>> {
+        if let Some((computed, metrics)) = PLATFORM_METRICS.lock().unwrap().as_ref()
+            && computed.elapsed() < PLATFORM_METRICS_TTL
+        {
+            return Ok(Arc::clone(metrics));
+        }
+
+        let metrics = Arc::new(Self::platform_metrics().await?);
+        *PLATFORM_METRICS.lock().unwrap() = Some((Instant::now(), Arc::clone(&metrics)));
+        Ok(metrics)
+    }
 }
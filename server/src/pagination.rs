@@ -0,0 +1,111 @@
+//! Shared page-number pagination for list endpoints.
+//!
+//! Handlers take a [`PageQuery`] extractor (`?page=&per_page=`, 1-based,
+//! clamped so a client can't ask for page 0 or ten thousand rows), hand it
+//! to a model's paged list method, and get back a [`Page`] — the items plus
+//! the total match count — which serializes as-is for JSON endpoints.
+
+use std::collections::HashMap;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use serde::Serialize;
+
+use crate::error::Error;
+
+/// Rows per page when the client doesn't say.
+pub const DEFAULT_PER_PAGE: u32 = 20;
+
+/// Upper bound on `per_page`; larger requests are clamped, not rejected.
+pub const MAX_PER_PAGE: u32 = 100;
+
+/// Which page to fetch. Always valid once constructed: `page >= 1` and
+/// `1 <= per_page <= MAX_PER_PAGE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageQuery {
+    pub page: u32,
+    pub per_page: u32,
+}
+
+impl Default for PageQuery {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl PageQuery {
+    /// Clamp raw values: missing or zero `page` → 1, missing `per_page` →
+    /// [`DEFAULT_PER_PAGE`], otherwise into `1..=MAX_PER_PAGE`.
+    pub fn new(page: Option<u32>, per_page: Option<u32>) -> Self {
+        Self {
+            page: page.unwrap_or(1).max(1),
+            per_page: per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE),
+        }
+    }
+
+    /// Rows to skip — the SurrealQL `START` value.
+    pub fn offset(&self) -> u64 {
+        u64::from(self.page - 1) * u64::from(self.per_page)
+    }
+
+    /// Rows to return — the SurrealQL `LIMIT` value.
+    pub fn limit(&self) -> u64 {
+        u64::from(self.per_page)
+    }
+}
+
+/// Reads `page` and `per_page` from the query string. Unparseable values
+/// fall back to the defaults rather than failing the request.
+impl<S> FromRequestParts<S> for PageQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let params: HashMap<String, String> =
+            serde_urlencoded::from_str(parts.uri.query().unwrap_or_default()).unwrap_or_default();
+        let number = |key: &str| params.get(key).and_then(|v| v.trim().parse().ok());
+        Ok(Self::new(number("page"), number("per_page")))
+    }
+}
+
+/// One page of a list plus what a client needs to render pager controls.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Matches across all pages, not just this one.
+    pub total: u64,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, total: u64, query: PageQuery) -> Self {
+        Self {
+            items,
+            total,
+            page: query.page,
+            per_page: query.per_page,
+        }
+    }
+
+    /// Number of pages, at least 1 so "page 1 of 1" renders for no results.
+    pub fn total_pages(&self) -> u64 {
+        self.total.div_ceil(u64::from(self.per_page)).max(1)
+    }
+
+    pub fn has_more(&self) -> bool {
+        u64::from(self.page) < self.total_pages()
+    }
+
+    /// Convert the items (e.g. model → view) keeping the paging fields.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            per_page: self.per_page,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! In-memory sliding-window rate limiting, plus the standard headers that
+//! tell clients how to back off.
+//!
+//! A [`RateLimiter`] keeps recent hit times per key (client IP, email, user
+//! id) and answers each [`RateLimiter::check`] with a [`RateLimitStatus`]:
+//! the limit, how many hits remain, and when the next slot frees up. A
+//! tripped limiter becomes [`Error::RateLimited`], whose response is a 429
+//! carrying `Retry-After`, `X-RateLimit-Limit`, `X-RateLimit-Remaining` and
+//! `X-RateLimit-Reset` alongside the usual JSON error body (`retry_after`
+//! in seconds). Counters live in process memory, so limits are per
+//! instance and reset on restart — a backstop, not a quota system.
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+
+/// `X-RateLimit-Limit`: hits allowed per window.
+pub const X_RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+/// `X-RateLimit-Remaining`: hits left in the current window.
+pub const X_RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+/// `X-RateLimit-Reset`: seconds until another hit is allowed.
+pub const X_RATELIMIT_RESET: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// Keys tracked before idle ones are swept on the next check.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+/// Where a key stands against its limiter after a check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub limit: usize,
+    pub remaining: usize,
+    /// Time until the oldest hit in the window expires and frees a slot.
+    pub reset_after: Duration,
+}
+
+impl RateLimitStatus {
+    /// Whole seconds until a retry can succeed (never 0, so clients wait).
+    pub fn retry_after_secs(&self) -> u64 {
+        self.reset_after.as_secs_f64().ceil().max(1.0) as u64
+    }
+
+    /// Write `X-RateLimit-Limit`/`-Remaining`/`-Reset`, plus `Retry-After`
+    /// when nothing remains.
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        let retry_after = HeaderValue::from(self.retry_after_secs());
+        headers.insert(X_RATELIMIT_LIMIT, HeaderValue::from(self.limit));
+        headers.insert(X_RATELIMIT_REMAINING, HeaderValue::from(self.remaining));
+        headers.insert(X_RATELIMIT_RESET, retry_after.clone());
+        if self.remaining == 0 {
+            headers.insert(header::RETRY_AFTER, retry_after);
+        }
+    }
+
+    /// Read back what [`RateLimitStatus::apply_headers`] wrote, for code
+    /// that rebuilds a rate-limited response (the HTML error pages).
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &HeaderName| -> Option<u64> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        };
+        Some(Self {
+            limit: number(&X_RATELIMIT_LIMIT)? as usize,
+            remaining: number(&X_RATELIMIT_REMAINING)? as usize,
+            reset_after: Duration::from_secs(number(&X_RATELIMIT_RESET)?),
+        })
+    }
+}
+
+/// The outcome of [`RateLimiter::check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub status: RateLimitStatus,
+}
+
+impl RateLimitDecision {
+    /// `Ok` with the status when allowed, otherwise [`Error::RateLimited`]
+    /// showing `message` to the client.
+    pub fn into_result(self, message: &str) -> Result<RateLimitStatus> {
+        if self.allowed {
+            Ok(self.status)
+        } else {
+            Err(Error::RateLimited {
+                message: message.to_string(),
+                status: self.status,
+            })
+        }
+    }
+}
+
+/// At most `max` hits per key in any rolling `window`.
+pub struct RateLimiter {
+    max: usize,
+    window: Duration,
+    hits: Mutex<HashMap<String, Vec<Instant>>>,
+}
+
+impl RateLimiter {
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a hit for `key` if it is under the limit. Rejected hits are
+    /// not recorded, so hammering a tripped limiter doesn't extend it.
+    pub fn check(&self, key: &str) -> RateLimitDecision {
+        self.check_at(key, Instant::now())
+    }
+
+    /// [`RateLimiter::check`] then [`RateLimitDecision::into_result`].
+    pub fn hit(&self, key: &str, message: &str) -> Result<RateLimitStatus> {
+        self.check(key).into_result(message)
+    }
+
+    /// [`RateLimiter::check`] against an explicit clock, for tests.
+    pub fn check_at(&self, key: &str, now: Instant) -> RateLimitDecision {
+        let mut map = self.hits.lock().unwrap();
+        let window = self.window;
+        if map.len() > SWEEP_THRESHOLD {
+            map.retain(|_, hits| {
+                hits.retain(|t| now.duration_since(*t) < window);
+                !hits.is_empty()
+            });
+        }
+
+        let hits = map.entry(key.to_string()).or_default();
+        hits.retain(|t| now.duration_since(*t) < window);
+        let allowed = hits.len() < self.max;
+        if allowed {
+            hits.push(now);
+        }
+
+        let reset_after = hits
+            .first()
+            .map(|oldest| window.saturating_sub(now.duration_since(*oldest)))
+            .unwrap_or(window);
+        RateLimitDecision {
+            allowed,
+            status: RateLimitStatus {
+                limit: self.max,
+                remaining: self.max.saturating_sub(hits.len()),
+                reset_after,
+            },
+        }
+    }
+}
@@ -31,6 +31,10 @@ pub trait RecordIdExt {
 
     /// Returns just the key portion as a string
     fn key_string(&self) -> String;
+
+    /// Whether `id` names this record. `id` may be `"table:key"` or a bare
+    /// key in `default_table`; see [`normalize_record_id`].
+    fn matches_id(&self, id: &str, default_table: &str) -> bool;
 }
 
 impl RecordIdExt for RecordId {
@@ -45,4 +49,27 @@ impl RecordIdExt for RecordId {
     fn key_string(&self) -> String {
         format_key(&self.key)
     }
+
+    fn matches_id(&self, id: &str, default_table: &str) -> bool {
+        let other = normalize_record_id(id, default_table);
+        other.table == self.table && format_key(&other.key) == format_key(&self.key)
+    }
+}
+
+/// Parse a record id that may or may not carry its table prefix.
+///
+/// Session and form ids arrive both as `"person:abc"` and as a bare
+/// `"abc"`; the bare form is placed in `default_table`. SurrealDB's
+/// `⟨…⟩`/backtick key escaping is stripped so ids copied from query output
+/// normalize to the same record. Comparing the result (rather than raw
+/// strings) is what makes ownership checks prefix-insensitive.
+pub fn normalize_record_id(id: &str, default_table: &str) -> RecordId {
+    let id = id.trim();
+    let (table, key) = id.split_once(':').unwrap_or((default_table, id));
+    let key = key
+        .strip_prefix('⟨')
+        .and_then(|k| k.strip_suffix('⟩'))
+        .or_else(|| key.strip_prefix('`').and_then(|k| k.strip_suffix('`')))
+        .unwrap_or(key);
+    RecordId::new(table, key)
 }
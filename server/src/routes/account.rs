@@ -1,34 +1,38 @@
 //! Account-settings routes under `/account`: the settings page plus
 //! password, email, and username changes (each re-verifying the current
 //! password and re-issuing the `auth_token` JWT cookie where identity
-//! claims change), messaging-preference and contact-visibility toggles,
-//! and password-confirmed account deletion with related-data cleanup.
+//! claims change), messaging-preference, timezone, contact-visibility, and
+//! profile-privacy toggles, and password-confirmed account deletion with
+//! related-data cleanup. `POST /language` stores the visitor's language
+//! choice in the `lang` cookie read by [`crate::middleware::locale`].
 
 use askama::Template;
 use axum::{
     Form, Router,
     extract::Query,
+    http::{HeaderMap, header},
     response::{Html, IntoResponse, Response},
     routing::{get, post},
 };
-use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use axum_extra::extract::cookie::CookieJar;
 use serde::Deserialize;
-use std::env;
 use tracing::{error, info};
 
 use crate::{
     auth,
     db::DB,
     error::Error,
+    i18n::{self, LANG_COOKIE, Locale},
     middleware::AuthenticatedUser,
-    models::person::Person,
+    models::person::{PRIVATE_FIELD_OPTIONS, Person},
     record_id_ext::RecordIdExt,
     response,
-    templates::{AccountSettingsTemplate, BaseContext, User},
+    templates::{AccountSettingsTemplate, BaseContext, PrivateFieldOption, User},
 };
 
 /// Routes for the `/account` settings page and its credential, preference,
-/// and deletion form handlers.
+/// and deletion form handlers, plus the signed-out-friendly `/language`
+/// picker.
 pub fn router() -> Router {
     Router::new()
         .route("/account", get(account_settings_page))
@@ -39,11 +43,14 @@ pub fn router() -> Router {
             "/account/messaging-preference",
             post(change_messaging_preference),
         )
+        .route("/account/timezone", post(change_timezone))
         .route(
             "/account/contact-visibility",
             post(change_contact_visibility),
         )
+        .route("/account/profile-privacy", post(change_profile_privacy))
         .route("/account/delete", post(delete_account))
+        .route("/language", post(set_language))
 }
 
 #[derive(Debug, Deserialize)]
@@ -63,14 +70,17 @@ async fn account_settings_page(
         .ok_or(Error::NotFound)?;
 
     let mut template = AccountSettingsTemplate::new(base);
+    template.private_field_options = PrivateFieldOption::for_selection(person.private_fields());
     template.username = person.username;
     template.email = person.email;
     template.messaging_preference = person.messaging_preference;
+    template.timezone = person.timezone.unwrap_or_default();
     template.show_contact_info = person
         .profile
         .as_ref()
         .map(|p| p.is_public)
         .unwrap_or(false);
+    template.profile_private = person.profile.as_ref().is_some_and(|p| p.is_private);
     template.success = query.success;
 
     let html = template.render().map_err(|e| {
@@ -183,12 +193,7 @@ async fn change_email(
     // Issue new JWT with updated email
     let token = auth::create_jwt(&person.id.to_raw_string(), &person.username, &new_email)?;
 
-    let cookie = Cookie::build(("auth_token", token))
-        .path("/")
-        .same_site(SameSite::Lax)
-        .http_only(true)
-        .secure(env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
-        .build();
+    let cookie = auth::session_cookie(token, false);
 
     Ok((
         CookieJar::new().add(cookie),
@@ -250,12 +255,7 @@ async fn change_username(
     // Issue new JWT with updated username
     let token = auth::create_jwt(&person.id.to_raw_string(), &new_username, &person.email)?;
 
-    let cookie = Cookie::build(("auth_token", token))
-        .path("/")
-        .same_site(SameSite::Lax)
-        .http_only(true)
-        .secure(env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
-        .build();
+    let cookie = auth::session_cookie(token, false);
 
     // Redirect back so the new cookie takes effect
     Ok((
@@ -299,6 +299,40 @@ async fn change_messaging_preference(
     render_settings_with_success(&current_user.id, "Messaging preference updated.").await
 }
 
+// -- Timezone --
+
+#[derive(Debug, Deserialize)]
+struct TimezoneForm {
+    /// IANA name, or empty to follow the browser.
+    timezone: String,
+}
+
+async fn change_timezone(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Form(form): Form<TimezoneForm>,
+) -> Result<Response, Error> {
+    let timezone = match form.timezone.trim() {
+        "" => None,
+        name => match i18n::parse_timezone(name) {
+            Some(tz) => Some(tz.name().to_string()),
+            None => return render_settings_with_error(&current_user.id, "Unknown timezone.").await,
+        },
+    };
+
+    DB.query("UPDATE $id SET timezone = $timezone")
+        .bind(("id", current_user.record_id()?))
+        .bind(("timezone", timezone.clone()))
+        .await
+        .map_err(|e| Error::Database(e.to_string()))?;
+
+    info!(
+        "Timezone changed to {:?} for user: {}",
+        timezone, current_user.username
+    );
+
+    render_settings_with_success(&current_user.id, "Timezone updated.").await
+}
+
 // -- Contact Visibility --
 
 #[derive(Debug, Deserialize)]
@@ -330,6 +364,43 @@ async fn change_contact_visibility(
     render_settings_with_success(&current_user.id, "Contact visibility updated.").await
 }
 
+// -- Profile Privacy --
+
+/// Raw pairs rather than a struct: `private_field` repeats once per checked
+/// box, which a plain serde struct can't collect.
+async fn change_profile_privacy(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Form(pairs): Form<Vec<(String, String)>>,
+) -> Result<Response, Error> {
+    let is_private = pairs
+        .iter()
+        .any(|(k, v)| k == "profile_private" && v == "on");
+    let private_fields: Vec<String> = PRIVATE_FIELD_OPTIONS
+        .iter()
+        .map(|&(key, _)| key)
+        .filter(|key| pairs.iter().any(|(k, v)| k == "private_field" && v == key))
+        .map(String::from)
+        .collect();
+
+    let person = Person::find_by_id(&current_user.id)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    DB.query("UPDATE $id SET profile.is_private = $is_private, profile.private_fields = $fields")
+        .bind(("id", person.id.clone()))
+        .bind(("is_private", is_private))
+        .bind(("fields", private_fields.clone()))
+        .await
+        .map_err(|e| Error::Database(e.to_string()))?;
+
+    info!(
+        "Profile privacy changed (private: {}, hidden fields: {:?}) for user: {}",
+        is_private, private_fields, current_user.username
+    );
+
+    render_settings_with_success(&current_user.id, "Profile privacy updated.").await
+}
+
 // -- Delete Account --
 
 #[derive(Debug, Deserialize)]
@@ -387,13 +458,7 @@ async fn delete_account(
     info!("Account deleted: {} ({})", person.username, person_id_str);
 
     // Clear auth cookie and redirect
-    let cookie = Cookie::build(("auth_token", ""))
-        .path("/")
-        .same_site(SameSite::Lax)
-        .http_only(true)
-        .secure(env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
-        .max_age(Default::default())
-        .build();
+    let cookie = auth::cleared_session_cookie();
 
     Ok((CookieJar::new().remove(cookie), response::redirect("/")).into_response())
 }
@@ -407,14 +472,17 @@ async fn render_settings_with_error(person_id: &str, error_msg: &str) -> Result<
 
     let base = BaseContext::new().with_page("account");
     let mut template = AccountSettingsTemplate::new(base);
+    template.private_field_options = PrivateFieldOption::for_selection(person.private_fields());
     template.username = person.username;
     template.email = person.email;
     template.messaging_preference = person.messaging_preference;
+    template.timezone = person.timezone.unwrap_or_default();
     template.show_contact_info = person
         .profile
         .as_ref()
         .map(|p| p.is_public)
         .unwrap_or(false);
+    template.profile_private = person.profile.as_ref().is_some_and(|p| p.is_private);
     template.error = Some(error_msg.to_string());
 
     let html = template.render().map_err(|e| {
@@ -440,14 +508,17 @@ async fn render_settings_with_success(
     base = base.with_user(User::from_session_user(&session_user).await);
 
     let mut template = AccountSettingsTemplate::new(base);
+    template.private_field_options = PrivateFieldOption::for_selection(person.private_fields());
     template.username = person.username;
     template.email = person.email;
     template.messaging_preference = person.messaging_preference;
+    template.timezone = person.timezone.unwrap_or_default();
     template.show_contact_info = person
         .profile
         .as_ref()
         .map(|p| p.is_public)
         .unwrap_or(false);
+    template.profile_private = person.profile.as_ref().is_some_and(|p| p.is_private);
     template.success = Some(success_msg.to_string());
 
     let html = template.render().map_err(|e| {
@@ -457,3 +528,42 @@ async fn render_settings_with_success(
 
     Ok(Html(html).into_response())
 }
+
+#[derive(Debug, Deserialize)]
+struct LanguageForm {
+    lang: String,
+    redirect_to: Option<String>,
+}
+
+/// Remember the chosen language for a year and go back to the page the
+/// picker was on: `redirect_to` if given, else the `Referer` path.
+async fn set_language(
+    headers: HeaderMap,
+    Form(form): Form<LanguageForm>,
+) -> Result<Response, Error> {
+    let locale = Locale::from_tag(&form.lang)
+        .ok_or_else(|| Error::bad_request(format!("Unsupported language: {}", form.lang)))?;
+    let cookie = cookie::Cookie::build((LANG_COOKIE, locale.code()))
+        .path("/")
+        .same_site(crate::config::cookie_same_site())
+        .secure(crate::config::cookie_secure())
+        .max_age(cookie::time::Duration::days(365))
+        .build();
+    let referer_path = headers
+        .get(header::REFERER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| url::Url::parse(v).ok())
+        .map(|u| match u.query() {
+            Some(query) => format!("{}?{}", u.path(), query),
+            None => u.path().to_string(),
+        });
+    let target = response::safe_redirect_target(
+        form.redirect_to.as_deref().or(referer_path.as_deref()),
+        "/",
+    );
+    Ok((
+        CookieJar::new().add(cookie),
+        response::redirect_to_target(&target),
+    )
+        .into_response())
+}
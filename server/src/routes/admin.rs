@@ -1,8 +1,9 @@
 //! Admin-only routes under `/admin`: a stats dashboard plus management
-//! pages for feedback, people, productions, organizations, locations,
-//! feature flags, and the mailing list, along with maintenance actions
-//! (embedding rebuild, backup, orphaned-file cleanup). Every handler
-//! re-checks `is_admin` via [`require_admin`] before doing anything.
+//! pages for feedback, people, productions, organizations, organization
+//! types, locations, feature flags, and the mailing list, along with
+//! maintenance actions (embedding rebuild, backup, orphaned-file cleanup,
+//! read-only mode). Every handler takes the [`AdminUser`] extractor, so
+//! non-admins get a 403 before the handler body runs.
 
 use askama::Template;
 use axum::{
@@ -19,10 +20,9 @@ use tracing::{error, info, warn};
 use crate::{
     db::DB,
     error::Error,
-    middleware::AuthenticatedUser,
-    models::person::SessionUser,
+    middleware::{AdminUser, read_only},
     record_id_ext::RecordIdExt,
-    services::s3::s3,
+    services::{s3::s3, storage_cleanup},
     templates::{BaseContext, User},
 };
 
@@ -32,18 +32,6 @@ use crate::templates::filters;
 /// Flag to prevent concurrent embedding rebuilds
 static REBUILD_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 
-// ============================
-// Admin guard helper
-// ============================
-
-async fn require_admin(user: &SessionUser) -> Result<User, Error> {
-    let template_user = User::from_session_user(user).await;
-    if !template_user.is_admin {
-        return Err(Error::Forbidden);
-    }
-    Ok(template_user)
-}
-
 // ============================
 // Templates
 // ============================
@@ -56,8 +44,10 @@ struct AdminDashboardTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     stats: AdminStats,
     embedding_rebuild_in_progress: bool,
+    read_only: bool,
     build_info: String,
 }
 
@@ -135,6 +125,7 @@ struct AdminFeedbackTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     feedback_items: Vec<FeedbackItem>,
 }
 
@@ -154,6 +145,7 @@ struct AdminPeopleTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     people: Vec<PersonRow>,
     search_query: String,
 }
@@ -179,6 +171,7 @@ struct AdminProductionsTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     productions: Vec<ProductionRow>,
     search_query: String,
 }
@@ -200,6 +193,7 @@ struct AdminOrganizationsTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     organizations: Vec<OrgRow>,
     search_query: String,
 }
@@ -214,6 +208,26 @@ struct OrgRow {
     created_at: String,
 }
 
+#[derive(Template)]
+#[template(path = "admin/org_types.html")]
+struct AdminOrgTypesTemplate {
+    app_name: String,
+    year: i32,
+    version: String,
+    active_page: String,
+    user: Option<User>,
+    nonce: String,
+    org_types: Vec<OrgTypeRow>,
+    flash: Option<String>,
+    flash_kind: String,
+}
+
+struct OrgTypeRow {
+    id: String,
+    name: String,
+    usage: u64,
+}
+
 #[derive(Template)]
 #[template(path = "admin/locations.html")]
 struct AdminLocationsTemplate {
@@ -222,6 +236,7 @@ struct AdminLocationsTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     locations: Vec<LocationRow>,
     search_query: String,
 }
@@ -243,6 +258,7 @@ struct AdminFeatureFlagsTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     flags: Vec<FeatureFlagRow>,
     flash: Option<String>,
 }
@@ -262,6 +278,7 @@ struct AdminMailingListTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     enabled: bool,
     list_ids: Vec<i64>,
     person_count: usize,
@@ -283,6 +300,7 @@ struct AdminLandingPagesTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     details: Vec<CampaignDetail>,
 }
 
@@ -329,6 +347,12 @@ pub fn router() -> Router {
             "/admin/organizations/{id}/toggle-verified",
             post(toggle_org_verified),
         )
+        .route(
+            "/admin/org-types",
+            get(list_org_types).post(create_org_type),
+        )
+        .route("/admin/org-types/{id}/rename", post(rename_org_type))
+        .route("/admin/org-types/{id}/delete", post(delete_org_type))
         .route("/admin/locations", get(list_locations))
         .route("/admin/locations/{id}/delete", post(delete_location))
         .route("/admin/feature-flags", get(feature_flags_page))
@@ -342,6 +366,7 @@ pub fn router() -> Router {
         .route("/admin/mailing-list/sync-all", post(mailing_list_sync_all))
         .route("/admin/rebuild-embeddings", post(rebuild_embeddings))
         .route("/admin/backup", post(backup_all))
+        .route("/admin/read-only", post(set_read_only_mode))
         .route("/admin/cleanup-files", get(preview_orphaned_files))
         .route("/admin/cleanup-files", post(cleanup_orphaned_files))
 }
@@ -350,9 +375,7 @@ pub fn router() -> Router {
 // Handlers
 // ============================
 
-async fn dashboard(AuthenticatedUser(user): AuthenticatedUser) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
+async fn dashboard(AdminUser { template_user, .. }: AdminUser) -> Result<Html<String>, Error> {
     use crate::models::activity::ActivityModel;
 
     // Run all queries in parallel
@@ -400,6 +423,7 @@ async fn dashboard(AuthenticatedUser(user): AuthenticatedUser) -> Result<Html<St
     let template = crate::with_base!(AdminDashboardTemplate, base, {
         stats,
         embedding_rebuild_in_progress: REBUILD_IN_PROGRESS.load(Ordering::Relaxed),
+        read_only: read_only::is_read_only(),
         build_info: format!("v{}", crate::version::VERSION),
     });
 
@@ -411,8 +435,7 @@ async fn dashboard(AuthenticatedUser(user): AuthenticatedUser) -> Result<Html<St
 
 // -- Landing pages --
 
-async fn landing_pages(AuthenticatedUser(user): AuthenticatedUser) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
+async fn landing_pages(AdminUser { template_user, .. }: AdminUser) -> Result<Html<String>, Error> {
     use crate::models::landing::LandingModel;
 
     let funnels = LandingModel::campaign_funnels().await;
@@ -452,9 +475,7 @@ async fn landing_pages(AuthenticatedUser(user): AuthenticatedUser) -> Result<Htm
 
 // -- Feedback --
 
-async fn list_feedback(AuthenticatedUser(user): AuthenticatedUser) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
+async fn list_feedback(AdminUser { template_user, .. }: AdminUser) -> Result<Html<String>, Error> {
     #[derive(Debug, Deserialize, surrealdb::types::SurrealValue)]
     struct FeedbackRow {
         id: surrealdb::types::RecordId,
@@ -497,11 +518,9 @@ async fn list_feedback(AuthenticatedUser(user): AuthenticatedUser) -> Result<Htm
 }
 
 async fn delete_feedback(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("feedback", id.as_str());
 
     info!(
@@ -535,11 +554,9 @@ struct SearchParams {
 }
 
 async fn list_people(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { template_user, .. }: AdminUser,
     Query(params): Query<SearchParams>,
 ) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
     let search = params.q.clone().unwrap_or_default();
 
     #[derive(Debug, Deserialize, surrealdb::types::SurrealValue)]
@@ -628,11 +645,9 @@ async fn list_people(
 }
 
 async fn delete_person(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("person", id.as_str());
 
     // Don't allow deleting yourself
@@ -653,11 +668,9 @@ async fn delete_person(
 }
 
 async fn toggle_admin(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("person", id.as_str());
 
     // Don't allow toggling your own admin status
@@ -683,12 +696,10 @@ struct AdminResetPasswordForm {
 }
 
 async fn admin_reset_password(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
     axum::Form(form): axum::Form<AdminResetPasswordForm>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     if form.new_password.len() < 8 {
         return Err(Error::BadRequest(
             "Password must be at least 8 characters".to_string(),
@@ -714,12 +725,10 @@ struct VerificationForm {
 }
 
 async fn update_verification(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
     axum::extract::Form(form): axum::extract::Form<VerificationForm>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let valid_statuses = ["unverified", "email", "sms", "identity"];
     if !valid_statuses.contains(&form.status.as_str()) {
         return Err(Error::BadRequest(format!(
@@ -765,11 +774,9 @@ async fn update_verification(
 /// (create_verification_code deletes unused ones), so the people list's Code
 /// column shows the new code after the redirect — ready to copy or read out.
 async fn admin_resend_verification(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let rid = surrealdb::types::RecordId::new("person", id.as_str());
     let person = crate::models::person::Person::find_by_record_id(&rid)
         .await?
@@ -820,11 +827,9 @@ async fn admin_resend_verification(
 // -- Productions --
 
 async fn list_productions(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { template_user, .. }: AdminUser,
     Query(params): Query<SearchParams>,
 ) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
     let search = params.q.clone().unwrap_or_default();
 
     #[derive(Debug, Deserialize, surrealdb::types::SurrealValue)]
@@ -881,11 +886,9 @@ async fn list_productions(
 }
 
 async fn delete_production(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("production", id.as_str());
 
     // Clean up involvements then delete
@@ -901,11 +904,9 @@ async fn delete_production(
 // -- Organizations --
 
 async fn list_organizations(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { template_user, .. }: AdminUser,
     Query(params): Query<SearchParams>,
 ) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
     let search = params.q.clone().unwrap_or_default();
 
     #[derive(Debug, Deserialize, surrealdb::types::SurrealValue)]
@@ -966,11 +967,9 @@ async fn list_organizations(
 }
 
 async fn delete_organization(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("organization", id.as_str());
 
     // Clean up memberships then delete
@@ -984,11 +983,9 @@ async fn delete_organization(
 }
 
 async fn toggle_org_verified(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("organization", id.as_str());
 
     DB.query("UPDATE $oid SET verified = !verified")
@@ -1003,14 +1000,149 @@ async fn toggle_org_verified(
     Ok(Redirect::to("/admin/organizations"))
 }
 
+// -- Organization types --
+
+#[derive(Deserialize)]
+struct OrgTypeFlashQuery {
+    status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OrgTypeForm {
+    name: String,
+}
+
+/// Map an org-type status code to `(message, kind)` for the flash banner.
+fn parse_org_type_flash(status: Option<&str>) -> (Option<String>, String) {
+    let (msg, kind) = match status {
+        Some("created") => ("Organization type created.".to_string(), "ok"),
+        Some("renamed") => ("Organization type renamed.".to_string(), "ok"),
+        Some("deleted") => ("Organization type deleted.".to_string(), "ok"),
+        Some("invalid") => (
+            format!(
+                "Enter a name of at most {} characters.",
+                crate::models::organization::ORGANIZATION_TYPE_NAME_MAX
+            ),
+            "err",
+        ),
+        Some("duplicate") => ("A type with that name already exists.".to_string(), "err"),
+        Some("in_use") => (
+            "That type is still used by organizations. Reassign them before deleting it."
+                .to_string(),
+            "err",
+        ),
+        Some("not_found") => ("Organization type not found.".to_string(), "err"),
+        _ => return (None, String::new()),
+    };
+    (Some(msg), kind.to_string())
+}
+
+/// Redirect back to the org-types page with a flash status, mapping the
+/// model's validation/conflict/not-found errors to status codes.
+fn org_type_redirect(
+    result: Result<(), Error>,
+    ok_status: &str,
+    conflict_status: &str,
+) -> Result<Redirect, Error> {
+    let status = match result {
+        Ok(()) => ok_status,
+        Err(Error::Validation(_) | Error::ValidationFields(_)) => "invalid",
+        Err(Error::Conflict(_)) => conflict_status,
+        Err(Error::NotFound) => "not_found",
+        Err(e) => return Err(e),
+    };
+    Ok(Redirect::to(&format!("/admin/org-types?status={}", status)))
+}
+
+async fn list_org_types(
+    AdminUser { template_user, .. }: AdminUser,
+    Query(q): Query<OrgTypeFlashQuery>,
+) -> Result<Html<String>, Error> {
+    let org_types: Vec<OrgTypeRow> = crate::models::organization::OrganizationModel::new()
+        .get_organization_types_with_usage()
+        .await?
+        .into_iter()
+        .map(|t| OrgTypeRow {
+            id: t.id.key_string(),
+            name: t.name,
+            usage: t.usage,
+        })
+        .collect();
+
+    let (flash, flash_kind) = parse_org_type_flash(q.status.as_deref());
+
+    let base = BaseContext::new()
+        .with_page("admin")
+        .with_user(template_user);
+
+    let template = crate::with_base!(AdminOrgTypesTemplate, base, {
+        org_types,
+        flash,
+        flash_kind,
+    });
+
+    Ok(Html(template.render().map_err(|e| {
+        error!("Failed to render admin org types: {}", e);
+        Error::template(e.to_string())
+    })?))
+}
+
+async fn create_org_type(
+    AdminUser { user, .. }: AdminUser,
+    axum::Form(form): axum::Form<OrgTypeForm>,
+) -> Result<Redirect, Error> {
+    let result = crate::models::organization::OrganizationModel::new()
+        .create_organization_type(&form.name)
+        .await
+        .map(|created| {
+            info!(
+                "Admin {} created organization type {} ({})",
+                user.username,
+                created.name,
+                created.id.display()
+            );
+        });
+    org_type_redirect(result, "created", "duplicate")
+}
+
+async fn rename_org_type(
+    AdminUser { user, .. }: AdminUser,
+    Path(id): Path<String>,
+    axum::Form(form): axum::Form<OrgTypeForm>,
+) -> Result<Redirect, Error> {
+    let record_id = surrealdb::types::RecordId::new("organization_type", id.as_str());
+    let result = crate::models::organization::OrganizationModel::new()
+        .rename_organization_type(&record_id, &form.name)
+        .await;
+    if result.is_ok() {
+        info!(
+            "Admin {} renamed organization type {} to {}",
+            user.username, id, form.name
+        );
+    }
+    org_type_redirect(result, "renamed", "duplicate")
+}
+
+async fn delete_org_type(
+    AdminUser { user, .. }: AdminUser,
+    Path(id): Path<String>,
+) -> Result<Redirect, Error> {
+    let record_id = surrealdb::types::RecordId::new("organization_type", id.as_str());
+    let result = crate::models::organization::OrganizationModel::new()
+        .delete_organization_type(&record_id)
+        .await;
+    if result.is_ok() {
+        info!("Admin {} deleted organization type {}", user.username, id);
+    }
+    org_type_redirect(result, "deleted", "in_use")
+}
+
 // -- Locations --
 
 async fn list_locations(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { template_user, .. }: AdminUser,
     Query(params): Query<SearchParams>,
 ) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
     let search = params.q.clone().unwrap_or_default();
 
     #[derive(Debug, Deserialize, surrealdb::types::SurrealValue)]
@@ -1067,11 +1199,9 @@ async fn list_locations(
 }
 
 async fn delete_location(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(id): Path<String>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let record_id = surrealdb::types::RecordId::new("location", id.as_str());
 
     DB.query("DELETE $id")
@@ -1085,9 +1215,27 @@ async fn delete_location(
 
 // -- Embedding rebuild --
 
-async fn rebuild_embeddings(AuthenticatedUser(user): AuthenticatedUser) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
+#[derive(Debug, Deserialize)]
+struct ReadOnlyForm {
+    enabled: bool,
+}
 
+/// POST /admin/read-only — switch read-only maintenance mode on or off
+/// until the next restart (see `middleware::read_only`).
+async fn set_read_only_mode(
+    AdminUser { user, .. }: AdminUser,
+    axum::Form(form): axum::Form<ReadOnlyForm>,
+) -> Result<Redirect, Error> {
+    read_only::set_read_only(form.enabled);
+    warn!(
+        "Admin {} turned read-only mode {}",
+        user.username,
+        if form.enabled { "on" } else { "off" }
+    );
+    Ok(Redirect::to("/admin"))
+}
+
+async fn rebuild_embeddings(AdminUser { user, .. }: AdminUser) -> Result<Redirect, Error> {
     if REBUILD_IN_PROGRESS
         .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
         .is_err()
@@ -1437,11 +1585,7 @@ async fn run_embedding_rebuild() -> Result<(), Box<dyn std::error::Error + Send
 
 // -- Backup --
 
-async fn backup_all(
-    AuthenticatedUser(user): AuthenticatedUser,
-) -> Result<impl IntoResponse, Error> {
-    require_admin(&user).await?;
-
+async fn backup_all(AdminUser { user, .. }: AdminUser) -> Result<impl IntoResponse, Error> {
     info!("Admin {} initiated full backup", user.username);
 
     // 1. Export database via SurrealDB HTTP endpoint
@@ -1540,304 +1684,13 @@ fn build_backup_zip(db_export: &[u8], files: &[(String, Vec<u8>)]) -> Result<Vec
 
 // -- Cleanup orphaned files --
 
-/// A file reference from the database: S3 key + source info for broken link reporting.
-#[derive(Debug, Clone)]
-struct FileRef {
-    key: String,
-    entity: String, // e.g. "person:abc123"
-    field: String,  // e.g. "avatar", "photos[2].url"
-}
-
-/// Collect all referenced S3 keys from every table that stores file URLs.
-/// Returns (set of all keys for orphan detection, vec of all refs for broken link detection).
-async fn collect_all_referenced_files()
--> Result<(std::collections::HashSet<String>, Vec<FileRef>), Error> {
-    let mut keys: std::collections::HashSet<String> = std::collections::HashSet::new();
-    let mut refs: Vec<FileRef> = Vec::new();
-
-    #[derive(Debug, Deserialize, SurrealValue)]
-    struct PhotoRef {
-        url: Option<String>,
-        thumbnail_url: Option<String>,
-    }
-
-    /// Helper: extract key, insert into set, and record the reference.
-    fn track(
-        keys: &mut std::collections::HashSet<String>,
-        refs: &mut Vec<FileRef>,
-        url: &str,
-        entity: &str,
-        field: &str,
-    ) {
-        let key = url.strip_prefix("/api/media/").unwrap_or(url).to_string();
-        keys.insert(key.clone());
-        // Also derive thumbnail key
-        if let Some(slash_pos) = key.rfind('/') {
-            let dir = &key[..slash_pos];
-            let filename = &key[slash_pos + 1..];
-            if !filename.starts_with("thumb_") {
-                keys.insert(format!("{}/thumb_{}", dir, filename));
-            }
-        }
-        refs.push(FileRef {
-            key,
-            entity: entity.to_string(),
-            field: field.to_string(),
-        });
-    }
-
-    // Person: avatar + photo gallery
-    {
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct PersonFiles {
-            id: String,
-            name: Option<String>,
-            avatar: Option<String>,
-            photos: Option<Vec<PhotoRef>>,
-        }
-
-        let rows: Vec<PersonFiles> = DB
-            .query("SELECT <string> id AS id, name, profile.avatar AS avatar, profile.photos AS photos FROM person")
-            .await
-            .map_err(|e| Error::Database(e.to_string()))?
-            .take(0)
-            .unwrap_or_default();
-
-        for row in rows {
-            let entity = format!("person {} ({})", row.name.as_deref().unwrap_or("?"), row.id);
-            if let Some(avatar) = row.avatar {
-                track(&mut keys, &mut refs, &avatar, &entity, "avatar");
-            }
-            if let Some(photos) = row.photos {
-                for (i, photo) in photos.iter().enumerate() {
-                    if let Some(ref url) = photo.url {
-                        track(
-                            &mut keys,
-                            &mut refs,
-                            url,
-                            &entity,
-                            &format!("photos[{}]", i),
-                        );
-                    }
-                    if let Some(ref thumb) = photo.thumbnail_url {
-                        track(
-                            &mut keys,
-                            &mut refs,
-                            thumb,
-                            &entity,
-                            &format!("photos[{}].thumb", i),
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    // Organization: logo
-    {
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct OrgFiles {
-            id: String,
-            name: Option<String>,
-            logo: Option<String>,
-        }
-
-        let rows: Vec<OrgFiles> = DB
-            .query("SELECT <string> id AS id, name, logo FROM organization")
-            .await
-            .map_err(|e| Error::Database(e.to_string()))?
-            .take(0)
-            .unwrap_or_default();
-
-        for row in rows {
-            let entity = format!("org {} ({})", row.name.as_deref().unwrap_or("?"), row.id);
-            if let Some(logo) = row.logo {
-                track(&mut keys, &mut refs, &logo, &entity, "logo");
-            }
-        }
-    }
-
-    // Production: header_photo, poster_photo, poster_url, photo gallery
-    {
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct ProdFiles {
-            id: String,
-            title: Option<String>,
-            header_photo: Option<String>,
-            poster_photo: Option<String>,
-            poster_url: Option<String>,
-            photos: Option<Vec<PhotoRef>>,
-        }
-
-        let rows: Vec<ProdFiles> = DB
-            .query("SELECT <string> id AS id, title, header_photo, poster_photo, poster_url, photos FROM production")
-            .await
-            .map_err(|e| Error::Database(e.to_string()))?
-            .take(0)
-            .unwrap_or_default();
-
-        for row in rows {
-            let entity = format!(
-                "production {} ({})",
-                row.title.as_deref().unwrap_or("?"),
-                row.id
-            );
-            if let Some(v) = row.header_photo {
-                track(&mut keys, &mut refs, &v, &entity, "header_photo");
-            }
-            if let Some(v) = row.poster_photo {
-                track(&mut keys, &mut refs, &v, &entity, "poster_photo");
-            }
-            if let Some(v) = row.poster_url
-                && v.starts_with("/api/media/")
-            {
-                track(&mut keys, &mut refs, &v, &entity, "poster_url");
-            }
-            if let Some(photos) = row.photos {
-                for (i, photo) in photos.iter().enumerate() {
-                    if let Some(ref url) = photo.url {
-                        track(
-                            &mut keys,
-                            &mut refs,
-                            url,
-                            &entity,
-                            &format!("photos[{}]", i),
-                        );
-                    }
-                    if let Some(ref thumb) = photo.thumbnail_url {
-                        track(
-                            &mut keys,
-                            &mut refs,
-                            thumb,
-                            &entity,
-                            &format!("photos[{}].thumb", i),
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    // Location: profile_photo + photo gallery
-    {
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct LocFiles {
-            id: String,
-            name: Option<String>,
-            profile_photo: Option<String>,
-            photos: Option<Vec<PhotoRef>>,
-        }
-
-        let rows: Vec<LocFiles> = DB
-            .query("SELECT <string> id AS id, name, profile_photo, photos FROM location")
-            .await
-            .map_err(|e| Error::Database(e.to_string()))?
-            .take(0)
-            .unwrap_or_default();
-
-        for row in rows {
-            let entity = format!(
-                "location {} ({})",
-                row.name.as_deref().unwrap_or("?"),
-                row.id
-            );
-            if let Some(v) = row.profile_photo {
-                track(&mut keys, &mut refs, &v, &entity, "profile_photo");
-            }
-            if let Some(photos) = row.photos {
-                for (i, photo) in photos.iter().enumerate() {
-                    if let Some(ref url) = photo.url {
-                        track(
-                            &mut keys,
-                            &mut refs,
-                            url,
-                            &entity,
-                            &format!("photos[{}]", i),
-                        );
-                    }
-                    if let Some(ref thumb) = photo.thumbnail_url {
-                        track(
-                            &mut keys,
-                            &mut refs,
-                            thumb,
-                            &entity,
-                            &format!("photos[{}].thumb", i),
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    // Production scripts: file_url / file_key
-    {
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct ScriptFiles {
-            id: String,
-            file_url: Option<String>,
-            file_key: Option<String>,
-        }
-
-        let rows: Vec<ScriptFiles> = DB
-            .query("SELECT <string> id AS id, file_url, file_key FROM production_script")
-            .await
-            .map_err(|e| Error::Database(e.to_string()))?
-            .take(0)
-            .unwrap_or_default();
-
-        for row in rows {
-            let entity = format!("script ({})", row.id);
-            if let Some(v) = row.file_url {
-                track(&mut keys, &mut refs, &v, &entity, "file_url");
-            }
-            if let Some(v) = row.file_key {
-                keys.insert(v.clone());
-                refs.push(FileRef {
-                    key: v,
-                    entity: entity.clone(),
-                    field: "file_key".to_string(),
-                });
-            }
-        }
-    }
-
-    // Media table: uri (linked from person.profile.media_other, person.profile.resume)
-    {
-        #[derive(Debug, Deserialize, SurrealValue)]
-        struct MediaFiles {
-            id: String,
-            uri: Option<String>,
-        }
-
-        let rows: Vec<MediaFiles> = DB
-            .query("SELECT <string> id AS id, uri FROM media")
-            .await
-            .map_err(|e| Error::Database(e.to_string()))?
-            .take(0)
-            .unwrap_or_default();
-
-        for row in rows {
-            let entity = format!("media ({})", row.id);
-            if let Some(v) = row.uri {
-                track(&mut keys, &mut refs, &v, &entity, "uri");
-            }
-        }
-    }
-
-    Ok((keys, refs))
-}
-
 /// GET /admin/cleanup-files — preview orphaned files AND broken links
-async fn preview_orphaned_files(
-    AuthenticatedUser(user): AuthenticatedUser,
-) -> Result<Html<String>, Error> {
-    require_admin(&user).await?;
-
+async fn preview_orphaned_files(_admin: AdminUser) -> Result<Html<String>, Error> {
     let s3_service = s3()?;
     let all_keys = s3_service.list_all_objects().await?;
     let all_keys_set: std::collections::HashSet<&str> =
         all_keys.iter().map(|k| k.as_str()).collect();
-    let (referenced_keys, all_refs) = collect_all_referenced_files().await?;
+    let (referenced_keys, all_refs) = storage_cleanup::collect_referenced_files().await?;
 
     let orphaned: Vec<&String> = all_keys
         .iter()
@@ -1845,7 +1698,7 @@ async fn preview_orphaned_files(
         .collect();
 
     // Find broken links: DB references pointing to S3 keys that don't exist
-    let broken: Vec<&FileRef> = all_refs
+    let broken: Vec<&storage_cleanup::FileRef> = all_refs
         .iter()
         .filter(|r| !all_keys_set.contains(r.key.as_str()))
         .collect();
@@ -1882,7 +1735,9 @@ h2 { font-size: 1.1rem; margin-top: 2rem; }
 .select-actions button { background: #333; color: #eee; border: 1px solid #555; padding: 0.3rem 0.8rem; border-radius: 4px; cursor: pointer; font-size: 0.8rem; }
 .select-actions button:hover { background: #444; }
 .count-label { color: #888; font-size: 0.8rem; margin-left: 0.5rem; }
-</style></head><body>
+</style>
+<script src="/static/js/inline-handlers.js"></script>
+</head><body>
 <h1>File Integrity Check</h1>"#,
     );
 
@@ -1917,8 +1772,8 @@ h2 { font-size: 1.1rem; margin-top: 2rem; }
     } else {
         html.push_str(r#"<form method="post" action="/admin/cleanup-files">"#);
         html.push_str(r#"<div class="select-actions">
-            <button type="button" onclick="document.querySelectorAll('input[name=keys]').forEach(c=>c.checked=true);updateCount()">Select All</button>
-            <button type="button" onclick="document.querySelectorAll('input[name=keys]').forEach(c=>c.checked=false);updateCount()">Select None</button>
+            <button type="button" data-handler-click="document.querySelectorAll('input[name=keys]').forEach(c=>c.checked=true);updateCount()">Select All</button>
+            <button type="button" data-handler-click="document.querySelectorAll('input[name=keys]').forEach(c=>c.checked=false);updateCount()">Select None</button>
             <span id="selected-count" class="count-label">0 selected</span>
         </div>"#);
         html.push_str(r#"<div class="file-list">"#);
@@ -1934,8 +1789,8 @@ h2 { font-size: 1.1rem; margin-top: 2rem; }
             if is_image {
                 html.push_str(&format!(
                     r#"<label class="file-item checkbox-item has-preview">
-                        <input type="checkbox" name="keys" value="{}" onchange="updateCount()">
-                        <img src="/api/media/{}" class="preview-img" loading="lazy" onerror="this.style.display='none'">
+                        <input type="checkbox" name="keys" value="{}" data-handler-change="updateCount()">
+                        <img src="/api/media/{}" class="preview-img" loading="lazy" data-handler-error="this.style.display='none'">
                         <span class="file-path">{}</span>
                     </label>"#,
                     escaped, escaped, escaped
@@ -1943,7 +1798,7 @@ h2 { font-size: 1.1rem; margin-top: 2rem; }
             } else {
                 html.push_str(&format!(
                     r#"<label class="file-item checkbox-item">
-                        <input type="checkbox" name="keys" value="{}" onchange="updateCount()">
+                        <input type="checkbox" name="keys" value="{}" data-handler-change="updateCount()">
                         <span class="file-path">{}</span>
                     </label>"#,
                     escaped, escaped
@@ -1952,14 +1807,20 @@ h2 { font-size: 1.1rem; margin-top: 2rem; }
         }
         html.push_str("</div>");
 
-        html.push_str(r#"
+        html.push_str(
+            r#"
             <button type="submit" class="btn btn-danger" id="delete-btn" disabled
-                onclick="return confirm('Delete the selected files? This cannot be undone.')">
+                data-handler-click="return confirm('Delete the selected files? This cannot be undone.')">
                 Delete Selected
             </button>
             <a href="/admin" class="btn btn-back">Cancel</a>
-        </form>
-        <script>
+        </form>"#,
+        );
+        html.push_str(&format!(
+            r#"<script nonce="{}">"#,
+            crate::middleware::csp::nonce()
+        ));
+        html.push_str(r#"
         function updateCount() {
             var checked = document.querySelectorAll('input[name=keys]:checked').length;
             document.getElementById('selected-count').textContent = checked + ' selected';
@@ -1978,11 +1839,9 @@ h2 { font-size: 1.1rem; margin-top: 2rem; }
 
 /// POST /admin/cleanup-files — delete selected orphaned files
 async fn cleanup_orphaned_files(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     axum::Form(form): axum::Form<Vec<(String, String)>>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     // Collect selected keys from form checkboxes
     let selected_keys: Vec<String> = form
         .iter()
@@ -1999,7 +1858,7 @@ async fn cleanup_orphaned_files(
     let all_keys = s3_service.list_all_objects().await?;
     let all_keys_set: std::collections::HashSet<&str> =
         all_keys.iter().map(|k| k.as_str()).collect();
-    let (referenced, _refs) = collect_all_referenced_files().await?;
+    let (referenced, _refs) = storage_cleanup::collect_referenced_files().await?;
 
     info!(
         "Admin {} deleting {} selected orphaned files",
@@ -2059,11 +1918,9 @@ struct FeatureFlagForm {
 }
 
 async fn feature_flags_page(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { template_user, .. }: AdminUser,
     Query(q): Query<FeatureFlagFlashQuery>,
 ) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
     let rows = crate::services::feature_flag::list_flags().await;
     let flags: Vec<FeatureFlagRow> = rows
         .into_iter()
@@ -2098,12 +1955,10 @@ async fn feature_flags_page(
 }
 
 async fn set_feature_flag(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     Path(key): Path<String>,
     axum::Form(form): axum::Form<FeatureFlagForm>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     use crate::services::feature_flag::FlagState;
     use std::str::FromStr;
     let new_state = match FlagState::from_str(form.state.as_str()) {
@@ -2143,11 +1998,9 @@ struct MailingListSubscribeForm {
 }
 
 async fn mailing_list_page(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { template_user, .. }: AdminUser,
     Query(q): Query<MailingListFlashQuery>,
 ) -> Result<Html<String>, Error> {
-    let template_user = require_admin(&user).await?;
-
     let svc = crate::services::listmonk::ListmonkService::from_env();
     let enabled = svc.is_some();
     let list_ids = svc
@@ -2178,11 +2031,9 @@ async fn mailing_list_page(
 }
 
 async fn mailing_list_subscribe(
-    AuthenticatedUser(user): AuthenticatedUser,
+    AdminUser { user, .. }: AdminUser,
     axum::Form(form): axum::Form<MailingListSubscribeForm>,
 ) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
     let target = form.target.trim();
     if target.is_empty() {
         return Ok(Redirect::to("/admin/mailing-list?status=missing"));
@@ -2230,11 +2081,7 @@ async fn mailing_list_subscribe(
     }
 }
 
-async fn mailing_list_sync_all(
-    AuthenticatedUser(user): AuthenticatedUser,
-) -> Result<Redirect, Error> {
-    require_admin(&user).await?;
-
+async fn mailing_list_sync_all(AdminUser { user, .. }: AdminUser) -> Result<Redirect, Error> {
     let Some(svc) = crate::services::listmonk::ListmonkService::from_env() else {
         return Ok(Redirect::to("/admin/mailing-list?status=disabled"));
     };
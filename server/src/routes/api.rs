@@ -1,7 +1,8 @@
-//! JSON/SSE API routes under `/api`: health and stats, TMDB/IMDB imports,
-//! production claims, involvement (credit) CRUD and verification, feedback,
-//! username checks, Datastar live-search/select endpoints for people, orgs,
-//! and productions, plus generated Open-Graph and QR profile images.
+//! JSON/SSE API routes under `/api`: health and stats (plus admin-only
+//! platform metrics), TMDB/IMDB imports, production claims, involvement
+//! (credit) CRUD and verification, feedback, username checks, skill
+//! typeahead, Datastar live-search/select endpoints for people, orgs, and
+//! productions, plus generated Open-Graph and QR profile images.
 
 use axum::{
     Extension, Json, Router,
@@ -18,11 +19,12 @@ use tracing::{debug, error, info};
 use crate::datastar;
 use crate::db::DB;
 use crate::html::escape_html;
-use crate::middleware::{AuthenticatedUser, CurrentUser};
+use crate::middleware::{AdminUser, AuthenticatedUser, CurrentUser};
 use crate::models::involvement::InvolvementModel;
+use crate::models::organization::VISIBLE_TO_VIEWER;
 use crate::models::production::ProductionModel;
 use crate::models::system::System;
-use crate::record_id_ext::RecordIdExt;
+use crate::record_id_ext::{RecordIdExt, normalize_record_id};
 
 /// Validate that a scope parameter is a safe identifier (alphanumeric, underscore, hyphen only).
 /// Prevents injection into Datastar signal keys and CSS selectors.
@@ -43,6 +45,7 @@ pub fn router() -> Router {
     Router::new()
         .route("/health", get(health_check))
         .route("/stats", get(stats))
+        .route("/admin/stats", get(admin_stats))
         .route("/avatar", get(avatar))
         .route("/fix-avatar-urls", post(fix_avatar_urls))
         .route("/tmdb/search", get(tmdb_search))
@@ -61,6 +64,7 @@ pub fn router() -> Router {
         .route("/involvements/{id}/reject", post(reject_involvement))
         .route("/feedback", post(submit_feedback))
         .route("/check-username", get(check_username))
+        .route("/skills", get(skills))
         .route("/people/search", get(people_search))
         .route("/people/search-sse", get(people_search_sse))
         .route("/people/select-sse", get(people_select_sse))
@@ -124,42 +128,56 @@ async fn stats() -> impl IntoResponse {
     Json(stats).into_response()
 }
 
+/// Admin-only platform metrics for the operations dashboard (cached briefly).
+async fn admin_stats(_admin: AdminUser) -> Result<Response, crate::error::Error> {
+    let metrics = System::cached_platform_metrics().await?;
+    Ok(Json(metrics.as_ref()).into_response())
+}
+
+/// Avatar for a person or organization (`?id=` record id, or a bare person
+/// key): redirects to the uploaded image, else serves a generated initials
+/// placeholder.
 #[axum::debug_handler]
-async fn avatar(Query(params): Query<HashMap<String, String>>) -> impl IntoResponse {
+async fn avatar(Query(params): Query<HashMap<String, String>>) -> Response {
     let id = params.get("id").map(|s| s.as_str()).unwrap_or("unknown");
-    debug!("Avatar requested for user: {}", id);
+    debug!("Avatar requested for: {}", id);
 
-    // First, try to get the actual avatar URL from the person's profile
-    let person_rid = if id.starts_with("person:") {
-        surrealdb::types::RecordId::parse_simple(id)
+    // Bare keys are people; `organization:…` ids get the same treatment
+    let rid = if id.contains(':') {
+        surrealdb::types::RecordId::parse_simple(id).ok()
     } else {
-        Ok(surrealdb::types::RecordId::new("person", id))
-    };
+        Some(surrealdb::types::RecordId::new("person", id))
+    }
+    .filter(|rid| matches!(rid.table.as_str(), "person" | "organization"));
 
-    // Query for the person's avatar URL
-    if let Ok(rid) = person_rid
+    let mut name = None;
+    if let Some(rid) = rid
         && let Ok(mut response) = DB
-            .query("SELECT profile.avatar FROM ONLY $pid LIMIT 1")
-            .bind(("pid", rid))
+            .query(
+                "SELECT (name ?? profile.name ?? username) AS name, \
+                 (profile.avatar ?? logo) AS image FROM ONLY $id LIMIT 1",
+            )
+            .bind(("id", rid))
             .await
-        && let Ok(result) = response.take::<Option<serde_json::Value>>(0)
-        && let Some(data) = result
-        && let Some(avatar_url) = data
-            .get("profile")
-            .and_then(|p| p.get("avatar"))
-            .and_then(|a| a.as_str())
+        && let Ok(Some(data)) = response.take::<Option<serde_json::Value>>(0)
     {
-        // User has a custom avatar, redirect to it
-        return Redirect::permanent(avatar_url);
+        // An uploaded avatar or logo wins; redirect to it
+        if let Some(image) = data["image"].as_str().filter(|s| !s.is_empty()) {
+            return Redirect::permanent(image).into_response();
+        }
+        name = data["name"].as_str().map(String::from);
     }
 
-    // Fall back to DiceBear for deterministic avatars based on user ID
-    let avatar_url = format!(
-        "https://api.dicebear.com/7.x/initials/svg?seed={}&backgroundColor=4f46e5",
-        id
-    );
-
-    Redirect::permanent(&avatar_url)
+    // Otherwise a generated initials placeholder with a per-id color
+    let name = name.unwrap_or_else(|| id.split_once(':').map_or(id, |(_, key)| key).to_string());
+    (
+        [
+            (axum::http::header::CONTENT_TYPE, "image/svg+xml"),
+            (axum::http::header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        crate::avatar::placeholder_svg(id, &name),
+    )
+        .into_response()
 }
 
 /// Search TMDB for people by name
@@ -1163,7 +1181,7 @@ async fn people_search_sse(
 
     if results.is_empty() && !is_email {
         html.push_str(if params.allow_email {
-            r##"<div class="invite-search-empty">No users found. Enter an email to invite someone new, or <a href="#" onclick="event.preventDefault();if(window.switchToLinkMode)window.switchToLinkMode();">invite by link</a>.</div>"##
+            r##"<div class="invite-search-empty">No users found. Enter an email to invite someone new, or <a href="#" data-handler-click="event.preventDefault();if(window.switchToLinkMode)window.switchToLinkMode();">invite by link</a>.</div>"##
         } else {
             r#"<div class="invite-search-empty">No users found.</div>"#
         });
@@ -1289,7 +1307,7 @@ struct OrgSearchSseQuery {
 
 #[axum::debug_handler]
 async fn orgs_search_sse(
-    _user: AuthenticatedUser,
+    AuthenticatedUser(user): AuthenticatedUser,
     Query(params): Query<OrgSearchSseQuery>,
 ) -> Response {
     use surrealdb::types::SurrealValue;
@@ -1318,7 +1336,8 @@ async fn orgs_search_sse(
         logo: Option<String>,
     }
 
-    let sql = "SELECT
+    let sql = format!(
+        "SELECT
             <string> id AS id,
             name,
             slug,
@@ -1327,12 +1346,20 @@ async fn orgs_search_sse(
             created_at
         FROM organization
         WHERE
-            string::lowercase(name ?? '') CONTAINS $q
-            OR string::lowercase(slug ?? '') CONTAINS $q
+            (string::lowercase(name ?? '') CONTAINS $q
+            OR string::lowercase(slug ?? '') CONTAINS $q)
+            AND {VISIBLE_TO_VIEWER}
         ORDER BY verified DESC, created_at DESC
-        LIMIT 8";
+        LIMIT 8"
+    );
+    let viewer = normalize_record_id(&user.id, "person");
 
-    let results: Vec<OrgHit> = match DB.query(sql).bind(("q", query_lower)).await {
+    let results: Vec<OrgHit> = match DB
+        .query(sql)
+        .bind(("q", query_lower))
+        .bind(("viewer", viewer))
+        .await
+    {
         Ok(mut resp) => resp.take(0).unwrap_or_default(),
         Err(e) => {
             error!("Org search SSE failed: {}", e);
@@ -1566,6 +1593,39 @@ async fn productions_select_sse(
     datastar::response(sse)
 }
 
+// -----------------------------------------------------------------------------
+// Skill Typeahead
+// -----------------------------------------------------------------------------
+
+/// Most suggestions `/api/skills` returns.
+const SKILL_SUGGESTIONS: usize = 10;
+
+#[derive(Deserialize)]
+struct SkillsQuery {
+    prefix: Option<String>,
+}
+
+/// Known skills for filter and profile-editing typeahead: skills from
+/// public profiles matching `?prefix=`, most used first, with usage counts.
+/// Without a prefix the most used skills are returned.
+#[axum::debug_handler]
+async fn skills(Query(params): Query<SkillsQuery>) -> impl IntoResponse {
+    use crate::models::person::{Person, skills_matching};
+
+    let known = Person::known_skills().await;
+    let matches = skills_matching(
+        &known,
+        params.prefix.as_deref().unwrap_or_default(),
+        SKILL_SUGGESTIONS,
+    );
+    let results: Vec<serde_json::Value> = matches
+        .into_iter()
+        .map(|(skill, count)| serde_json::json!({ "skill": skill, "count": count }))
+        .collect();
+
+    Json(serde_json::json!({ "results": results }))
+}
+
 // -----------------------------------------------------------------------------
 // Username Availability Check
 // -----------------------------------------------------------------------------
@@ -1772,7 +1832,7 @@ async fn og_invite_image(
             let poster_url = prod.poster_photo.or(prod.poster_url);
             if let Some(url) = poster_url {
                 let abs_url = if url.starts_with('/') {
-                    format!("{}{}", crate::config::app_url(), url)
+                    format!("{}{}", crate::config::public_base_url(), url)
                 } else {
                     url
                 };
@@ -1820,7 +1880,7 @@ async fn qr_profile_image(
         .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or_else(|| (axum::http::StatusCode::NOT_FOUND, "Not found".to_string()))?;
 
-    let profile_url = format!("{}/{}", crate::config::app_url(), username);
+    let profile_url = format!("{}/{}", crate::config::public_base_url(), username);
     debug!("QR code: generating for {}", profile_url);
 
     // QR matrix generation + pixel rasterization + PNG encode are CPU-bound;
@@ -1,6 +1,6 @@
 //! Authentication routes: signup (honeypot + form-token timing +
-//! proof-of-work spam layers, IP rate limiting), login/logout with the
-//! `auth_token` JWT cookie, email verification (code form and direct
+//! proof-of-work spam layers, IP rate limiting), rate-limited login/logout
+//! with the `auth_token` JWT cookie, email verification (code form and direct
 //! link), password reset, resend-verification, and `/i/{token}` short
 //! invite links that either join the target directly or land on signup.
 
@@ -12,14 +12,13 @@ use axum::{
     response::{Html, IntoResponse, Response},
     routing::{get, post},
 };
-use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use axum_extra::extract::cookie::CookieJar;
 use serde::Deserialize;
 
-use std::collections::HashMap;
 use std::env;
 use std::net::{IpAddr, SocketAddr};
-use std::sync::{LazyLock, Mutex, Once};
-use std::time::Instant;
+use std::sync::{LazyLock, Once};
+use std::time::Duration;
 
 use spow::pow::Pow;
 use tracing::{debug, error, info, warn};
@@ -35,7 +34,7 @@ fn ensure_spow_init() {
 }
 
 /// Generate a PoW challenge (valid for 300 seconds / 5 minutes)
-fn generate_pow_challenge() -> String {
+pub(crate) fn generate_pow_challenge() -> String {
     ensure_spow_init();
     Pow::with_difficulty(20, 300)
         .map(|p| p.build_challenge())
@@ -44,7 +43,7 @@ fn generate_pow_challenge() -> String {
 
 /// Generate a signed form token encoding the current timestamp.
 /// Uses jsonwebtoken to create a short-lived token.
-fn generate_form_token() -> String {
+pub(crate) fn generate_form_token() -> String {
     use jsonwebtoken::{EncodingKey, Header, encode};
     #[derive(serde::Serialize)]
     struct FormClaims {
@@ -66,7 +65,7 @@ fn generate_form_token() -> String {
 }
 
 /// Validate the form token and check minimum elapsed time (3 seconds).
-fn validate_form_token(token: &str) -> bool {
+pub(crate) fn validate_form_token(token: &str) -> bool {
     use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
     #[derive(serde::Deserialize)]
     struct FormClaims {
@@ -98,10 +97,17 @@ fn validate_form_token(token: &str) -> bool {
     }
 }
 
-/// Simple in-memory per-IP rate limiter for signup — a coarse backstop behind
-/// the per-request honeypot / form-token / proof-of-work layers.
-static SIGNUP_RATE_LIMIT: LazyLock<Mutex<HashMap<String, Vec<Instant>>>> =
-    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// Whether `solution` solves a challenge from [`generate_pow_challenge`].
+pub(crate) fn validate_pow_solution(solution: &str) -> bool {
+    ensure_spow_init();
+    match Pow::validate(solution) {
+        Ok(_) => true,
+        Err(e) => {
+            debug!("PoW validation failed: {}", e);
+            false
+        }
+    }
+}
 
 /// Max signups per resolved client IP per hour. Configurable via
 /// `SIGNUP_MAX_PER_HOUR` (default 20) so it can be raised in production without
@@ -115,20 +121,21 @@ static SIGNUP_MAX_PER_HOUR: LazyLock<usize> = LazyLock::new(|| {
         .filter(|n| *n > 0)
         .unwrap_or(20)
 });
-const SIGNUP_WINDOW_SECS: u64 = 3600;
-
-fn check_signup_rate_limit(ip: &str) -> bool {
-    let mut map = SIGNUP_RATE_LIMIT.lock().unwrap();
-    let now = Instant::now();
-    let attempts = map.entry(ip.to_string()).or_default();
-    attempts.retain(|t| now.duration_since(*t).as_secs() < SIGNUP_WINDOW_SECS);
-    if attempts.len() >= *SIGNUP_MAX_PER_HOUR {
-        false
-    } else {
-        attempts.push(now);
-        true
-    }
-}
+
+/// Per-IP signup limiter — a coarse backstop behind the per-request
+/// honeypot / form-token / proof-of-work layers.
+static SIGNUP_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(*SIGNUP_MAX_PER_HOUR, Duration::from_secs(3600)));
+
+/// Per-IP login limiter: generous enough for a shared office or CGNAT
+/// address, tight enough to stop password guessing.
+static LOGIN_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(20, Duration::from_secs(15 * 60)));
+
+/// Per-address resend-verification limiter, so the form can't be used to
+/// flood someone's inbox.
+static RESEND_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(3, Duration::from_secs(15 * 60)));
 
 /// Client-IP precedence, pure for testing: left-most `X-Forwarded-For` entry,
 /// then `X-Real-IP`, then the socket peer address. The socket fallback means
@@ -149,7 +156,7 @@ pub fn resolve_client_ip(
         .unwrap_or_else(|| peer.to_string())
 }
 
-fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> String {
+pub(crate) fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> String {
     resolve_client_ip(
         headers.get("x-forwarded-for").and_then(|v| v.to_str().ok()),
         headers.get("x-real-ip").and_then(|v| v.to_str().ok()),
@@ -159,8 +166,10 @@ fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> String {
 
 use crate::{
     error::Error,
+    i18n::{t, tf},
     middleware::UserExtractor,
     models::person::{CreateUser, LoginUser, Person},
+    rate_limit::RateLimiter,
     record_id_ext::RecordIdExt,
     response,
     services::{
@@ -279,34 +288,26 @@ async fn signup(
     let campaign = form.campaign.as_deref().unwrap_or("-");
 
     // Coarse per-IP rate limit (configurable via SIGNUP_MAX_PER_HOUR).
-    if !check_signup_rate_limit(&ip) {
+    if let Err(e) = SIGNUP_LIMITER.hit(&ip, &t("auth.too_many_signups")) {
         warn!(reason = "rate_limit", ip = %ip, campaign, "signup blocked");
-        return Err(Error::Validation(
-            "Too many signup attempts. Please try again later.".to_string(),
-        ));
+        return Err(e);
     }
 
     // Layer 1: Honeypot — reject if the hidden "website" field is filled
     if form.website.as_ref().is_some_and(|w| !w.is_empty()) {
         warn!(reason = "honeypot", ip = %ip, campaign, "signup blocked");
-        return Err(Error::Validation(
-            "Signup failed. Please try again.".to_string(),
-        ));
+        return Err(Error::Validation(t("auth.signup_failed")));
     }
 
     // Layer 2: Time check — reject if form was submitted too fast (< 3 seconds)
     if let Some(ref token) = form.form_token {
         if !validate_form_token(token) {
             warn!(reason = "form_token", ip = %ip, campaign, "signup blocked: token invalid or too fast");
-            return Err(Error::Validation(
-                "Signup failed. Please try again.".to_string(),
-            ));
+            return Err(Error::Validation(t("auth.signup_failed")));
         }
     } else {
         warn!(reason = "form_token_missing", ip = %ip, campaign, "signup blocked");
-        return Err(Error::Validation(
-            "Signup failed. Please try again.".to_string(),
-        ));
+        return Err(Error::Validation(t("auth.signup_failed")));
     }
 
     // Layer 3: Proof-of-Work — reject if PoW solution is missing or invalid
@@ -315,19 +316,23 @@ async fn signup(
         Some(solution) if !solution.is_empty() => {
             if let Err(e) = Pow::validate(solution) {
                 warn!(reason = "pow_invalid", ip = %ip, campaign, error = %e, "signup blocked");
-                return Err(Error::Validation(
-                    "Verification failed. Please reload and try again.".to_string(),
-                ));
+                return Err(Error::Validation(t("auth.verification_failed")));
             }
         }
         _ => {
             warn!(reason = "pow_missing", ip = %ip, campaign, "signup blocked");
-            return Err(Error::Validation(
-                "Verification failed. Please reload and try again.".to_string(),
-            ));
+            return Err(Error::Validation(t("auth.verification_failed")));
         }
     }
 
+    // Layer 4: third-party captcha, when configured (no-op otherwise)
+    crate::captcha::verify(
+        crate::captcha::CaptchaAction::Signup,
+        form.captcha_token.as_deref(),
+        &ip,
+    )
+    .await?;
+
     // Try to create the user
     let email = form.email.clone();
     let redirect = form.redirect.clone();
@@ -355,12 +360,7 @@ async fn signup(
             }
 
             // Create authentication cookie with the JWT token
-            let cookie = Cookie::build(("auth_token", token))
-                .path("/")
-                .same_site(SameSite::Lax)
-                .http_only(true)
-                .secure(env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
-                .build();
+            let cookie = crate::auth::session_cookie(token, false);
 
             // Redirect to email verification page, forwarding redirect param
             let mut verify_url = format!("/verify-email?email={}", urlencoding::encode(&email));
@@ -422,9 +422,19 @@ async fn login_form(
 }
 
 #[axum::debug_handler]
-async fn login(Form(form): Form<LoginUser>) -> Result<Response, Error> {
+async fn login(
+    headers: HeaderMap,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Form(form): Form<LoginUser>,
+) -> Result<Response, Error> {
     debug!("Processing login for: {}", form.email);
 
+    let ip = client_ip(&headers, peer);
+    if let Err(e) = LOGIN_LIMITER.hit(&ip, &t("auth.too_many_logins")) {
+        warn!(ip = %ip, "login blocked by rate limit");
+        return Err(e);
+    }
+
     // "Remember me": 30-day session instead of the standard 12 hours.
     let remember = form.remember.is_some();
 
@@ -437,26 +447,16 @@ async fn login(Form(form): Form<LoginUser>) -> Result<Response, Error> {
             // Create authentication cookie with the JWT token. A remembered
             // login gets a persistent cookie whose Max-Age matches the JWT
             // expiry; otherwise it stays a browser-session cookie.
-            let mut builder = Cookie::build(("auth_token", token))
-                .path("/")
-                .same_site(SameSite::Lax)
-                .http_only(true)
-                .secure(
-                    env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false",
-                );
-            if remember {
-                builder = builder.max_age(cookie::time::Duration::seconds(
-                    crate::auth::session_duration(true) as i64,
-                ));
-            }
-            let cookie = builder.build();
+            let cookie = crate::auth::session_cookie(token, remember);
 
-            // Redirect to profile or the originally requested page
-            let redirect_to = form.redirect_to.unwrap_or_else(|| "/profile".to_string());
+            // Redirect to profile or the originally requested page (if it's
+            // one of ours — never an open redirect)
+            let redirect_to =
+                response::safe_redirect_target(form.redirect_to.as_deref(), "/profile");
 
             Ok((
                 CookieJar::new().add(cookie),
-                response::redirect(&redirect_to),
+                response::redirect_to_target(&redirect_to),
             )
                 .into_response())
         }
@@ -471,9 +471,9 @@ async fn login(Form(form): Form<LoginUser>) -> Result<Response, Error> {
             // Check if the error is about email verification
             let error_message = match &e {
                 Error::Validation(msg) if msg.contains("email address has not been verified") => {
-                    msg.clone()
+                    t("auth.email_not_verified")
                 }
-                _ => "Invalid email or password".to_string(),
+                _ => t("auth.invalid_credentials"),
             };
 
             template.error = Some(error_message);
@@ -494,13 +494,7 @@ async fn logout(jar: CookieJar) -> Response {
     debug!("Processing logout");
 
     // Create a cookie that expires immediately to clear the auth
-    let cookie = Cookie::build(("auth_token", ""))
-        .path("/")
-        .same_site(SameSite::Lax)
-        .http_only(true)
-        .secure(env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
-        .max_age(Default::default())
-        .build();
+    let cookie = crate::auth::cleared_session_cookie();
 
     (jar.remove(cookie), response::redirect("/")).into_response()
 }
@@ -524,8 +518,7 @@ async fn verify_email_form(
     template.email = params.get("email").cloned();
     template.redirect = params.get("redirect").cloned();
     if params.contains_key("resent") {
-        template.success =
-            Some("A new verification code has been sent. Please check your email.".to_string());
+        template.success = Some(t("auth.verification_code_sent"));
     }
     template.pixel_id = crate::config::meta_pixel_id();
 
@@ -575,7 +568,7 @@ async fn verify_email(
             if let Ok(email_service) = EmailService::from_env() {
                 let to_email = person.email.clone();
                 let to_name = person.name.clone();
-                let app = crate::config::app_url();
+                let app = crate::config::public_base_url();
                 let invite_url = app.clone();
                 let profile_url = format!("{}/{}", app, person.username);
                 tokio::spawn(async move {
@@ -612,18 +605,15 @@ async fn verify_email(
                         );
                         url
                     }
-                    Ok(None) => form
-                        .redirect
-                        .clone()
-                        .unwrap_or_else(|| "/profile/edit".to_string()),
+                    Ok(None) => {
+                        response::safe_redirect_target(form.redirect.as_deref(), "/profile/edit")
+                    }
                     Err(e) => {
                         error!(
                             "Failed to process pending invitations for {}: {}",
                             form.email, e
                         );
-                        form.redirect
-                            .clone()
-                            .unwrap_or_else(|| "/profile/edit".to_string())
+                        response::safe_redirect_target(form.redirect.as_deref(), "/profile/edit")
                     }
                 };
 
@@ -644,12 +634,7 @@ async fn verify_email(
 
             // Create authentication token + cookie for the verified user
             let token = crate::auth::create_jwt(&person_id, &person.username, &person.email)?;
-            let cookie = Cookie::build(("auth_token", token))
-                .path("/")
-                .same_site(SameSite::Lax)
-                .http_only(true)
-                .secure(env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
-                .build();
+            let cookie = crate::auth::session_cookie(token, false);
 
             // Campaign-attributed signups land on a brief interstitial that
             // fires the Meta Pixel CompleteRegistration before continuing;
@@ -666,7 +651,7 @@ async fn verify_email(
                 })?;
                 Ok((jar.add(cookie), Html(html)).into_response())
             } else {
-                Ok((jar.add(cookie), response::redirect(&redirect_url)).into_response())
+                Ok((jar.add(cookie), response::redirect_to_target(&redirect_url)).into_response())
             }
         }
         Err(e) => {
@@ -676,7 +661,7 @@ async fn verify_email(
             let base = BaseContext::new().with_page("verify-email");
 
             let mut template = EmailVerificationTemplate::new(base);
-            template.error = Some("Invalid or expired verification code".to_string());
+            template.error = Some(t("auth.invalid_verification_code"));
             template.email = Some(form.email);
 
             let html = template.render().map_err(|e| {
@@ -780,10 +765,7 @@ async fn forgot_password(Form(form): Form<ForgotPasswordForm>) -> Result<Respons
     let base = BaseContext::new().with_page("forgot-password");
 
     let mut template = ForgotPasswordTemplate::new(base);
-    template.success = Some(format!(
-        "If an account exists for {}, a password reset code has been sent.",
-        form.email
-    ));
+    template.success = Some(tf("auth.reset_code_sent", &[("email", &form.email)]));
     template.email = Some(form.email.clone());
 
     let html = template.render().map_err(|e| {
@@ -846,7 +828,7 @@ async fn reset_password(Form(form): Form<ResetPasswordForm>) -> Result<Response,
         let base = BaseContext::new().with_page("reset-password");
 
         let mut template = ResetPasswordTemplate::new(base);
-        template.error = Some("Passwords do not match".to_string());
+        template.error = Some(t("auth.passwords_mismatch"));
         template.email = Some(form.email);
         template.code = Some(form.code);
 
@@ -893,7 +875,7 @@ async fn reset_password(Form(form): Form<ResetPasswordForm>) -> Result<Response,
             let base = BaseContext::new().with_page("reset-password");
 
             let mut template = ResetPasswordTemplate::new(base);
-            template.error = Some("Invalid or expired reset code".to_string());
+            template.error = Some(t("auth.invalid_reset_code"));
             template.email = Some(form.email);
             template.code = Some(form.code);
 
@@ -922,6 +904,11 @@ struct ResendVerificationForm {
 async fn resend_verification(Form(form): Form<ResendVerificationForm>) -> Result<Response, Error> {
     info!(email = %form.email, "resend verification requested");
 
+    RESEND_LIMITER.hit(
+        &form.email.trim().to_lowercase(),
+        &t("auth.too_many_verification_emails"),
+    )?;
+
     // The user-facing response is intentionally identical in every branch
     // below (anti-enumeration); these logs are the only way to see what
     // actually happened, so they're at info/warn rather than debug.
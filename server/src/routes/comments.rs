@@ -0,0 +1,205 @@
+//! Production comment routes under `/productions/{slug}/comments`: a
+//! paginated JSON list, plus form actions to post, edit (author only), and
+//! delete (author, production editors, or platform admins). New comments
+//! notify the production's owners via the bell.
+
+use axum::{
+    Form, Json, Router,
+    extract::{Path, Query},
+    response::Redirect,
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use surrealdb::types::RecordId;
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::Error,
+    middleware::AuthenticatedUser,
+    models::{
+        comment::{CommentModel, CommentView},
+        notification::NotificationModel,
+        production::{Production, ProductionModel},
+    },
+    record_id_ext::RecordIdExt,
+    templates::User,
+};
+
+/// Comments per page, for both the JSON list and the inline first page on
+/// the production view.
+pub(crate) const COMMENTS_PAGE_SIZE: usize = 20;
+
+/// Routes for listing, posting, editing, and deleting production comments.
+pub fn router() -> Router {
+    Router::new()
+        .route(
+            "/productions/{slug}/comments",
+            get(list_comments).post(create_comment),
+        )
+        .route(
+            "/productions/{slug}/comments/{comment_id}/edit",
+            post(edit_comment),
+        )
+        .route(
+            "/productions/{slug}/comments/{comment_id}/delete",
+            post(delete_comment),
+        )
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    page: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct CommentListResponse {
+    comments: Vec<CommentView>,
+    total: u64,
+    page: usize,
+    per_page: usize,
+    has_more: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentForm {
+    body: String,
+}
+
+/// Resolve `{comment_id}` (bare key) and make sure it belongs to this
+/// production — a comment id from another production is a 404 here.
+async fn load_comment(
+    production: &Production,
+    comment_key: &str,
+) -> Result<crate::models::comment::Comment, Error> {
+    let comment_id = RecordId::new("comment", comment_key);
+    let comment = CommentModel::get(&comment_id).await?;
+    if comment.target != production.id {
+        return Err(Error::NotFound);
+    }
+    Ok(comment)
+}
+
+/// Paginated comments for a production (JSON, oldest first).
+async fn list_comments(
+    Path(slug): Path<String>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<CommentListResponse>, Error> {
+    let production = ProductionModel::get_by_slug(&slug).await?;
+    let page = query.page.unwrap_or(1).max(1);
+    let offset = (page - 1) * COMMENTS_PAGE_SIZE;
+
+    let comments =
+        CommentModel::list_for_target(&production.id, COMMENTS_PAGE_SIZE, offset).await?;
+    let total = CommentModel::count_for_target(&production.id).await?;
+
+    Ok(Json(CommentListResponse {
+        has_more: ((offset + comments.len()) as u64) < total,
+        comments,
+        total,
+        page,
+        per_page: COMMENTS_PAGE_SIZE,
+    }))
+}
+
+/// Post a comment on a production, then notify its owners.
+async fn create_comment(
+    Path(slug): Path<String>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Form(form): Form<CommentForm>,
+) -> Result<Redirect, Error> {
+    debug!("User {} commenting on production {}", user.id, slug);
+
+    let production = ProductionModel::get_by_slug(&slug).await?;
+    let author = user.record_id()?;
+    let comment = CommentModel::create(&production.id, &author, &form.body).await?;
+
+    crate::services::activity::log_activity(
+        Some(&user.id),
+        "comment_create",
+        &format!("/productions/{}", slug),
+    );
+
+    let link = format!("/productions/{}#comment-{}", slug, comment.id.key_string());
+    let commenter = if user.name.is_empty() {
+        &user.username
+    } else {
+        &user.name
+    };
+    let notification_model = NotificationModel::new();
+    let owners = ProductionModel::get_owner_person_ids(&production.id)
+        .await
+        .unwrap_or_default();
+    for owner_id in owners.iter().filter(|id| **id != user.id) {
+        if let Err(e) = notification_model
+            .create(
+                owner_id,
+                "comment",
+                &format!("New comment on {}", production.title),
+                &format!("{} commented on {}", commenter, production.title),
+                Some(&link),
+                Some(&comment.id.to_raw_string()),
+            )
+            .await
+        {
+            warn!("Failed to notify {} of comment: {}", owner_id, e);
+        }
+    }
+
+    info!(
+        "Comment {} created on {}",
+        comment.id.display(),
+        production.id.display()
+    );
+
+    Ok(Redirect::to(&link))
+}
+
+/// Edit a comment's body (author only); stamps `updated_at`.
+async fn edit_comment(
+    Path((slug, comment_key)): Path<(String, String)>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Form(form): Form<CommentForm>,
+) -> Result<Redirect, Error> {
+    let production = ProductionModel::get_by_slug(&slug).await?;
+    let comment = load_comment(&production, &comment_key).await?;
+
+    if comment.author != user.record_id()? {
+        return Err(Error::Forbidden);
+    }
+
+    CommentModel::update_body(&comment.id, &form.body).await?;
+
+    Ok(Redirect::to(&format!(
+        "/productions/{}#comment-{}",
+        slug, comment_key
+    )))
+}
+
+/// Delete a comment. Allowed for its author, anyone who can edit the
+/// production, and platform admins.
+async fn delete_comment(
+    Path((slug, comment_key)): Path<(String, String)>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Redirect, Error> {
+    let production = ProductionModel::get_by_slug(&slug).await?;
+    let comment = load_comment(&production, &comment_key).await?;
+
+    let is_author = comment.author == user.record_id()?;
+    let allowed = is_author
+        || ProductionModel::can_edit(&production.id, &user.id)
+            .await
+            .unwrap_or(false)
+        || User::from_session_user(&user).await.is_admin;
+    if !allowed {
+        return Err(Error::Forbidden);
+    }
+
+    CommentModel::delete(&comment.id).await?;
+    let _ = NotificationModel::new()
+        .delete_by_related(&comment.id.to_raw_string(), "comment")
+        .await;
+
+    info!("Comment {} deleted by {}", comment.id.display(), user.id);
+
+    Ok(Redirect::to(&format!("/productions/{}#comments", slug)))
+}
@@ -33,6 +33,7 @@ struct DevelopersIndexTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     issuer: String,
 }
 
@@ -44,6 +45,7 @@ struct OidcDocTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     issuer: String,
 }
 
@@ -55,6 +57,7 @@ struct SecurityEventsTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     issuer: String,
 }
 
@@ -64,7 +67,7 @@ async fn index(request: Request) -> Result<Html<String>, Error> {
         base = base.with_user(User::from_session_user(&u).await);
     }
     let template = crate::with_base!(DevelopersIndexTemplate, base, {
-        issuer: config::app_url(),
+        issuer: config::public_base_url(),
     });
     Ok(Html(template.render().map_err(|e| {
         error!("developers index template: {}", e);
@@ -78,7 +81,7 @@ async fn oidc_doc(request: Request) -> Result<Html<String>, Error> {
         base = base.with_user(User::from_session_user(&u).await);
     }
     let template = crate::with_base!(OidcDocTemplate, base, {
-        issuer: config::app_url(),
+        issuer: config::public_base_url(),
     });
     Ok(Html(template.render().map_err(|e| {
         error!("developers oidc template: {}", e);
@@ -92,7 +95,7 @@ async fn security_events_doc(request: Request) -> Result<Html<String>, Error> {
         base = base.with_user(User::from_session_user(&u).await);
     }
     let template = crate::with_base!(SecurityEventsTemplate, base, {
-        issuer: config::app_url(),
+        issuer: config::public_base_url(),
     });
     Ok(Html(template.render().map_err(|e| {
         error!("developers security events template: {}", e);
@@ -5,32 +5,43 @@
 //! org). Detail pages are public, but edit affordances stay owner-only.
 
 use axum::{
-    Form, Router,
+    Form, Json, Router,
     extract::{Path, Query, Request},
+    http::header,
     response::{Html, IntoResponse, Redirect, Response},
     routing::{get, post},
 };
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::borrow::Cow;
+use surrealdb::types::RecordId;
 use tracing::info;
 
 use crate::{
+    db::DB,
     error::Error,
-    middleware::{AuthenticatedUser, UserExtractor},
+    middleware::{AuthenticatedUser, CurrentUser, UserExtractor},
     models::{
         equipment::{
             CheckinData, CheckoutData, CreateEquipmentData, CreateKitData, Equipment,
-            EquipmentModel, UpdateEquipmentData,
+            EquipmentLookup, EquipmentModel, EquipmentRental, EquipmentSort, KitCompleteness,
+            UpdateEquipmentData, serial_duplicates,
         },
         organization::OrganizationModel,
     },
-    record_id_ext::RecordIdExt,
+    pagination::{Page, PageQuery},
+    record_id_ext::{RecordIdExt, normalize_record_id},
+    serde_utils::{deserialize_optional_f64, parse_form},
+    services::rental_agreement::{self, RentalAgreement},
     templates::{
-        BaseContext, User,
+        BaseContext, FormValues, User,
         equipment::{
-            EquipmentCheckInTemplate, EquipmentCheckoutTemplate, EquipmentDetailTemplate,
-            EquipmentFormTemplate, EquipmentListTemplate, KitDetailTemplate, KitFormTemplate,
+            EquipmentCheckInTemplate, EquipmentCheckoutTemplate, EquipmentDashboardTemplate,
+            EquipmentDetailTemplate, EquipmentFindTemplate, EquipmentFormTemplate,
+            EquipmentListTemplate, KitDetailTemplate, KitFormTemplate,
         },
     },
+    validation::ValidationErrors,
 };
 
 // ============================
@@ -45,34 +56,24 @@ pub struct EquipmentQuery {
     pub available_only: Option<bool>,
     pub equipment_id: Option<String>,
     pub kit_id: Option<String>,
+    pub sort: Option<String>,
+    /// Checkout form: the renter whose history to show.
+    pub renter_type: Option<String>,
+    pub renter_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FindQuery {
+    #[serde(default)]
+    pub q: String,
+    pub owner_type: Option<String>,
+    pub owner_id: Option<String>,
 }
 
 // ============================
 // Form Data Structures
 // ============================
 
-#[derive(Debug, Deserialize)]
-pub struct ErrorQuery {
-    pub error: Option<String>,
-}
-
-/// Deserialize an optional float from a string that might be empty
-fn deserialize_optional_float<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: Option<String> = Option::deserialize(deserializer)?;
-    match s {
-        Some(s) if s.trim().is_empty() => Ok(None),
-        Some(s) => s
-            .trim()
-            .parse::<f64>()
-            .map(Some)
-            .map_err(serde::de::Error::custom),
-        None => Ok(None),
-    }
-}
-
 #[derive(Debug, Deserialize)]
 pub struct EquipmentFormData {
     pub name: String,
@@ -82,8 +83,9 @@ pub struct EquipmentFormData {
     pub manufacturer: Option<String>,
     pub description: Option<String>,
     pub purchase_date: Option<String>,
-    #[serde(default, deserialize_with = "deserialize_optional_float")]
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
     pub purchase_price: Option<f64>,
+    pub purchase_currency: Option<String>,
     pub condition: String,
     pub notes: Option<String>,
     pub current_location: Option<String>,
@@ -107,6 +109,9 @@ pub struct CheckoutFormData {
     pub expected_return_date: Option<String>,
     pub condition: String,
     pub notes: Option<String>,
+    /// Confirms checking out a kit with some items unavailable.
+    #[serde(default)]
+    pub allow_partial: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -119,37 +124,56 @@ pub struct CheckinFormData {
 // Equipment List & Management
 // ============================
 
+/// `?sort=` → [`EquipmentSort`]; absent or blank means newest first,
+/// anything off the allowlist is a 400.
+fn parse_sort(sort: Option<&str>) -> Result<EquipmentSort, Error> {
+    sort.filter(|s| !s.is_empty())
+        .map_or(Ok(EquipmentSort::default()), str::parse)
+}
+
+/// The `(owner_type, owner_id)` whose inventory a listing shows: the
+/// requested owner when the user may see it (themselves, or an org they
+/// belong to), otherwise `Unauthorized`; the user's own gear by default.
+async fn resolve_list_owner(
+    current_user: &CurrentUser,
+    owner_type: Option<String>,
+    owner_id: Option<String>,
+) -> Result<(String, String), Error> {
+    let (Some(ot), Some(oi)) = (owner_type, owner_id) else {
+        // Default to current user's personal equipment
+        return Ok(("person".to_string(), current_user.id.clone()));
+    };
+    if ot == "organization" {
+        // Check if user is a member of the organization
+        let org_model = OrganizationModel::new();
+        let _org = org_model.get_by_id(&oi).await?;
+        let members = org_model.get_members(&oi).await?;
+        if !members
+            .iter()
+            .any(|m| m.person_id.matches_id(&current_user.id, "person"))
+        {
+            return Err(Error::Unauthorized);
+        }
+        Ok(("organization".to_string(), oi))
+    } else if ot == "person" && oi == current_user.id {
+        Ok(("person".to_string(), oi))
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
+
 pub async fn list_equipment(
     AuthenticatedUser(current_user): AuthenticatedUser,
     Query(query): Query<EquipmentQuery>,
 ) -> Result<Response, Error> {
-    // Determine owner context
-    let (owner_type, owner_id) = if let (Some(ot), Some(oi)) = (query.owner_type, query.owner_id) {
-        // Verify authorization for the specified owner
-        if ot == "organization" {
-            // Check if user is a member of the organization
-            let org_model = OrganizationModel::new();
-            let _org = org_model.get_by_id(&oi).await?;
-            let members = org_model.get_members(&oi).await?;
-            if !members
-                .iter()
-                .any(|m| m.person_id.to_raw_string() == current_user.id)
-            {
-                return Err(Error::Unauthorized);
-            }
-            ("organization".to_string(), oi)
-        } else if ot == "person" && oi == current_user.id {
-            ("person".to_string(), oi)
-        } else {
-            return Err(Error::Unauthorized);
-        }
-    } else {
-        // Default to current user's personal equipment
-        ("person".to_string(), current_user.id.clone())
-    };
+    let sort = parse_sort(query.sort.as_deref())?;
+    let (owner_type, owner_id) =
+        resolve_list_owner(&current_user, query.owner_type, query.owner_id).await?;
 
     // Get equipment list
-    let equipment = EquipmentModel::list_equipment_for_owner(&owner_type, &owner_id).await?;
+    let equipment = EquipmentModel::list_equipment_for_owner(&owner_type, &owner_id, sort).await?;
+    // Duplicates are reported across the whole inventory, before filtering
+    let serial_duplicates = serial_duplicates(&equipment);
 
     // Get kits list
     let kits = EquipmentModel::list_kits_for_owner(&owner_type, &owner_id).await?;
@@ -180,11 +204,14 @@ pub async fn list_equipment(
         version: base.version,
         active_page: base.active_page,
         user: Some(user),
+        nonce: base.nonce,
         current_user: Some((*current_user).clone()),
         equipment,
         kits,
+        serial_duplicates,
         owner_type,
         owner_id,
+        sort_by: sort.as_str().to_string(),
         page_title: "Equipment".to_string(),
         error_message: None,
     };
@@ -192,6 +219,83 @@ pub async fn list_equipment(
     Ok(Html(template.to_string()).into_response())
 }
 
+/// Status counts, overdue rentals, and upcoming returns for one owner's
+/// gear; same owner rules as [`list_equipment`].
+pub async fn equipment_dashboard(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Query(query): Query<EquipmentQuery>,
+) -> Result<Response, Error> {
+    let (owner_type, owner_id) =
+        resolve_list_owner(&current_user, query.owner_type, query.owner_id).await?;
+    let dashboard = EquipmentModel::owner_dashboard(&owner_type, &owner_id).await?;
+
+    let base = BaseContext::new().with_page("equipment");
+    let user = User::from_session_user(&current_user).await;
+
+    let template = EquipmentDashboardTemplate {
+        app_name: base.app_name,
+        year: base.year,
+        version: base.version,
+        active_page: base.active_page,
+        user: Some(user),
+        nonce: base.nonce,
+        current_user: Some((*current_user).clone()),
+        dashboard,
+        owner_type,
+        owner_id,
+        page_title: "Equipment Dashboard".to_string(),
+    };
+
+    Ok(Html(template.to_string()).into_response())
+}
+
+/// One search box for scanned QR codes and typed serials or names. A
+/// single hit redirects to its detail page; otherwise the matches (or
+/// none) are listed. See [`EquipmentModel::lookup`] for the order tried.
+pub async fn find_equipment(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Query(query): Query<FindQuery>,
+) -> Result<Response, Error> {
+    let (owner_type, owner_id) =
+        resolve_list_owner(&current_user, query.owner_type, query.owner_id).await?;
+
+    let matches = match EquipmentModel::lookup(&owner_type, &owner_id, &query.q).await? {
+        EquipmentLookup::Item(item) => {
+            return Ok(
+                Redirect::to(&format!("/equipment/{}", item.id.key_string())).into_response(),
+            );
+        }
+        EquipmentLookup::Kit(kit) => {
+            return Ok(
+                Redirect::to(&format!("/equipment/kit/{}", kit.id.key_string())).into_response(),
+            );
+        }
+        EquipmentLookup::Candidates(items) => items,
+        EquipmentLookup::NotFound => Vec::new(),
+    };
+
+    let base = BaseContext::new().with_page("equipment");
+    let user = User::from_session_user(&current_user).await;
+
+    let template = EquipmentFindTemplate {
+        app_name: base.app_name,
+        year: base.year,
+        version: base.version,
+        active_page: base.active_page,
+        user: Some(user),
+        nonce: base.nonce,
+        current_user: Some((*current_user).clone()),
+        query: query.q.trim().to_string(),
+        matches,
+        owner_type,
+        owner_id,
+        page_title: "Find Equipment".to_string(),
+        error_message: None,
+    };
+
+    Ok(Html(template.to_string()).into_response())
+}
+
 // ============================
 // Equipment CRUD Operations
 // ============================
@@ -199,16 +303,43 @@ pub async fn list_equipment(
 pub async fn show_create_equipment_form(
     AuthenticatedUser(current_user): AuthenticatedUser,
     Query(query): Query<EquipmentQuery>,
+) -> Result<Response, Error> {
+    let owner_type = query.owner_type.unwrap_or("person".to_string());
+    let owner_id = query.owner_id.unwrap_or(current_user.id.clone());
+
+    render_equipment_form(
+        &current_user,
+        None,
+        owner_type,
+        owner_id,
+        None,
+        FormValues::default().with("purchase_currency", Some(crate::config::default_currency())),
+    )
+    .await
+}
+
+/// Render the create/edit equipment form. `equipment` is `Some` when
+/// editing; `values` holds the field contents to show.
+async fn render_equipment_form(
+    current_user: &CurrentUser,
+    equipment: Option<Equipment>,
+    owner_type: String,
+    owner_id: String,
+    error_message: Option<String>,
+    values: FormValues,
 ) -> Result<Response, Error> {
     // Get categories and conditions for dropdowns
     let categories = EquipmentModel::get_all_categories().await?;
     let conditions = EquipmentModel::get_all_conditions().await?;
 
-    let owner_type = query.owner_type.unwrap_or("person".to_string());
-    let owner_id = query.owner_id.unwrap_or(current_user.id.clone());
-
     let base = BaseContext::new().with_page("equipment");
-    let user = User::from_session_user(&current_user).await;
+    let user = User::from_session_user(current_user).await;
+
+    let page_title = if equipment.is_some() {
+        "Edit Equipment"
+    } else {
+        "Add Equipment"
+    };
 
     let template = EquipmentFormTemplate {
         app_name: base.app_name,
@@ -216,60 +347,115 @@ pub async fn show_create_equipment_form(
         version: base.version,
         active_page: base.active_page,
         user: Some(user),
-        current_user: Some((*current_user).clone()),
-        equipment: None,
+        nonce: base.nonce,
+        current_user: Some(current_user.clone()),
+        equipment,
         categories,
         conditions,
         owner_type,
         owner_id,
-        page_title: "Add Equipment".to_string(),
-        error_message: None,
+        page_title: page_title.to_string(),
+        error_message,
+        values,
     };
 
     Ok(Html(template.to_string()).into_response())
 }
 
-pub async fn create_equipment(
+/// Seed the edit form from the stored equipment.
+fn equipment_form_values(equipment: &Equipment) -> FormValues {
+    FormValues::default()
+        .with("name", Some(&equipment.name))
+        .with("category", Some(equipment.category.id.to_raw_string()))
+        .with("serial_number", equipment.serial_number.as_ref())
+        .with("model", equipment.model.as_ref())
+        .with("manufacturer", equipment.manufacturer.as_ref())
+        .with("description", equipment.description.as_ref())
+        .with(
+            "purchase_date",
+            equipment
+                .purchase_date
+                .as_ref()
+                .map(|d| d.format("%Y-%m-%d")),
+        )
+        .with("purchase_price", equipment.purchase_price)
+        .with(
+            "purchase_currency",
+            Some(
+                equipment
+                    .purchase_currency
+                    .as_deref()
+                    .unwrap_or_else(|| crate::config::default_currency()),
+            ),
+        )
+        .with("condition", Some(equipment.condition.id.to_raw_string()))
+        .with("current_location", equipment.current_location.as_ref())
+        .with("notes", equipment.notes.as_ref())
+}
+
+/// Parse an optional `YYYY-MM-DD` purchase date as midnight UTC.
+fn parse_purchase_date(date: Option<&String>) -> Option<chrono::DateTime<chrono::Utc>> {
+    date.and_then(|d| {
+        chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .ok()
+            .map(|date| date.and_hms_opt(0, 0, 0).unwrap())
+            .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
+    })
+}
+
+/// One page of an owner's equipment as JSON, `?page=&per_page=` paged;
+/// same owner rules as [`list_equipment`].
+pub async fn list_equipment_json(
     AuthenticatedUser(current_user): AuthenticatedUser,
     Query(query): Query<EquipmentQuery>,
-    form_result: Result<Form<EquipmentFormData>, axum::extract::rejection::FormRejection>,
-) -> Result<Response, Error> {
-    // Handle form validation errors
-    let form = match form_result {
-        Ok(Form(form)) => form,
-        Err(err) => {
-            // Return the form with error message
-            let categories = EquipmentModel::get_all_categories().await?;
-            let conditions = EquipmentModel::get_all_conditions().await?;
-            let owner_type = query.owner_type.unwrap_or("person".to_string());
-            let owner_id = query.owner_id.unwrap_or(current_user.id.clone());
-
-            let base = BaseContext::new().with_page("equipment");
-            let user = User::from_session_user(&current_user).await;
-
-            let template = EquipmentFormTemplate {
-                app_name: base.app_name,
-                year: base.year,
-                version: base.version,
-                active_page: base.active_page,
-                user: Some(user),
-                current_user: Some((*current_user).clone()),
-                equipment: None,
-                categories,
-                conditions,
-                owner_type,
-                owner_id,
-                page_title: "Add Equipment".to_string(),
-                error_message: Some(format!(
-                    "Invalid form data: {}. Please check numeric fields are valid numbers.",
-                    err
-                )),
-            };
+    page: PageQuery,
+) -> Result<Json<Page<serde_json::Value>>, Error> {
+    let sort = parse_sort(query.sort.as_deref())?;
+    let (owner_type, owner_id) =
+        resolve_list_owner(&current_user, query.owner_type, query.owner_id).await?;
+    let equipment =
+        EquipmentModel::page_equipment_for_owner(&owner_type, &owner_id, sort, page).await?;
+    Ok(Json(equipment.map(|e| {
+        json!({
+            "id": e.id.key_string(),
+            "name": e.name,
+            "category": e.category.name,
+            "condition": e.condition.name,
+            "manufacturer": e.manufacturer,
+            "model": e.model,
+            "is_available": e.is_available,
+            "created_at": e.created_at,
+        })
+    })))
+}
 
-            return Ok(Html(template.to_string()).into_response());
-        }
-    };
+const EQUIPMENT_NAME_MAX: usize = 200;
+const EQUIPMENT_DESCRIPTION_MAX: usize = 5000;
+
+/// Field checks shared by the create and edit forms, run on the raw
+/// submission so every problem is reported at once.
+fn validate_equipment_form(values: &FormValues) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Name", values.get("name"))
+        .max_len("name", "Name", values.get("name"), EQUIPMENT_NAME_MAX)
+        .required("category", "Category", values.get("category"))
+        .required("condition", "Condition", values.get("condition"))
+        .max_len(
+            "description",
+            "Description",
+            values.get("description"),
+            EQUIPMENT_DESCRIPTION_MAX,
+        )
+        .currency("purchase_currency", values.get("purchase_currency"));
+    errors
+}
 
+pub async fn create_equipment(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Query(query): Query<EquipmentQuery>,
+    body: String,
+) -> Result<Response, Error> {
     let owner_type = query.owner_type.unwrap_or("person".to_string());
     let owner_id = query.owner_id.unwrap_or(current_user.id.clone());
 
@@ -279,7 +465,7 @@ pub async fn create_equipment(
         let members = org_model.get_members(&owner_id).await?;
         if !members
             .iter()
-            .any(|m| m.person_id.to_raw_string() == current_user.id)
+            .any(|m| m.person_id.matches_id(&current_user.id, "person"))
         {
             return Err(Error::Unauthorized);
         }
@@ -287,46 +473,62 @@ pub async fn create_equipment(
         return Err(Error::Unauthorized);
     }
 
-    // Parse purchase date if provided
-    let purchase_date = form.purchase_date.as_ref().and_then(|d| {
-        chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
-            .ok()
-            .map(|date| date.and_hms_opt(0, 0, 0).unwrap())
-            .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
-    });
-
-    let data = CreateEquipmentData {
-        name: form.name,
-        category: form.category,
-        serial_number: form.serial_number,
-        model: form.model,
-        manufacturer: form.manufacturer,
-        description: form.description,
-        purchase_date,
-        purchase_price: form.purchase_price,
-        condition: form.condition,
-        notes: form.notes,
-        owner_type: owner_type.clone(),
-        owner_person: if owner_type == "person" {
-            Some(owner_id.clone())
-        } else {
-            None
-        },
-        owner_organization: if owner_type == "organization" {
-            Some(owner_id.clone())
-        } else {
-            None
-        },
-        is_kit_item: false,
-        parent_kit: None,
-        current_location: form.current_location,
-    };
-
-    let equipment = EquipmentModel::create_equipment(data).await?;
-
-    info!("Equipment created: {}", equipment.id.display());
+    let result = async {
+        validate_equipment_form(&FormValues::from_body(&body)).into_result()?;
+        let form: EquipmentFormData = parse_form(&body)?;
+
+        let data = CreateEquipmentData {
+            name: form.name,
+            category: form.category,
+            serial_number: form.serial_number,
+            model: form.model,
+            manufacturer: form.manufacturer,
+            description: form.description,
+            purchase_date: parse_purchase_date(form.purchase_date.as_ref()),
+            purchase_price: form.purchase_price,
+            purchase_currency: form.purchase_currency,
+            condition: form.condition,
+            notes: form.notes,
+            owner_type: owner_type.clone(),
+            owner_person: if owner_type == "person" {
+                Some(owner_id.clone())
+            } else {
+                None
+            },
+            owner_organization: if owner_type == "organization" {
+                Some(owner_id.clone())
+            } else {
+                None
+            },
+            is_kit_item: false,
+            parent_kit: None,
+            current_location: form.current_location,
+        };
+
+        EquipmentModel::create_equipment(data).await
+    }
+    .await;
 
-    Ok(Redirect::to(&format!("/equipment/{}", equipment.id.display())).into_response())
+    match result {
+        Ok(equipment) => {
+            info!("Equipment created: {}", equipment.id.display());
+            Ok(Redirect::to(&format!("/equipment/{}", equipment.id.display())).into_response())
+        }
+        // Input the user can fix: show the form again with what they typed.
+        Err(e) if e.form_message().is_some() => {
+            let message = e.form_message().map(Cow::into_owned);
+            render_equipment_form(
+                &current_user,
+                None,
+                owner_type,
+                owner_id,
+                message,
+                FormValues::from_body(&body),
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    }
 }
 
 pub async fn show_equipment_detail(
@@ -346,7 +548,7 @@ pub async fn show_equipment_detail(
             equipment
                 .owner_person
                 .as_ref()
-                .is_some_and(|p| p.to_raw_string() == user.id)
+                .is_some_and(|p| p.matches_id(&user.id, "person"))
         } else if let Some(org_id) = equipment.owner_organization.as_ref() {
             let org_model = OrganizationModel::new();
             let members = org_model
@@ -355,7 +557,7 @@ pub async fn show_equipment_detail(
                 .unwrap_or_default();
             members
                 .iter()
-                .any(|m| m.person_id.to_raw_string() == user.id)
+                .any(|m| m.person_id.matches_id(&user.id, "person"))
         } else {
             false
         }
@@ -363,6 +565,19 @@ pub async fn show_equipment_detail(
         false
     };
 
+    // Other items of the owner's with the same serial, shown to editors
+    let serial_duplicates = match equipment.serial_number.as_deref() {
+        Some(serial) if can_edit => {
+            let (owner_type, owner_id) = equipment_owner(&equipment);
+            EquipmentModel::find_by_serial(&owner_type, &owner_id, serial)
+                .await?
+                .into_iter()
+                .filter(|item| item.id != equipment.id)
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
     let base = BaseContext::new().with_page("equipment");
     let user = if let Some(ref cu) = current_user_opt {
         Some(User::from_session_user(cu).await)
@@ -376,9 +591,11 @@ pub async fn show_equipment_detail(
         version: base.version,
         active_page: base.active_page,
         user,
+        nonce: base.nonce,
         current_user: current_user_opt.as_ref().map(|u| (**u).clone()),
         equipment,
         rentals,
+        serial_duplicates,
         can_edit,
         page_title: "Equipment Details".to_string(),
         error_message: None,
@@ -390,135 +607,128 @@ pub async fn show_equipment_detail(
 pub async fn show_edit_equipment_form(
     AuthenticatedUser(current_user): AuthenticatedUser,
     Path(id): Path<String>,
-    Query(error_query): Query<ErrorQuery>,
 ) -> Result<Response, Error> {
     let equipment = EquipmentModel::get_equipment(&id).await?;
+    require_equipment_owner(&current_user, &equipment).await?;
 
-    // Verify authorization
-    if equipment.owner_type == "person" {
-        if equipment
-            .owner_person
-            .as_ref()
-            .is_none_or(|p| p.to_raw_string() != current_user.id)
-        {
+    let values = equipment_form_values(&equipment);
+    let (owner_type, owner_id) = equipment_owner(&equipment);
+    render_equipment_form(
+        &current_user,
+        Some(equipment),
+        owner_type,
+        owner_id,
+        None,
+        values,
+    )
+    .await
+}
+
+/// `Unauthorized` unless the user owns the equipment or belongs to the
+/// owning organization.
+async fn require_equipment_owner(
+    current_user: &CurrentUser,
+    equipment: &Equipment,
+) -> Result<(), Error> {
+    require_owner(
+        current_user,
+        &equipment.owner_type,
+        equipment.owner_person.as_ref(),
+        equipment.owner_organization.as_ref(),
+    )
+    .await
+}
+
+/// Ownership check shared by equipment and kits: the owning person, or any
+/// member of the owning organization.
+async fn require_owner(
+    current_user: &CurrentUser,
+    owner_type: &str,
+    owner_person: Option<&RecordId>,
+    owner_organization: Option<&RecordId>,
+) -> Result<(), Error> {
+    if owner_type == "person" {
+        if owner_person.is_none_or(|p| !p.matches_id(&current_user.id, "person")) {
             return Err(Error::Unauthorized);
         }
-    } else if let Some(org_id) = equipment.owner_organization.as_ref() {
+    } else if let Some(org_id) = owner_organization {
         let org_model = OrganizationModel::new();
         let members = org_model.get_members(&org_id.to_raw_string()).await?;
         if !members
             .iter()
-            .any(|m| m.person_id.to_raw_string() == current_user.id)
+            .any(|m| m.person_id.matches_id(&current_user.id, "person"))
         {
             return Err(Error::Unauthorized);
         }
     } else {
         return Err(Error::Unauthorized);
     }
+    Ok(())
+}
 
-    // Get categories and conditions for dropdowns
-    let categories = EquipmentModel::get_all_categories().await?;
-    let conditions = EquipmentModel::get_all_conditions().await?;
-
-    let base = BaseContext::new().with_page("equipment");
-    let user = User::from_session_user(&current_user).await;
-
-    let template = EquipmentFormTemplate {
-        app_name: base.app_name,
-        year: base.year,
-        version: base.version,
-        active_page: base.active_page,
-        user: Some(user),
-        current_user: Some((*current_user).clone()),
-        equipment: Some(equipment.clone()),
-        categories,
-        conditions,
-        owner_type: equipment.owner_type.clone(),
-        owner_id: equipment
-            .owner_person
-            .or(equipment.owner_organization)
-            .map(|r| r.to_raw_string())
-            .unwrap_or_default(),
-        page_title: "Edit Equipment".to_string(),
-        error_message: error_query.error,
-    };
-
-    Ok(Html(template.to_string()).into_response())
+/// The equipment's `(owner_type, owner_id)` as the form expects them.
+fn equipment_owner(equipment: &Equipment) -> (String, String) {
+    let owner_id = equipment
+        .owner_person
+        .as_ref()
+        .or(equipment.owner_organization.as_ref())
+        .map(|r| r.to_raw_string())
+        .unwrap_or_default();
+    (equipment.owner_type.clone(), owner_id)
 }
 
 pub async fn update_equipment(
     AuthenticatedUser(current_user): AuthenticatedUser,
     Path(id): Path<String>,
-    form_result: Result<Form<EquipmentFormData>, axum::extract::rejection::FormRejection>,
+    body: String,
 ) -> Result<Response, Error> {
-    // Handle form validation errors
-    let form = match form_result {
-        Ok(Form(form)) => form,
-        Err(err) => {
-            // Redirect back to edit form with error
-            return Ok(Redirect::to(&format!(
-                "/equipment/{}/edit?error={}",
-                id,
-                urlencoding::encode(&format!(
-                    "Invalid form data: {}. Please check numeric fields are valid numbers.",
-                    err
-                ))
-            ))
-            .into_response());
-        }
-    };
-
     let equipment = EquipmentModel::get_equipment(&id).await?;
+    require_equipment_owner(&current_user, &equipment).await?;
+
+    let result = async {
+        validate_equipment_form(&FormValues::from_body(&body)).into_result()?;
+        let form: EquipmentFormData = parse_form(&body)?;
+
+        let data = UpdateEquipmentData {
+            name: form.name,
+            category: form.category,
+            serial_number: form.serial_number,
+            model: form.model,
+            manufacturer: form.manufacturer,
+            description: form.description,
+            purchase_date: parse_purchase_date(form.purchase_date.as_ref()),
+            purchase_price: form.purchase_price,
+            purchase_currency: form.purchase_currency,
+            condition: form.condition,
+            notes: form.notes,
+            current_location: form.current_location,
+        };
+
+        EquipmentModel::update_equipment(&id, data).await
+    }
+    .await;
 
-    // Verify authorization
-    if equipment.owner_type == "person" {
-        if equipment
-            .owner_person
-            .as_ref()
-            .is_none_or(|p| p.to_raw_string() != current_user.id)
-        {
-            return Err(Error::Unauthorized);
+    match result {
+        Ok(updated_equipment) => {
+            info!("Equipment updated: {}", updated_equipment.id.display());
+            Ok(Redirect::to(&format!("/equipment/{}", id)).into_response())
         }
-    } else if let Some(org_id) = equipment.owner_organization.as_ref() {
-        let org_model = OrganizationModel::new();
-        let members = org_model.get_members(&org_id.to_raw_string()).await?;
-        if !members
-            .iter()
-            .any(|m| m.person_id.to_raw_string() == current_user.id)
-        {
-            return Err(Error::Unauthorized);
+        // Input the user can fix: show the form again with what they typed.
+        Err(e) if e.form_message().is_some() => {
+            let message = e.form_message().map(Cow::into_owned);
+            let (owner_type, owner_id) = equipment_owner(&equipment);
+            render_equipment_form(
+                &current_user,
+                Some(equipment),
+                owner_type,
+                owner_id,
+                message,
+                FormValues::from_body(&body),
+            )
+            .await
         }
-    } else {
-        return Err(Error::Unauthorized);
+        Err(e) => Err(e),
     }
-
-    // Parse purchase date if provided
-    let purchase_date = form.purchase_date.as_ref().and_then(|d| {
-        chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
-            .ok()
-            .map(|date| date.and_hms_opt(0, 0, 0).unwrap())
-            .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
-    });
-
-    let data = UpdateEquipmentData {
-        name: form.name,
-        category: form.category,
-        serial_number: form.serial_number,
-        model: form.model,
-        manufacturer: form.manufacturer,
-        description: form.description,
-        purchase_date,
-        purchase_price: form.purchase_price,
-        condition: form.condition,
-        notes: form.notes,
-        current_location: form.current_location,
-    };
-
-    let updated_equipment = EquipmentModel::update_equipment(&id, data).await?;
-
-    info!("Equipment updated: {}", updated_equipment.id.display());
-
-    Ok(Redirect::to(&format!("/equipment/{}", id)).into_response())
 }
 
 pub async fn delete_equipment(
@@ -526,35 +736,9 @@ pub async fn delete_equipment(
     Path(id): Path<String>,
 ) -> Result<Response, Error> {
     let equipment = EquipmentModel::get_equipment(&id).await?;
+    require_equipment_owner(&current_user, &equipment).await?;
 
-    // Verify authorization
-    if equipment.owner_type == "person" {
-        if equipment
-            .owner_person
-            .as_ref()
-            .is_none_or(|p| p.to_raw_string() != current_user.id)
-        {
-            return Err(Error::Unauthorized);
-        }
-    } else if let Some(org_id) = equipment.owner_organization.as_ref() {
-        let org_model = OrganizationModel::new();
-        let members = org_model.get_members(&org_id.to_raw_string()).await?;
-        if !members
-            .iter()
-            .any(|m| m.person_id.to_raw_string() == current_user.id)
-        {
-            return Err(Error::Unauthorized);
-        }
-    } else {
-        return Err(Error::Unauthorized);
-    }
-
-    let owner_type = equipment.owner_type.clone();
-    let owner_id = equipment
-        .owner_person
-        .or(equipment.owner_organization)
-        .map(|r| r.to_raw_string())
-        .unwrap_or_default();
+    let (owner_type, owner_id) = equipment_owner(&equipment);
 
     EquipmentModel::delete_equipment(&id).await?;
 
@@ -579,11 +763,12 @@ pub async fn show_create_kit_form(
     let owner_id = query.owner_id.unwrap_or(current_user.id.clone());
 
     // Get available equipment for this owner
-    let available_equipment = EquipmentModel::list_equipment_for_owner(&owner_type, &owner_id)
-        .await?
-        .into_iter()
-        .filter(|e| e.is_available && !e.is_kit_item)
-        .collect();
+    let available_equipment =
+        EquipmentModel::list_equipment_for_owner(&owner_type, &owner_id, EquipmentSort::Name)
+            .await?
+            .into_iter()
+            .filter(|e| e.is_available && !e.is_kit_item)
+            .collect();
 
     // Get categories for dropdown
     let categories = EquipmentModel::get_all_categories().await?;
@@ -597,6 +782,7 @@ pub async fn show_create_kit_form(
         version: base.version,
         active_page: base.active_page,
         user: Some(user),
+        nonce: base.nonce,
         current_user: Some((*current_user).clone()),
         kit: None,
         available_equipment,
@@ -625,7 +811,7 @@ pub async fn create_kit(
         let members = org_model.get_members(&owner_id).await?;
         if !members
             .iter()
-            .any(|m| m.person_id.to_raw_string() == current_user.id)
+            .any(|m| m.person_id.matches_id(&current_user.id, "person"))
         {
             return Err(Error::Unauthorized);
         }
@@ -663,7 +849,7 @@ pub async fn show_kit_detail(Path(id): Path<String>, request: Request) -> Result
     let current_user_opt = request.get_user();
 
     let kit = EquipmentModel::get_kit(&id).await?;
-    let kit_items = EquipmentModel::get_kit_items(&id).await?;
+    let completeness = KitCompleteness::from_items(EquipmentModel::get_kit_items(&id).await?);
 
     // Get rental history
     let rentals = EquipmentModel::get_rental_history_for_kit(&id).await?;
@@ -673,7 +859,7 @@ pub async fn show_kit_detail(Path(id): Path<String>, request: Request) -> Result
         if kit.owner_type == "person" {
             kit.owner_person
                 .as_ref()
-                .is_some_and(|p| p.to_raw_string() == user.id)
+                .is_some_and(|p| p.matches_id(&user.id, "person"))
         } else if let Some(org_id) = kit.owner_organization.as_ref() {
             let org_model = OrganizationModel::new();
             let members = org_model
@@ -682,7 +868,7 @@ pub async fn show_kit_detail(Path(id): Path<String>, request: Request) -> Result
                 .unwrap_or_default();
             members
                 .iter()
-                .any(|m| m.person_id.to_raw_string() == user.id)
+                .any(|m| m.person_id.matches_id(&user.id, "person"))
         } else {
             false
         }
@@ -703,9 +889,10 @@ pub async fn show_kit_detail(Path(id): Path<String>, request: Request) -> Result
         version: base.version,
         active_page: base.active_page,
         user,
+        nonce: base.nonce,
         current_user: current_user_opt.as_ref().map(|u| (**u).clone()),
         kit,
-        kit_items,
+        completeness,
         rentals,
         can_edit,
         page_title: "Kit Details".to_string(),
@@ -727,16 +914,58 @@ pub async fn show_checkout_form(
     let conditions = EquipmentModel::get_all_conditions().await?;
 
     // Get the equipment or kit to checkout
-    let (equipment, kit) = if let Some(ref eq_id) = query.equipment_id {
-        (Some(EquipmentModel::get_equipment(eq_id).await?), None)
+    let (equipment, kit, completeness) = if let Some(ref eq_id) = query.equipment_id {
+        (
+            Some(EquipmentModel::get_equipment(eq_id).await?),
+            None,
+            None,
+        )
     } else if let Some(ref kit_id) = query.kit_id {
-        (None, Some(EquipmentModel::get_kit(kit_id).await?))
+        (
+            None,
+            Some(EquipmentModel::get_kit(kit_id).await?),
+            Some(EquipmentModel::kit_completeness(kit_id).await?),
+        )
     } else {
         return Err(Error::Validation(
             "No equipment or kit specified".to_string(),
         ));
     };
 
+    // The renter's return record, for the item's owner only.
+    let renter_type = query.renter_type.filter(|t| !t.is_empty());
+    let renter_id = query.renter_id.map(|id| id.trim().to_string());
+    let renter_history = match (&renter_type, renter_id.as_deref()) {
+        (Some(renter_type), Some(renter_id)) if !renter_id.is_empty() => {
+            let owner_check = match (&equipment, &kit) {
+                (Some(e), _) => {
+                    require_owner(
+                        &current_user,
+                        &e.owner_type,
+                        e.owner_person.as_ref(),
+                        e.owner_organization.as_ref(),
+                    )
+                    .await
+                }
+                (_, Some(k)) => {
+                    require_owner(
+                        &current_user,
+                        &k.owner_type,
+                        k.owner_person.as_ref(),
+                        k.owner_organization.as_ref(),
+                    )
+                    .await
+                }
+                _ => Err(Error::Unauthorized),
+            };
+            match owner_check {
+                Ok(()) => Some(EquipmentModel::renter_history(renter_type, renter_id).await?),
+                Err(_) => None,
+            }
+        }
+        _ => None,
+    };
+
     let base = BaseContext::new().with_page("equipment");
     let user = User::from_session_user(&current_user).await;
 
@@ -746,10 +975,15 @@ pub async fn show_checkout_form(
         version: base.version,
         active_page: base.active_page,
         user: Some(user),
+        nonce: base.nonce,
         current_user: Some((*current_user).clone()),
         equipment,
         kit,
+        completeness,
         conditions,
+        renter_type: renter_type.unwrap_or_default(),
+        renter_id: renter_id.unwrap_or_default(),
+        renter_history,
         page_title: "Checkout Equipment".to_string(),
         error_message: None,
     };
@@ -787,6 +1021,7 @@ pub async fn checkout_equipment_post(
         condition: form.condition,
         notes: form.notes,
         checkout_by: current_user.id.clone(),
+        allow_partial: form.allow_partial,
     };
 
     let rental = EquipmentModel::checkout_equipment(data).await?;
@@ -821,6 +1056,7 @@ pub async fn show_checkin_form(
         version: base.version,
         active_page: base.active_page,
         user: Some(user),
+        nonce: base.nonce,
         current_user: Some((*current_user).clone()),
         rental,
         conditions,
@@ -856,18 +1092,301 @@ pub async fn checkin_equipment_post(
     }
 }
 
+/// `GET /equipment/rental/{id}/agreement.pdf`: the printable rental
+/// agreement / receipt for a checkout. Visible to the owner (or a member of
+/// the owning organization), the renter, and whoever processed the
+/// checkout.
+pub async fn rental_agreement_pdf(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Path(rental_id): Path<String>,
+) -> Result<Response, Error> {
+    let rental = EquipmentModel::get_rental(&rental_id).await?;
+
+    let mut agreement = RentalAgreement {
+        reference: rental.id.key_string(),
+        checkout_date: rental
+            .checkout_date
+            .format("%b %d, %Y %H:%M UTC")
+            .to_string(),
+        expected_return_date: rental
+            .expected_return_date
+            .map(|d| d.format("%b %d, %Y").to_string()),
+        checkout_condition: rental.checkout_condition.name.clone(),
+        checkout_notes: rental.checkout_notes.clone(),
+        ..Default::default()
+    };
+
+    let owner = if let Some(equipment_id) = &rental.equipment_id {
+        let equipment = EquipmentModel::get_equipment(&equipment_id.key_string()).await?;
+        agreement.item_kind = "Equipment".to_string();
+        agreement.item_name = equipment.name.clone();
+        agreement.item_details = [
+            ("Category", Some(equipment.category.name.clone())),
+            ("Manufacturer", equipment.manufacturer.clone()),
+            ("Model", equipment.model.clone()),
+            ("Serial number", equipment.serial_number.clone()),
+            ("QR code", equipment.qr_code.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.map(|v| (label.to_string(), v)))
+        .collect();
+        (
+            equipment.owner_type,
+            equipment.owner_person,
+            equipment.owner_organization,
+        )
+    } else if let Some(kit_id) = &rental.kit_id {
+        let kit = EquipmentModel::get_kit(&kit_id.key_string()).await?;
+        agreement.item_kind = "Kit".to_string();
+        agreement.item_name = kit.name.clone();
+        agreement.item_details = [
+            ("Category", Some(kit.category.name.clone())),
+            ("QR code", kit.qr_code.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.map(|v| (label.to_string(), v)))
+        .collect();
+        agreement.kit_items = EquipmentModel::get_kit_items(&kit_id.key_string())
+            .await?
+            .into_iter()
+            .map(|item| item.name)
+            .collect();
+        (kit.owner_type, kit.owner_person, kit.owner_organization)
+    } else {
+        return Err(Error::NotFound);
+    };
+    let (owner_type, owner_person, owner_organization) = owner;
+
+    require_rental_party(
+        &current_user,
+        &rental,
+        require_owner(
+            &current_user,
+            &owner_type,
+            owner_person.as_ref(),
+            owner_organization.as_ref(),
+        )
+        .await,
+    )
+    .await?;
+
+    agreement.owner = party_name(owner_person.as_ref().or(owner_organization.as_ref())).await;
+    agreement.renter = party_name(
+        rental
+            .renter_person
+            .as_ref()
+            .or(rental.renter_organization.as_ref()),
+    )
+    .await;
+    agreement.checkout_by = party_name(Some(&rental.checkout_by)).await;
+
+    let pdf = rental_agreement::render_pdf(&agreement)?;
+    let filename = format!("rental-agreement-{}.pdf", rental.id.key_string());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{filename}\""),
+            ),
+        ],
+        pdf,
+    )
+        .into_response())
+}
+
+/// Passes if `owner_check` did, or the user is the renter (in person or
+/// through the renting organization) or processed the checkout.
+async fn require_rental_party(
+    current_user: &CurrentUser,
+    rental: &EquipmentRental,
+    owner_check: Result<(), Error>,
+) -> Result<(), Error> {
+    match owner_check {
+        Err(Error::Unauthorized) => {}
+        other => return other,
+    }
+    if rental.checkout_by.matches_id(&current_user.id, "person")
+        || rental
+            .renter_person
+            .as_ref()
+            .is_some_and(|p| p.matches_id(&current_user.id, "person"))
+    {
+        return Ok(());
+    }
+    match &rental.renter_organization {
+        Some(org_id) => require_owner(current_user, "organization", None, Some(org_id)).await,
+        None => Err(Error::Unauthorized),
+    }
+}
+
+/// Display name for a person or organization record (a person's username
+/// if they haven't set a name), falling back to the raw id if it can't be
+/// loaded.
+async fn party_name(id: Option<&RecordId>) -> String {
+    let Some(id) = id else {
+        return "Unknown".to_string();
+    };
+    let name: Option<String> = match DB
+        .query("RETURN $id.name ?? $id.username")
+        .bind(("id", id.clone()))
+        .await
+    {
+        Ok(mut result) => result.take(0).ok().flatten(),
+        Err(_) => None,
+    };
+    name.unwrap_or_else(|| id.to_raw_string())
+}
+
+/// Most rentals one bulk check-in may return.
+const BULK_CHECKIN_MAX: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct BulkCheckinRequest {
+    pub rental_ids: Vec<String>,
+    pub return_condition: String,
+    #[serde(default)]
+    pub return_notes: Option<String>,
+}
+
+/// Outcome for one rental in a bulk check-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkCheckinStatus {
+    CheckedIn,
+    NotFound,
+    AlreadyReturned,
+    Forbidden,
+}
+
+/// `POST /equipment/checkin-bulk`: return several rentals at once with a
+/// shared condition and notes.
+///
+/// Each rental is checked first — it must exist, still be out, and belong
+/// (via its equipment or kit) to the caller or one of their organizations.
+/// The ones that pass are returned together in a single transaction; the
+/// response lists a status per requested id, in request order.
+pub async fn checkin_bulk(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+    Json(request): Json<BulkCheckinRequest>,
+) -> Result<Json<serde_json::Value>, Error> {
+    let mut rental_ids: Vec<RecordId> = Vec::new();
+    for id in &request.rental_ids {
+        let id = normalize_record_id(id, "equipment_rental");
+        if !rental_ids.contains(&id) {
+            rental_ids.push(id);
+        }
+    }
+    if rental_ids.is_empty() {
+        return Err(Error::invalid_field(
+            "rental_ids",
+            "Select at least one rental",
+        ));
+    }
+    if rental_ids.len() > BULK_CHECKIN_MAX {
+        return Err(Error::invalid_field(
+            "rental_ids",
+            format!("At most {BULK_CHECKIN_MAX} rentals can be returned at once"),
+        ));
+    }
+    if request.return_condition.trim().is_empty() {
+        return Err(Error::invalid_field(
+            "return_condition",
+            "Return condition is required",
+        ));
+    }
+
+    let mut results = Vec::with_capacity(rental_ids.len());
+    for id in rental_ids {
+        let status = bulk_checkin_status(&current_user, &id).await?;
+        results.push((id, status));
+    }
+
+    let returnable: Vec<RecordId> = results
+        .iter()
+        .filter(|(_, status)| *status == BulkCheckinStatus::CheckedIn)
+        .map(|(id, _)| id.clone())
+        .collect();
+    if !returnable.is_empty() {
+        let data = CheckinData {
+            return_condition: request.return_condition,
+            return_notes: request.return_notes.filter(|n| !n.trim().is_empty()),
+            return_by: current_user.id.clone(),
+        };
+        EquipmentModel::checkin_rentals(&returnable, &data).await?;
+        info!(
+            "Bulk check-in of {} rentals by {}",
+            returnable.len(),
+            current_user.id
+        );
+    }
+
+    Ok(Json(json!({
+        "results": results
+            .iter()
+            .map(|(id, status)| json!({ "rental_id": id.to_raw_string(), "status": status }))
+            .collect::<Vec<_>>(),
+    })))
+}
+
+/// Whether `rental_id` can be checked in by `current_user`, before
+/// anything is written.
+async fn bulk_checkin_status(
+    current_user: &CurrentUser,
+    rental_id: &RecordId,
+) -> Result<BulkCheckinStatus, Error> {
+    if rental_id.table.as_str() != "equipment_rental" {
+        return Ok(BulkCheckinStatus::NotFound);
+    }
+    let rental = match EquipmentModel::get_rental(&rental_id.key_string()).await {
+        Ok(rental) => rental,
+        Err(Error::NotFound) => return Ok(BulkCheckinStatus::NotFound),
+        Err(e) => return Err(e),
+    };
+    if !rental.is_active {
+        return Ok(BulkCheckinStatus::AlreadyReturned);
+    }
+
+    let owned = if let Some(equipment_id) = &rental.equipment_id {
+        let equipment = EquipmentModel::get_equipment(&equipment_id.key_string()).await?;
+        require_equipment_owner(current_user, &equipment).await
+    } else if let Some(kit_id) = &rental.kit_id {
+        let kit = EquipmentModel::get_kit(&kit_id.key_string()).await?;
+        require_owner(
+            current_user,
+            &kit.owner_type,
+            kit.owner_person.as_ref(),
+            kit.owner_organization.as_ref(),
+        )
+        .await
+    } else {
+        Err(Error::Unauthorized)
+    };
+    match owned {
+        Ok(()) => Ok(BulkCheckinStatus::CheckedIn),
+        Err(Error::Unauthorized) => Ok(BulkCheckinStatus::Forbidden),
+        Err(e) => Err(e),
+    }
+}
+
 // ============================
 // Router Configuration
 // ============================
 
-/// Mounts the equipment pages: `/equipment` (list), `/equipment/new`,
-/// `/equipment/{id}` detail/edit/delete, kit creation and detail under
-/// `/equipment/kit/...`, and the rental `/equipment/checkout` and
-/// `/equipment/rental/{id}/checkin` flows.
+/// Mounts the equipment pages: `/equipment` (list), `/equipment/find`
+/// (QR/serial/name lookup), `/equipment/new`, `/equipment/{id}`
+/// detail/edit/delete, kit creation and detail under `/equipment/kit/...`,
+/// the rental `/equipment/checkout` and `/equipment/rental/{id}/checkin`
+/// flows with the rental's `agreement.pdf`, the JSON
+/// `/equipment/checkin-bulk` return, and the paged `/api/equipment` JSON
+/// listing.
 pub fn router() -> Router {
     Router::new()
         // Equipment list
         .route("/equipment", get(list_equipment))
+        .route("/api/equipment", get(list_equipment_json))
+        .route("/equipment/find", get(find_equipment))
+        .route("/equipment/dashboard", get(equipment_dashboard))
         // Equipment CRUD
         .route(
             "/equipment/new",
@@ -894,4 +1413,9 @@ pub fn router() -> Router {
             "/equipment/rental/{id}/checkin",
             get(show_checkin_form).post(checkin_equipment_post),
         )
+        .route(
+            "/equipment/rental/{id}/agreement.pdf",
+            get(rental_agreement_pdf),
+        )
+        .route("/equipment/checkin-bulk", post(checkin_bulk))
 }
@@ -107,7 +107,7 @@ fn session_cookie(name: &'static str, value: String) -> Cookie<'static> {
         .path("/")
         .same_site(SameSite::Lax)
         .http_only(true)
-        .secure(std::env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false")
+        .secure(crate::config::cookie_secure())
         .build()
 }
 
@@ -1,8 +1,10 @@
 //! Like/favorite routes: the `/likes` page listing a member's liked people
 //! and locations, plus JSON and Datastar-SSE APIs to toggle a like and to
-//! check which targets are already liked. SSE responses re-render the heart
-//! button in a page-specific variant and, on the likes page itself, remove
-//! the card and refresh the tab counts.
+//! check which targets are already liked. Toggles return the target's
+//! updated like count. SSE responses re-render the heart button in a
+//! page-specific variant (the `count` variant, used for productions and
+//! comments, shows the count beside the heart) and, on the likes page
+//! itself, remove the card and refresh the tab counts.
 
 use askama::Template;
 use axum::{
@@ -40,6 +42,7 @@ struct ToggleRequest {
 #[derive(Serialize)]
 struct ToggleResponse {
     liked: bool,
+    count: u64,
 }
 
 #[derive(Deserialize)]
@@ -89,8 +92,9 @@ async fn toggle_like(
 
     let target_id = parse_target_id(&body.target_id)?;
     let liked = LikesModel::toggle(&person_id, &target_id).await?;
+    let count = LikesModel::count(&target_id).await?;
 
-    Ok(Json(ToggleResponse { liked }))
+    Ok(Json(ToggleResponse { liked, count }))
 }
 
 /// Check which of the given IDs are liked (requires auth)
@@ -117,6 +121,25 @@ async fn check_likes(
 
 const HEART_PATH: &str = "M20.84 4.61a5.5 5.5 0 0 0-7.78 0L12 5.67l-1.06-1.06a5.5 5.5 0 0 0-7.78 7.78l1.06 1.06L12 21.23l7.78-7.78 1.06-1.06a5.5 5.5 0 0 0 0-7.78z";
 
+/// Heart button with the target's like count beside it (productions and
+/// comments). Must stay in sync with the matching markup in
+/// `templates/productions/production.html`.
+fn counted_like_button_html(target_id: &str, liked: bool, count: u64) -> String {
+    let fill = if liked { "#e53e3e" } else { "none" };
+    let stroke = if liked { "#e53e3e" } else { "currentColor" };
+    let label = if liked { "Unlike" } else { "Like" };
+    format!(
+        r#"<button type="button" data-role="like-count" data-like-target="{tid}" data-on:click="@post('/api/likes/toggle-sse/{tid}?v=count')" data-liked="{liked}" aria-label="{label}"><svg width="16" height="16" viewBox="0 0 24 24" fill="{fill}" stroke="{stroke}" stroke-width="1.5"><path d="{hp}"/></svg><span>{count}</span></button>"#,
+        tid = target_id,
+        liked = liked,
+        label = label,
+        fill = fill,
+        stroke = stroke,
+        hp = HEART_PATH,
+        count = count
+    )
+}
+
 fn like_button_html(target_id: &str, liked: bool, variant: &str) -> String {
     let fill = if liked { "#e53e3e" } else { "none" };
     let stroke = if liked { "#e53e3e" } else { "currentColor" };
@@ -203,7 +226,12 @@ async fn toggle_like_sse(
     let liked = LikesModel::toggle(&person_id, &target_id).await?;
 
     let variant = query.v.as_deref().unwrap_or("default");
-    let btn_html = like_button_html(&target_id_raw, liked, variant);
+    let btn_html = if variant == "count" {
+        let count = LikesModel::count(&target_id).await?;
+        counted_like_button_html(&target_id_raw, liked, count)
+    } else {
+        like_button_html(&target_id_raw, liked, variant)
+    };
     let selector = format!(r#"[data-like-target="{}"]"#, target_id_raw);
 
     let mut sse = datastar::patch_elements(&selector, "outer", &btn_html);
@@ -8,27 +8,35 @@
 use crate::datastar;
 use crate::error::Error;
 use crate::html::escape_html;
-use crate::middleware::{AuthenticatedUser, UserExtractor};
+use crate::middleware::{AuthenticatedUser, CurrentUser, UserExtractor};
 use crate::models::likes::LikesModel;
 use crate::models::location::{
-    CreateLocationData, CreateRateData, LocationModel, LocationRate, UpdateLocationData,
+    CreateLocationData, CreateRateData, Location, LocationModel, LocationOwner, LocationRate,
+    LocationSort, UpdateLocationData,
 };
+use crate::pagination::{Page, PageQuery};
+use crate::rate_limit::RateLimiter;
 use crate::record_id_ext::RecordIdExt;
-use crate::serde_utils::deserialize_optional_i32;
+use crate::serde_utils::{deserialize_optional_i32, parse_form};
 use crate::services::embedding::generate_embedding_async;
 use crate::services::search_log::log_search;
 use crate::templates::{
-    BaseContext, LocationCreateTemplate, LocationEditTemplate, LocationTemplate, LocationsTemplate,
-    User,
+    BaseContext, DuplicateLocation, FormValues, LocationCreateTemplate, LocationEditTemplate,
+    LocationTemplate, LocationView, LocationsTemplate, User,
 };
+use crate::validation::ValidationErrors;
 use askama::Template;
 use axum::{
-    Form, Json, Router,
-    extract::{Path, Query, Request},
+    Extension, Form, Json, Router,
+    extract::{ConnectInfo, Path, Query, Request},
+    http::HeaderMap,
     response::{Html, IntoResponse, Redirect, Response},
     routing::{get, post},
 };
 use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use surrealdb::types::RecordId;
 use tracing::{debug, error, info};
 
@@ -36,7 +44,8 @@ const PAGE_SIZE: usize = 20;
 
 /// Mounts the location pages: `/locations` (list), `/locations/new`,
 /// `/locations/{id}` view/edit/delete, rate list/add/delete endpoints, and
-/// the `/api/locations/more-sse` infinite-scroll feed.
+/// the `/api/locations/more-sse` infinite-scroll feed, and the paged
+/// `/api/locations` JSON listing.
 pub fn router() -> Router {
     Router::new()
         .route("/locations", get(list_locations))
@@ -53,7 +62,9 @@ pub fn router() -> Router {
         .route("/locations/{id}/rates", get(get_rates))
         .route("/locations/{id}/rates/add", post(add_rate))
         .route("/locations/{id}/rates/{rate_id}/delete", post(delete_rate))
+        .route("/api/locations", get(list_locations_json))
         .route("/api/locations/more-sse", get(locations_more_sse))
+        .route("/api/locations/near", get(locations_near_json))
 }
 
 /// Query parameters for filtering locations
@@ -65,6 +76,119 @@ struct ListQuery {
     sort: Option<String>,
 }
 
+/// `?sort=` → [`LocationSort`]; absent or blank means the default order,
+/// anything off the allowlist is a 400.
+fn parse_sort(sort: Option<&str>) -> Result<LocationSort, Error> {
+    sort.filter(|s| !s.is_empty())
+        .map_or(Ok(LocationSort::default()), str::parse)
+}
+
+/// Card-sized view of a location for list pages and the JSON listing.
+fn location_view(l: Location) -> LocationView {
+    LocationView {
+        id: l.id.key_string(),
+        name: l.name,
+        address: l.address,
+        city: l.city,
+        state: l.state,
+        country: l.country,
+        description: l.description,
+        is_public: l.is_public,
+        profile_photo: l.profile_photo,
+        created_at: l.created_at.to_string(),
+    }
+}
+
+/// Public locations as JSON, `?page=&per_page=` paged.
+async fn list_locations_json(page: PageQuery) -> Result<Json<Page<LocationView>>, Error> {
+    let locations = LocationModel::list_public_page(page).await?;
+    Ok(Json(locations.map(location_view)))
+}
+
+/// Default and largest search radius for `/api/locations/near`.
+const NEAR_DEFAULT_RADIUS_KM: f64 = 50.0;
+const NEAR_MAX_RADIUS_KM: f64 = 500.0;
+const NEAR_LIMIT: usize = 50;
+
+/// Per-IP limiter for `/api/locations/near?q=`: each uncached place name
+/// costs a request to the shared geocoder, so anonymous clients get a
+/// modest budget. Coordinate lookups aren't counted.
+static NEAR_GEOCODE_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(30, Duration::from_secs(60)));
+
+/// `/api/locations/near` parameters: a point (`lat`/`lng`) or a place to
+/// geocode (`q`), an optional radius, and an optional ISO country code.
+#[derive(Debug, Deserialize)]
+struct NearQuery {
+    lat: Option<f64>,
+    lng: Option<f64>,
+    q: Option<String>,
+    radius_km: Option<f64>,
+    country: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct NearbyLocationView {
+    #[serde(flatten)]
+    location: LocationView,
+    distance_km: f64,
+}
+
+/// Public geocoded locations near a point, nearest first.
+async fn locations_near_json(
+    peer: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
+    Query(params): Query<NearQuery>,
+) -> Result<Json<Vec<NearbyLocationView>>, Error> {
+    let (lat, lng) = match (params.lat, params.lng, params.q.as_deref()) {
+        (Some(lat), Some(lng), _) => (lat, lng),
+        (_, _, Some(q)) if !q.trim().is_empty() => {
+            let peer = peer.map_or(SocketAddr::from(([0, 0, 0, 0], 0)), |p| p.0.0);
+            NEAR_GEOCODE_LIMITER.hit(
+                &super::auth::client_ip(&headers, peer),
+                "Too many place lookups. Please try again in a minute.",
+            )?;
+            let place = crate::services::geocoding::geocode(q)
+                .await
+                .ok_or_else(|| Error::invalid_field("q", format!("Unknown place: {q}")))?;
+            (place.lat, place.lng)
+        }
+        _ => {
+            return Err(Error::BadRequest(
+                "Pass lat and lng, or a place as q".to_string(),
+            ));
+        }
+    };
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) {
+        return Err(Error::BadRequest("Coordinates out of range".to_string()));
+    }
+    let radius_km = params
+        .radius_km
+        .unwrap_or(NEAR_DEFAULT_RADIUS_KM)
+        .clamp(0.0, NEAR_MAX_RADIUS_KM);
+    let country_code = params
+        .country
+        .filter(|c| !c.trim().is_empty())
+        .map(|c| {
+            crate::country::normalize(&c).ok_or_else(|| {
+                Error::invalid_field("country", format!("Unknown country code: {c}"))
+            })
+        })
+        .transpose()?;
+
+    let nearby =
+        LocationModel::find_near(lat, lng, radius_km, country_code.as_deref(), NEAR_LIMIT).await?;
+    Ok(Json(
+        nearby
+            .into_iter()
+            .map(|(location, distance_km)| NearbyLocationView {
+                location: location_view(location),
+                distance_km,
+            })
+            .collect(),
+    ))
+}
+
 /// List all locations
 async fn list_locations(
     Query(params): Query<ListQuery>,
@@ -83,7 +207,7 @@ async fn list_locations(
         None
     };
 
-    let sort_by = params.sort.unwrap_or_else(|| "recent".to_string());
+    let sort = parse_sort(params.sort.as_deref())?;
     let filter_text = params.filter.filter(|s| !s.is_empty());
     let city_text = params.city.filter(|s| !s.is_empty());
 
@@ -107,48 +231,42 @@ async fn list_locations(
                 None,
                 filter_text.as_deref(),
                 query_embedding.clone(),
-                Some(sort_by.as_str()),
+                sort,
                 0,
             )
             .await?,
             false,
         )
     } else {
-        let mut all_locations = LocationModel::list(
+        let all_locations = LocationModel::list(
             Some(PAGE_SIZE + 1),
             false,
             city_text.as_deref(),
             None,
             filter_text.as_deref(),
             query_embedding.clone(),
-            Some(sort_by.as_str()),
+            sort,
             0,
         )
         .await?;
 
-        if let Some(ref uid) = user_id {
-            all_locations.retain(|loc| loc.is_public || loc.created_by.key_string() == *uid);
+        // Private locations only for their owner, or the owning
+        // organization's members
+        let mut visible = Vec::with_capacity(all_locations.len());
+        for location in all_locations {
+            if LocationModel::can_view(&location, user_id.as_deref()).await? {
+                visible.push(location);
+            }
         }
 
-        (all_locations, true)
+        (visible, true)
     };
 
     let has_more = locations.len() > PAGE_SIZE;
-    let locations: Vec<crate::templates::LocationView> = locations
+    let locations: Vec<LocationView> = locations
         .into_iter()
         .take(PAGE_SIZE)
-        .map(|l| crate::templates::LocationView {
-            id: l.id.key_string(),
-            name: l.name,
-            address: l.address,
-            city: l.city,
-            state: l.state,
-            country: l.country,
-            description: l.description,
-            is_public: l.is_public,
-            profile_photo: l.profile_photo,
-            created_at: l.created_at.to_string(),
-        })
+        .map(location_view)
         .collect();
 
     // Fetch liked IDs if user is logged in
@@ -181,7 +299,7 @@ async fn list_locations(
         filter: filter_text,
         city: city_text,
         show_private,
-        sort_by,
+        sort_by: sort.as_str().to_string(),
         liked_ids,
         has_more,
     });
@@ -201,12 +319,17 @@ async fn view_location(Path(id): Path<String>, request: Request) -> Result<Html<
     let location_id = RecordId::new("location", id.as_str());
     let location = LocationModel::get(&location_id).await?;
 
+    let viewer = request.get_user();
+    if !LocationModel::can_view(&location, viewer.as_ref().map(|u| u.id.as_str())).await? {
+        return Err(Error::Forbidden);
+    }
+
     let mut base = BaseContext::new().with_page("locations");
 
     // Add user to context if authenticated
     let mut can_edit = false;
     let mut is_liked = false;
-    if let Some(user) = request.get_user() {
+    if let Some(user) = viewer {
         base = base.with_user(User::from_session_user(&user).await);
 
         // Check if user can edit this location
@@ -221,7 +344,7 @@ async fn view_location(Path(id): Path<String>, request: Request) -> Result<Html<
             Some(RecordId::new("person", user.id.as_str()))
         };
         if let Some(rid) = person_rid {
-            is_liked = LikesModel::is_liked(&rid, &location.id)
+            is_liked = LikesModel::has_liked(&rid, &location.id)
                 .await
                 .unwrap_or(false);
         }
@@ -241,6 +364,9 @@ async fn view_location(Path(id): Path<String>, request: Request) -> Result<Html<
             state: location.state,
             country: location.country,
             postal_code: location.postal_code,
+            country_code: location.country_code,
+            lat: location.geo.as_ref().map(|g| g.lat),
+            lng: location.geo.as_ref().map(|g| g.lng),
             description: location.description,
             contact_name: location.contact_name,
             contact_email: location.contact_email,
@@ -280,6 +406,7 @@ async fn view_location(Path(id): Path<String>, request: Request) -> Result<Html<
             can_edit,
         },
         is_liked,
+        default_currency: crate::config::default_currency().to_string(),
     });
 
     let html = template.render().map_err(|e| {
@@ -296,11 +423,46 @@ async fn new_location_form(
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Html<String>, Error> {
     debug!("Showing new location form");
+    render_location_create(&user, None, FormValues::default(), None).await
+}
 
+/// Render the create form, optionally with errors, the values the user
+/// submitted, and an existing location the submission probably duplicates.
+async fn render_location_create(
+    user: &CurrentUser,
+    errors: Option<Vec<String>>,
+    values: FormValues,
+    duplicate_of: Option<Location>,
+) -> Result<Html<String>, Error> {
+    let duplicate_of = duplicate_of.map(|l| DuplicateLocation {
+        id: l.id.key_string(),
+        name: l.name,
+        address: l.address,
+        city: l.city,
+    });
     let mut base = BaseContext::new().with_page("locations");
-    base = base.with_user(User::from_session_user(&user).await);
+    base = base.with_user(User::from_session_user(user).await);
 
-    let template = crate::with_base!(LocationCreateTemplate, base, { errors: None });
+    // Organizations the user may list a location under (owner or admin).
+    let user_organizations = crate::models::organization::OrganizationModel::new()
+        .get_user_organizations(&user.id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, role, _)| role == "owner" || role == "admin")
+        .map(|(org, role, _)| crate::templates::OrgOption {
+            id: org.id.to_raw_string(),
+            name: org.name,
+            role,
+        })
+        .collect();
+
+    let template = crate::with_base!(LocationCreateTemplate, base, {
+        errors,
+        values,
+        user_organizations,
+        duplicate_of,
+    });
 
     let html = template.render().map_err(|e| {
         error!("Failed to render location create template: {}", e);
@@ -310,26 +472,115 @@ async fn new_location_form(
     Ok(Html(html))
 }
 
+const LOCATION_DESCRIPTION_MAX: usize = 5000;
+
+/// Field checks for the create form, run on the raw submission so every
+/// problem is reported at once.
+fn validate_location_form(values: &FormValues) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Name", values.get("name"))
+        .max_len(
+            "description",
+            "Description",
+            values.get("description"),
+            LOCATION_DESCRIPTION_MAX,
+        )
+        .required("address", "Address", values.get("address"))
+        .required("city", "City", values.get("city"))
+        .required("country", "Country", values.get("country"))
+        .required("contact_name", "Contact name", values.get("contact_name"))
+        .required(
+            "contact_email",
+            "Contact email",
+            values.get("contact_email"),
+        )
+        .email(
+            "contact_email",
+            "Contact email",
+            values.get("contact_email"),
+        )
+        .integer_at_least(
+            "max_capacity",
+            "Maximum capacity",
+            values.get("max_capacity"),
+            1,
+        );
+    errors
+}
+
 /// Create a new location
 #[axum::debug_handler]
 async fn create_location(
     AuthenticatedUser(user): AuthenticatedUser,
-    Form(data): Form<CreateLocationForm>,
+    body: String,
 ) -> Result<Response, Error> {
+    let values = FormValues::from_body(&body);
+    let errors = match validate_location_form(&values).into_result() {
+        Err(errors) => errors.into_messages(),
+        Ok(()) => match try_create_location(&user, &body).await {
+            Ok(CreateOutcome::Created(location)) => {
+                // Redirect to the edit page so user can add photos
+                return Ok(
+                    Redirect::to(&format!("/locations/{}/edit", location.id.key_string()))
+                        .into_response(),
+                );
+            }
+            Ok(CreateOutcome::Duplicate(existing)) => {
+                // Soft check: offer the existing location; resubmitting with
+                // `create_anyway` creates the new one regardless.
+                return Ok(render_location_create(&user, None, values, Some(existing))
+                    .await?
+                    .into_response());
+            }
+            Err(e) => match e.form_message() {
+                Some(message) => vec![message.to_string()],
+                None => return Err(e),
+            },
+        },
+    };
+
+    // Show the form again with what the user typed.
+    Ok(render_location_create(&user, Some(errors), values, None)
+        .await?
+        .into_response())
+}
+
+/// What a create submission did.
+enum CreateOutcome {
+    Created(Location),
+    /// Nothing created: the owner already has a near-identical location.
+    Duplicate(Location),
+}
+
+async fn try_create_location(user: &CurrentUser, body: &str) -> Result<CreateOutcome, Error> {
+    let data: CreateLocationForm = parse_form(body)?;
     debug!("Creating new location: {}", data.name);
 
-    // Validate required fields
-    if data.name.is_empty() {
-        return Err(Error::Validation("Name is required".to_string()));
-    }
-    if data.address.is_empty() {
-        return Err(Error::Validation("Address is required".to_string()));
-    }
-    if data.contact_name.is_empty() {
-        return Err(Error::Validation("Contact name is required".to_string()));
+    let owner_organization = data.owner_organization.filter(|s| !s.is_empty());
+    if let Some(org_id) = &owner_organization {
+        let role = crate::models::organization::OrganizationModel::new()
+            .get_member_role(org_id, &user.id)
+            .await?;
+        if !matches!(role.as_deref(), Some("owner") | Some("admin")) {
+            return Err(Error::Forbidden);
+        }
     }
-    if data.contact_email.is_empty() {
-        return Err(Error::Validation("Contact email is required".to_string()));
+
+    if !data.create_anyway.unwrap_or(false) {
+        let owner = match &owner_organization {
+            Some(org_id) => LocationOwner::Organization(
+                RecordId::parse_simple(org_id).map_err(|e| Error::BadRequest(e.to_string()))?,
+            ),
+            None => LocationOwner::Person(
+                RecordId::parse_simple(&user.id).map_err(|e| Error::BadRequest(e.to_string()))?,
+            ),
+        };
+        if let Some(existing) =
+            LocationModel::find_duplicate(&owner, &data.name, &data.address).await?
+        {
+            return Ok(CreateOutcome::Duplicate(existing));
+        }
     }
 
     // Create location data
@@ -353,6 +604,11 @@ async fn create_location(
             .map(|r| r.split(',').map(|s| s.trim().to_string()).collect()),
         parking_info: data.parking_info.filter(|s| !s.is_empty()),
         max_capacity: data.max_capacity,
+        owner_organization,
+        street: data.street,
+        unit: data.unit,
+        region_code: data.region_code,
+        country_code: data.country_code,
     };
 
     // Create the location
@@ -363,9 +619,7 @@ async fn create_location(
         location.name,
         location.id.display()
     );
-
-    // Redirect to the edit page so user can add photos
-    Ok(Redirect::to(&format!("/locations/{}/edit", location.id.key_string())).into_response())
+    Ok(CreateOutcome::Created(location))
 }
 
 /// Show form to edit a location
@@ -376,16 +630,61 @@ async fn edit_location_form(
 ) -> Result<Html<String>, Error> {
     debug!("Showing edit form for location: {}", id);
 
-    let location_id = RecordId::new("location", id.as_str());
+    let location = editable_location(&id, &user).await?;
+    let values = location_form_values(&location);
+    render_location_edit(&user, location, None, values).await
+}
+
+/// Load a location for editing, or `Forbidden` if the user can't edit it.
+async fn editable_location(id: &str, user: &CurrentUser) -> Result<Location, Error> {
+    let location_id = RecordId::new("location", id);
     let location = LocationModel::get(&location_id).await?;
 
     // Check if user can edit
     if !LocationModel::can_edit(&location.id, &user.id).await? {
         return Err(Error::Forbidden);
     }
+    Ok(location)
+}
 
+/// Seed the edit form from the stored location.
+fn location_form_values(location: &Location) -> FormValues {
+    FormValues::default()
+        .with("name", Some(&location.name))
+        .with("description", location.description.as_ref())
+        .with("address", Some(&location.address))
+        .with("city", Some(&location.city))
+        .with("state", Some(&location.state))
+        .with("country", Some(&location.country))
+        .with("postal_code", location.postal_code.as_ref())
+        .with("street", location.street.as_ref())
+        .with("unit", location.unit.as_ref())
+        .with("region_code", location.region_code.as_ref())
+        .with("country_code", location.country_code.as_ref())
+        .with("contact_name", Some(&location.contact_name))
+        .with("contact_email", Some(&location.contact_email))
+        .with("contact_phone", location.contact_phone.as_ref())
+        .with(
+            "amenities",
+            location.amenities.as_ref().map(|a| a.join(", ")),
+        )
+        .with(
+            "restrictions",
+            location.restrictions.as_ref().map(|r| r.join(", ")),
+        )
+        .with("parking_info", location.parking_info.as_ref())
+        .with("max_capacity", location.max_capacity)
+        .with_checked("is_public", location.is_public)
+}
+
+async fn render_location_edit(
+    user: &CurrentUser,
+    location: Location,
+    errors: Option<Vec<String>>,
+    values: FormValues,
+) -> Result<Html<String>, Error> {
     let mut base = BaseContext::new().with_page("locations");
-    base = base.with_user(User::from_session_user(&user).await);
+    base = base.with_user(User::from_session_user(user).await);
 
     let template = crate::with_base!(LocationEditTemplate, base, {
         location: crate::templates::LocationEditData {
@@ -416,7 +715,8 @@ async fn edit_location_form(
                 })
                 .collect(),
         },
-        errors: None,
+        errors,
+        values,
     });
 
     let html = template.render().map_err(|e| {
@@ -432,52 +732,68 @@ async fn edit_location_form(
 async fn update_location(
     AuthenticatedUser(user): AuthenticatedUser,
     Path(id): Path<String>,
-    Form(data): Form<UpdateLocationForm>,
+    body: String,
 ) -> Result<Response, Error> {
     debug!("Updating location: {}", id);
 
-    let location_id = RecordId::new("location", id.as_str());
-    let location = LocationModel::get(&location_id).await?;
+    let location = editable_location(&id, &user).await?;
+
+    let result = async {
+        let data: UpdateLocationForm = parse_form(&body)?;
+
+        // Create update data
+        let update_data = UpdateLocationData {
+            name: data.name.filter(|s| !s.is_empty()),
+            address: data.address.filter(|s| !s.is_empty()),
+            city: data.city.filter(|s| !s.is_empty()),
+            state: data.state.filter(|s| !s.is_empty()),
+            country: data.country.filter(|s| !s.is_empty()),
+            postal_code: data.postal_code.filter(|s| !s.is_empty()),
+            description: data.description.filter(|s| !s.is_empty()),
+            contact_name: data.contact_name.filter(|s| !s.is_empty()),
+            contact_email: data.contact_email.filter(|s| !s.is_empty()),
+            contact_phone: data.contact_phone.filter(|s| !s.is_empty()),
+            is_public: data.is_public,
+            amenities: data
+                .amenities
+                .map(|a| a.split(',').map(|s| s.trim().to_string()).collect()),
+            restrictions: data
+                .restrictions
+                .map(|r| r.split(',').map(|s| s.trim().to_string()).collect()),
+            parking_info: data.parking_info.filter(|s| !s.is_empty()),
+            max_capacity: data.max_capacity,
+            street: data.street.filter(|s| !s.is_empty()),
+            unit: data.unit.filter(|s| !s.is_empty()),
+            region_code: data.region_code.filter(|s| !s.is_empty()),
+            country_code: data.country_code.filter(|s| !s.is_empty()),
+        };
 
-    // Check if user can edit
-    if !LocationModel::can_edit(&location.id, &user.id).await? {
-        return Err(Error::Forbidden);
+        // Update the location
+        LocationModel::update(&location.id, update_data).await
+    }
+    .await;
+
+    match result {
+        Ok(updated) => {
+            info!(
+                "Updated location: {} ({})",
+                updated.name,
+                updated.id.display()
+            );
+
+            // Redirect to the location page
+            Ok(Redirect::to(&format!("/locations/{}", updated.id.key_string())).into_response())
+        }
+        // Input the user can fix: show the form again with what they typed.
+        Err(e) if e.form_message().is_some() => {
+            let errors = e.form_message().map(|m| vec![m.to_string()]);
+            let values = FormValues::from_body(&body);
+            Ok(render_location_edit(&user, location, errors, values)
+                .await?
+                .into_response())
+        }
+        Err(e) => Err(e),
     }
-
-    // Create update data
-    let update_data = UpdateLocationData {
-        name: data.name.filter(|s| !s.is_empty()),
-        address: data.address.filter(|s| !s.is_empty()),
-        city: data.city.filter(|s| !s.is_empty()),
-        state: data.state.filter(|s| !s.is_empty()),
-        country: data.country.filter(|s| !s.is_empty()),
-        postal_code: data.postal_code.filter(|s| !s.is_empty()),
-        description: data.description.filter(|s| !s.is_empty()),
-        contact_name: data.contact_name.filter(|s| !s.is_empty()),
-        contact_email: data.contact_email.filter(|s| !s.is_empty()),
-        contact_phone: data.contact_phone.filter(|s| !s.is_empty()),
-        is_public: data.is_public,
-        amenities: data
-            .amenities
-            .map(|a| a.split(',').map(|s| s.trim().to_string()).collect()),
-        restrictions: data
-            .restrictions
-            .map(|r| r.split(',').map(|s| s.trim().to_string()).collect()),
-        parking_info: data.parking_info.filter(|s| !s.is_empty()),
-        max_capacity: data.max_capacity,
-    };
-
-    // Update the location
-    let updated = LocationModel::update(&location.id, update_data).await?;
-
-    info!(
-        "Updated location: {} ({})",
-        updated.name,
-        updated.id.display()
-    );
-
-    // Redirect to the location page
-    Ok(Redirect::to(&format!("/locations/{}", updated.id.key_string())).into_response())
 }
 
 /// Delete a location
@@ -509,12 +825,20 @@ async fn delete_location(
     Ok(Redirect::to("/locations").into_response())
 }
 
-/// Get rates for a location (JSON API)
-async fn get_rates(Path(id): Path<String>) -> Result<Json<Vec<LocationRate>>, Error> {
+/// Get rates for a location (JSON API); private locations as for
+/// [`view_location`].
+async fn get_rates(
+    Path(id): Path<String>,
+    viewer: Option<Extension<Arc<CurrentUser>>>,
+) -> Result<Json<Vec<LocationRate>>, Error> {
     debug!("Getting rates for location: {}", id);
 
     let location_id = RecordId::new("location", id.as_str());
     let location = LocationModel::get(&location_id).await?;
+    let viewer_id = viewer.as_ref().map(|Extension(u)| u.id.as_str());
+    if !LocationModel::can_view(&location, viewer_id).await? {
+        return Err(Error::Forbidden);
+    }
     let rates = LocationModel::get_rates(&location.id).await?;
 
     Ok(Json(rates))
@@ -643,7 +967,10 @@ fn render_location_card(loc: &crate::templates::LocationView) -> String {
 async fn locations_more_sse(Query(params): Query<MoreQuery>) -> Response {
     let filter = params.filter.as_deref().filter(|s| !s.is_empty());
     let city = params.city.as_deref().filter(|s| !s.is_empty());
-    let sort = params.sort.as_deref().filter(|s| !s.is_empty());
+    let sort = match parse_sort(params.sort.as_deref()) {
+        Ok(sort) => sort,
+        Err(e) => return e.into_response(),
+    };
     let offset = params.offset;
 
     let query_embedding = if let Some(f) = filter {
@@ -665,22 +992,7 @@ async fn locations_more_sse(Query(params): Query<MoreQuery>) -> Response {
     .unwrap_or_default();
     let has_more = all.len() > PAGE_SIZE;
 
-    let locs: Vec<crate::templates::LocationView> = all
-        .into_iter()
-        .take(PAGE_SIZE)
-        .map(|l| crate::templates::LocationView {
-            id: l.id.key_string(),
-            name: l.name,
-            address: l.address,
-            city: l.city,
-            state: l.state,
-            country: l.country,
-            description: l.description,
-            is_public: l.is_public,
-            profile_photo: l.profile_photo,
-            created_at: l.created_at.to_string(),
-        })
-        .collect();
+    let locs: Vec<LocationView> = all.into_iter().take(PAGE_SIZE).map(location_view).collect();
 
     if locs.is_empty() {
         return datastar::response(datastar::patch_elements("#loc-sentinel", "remove", ""));
@@ -700,8 +1012,8 @@ async fn locations_more_sse(Query(params): Query<MoreQuery>) -> Response {
         if let Some(c) = city {
             q_params.push_str(&format!("&city={}", urlencoding::encode(c)));
         }
-        if let Some(s) = sort {
-            q_params.push_str(&format!("&sort={}", urlencoding::encode(s)));
+        if sort != LocationSort::default() {
+            q_params.push_str(&format!("&sort={}", sort.as_str()));
         }
         replacement.push_str(&format!(
             r#"<div id="loc-sentinel" data-on-intersect="@get('/api/locations/more-sse?{}')"><div class="loc-loading">Loading more...</div></div>"#,
@@ -723,6 +1035,8 @@ struct CreateLocationForm {
     name: String,
     address: String,
     city: String,
+    /// Optional: many countries have no state or province.
+    #[serde(default)]
     state: String,
     country: String,
     postal_code: Option<String>,
@@ -736,6 +1050,16 @@ struct CreateLocationForm {
     parking_info: Option<String>,
     #[serde(deserialize_with = "deserialize_optional_i32")]
     max_capacity: Option<i32>,
+    /// `organization:…` to list under an organization; blank for yourself.
+    #[serde(default)]
+    owner_organization: Option<String>,
+    /// Set when the user confirmed a suspected duplicate is a new place.
+    #[serde(default)]
+    create_anyway: Option<bool>,
+    street: Option<String>,
+    unit: Option<String>,
+    region_code: Option<String>,
+    country_code: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -756,6 +1080,10 @@ struct UpdateLocationForm {
     parking_info: Option<String>,
     #[serde(deserialize_with = "deserialize_optional_i32")]
     max_capacity: Option<i32>,
+    street: Option<String>,
+    unit: Option<String>,
+    region_code: Option<String>,
+    country_code: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
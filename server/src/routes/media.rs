@@ -1,35 +1,70 @@
 //! Media upload/delete/proxy APIs (mounted under `/api/media`): profile
-//! avatars and photo galleries, organization logos (incl. SVG passthrough),
+//! avatars and photo galleries, organization logos (SVGs rasterized on upload),
 //! location photos, and production header/poster/gallery images. Uploads are
-//! validated (type, 10MB cap, per-entity counts), CPU-heavy resizing runs on
-//! the blocking pool, files land in S3, and the catch-all `/{*path}` route
-//! streams them back out so S3 is never exposed directly.
+//! validated (type, 10MB cap, per-entity counts, a per-user hourly rate
+//! limit, the owner's storage quota), CPU-heavy resizing runs on the
+//! bounded image pool (`services::image_pool`), files land in S3 with a `media` row recording their size,
+//! and the catch-all `/{*path}` route streams them back out so S3 is never
+//! exposed directly. `/usage` reports storage used against the quota and
+//! `/library` lists a user's past uploads for reuse (including files
+//! replaced in a single-image slot, which can be deleted there once
+//! detached); the `regenerate` endpoints rebuild stored thumbnails after the thumbnail pipeline changes.
 
 use axum::{
     Router,
     body::Body,
-    extract::{Path, Query, multipart::Multipart},
+    extract::{
+        Path, Query,
+        multipart::{Field, Multipart},
+    },
     http::{StatusCode, header},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
 };
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use image::{DynamicImage, ImageFormat};
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
-use tracing::{debug, info};
+use std::sync::LazyLock;
+use std::time::Duration;
+use surrealdb::types::{RecordId, SurrealValue};
+use tracing::{debug, info, warn};
 use ulid::Ulid;
 
 use crate::{
-    db::DB, error::Error, middleware::AuthenticatedUser, models::location::LocationModel,
-    models::organization::OrganizationModel, models::production::ProductionModel,
-    record_id_ext::RecordIdExt, services::s3::s3, verification_limits,
+    config,
+    db::DB,
+    error::Error,
+    middleware::{AuthenticatedUser, CurrentUser},
+    models::location::LocationModel,
+    models::media::{self, Media, NewMedia, kind as media_kind},
+    models::membership::{MembershipModel, Permission},
+    models::organization::OrganizationModel,
+    models::production::ProductionModel,
+    pagination::{Page, PageQuery},
+    rate_limit::RateLimiter,
+    record_id_ext::{RecordIdExt, normalize_record_id},
+    services::{
+        image_pool,
+        s3::{self, s3},
+        svg,
+    },
+    templates::User,
+    verification_limits,
 };
 
 /// Routes for media upload/delete per entity type plus the catch-all
 /// S3 proxy (`/{*path}`), which must stay last in this router.
 pub fn router() -> Router {
     Router::new()
+        .route("/usage", get(storage_usage))
+        .route("/library", get(media_library))
+        .route("/library/{media_id}/delete", post(delete_library_media))
+        .route("/thumbnails/regenerate", post(regenerate_owner_thumbnails))
+        .route(
+            "/thumbnails/{media_id}/regenerate",
+            post(regenerate_media_thumbnail),
+        )
         .route("/upload/profile-image", post(upload_profile_image))
         .route("/delete/profile-image", post(delete_profile_image))
         .route("/profile-image/{person_id}", get(get_profile_image_url))
@@ -113,10 +148,109 @@ struct ImageProcessParams {
 }
 
 /// Maximum file size in bytes (10MB)
-const MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
+pub const MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Read a multipart file field chunk by chunk, failing as soon as it
+/// passes `max` bytes instead of buffering the whole oversized body first.
+pub async fn read_field_limited(mut field: Field<'_>, max: usize) -> Result<Bytes, Error> {
+    let mut data = BytesMut::new();
+    while let Some(chunk) = field
+        .chunk()
+        .await
+        .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?
+    {
+        if data.len() + chunk.len() > max {
+            return Err(Error::bad_request(format!(
+                "File too large. Maximum size is {}MB",
+                max / (1024 * 1024)
+            )));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data.freeze())
+}
+
+/// The image type `data` actually is, as one of
+/// [`config::SUPPORTED_IMAGE_FORMATS`].
+///
+/// The multipart `Content-Type` is client-supplied, so it only pre-filters;
+/// this checks the bytes (JPEG/PNG/WebP magic numbers via
+/// `image::guess_format`, SVG by its leading markup), and handlers carry the
+/// result forward in place of the declared type.
+fn sniff_image_type(data: &[u8]) -> Result<&'static str, Error> {
+    match image::guess_format(data) {
+        Ok(ImageFormat::Jpeg) => Ok("image/jpeg"),
+        Ok(ImageFormat::Png) => Ok("image/png"),
+        Ok(ImageFormat::WebP) => Ok("image/webp"),
+        _ if looks_like_svg(data) => Ok("image/svg+xml"),
+        _ => Err(Error::bad_request(
+            "File contents are not a JPEG, PNG, WebP, or SVG image",
+        )),
+    }
+}
+
+/// Image types an upload endpoint accepts: the operator's
+/// `UPLOAD_IMAGE_FORMATS` ([`config::allowed_image_formats`]), with SVG
+/// only where it is rasterized first (`allow_svg`, organization logos) — the
+/// other pipelines decode the upload directly.
+fn accepted_image_types(allow_svg: bool) -> Vec<&'static str> {
+    config::allowed_image_formats()
+        .iter()
+        .map(String::as_str)
+        .filter(|t| allow_svg || *t != "image/svg+xml")
+        .collect()
+}
+
+/// "JPEG, PNG, WebP" for error messages.
+fn describe_image_types(types: &[&str]) -> String {
+    types
+        .iter()
+        .map(|t| match *t {
+            "image/jpeg" => "JPEG",
+            "image/png" => "PNG",
+            "image/webp" => "WebP",
+            "image/svg+xml" => "SVG",
+            other => other,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Pre-filter on the client's declared `Content-Type`.
+fn check_declared_type(content_type: &str, allow_svg: bool) -> Result<(), Error> {
+    let accepted = accepted_image_types(allow_svg);
+    if !accepted.contains(&content_type) {
+        return Err(Error::bad_request(format!(
+            "Invalid file type: {}. Allowed types: {}",
+            content_type,
+            describe_image_types(&accepted)
+        )));
+    }
+    Ok(())
+}
+
+/// [`sniff_image_type`], then the same policy as [`check_declared_type`]
+/// on what the bytes really are.
+fn check_image_type(data: &[u8], allow_svg: bool) -> Result<&'static str, Error> {
+    let actual = sniff_image_type(data)?;
+    let accepted = accepted_image_types(allow_svg);
+    if !accepted.contains(&actual) {
+        return Err(Error::bad_request(format!(
+            "File contents are {}, which isn't allowed here. Allowed types: {}",
+            describe_image_types(&[actual]),
+            describe_image_types(&accepted)
+        )));
+    }
+    Ok(actual)
+}
 
-/// Allowed image formats
-const ALLOWED_FORMATS: &[&str] = &["image/jpeg", "image/png", "image/webp", "image/svg+xml"];
+/// An SVG document: optional BOM, XML declaration, or comments, then `<svg`.
+fn looks_like_svg(data: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&data[..data.len().min(1024)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    (head.starts_with("<svg") || head.starts_with("<?xml") || head.starts_with("<!--"))
+        && head.contains("<svg")
+}
 
 /// Profile image dimensions
 const PROFILE_IMAGE_SIZE: u32 = 400;
@@ -126,12 +260,411 @@ const THUMBNAIL_SIZE: u32 = 100;
 const LOGO_SIZE: u32 = 400;
 const LOGO_THUMBNAIL_SIZE: u32 = 100;
 
+/// Uploads per user per rolling hour, across every upload endpoint: each
+/// one is decoded/resized on the blocking pool and stored in S3.
+static UPLOAD_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(60, Duration::from_secs(3600)));
+
+/// Reject the upload with a 429 (and `Retry-After`) once `user` has hit
+/// [`UPLOAD_LIMITER`].
+fn check_upload_rate(user: &CurrentUser) -> Result<(), Error> {
+    UPLOAD_LIMITER
+        .hit(
+            &user.id,
+            "Too many uploads. Please wait a while and try again.",
+        )
+        .map(|_| ())
+}
+
+/// A processed upload ready for S3: the main object and, optionally, its
+/// JPEG thumbnail, plus the `media` row bookkeeping.
+struct PendingUpload {
+    /// Whose storage quota the files count against
+    owner: RecordId,
+    /// Record the upload is attached to
+    target: RecordId,
+    /// One of [`media_kind`]
+    media_type: &'static str,
+    /// Single-image slot: the previous file is detached into the library
+    replace: bool,
+    content_type: String,
+    main: (String, Bytes),
+    thumbnail: Option<(String, Bytes)>,
+}
+
+/// Check the owner's storage quota, upload the files, and record the
+/// `media` row that counts them toward the quota. Returns the row's id.
+async fn store_upload(user: &CurrentUser, upload: PendingUpload) -> Result<RecordId, Error> {
+    let (main_key, main_data) = upload.main;
+    let size = (main_data.len() + upload.thumbnail.as_ref().map_or(0, |(_, t)| t.len())) as u64;
+    let dimensions = image_dimensions(&main_data);
+
+    Media::ensure_quota(&upload.owner, size).await?;
+
+    let s3_service = s3()?;
+    s3_service
+        .upload_file(&main_key, main_data, &upload.content_type)
+        .await?;
+    let thumbnail_key = match upload.thumbnail {
+        Some((key, data)) => {
+            s3_service.upload_file(&key, data, "image/jpeg").await?;
+            Some(key)
+        }
+        None => None,
+    };
+
+    let name = main_key.rsplit('/').next().unwrap_or(&main_key).to_string();
+    Media::record(
+        NewMedia {
+            owner: upload.owner,
+            uploaded_by: normalize_record_id(&user.id, "person"),
+            target: upload.target,
+            media_type: upload.media_type,
+            name,
+            mime_type: upload.content_type,
+            object_key: main_key,
+            thumbnail_key,
+            size,
+            dimensions,
+        },
+        upload.replace,
+    )
+    .await
+}
+
+/// Pixel size read from the image header; `None` for SVG or unreadable data.
+fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    image::io::Reader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageQuery {
+    /// Report an organization's usage instead of the caller's
+    org: Option<String>,
+}
+
+/// Storage used by the authenticated user (or, with `?org=slug`, an
+/// organization they can edit) against its quota.
+async fn storage_usage(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<serde_json::Value>, Error> {
+    let owner = match query.org.as_deref() {
+        Some(slug) => check_organization_edit(slug, &user.id).await?,
+        None => normalize_record_id(&user.id, "person"),
+    };
+    let usage = Media::storage_usage(&owner).await?;
+
+    Ok(Json(serde_json::json!({
+        "owner": usage.owner,
+        "used_bytes": usage.used_bytes,
+        "quota_bytes": usage.quota_bytes,
+        "remaining_bytes": usage.remaining_bytes(),
+        "files": usage.files,
+        "used": media::format_bytes(usage.used_bytes),
+        "quota": usage.quota_bytes.map(media::format_bytes),
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+struct LibraryQuery {
+    /// Only this upload slot (`avatar`, `profile_photo`, ...)
+    #[serde(rename = "type")]
+    media_type: Option<String>,
+}
+
+/// The authenticated user's uploads, newest first, so earlier files can be
+/// found and reused without uploading them again.
+async fn media_library(
+    AuthenticatedUser(user): AuthenticatedUser,
+    page: PageQuery,
+    Query(query): Query<LibraryQuery>,
+) -> Result<Json<Page<serde_json::Value>>, Error> {
+    let media_type = query
+        .media_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    if let Some(t) = media_type
+        && !media_kind::ALL.contains(&t)
+    {
+        return Err(Error::bad_request(format!(
+            "Unknown media type: {}. Expected one of: {}",
+            t,
+            media_kind::ALL.join(", ")
+        )));
+    }
+
+    let uploader = normalize_record_id(&user.id, "person");
+    let library = Media::library(&uploader, media_type, page).await?;
+    Ok(Json(library.map(|m| {
+        serde_json::json!({
+            "id": m.id.key_string(),
+            "type": m.media_type,
+            "name": m.name,
+            "url": m.uri,
+            "thumbnail_url": m.thumbnail_uri,
+            "object_key": m.object_key,
+            "thumbnail_key": m.thumbnail_key,
+            "content_type": m.mime_type,
+            "size": m.size,
+            "width": m.width,
+            "height": m.height,
+            "attached_to": m.target.map(|t| t.to_raw_string()),
+            "created_at": m.created_at,
+        })
+    })))
+}
+
+/// Delete one of the caller's library files that is no longer shown
+/// anywhere (a replaced headshot or logo, say), freeing its quota.
+async fn delete_library_media(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(media_id): Path<String>,
+) -> Result<Json<serde_json::Value>, Error> {
+    let media = Media::find_by_id(&media_id).await?.ok_or(Error::NotFound)?;
+    if !media.is_managed_by(&normalize_record_id(&user.id, "person")) {
+        return Err(Error::Forbidden);
+    }
+    Media::delete_detached(&media.id).await?;
+    info!("Library file {} deleted by {}", media_id, user.username);
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+/// Outcome of a thumbnail rebuild.
+#[derive(Debug, Default, Serialize)]
+struct ThumbnailReport {
+    regenerated: usize,
+    /// No thumbnail for this kind of upload, or the original is gone
+    skipped: usize,
+    failed: usize,
+}
+
+/// Rebuild one upload's thumbnail from its stored original. Allowed for the
+/// uploader, the quota owner, and platform admins.
+async fn regenerate_media_thumbnail(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(media_id): Path<String>,
+) -> Result<Json<ThumbnailReport>, Error> {
+    let media = Media::find_by_id(&media_id).await?.ok_or(Error::NotFound)?;
+    let person = normalize_record_id(&user.id, "person");
+    if !media.is_managed_by(&person) && !User::from_session_user(&user).await.is_admin {
+        return Err(Error::Forbidden);
+    }
+
+    let mut report = ThumbnailReport::default();
+    regenerate_thumbnail(&media, &mut report).await;
+    Ok(Json(report))
+}
+
+#[derive(Debug, Deserialize)]
+struct RegenerateQuery {
+    /// Record id of another person or organization (admins only)
+    owner: Option<String>,
+}
+
+/// Rebuild thumbnails for every upload of the caller — or, for admins,
+/// of `?owner=`. Failures are counted, not fatal, so one broken file
+/// doesn't stop the batch.
+async fn regenerate_owner_thumbnails(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(query): Query<RegenerateQuery>,
+) -> Result<Json<ThumbnailReport>, Error> {
+    let person = normalize_record_id(&user.id, "person");
+    let owner = match query.owner.as_deref().map(str::trim) {
+        Some(owner) if !owner.is_empty() => {
+            let owner = normalize_record_id(owner, "person");
+            if owner != person && !User::from_session_user(&user).await.is_admin {
+                return Err(Error::Forbidden);
+            }
+            owner
+        }
+        _ => person,
+    };
+
+    let mut report = ThumbnailReport::default();
+    for media in Media::list_for_owner(&owner).await? {
+        regenerate_thumbnail(&media, &mut report).await;
+    }
+    info!(
+        "Thumbnails regenerated for {}: {:?}",
+        owner.display(),
+        report
+    );
+    Ok(Json(report))
+}
+
+/// Re-run the thumbnail step for `media` from its stored original, writing
+/// over the old thumbnail and updating the recorded size.
+async fn regenerate_thumbnail(media: &Media, report: &mut ThumbnailReport) {
+    let (Some(main_key), Some(thumb_key), Some(media_type)) = (
+        media.object_key.as_deref(),
+        media.thumbnail_key.as_deref(),
+        media.media_type.clone(),
+    ) else {
+        report.skipped += 1;
+        return;
+    };
+
+    let result = async {
+        let s3_service = s3()?;
+        if !s3_service.file_exists(main_key).await? {
+            debug!("Original {} is gone; skipping thumbnail", main_key);
+            return Ok(None);
+        }
+        let (original, _) = s3_service.download_file(main_key).await?;
+        let original_len = original.len();
+        let content_type = media.mime_type.clone();
+        let thumbnail =
+            image_pool::run(move || thumbnail_for(&media_type, &content_type, &original)).await?;
+        let Some(thumbnail) = thumbnail else {
+            return Ok(None);
+        };
+        let size = (original_len + thumbnail.len()) as u64;
+        s3_service
+            .upload_file(thumb_key, thumbnail, "image/jpeg")
+            .await?;
+        Media::set_size(&media.id, size).await?;
+        Ok::<_, Error>(Some(()))
+    }
+    .await;
+
+    match result {
+        Ok(Some(())) => report.regenerated += 1,
+        Ok(None) => report.skipped += 1,
+        Err(e) => {
+            warn!(
+                "Failed to regenerate thumbnail for {}: {}",
+                media.id.display(),
+                e
+            );
+            report.failed += 1;
+        }
+    }
+}
+
+/// The thumbnail the upload pipeline makes for a `media_type` upload, built
+/// from the stored (already processed) main file. `None` for kinds that
+/// have no thumbnail.
+fn thumbnail_for(
+    media_type: &str,
+    content_type: &str,
+    data: &[u8],
+) -> Result<Option<Bytes>, Error> {
+    let Some((_, thumb_fit)) = fits_for(media_type) else {
+        return Ok(None);
+    };
+
+    // Logos uploaded before SVGs were rasterized on upload
+    let rasterized;
+    let data = if media_type == media_kind::ORG_LOGO && content_type.contains("svg") {
+        rasterized = svg::rasterize(data, LOGO_SIZE)?;
+        &rasterized[..]
+    } else {
+        data
+    };
+
+    encode_jpeg(&thumb_fit.apply(decode_image(data)?)).map(Some)
+}
+
+/// How a processed upload or its thumbnail is sized.
+#[derive(Clone, Copy)]
+enum Fit {
+    /// Center-crop to a square, then scale to this side.
+    Square(u32),
+    /// Scale and crop to fill exactly `width` x `height`.
+    Fill(u32, u32),
+    /// Shrink to at most this width, aspect preserved.
+    MaxWidth(u32),
+}
+
+impl Fit {
+    fn apply(self, img: DynamicImage) -> DynamicImage {
+        let filter = image::imageops::FilterType::Lanczos3;
+        match self {
+            Fit::Square(side) => center_crop_square(img).resize_exact(side, side, filter),
+            Fit::Fill(width, height) => img.resize_to_fill(width, height, filter),
+            Fit::MaxWidth(width) if img.width() > width => img.resize(width, u32::MAX, filter),
+            Fit::MaxWidth(_) => img,
+        }
+    }
+}
+
+/// `(main, thumbnail)` sizing for each upload shape.
+const PROFILE_FITS: (Fit, Fit) = (Fit::Square(PROFILE_IMAGE_SIZE), Fit::Square(THUMBNAIL_SIZE));
+const LOGO_FITS: (Fit, Fit) = (Fit::Square(LOGO_SIZE), Fit::Square(LOGO_THUMBNAIL_SIZE));
+const PHOTO_FITS: (Fit, Fit) = (
+    Fit::MaxWidth(PHOTO_MAX_WIDTH),
+    Fit::MaxWidth(PHOTO_THUMB_WIDTH),
+);
+const POSTER_FITS: (Fit, Fit) = (
+    Fit::Fill(POSTER_WIDTH, POSTER_HEIGHT),
+    Fit::Fill(POSTER_THUMB_WIDTH, POSTER_THUMB_HEIGHT),
+);
+
+/// The sizing a stored `media_type` file was processed with. `None` for
+/// kinds that have no thumbnail.
+fn fits_for(media_type: &str) -> Option<(Fit, Fit)> {
+    match media_type {
+        media_kind::AVATAR => Some(PROFILE_FITS),
+        media_kind::ORG_LOGO => Some(LOGO_FITS),
+        media_kind::PRODUCTION_POSTER => Some(POSTER_FITS),
+        media_kind::PROFILE_PHOTO | media_kind::LOCATION_PHOTO | media_kind::PRODUCTION_PHOTO => {
+            Some(PHOTO_FITS)
+        }
+        _ => None,
+    }
+}
+
+fn decode_image(data: &[u8]) -> Result<DynamicImage, Error> {
+    image::load_from_memory(data)
+        .map_err(|e| Error::bad_request(format!("Invalid image file: {}", e)))
+}
+
+fn encode_jpeg(img: &DynamicImage) -> Result<Bytes, Error> {
+    let mut bytes = Cursor::new(Vec::new());
+    img.write_to(&mut bytes, ImageFormat::Jpeg)
+        .map_err(|e| Error::Internal(format!("Failed to encode image: {}", e)))?;
+    Ok(Bytes::from(bytes.into_inner()))
+}
+
+/// Decode an upload, apply the editor's crop when all three crop params are
+/// given, and size and JPEG-encode the main file and its thumbnail (cut from
+/// the main file, as [`thumbnail_for`] does on regeneration).
+///
+/// Decode + Lanczos3 resize + JPEG encode are CPU-bound (hundreds of ms on
+/// large uploads), so the work runs on the bounded [`image_pool`] to keep
+/// the async runtime responsive. Returns `(main, thumbnail)` JPEG bytes.
+async fn process_image(
+    image_data: Bytes,
+    (main_fit, thumb_fit): (Fit, Fit),
+    crop: (Option<f32>, Option<f32>, Option<f32>),
+) -> Result<(Bytes, Bytes), Error> {
+    image_pool::run(move || {
+        let img = decode_image(&image_data)?;
+        let img = match crop {
+            (Some(x), Some(y), Some(zoom)) => apply_circular_crop(img, x, y, zoom)?,
+            _ => img,
+        };
+        let main = main_fit.apply(img);
+        let thumb = thumb_fit.apply(main.clone());
+        Ok((encode_jpeg(&main)?, encode_jpeg(&thumb)?))
+    })
+    .await
+}
+
 /// Upload and process a profile image
 async fn upload_profile_image(
     AuthenticatedUser(user): AuthenticatedUser,
     Query(params): Query<ImageProcessParams>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!("User {} uploading profile image", user.username);
 
     // Extract the image from multipart
@@ -155,25 +688,11 @@ async fn upload_profile_image(
             .to_string();
 
         // Validate content type
-        if !ALLOWED_FORMATS.contains(&content_type.as_str()) {
-            return Err(Error::bad_request(format!(
-                "Invalid file type: {}. Allowed types: JPEG, PNG, WebP",
-                content_type
-            )));
-        }
+        check_declared_type(&content_type, false)?;
 
-        let data = field
-            .bytes()
-            .await
-            .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-
-        // Check file size
-        if data.len() > MAX_FILE_SIZE {
-            return Err(Error::bad_request(
-                "File too large. Maximum size is 10MB".to_string(),
-            ));
-        }
+        let data = read_field_limited(field, MAX_FILE_SIZE).await?;
 
+        let content_type = check_image_type(&data, false)?.to_string();
         image_data = Some((filename, content_type, data));
         break;
     }
@@ -192,36 +711,31 @@ async fn upload_profile_image(
     let main_key = format!("profiles/{}/{}.jpg", sanitized_user_id, image_id);
     let thumb_key = format!("profiles/{}/thumb_{}.jpg", sanitized_user_id, image_id);
 
-    // Upload to S3
-    let s3_service = s3()?;
-
-    // Upload to S3 but don't use the returned URLs
-    s3_service
-        .upload_file(&main_key, processed_image.clone(), "image/jpeg")
-        .await?;
-
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     // Create proxy URLs instead of using direct S3 URLs
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
-    // Update the person's profile with the new avatar URL
-    let person_id = if user.id.starts_with("person:") {
-        user.id.clone()
-    } else {
-        format!("person:{}", user.id)
-    };
-
-    // Use SET to update only the avatar field without replacing the entire profile object
-    let person_rid = surrealdb::types::RecordId::parse_simple(&person_id)
-        .map_err(|e| Error::BadRequest(e.to_string()))?;
-
-    DB.query("UPDATE $pid SET profile.avatar = $avatar RETURN NONE")
-        .bind(("pid", person_rid))
+    let person_rid = normalize_record_id(&user.id, "person");
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: person_rid.clone(),
+            target: person_rid.clone(),
+            media_type: media_kind::AVATAR,
+            replace: true,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed_image),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
+    // Point the profile at the new avatar (URL kept denormalized for templates).
+    // Use SET to update only these fields without replacing the entire profile object
+    DB.query("UPDATE $pid SET profile.avatar = $avatar, profile.avatar_media = $media RETURN NONE")
+        .bind(("pid", person_rid.clone()))
         .bind(("avatar", main_url.clone()))
+        .bind(("media", media_id.clone()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to update profile avatar: {}", e)))?;
 
@@ -232,10 +746,14 @@ async fn upload_profile_image(
 
     // Activation signal: this member now has a profile photo (the single
     // biggest discoverability driver). Fire-and-forget into activity_event.
-    crate::services::activity::log_activity(Some(&person_id), "avatar_added", "/profile/edit");
+    crate::services::activity::log_activity(
+        Some(&person_rid.to_raw_string()),
+        "avatar_added",
+        "/profile/edit",
+    );
 
     Ok(Json(UploadResponse {
-        media_id: image_id, // Use the generated UUID as the ID
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
@@ -245,19 +763,13 @@ async fn upload_profile_image(
 async fn delete_profile_image(
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Json<serde_json::Value>, Error> {
-    let person_id = if user.id.starts_with("person:") {
-        user.id.clone()
-    } else {
-        format!("person:{}", user.id)
-    };
-
-    let person_rid = surrealdb::types::RecordId::parse_simple(&person_id)
-        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    let person_rid = normalize_record_id(&user.id, "person");
 
-    DB.query("UPDATE $pid SET profile.avatar = NONE RETURN NONE")
-        .bind(("pid", person_rid))
+    DB.query("UPDATE $pid SET profile.avatar = NONE, profile.avatar_media = NONE RETURN NONE")
+        .bind(("pid", person_rid.clone()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete profile avatar: {}", e)))?;
+    Media::forget(&person_rid, media_kind::AVATAR, None).await?;
 
     info!("Profile image deleted for user {}", user.username);
 
@@ -273,6 +785,8 @@ async fn upload_profile_photo(
     AuthenticatedUser(user): AuthenticatedUser,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!("User {} uploading profile photo", user.username);
 
     // Extract the image from multipart
@@ -293,22 +807,11 @@ async fn upload_profile_photo(
             .unwrap_or("application/octet-stream")
             .to_string();
 
-        if !ALLOWED_FORMATS.contains(&content_type.as_str()) {
-            return Err(Error::bad_request(format!(
-                "Invalid file type: {}. Allowed types: JPEG, PNG, WebP",
-                content_type
-            )));
-        }
+        check_declared_type(&content_type, false)?;
 
-        let data = field
-            .bytes()
-            .await
-            .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-
-        if data.len() > MAX_FILE_SIZE {
-            return Err(Error::bad_request("File too large. Maximum size is 10MB"));
-        }
+        let data = read_field_limited(field, MAX_FILE_SIZE).await?;
 
+        let content_type = check_image_type(&data, false)?.to_string();
         image_data = Some((content_type, data));
         break;
     }
@@ -318,18 +821,11 @@ async fn upload_profile_photo(
 
     // Check current photo count and verification-based limits
     let sanitized_user_id = user.id.strip_prefix("person:").unwrap_or(&user.id);
-    let person_id = if user.id.starts_with("person:") {
-        user.id.clone()
-    } else {
-        format!("person:{}", user.id)
-    };
+    let person_rid = normalize_record_id(&user.id, "person");
 
-    let info_sql = format!(
-        "SELECT array::len(profile.photos) AS photo_count, verification_status FROM {}",
-        person_id
-    );
     let mut info_resp = DB
-        .query(&info_sql)
+        .query("SELECT array::len(profile.photos) AS photo_count, verification_status FROM $pid")
+        .bind(("pid", person_rid.clone()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to check photo count: {}", e)))?;
     let info: Option<serde_json::Value> = info_resp.take(0).ok().and_then(|v| v);
@@ -364,20 +860,24 @@ async fn upload_profile_photo(
         sanitized_user_id, image_id
     );
 
-    let s3_service = s3()?;
-    s3_service
-        .upload_file(&main_key, processed, "image/jpeg")
-        .await?;
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: person_rid.clone(),
+            target: person_rid.clone(),
+            media_type: media_kind::PROFILE_PHOTO,
+            replace: false,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
     // Append photo to profile.photos array
-    let person_rid = surrealdb::types::RecordId::parse_simple(&person_id)
-        .map_err(|e| Error::BadRequest(e.to_string()))?;
     DB.query("UPDATE $pid SET profile.photos += $photo RETURN NONE")
         .bind(("pid", person_rid))
         .bind((
@@ -397,7 +897,7 @@ async fn upload_profile_photo(
     );
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
@@ -413,21 +913,14 @@ async fn delete_profile_photo(
         .and_then(|v| v.as_str())
         .ok_or_else(|| Error::bad_request("Missing 'url' field"))?;
 
-    let person_id = if user.id.starts_with("person:") {
-        user.id.clone()
-    } else {
-        format!("person:{}", user.id)
-    };
-
-    // Remove the photo with matching URL from the array
-    let person_rid = surrealdb::types::RecordId::parse_simple(&person_id)
-        .map_err(|e| Error::BadRequest(e.to_string()))?;
+    let person_rid = normalize_record_id(&user.id, "person");
 
     DB.query("UPDATE $pid SET profile.photos = profile.photos[WHERE url != $url] RETURN NONE")
-        .bind(("pid", person_rid))
+        .bind(("pid", person_rid.clone()))
         .bind(("url", url.to_string()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete profile photo: {}", e)))?;
+    Media::forget(&person_rid, media_kind::PROFILE_PHOTO, Some(url)).await?;
 
     info!("Profile photo deleted for user {}", user.username);
 
@@ -435,120 +928,18 @@ async fn delete_profile_photo(
 }
 
 /// Resize a photo (max width, aspect preserved) and build its thumbnail.
-///
-/// Decode + Lanczos3 resize + JPEG encode are CPU-bound (hundreds of ms on
-/// large uploads), so the work runs on tokio's blocking pool to keep the
-/// async runtime responsive. Returns `(full, thumbnail)` JPEG bytes.
 async fn process_photo(image_data: Bytes) -> Result<(Bytes, Bytes), Error> {
-    tokio::task::spawn_blocking(move || process_photo_blocking(&image_data))
-        .await
-        .map_err(|e| Error::Internal(format!("image task join error: {e}")))?
+    process_image(image_data, PHOTO_FITS, (None, None, None)).await
 }
 
-fn process_photo_blocking(image_data: &[u8]) -> Result<(Bytes, Bytes), Error> {
-    let img = image::load_from_memory(image_data)
-        .map_err(|e| Error::bad_request(format!("Invalid image file: {}", e)))?;
-
-    // Resize to max width, maintaining aspect ratio
-    let full = if img.width() > PHOTO_MAX_WIDTH {
-        img.resize(
-            PHOTO_MAX_WIDTH,
-            u32::MAX,
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else {
-        img.clone()
-    };
-
-    // Create thumbnail
-    let thumb = if img.width() > PHOTO_THUMB_WIDTH {
-        img.resize(
-            PHOTO_THUMB_WIDTH,
-            u32::MAX,
-            image::imageops::FilterType::Lanczos3,
-        )
-    } else {
-        img
-    };
-
-    let mut full_bytes = Cursor::new(Vec::new());
-    full.write_to(&mut full_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode photo: {}", e)))?;
-
-    let mut thumb_bytes = Cursor::new(Vec::new());
-    thumb
-        .write_to(&mut thumb_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode thumbnail: {}", e)))?;
-
-    Ok((
-        Bytes::from(full_bytes.into_inner()),
-        Bytes::from(thumb_bytes.into_inner()),
-    ))
-}
-
-/// Crop (circular or center-square), resize, and JPEG-encode a profile image.
-///
-/// CPU-bound; runs on the blocking pool — see [`process_photo`].
+/// Crop (circular or center-square) and resize a profile image.
 async fn process_profile_image(
     image_data: Bytes,
     crop_x: Option<f32>,
     crop_y: Option<f32>,
     crop_zoom: Option<f32>,
 ) -> Result<(Bytes, Bytes), Error> {
-    tokio::task::spawn_blocking(move || {
-        process_profile_image_blocking(&image_data, crop_x, crop_y, crop_zoom)
-    })
-    .await
-    .map_err(|e| Error::Internal(format!("image task join error: {e}")))?
-}
-
-fn process_profile_image_blocking(
-    image_data: &[u8],
-    crop_x: Option<f32>,
-    crop_y: Option<f32>,
-    crop_zoom: Option<f32>,
-) -> Result<(Bytes, Bytes), Error> {
-    // Load the image
-    let img = image::load_from_memory(image_data)
-        .map_err(|e| Error::bad_request(format!("Invalid image file: {}", e)))?;
-
-    // Apply crop if parameters provided
-    let cropped = if let (Some(x), Some(y), Some(zoom)) = (crop_x, crop_y, crop_zoom) {
-        apply_circular_crop(img, x, y, zoom)?
-    } else {
-        // Center crop to square
-        center_crop_square(img)
-    };
-
-    // Resize for profile image
-    let profile_img = cropped.resize_exact(
-        PROFILE_IMAGE_SIZE,
-        PROFILE_IMAGE_SIZE,
-        image::imageops::FilterType::Lanczos3,
-    );
-
-    // Create thumbnail
-    let thumbnail = profile_img.resize_exact(
-        THUMBNAIL_SIZE,
-        THUMBNAIL_SIZE,
-        image::imageops::FilterType::Lanczos3,
-    );
-
-    // Convert to JPEG bytes
-    let mut profile_bytes = Cursor::new(Vec::new());
-    profile_img
-        .write_to(&mut profile_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode image: {}", e)))?;
-
-    let mut thumb_bytes = Cursor::new(Vec::new());
-    thumbnail
-        .write_to(&mut thumb_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode thumbnail: {}", e)))?;
-
-    Ok((
-        Bytes::from(profile_bytes.into_inner()),
-        Bytes::from(thumb_bytes.into_inner()),
-    ))
+    process_image(image_data, PROFILE_FITS, (crop_x, crop_y, crop_zoom)).await
 }
 
 /// Apply circular crop with zoom and position
@@ -599,38 +990,37 @@ async fn get_profile_image_url(
         return Err(Error::BadRequest("Invalid person ID".to_string()));
     }
 
-    // Get the profile avatar URL directly from the person record
+    #[derive(Deserialize, SurrealValue)]
+    struct AvatarRow {
+        avatar: Option<String>,
+        avatar_media: Option<RecordId>,
+    }
+
+    // Get the profile avatar URL (and its media record) directly from the person record
     let mut response = DB
-        .query("SELECT profile.avatar FROM type::record('person', $id) LIMIT 1")
+        .query(
+            "SELECT profile.avatar AS avatar, profile.avatar_media AS avatar_media
+             FROM type::record('person', $id) LIMIT 1",
+        )
         .bind(("id", id.to_string()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to fetch profile avatar: {}", e)))?;
 
-    let result: Option<serde_json::Value> = response.take(0).ok().and_then(|v| v);
-
-    if let Some(data) = result {
-        if let Some(avatar_url) = data
-            .get("profile")
-            .and_then(|p| p.get("avatar"))
-            .and_then(|a| a.as_str())
-        {
-            Ok(Json(serde_json::json!({
-                "url": avatar_url,
-                "media_id": null,
-            })))
-        } else {
-            // Return default avatar URL
-            Ok(Json(serde_json::json!({
-                "url": "/static/images/default-avatar.svg",
-                "media_id": null,
-            })))
-        }
-    } else {
+    let result: Option<AvatarRow> = response.take(0).ok().and_then(|v| v);
+
+    match result {
+        Some(AvatarRow {
+            avatar: Some(avatar_url),
+            avatar_media,
+        }) => Ok(Json(serde_json::json!({
+            "url": avatar_url,
+            "media_id": avatar_media.map(|m| m.key_string()),
+        }))),
         // Return default avatar URL
-        Ok(Json(serde_json::json!({
+        _ => Ok(Json(serde_json::json!({
             "url": "/static/images/default-avatar.svg",
             "media_id": null,
-        })))
+        }))),
     }
 }
 
@@ -640,6 +1030,8 @@ async fn upload_organization_logo(
     Query(params): Query<ImageProcessParams>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!("User {} uploading organization logo", user.username);
 
     // Extract organization slug from query params
@@ -667,26 +1059,12 @@ async fn upload_organization_logo(
                 .to_string();
 
             // Validate content type
-            if !ALLOWED_FORMATS.contains(&content_type.as_str()) {
-                return Err(Error::bad_request(format!(
-                    "Invalid file format. Allowed: JPEG, PNG, WebP. Got: {}",
-                    content_type
-                )));
-            }
+            check_declared_type(&content_type, true)?;
 
             let filename = field.file_name().unwrap_or("upload").to_string();
-            let data = field
-                .bytes()
-                .await
-                .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-
-            // Check file size
-            if data.len() > MAX_FILE_SIZE {
-                return Err(Error::bad_request(
-                    "File too large. Maximum size is 10MB".to_string(),
-                ));
-            }
+            let data = read_field_limited(field, MAX_FILE_SIZE).await?;
 
+            let content_type = check_image_type(&data, true)?.to_string();
             image_data = Some((filename, content_type, data));
         }
     }
@@ -703,148 +1081,63 @@ async fn upload_organization_logo(
     );
 
     // Check if user has permission to upload logo for this organization
-    // Check permission using OrganizationModel
-    let model = OrganizationModel::new();
-    let organization = model.get_by_slug(&org_slug).await?;
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    if role != Some("owner".to_string()) && role != Some("admin".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    let org_rid = check_organization_edit(&org_slug, &user.id).await?;
 
-    // Process the logo image (with optional SVG support)
-    let (processed_image, thumbnail) = if content_type.contains("svg") {
-        // For SVG, we'll store as-is and create a rasterized thumbnail
-        let thumbnail = create_svg_thumbnail(&data)?;
-        (data.clone(), thumbnail)
+    // SVG is rasterized and the markup discarded, so no uploaded SVG is
+    // ever stored or served (see `services::svg`)
+    let data = if content_type.contains("svg") {
+        image_pool::run(move || svg::rasterize(&data, LOGO_SIZE)).await?
     } else {
-        // For raster images, process normally
-        process_logo_image(data.clone(), params.crop_x, params.crop_y, params.crop_zoom).await?
+        data
     };
+    let (processed_image, thumbnail) =
+        process_logo_image(data, params.crop_x, params.crop_y, params.crop_zoom).await?;
 
     // Generate unique keys for S3
     let image_id = Ulid::new().to_string();
-    let file_extension = if content_type.contains("svg") {
-        "svg"
-    } else {
-        "jpg"
-    };
-
-    let main_key = format!(
-        "organizations/{}/logo_{}.{}",
-        org_slug, image_id, file_extension
-    );
+    let main_key = format!("organizations/{}/logo_{}.jpg", org_slug, image_id);
     let thumb_key = format!("organizations/{}/thumb_{}.jpg", org_slug, image_id);
 
-    // Upload to S3
-    let s3_service = s3()?;
-
-    // Upload to S3 but don't use the returned URLs
-    s3_service
-        .upload_file(&main_key, processed_image.clone(), &content_type)
-        .await?;
-
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     // Create proxy URLs instead of using direct S3 URLs
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: org_rid.clone(),
+            target: org_rid,
+            media_type: media_kind::ORG_LOGO,
+            replace: true,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed_image),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
     // Update the organization's logo field
-    DB.query("UPDATE organization SET logo = $logo WHERE slug = $slug")
-        .bind(("logo", main_url.clone()))
-        .bind(("slug", org_slug.clone()))
-        .await
-        .map_err(|e| Error::Internal(format!("Failed to update organization logo: {}", e)))?;
+    OrganizationModel::new()
+        .set_logo(&org_slug, Some(main_url.clone()))
+        .await?;
 
     info!("Organization logo uploaded successfully for {}", org_slug);
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
 }
 
-/// Crop, resize, and JPEG-encode an organization logo + thumbnail.
-///
-/// CPU-bound; runs on the blocking pool — see [`process_photo`].
+/// Crop and resize an organization logo + thumbnail.
 async fn process_logo_image(
     image_data: Bytes,
     crop_x: Option<f32>,
     crop_y: Option<f32>,
     crop_zoom: Option<f32>,
 ) -> Result<(Bytes, Bytes), Error> {
-    tokio::task::spawn_blocking(move || {
-        process_logo_image_blocking(&image_data, crop_x, crop_y, crop_zoom)
-    })
-    .await
-    .map_err(|e| Error::Internal(format!("image task join error: {e}")))?
-}
-
-fn process_logo_image_blocking(
-    image_data: &[u8],
-    crop_x: Option<f32>,
-    crop_y: Option<f32>,
-    crop_zoom: Option<f32>,
-) -> Result<(Bytes, Bytes), Error> {
-    // Load the image
-    let img = image::load_from_memory(image_data)
-        .map_err(|e| Error::bad_request(format!("Invalid image file: {}", e)))?;
-
-    // Apply crop if parameters provided
-    let cropped = if let (Some(x), Some(y), Some(zoom)) = (crop_x, crop_y, crop_zoom) {
-        apply_circular_crop(img, x, y, zoom)?
-    } else {
-        // Center crop to square
-        center_crop_square(img)
-    };
-
-    // Resize for logo
-    let logo_img =
-        cropped.resize_exact(LOGO_SIZE, LOGO_SIZE, image::imageops::FilterType::Lanczos3);
-
-    // Create thumbnail
-    let thumbnail = logo_img.resize_exact(
-        LOGO_THUMBNAIL_SIZE,
-        LOGO_THUMBNAIL_SIZE,
-        image::imageops::FilterType::Lanczos3,
-    );
-
-    // Convert to JPEG bytes
-    let mut logo_bytes = Cursor::new(Vec::new());
-    logo_img
-        .write_to(&mut logo_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode image: {}", e)))?;
-
-    let mut thumb_bytes = Cursor::new(Vec::new());
-    thumbnail
-        .write_to(&mut thumb_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode thumbnail: {}", e)))?;
-
-    Ok((
-        Bytes::from(logo_bytes.into_inner()),
-        Bytes::from(thumb_bytes.into_inner()),
-    ))
-}
-
-/// Create a thumbnail from SVG data
-fn create_svg_thumbnail(_svg_data: &[u8]) -> Result<Bytes, Error> {
-    // For now, we'll just create a simple placeholder thumbnail
-    // In production, you'd want to use a library like resvg to rasterize SVG
-    // TODO: Implement proper SVG rasterization
-
-    // Create a simple placeholder image
-    let img = DynamicImage::new_rgb8(LOGO_THUMBNAIL_SIZE, LOGO_THUMBNAIL_SIZE);
-
-    let mut thumb_bytes = Cursor::new(Vec::new());
-    img.write_to(&mut thumb_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to create thumbnail: {}", e)))?;
-
-    Ok(Bytes::from(thumb_bytes.into_inner()))
+    process_image(image_data, LOGO_FITS, (crop_x, crop_y, crop_zoom)).await
 }
 
 /// Get the logo URL for an organization
@@ -887,21 +1180,11 @@ async fn delete_organization_logo(
         user.username, org_slug
     );
 
-    // Check permission using OrganizationModel
-    let model = OrganizationModel::new();
-    let organization = model.get_by_slug(&org_slug).await?;
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    if role != Some("owner".to_string()) && role != Some("admin".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    let org_rid = check_organization_edit(&org_slug, &user.id).await?;
 
     // Clear the logo field
-    DB.query("UPDATE organization SET logo = NONE WHERE slug = $slug")
-        .bind(("slug", org_slug.clone()))
-        .await
-        .map_err(|e| Error::Internal(format!("Failed to delete organization logo: {}", e)))?;
+    OrganizationModel::new().set_logo(&org_slug, None).await?;
+    Media::forget(&org_rid, media_kind::ORG_LOGO, None).await?;
 
     info!("Organization logo deleted for {}", org_slug);
 
@@ -915,6 +1198,8 @@ async fn upload_organization_logo_with_slug(
     Query(params): Query<ImageProcessParams>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!(
         "User {} uploading organization logo for {}",
         user.username, org_slug
@@ -937,26 +1222,12 @@ async fn upload_organization_logo_with_slug(
                 .to_string();
 
             // Validate content type
-            if !ALLOWED_FORMATS.contains(&content_type.as_str()) && !content_type.contains("svg") {
-                return Err(Error::bad_request(format!(
-                    "Invalid file format. Allowed: JPEG, PNG, WebP, SVG. Got: {}",
-                    content_type
-                )));
-            }
+            check_declared_type(&content_type, true)?;
 
             let filename = field.file_name().unwrap_or("upload").to_string();
-            let data = field
-                .bytes()
-                .await
-                .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-
-            // Check file size
-            if data.len() > MAX_FILE_SIZE {
-                return Err(Error::bad_request(
-                    "File too large. Maximum size is 10MB".to_string(),
-                ));
-            }
+            let data = read_field_limited(field, MAX_FILE_SIZE).await?;
 
+            let content_type = check_image_type(&data, true)?.to_string();
             image_data = Some((filename, content_type, data));
             break;
         }
@@ -973,67 +1244,50 @@ async fn upload_organization_logo_with_slug(
     );
 
     // Check if user has permission to upload logo for this organization
-    // Check permission using OrganizationModel
-    let model = OrganizationModel::new();
-    let organization = model.get_by_slug(&org_slug).await?;
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    if role != Some("owner".to_string()) && role != Some("admin".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    let org_rid = check_organization_edit(&org_slug, &user.id).await?;
 
-    // Process the logo image (with optional SVG support)
-    let (processed_image, thumbnail) = if content_type.contains("svg") {
-        // For SVG, we'll store as-is and create a rasterized thumbnail
-        let thumbnail = create_svg_thumbnail(&data)?;
-        (data.clone(), thumbnail)
+    // SVG is rasterized and the markup discarded, so no uploaded SVG is
+    // ever stored or served (see `services::svg`)
+    let data = if content_type.contains("svg") {
+        image_pool::run(move || svg::rasterize(&data, LOGO_SIZE)).await?
     } else {
-        // For raster images, process normally
-        process_logo_image(data.clone(), params.crop_x, params.crop_y, params.crop_zoom).await?
+        data
     };
+    let (processed_image, thumbnail) =
+        process_logo_image(data, params.crop_x, params.crop_y, params.crop_zoom).await?;
 
     // Generate unique keys for S3
     let image_id = Ulid::new().to_string();
-    let file_extension = if content_type.contains("svg") {
-        "svg"
-    } else {
-        "jpg"
-    };
-
-    let main_key = format!(
-        "organizations/{}/logo_{}.{}",
-        org_slug, image_id, file_extension
-    );
+    let main_key = format!("organizations/{}/logo_{}.jpg", org_slug, image_id);
     let thumb_key = format!("organizations/{}/thumb_{}.jpg", org_slug, image_id);
 
-    // Upload to S3
-    let s3_service = s3()?;
-
-    // Upload to S3 but don't use the returned URLs
-    s3_service
-        .upload_file(&main_key, processed_image.clone(), &content_type)
-        .await?;
-
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     // Create proxy URLs instead of using direct S3 URLs
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: org_rid.clone(),
+            target: org_rid,
+            media_type: media_kind::ORG_LOGO,
+            replace: true,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed_image),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
     // Update the organization's logo field
-    DB.query("UPDATE organization SET logo = $logo WHERE slug = $slug")
-        .bind(("logo", main_url.clone()))
-        .bind(("slug", org_slug.clone()))
-        .await
-        .map_err(|e| Error::Internal(format!("Failed to update organization logo: {}", e)))?;
+    OrganizationModel::new()
+        .set_logo(&org_slug, Some(main_url.clone()))
+        .await?;
 
     info!("Organization logo uploaded successfully for {}", org_slug);
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
@@ -1049,6 +1303,8 @@ async fn upload_location_profile_photo(
     Query(params): Query<ImageProcessParams>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!(
         "User {} uploading profile photo for location {}",
         user.username, location_id
@@ -1073,19 +1329,9 @@ async fn upload_location_profile_photo(
             .content_type()
             .unwrap_or("application/octet-stream")
             .to_string();
-        if !ALLOWED_FORMATS.contains(&content_type.as_str()) {
-            return Err(Error::bad_request(format!(
-                "Invalid file type: {}. Allowed: JPEG, PNG, WebP",
-                content_type
-            )));
-        }
-        let data = field
-            .bytes()
-            .await
-            .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-        if data.len() > MAX_FILE_SIZE {
-            return Err(Error::bad_request("File too large. Maximum size is 10MB"));
-        }
+        check_declared_type(&content_type, false)?;
+        let data = read_field_limited(field, MAX_FILE_SIZE).await?;
+        let content_type = check_image_type(&data, false)?.to_string();
         image_data = Some((content_type, data));
         break;
     }
@@ -1099,13 +1345,22 @@ async fn upload_location_profile_photo(
     let image_id = Ulid::new().to_string();
     let main_key = format!("locations/{}/{}.jpg", location_id, image_id);
 
-    let s3_service = s3()?;
-    s3_service
-        .upload_file(&main_key, processed, "image/jpeg")
-        .await?;
-
     let main_url = format!("/api/media/{}", main_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: normalize_record_id(&user.id, "person"),
+            target: loc_rid.clone(),
+            media_type: media_kind::LOCATION_PROFILE_PHOTO,
+            replace: true,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed),
+            thumbnail: None,
+        },
+    )
+    .await?;
+
     DB.query("UPDATE $lid SET profile_photo = $url")
         .bind(("lid", loc_rid))
         .bind(("url", main_url.clone()))
@@ -1118,7 +1373,7 @@ async fn upload_location_profile_photo(
     );
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: None,
     }))
@@ -1135,9 +1390,10 @@ async fn delete_location_profile_photo(
     }
 
     DB.query("UPDATE $lid SET profile_photo = NONE")
-        .bind(("lid", loc_rid))
+        .bind(("lid", loc_rid.clone()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete location profile photo: {}", e)))?;
+    Media::forget(&loc_rid, media_kind::LOCATION_PROFILE_PHOTO, None).await?;
 
     info!(
         "Location profile photo deleted for location {}",
@@ -1152,6 +1408,8 @@ async fn upload_location_photo(
     Path(location_id): Path<String>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!(
         "User {} uploading photo for location {}",
         user.username, location_id
@@ -1196,19 +1454,9 @@ async fn upload_location_photo(
             .content_type()
             .unwrap_or("application/octet-stream")
             .to_string();
-        if !ALLOWED_FORMATS.contains(&content_type.as_str()) {
-            return Err(Error::bad_request(format!(
-                "Invalid file type: {}. Allowed: JPEG, PNG, WebP",
-                content_type
-            )));
-        }
-        let data = field
-            .bytes()
-            .await
-            .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-        if data.len() > MAX_FILE_SIZE {
-            return Err(Error::bad_request("File too large. Maximum size is 10MB"));
-        }
+        check_declared_type(&content_type, false)?;
+        let data = read_field_limited(field, MAX_FILE_SIZE).await?;
+        let content_type = check_image_type(&data, false)?.to_string();
         image_data = Some((content_type, data));
         break;
     }
@@ -1222,17 +1470,23 @@ async fn upload_location_photo(
     let main_key = format!("locations/{}/photos/{}.jpg", location_id, image_id);
     let thumb_key = format!("locations/{}/photos/thumb_{}.jpg", location_id, image_id);
 
-    let s3_service = s3()?;
-    s3_service
-        .upload_file(&main_key, processed, "image/jpeg")
-        .await?;
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: normalize_record_id(&user.id, "person"),
+            target: loc_rid.clone(),
+            media_type: media_kind::LOCATION_PHOTO,
+            replace: false,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
     DB.query("UPDATE $lid SET photos += $photo")
         .bind(("lid", loc_rid))
         .bind((
@@ -1249,7 +1503,7 @@ async fn upload_location_photo(
     info!("Location photo uploaded for location {}", location_id);
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
@@ -1272,10 +1526,11 @@ async fn delete_location_photo(
     }
 
     DB.query("UPDATE $lid SET photos = photos[WHERE url != $url]")
-        .bind(("lid", loc_rid))
+        .bind(("lid", loc_rid.clone()))
         .bind(("url", url.to_string()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete location photo: {}", e)))?;
+    Media::forget(&loc_rid, media_kind::LOCATION_PHOTO, Some(url)).await?;
 
     info!("Location photo deleted for location {}", location_id);
     Ok(Json(serde_json::json!({ "success": true })))
@@ -1293,34 +1548,30 @@ const POSTER_HEIGHT: u32 = 900;
 const POSTER_THUMB_WIDTH: u32 = 200;
 const POSTER_THUMB_HEIGHT: u32 = 300;
 
-pub fn process_poster(image_data: &[u8]) -> Result<(Bytes, Bytes), Error> {
-    let img = image::load_from_memory(image_data)
-        .map_err(|e| Error::bad_request(format!("Invalid image file: {}", e)))?;
-
-    let full = img.resize_to_fill(
-        POSTER_WIDTH,
-        POSTER_HEIGHT,
-        image::imageops::FilterType::Lanczos3,
-    );
-    let thumb = img.resize_to_fill(
-        POSTER_THUMB_WIDTH,
-        POSTER_THUMB_HEIGHT,
-        image::imageops::FilterType::Lanczos3,
-    );
-
-    let mut full_bytes = Cursor::new(Vec::new());
-    full.write_to(&mut full_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode poster: {}", e)))?;
-
-    let mut thumb_bytes = Cursor::new(Vec::new());
-    thumb
-        .write_to(&mut thumb_bytes, ImageFormat::Jpeg)
-        .map_err(|e| Error::Internal(format!("Failed to encode poster thumbnail: {}", e)))?;
+/// Crop a poster to 2:3 and build its thumbnail, on the [`image_pool`].
+pub async fn process_poster(image_data: Bytes) -> Result<(Bytes, Bytes), Error> {
+    process_image(image_data, POSTER_FITS, (None, None, None)).await
+}
 
-    Ok((
-        Bytes::from(full_bytes.into_inner()),
-        Bytes::from(thumb_bytes.into_inner()),
-    ))
+/// Helper: check organization edit permissions (logo upload/delete).
+///
+/// Same gate as the org edit page — an accepted membership carrying
+/// [`Permission::UpdateOrganization`] — resolved through the membership
+/// model with bound parameters, so pending invites and join requests
+/// don't qualify.
+async fn check_organization_edit(org_slug: &str, user_id: &str) -> Result<RecordId, Error> {
+    let organization = OrganizationModel::new().get_by_slug(org_slug).await?;
+    let allowed = MembershipModel::new()
+        .has_permission(
+            user_id,
+            &organization.id.to_raw_string(),
+            Permission::UpdateOrganization,
+        )
+        .await?;
+    if !allowed {
+        return Err(Error::Forbidden);
+    }
+    Ok(organization.id)
 }
 
 /// Helper: check production edit permissions
@@ -1350,19 +1601,9 @@ async fn extract_image_from_multipart(multipart: &mut Multipart) -> Result<(Stri
             .content_type()
             .unwrap_or("application/octet-stream")
             .to_string();
-        if !ALLOWED_FORMATS.contains(&content_type.as_str()) {
-            return Err(Error::bad_request(format!(
-                "Invalid file type: {}. Allowed: JPEG, PNG, WebP",
-                content_type
-            )));
-        }
-        let data = field
-            .bytes()
-            .await
-            .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-        if data.len() > MAX_FILE_SIZE {
-            return Err(Error::bad_request("File too large. Maximum size is 10MB"));
-        }
+        check_declared_type(&content_type, false)?;
+        let data = read_field_limited(field, MAX_FILE_SIZE).await?;
+        let content_type = check_image_type(&data, false)?.to_string();
         return Ok((content_type, data));
     }
     Err(Error::bad_request("No image file provided"))
@@ -1375,6 +1616,8 @@ async fn upload_production_header_photo(
     Query(params): Query<ImageProcessParams>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!(
         "User {} uploading header photo for production {}",
         user.username, production_id
@@ -1389,13 +1632,22 @@ async fn upload_production_header_photo(
     let image_id = Ulid::new().to_string();
     let main_key = format!("productions/{}/{}.jpg", production_id, image_id);
 
-    let s3_service = s3()?;
-    s3_service
-        .upload_file(&main_key, processed, "image/jpeg")
-        .await?;
-
     let main_url = format!("/api/media/{}", main_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: normalize_record_id(&user.id, "person"),
+            target: prod_rid.clone(),
+            media_type: media_kind::PRODUCTION_HEADER,
+            replace: true,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed),
+            thumbnail: None,
+        },
+    )
+    .await?;
+
     DB.query("UPDATE $pid SET header_photo = $url")
         .bind(("pid", prod_rid))
         .bind(("url", main_url.clone()))
@@ -1405,7 +1657,7 @@ async fn upload_production_header_photo(
     info!("Production header photo uploaded for {}", production_id);
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: None,
     }))
@@ -1419,9 +1671,10 @@ async fn delete_production_header_photo(
     let prod_rid = check_production_edit(&production_id, &user.id).await?;
 
     DB.query("UPDATE $pid SET header_photo = NONE")
-        .bind(("pid", prod_rid))
+        .bind(("pid", prod_rid.clone()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete production header photo: {}", e)))?;
+    Media::forget(&prod_rid, media_kind::PRODUCTION_HEADER, None).await?;
 
     info!("Production header photo deleted for {}", production_id);
     Ok(Json(serde_json::json!({ "success": true })))
@@ -1433,6 +1686,8 @@ async fn upload_production_poster(
     Path(production_id): Path<String>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!(
         "User {} uploading poster for production {}",
         user.username, production_id
@@ -1441,7 +1696,7 @@ async fn upload_production_poster(
     let prod_rid = check_production_edit(&production_id, &user.id).await?;
 
     let (_content_type, data) = extract_image_from_multipart(&mut multipart).await?;
-    let (processed, thumbnail) = process_poster(&data)?;
+    let (processed, thumbnail) = process_poster(data).await?;
 
     let image_id = Ulid::new().to_string();
     let main_key = format!("productions/{}/poster_{}.jpg", production_id, image_id);
@@ -1450,17 +1705,23 @@ async fn upload_production_poster(
         production_id, image_id
     );
 
-    let s3_service = s3()?;
-    s3_service
-        .upload_file(&main_key, processed, "image/jpeg")
-        .await?;
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: normalize_record_id(&user.id, "person"),
+            target: prod_rid.clone(),
+            media_type: media_kind::PRODUCTION_POSTER,
+            replace: true,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
     DB.query("UPDATE $pid SET poster_photo = $url")
         .bind(("pid", prod_rid))
         .bind(("url", main_url.clone()))
@@ -1470,7 +1731,7 @@ async fn upload_production_poster(
     info!("Production poster uploaded for {}", production_id);
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
@@ -1484,9 +1745,10 @@ async fn delete_production_poster(
     let prod_rid = check_production_edit(&production_id, &user.id).await?;
 
     DB.query("UPDATE $pid SET poster_photo = NONE")
-        .bind(("pid", prod_rid))
+        .bind(("pid", prod_rid.clone()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete production poster: {}", e)))?;
+    Media::forget(&prod_rid, media_kind::PRODUCTION_POSTER, None).await?;
 
     info!("Production poster deleted for {}", production_id);
     Ok(Json(serde_json::json!({ "success": true })))
@@ -1498,6 +1760,8 @@ async fn upload_production_photo(
     Path(production_id): Path<String>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, Error> {
+    check_upload_rate(&user)?;
+
     debug!(
         "User {} uploading gallery photo for production {}",
         user.username, production_id
@@ -1535,17 +1799,23 @@ async fn upload_production_photo(
         production_id, image_id
     );
 
-    let s3_service = s3()?;
-    s3_service
-        .upload_file(&main_key, processed, "image/jpeg")
-        .await?;
-    s3_service
-        .upload_file(&thumb_key, thumbnail, "image/jpeg")
-        .await?;
-
     let main_url = format!("/api/media/{}", main_key);
     let thumb_url = format!("/api/media/{}", thumb_key);
 
+    let media_id = store_upload(
+        &user,
+        PendingUpload {
+            owner: normalize_record_id(&user.id, "person"),
+            target: prod_rid.clone(),
+            media_type: media_kind::PRODUCTION_PHOTO,
+            replace: false,
+            content_type: "image/jpeg".to_string(),
+            main: (main_key, processed),
+            thumbnail: Some((thumb_key, thumbnail)),
+        },
+    )
+    .await?;
+
     DB.query("UPDATE $pid SET photos += $photo")
         .bind(("pid", prod_rid))
         .bind((
@@ -1562,7 +1832,7 @@ async fn upload_production_photo(
     info!("Production gallery photo uploaded for {}", production_id);
 
     Ok(Json(UploadResponse {
-        media_id: image_id,
+        media_id: media_id.key_string(),
         url: main_url,
         thumbnail_url: Some(thumb_url),
     }))
@@ -1582,27 +1852,66 @@ async fn delete_production_photo(
     let prod_rid = check_production_edit(&production_id, &user.id).await?;
 
     DB.query("UPDATE $pid SET photos = photos[WHERE url != $url]")
-        .bind(("pid", prod_rid))
+        .bind(("pid", prod_rid.clone()))
         .bind(("url", url.to_string()))
         .await
         .map_err(|e| Error::Internal(format!("Failed to delete production photo: {}", e)))?;
+    Media::forget(&prod_rid, media_kind::PRODUCTION_PHOTO, Some(url)).await?;
 
     info!("Production gallery photo deleted for {}", production_id);
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
-/// Proxy media files from S3 through the application
+/// Served with any stored SVG in place of the site-wide policy.
+const SVG_CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; img-src data:; sandbox";
+
+/// Proxy media files from S3 through the application.
+///
+/// Images, audio, and video are served `inline`; anything else (resumes and
+/// other documents) as an `attachment` named after the upload's original
+/// filename. The recorded `media` row also supplies the content type when
+/// storage only has `application/octet-stream`.
 async fn proxy_media(Path(path): Path<String>) -> Result<impl IntoResponse, Error> {
     debug!("Proxying media file: {}", path);
 
     let s3 = s3()?;
-    let (data, content_type) = s3.download_file(&path).await?;
+    let (data, stored_type) = s3.download_file(&path).await?;
+
+    let mut content_type = stored_type;
+    let mut filename = s3::key_filename(&path).to_string();
+    let generic = content_type == "application/octet-stream";
+    if generic || !s3::renders_inline(&content_type) {
+        match Media::find_by_uri(&format!("/api/media/{}", path)).await {
+            Ok(Some(media)) => {
+                if generic {
+                    content_type = media.mime_type;
+                }
+                if !media.name.trim().is_empty() {
+                    filename = media.name;
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Media lookup for {} failed: {}", path, e),
+        }
+    }
 
     // Build the response with appropriate headers
-    let response = Response::builder()
+    let mut response = Response::builder()
         .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, content_type)
         .header(header::CACHE_CONTROL, "public, max-age=31536000") // Cache for 1 year
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(
+            header::CONTENT_DISPOSITION,
+            s3::content_disposition(&content_type, &filename),
+        );
+    if content_type.contains("svg") {
+        // Logos uploaded before SVGs were rasterized: opened directly, the
+        // sandbox keeps any embedded script from running on our origin
+        response = response.header(header::CONTENT_SECURITY_POLICY, SVG_CONTENT_SECURITY_POLICY);
+    }
+    let response = response
+        .header(header::CONTENT_TYPE, content_type)
         .body(Body::from(data))
         .map_err(|e| Error::Internal(format!("Failed to build response: {}", e)))?;
 
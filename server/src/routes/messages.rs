@@ -56,6 +56,7 @@ struct InboxTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     conversations: Vec<ConversationView>,
 }
 
@@ -67,6 +68,7 @@ struct ConversationTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     conversation_id: String,
     other_person_name: String,
     other_person_username: String,
@@ -84,6 +86,7 @@ struct NewMessageTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     recipient_username: String,
     recipient_name: String,
     recipient_avatar: Option<String>,
@@ -145,6 +148,7 @@ async fn inbox(AuthenticatedUser(user): AuthenticatedUser) -> Result<Html<String
                 verification_status: "unverified".to_string(),
                 profile: None,
                 messaging_preference: "nobody".to_string(),
+                timezone: None,
             });
 
         // Count unread messages in this conversation
@@ -663,7 +667,7 @@ async fn send_new_message_notification(
     let conv_id = conversation_id.to_string();
     tokio::spawn(async move {
         if let Ok(email_service) = EmailService::from_env() {
-            let base_url = crate::config::app_url();
+            let base_url = crate::config::public_base_url();
             let message_url = format!("{}/messages/{}", base_url, conv_id);
             let subject = format!("New message from {} on SlateHub", sender_name_clone);
 
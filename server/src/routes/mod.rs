@@ -1,6 +1,7 @@
 //! Route registry: assembles every feature router plus static files, the
-//! MCP service, and the middleware stack (request-id, errors, auth,
-//! activity, tracing, CORS, security headers) into the app `Router`.
+//! MCP service, and the middleware stack (request-id, locale, errors, DB
+//! gate, read-only mode, auth, timezone, activity, tracing, compression,
+//! CORS, security headers) into the app `Router`.
 //! Specific routers mount before generic ones — org_settings and analytics
 //! ahead of the profile routers — and `public_profiles` merges last so its
 //! root-level `/{username}` catch-all can't conflict with any literal path.
@@ -10,13 +11,21 @@ use axum::http::{HeaderValue, Method, Request, Response, header};
 use axum::{Router, middleware, routing::get_service};
 use std::time::Duration;
 use tower_http::{
-    compression::CompressionLayer, cors::CorsLayer, services::ServeDir,
-    set_header::SetResponseHeaderLayer, trace::TraceLayer,
+    compression::{
+        CompressionLayer,
+        predicate::{NotForContentType, Predicate, SizeAbove},
+    },
+    cors::CorsLayer,
+    services::ServeDir,
+    set_header::SetResponseHeaderLayer,
+    trace::TraceLayer,
 };
 use tracing::{Span, error, info};
 
 use crate::middleware::{
-    RequestIdExt, auth_middleware, error_response_middleware, request_id_middleware,
+    CspPolicy, RequestIdExt, auth_middleware, csp_middleware, db_gate_middleware,
+    error_response_middleware, locale_middleware, read_only_middleware, request_id_middleware,
+    timezone_middleware,
 };
 
 mod account;
@@ -24,6 +33,7 @@ mod admin;
 mod analytics;
 mod api;
 mod auth;
+mod comments;
 mod developers;
 mod equipment;
 mod jobs;
@@ -79,6 +89,8 @@ pub fn app() -> Router {
         .merge(productions::router())
         // Mount the production-management workspace (gated by feature flag + membership)
         .merge(productions_manage::router())
+        // Mount production comment routes
+        .merge(comments::router())
         // Mount jobs routes
         .merge(jobs::router())
         // Mount likes routes
@@ -111,12 +123,12 @@ pub fn app() -> Router {
         .nest_service("/mcp", crate::mcp::create_mcp_service())
         // Raise body limit to 50MB to support script uploads (individual handlers enforce their own limits)
         .layer(DefaultBodyLimit::max(50 * 1024 * 1024))
-        // Static files — long cache with immutable (URLs include ?v= cache buster)
+        // Static files — immutable when the URL is versioned, short-lived otherwise
         .nest_service(
             "/static",
-            get_service(static_service).layer(SetResponseHeaderLayer::overriding(
-                header::CACHE_CONTROL,
-                header::HeaderValue::from_static("public, max-age=31536000, immutable"),
+            get_service(static_service).layer(middleware::from_fn_with_state(
+                crate::config::StaticCachePolicy::from_env(),
+                static_cache_middleware,
             )),
         )
         // Mount ad landing pages (/a/{campaign}) ahead of the public-profile
@@ -129,10 +141,18 @@ pub fn app() -> Router {
         .layer(middleware::from_fn(
             crate::middleware::activity::activity_middleware,
         ))
+        // Render dates in the user's timezone (needs the auth user)
+        .layer(middleware::from_fn(timezone_middleware))
         // Apply auth middleware to extract user from JWT cookies
         .layer(middleware::from_fn(auth_middleware))
+        // Reject writes while in read-only maintenance mode
+        .layer(middleware::from_fn(read_only_middleware))
+        // Answer with a 503 while the database health monitor reconnects
+        .layer(middleware::from_fn(db_gate_middleware))
         // Error response middleware - converts errors to HTML/JSON based on Accept header
         .layer(middleware::from_fn(error_response_middleware))
+        // Pick the response language (lang cookie, then Accept-Language)
+        .layer(middleware::from_fn(locale_middleware))
         // Security headers
         .layer(SetResponseHeaderLayer::overriding(
             header::X_FRAME_OPTIONS,
@@ -150,6 +170,12 @@ pub fn app() -> Router {
             header::HeaderName::from_static("x-xss-protection"),
             HeaderValue::from_static("1; mode=block"),
         ))
+        // Content-Security-Policy with a per-request nonce for inline tags.
+        // Handlers that need a stricter or looser policy set their own.
+        .layer(middleware::from_fn_with_state(
+            CspPolicy::from_config(),
+            csp_middleware,
+        ))
         // CORS — allow Chrome extension origins to call /api/* endpoints
         .layer(
             CorsLayer::new()
@@ -161,7 +187,9 @@ pub fn app() -> Router {
                 .max_age(Duration::from_secs(3600)),
         )
         // Middleware
-        .layer(CompressionLayer::new())
+        .layer(compression_layer(
+            crate::config::CompressionConfig::from_env(),
+        ))
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(|request: &Request<_>| {
@@ -217,3 +245,53 @@ pub fn app() -> Router {
         // This ensures the request ID is available to all other middleware
         .layer(middleware::from_fn(request_id_middleware))
 }
+
+/// Gzip/brotli for text responses. When a client accepts both at equal
+/// weight brotli wins (tower-http ranks it above gzip), and at the default
+/// level it runs at quality 4, nginx's on-the-fly setting.
+///
+/// Bodies under `min_size` go out as-is. A short body can come out larger
+/// than it went in once the gzip header and trailer are added, and anything
+/// under ~1 KB fits in a single TCP segment with its headers either way, so
+/// compressing it saves no round trip and only costs encoder setup.
+///
+/// Formats that are already compressed — JPEG/PNG/WebP images, WOFF fonts,
+/// audio/video, archives, PDFs, and opaque `application/octet-stream`
+/// downloads — only burn CPU when re-encoded; SVG and OTF are text-like and
+/// still qualify. SSE streams stay uncompressed so events aren't held in the
+/// encoder's buffer.
+fn compression_layer(config: crate::config::CompressionConfig) -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().quality(config.level).compress_when(
+        SizeAbove::new(config.min_size)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::const_new("font/woff"))
+            .and(NotForContentType::const_new("audio/"))
+            .and(NotForContentType::const_new("video/"))
+            .and(NotForContentType::const_new("application/zip"))
+            .and(NotForContentType::const_new("application/gzip"))
+            .and(NotForContentType::const_new("application/pdf"))
+            .and(NotForContentType::const_new("application/octet-stream"))
+            .and(NotForContentType::SSE)
+            .and(NotForContentType::GRPC),
+    )
+}
+
+/// `Cache-Control` for `/static` responses per [`StaticCachePolicy`]. Misses
+/// and errors are left uncached so a file added by the next deploy shows up.
+///
+/// [`StaticCachePolicy`]: crate::config::StaticCachePolicy
+async fn static_cache_middleware(
+    axum::extract::State(policy): axum::extract::State<crate::config::StaticCachePolicy>,
+    request: axum::extract::Request,
+    next: middleware::Next,
+) -> axum::response::Response {
+    let value = policy.cache_control(request.uri().path(), request.uri().query());
+    let mut response = next.run(request).await;
+    let status = response.status();
+    if (status.is_success() || status == axum::http::StatusCode::NOT_MODIFIED)
+        && let Ok(value) = HeaderValue::from_str(&value)
+    {
+        response.headers_mut().insert(header::CACHE_CONTROL, value);
+    }
+    response
+}
@@ -1,23 +1,29 @@
 //! Notification routes: the `/notifications` list page, mark-read /
-//! delete / clear-all form actions, org-invitation accept/decline, and a
-//! long-lived SSE stream (`/api/notifications/stream`) that pushes unread
-//! badge counts to the navbar whenever a broadcast event for the signed-in
-//! user arrives.
+//! delete / clear-all form actions, org-invitation accept/decline, a JSON
+//! API (`/api/notifications` list, mark-read, read-all, and the bell
+//! `count`), and a long-lived SSE stream (`/api/notifications/stream`) that
+//! pushes unread badge counts to the navbar whenever a broadcast event for
+//! the signed-in user arrives.
 
 use askama::Template;
 use axum::{
-    Form, Router,
+    Form, Json, Router,
+    extract::{Path, Query},
     response::{Html, Redirect},
     routing::{get, post},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
 use crate::{
     error::Error,
     middleware::AuthenticatedUser,
-    models::{membership::MembershipModel, notification::NotificationModel},
+    models::{
+        membership::MembershipModel,
+        notification::{Notification, NotificationModel},
+    },
     record_id_ext::RecordIdExt,
+    response,
     templates::{BaseContext, User},
 };
 
@@ -33,7 +39,7 @@ struct NotificationView {
     link: Option<String>,
     read: bool,
     related_id: Option<String>,
-    created_at: String,
+    created_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Template)]
@@ -44,6 +50,7 @@ struct NotificationsTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     notifications: Vec<NotificationView>,
 }
 
@@ -54,10 +61,15 @@ impl NotificationsTemplate {
 }
 
 /// Routes for `/notifications` pages/actions, `/invitations` accept/decline,
-/// and the `/api/notifications/stream` SSE badge feed.
+/// the `/api/notifications` JSON API, and the `/api/notifications/stream`
+/// SSE badge feed.
 pub fn router() -> Router {
     Router::new()
         .route("/notifications", get(list_notifications))
+        .route("/api/notifications", get(api_list_notifications))
+        .route("/api/notifications/count", get(api_unread_count))
+        .route("/api/notifications/read-all", post(api_mark_all_read))
+        .route("/api/notifications/{id}/read", post(api_mark_read))
         .route("/api/notifications/stream", get(notification_stream_sse))
         .route("/notifications/mark-read", post(mark_read))
         .route("/notifications/read-all", post(mark_all_read))
@@ -85,7 +97,7 @@ async fn list_notifications(
             link: n.link,
             read: n.read,
             related_id: n.related_id,
-            created_at: n.created_at.format("%b %d, %Y at %H:%M").to_string(),
+            created_at: n.created_at,
         })
         .collect();
 
@@ -168,18 +180,13 @@ async fn accept_invitation(
         .delete(&form.notification_id, &user.id)
         .await?;
 
-    // Redirect to the link from the notification, or fall back to org slug lookup
-    let redirect_url = if let Some(ref redirect) = form.redirect {
-        if !redirect.is_empty() {
-            redirect.clone()
-        } else {
-            let org_slug = get_org_slug(&form.org_id).await;
-            format!("/orgs/{}", org_slug.unwrap_or_default())
-        }
-    } else {
-        let org_slug = get_org_slug(&form.org_id).await;
-        format!("/orgs/{}", org_slug.unwrap_or_default())
-    };
+    // Redirect to the link from the notification if it's same-site (or an
+    // allowed host), otherwise to the org's page
+    let org_slug = get_org_slug(&form.org_id).await;
+    let redirect_url = response::safe_redirect_target(
+        form.redirect.as_deref(),
+        &format!("/orgs/{}", org_slug.unwrap_or_default()),
+    );
     Ok(Redirect::to(&redirect_url))
 }
 
@@ -241,6 +248,111 @@ async fn clear_all_notifications(
     Ok(Redirect::to("/notifications"))
 }
 
+/// Largest page the JSON list will return.
+const API_MAX_LIMIT: u32 = 100;
+
+/// JSON shape of one notification (record ids as "table:key" strings).
+#[derive(Serialize)]
+struct NotificationJson {
+    id: String,
+    notification_type: String,
+    title: String,
+    message: String,
+    link: Option<String>,
+    read: bool,
+    related_id: Option<String>,
+    created_at: String,
+}
+
+impl From<Notification> for NotificationJson {
+    fn from(n: Notification) -> Self {
+        Self {
+            id: n.id.to_raw_string(),
+            notification_type: n.notification_type,
+            title: n.title,
+            message: n.message,
+            link: n.link,
+            read: n.read,
+            related_id: n.related_id,
+            created_at: n.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiListQuery {
+    #[serde(default)]
+    unread_only: bool,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct ApiListResponse {
+    notifications: Vec<NotificationJson>,
+    unread_count: u32,
+}
+
+#[derive(Serialize)]
+struct UnreadCountResponse {
+    unread_count: u32,
+}
+
+/// `GET /api/notifications?unread_only=&limit=&offset=` — newest first.
+async fn api_list_notifications(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(query): Query<ApiListQuery>,
+) -> Result<Json<ApiListResponse>, Error> {
+    let notification_model = NotificationModel::new();
+    let limit = query.limit.unwrap_or(20).clamp(1, API_MAX_LIMIT);
+    let notifications = notification_model
+        .list(
+            &user.id,
+            query.unread_only,
+            limit,
+            query.offset.unwrap_or(0),
+        )
+        .await?;
+    let unread_count = notification_model.get_unread_count(&user.id).await?;
+
+    Ok(Json(ApiListResponse {
+        notifications: notifications.into_iter().map(Into::into).collect(),
+        unread_count,
+    }))
+}
+
+/// `GET /api/notifications/count` — the bell badge number.
+async fn api_unread_count(
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<UnreadCountResponse>, Error> {
+    let unread_count = NotificationModel::new().get_unread_count(&user.id).await?;
+    Ok(Json(UnreadCountResponse { unread_count }))
+}
+
+/// `POST /api/notifications/{id}/read` — `id` is the bare record key.
+async fn api_mark_read(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Json<UnreadCountResponse>, Error> {
+    let notification_model = NotificationModel::new();
+    let id = if id.starts_with("notification:") {
+        id
+    } else {
+        format!("notification:{}", id)
+    };
+    notification_model.mark_read(&id, &user.id).await?;
+    let unread_count = notification_model.get_unread_count(&user.id).await?;
+    Ok(Json(UnreadCountResponse { unread_count }))
+}
+
+/// `POST /api/notifications/read-all`.
+async fn api_mark_all_read(
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Json<UnreadCountResponse>, Error> {
+    NotificationModel::new().mark_all_read(&user.id).await?;
+    Ok(Json(UnreadCountResponse { unread_count: 0 }))
+}
+
 async fn get_org_slug(org_id: &str) -> Option<String> {
     use crate::models::organization::OrganizationModel;
     let model = OrganizationModel::new();
@@ -72,7 +72,7 @@ pub fn router() -> Router {
 // ---------- Discovery + JWKS ----------
 
 async fn discovery() -> Result<Json<Value>, Error> {
-    let issuer = config::app_url();
+    let issuer = config::public_base_url();
     Ok(Json(json!({
         "issuer": issuer,
         "authorization_endpoint": format!("{issuer}/authorize"),
@@ -123,6 +123,7 @@ pub struct ConsentTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub client_name: String,
     pub org_name: String,
     pub requested_scopes: Vec<ScopeView>,
@@ -576,7 +577,7 @@ async fn build_id_token(
         .unwrap_or(0);
     let exp = now + 600; // id_token lives 10 minutes — clients should treat access_token as session
     let mut claims = serde_json::Map::new();
-    claims.insert("iss".into(), json!(config::app_url()));
+    claims.insert("iss".into(), json!(config::public_base_url()));
     claims.insert("sub".into(), json!(person.to_raw_string()));
     claims.insert("aud".into(), json!(client.client_id));
     claims.insert("iat".into(), json!(now));
@@ -839,9 +840,6 @@ async fn rp_initiated_logout(
     Query(p): Query<LogoutParams>,
     jar: axum_extra::extract::CookieJar,
 ) -> Result<Response, Error> {
-    use axum_extra::extract::cookie::Cookie;
-    use cookie::SameSite;
-
     // If a post-logout URI is requested, validate it against the client's allowlist.
     let mut validated_redirect: Option<String> = None;
     if let Some(uri) = p.post_logout_redirect_uri.clone() {
@@ -869,15 +867,7 @@ async fn rp_initiated_logout(
     }
 
     // Kill the local SlateHub session cookie.
-    let secure = std::env::var("COOKIE_SECURE").unwrap_or_else(|_| "true".to_string()) != "false";
-    let cookie = Cookie::build(("auth_token", ""))
-        .path("/")
-        .same_site(SameSite::Lax)
-        .http_only(true)
-        .secure(secure)
-        .max_age(Default::default())
-        .build();
-    let jar = jar.remove(cookie);
+    let jar = jar.remove(crate::auth::cleared_session_cookie());
 
     let target = match validated_redirect {
         Some(uri) => {
@@ -71,6 +71,7 @@ pub struct OrganizationSettingsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub organization: Organization,
     pub oidc: Option<OidcView>,
     pub new_secret: Option<String>,
@@ -200,7 +201,7 @@ async fn settings_page(
         organization,
         oidc,
         new_secret: qs.get("new_secret").cloned(),
-        issuer: config::app_url(),
+        issuer: config::public_base_url(),
         sessions,
         scope_checkboxes,
         ssf_checkboxes,
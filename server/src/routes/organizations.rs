@@ -1,42 +1,56 @@
 //! Organization directory and per-org pages.
 //!
 //! Serves `/orgs` (browse with infinite-scroll SSE), `/my-orgs`, org
-//! create/edit/delete, member invites/roles/removal, and the join-request
-//! flow. Private orgs are hidden from non-members; member management
+//! create/edit/delete, member invites/roles/removal, the join-request
+//! flow, and a contact form that emails the org without revealing its
+//! address. Private orgs are hidden from non-members; member management
 //! requires an owner/admin role (deletion: owner only).
 
 use askama::Template;
 use axum::{
-    Router,
-    extract::{Path, Query, Request},
+    Extension, Router,
+    extract::{ConnectInfo, Path, Query, Request},
+    http::HeaderMap,
     response::{Html, IntoResponse, Json, Redirect, Response},
     routing::{get, post},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::{debug, error, info};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
 
 use crate::{
     datastar,
     error::Error,
     html::escape_html,
-    middleware::{AuthenticatedUser, UserExtractor},
+    middleware::{AuthenticatedUser, CurrentUser, UserExtractor},
+    models::membership::{MembershipModel, MembershipRole, Permission, PermissionSet},
     models::organization::{
-        CreateOrganizationData, Organization, OrganizationMember, OrganizationModel,
-        UpdateOrganizationData,
+        CreateOrganizationData, Organization, OrganizationMember, OrganizationModel, ServiceFacet,
+        UpdateOrganizationData, normalize_service,
     },
+    pagination::{Page, PageQuery},
+    rate_limit::RateLimiter,
     record_id_ext::RecordIdExt,
+    serde_utils::{deserialize_optional_i32, parse_form},
+    services::email::{EmailService, OrgInquiry},
     services::embedding::generate_embedding_async,
     services::search_log::log_search,
-    templates::{BaseContext, User},
+    templates::{BaseContext, FormValues, ShareMeta, User},
+    validation::ValidationErrors,
 };
 
 const PAGE_SIZE: usize = 20;
 
 /// Mounts the org pages: `/orgs` (list) and `/my-orgs`, `/orgs/new`,
 /// `/orgs/{slug}` profile/edit/delete, member and join-request management
-/// POSTs, plus the `/api/orgs/more-sse` infinite-scroll feed and
-/// `/api/organizations/check-slug`.
+/// POSTs, the `/orgs/{slug}/contact` form, plus the paged `/api/orgs` JSON
+/// listing, the `/api/orgs/more-sse` infinite-scroll feed, the
+/// `/api/organizations/{slug}` detail JSON, and the
+/// `/api/organizations/check-slug` and `suggest-slug` helpers.
 pub fn router() -> Router {
     Router::new()
         // Public organization routes
@@ -66,6 +80,10 @@ pub fn router() -> Router {
             post(remove_member),
         )
         .route("/orgs/{slug}/join-request", post(request_to_join))
+        .route(
+            "/orgs/{slug}/contact",
+            get(contact_form).post(send_contact_message),
+        )
         .route(
             "/orgs/{slug}/join-requests/{member_id}/accept",
             post(accept_join_request),
@@ -75,11 +93,14 @@ pub fn router() -> Router {
             post(reject_join_request),
         )
         // API endpoints
+        .route("/api/orgs", get(list_organizations_json))
         .route("/api/orgs/more-sse", get(orgs_more_sse))
         .route(
             "/api/organizations/check-slug",
             get(check_slug_availability),
         )
+        .route("/api/organizations/suggest-slug", get(suggest_slug))
+        .route("/api/organizations/{slug}", get(organization_json))
 }
 
 // ============================
@@ -90,12 +111,14 @@ pub fn router() -> Router {
 pub struct OrganizationMembership {
     pub organization: Organization,
     pub role: String,
-    pub joined_at: String,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateOrganizationForm {
     pub name: String,
+    /// Blank means "derive from the name" (see `OrganizationModel::suggest_slug`).
+    #[serde(default)]
     pub slug: String,
     pub org_type: String,
     pub description: Option<String>,
@@ -103,9 +126,10 @@ pub struct CreateOrganizationForm {
     pub website: Option<String>,
     pub contact_email: Option<String>,
     pub phone: Option<String>,
-    pub services: Option<String>,     // Comma-separated
-    pub founded_year: Option<String>, // Parse to i32 manually
-    pub public: Option<String>,       // Checkbox value "on" or None
+    pub services: Option<String>, // Comma-separated
+    #[serde(default, deserialize_with = "deserialize_optional_i32")]
+    pub founded_year: Option<i32>,
+    pub public: Option<String>, // Checkbox value "on" or None
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,9 +141,11 @@ pub struct UpdateOrganizationForm {
     pub website: Option<String>,
     pub contact_email: Option<String>,
     pub phone: Option<String>,
-    pub services: Option<String>,            // Comma-separated
-    pub founded_year: Option<String>,        // Parse to i32 manually
-    pub employees_count: Option<String>,     // Parse to i32 manually
+    pub services: Option<String>, // Comma-separated
+    #[serde(default, deserialize_with = "deserialize_optional_i32")]
+    pub founded_year: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_i32")]
+    pub employees_count: Option<i32>,
     pub public: Option<String>,              // Checkbox value "on" or None
     pub allow_join_requests: Option<String>, // Checkbox value "on" or None
 }
@@ -129,6 +155,7 @@ pub struct SearchQuery {
     pub q: Option<String>,
     pub org_type: Option<String>,
     pub location: Option<String>,
+    pub service: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -136,6 +163,11 @@ pub struct SlugCheckQuery {
     pub slug: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SlugSuggestQuery {
+    pub name: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct InviteMemberForm {
     pub username: String,
@@ -175,9 +207,12 @@ pub struct OrganizationsListTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub organizations: Vec<Organization>,
     pub search_query: Option<String>,
     pub org_types: Vec<OrgType>,
+    pub services: Vec<ServiceFacet>,
+    pub selected_service: Option<String>,
     pub has_more: bool,
 }
 
@@ -189,14 +224,48 @@ pub struct OrganizationProfileTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub organization: Organization,
     pub description_html: Option<String>,
     pub members: Vec<OrganizationMember>,
     pub join_requests: Vec<OrganizationMember>,
     pub is_member: bool,
-    pub is_admin: bool,
-    pub is_owner: bool,
+    /// What the viewer may do here; empty for non-members.
+    pub permissions: PermissionSet,
     pub has_pending_request: bool,
+    /// Social-share meta; `None` when the organization is private.
+    pub share: Option<ShareMeta>,
+}
+
+/// One row of the profile's "Manage Members" list, rendered alone to patch
+/// it after a Datastar role change.
+#[derive(Template)]
+#[template(path = "partials/org_member_admin_row.html")]
+pub struct OrgMemberAdminRowTemplate<'a> {
+    pub organization_slug: &'a str,
+    pub member: &'a OrganizationMember,
+    /// The acting member's permissions; pick which controls the row shows.
+    pub permissions: &'a PermissionSet,
+}
+
+#[derive(Template)]
+#[template(path = "organizations/contact.html")]
+pub struct OrganizationContactTemplate {
+    pub app_name: String,
+    pub year: i32,
+    pub version: String,
+    pub active_page: String,
+    pub user: Option<User>,
+    pub nonce: String,
+    pub organization: Organization,
+    pub errors: Option<Vec<String>>,
+    /// Submitted field values, kept when re-rendering after an error.
+    pub values: FormValues,
+    /// Show the "message sent" confirmation instead of the form.
+    pub sent: bool,
+    /// Anti-bot tokens for anonymous senders; empty when signed in.
+    pub form_token: String,
+    pub pow_challenge: String,
 }
 
 #[derive(Template)]
@@ -207,8 +276,11 @@ pub struct NewOrganizationTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub org_types: Vec<OrgType>,
-    pub error: Option<String>,
+    pub errors: Option<Vec<String>>,
+    /// Submitted field values, kept when re-rendering after an error.
+    pub values: FormValues,
 }
 
 #[derive(Template)]
@@ -219,9 +291,14 @@ pub struct EditOrganizationTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub organization: Organization,
     pub org_types: Vec<OrgType>,
-    pub error: Option<String>,
+    pub permissions: PermissionSet,
+    pub errors: Option<Vec<String>>,
+    /// Field values: the stored organization on first load, the submitted
+    /// body when re-rendering after an error.
+    pub values: FormValues,
 }
 
 #[derive(Template)]
@@ -232,6 +309,7 @@ pub struct MyOrganizationsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub organizations: Vec<OrganizationMembership>,
 }
 
@@ -248,8 +326,9 @@ async fn list_organizations(
 
     let mut base = BaseContext::new().with_page("organizations");
 
-    if let Some(user) = request.get_user() {
-        base = base.with_user(User::from_session_user(&user).await);
+    let viewer = request.get_user();
+    if let Some(user) = &viewer {
+        base = base.with_user(User::from_session_user(user).await);
     }
 
     // Use model to fetch organizations
@@ -265,7 +344,9 @@ async fn list_organizations(
             params.q.as_deref(),
             params.org_type.as_deref(),
             params.location.as_deref(),
+            params.service.as_deref(),
             query_embedding,
+            viewer.as_ref().map(|u| u.id.as_str()),
             PAGE_SIZE + 1,
             0,
         )
@@ -283,11 +364,15 @@ async fn list_organizations(
         .into_iter()
         .map(|(id, name)| OrgType { id, name })
         .collect();
+    let services = model.service_facets().await?;
+    let selected_service = params.service.as_deref().and_then(normalize_service);
 
     let template = crate::with_base!(OrganizationsListTemplate, base, {
         organizations,
         search_query: params.q,
         org_types,
+        services,
+        selected_service,
         has_more,
     });
 
@@ -356,21 +441,25 @@ async fn my_organizations(request: Request) -> Result<Html<String>, Error> {
 async fn new_organization_page(request: Request) -> Result<Html<String>, Error> {
     // Check if user is authenticated
     let user = request.get_user().ok_or(Error::Unauthorized)?;
+    render_new_organization(&user, None, FormValues::default()).await
+}
 
+/// Render the create form, optionally with errors and the values the user
+/// submitted.
+async fn render_new_organization(
+    user: &CurrentUser,
+    errors: Option<Vec<String>>,
+    values: FormValues,
+) -> Result<Html<String>, Error> {
     let mut base = BaseContext::new().with_page("new-organization");
-    base = base.with_user(User::from_session_user(&user).await);
+    base = base.with_user(User::from_session_user(user).await);
 
-    // Get organization types
-    let model = OrganizationModel::new();
-    let org_types_data = model.get_organization_types().await?;
-    let org_types: Vec<OrgType> = org_types_data
-        .into_iter()
-        .map(|(id, name)| OrgType { id, name })
-        .collect();
+    let org_types = org_types().await?;
 
     let template = crate::with_base!(NewOrganizationTemplate, base, {
         org_types,
-        error: None,
+        errors,
+        values,
     });
 
     Ok(Html(template.render().map_err(|e| {
@@ -379,11 +468,83 @@ async fn new_organization_page(request: Request) -> Result<Html<String>, Error>
     })?))
 }
 
+/// All organization types, for the create/edit type select.
+async fn org_types() -> Result<Vec<OrgType>, Error> {
+    let org_types_data = OrganizationModel::new().get_organization_types().await?;
+    Ok(org_types_data
+        .into_iter()
+        .map(|(id, name)| OrgType { id, name })
+        .collect())
+}
+
+const ORGANIZATION_NAME_MAX: usize = 120;
+const ORGANIZATION_DESCRIPTION_MAX: usize = 5000;
+
+/// Field checks shared by the create and edit forms, run on the raw
+/// submission so every problem is reported at once. The edit form has no
+/// slug field; on create a blank slug is generated from the name.
+fn validate_organization_form(values: &FormValues) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Organization name", values.get("name"))
+        .max_len(
+            "name",
+            "Organization name",
+            values.get("name"),
+            ORGANIZATION_NAME_MAX,
+        );
+    errors
+        .required("org_type", "Organization type", values.get("org_type"))
+        .max_len(
+            "description",
+            "Description",
+            values.get("description"),
+            ORGANIZATION_DESCRIPTION_MAX,
+        )
+        .email(
+            "contact_email",
+            "Contact email",
+            values.get("contact_email"),
+        )
+        .year("founded_year", "Founded year", values.get("founded_year"))
+        .integer_at_least(
+            "employees_count",
+            "Number of employees",
+            values.get("employees_count"),
+            0,
+        );
+    errors
+}
+
 #[axum::debug_handler]
 async fn create_organization(
     AuthenticatedUser(user): AuthenticatedUser,
-    axum::Form(data): axum::Form<CreateOrganizationForm>,
-) -> Result<Redirect, Error> {
+    body: String,
+) -> Result<Response, Error> {
+    let values = FormValues::from_body(&body);
+    let errors = match validate_organization_form(&values).into_result() {
+        Err(errors) => errors.into_messages(),
+        Ok(()) => match try_create_organization(&user, &body).await {
+            Ok(slug) => return Ok(Redirect::to(&format!("/orgs/{slug}")).into_response()),
+            // Checks only the model can make (slug taken, unknown type).
+            Err(e) => match e.form_message() {
+                Some(message) => vec![message.to_string()],
+                None => return Err(e),
+            },
+        },
+    };
+
+    // Show the form again with what the user typed.
+    Ok(render_new_organization(&user, Some(errors), values)
+        .await?
+        .into_response())
+}
+
+/// Parse and create; returns the new organization's slug.
+async fn try_create_organization(user: &CurrentUser, body: &str) -> Result<String, Error> {
+    // Surface bad numeric input ("abc" as a year) as a validation error.
+    let data: CreateOrganizationForm = parse_form(body)?;
+
     // Parse services from comma-separated string
     let services: Vec<String> = data
         .services
@@ -391,17 +552,18 @@ async fn create_organization(
         .map(|s| s.split(',').map(|s| s.trim().to_string()).collect())
         .unwrap_or_default();
 
-    // Parse founded_year from string to i32
-    let founded_year = data
-        .founded_year
-        .as_ref()
-        .filter(|s| !s.is_empty())
-        .and_then(|s| s.parse::<i32>().ok());
+    let model = OrganizationModel::new();
+    // A typed slug is normalized the same way as a generated one; its
+    // availability is checked by `create`.
+    let slug = match crate::util::slug::slugify(&data.slug) {
+        slug if slug.is_empty() => model.suggest_slug(&data.name).await?,
+        slug => slug,
+    };
 
     // Prepare data for model
     let create_data = CreateOrganizationData {
         name: data.name,
-        slug: data.slug.clone(),
+        slug: slug.clone(),
         org_type: data.org_type,
         description: data.description.filter(|s| !s.is_empty()),
         location: data.location.filter(|s| !s.is_empty()),
@@ -409,23 +571,22 @@ async fn create_organization(
         contact_email: data.contact_email.filter(|s| !s.is_empty()),
         phone: data.phone.filter(|s| !s.is_empty()),
         services,
-        founded_year,
+        founded_year: data.founded_year,
         employees_count: None,
         public: data.public.as_deref() == Some("on"),
     };
 
     // Use model to create organization
-    let model = OrganizationModel::new();
     let _org = model.create(create_data, &user.id).await?;
 
-    info!("Organization '{}' created by user {}", data.slug, user.id);
+    info!("Organization '{}' created by user {}", slug, user.id);
     crate::services::activity::log_activity(
         Some(&user.id),
         "organization_create",
-        &format!("/orgs/{}", data.slug),
+        &format!("/orgs/{}", slug),
     );
 
-    Ok(Redirect::to(&format!("/orgs/{}", data.slug)))
+    Ok(slug)
 }
 
 async fn organization_profile(
@@ -436,8 +597,7 @@ async fn organization_profile(
 
     let mut base = BaseContext::new().with_page("organization-profile");
     let mut is_member = false;
-    let mut is_admin = false;
-    let mut is_owner = false;
+    let mut permissions = PermissionSet::default();
     let mut has_pending_request = false;
 
     // Use model to get organization
@@ -469,8 +629,8 @@ async fn organization_profile(
             match existing.invitation_status.as_str() {
                 "accepted" => {
                     is_member = true;
-                    is_admin = existing.role == "admin" || existing.role == "owner";
-                    is_owner = existing.role == "owner";
+                    permissions =
+                        PermissionSet::for_membership(&existing.role, &existing.permissions);
                 }
                 "requested" | "pending" => {
                     has_pending_request = true;
@@ -490,16 +650,18 @@ async fn organization_profile(
     }
 
     // Get organization members using model
-    let members = model.get_members(&organization.id.to_raw_string()).await?;
+    let mut members = model.get_members(&organization.id.to_raw_string()).await?;
 
     // Get join requests for admins/owners
-    let join_requests = if is_admin || is_owner {
+    let mut join_requests = if permissions.contains(Permission::InviteMembers) {
         model
             .get_join_requests(&organization.id.to_raw_string())
             .await?
     } else {
         vec![]
     };
+    resolve_member_avatars(&mut members).await;
+    resolve_member_avatars(&mut join_requests).await;
 
     // Double-check pending request from members list (in case membership lookup missed it)
     if !has_pending_request
@@ -525,15 +687,31 @@ async fn organization_profile(
         .as_deref()
         .map(crate::markdown::render);
 
+    let share = organization.public.then(|| {
+        let description = organization.description.clone().unwrap_or_else(|| {
+            let mut text = format!("{} is a {}", organization.name, organization.org_type.name);
+            if let Some(location) = &organization.location {
+                text.push_str(&format!(" based in {}", location));
+            }
+            format!("{} on {}.", text, base.app_name)
+        });
+        ShareMeta::new(
+            format!("{} — {}", organization.name, organization.org_type.name),
+            &description,
+            &format!("/orgs/{}", organization.slug),
+        )
+        .with_image(organization.logo.as_deref())
+    });
+
     let template = crate::with_base!(OrganizationProfileTemplate, base, {
         organization,
         description_html,
         members,
         join_requests,
         is_member,
-        is_admin,
-        is_owner,
+        permissions,
         has_pending_request,
+        share,
     });
 
     Ok(Html(template.render().map_err(|e| {
@@ -549,30 +727,66 @@ async fn edit_organization_page(
     // Check if user is authenticated
     let user = request.get_user().ok_or(Error::Unauthorized)?;
 
-    let model = OrganizationModel::new();
-    let organization = model.get_by_slug(&slug).await?;
+    let organization = OrganizationModel::new().get_by_slug(&slug).await?;
+    let permissions =
+        require_permission(&user, &organization, Permission::UpdateOrganization).await?;
+    let values = organization_form_values(&organization);
+
+    render_edit_organization(&user, organization, permissions, None, values).await
+}
 
-    // Check if user has permission to edit
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
+/// The viewer's permissions on `organization`, or `Forbidden` unless they
+/// include `permission`. The same [`PermissionSet`] drives which controls
+/// the templates show, so the page and the handler agree on who may act.
+async fn require_permission(
+    user: &CurrentUser,
+    organization: &Organization,
+    permission: Permission,
+) -> Result<PermissionSet, Error> {
+    let permissions = MembershipModel::new()
+        .permissions_for(&user.id, &organization.id.to_raw_string())
         .await?;
-    if role != Some("owner".to_string()) && role != Some("admin".to_string()) {
+    if !permissions.contains(permission) {
         return Err(Error::Forbidden);
     }
+    Ok(permissions)
+}
+
+/// Seed the edit form from the stored organization.
+fn organization_form_values(organization: &Organization) -> FormValues {
+    FormValues::default()
+        .with("name", Some(&organization.name))
+        .with("org_type", Some(organization.org_type.id.to_raw_string()))
+        .with("description", organization.description.as_ref())
+        .with("location", organization.location.as_ref())
+        .with("website", organization.website.as_ref())
+        .with("contact_email", organization.contact_email.as_ref())
+        .with("phone", organization.phone.as_ref())
+        .with("services", Some(organization.services.join(", ")))
+        .with("founded_year", organization.founded_year)
+        .with("employees_count", organization.employees_count)
+        .with_checked("public", organization.public)
+        .with_checked("allow_join_requests", organization.allow_join_requests)
+}
 
+async fn render_edit_organization(
+    user: &CurrentUser,
+    organization: Organization,
+    permissions: PermissionSet,
+    errors: Option<Vec<String>>,
+    values: FormValues,
+) -> Result<Html<String>, Error> {
     let mut base = BaseContext::new().with_page("edit-organization");
-    base = base.with_user(User::from_session_user(&user).await);
+    base = base.with_user(User::from_session_user(user).await);
 
-    let org_types_data = model.get_organization_types().await?;
-    let org_types: Vec<OrgType> = org_types_data
-        .into_iter()
-        .map(|(id, name)| OrgType { id, name })
-        .collect();
+    let org_types = org_types().await?;
 
     let template = crate::with_base!(EditOrganizationTemplate, base, {
         organization,
         org_types,
-        error: None,
+        permissions,
+        errors,
+        values,
     });
 
     Ok(Html(template.render().map_err(|e| {
@@ -585,18 +799,38 @@ async fn edit_organization_page(
 async fn update_organization(
     AuthenticatedUser(user): AuthenticatedUser,
     Path(slug): Path<String>,
-    axum::Form(data): axum::Form<UpdateOrganizationForm>,
-) -> Result<Redirect, Error> {
+    body: String,
+) -> Result<Response, Error> {
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
+    let permissions =
+        require_permission(&user, &organization, Permission::UpdateOrganization).await?;
+
+    let values = FormValues::from_body(&body);
+    let errors = match validate_organization_form(&values).into_result() {
+        Err(errors) => errors.into_messages(),
+        Ok(()) => match try_update_organization(&organization, &body).await {
+            Ok(()) => {
+                info!("Organization '{}' updated by user {}", slug, user.id);
+                return Ok(Redirect::to(&format!("/orgs/{}", slug)).into_response());
+            }
+            Err(e) => match e.form_message() {
+                Some(message) => vec![message.to_string()],
+                None => return Err(e),
+            },
+        },
+    };
 
-    // Check if user has permission to edit
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    if role != Some("owner".to_string()) && role != Some("admin".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    // Show the form again with what the user typed.
+    Ok(
+        render_edit_organization(&user, organization, permissions, Some(errors), values)
+            .await?
+            .into_response(),
+    )
+}
+
+async fn try_update_organization(organization: &Organization, body: &str) -> Result<(), Error> {
+    let data: UpdateOrganizationForm = parse_form(body)?;
 
     // Parse services from comma-separated string
     let services: Vec<String> = data
@@ -605,20 +839,6 @@ async fn update_organization(
         .map(|s| s.split(',').map(|s| s.trim().to_string()).collect())
         .unwrap_or_default();
 
-    // Parse founded_year from string to i32
-    let founded_year = data
-        .founded_year
-        .as_ref()
-        .filter(|s| !s.is_empty())
-        .and_then(|s| s.parse::<i32>().ok());
-
-    // Parse employees_count from string to i32
-    let employees_count = data
-        .employees_count
-        .as_ref()
-        .filter(|s| !s.is_empty())
-        .and_then(|s| s.parse::<i32>().ok());
-
     // Prepare update data
     let update_data = UpdateOrganizationData {
         name: data.name,
@@ -629,23 +849,26 @@ async fn update_organization(
         contact_email: data.contact_email.filter(|s| !s.is_empty()),
         phone: data.phone.filter(|s| !s.is_empty()),
         services,
-        founded_year,
-        employees_count,
+        founded_year: data.founded_year,
+        employees_count: data.employees_count,
         public: data.public.as_deref() == Some("on"),
         allow_join_requests: data.allow_join_requests.as_deref() == Some("on"),
     };
 
     // Use model to update
-    model
+    OrganizationModel::new()
         .update(&organization.id.to_raw_string(), update_data)
         .await?;
-
-    info!("Organization '{}' updated by user {}", slug, user.id);
-
-    Ok(Redirect::to(&format!("/orgs/{}", slug)))
+    Ok(())
 }
 
+/// Developer diagnostic listing the seeded organization types. Only served
+/// when `DEBUG_ROUTES=true` ([`crate::config::debug_routes_enabled`]);
+/// otherwise it 404s like any unknown page.
 async fn test_organization_types() -> Result<Html<String>, Error> {
+    if !crate::config::debug_routes_enabled() {
+        return Err(Error::NotFound);
+    }
     debug!("Test endpoint: fetching organization types");
 
     let model = OrganizationModel::new();
@@ -704,13 +927,7 @@ async fn delete_organization(
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
 
-    // Check if user is owner
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    if role != Some("owner".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    require_permission(&user, &organization, Permission::DeleteOrganization).await?;
 
     // Use model to delete
     model.delete(&organization.id.to_raw_string()).await?;
@@ -720,6 +937,23 @@ async fn delete_organization(
     Ok(Redirect::to("/orgs"))
 }
 
+/// Fill in each member's avatar from one batched person lookup
+/// ([`User::batch_from_profiles`]) instead of leaving the template to fall
+/// back per row.
+async fn resolve_member_avatars(members: &mut [OrganizationMember]) {
+    let ids: Vec<String> = members
+        .iter()
+        .map(|m| m.person_id.to_raw_string())
+        .collect();
+    let users = User::batch_from_profiles(&ids).await;
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    for member in members {
+        if let Some(user) = by_id.get(member.person_id.to_raw_string().as_str()) {
+            member.person_avatar = Some(user.avatar.clone());
+        }
+    }
+}
+
 async fn list_members(
     Path(slug): Path<String>,
     request: Request,
@@ -743,13 +977,7 @@ async fn invite_member(
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
 
-    // Check if user has permission to invite
-    let role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    if role != Some("owner".to_string()) && role != Some("admin".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    require_permission(&user, &organization, Permission::InviteMembers).await?;
 
     let org_id = organization.id.to_raw_string();
     let inviter_name = user.name.clone();
@@ -793,41 +1021,63 @@ async fn invite_member(
     Ok(Redirect::to(&format!("/orgs/{slug}")))
 }
 
+/// Change a member's role. A Datastar submit gets the member's updated
+/// "Manage Members" row back (or its removal, on promotion to owner); a
+/// plain form post redirects to the profile.
 #[axum::debug_handler]
 async fn update_member_role(
     AuthenticatedUser(user): AuthenticatedUser,
     Path((slug, member_id)): Path<(String, String)>,
+    headers: HeaderMap,
     axum::Form(data): axum::Form<UpdateRoleForm>,
-) -> Result<Redirect, Error> {
+) -> Result<Response, Error> {
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
     let org_id = organization.id.to_raw_string();
 
-    // Check if user is owner
-    let role = model.get_member_role(&org_id, &user.id).await?;
-    if role != Some("owner".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    let permissions =
+        require_permission(&user, &organization, Permission::UpdateMemberRoles).await?;
 
     // Verify the member belongs to this organization
     let members = model.get_members(&org_id).await?;
-    let member_belongs = members.iter().any(|m| m.id.to_raw_string() == member_id);
-    if !member_belongs {
+    let Some(mut member) = members
+        .into_iter()
+        .find(|m| m.id.to_raw_string() == member_id)
+    else {
         return Err(Error::BadRequest(
             "Member does not belong to this organization".to_string(),
         ));
-    }
+    };
 
     // Update member role
     model.update_member_role(&member_id, &data.role).await?;
 
-    Ok(Redirect::to(&format!("/orgs/{}", slug)))
+    if !datastar::is_datastar_request(&headers) {
+        return Ok(Redirect::to(&format!("/orgs/{}", slug)).into_response());
+    }
+    member.role = MembershipRole::from_str(&data.role)?.as_str().to_string();
+    let selector = format!("#org-member-{}", member.id.key_string());
+    let patch = if member.role == "owner" {
+        // Owners aren't listed under "Manage Members"
+        datastar::patch_elements(&selector, "remove", "")
+    } else {
+        let row = OrgMemberAdminRowTemplate {
+            organization_slug: &slug,
+            member: &member,
+            permissions: &permissions,
+        }
+        .render()?;
+        datastar::patch_elements(&selector, "outer", &row)
+    };
+    Ok(datastar::response(patch))
 }
 
+/// Remove a member. A Datastar submit gets a patch dropping the member's
+/// "Manage Members" row; a plain form post redirects to the profile.
 async fn remove_member(
     Path((slug, member_id)): Path<(String, String)>,
     request: Request,
-) -> Result<Redirect, Error> {
+) -> Result<Response, Error> {
     // Check if user is authenticated
     let user = request.get_user().ok_or(Error::Unauthorized)?;
 
@@ -835,37 +1085,64 @@ async fn remove_member(
     let organization = model.get_by_slug(&slug).await?;
     let org_id = organization.id.to_raw_string();
 
-    // Check if user is owner
-    let role = model.get_member_role(&org_id, &user.id).await?;
-    if role != Some("owner".to_string()) {
-        return Err(Error::Forbidden);
-    }
+    let permissions = require_permission(&user, &organization, Permission::RemoveMembers).await?;
 
     // Verify the member belongs to this organization
     let members = model.get_members(&org_id).await?;
-    let member_belongs = members.iter().any(|m| m.id.to_raw_string() == member_id);
-    if !member_belongs {
+    let Some(member) = members.iter().find(|m| m.id.to_raw_string() == member_id) else {
         return Err(Error::BadRequest(
             "Member does not belong to this organization".to_string(),
         ));
+    };
+    // Removing an owner takes the same standing as changing their role
+    if member.role == "owner" && !permissions.contains(Permission::UpdateMemberRoles) {
+        return Err(Error::Forbidden);
     }
 
     // Remove member
     model.remove_member(&member_id).await?;
 
-    Ok(Redirect::to(&format!("/orgs/{}", slug)))
+    if !datastar::is_datastar_request(request.headers()) {
+        return Ok(Redirect::to(&format!("/orgs/{}", slug)).into_response());
+    }
+    let selector = format!("#org-member-{}", member.id.key_string());
+    Ok(datastar::response(datastar::patch_elements(
+        &selector, "remove", "",
+    )))
 }
 
+/// Per-IP join-request limiter, so throwaway accounts from one client
+/// can't spam every organization's request queue.
+static JOIN_REQUEST_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(20, Duration::from_secs(3600)));
+
 #[derive(Debug, Deserialize)]
 struct JoinRequestForm {
     note: Option<String>,
+    #[serde(alias = "h-captcha-response", alias = "cf-turnstile-response")]
+    captcha_token: Option<String>,
 }
 
 async fn request_to_join(
     AuthenticatedUser(user): AuthenticatedUser,
     Path(slug): Path<String>,
+    peer: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
     axum::Form(form): axum::Form<JoinRequestForm>,
 ) -> Result<Response, Error> {
+    let peer = peer.map_or(SocketAddr::from(([0, 0, 0, 0], 0)), |p| p.0.0);
+    let ip = super::auth::client_ip(&headers, peer);
+    JOIN_REQUEST_LIMITER.hit(
+        &ip,
+        "Too many join requests from your network. Please try again later.",
+    )?;
+    crate::captcha::verify(
+        crate::captcha::CaptchaAction::JoinRequest,
+        form.captcha_token.as_deref(),
+        &ip,
+    )
+    .await?;
+
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
 
@@ -947,6 +1224,221 @@ async fn request_to_join(
     Ok(Redirect::to(&format!("/orgs/{}", slug)).into_response())
 }
 
+const CONTACT_MESSAGE_MAX: usize = 5000;
+
+/// Per-sender contact-form limiter (signed-in user id, else client IP), so
+/// the form can't be used to flood an organization's inbox.
+static CONTACT_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(5, Duration::from_secs(3600)));
+
+#[derive(Debug, Deserialize)]
+struct ContactQuery {
+    #[serde(default)]
+    sent: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContactForm {
+    /// Ignored for signed-in senders, who send as their account.
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    email: String,
+    message: String,
+    /// Honeypot, form token and proof-of-work, as on `/signup`; only
+    /// checked for anonymous senders.
+    website: Option<String>,
+    form_token: Option<String>,
+    pow_solution: Option<String>,
+    #[serde(alias = "h-captcha-response", alias = "cf-turnstile-response")]
+    captcha_token: Option<String>,
+}
+
+/// The organization behind a contact form: public and with a contact
+/// address, otherwise `NotFound` so private orgs aren't confirmed to exist.
+async fn contactable_organization(slug: &str) -> Result<Organization, Error> {
+    let organization = OrganizationModel::new().get_by_slug(slug).await?;
+    if !organization.public
+        || organization
+            .contact_email
+            .as_deref()
+            .is_none_or(|e| e.trim().is_empty())
+    {
+        return Err(Error::NotFound);
+    }
+    Ok(organization)
+}
+
+async fn contact_form(
+    Path(slug): Path<String>,
+    Query(query): Query<ContactQuery>,
+    request: Request,
+) -> Result<Html<String>, Error> {
+    let organization = contactable_organization(&slug).await?;
+    render_contact_form(
+        organization,
+        request.get_user().as_deref(),
+        None,
+        FormValues::default(),
+        query.sent,
+    )
+    .await
+}
+
+/// Render the contact page. Anonymous visitors get fresh anti-bot tokens
+/// on every render, error re-renders included.
+async fn render_contact_form(
+    organization: Organization,
+    user: Option<&CurrentUser>,
+    errors: Option<Vec<String>>,
+    values: FormValues,
+    sent: bool,
+) -> Result<Html<String>, Error> {
+    let mut base = BaseContext::new().with_page("organization-contact");
+    let (form_token, pow_challenge) = match user {
+        Some(user) => {
+            base = base.with_user(User::from_session_user(user).await);
+            (String::new(), String::new())
+        }
+        None => (
+            super::auth::generate_form_token(),
+            super::auth::generate_pow_challenge(),
+        ),
+    };
+
+    let template = crate::with_base!(OrganizationContactTemplate, base, {
+        organization,
+        errors,
+        values,
+        sent,
+        form_token,
+        pow_challenge,
+    });
+
+    Ok(Html(template.render().map_err(|e| {
+        error!("Failed to render organization contact template: {}", e);
+        Error::template(e.to_string())
+    })?))
+}
+
+/// Email the organization's contact address on the sender's behalf. The
+/// address is never shown; the organization sees the sender's address as
+/// `Reply-To`. Signed-in users send as their account; anonymous senders
+/// give a name and email and pass signup's anti-bot checks.
+async fn send_contact_message(
+    Path(slug): Path<String>,
+    user: Option<Extension<Arc<CurrentUser>>>,
+    peer: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, Error> {
+    let organization = contactable_organization(&slug).await?;
+    let user = user.map(|Extension(user)| user);
+    let values = FormValues::from_body(&body);
+    let form: ContactForm = parse_form(&body)?;
+
+    let peer = peer.map_or(SocketAddr::from(([0, 0, 0, 0], 0)), |p| p.0.0);
+    let ip = super::auth::client_ip(&headers, peer);
+    let sender_key = match &user {
+        Some(user) => format!("person:{}", user.id),
+        None => format!("ip:{ip}"),
+    };
+
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("message", "Message", &form.message)
+        .max_len("message", "Message", &form.message, CONTACT_MESSAGE_MAX);
+    if user.is_none() {
+        errors
+            .required("name", "Name", &form.name)
+            .max_len("name", "Name", &form.name, 200)
+            .required("email", "Email", &form.email)
+            .email("email", "Email", &form.email);
+    }
+    if let Err(errors) = errors.into_result() {
+        return Ok(render_contact_form(
+            organization,
+            user.as_deref(),
+            Some(errors.into_messages()),
+            values,
+            false,
+        )
+        .await?
+        .into_response());
+    }
+
+    if user.is_none() {
+        let honeypot = form.website.as_deref().is_some_and(|w| !w.is_empty());
+        let token_ok = form
+            .form_token
+            .as_deref()
+            .is_some_and(super::auth::validate_form_token);
+        let pow_ok = form
+            .pow_solution
+            .as_deref()
+            .is_some_and(|s| !s.is_empty() && super::auth::validate_pow_solution(s));
+        if honeypot || !token_ok || !pow_ok {
+            warn!(sender = %sender_key, org = %slug, honeypot, token_ok, pow_ok, "contact message blocked");
+            return Err(Error::Validation(
+                "We couldn't verify your message. Please reload the page and try again."
+                    .to_string(),
+            ));
+        }
+        crate::captcha::verify(
+            crate::captcha::CaptchaAction::Contact,
+            form.captcha_token.as_deref(),
+            &ip,
+        )
+        .await?;
+    }
+
+    CONTACT_LIMITER.hit(
+        &sender_key,
+        "You've sent several messages recently. Please try again later.",
+    )?;
+
+    let (sender_name, sender_email) = match &user {
+        Some(user) => (
+            if user.name.is_empty() {
+                user.username.clone()
+            } else {
+                user.name.clone()
+            },
+            user.email.clone(),
+        ),
+        None => (form.name.trim().to_string(), form.email.trim().to_string()),
+    };
+    let to = organization.contact_email.as_deref().unwrap_or_default();
+    let profile_url = format!("{}/orgs/{}", crate::config::public_base_url(), slug);
+
+    let email_service = EmailService::from_env().map_err(|e| {
+        error!(
+            "Email service not configured, can't send contact message: {}",
+            e
+        );
+        Error::ExternalService("Messages can't be sent right now".to_string())
+    })?;
+    email_service
+        .send_org_contact_email(
+            to,
+            &OrgInquiry {
+                org_name: &organization.name,
+                sender_name: &sender_name,
+                sender_email: &sender_email,
+                message: &form.message,
+                profile_url: &profile_url,
+            },
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to send contact message to org {}: {}", slug, e);
+            Error::ExternalService("Your message couldn't be sent".to_string())
+        })?;
+
+    info!(sender = %sender_key, org = %slug, "contact message sent");
+    Ok(Redirect::to(&format!("/orgs/{slug}/contact?sent=true")).into_response())
+}
+
 async fn accept_join_request(
     AuthenticatedUser(user): AuthenticatedUser,
     Path((slug, member_id)): Path<(String, String)>,
@@ -954,14 +1446,8 @@ async fn accept_join_request(
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
 
-    // Verify user is owner/admin
-    let user_role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    match user_role.as_deref() {
-        Some("owner") | Some("admin") => {}
-        _ => return Err(Error::Forbidden),
-    }
+    // Join requests are listed to whoever may invite members
+    require_permission(&user, &organization, Permission::InviteMembers).await?;
 
     model.accept_join_request(&member_id).await?;
 
@@ -989,14 +1475,8 @@ async fn reject_join_request(
     let model = OrganizationModel::new();
     let organization = model.get_by_slug(&slug).await?;
 
-    // Verify user is owner/admin
-    let user_role = model
-        .get_member_role(&organization.id.to_raw_string(), &user.id)
-        .await?;
-    match user_role.as_deref() {
-        Some("owner") | Some("admin") => {}
-        _ => return Err(Error::Forbidden),
-    }
+    // Join requests are listed to whoever may invite members
+    require_permission(&user, &organization, Permission::InviteMembers).await?;
 
     // Clean up join request notifications before deleting membership
     let membership_model = crate::models::membership::MembershipModel::new();
@@ -1021,6 +1501,7 @@ async fn reject_join_request(
 struct MoreQuery {
     offset: usize,
     q: Option<String>,
+    service: Option<String>,
 }
 
 const VERIFIED_BADGE_PATH: &str = "M22.5 12.5c0-1.58-.875-2.95-2.148-3.6.154-.435.238-.905.238-1.4 0-2.21-1.71-3.998-3.818-3.998-.47 0-.92.084-1.336.25C14.818 2.415 13.51 1.5 12 1.5s-2.816.917-3.437 2.25c-.415-.165-.866-.25-1.336-.25-2.11 0-3.818 1.79-3.818 4 0 .494.083.964.237 1.4-1.272.65-2.147 2.018-2.147 3.6 0 1.495.782 2.798 1.942 3.486-.02.17-.032.34-.032.514 0 2.21 1.708 4 3.818 4 .47 0 .92-.086 1.335-.25.62 1.334 1.926 2.25 3.437 2.25 1.512 0 2.818-.916 3.437-2.25.415.163.865.248 1.336.248 2.11 0 3.818-1.79 3.818-4 0-.174-.012-.344-.033-.513 1.158-.687 1.943-1.99 1.943-3.484zm-6.616-3.334l-4.334 6.5c-.145.217-.382.334-.625.334-.143 0-.288-.04-.416-.126l-.115-.094-2.415-2.415c-.293-.293-.293-.768 0-1.06s.768-.294 1.06 0l1.77 1.767 3.825-5.74c.23-.345.696-.436 1.04-.207.346.23.44.696.21 1.04z";
@@ -1035,7 +1516,7 @@ fn render_org_card(org: &Organization) -> String {
 
     if let Some(ref logo) = org.logo {
         html.push_str(&format!(
-            r#"<img src="{}" alt="{}" loading="lazy" onerror="this.style.display='none'" />"#,
+            r#"<img src="{}" alt="{}" loading="lazy" data-handler-error="this.style.display='none'" />"#,
             escape_html(logo),
             escape_html(&org.name)
         ));
@@ -1081,8 +1562,12 @@ fn render_org_card(org: &Organization) -> String {
     html
 }
 
-async fn orgs_more_sse(Query(params): Query<MoreQuery>) -> Response {
+async fn orgs_more_sse(
+    Query(params): Query<MoreQuery>,
+    viewer: Option<Extension<Arc<CurrentUser>>>,
+) -> Response {
     let search = params.q.as_deref().filter(|s| !s.is_empty());
+    let service = params.service.as_deref().filter(|s| !s.is_empty());
     let offset = params.offset;
 
     let query_embedding = if let Some(s) = search {
@@ -1093,7 +1578,16 @@ async fn orgs_more_sse(Query(params): Query<MoreQuery>) -> Response {
 
     let model = OrganizationModel::new();
     let all = model
-        .search(search, None, None, query_embedding, PAGE_SIZE + 1, offset)
+        .search(
+            search,
+            None,
+            None,
+            service,
+            query_embedding,
+            viewer.as_ref().map(|Extension(u)| u.id.as_str()),
+            PAGE_SIZE + 1,
+            offset,
+        )
         .await
         .unwrap_or_default();
     let has_more = all.len() > PAGE_SIZE;
@@ -1110,10 +1604,13 @@ async fn orgs_more_sse(Query(params): Query<MoreQuery>) -> Response {
 
     if has_more {
         let new_offset = offset + PAGE_SIZE;
-        let q_param = match search {
+        let mut q_param = match search {
             Some(q) => format!("&q={}", urlencoding::encode(q)),
             None => String::new(),
         };
+        if let Some(service) = service {
+            q_param.push_str(&format!("&service={}", urlencoding::encode(service)));
+        }
         replacement.push_str(&format!(
             r#"<div id="orgs-sentinel" data-on-intersect="@get('/api/orgs/more-sse?offset={}{}')"><div class="orgs-loading">Loading more...</div></div>"#,
             new_offset, q_param
@@ -1138,3 +1635,93 @@ async fn check_slug_availability(
         "reason": reason
     })))
 }
+
+/// Public organizations as JSON, `?page=&per_page=` paged.
+async fn list_organizations_json(page: PageQuery) -> Result<Json<Page<serde_json::Value>>, Error> {
+    let organizations = OrganizationModel::new().list_public_page(page).await?;
+    Ok(Json(organizations.map(|org| {
+        json!({
+            "slug": org.slug,
+            "name": org.name,
+            "type": org.org_type.name,
+            "description": org.description,
+            "location": org.location,
+            "logo": org.logo,
+            "verified": org.verified,
+        })
+    })))
+}
+
+/// One organization as JSON, with the same visibility as its profile page:
+/// a private org is `403` unless the viewer is an accepted member. Members
+/// also get the accepted member list and the contact address, which the
+/// public profile no longer shows.
+async fn organization_json(
+    Path(slug): Path<String>,
+    viewer: Option<Extension<Arc<CurrentUser>>>,
+) -> Result<Json<serde_json::Value>, Error> {
+    let model = OrganizationModel::new();
+    let organization = model.get_by_slug(&slug).await?;
+    let org_id = organization.id.to_raw_string();
+
+    let is_member = match &viewer {
+        Some(Extension(user)) => MembershipModel::new()
+            .find_by_person_and_org(&user.id, &org_id)
+            .await?
+            .is_some_and(|m| m.invitation_status == "accepted"),
+        None => false,
+    };
+    if !organization.public && !is_member {
+        return Err(Error::Forbidden);
+    }
+
+    let mut body = json!({
+        "slug": organization.slug,
+        "name": organization.name,
+        "type": organization.org_type.name,
+        "description": organization.description,
+        "location": organization.location,
+        "website": organization.website,
+        "logo": organization.logo,
+        "phone": organization.phone,
+        "social_links": organization.social_links,
+        "services": organization.services,
+        "founded_year": organization.founded_year,
+        "employees_count": organization.employees_count,
+        "public": organization.public,
+        "verified": organization.verified,
+        "allow_join_requests": organization.allow_join_requests,
+        "created_at": organization.created_at,
+        "updated_at": organization.updated_at,
+    });
+
+    if is_member {
+        let members: Vec<serde_json::Value> = model
+            .get_members(&org_id)
+            .await?
+            .into_iter()
+            .filter(|m| m.invitation_status == "accepted")
+            .map(|m| {
+                json!({
+                    "username": m.person_username,
+                    "name": m.person_name,
+                    "avatar": m.person_avatar,
+                    "role": m.role,
+                    "joined_at": m.joined_at,
+                })
+            })
+            .collect();
+        body["contact_email"] = json!(organization.contact_email);
+        body["members"] = json!(members);
+    }
+
+    Ok(Json(body))
+}
+
+/// Available slug derived from `name`, for prefilling the create form.
+async fn suggest_slug(
+    Query(params): Query<SlugSuggestQuery>,
+) -> Result<Json<serde_json::Value>, Error> {
+    let slug = OrganizationModel::new().suggest_slug(&params.name).await?;
+    Ok(Json(json!({ "slug": slug })))
+}
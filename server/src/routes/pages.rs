@@ -1,13 +1,14 @@
 //! Public site pages and crawler plumbing: home, about, terms, privacy,
 //! and impressum, plus `/healthcheck`, `robots.txt`, `llms.txt`,
-//! `sitemap.xml`, the favicon redirect, and the Datastar SSE feed that
-//! rotates verified-profile tiles in the homepage hero ticker.
+//! `sitemap.xml` (and its paged `/sitemaps/{file}`), the favicon redirect,
+//! and the Datastar SSE feed that rotates verified-profile tiles in the
+//! homepage hero ticker.
 
 use askama::Template;
 use axum::http::{HeaderValue, header};
 use axum::{
     Router,
-    extract::Request,
+    extract::{Path, Request},
     response::{Html, IntoResponse, Redirect, Response},
     routing::get,
 };
@@ -19,6 +20,7 @@ use crate::{
     error::Error,
     html::escape_attr,
     middleware::UserExtractor,
+    services::sitemap::{self, Section},
     templates::{
         AboutTemplate, Activity, BaseContext, ImpressumTemplate, IndexTemplate, PrivacyTemplate,
         TermsTemplate, User,
@@ -38,6 +40,7 @@ pub fn router() -> Router {
         .route("/robots.txt", get(robots_txt))
         .route("/llms.txt", get(llms_txt))
         .route("/sitemap.xml", get(sitemap_xml))
+        .route("/sitemaps/{file}", get(sitemap_page))
         .route("/favicon.ico", get(favicon))
         .route("/api/homepage/profiles-sse", get(profiles_ticker_sse))
 }
@@ -61,31 +64,32 @@ async fn index(request: Request) -> Result<Html<String>, Error> {
     template.connection_count = 18453;
 
     // Add sample activities (in production, fetch from database)
+    let now = chrono::Utc::now();
     template.activities = vec![
         Activity {
             user: "Sarah Johnson".to_string(),
             action: "created a new production".to_string(),
-            time: "2 minutes ago".to_string(),
+            at: now - chrono::Duration::minutes(2),
         },
         Activity {
             user: "Mike Chen".to_string(),
             action: "joined the platform".to_string(),
-            time: "15 minutes ago".to_string(),
+            at: now - chrono::Duration::minutes(15),
         },
         Activity {
             user: "Emily Rodriguez".to_string(),
             action: "posted a job opening".to_string(),
-            time: "1 hour ago".to_string(),
+            at: now - chrono::Duration::hours(1),
         },
         Activity {
             user: "David Kim".to_string(),
             action: "completed a collaboration".to_string(),
-            time: "3 hours ago".to_string(),
+            at: now - chrono::Duration::hours(3),
         },
         Activity {
             user: "Lisa Thompson".to_string(),
             action: "updated their portfolio".to_string(),
-            time: "5 hours ago".to_string(),
+            at: now - chrono::Duration::hours(5),
         },
     ];
 
@@ -326,7 +330,7 @@ fn render_ticker_tile(row: &serde_json::Value, slot: usize) -> String {
 }
 
 async fn robots_txt() -> Response {
-    let base = crate::config::app_url();
+    let base = crate::config::public_base_url();
     let body = format!(
         "\
 User-agent: *
@@ -356,7 +360,7 @@ Sitemap: {base}/sitemap.xml
 }
 
 async fn llms_txt() -> Response {
-    let base = crate::config::app_url();
+    let base = crate::config::public_base_url();
     let mut body = format!(
         "\
 # SlateHub
@@ -398,10 +402,10 @@ SlateHub is a free, open-source creative networking platform — the professiona
     // Dynamic entries
     if let Ok(mut result) = DB
         .query(
-            "SELECT username, profile.name AS name FROM person WHERE verification_status != 'unverified' ORDER BY username ASC;
+            "SELECT username, profile.name AS name FROM person WHERE verification_status != 'unverified' AND profile.is_private != true ORDER BY username ASC;
              SELECT slug, title FROM production ORDER BY slug ASC;
-             SELECT slug, name FROM organization ORDER BY slug ASC;
-             SELECT <string> meta::id(id) AS key, name FROM location ORDER BY name ASC;
+             SELECT slug, name FROM organization WHERE (public ?? false) = true ORDER BY slug ASC;
+             SELECT <string> meta::id(id) AS key, name FROM location WHERE (is_public ?? false) = true ORDER BY name ASC;
              SELECT <string> meta::id(id) AS key, title FROM job_posting WHERE status = 'open' ORDER BY title ASC;"
         )
         .await
@@ -472,105 +476,48 @@ SlateHub is a free, open-source creative networking platform — the professiona
         .into_response()
 }
 
-async fn sitemap_xml() -> Response {
-    let base = crate::config::app_url();
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-
-    let mut urls = Vec::new();
-
-    // Static pages
-    let static_pages = [
-        ("/", "1.0", "weekly"),
-        ("/about", "0.8", "monthly"),
-        ("/search", "0.9", "daily"),
-        ("/people", "0.9", "daily"),
-        ("/productions", "0.9", "daily"),
-        ("/orgs", "0.8", "daily"),
-        ("/locations", "0.8", "daily"),
-        ("/jobs", "0.9", "daily"),
-        ("/terms", "0.3", "yearly"),
-        ("/privacy", "0.3", "yearly"),
-        ("/impressum", "0.3", "yearly"),
-    ];
+/// `/sitemap.xml`: one `<urlset>` while everything fits in a single file,
+/// otherwise a `<sitemapindex>` of per-section pages (see
+/// [`crate::services::sitemap`]).
+async fn sitemap_xml() -> Result<Response, Error> {
+    let base = crate::config::public_base_url();
 
-    for (path, priority, changefreq) in static_pages {
-        urls.push(format!(
-            "  <url>\n    <loc>{base}{path}</loc>\n    <lastmod>{today}</lastmod>\n    <changefreq>{changefreq}</changefreq>\n    <priority>{priority}</priority>\n  </url>"
-        ));
+    let mut counts = Vec::with_capacity(Section::ALL.len());
+    for section in Section::ALL {
+        counts.push((section, sitemap::section_count(section).await?));
     }
+    let total: usize = counts.iter().map(|(_, n)| n).sum();
 
-    // Dynamic entries — single query for all entity types
-    if let Ok(mut result) = DB
-        .query(
-            "SELECT username FROM person WHERE verification_status != 'unverified' ORDER BY username ASC;
-             SELECT slug FROM production ORDER BY slug ASC;
-             SELECT slug FROM organization ORDER BY slug ASC;
-             SELECT <string> meta::id(id) AS key FROM location ORDER BY key ASC;
-             SELECT <string> meta::id(id) AS key FROM job_posting ORDER BY key ASC;"
-        )
-        .await
-    {
-        // Profiles: /{username}
-        if let Ok(rows) = result.take::<Vec<serde_json::Value>>(0) {
-            for row in rows {
-                if let Some(v) = row.get("username").and_then(|v| v.as_str()) {
-                    urls.push(format!(
-                        "  <url>\n    <loc>{base}/{v}</loc>\n    <changefreq>weekly</changefreq>\n    <priority>0.7</priority>\n  </url>"
-                    ));
-                }
-            }
-        }
-
-        // Productions: /productions/{slug}
-        if let Ok(rows) = result.take::<Vec<serde_json::Value>>(1) {
-            for row in rows {
-                if let Some(v) = row.get("slug").and_then(|v| v.as_str()) {
-                    urls.push(format!(
-                        "  <url>\n    <loc>{base}/productions/{v}</loc>\n    <changefreq>weekly</changefreq>\n    <priority>0.6</priority>\n  </url>"
-                    ));
-                }
-            }
-        }
-
-        // Organizations: /orgs/{slug}
-        if let Ok(rows) = result.take::<Vec<serde_json::Value>>(2) {
-            for row in rows {
-                if let Some(v) = row.get("slug").and_then(|v| v.as_str()) {
-                    urls.push(format!(
-                        "  <url>\n    <loc>{base}/orgs/{v}</loc>\n    <changefreq>weekly</changefreq>\n    <priority>0.6</priority>\n  </url>"
-                    ));
-                }
-            }
+    let xml = if total > sitemap::MAX_URLS_PER_SITEMAP {
+        sitemap::render_index(&base, &counts)
+    } else {
+        let mut urls = Vec::with_capacity(total);
+        for section in Section::ALL {
+            urls.extend(sitemap::section_urls(section, 1).await?);
         }
+        sitemap::render_urlset(&base, &urls)
+    };
 
-        // Locations: /locations/{key}
-        if let Ok(rows) = result.take::<Vec<serde_json::Value>>(3) {
-            for row in rows {
-                if let Some(v) = row.get("key").and_then(|v| v.as_str()) {
-                    urls.push(format!(
-                        "  <url>\n    <loc>{base}/locations/{v}</loc>\n    <changefreq>weekly</changefreq>\n    <priority>0.5</priority>\n  </url>"
-                    ));
-                }
-            }
-        }
+    Ok(xml_response(xml))
+}
 
-        // Jobs: /jobs/{key}
-        if let Ok(rows) = result.take::<Vec<serde_json::Value>>(4) {
-            for row in rows {
-                if let Some(v) = row.get("key").and_then(|v| v.as_str()) {
-                    urls.push(format!(
-                        "  <url>\n    <loc>{base}/jobs/{v}</loc>\n    <changefreq>daily</changefreq>\n    <priority>0.6</priority>\n  </url>"
-                    ));
-                }
-            }
-        }
+/// `/sitemaps/{section}-{page}.xml`: one page of a section, linked from the
+/// sitemap index.
+async fn sitemap_page(Path(file): Path<String>) -> Result<Response, Error> {
+    let (section, page) = sitemap::parse_file_name(&file).ok_or(Error::NotFound)?;
+    let count = sitemap::section_count(section).await?;
+    if page > sitemap::page_count(count) {
+        return Err(Error::NotFound);
     }
 
-    let xml = format!(
-        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n{}\n</urlset>\n",
-        urls.join("\n")
-    );
+    let urls = sitemap::section_urls(section, page).await?;
+    Ok(xml_response(sitemap::render_urlset(
+        &crate::config::public_base_url(),
+        &urls,
+    )))
+}
 
+fn xml_response(xml: String) -> Response {
     (
         [(
             header::CONTENT_TYPE,
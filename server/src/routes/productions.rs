@@ -1,38 +1,48 @@
 //! Public production catalog and per-production CRUD pages.
 //!
 //! Serves `/productions` (browse with infinite-scroll SSE), `/my-productions`,
-//! production create/edit/delete, member and invite management, and script
-//! upload/visibility/delete (script actions redirect into the management
-//! workspace). Mutating routes are gated on `ProductionModel::can_edit`.
+//! production create/edit/delete, member and invite management, skill-based
+//! crew recommendations, members-only crew list exports (CSV/PDF), and
+//! script upload/visibility/delete (script actions redirect into the
+//! management workspace). Mutating routes are gated on
+//! `ProductionModel::can_edit`.
 
+use super::comments::COMMENTS_PAGE_SIZE;
 use crate::datastar;
 use crate::error::Error;
 use crate::html::escape_html;
-use crate::middleware::{AuthenticatedUser, UserExtractor};
+use crate::middleware::{AuthenticatedUser, CurrentUser, UserExtractor};
+use crate::models::comment::CommentModel;
 use crate::models::involvement::InvolvementModel;
+use crate::models::likes::LikesModel;
 use crate::models::production::{
-    CreateProductionData, ProductionMember, ProductionMembership, ProductionModel,
-    UpdateProductionData,
+    CreateProductionData, CrewListEntry, CrewRecommendation, ProductionMember,
+    ProductionMembership, ProductionModel, UpdateProductionData, allowed_statuses,
 };
 use crate::models::script::ScriptModel;
 use crate::record_id_ext::RecordIdExt;
+use crate::services::crew_list;
 use crate::services::embedding::generate_embedding_async;
 use crate::services::invitation::InvitationService;
 use crate::services::search_log::log_search;
 use crate::templates::{
-    BaseContext, CastCrewMember, ProductionCreateTemplate, ProductionEditTemplate,
-    ProductionScriptView, ProductionTemplate, ProductionsTemplate, User,
+    BaseContext, CastCrewMember, FormValues, ProductionCommentView, ProductionCreateTemplate,
+    ProductionEditTemplate, ProductionScriptView, ProductionTemplate, ProductionsTemplate,
+    ShareMeta, User,
 };
+use crate::validation::ValidationErrors;
 use askama::Template;
 use axum::Form;
 use axum::{
     Json, Router,
     extract::{Path, Query, Request, multipart::Multipart},
+    http::header,
     response::{Html, IntoResponse, Redirect, Response},
     routing::{get, post},
 };
 use axum_extra::extract::Form as HtmlForm;
 use serde::Deserialize;
+use surrealdb::types::RecordId;
 use tracing::{debug, error, info};
 
 const PAGE_SIZE: usize = 20;
@@ -60,7 +70,8 @@ use crate::templates::filters;
 
 /// Mounts the production pages: `/productions` (list) and `/my-productions`,
 /// `/productions/new`, `/productions/{slug}` view/edit/delete, the member,
-/// invite, and script management POST endpoints, and the
+/// invite, and script management POST endpoints, editor-only crew
+/// recommendations, the members-only `crew.csv`/`crew.pdf` exports, and the
 /// `/api/productions/more-sse` infinite-scroll feed.
 pub fn router() -> Router {
     Router::new()
@@ -77,6 +88,12 @@ pub fn router() -> Router {
         )
         .route("/productions/{slug}/delete", post(delete_production))
         .route("/productions/{slug}/members", get(get_members))
+        .route("/productions/{slug}/crew.csv", get(crew_list_csv))
+        .route("/productions/{slug}/crew.pdf", get(crew_list_pdf))
+        .route(
+            "/productions/{slug}/recommendations",
+            get(crew_recommendations),
+        )
         .route("/productions/{slug}/members/add", post(add_member))
         .route("/productions/{slug}/members/add-org", post(add_org_member))
         .route("/productions/{slug}/members/remove", post(remove_member))
@@ -113,6 +130,7 @@ pub struct MyProductionsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub productions: Vec<ProductionMembership>,
 }
 
@@ -240,8 +258,15 @@ async fn view_production(
     // Add user to context if authenticated
     let mut can_edit = false;
     let mut can_manage = false;
+    let mut viewer_id: Option<String> = None;
+    let mut viewer_rid: Option<RecordId> = None;
+    let mut viewer_is_admin = false;
     if let Some(user) = request.get_user() {
-        base = base.with_user(User::from_session_user(&user).await);
+        let template_user = User::from_session_user(&user).await;
+        viewer_is_admin = template_user.is_admin;
+        viewer_id = Some(user.id.clone());
+        viewer_rid = user.record_id().ok();
+        base = base.with_user(template_user);
 
         // Check if user can edit this production
         can_edit = ProductionModel::can_edit(&production.id, &user.id)
@@ -338,6 +363,57 @@ async fn view_production(
         })
         .collect();
 
+    // First page of comments. Editing is author-only; deleting is also open
+    // to production editors and platform admins.
+    let comment_count = CommentModel::count_for_target(&production.id)
+        .await
+        .unwrap_or(0);
+    let raw_comments = CommentModel::list_for_target(&production.id, COMMENTS_PAGE_SIZE, 0)
+        .await
+        .unwrap_or_default();
+
+    // Like counts + the viewer's liked state for the production and every
+    // comment on this page, in one query each.
+    let mut like_targets: Vec<RecordId> = raw_comments
+        .iter()
+        .filter_map(|c| RecordId::parse_simple(&c.id).ok())
+        .collect();
+    like_targets.push(production.id.clone());
+    let like_counts = LikesModel::count_many(&like_targets)
+        .await
+        .unwrap_or_default();
+    let liked_ids: Vec<String> = match &viewer_rid {
+        Some(rid) => LikesModel::get_liked_ids(rid, &like_targets)
+            .await
+            .unwrap_or_default(),
+        None => vec![],
+    };
+    let production_raw_id = production.id.to_raw_string();
+
+    let comments: Vec<ProductionCommentView> = raw_comments
+        .into_iter()
+        .map(|c| {
+            let is_author = viewer_id.as_deref() == Some(c.author_id.as_str());
+            let author_name = c
+                .author_name
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| c.author_username.clone());
+            ProductionCommentView {
+                id: c.id.strip_prefix("comment:").unwrap_or(&c.id).to_string(),
+                like_count: like_counts.get(&c.id).copied().unwrap_or(0),
+                viewer_liked: liked_ids.contains(&c.id),
+                body: c.body,
+                author_username: c.author_username,
+                author_name,
+                author_avatar: c.author_avatar,
+                created_at: c.created_at.format("%b %d, %Y at %H:%M").to_string(),
+                edited: c.updated_at.is_some(),
+                can_edit: is_author,
+                can_delete: is_author || can_edit || viewer_is_admin,
+            }
+        })
+        .collect();
+
     let production_roles = ProductionModel::get_roles_by_type("individual")
         .await
         .unwrap_or_default();
@@ -376,9 +452,30 @@ async fn view_production(
         .cloned()
         .collect();
 
+    let share = {
+        let description = production.description.clone().unwrap_or_else(|| {
+            format!(
+                "{} — {} on {}.",
+                production.title, production.production_type, base.app_name
+            )
+        });
+        ShareMeta::new(
+            format!("{} ({})", production.title, production.production_type),
+            &description,
+            &format!("/productions/{}", production.slug),
+        )
+        .with_image(
+            production
+                .poster_photo
+                .as_deref()
+                .or(production.poster_url.as_deref()),
+        )
+    };
+
     let template = crate::with_base!(ProductionTemplate, base, {
         production_roles,
         org_production_roles,
+        share: Some(share),
         production: crate::templates::ProductionDetail {
             id: production.id.key_string(),
             slug: production.slug.clone(),
@@ -435,6 +532,10 @@ async fn view_production(
             } else {
                 vec![]
             },
+            like_count: like_counts.get(&production_raw_id).copied().unwrap_or(0),
+            viewer_liked: liked_ids.contains(&production_raw_id),
+            comments,
+            comment_count,
         },
     });
 
@@ -452,9 +553,18 @@ async fn new_production_form(
     AuthenticatedUser(user): AuthenticatedUser,
 ) -> Result<Html<String>, Error> {
     debug!("Showing new production form");
+    render_production_create(&user, None, FormValues::default()).await
+}
 
+/// Render the create form, optionally with errors and the values the user
+/// submitted.
+async fn render_production_create(
+    user: &CurrentUser,
+    errors: Option<Vec<String>>,
+    values: FormValues,
+) -> Result<Html<String>, Error> {
     let mut base = BaseContext::new().with_page("productions");
-    base = base.with_user(User::from_session_user(&user).await);
+    base = base.with_user(User::from_session_user(user).await);
 
     // Get production types, statuses, budget levels, and tiers for dropdowns
     let production_types = ProductionModel::get_production_types()
@@ -500,7 +610,8 @@ async fn new_production_form(
         user_organizations: user_orgs,
         production_roles,
         org_production_roles,
-        errors: None,
+        errors,
+        values,
     });
 
     let html = template.render().map_err(|e| {
@@ -511,6 +622,37 @@ async fn new_production_form(
     Ok(Html(html))
 }
 
+const PRODUCTION_TITLE_MAX: usize = 200;
+const PRODUCTION_DESCRIPTION_MAX: usize = 10000;
+
+/// Field checks for the create form, so every problem is reported at once.
+fn validate_production_form(values: &FormValues) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("title", "Title", values.get("title"))
+        .max_len("title", "Title", values.get("title"), PRODUCTION_TITLE_MAX)
+        .required(
+            "production_type",
+            "Production type",
+            values.get("production_type"),
+        )
+        .required("status", "Production status", values.get("status"))
+        .max_len(
+            "description",
+            "Description",
+            values.get("description"),
+            PRODUCTION_DESCRIPTION_MAX,
+        );
+
+    let date = |field| chrono::NaiveDate::parse_from_str(values.get(field), "%Y-%m-%d").ok();
+    if let (Some(start), Some(end)) = (date("start_date"), date("end_date"))
+        && end < start
+    {
+        errors.add("end_date", "End date must be on or after the start date");
+    }
+    errors
+}
+
 /// Create a new production (multipart form for poster upload)
 #[axum::debug_handler]
 async fn create_production(
@@ -530,6 +672,8 @@ async fn create_production(
     let mut budget_level: Option<String> = None;
     let mut production_tier: Option<String> = None;
     let mut poster_data: Option<Vec<u8>> = None;
+    // Text fields as submitted, for re-rendering the form on error.
+    let mut values = FormValues::default();
 
     while let Some(field) = multipart
         .next_field()
@@ -539,14 +683,18 @@ async fn create_production(
         let name = field.name().unwrap_or("").to_string();
         match name.as_str() {
             "poster" => {
-                if let Ok(bytes) = field.bytes().await
-                    && !bytes.is_empty()
-                {
+                let bytes = crate::routes::media::read_field_limited(
+                    field,
+                    crate::routes::media::MAX_FILE_SIZE,
+                )
+                .await?;
+                if !bytes.is_empty() {
                     poster_data = Some(bytes.to_vec());
                 }
             }
             _ => {
                 let value = field.text().await.unwrap_or_default();
+                values = values.with(&name, Some(&value));
                 match name.as_str() {
                     "title" => title = value,
                     "production_type" => production_type = value,
@@ -572,8 +720,12 @@ async fn create_production(
 
     debug!("Creating new production: {}", title);
 
-    if title.is_empty() {
-        return Err(Error::Validation("Title is required".to_string()));
+    if let Err(errors) = validate_production_form(&values).into_result() {
+        return Ok(
+            render_production_create(&user, Some(errors.into_messages()), values)
+                .await?
+                .into_response(),
+        );
     }
 
     let production_data = CreateProductionData {
@@ -607,13 +759,25 @@ async fn create_production(
         Some(owner_production_role)
     };
 
-    let production = ProductionModel::create(
+    let production = match ProductionModel::create(
         production_data,
         &creator_id,
         creator_type,
         owner_production_roles,
     )
-    .await?;
+    .await
+    {
+        Ok(production) => production,
+        Err(e) => {
+            let Some(message) = e.form_message() else {
+                return Err(e);
+            };
+            let errors = vec![message.to_string()];
+            return Ok(render_production_create(&user, Some(errors), values)
+                .await?
+                .into_response());
+        }
+    };
 
     info!(
         "Created production: {} ({})",
@@ -645,7 +809,8 @@ async fn upload_poster_for_production(
 ) -> Result<(), Error> {
     use crate::services::s3::s3;
 
-    let (processed, thumbnail) = crate::routes::media::process_poster(image_bytes)?;
+    let (processed, thumbnail) =
+        crate::routes::media::process_poster(bytes::Bytes::copy_from_slice(image_bytes)).await?;
 
     let image_id = ulid::Ulid::new().to_string();
     let main_key = format!("productions/{}/poster_{}.jpg", production_id, image_id);
@@ -696,9 +861,13 @@ async fn edit_production_form(
     let production_types = ProductionModel::get_production_types()
         .await
         .unwrap_or_default();
-    let production_statuses = ProductionModel::get_production_statuses()
-        .await
-        .unwrap_or_default();
+    // Only the statuses the lifecycle allows from here
+    let production_statuses = allowed_statuses(
+        &production.status,
+        &ProductionModel::get_production_statuses()
+            .await
+            .unwrap_or_default(),
+    );
     let budget_levels = ProductionModel::get_budget_levels()
         .await
         .unwrap_or_default();
@@ -832,8 +1001,20 @@ async fn update_production(
         production_tier: data.production_tier.filter(|s| !s.is_empty()),
     };
 
-    // Update the production
+    // Update the production (rejects status moves the lifecycle forbids)
     let updated = ProductionModel::update(&production.id, update_data).await?;
+    // The new status is already saved; a missing history entry shouldn't
+    // turn that into an error page.
+    if updated.status != production.status
+        && let Err(e) =
+            ProductionModel::record_status_change(&updated, &production.status, &user.id).await
+    {
+        error!(
+            "Failed to record status change for production {}: {}",
+            updated.id.display(),
+            e
+        );
+    }
 
     info!(
         "Updated production: {} ({})",
@@ -883,6 +1064,103 @@ async fn get_members(Path(slug): Path<String>) -> Result<Json<Vec<ProductionMemb
     Ok(Json(members))
 }
 
+/// The production and its crew list, for a user who is an accepted member.
+/// Contact details in the rows are already redacted per member.
+async fn crew_export(
+    slug: &str,
+    user: &crate::models::person::SessionUser,
+) -> Result<(crate::models::production::Production, Vec<CrewListEntry>), Error> {
+    let production = ProductionModel::get_by_slug(slug).await?;
+    if !ProductionModel::is_accepted_member(&production.id, &user.id).await? {
+        return Err(Error::Forbidden);
+    }
+    let crew = ProductionModel::crew_list(&production.id).await?;
+    Ok((production, crew))
+}
+
+fn download(content_type: &str, filename: &str, body: impl IntoResponse) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                crate::services::s3::content_disposition(content_type, filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// The crew list as a spreadsheet-friendly CSV download.
+async fn crew_list_csv(
+    Path(slug): Path<String>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Response, Error> {
+    let (production, crew) = crew_export(&slug, &user).await?;
+    Ok(download(
+        "text/csv; charset=utf-8",
+        &format!("{}-crew.csv", production.slug),
+        crew_list::to_csv(&crew),
+    ))
+}
+
+/// The crew list as a printable PDF download.
+async fn crew_list_pdf(
+    Path(slug): Path<String>,
+    AuthenticatedUser(user): AuthenticatedUser,
+) -> Result<Response, Error> {
+    let (production, crew) = crew_export(&slug, &user).await?;
+    let generated_on = chrono::Utc::now().format("%B %-d, %Y").to_string();
+    let pdf = crew_list::render_pdf(&production.title, &generated_on, &crew)?;
+    Ok(download(
+        "application/pdf",
+        &format!("{}-crew.pdf", production.slug),
+        pdf,
+    ))
+}
+
+/// Most crew suggestions one request can ask for.
+const MAX_RECOMMENDATIONS: usize = 50;
+
+#[derive(Debug, Deserialize)]
+struct RecommendationsQuery {
+    /// Comma-separated skills the production needs, e.g. `Gaffer,Grip`.
+    skills: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Suggest crew for a production by overlapping skills (JSON response).
+/// Only the production's editors may ask; see
+/// [`ProductionModel::recommend_crew`] for the ranking.
+#[axum::debug_handler]
+async fn crew_recommendations(
+    Path(slug): Path<String>,
+    AuthenticatedUser(user): AuthenticatedUser,
+    Query(params): Query<RecommendationsQuery>,
+) -> Result<Json<Vec<CrewRecommendation>>, Error> {
+    let production = ProductionModel::get_by_slug(&slug).await?;
+    if !ProductionModel::can_edit(&production.id, &user.id).await? {
+        return Err(Error::Forbidden);
+    }
+
+    let skills: Vec<String> = params
+        .skills
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let limit = params
+        .limit
+        .unwrap_or(PAGE_SIZE)
+        .clamp(1, MAX_RECOMMENDATIONS);
+
+    let recommendations = ProductionModel::recommend_crew(&production.id, &skills, limit).await?;
+    Ok(Json(recommendations))
+}
+
 /// Add a member to a production (via invitation)
 #[axum::debug_handler]
 async fn add_member(
@@ -1331,13 +1609,7 @@ async fn upload_script(
         match name.as_str() {
             "file" => {
                 let filename = field.file_name().unwrap_or_default().to_string();
-                let data = field
-                    .bytes()
-                    .await
-                    .map_err(|e| Error::bad_request(format!("Failed to read file data: {}", e)))?;
-                if data.len() > MAX_SCRIPT_SIZE {
-                    return Err(Error::bad_request("File too large. Maximum size is 50MB."));
-                }
+                let data = crate::routes::media::read_field_limited(field, MAX_SCRIPT_SIZE).await?;
                 file_data = Some((filename, data));
             }
             "visibility" => {
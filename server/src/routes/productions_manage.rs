@@ -15,21 +15,27 @@
 
 use askama::Template;
 use axum::{
-    Router,
+    Form, Router,
     extract::Path,
     response::{Html, IntoResponse, Redirect, Response},
-    routing::get,
+    routing::{get, post},
 };
-use tracing::error;
+use serde::Deserialize;
+use surrealdb::types::RecordId;
+use tracing::{error, info};
 
 use crate::{
     error::Error,
     middleware::AuthenticatedUser,
     models::{
+        equipment::EquipmentModel,
+        location::LocationModel,
         person::SessionUser,
         production::{Production, ProductionModel},
+        schedule::{SHOOT_DAY_STATUSES, ScheduleModel, ShootDay, ShootDayForm, ShootDayInput},
         script::ScriptModel,
     },
+    record_id_ext::RecordIdExt,
     services::feature_flag,
     // `filters` must be in scope for the Template derives below — askama's
     // generated code calls `filters::<name>` unqualified at the derive site.
@@ -64,7 +70,9 @@ impl ManageTab {
 /// Mounts the `/productions/manage` hub and the per-production workspace
 /// tabs under `/productions/{slug}/manage`: overview (root), `script`,
 /// `breakdown`, `schedule`, `call-sheets`, and `team`. Tab routes gate
-/// through `require_member`; the hub gates on the flag alone.
+/// through `require_member`; the hub gates on the flag alone. The schedule
+/// tab also takes shoot-day create/update/delete posts and equipment
+/// bookings, open to any member.
 /// (`/productions/manage` is a static segment, so axum matches it before
 /// the `{slug}` captures — same precedent as `/productions/new`.)
 pub fn router() -> Router {
@@ -73,7 +81,26 @@ pub fn router() -> Router {
         .route("/productions/{slug}/manage", get(overview))
         .route("/productions/{slug}/manage/script", get(script_tab))
         .route("/productions/{slug}/manage/breakdown", get(breakdown_tab))
-        .route("/productions/{slug}/manage/schedule", get(schedule_tab))
+        .route(
+            "/productions/{slug}/manage/schedule",
+            get(schedule_tab).post(create_shoot_day),
+        )
+        .route(
+            "/productions/{slug}/manage/schedule/{day}",
+            post(update_shoot_day),
+        )
+        .route(
+            "/productions/{slug}/manage/schedule/{day}/delete",
+            post(delete_shoot_day),
+        )
+        .route(
+            "/productions/{slug}/manage/schedule/{day}/equipment",
+            post(book_shoot_day_equipment),
+        )
+        .route(
+            "/productions/{slug}/manage/schedule/{day}/equipment/{equipment}/remove",
+            post(unbook_shoot_day_equipment),
+        )
         .route(
             "/productions/{slug}/manage/call-sheets",
             get(call_sheets_tab),
@@ -89,6 +116,7 @@ struct ManageHubTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     productions: Vec<crate::models::production::ProductionMembership>,
 }
 
@@ -145,6 +173,7 @@ struct OverviewTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     production: ProductionView,
     active_tab: String,
     role: String,
@@ -160,6 +189,7 @@ struct ScriptTabTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     production: ProductionView,
     active_tab: String,
     role: String,
@@ -175,6 +205,7 @@ struct BreakdownTabTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     production: ProductionView,
     active_tab: String,
     role: String,
@@ -188,9 +219,14 @@ struct ScheduleTabTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     production: ProductionView,
     active_tab: String,
     role: String,
+    shoot_days: Vec<ShootDayView>,
+    location_options: Vec<LocationOption>,
+    equipment_options: Vec<EquipmentOption>,
+    statuses: Vec<String>,
 }
 
 #[derive(Template)]
@@ -201,9 +237,11 @@ struct CallSheetsTabTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     production: ProductionView,
     active_tab: String,
     role: String,
+    shoot_days: Vec<ShootDayView>,
 }
 
 #[derive(Template)]
@@ -214,6 +252,7 @@ struct TeamTabTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     production: ProductionView,
     active_tab: String,
     role: String,
@@ -248,6 +287,71 @@ impl ProductionView {
     }
 }
 
+/// A shoot day as the Schedule and Call Sheets tabs render it: form-ready
+/// values (`date` as `YYYY-MM-DD`, `call_time` as `HH:MM`, blank when unset)
+/// next to display labels.
+struct ShootDayView {
+    id: String,
+    date: String,
+    date_label: String,
+    call_time: String,
+    location: String,
+    place: String,
+    location_label: String,
+    notes: String,
+    status: String,
+    equipment: Vec<EquipmentOption>,
+}
+
+impl ShootDayView {
+    fn from(day: &ShootDay) -> Self {
+        Self {
+            id: day.id.key_string(),
+            date: day.day().format("%Y-%m-%d").to_string(),
+            date_label: day.day().format("%a, %b %-d %Y").to_string(),
+            call_time: day
+                .call_time()
+                .map(|t| t.format("%H:%M").to_string())
+                .unwrap_or_default(),
+            location: day
+                .location
+                .as_ref()
+                .map(|l| l.key_string())
+                .unwrap_or_default(),
+            place: day.place().unwrap_or_default().to_string(),
+            location_label: day.location_label.clone().unwrap_or_default(),
+            notes: day.notes.clone().unwrap_or_default(),
+            status: day.status.clone(),
+            equipment: day
+                .equipment
+                .iter()
+                .map(|item| EquipmentOption {
+                    id: item.id.key_string(),
+                    name: item.name.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A location the member can attach to a shoot day.
+struct LocationOption {
+    id: String,
+    name: String,
+}
+
+/// An item the member can book for a shoot day, or one already booked.
+struct EquipmentOption {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BookEquipmentForm {
+    #[serde(default)]
+    equipment: String,
+}
+
 // ── Handlers ───────────────────────────────────────────────────────────────
 
 async fn overview(
@@ -346,6 +450,26 @@ async fn schedule_tab(
     Path(slug): Path<String>,
 ) -> Result<Response, Error> {
     let (production, role) = require_member(&user, &slug).await?;
+    let shoot_days = load_shoot_days(&slug, &production).await?;
+    let location_options = LocationModel::get_for_person(&user.id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|l| LocationOption {
+            id: l.id.key_string(),
+            name: l.name,
+        })
+        .collect();
+    let equipment_options = EquipmentModel::list_for_member(&user.id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| EquipmentOption {
+            id: item.id.key_string(),
+            name: item.name,
+        })
+        .collect();
+
     let base = BaseContext::new()
         .with_page("productions")
         .with_user(User::from_session_user(&user).await);
@@ -353,15 +477,126 @@ async fn schedule_tab(
         production: ProductionView::from(&production),
         active_tab: ManageTab::Schedule.slug().to_string(),
         role,
+        shoot_days,
+        location_options,
+        equipment_options,
+        statuses: SHOOT_DAY_STATUSES.iter().map(|s| s.to_string()).collect(),
     });
     Ok(render(template)?.into_response())
 }
 
+async fn load_shoot_days(slug: &str, production: &Production) -> Result<Vec<ShootDayView>, Error> {
+    let days = ScheduleModel::list(&production.id).await.map_err(|e| {
+        error!(slug, error = %e, "manage: failed to load shoot days");
+        Error::Internal("Failed to load the schedule".to_string())
+    })?;
+    Ok(days.iter().map(ShootDayView::from).collect())
+}
+
+/// Validate a posted shoot day. A linked location must exist and be one the
+/// member can see — the same visibility rule as the location pages.
+async fn shoot_day_input(user: &SessionUser, form: &ShootDayForm) -> Result<ShootDayInput, Error> {
+    let input = ShootDayInput::parse(form)?;
+    if let Some(location_id) = &input.location {
+        let visible = match LocationModel::get(location_id).await {
+            Ok(location) => LocationModel::can_view(&location, Some(&user.id)).await?,
+            Err(Error::NotFound) => false,
+            Err(e) => return Err(e),
+        };
+        if !visible {
+            return Err(Error::Validation("Unknown location".to_string()));
+        }
+    }
+    Ok(input)
+}
+
+fn schedule_redirect(slug: &str) -> Response {
+    Redirect::to(&format!("/productions/{}/manage/schedule", slug)).into_response()
+}
+
+async fn create_shoot_day(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path(slug): Path<String>,
+    Form(form): Form<ShootDayForm>,
+) -> Result<Response, Error> {
+    let (production, _role) = require_member(&user, &slug).await?;
+    let input = shoot_day_input(&user, &form).await?;
+    ScheduleModel::create(&production.id, &input).await?;
+    info!("Shoot day {} added to production {}", input.date, slug);
+    Ok(schedule_redirect(&slug))
+}
+
+async fn update_shoot_day(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path((slug, day)): Path<(String, String)>,
+    Form(form): Form<ShootDayForm>,
+) -> Result<Response, Error> {
+    let (production, _role) = require_member(&user, &slug).await?;
+    let input = shoot_day_input(&user, &form).await?;
+    ScheduleModel::update(
+        &production.id,
+        &RecordId::new("schedule_day", &*day),
+        &input,
+    )
+    .await?;
+    Ok(schedule_redirect(&slug))
+}
+
+async fn delete_shoot_day(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path((slug, day)): Path<(String, String)>,
+) -> Result<Response, Error> {
+    let (production, _role) = require_member(&user, &slug).await?;
+    ScheduleModel::delete(&production.id, &RecordId::new("schedule_day", &*day)).await?;
+    info!("Shoot day {} deleted from production {}", day, slug);
+    Ok(schedule_redirect(&slug))
+}
+
+/// Book an item for a shoot day. The item must be one the member can book
+/// (their own or their organization's); one that's checked out on the day
+/// is refused with a conflict.
+async fn book_shoot_day_equipment(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path((slug, day)): Path<(String, String)>,
+    Form(form): Form<BookEquipmentForm>,
+) -> Result<Response, Error> {
+    let (production, _role) = require_member(&user, &slug).await?;
+    let key = form.equipment.trim();
+    let bookable = EquipmentModel::list_for_member(&user.id)
+        .await?
+        .into_iter()
+        .find(|item| item.id.key_string() == key)
+        .ok_or_else(|| Error::Validation("Unknown equipment".to_string()))?;
+    ScheduleModel::attach_equipment(
+        &production.id,
+        &RecordId::new("schedule_day", &*day),
+        &bookable.id,
+    )
+    .await?;
+    info!("{} booked for shoot day {} of {}", bookable.name, day, slug);
+    Ok(schedule_redirect(&slug))
+}
+
+async fn unbook_shoot_day_equipment(
+    AuthenticatedUser(user): AuthenticatedUser,
+    Path((slug, day, equipment)): Path<(String, String, String)>,
+) -> Result<Response, Error> {
+    let (production, _role) = require_member(&user, &slug).await?;
+    ScheduleModel::detach_equipment(
+        &production.id,
+        &RecordId::new("schedule_day", &*day),
+        &RecordId::new("equipment", &*equipment),
+    )
+    .await?;
+    Ok(schedule_redirect(&slug))
+}
+
 async fn call_sheets_tab(
     AuthenticatedUser(user): AuthenticatedUser,
     Path(slug): Path<String>,
 ) -> Result<Response, Error> {
     let (production, role) = require_member(&user, &slug).await?;
+    let shoot_days = load_shoot_days(&slug, &production).await?;
     let base = BaseContext::new()
         .with_page("productions")
         .with_user(User::from_session_user(&user).await);
@@ -369,6 +604,7 @@ async fn call_sheets_tab(
         production: ProductionView::from(&production),
         active_tab: ManageTab::CallSheets.slug().to_string(),
         role,
+        shoot_days,
     });
     Ok(render(template)?.into_response())
 }
@@ -408,10 +644,3 @@ pub async fn is_member_of(production_id: &surrealdb::types::RecordId, user: &Ses
         .flatten()
         .is_some()
 }
-
-/// Silences the unused-import warning when the workspace is built without
-/// the management routes ever being invoked at compile-time-known sites.
-#[allow(dead_code)]
-fn _redirect_unused() -> Redirect {
-    Redirect::to("/")
-}
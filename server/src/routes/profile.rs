@@ -3,11 +3,13 @@
 //! the profile-edit form — parsing the flat `social_links[i][..]`,
 //! `reels[i][..]`, and `photos[i][..]` form fields, converting height and
 //! weight units, and enforcing verification-based photo/reel limits.
+//! `/profile/export.json` downloads the signed-in user's data as JSON.
 
 use askama::Template;
 use axum::{
     Form, Router,
     extract::{Path, Request},
+    http::header,
     response::{Html, IntoResponse, Redirect, Response},
     routing::get,
 };
@@ -19,7 +21,7 @@ use crate::{
     middleware::{AuthenticatedUser, UserExtractor},
     models::involvement::InvolvementModel,
     models::person::{Person, Photo, Reel, SocialLink},
-    record_id_ext::RecordIdExt,
+    record_id_ext::{RecordIdExt, normalize_record_id},
     social_platforms::{self, SOCIAL_PLATFORMS},
     templates::{
         BaseContext, DateRange, Education, InvolvementDisplay, PhotoDisplay, ProfileData,
@@ -28,11 +30,12 @@ use crate::{
     verification_limits, video_platforms,
 };
 
-/// Routes for the `/profile` redirects and the `/profile/edit` form
-/// (GET renders, POST saves).
+/// Routes for the `/profile` redirects, the data export, and the
+/// `/profile/edit` form (GET renders, POST saves).
 pub fn router() -> Router {
     Router::new()
         .route("/profile", get(own_profile))
+        .route("/profile/export.json", get(export_data))
         .route("/profile/{username}", get(user_profile))
         .route("/profile/edit", get(edit_profile_form).post(update_profile))
 }
@@ -116,6 +119,33 @@ async fn user_profile(Path(username): Path<String>) -> Response {
     Redirect::permanent(&format!("/{}", username)).into_response()
 }
 
+/// Handler for /profile/export.json — downloads everything SlateHub holds
+/// about the signed-in user (GDPR data portability).
+async fn export_data(
+    AuthenticatedUser(current_user): AuthenticatedUser,
+) -> Result<Response, Error> {
+    let person_id = normalize_record_id(&current_user.id, "person");
+    let export = Person::export_data(&person_id).await?;
+    let body = serde_json::to_vec_pretty(&export)
+        .map_err(|e| Error::Internal(format!("Failed to serialize data export: {e}")))?;
+
+    info!("Data export downloaded by {}", current_user.username);
+
+    let filename = format!("slatehub-{}-export.json", current_user.username);
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 /// Handler for displaying the profile edit form
 async fn edit_profile_form(request: Request) -> Result<Response, Error> {
     debug!("Handling profile edit form request");
@@ -168,6 +198,7 @@ async fn edit_profile_form(request: Request) -> Result<Response, Error> {
         skills: profile.map(|p| p.skills.clone()).unwrap_or_default(),
         languages: profile.map(|p| p.languages.clone()).unwrap_or_default(),
         availability: profile.and_then(|p| p.availability.clone()),
+        availability_status: profile.and_then(|p| p.availability_status()),
         involvements: {
             let pid = profile_user.id.to_raw_string();
             match InvolvementModel::get_for_person(&pid).await {
@@ -550,6 +581,7 @@ async fn update_profile(
         form.get("skills").cloned(),
         form.get("languages").cloned(),
         form.get("availability").cloned(),
+        form.get("availability_status").cloned(),
         Some(social_links),
         Some(reels),
         Some(photos),
@@ -1,7 +1,10 @@
 //! Public people discovery: the `/people` directory with hybrid
-//! text/vector search and infinite scroll via Datastar SSE, plus the
-//! catch-all `/{username}` public profile page (reserved route names
-//! excluded), which records profile views and like state.
+//! text/vector search, `?skill=`/`?location=`/`?availability=` filters,
+//! specialty buttons aggregated from public profiles, and infinite scroll
+//! via Datastar SSE, plus the catch-all `/{username}` public profile page
+//! (reserved route names excluded), which records profile views and like
+//! state. Visitors see a redacted profile, or a minimal "private" page when
+//! the owner has hidden it (see [`Person::profile_access`]).
 
 use askama::Template;
 use axum::{
@@ -12,19 +15,19 @@ use axum::{
     routing::get,
 };
 use serde::Deserialize;
+use std::collections::HashMap;
 use tracing::{debug, error, info};
 
 use crate::{
     config, datastar,
-    db::DB,
     error::Error,
     html::escape_html,
     middleware::UserExtractor,
     models::analytics::AnalyticsModel,
     models::involvement::InvolvementModel,
     models::likes::LikesModel,
-    models::person::Person,
-    record_id_ext::RecordIdExt,
+    models::person::{AvailabilityStatus, Person, ProfileAccess},
+    record_id_ext::{RecordIdExt, normalize_record_id},
     services::embedding::generate_embedding_async,
     services::search::{self, PersonSearchResult, SearchParams},
     services::search_log::log_search,
@@ -32,7 +35,8 @@ use crate::{
     social_platforms,
     templates::{
         BaseContext, DateRange, Education, InvolvementDisplay, PeopleTemplate, PersonCard,
-        PhotoDisplay, ProfileData, ProfileTemplate, ReelDisplay, SocialLinkDisplay, User,
+        PhotoDisplay, PrivateProfileTemplate, ProfileData, ProfileTemplate, ReelDisplay, ShareMeta,
+        SocialLinkDisplay, User,
     },
     video_platforms,
 };
@@ -173,6 +177,28 @@ async fn user_profile(
         }
     };
 
+    // Private profiles: visitors get identity only — no view recorded, no
+    // profile data loaded.
+    let access = profile_user.profile_access(is_own_profile);
+    if access == ProfileAccess::Private {
+        debug!("Profile {} is private; rendering minimal page", username);
+        let mut base = BaseContext::new().with_page("profile");
+        if let Some(ref user) = current_user {
+            base = base.with_user(User::from_session_user(user).await);
+        }
+        let template = crate::with_base!(PrivateProfileTemplate, base, {
+            name: profile_user.get_display_name(),
+            username: profile_user.username.clone(),
+            avatar: profile_user.get_avatar_url(),
+            initials: profile_user.get_initials(),
+        });
+        let html = template.render().map_err(|e| {
+            error!("Failed to render private profile template: {}", e);
+            Error::template(e.to_string())
+        })?;
+        return Ok(Html(html).into_response());
+    }
+
     // Record profile view (fire-and-forget, skip own profile)
     if !is_own_profile {
         let pid = profile_user.id.clone();
@@ -216,7 +242,7 @@ async fn user_profile(
                 Some(RecordId::new("person", user.id.as_str()))
             };
             if let Some(rid) = person_rid {
-                is_liked = LikesModel::is_liked(&rid, &profile_user.id)
+                is_liked = LikesModel::has_liked(&rid, &profile_user.id)
                     .await
                     .unwrap_or(false);
             }
@@ -225,7 +251,7 @@ async fn user_profile(
 
     // Convert Person model to ProfileData (same structure as /profile/{username} used)
     let profile = profile_user.profile.as_ref();
-    let profile_data = ProfileData {
+    let mut profile_data = ProfileData {
         id: profile_user.id.to_raw_string(),
         name: profile_user.get_display_name(),
         username: profile_user.username.clone(),
@@ -239,6 +265,7 @@ async fn user_profile(
         skills: profile.map(|p| p.skills.clone()).unwrap_or_default(),
         languages: profile.map(|p| p.languages.clone()).unwrap_or_default(),
         availability: profile.and_then(|p| p.availability.clone()),
+        availability_status: profile.and_then(|p| p.availability_status()),
         involvements: {
             let pid = profile_user.id.to_raw_string();
             match InvolvementModel::get_for_person(&pid).await {
@@ -329,6 +356,9 @@ async fn user_profile(
         messaging_preference: profile_user.messaging_preference.clone(),
         phone: profile.and_then(|p| p.phone.clone()),
     };
+    if access == ProfileAccess::Visitor {
+        profile_data.redact_for_visitor(profile_user.private_fields());
+    }
 
     // Owner-only profile-completeness meter (nudges profile activation).
     let completeness = if is_own_profile {
@@ -357,11 +387,16 @@ async fn user_profile(
         return Ok(Redirect::to("/profile/edit").into_response());
     }
 
+    // Share meta only for profiles visitors can actually see.
+    let is_private = profile_user.profile.as_ref().is_some_and(|p| p.is_private);
+    let share = (!is_private).then(|| ShareMeta::for_profile(&profile_data, &base.app_name));
+
     // Create and render template using the same ProfileTemplate
     let template = crate::with_base!(ProfileTemplate, base, {
         profile: profile_data,
         is_liked,
         completeness,
+        share,
     });
 
     let html = template.render().map_err(|e| {
@@ -372,52 +407,88 @@ async fn user_profile(
     Ok(Html(html).into_response())
 }
 
-#[derive(Deserialize)]
+/// How many of the most-listed skills the specialty filter offers.
+const SPECIALTY_COUNT: usize = 12;
+
+/// The most-listed skills across public profiles (cached by
+/// [`Person::known_skills`]).
+async fn specialties() -> Vec<String> {
+    Person::known_skills()
+        .await
+        .iter()
+        .take(SPECIALTY_COUNT)
+        .map(|(skill, _)| skill.clone())
+        .collect()
+}
+
+/// Directory filters shared by `/people` and its infinite-scroll feed.
+#[derive(Debug, Default, Deserialize)]
 struct PeopleQuery {
+    /// Free-text search across name, skills, and location.
     filter: Option<String>,
+    /// Exact skill, as offered by the specialty buttons.
+    skill: Option<String>,
+    /// Case-insensitive substring of the profile location.
+    location: Option<String>,
+    /// An [`AvailabilityStatus`] value; unknown values are ignored.
+    availability: Option<String>,
+    /// Rows already shown (infinite scroll only).
+    #[serde(default)]
+    offset: usize,
 }
 
-async fn people(
-    Query(params): Query<PeopleQuery>,
-    request: Request,
-) -> Result<Html<String>, Error> {
-    let filter = params
-        .filter
-        .as_deref()
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty());
-    debug!("Rendering people page, filter: {:?}", filter);
+impl PeopleQuery {
+    fn filter(&self) -> Option<&str> {
+        non_empty(&self.filter)
+    }
 
-    let mut base = BaseContext::new().with_page("people");
+    fn skill(&self) -> Option<&str> {
+        non_empty(&self.skill)
+    }
 
-    // Add user to context if authenticated
-    let current_user_id = if let Some(user) = request.get_user() {
-        let uid = user.id.clone();
-        base = base.with_user(User::from_session_user(&user).await);
-        Some(uid)
-    } else {
-        None
-    };
+    fn location(&self) -> Option<&str> {
+        non_empty(&self.location)
+    }
 
-    let mut template = PeopleTemplate::new(base);
-    template.current_user_id = current_user_id.clone().unwrap_or_default();
-    template.filter = filter.map(|s| s.to_string());
-
-    // Add specialties list (in production, fetch from database)
-    template.specialties = vec![
-        "Director".to_string(),
-        "Producer".to_string(),
-        "Cinematographer".to_string(),
-        "Editor".to_string(),
-        "Sound Designer".to_string(),
-        "Actor".to_string(),
-        "Writer".to_string(),
-        "Composer".to_string(),
-    ];
-
-    // Fetch profiles from the database, optionally filtered
-    let (persons, search_cards) = if let Some(filter_text) = filter {
-        let parsed = search_utils::parse_query(filter_text);
+    fn availability(&self) -> Option<AvailabilityStatus> {
+        non_empty(&self.availability).and_then(AvailabilityStatus::parse)
+    }
+
+    /// The active filters as `&key=value` pairs for the next-page URL.
+    fn more_params(&self) -> String {
+        [
+            ("filter", self.filter()),
+            ("skill", self.skill()),
+            ("location", self.location()),
+            ("availability", self.availability().map(|a| a.as_str())),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| format!("&{key}={}", urlencoding::encode(v))))
+        .collect()
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// One page of directory cards starting at `params.offset`, and whether
+/// more follow. Free-text filters go through hybrid search, with skill,
+/// location and availability as hard filters in the query (an explicit
+/// location overrides one parsed from the text, as in the MCP tool);
+/// otherwise the public directory is listed directly. Private and
+/// unverified profiles are never included.
+async fn directory_cards(params: &PeopleQuery) -> (Vec<PersonCard>, bool) {
+    let skill = params.skill();
+    let location = params.location();
+    let availability = params.availability();
+
+    if let Some(filter_text) = params.filter() {
+        let mut parsed = search_utils::parse_query(filter_text);
+        if let Some(location) = location {
+            parsed.location = Some(location.to_string());
+        }
+        parsed.availability = availability.map(|a| a.as_str().to_string());
         let query_embedding = generate_embedding_async(&parsed.cleaned).await.ok();
         let weights = config::search_weights();
 
@@ -426,98 +497,118 @@ async fn people(
             embedding: query_embedding.as_ref(),
             weights,
             limit: PAGE_SIZE + 1,
-            offset: 0,
+            offset: params.offset,
         };
 
-        let results = search::search_people(&search_params, &parsed, None)
+        let results = search::search_people(&search_params, &parsed, skill)
             .await
             .unwrap_or_else(|e| {
                 error!("Failed to search people: {}", e);
                 vec![]
             });
 
-        log_search(filter_text, "web", "people", Some(results.len()));
-        (vec![], Some(results))
-    } else {
-        let query = r#"
-            SELECT *, verification_status = 'identity' AS _vord OMIT embedding, embedding_text FROM person
-            WHERE verification_status != 'unverified'
-              AND (profile.name IS NOT NULL
-               OR profile.headline IS NOT NULL
-               OR profile.bio IS NOT NULL)
-            ORDER BY _vord DESC, created_at DESC
-            LIMIT $limit
-            START $offset
-        "#;
-        let persons = match DB
-            .query(query)
-            .bind(("limit", PAGE_SIZE as i64 + 1))
-            .bind(("offset", 0i64))
+        if params.offset == 0 {
+            log_search(filter_text, "web", "people", Some(results.len()));
+        }
+
+        let has_more = results.len() > PAGE_SIZE;
+        let mut cards: Vec<PersonCard> = results
+            .into_iter()
+            .take(PAGE_SIZE)
+            .map(person_card_from_search_result)
+            .collect();
+        resolve_card_avatars(&mut cards).await;
+        return (cards, has_more);
+    }
+
+    let persons =
+        Person::directory_page(skill, location, availability, PAGE_SIZE + 1, params.offset)
             .await
-        {
-            Ok(mut result) => match result.take::<Vec<Person>>(0) {
-                Ok(persons) => persons,
-                Err(e) => {
-                    error!("Failed to fetch persons from database: {}", e);
-                    vec![]
-                }
-            },
-            Err(e) => {
-                error!("Failed to query persons from database: {}", e);
+            .unwrap_or_else(|e| {
+                error!("Failed to fetch people directory: {}", e);
                 vec![]
-            }
-        };
-        (persons, None::<Vec<PersonSearchResult>>)
-    };
+            });
 
-    // Convert to PersonCards — either from search results or from Person models
-    template.has_more = if let Some(ref results) = search_cards {
-        results.len() > PAGE_SIZE
-    } else {
-        persons.len() > PAGE_SIZE
+    let has_more = persons.len() > PAGE_SIZE;
+    let mut cards: Vec<PersonCard> = persons
+        .into_iter()
+        .take(PAGE_SIZE)
+        .filter_map(person_card_from_person)
+        .collect();
+    resolve_card_avatars(&mut cards).await;
+    (cards, has_more)
+}
+
+/// Fill in each card's avatar and identity badge from one batched person
+/// lookup ([`User::batch_from_profiles`]). Cards whose person can't be
+/// loaded keep what they had.
+async fn resolve_card_avatars(cards: &mut [PersonCard]) {
+    let ids: Vec<String> = cards.iter().map(|card| card.id.clone()).collect();
+    let users = User::batch_from_profiles(&ids).await;
+    let by_id: HashMap<&str, &User> = users.iter().map(|u| (u.id.as_str(), u)).collect();
+    for card in cards {
+        let id = normalize_record_id(&card.id, "person").to_raw_string();
+        if let Some(user) = by_id.get(id.as_str()) {
+            card.avatar = user.avatar.clone();
+            card.is_identity_verified = user.is_identity_verified;
+        }
+    }
+}
+
+fn person_card_from_person(person: Person) -> Option<PersonCard> {
+    let profile = person.profile?;
+    if profile.name.is_none() && profile.headline.is_none() && profile.bio.is_none() {
+        return None;
+    }
+    Some(PersonCard {
+        id: person.id.to_raw_string(),
+        name: profile.name.unwrap_or_else(|| person.username.clone()),
+        username: person.username,
+        headline: profile.headline,
+        bio: profile.bio,
+        location: profile.location,
+        skills: profile.skills,
+        avatar: profile
+            .avatar
+            .unwrap_or_else(|| "/static/images/default-avatar.svg".to_string()),
+        is_identity_verified: person.verification_status == "identity",
+    })
+}
+
+async fn people(
+    Query(params): Query<PeopleQuery>,
+    request: Request,
+) -> Result<Html<String>, Error> {
+    let params = PeopleQuery {
+        offset: 0,
+        ..params
     };
+    debug!("Rendering people page, query: {:?}", params);
 
-    template.people = if let Some(results) = search_cards {
-        results
-            .into_iter()
-            .take(PAGE_SIZE)
-            .map(person_card_from_search_result)
-            .collect()
+    let mut base = BaseContext::new().with_page("people");
+
+    // Add user to context if authenticated
+    let current_user_id = if let Some(user) = request.get_user() {
+        let uid = user.id.clone();
+        base = base.with_user(User::from_session_user(&user).await);
+        Some(uid)
     } else {
-        persons
-            .into_iter()
-            .take(PAGE_SIZE)
-            .filter_map(|person| {
-                if let Some(profile) = person.profile {
-                    if profile.name.is_some() || profile.headline.is_some() || profile.bio.is_some()
-                    {
-                        Some(PersonCard {
-                            id: person.id.to_raw_string(),
-                            name: profile
-                                .name
-                                .clone()
-                                .unwrap_or_else(|| person.username.clone()),
-                            username: person.username.clone(),
-                            headline: profile.headline.clone(),
-                            bio: profile.bio.clone(),
-                            location: profile.location.clone(),
-                            skills: profile.skills,
-                            avatar: profile
-                                .avatar
-                                .clone()
-                                .unwrap_or_else(|| "/static/images/default-avatar.svg".to_string()),
-                            is_identity_verified: person.verification_status == "identity",
-                        })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect()
+        None
     };
 
+    let mut template = PeopleTemplate::new(base);
+    template.current_user_id = current_user_id.clone().unwrap_or_default();
+    template.filter = params.filter().map(str::to_string);
+    template.skill = params.skill().map(str::to_string);
+    template.location = params.location().map(str::to_string);
+    template.availability = params.availability();
+    template.more_params = params.more_params();
+    template.specialties = specialties().await;
+
+    let (cards, has_more) = directory_cards(&params).await;
+    template.people = cards;
+    template.has_more = has_more;
+
     // Fetch liked IDs if user is logged in
     if let Some(ref uid) = current_user_id {
         let person_rid = if uid.starts_with("person:") {
@@ -545,12 +636,6 @@ async fn people(
     Ok(Html(html))
 }
 
-#[derive(Debug, Deserialize)]
-struct PeopleMoreQuery {
-    offset: usize,
-    filter: Option<String>,
-}
-
 const VERIFIED_BADGE_PATH: &str = "M22.5 12.5c0-1.58-.875-2.95-2.148-3.6.154-.435.238-.905.238-1.4 0-2.21-1.71-3.998-3.818-3.998-.47 0-.92.084-1.336.25C14.818 2.415 13.51 1.5 12 1.5s-2.816.917-3.437 2.25c-.415-.165-.866-.25-1.336-.25-2.11 0-3.818 1.79-3.818 4 0 .494.083.964.237 1.4-1.272.65-2.147 2.018-2.147 3.6 0 1.495.782 2.798 1.942 3.486-.02.17-.032.34-.032.514 0 2.21 1.708 4 3.818 4 .47 0 .92-.086 1.335-.25.62 1.334 1.926 2.25 3.437 2.25 1.512 0 2.818-.916 3.437-2.25.415.163.865.248 1.336.248 2.11 0 3.818-1.79 3.818-4 0-.174-.012-.344-.033-.513 1.158-.687 1.943-1.99 1.943-3.484zm-6.616-3.334l-4.334 6.5c-.145.217-.382.334-.625.334-.143 0-.288-.04-.416-.126l-.115-.094-2.415-2.415c-.293-.293-.293-.768 0-1.06s.768-.294 1.06 0l1.77 1.767 3.825-5.74c.23-.345.696-.436 1.04-.207.346.23.44.696.21 1.04z";
 
 fn render_person_card(person: &PersonCard) -> String {
@@ -561,7 +646,7 @@ fn render_person_card(person: &PersonCard) -> String {
         escape_html(&person.username)
     ));
     html.push_str(&format!(
-        r#"<img src="{}" alt="{}" loading="lazy" onerror="this.style.display='none'" />"#,
+        r#"<img src="{}" alt="{}" loading="lazy" data-handler-error="this.style.display='none'" />"#,
         escape_html(&person.avatar),
         escape_html(&person.name)
     ));
@@ -605,100 +690,9 @@ fn render_person_card(person: &PersonCard) -> String {
     html
 }
 
-async fn people_more_sse(Query(params): Query<PeopleMoreQuery>) -> Response {
-    let filter = params.filter.as_deref().filter(|s| !s.is_empty());
+async fn people_more_sse(Query(params): Query<PeopleQuery>) -> Response {
     let offset = params.offset;
-
-    let (persons, search_cards) = if let Some(filter_text) = filter {
-        let parsed = search_utils::parse_query(filter_text);
-        let query_embedding = generate_embedding_async(&parsed.cleaned).await.ok();
-        let weights = config::search_weights();
-
-        let search_params = SearchParams {
-            query: &parsed.cleaned,
-            embedding: query_embedding.as_ref(),
-            weights,
-            limit: PAGE_SIZE + 1,
-            offset,
-        };
-
-        let results = search::search_people(&search_params, &parsed, None)
-            .await
-            .unwrap_or_else(|e| {
-                error!("Failed to search people (SSE): {}", e);
-                vec![]
-            });
-
-        (vec![], Some(results))
-    } else {
-        let query = r#"
-            SELECT *, verification_status = 'identity' AS _vord OMIT embedding, embedding_text FROM person
-            WHERE verification_status != 'unverified'
-              AND (profile.name IS NOT NULL
-               OR profile.headline IS NOT NULL
-               OR profile.bio IS NOT NULL)
-            ORDER BY _vord DESC, created_at DESC
-            LIMIT $limit
-            START $offset
-        "#;
-        let persons: Vec<Person> = match DB
-            .query(query)
-            .bind(("limit", PAGE_SIZE as i64 + 1))
-            .bind(("offset", offset as i64))
-            .await
-        {
-            Ok(mut result) => result.take::<Vec<Person>>(0).unwrap_or_default(),
-            Err(_) => vec![],
-        };
-        (persons, None::<Vec<PersonSearchResult>>)
-    };
-
-    let has_more = if let Some(ref results) = search_cards {
-        results.len() > PAGE_SIZE
-    } else {
-        persons.len() > PAGE_SIZE
-    };
-
-    let cards: Vec<PersonCard> = if let Some(results) = search_cards {
-        results
-            .into_iter()
-            .take(PAGE_SIZE)
-            .map(person_card_from_search_result)
-            .collect()
-    } else {
-        persons
-            .into_iter()
-            .take(PAGE_SIZE)
-            .filter_map(|person| {
-                if let Some(profile) = person.profile {
-                    if profile.name.is_some() || profile.headline.is_some() || profile.bio.is_some()
-                    {
-                        Some(PersonCard {
-                            id: person.id.to_raw_string(),
-                            name: profile
-                                .name
-                                .clone()
-                                .unwrap_or_else(|| person.username.clone()),
-                            username: person.username.clone(),
-                            headline: profile.headline.clone(),
-                            bio: profile.bio.clone(),
-                            location: profile.location.clone(),
-                            skills: profile.skills,
-                            avatar: profile
-                                .avatar
-                                .clone()
-                                .unwrap_or_else(|| "/static/images/default-avatar.svg".to_string()),
-                            is_identity_verified: person.verification_status == "identity",
-                        })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .collect()
-    };
+    let (cards, has_more) = directory_cards(&params).await;
 
     if cards.is_empty() {
         return datastar::response(datastar::patch_elements("#people-sentinel", "remove", ""));
@@ -711,13 +705,10 @@ async fn people_more_sse(Query(params): Query<PeopleMoreQuery>) -> Response {
 
     if has_more {
         let new_offset = offset + PAGE_SIZE;
-        let q_param = match filter {
-            Some(f) => format!("&filter={}", urlencoding::encode(f)),
-            None => String::new(),
-        };
         replacement.push_str(&format!(
             r#"<div id="people-sentinel" data-on-intersect="@get('/api/people/more-sse?offset={}{}')"><div class="people-loading">Loading more...</div></div>"#,
-            new_offset, q_param
+            new_offset,
+            params.more_params()
         ));
     }
 
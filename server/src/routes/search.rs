@@ -11,7 +11,6 @@ use axum::{
     response::{Html, IntoResponse},
     routing::get,
 };
-use chrono::Datelike;
 use serde::Deserialize;
 use tracing::{debug, error};
 
@@ -29,8 +28,8 @@ use crate::services::search::{
     SearchParams,
 };
 use crate::services::search_log::log_search;
-use crate::services::search_utils;
-use crate::templates::User;
+use crate::services::search_utils::{self, MatchContext};
+use crate::templates::{BaseContext, User};
 
 // Shared Askama filters (abs_url, …) for the in-file Template derives.
 use crate::templates::filters;
@@ -49,6 +48,7 @@ struct PersonView {
     avatar_url: Option<String>,
     initials: String,
     score: f64,
+    matched: Option<MatchContext>,
 }
 
 impl From<crate::services::search::PersonSearchResult> for PersonView {
@@ -71,6 +71,7 @@ impl From<crate::services::search::PersonSearchResult> for PersonView {
             avatar_url: p.avatar_url,
             initials,
             score: p.score,
+            matched: p.matched,
         }
     }
 }
@@ -83,6 +84,7 @@ struct SearchTemplate {
     version: String,
     active_page: String,
     user: Option<User>,
+    nonce: String,
     query: Option<String>,
     has_results: bool,
     total_results: usize,
@@ -122,13 +124,16 @@ async fn search_page(
         (None, None)
     };
 
+    let base = BaseContext::new().with_page("search");
+
     if query.is_empty() {
         let template = SearchTemplate {
-            app_name: "SlateHub".to_string(),
-            year: chrono::Utc::now().year(),
-            version: crate::version::asset_version().to_string(),
-            active_page: "search".to_string(),
+            app_name: base.app_name,
+            year: base.year,
+            version: base.version,
+            active_page: base.active_page,
             user: user.clone(),
+            nonce: base.nonce,
             query: None,
             has_results: false,
             total_results: 0,
@@ -170,9 +175,12 @@ async fn search_page(
 
     let weights = config::search_weights();
 
-    // --- People: use parse_query for structured filter extraction ---
+    // Structured filters come out of the query once; every entity matches
+    // (and builds its snippets) on what's left.
+    let parsed = search_utils::parse_query(query);
+    let location = parsed.location.as_deref();
+
     let people = if intent.people {
-        let parsed = search_utils::parse_query(query);
         let search_params = SearchParams {
             query: &parsed.cleaned,
             embedding: query_embedding.as_ref(),
@@ -189,40 +197,36 @@ async fn search_page(
         vec![]
     };
 
-    // --- Non-people: extract location, normalize remaining query ---
-    let (location, cleaned_query) = search_utils::extract_location(query);
-    let normalized = search_utils::normalize_query(&cleaned_query);
-
     let organizations = if intent.organizations {
         let search_params = SearchParams {
-            query: &normalized,
+            query: &parsed.cleaned,
             embedding: query_embedding.as_ref(),
             weights,
             limit: 10,
             offset: 0,
         };
-        crate::services::search::search_organizations(&search_params, location.as_deref()).await?
+        crate::services::search::search_organizations(&search_params, location).await?
     } else {
         vec![]
     };
 
     let locations = if intent.locations {
         let search_params = SearchParams {
-            query: &normalized,
+            query: &parsed.cleaned,
             embedding: query_embedding.as_ref(),
             weights,
             limit: 10,
             offset: 0,
         };
         // For locations, pass extracted location as city filter
-        crate::services::search::search_locations(&search_params, location.as_deref(), None).await?
+        crate::services::search::search_locations(&search_params, location, None).await?
     } else {
         vec![]
     };
 
     let productions = if intent.productions {
         let search_params = SearchParams {
-            query: &normalized,
+            query: &parsed.cleaned,
             embedding: query_embedding.as_ref(),
             weights,
             limit: 10,
@@ -235,13 +239,13 @@ async fn search_page(
 
     let jobs = if intent.jobs {
         let search_params = SearchParams {
-            query: &normalized,
+            query: &parsed.cleaned,
             embedding: query_embedding.as_ref(),
             weights,
             limit: 10,
             offset: 0,
         };
-        crate::services::search::search_jobs(&search_params, location.as_deref(), true).await?
+        crate::services::search::search_jobs(&search_params, location, true).await?
     } else {
         vec![]
     };
@@ -274,11 +278,12 @@ async fn search_page(
     };
 
     let template = SearchTemplate {
-        app_name: "SlateHub".to_string(),
-        year: chrono::Utc::now().year(),
-        version: crate::version::asset_version().to_string(),
-        active_page: "search".to_string(),
+        app_name: base.app_name,
+        year: base.year,
+        version: base.version,
+        active_page: base.active_page,
         user,
+        nonce: base.nonce,
         query: Some(query.to_string()),
         has_results: total_results > 0,
         total_results,
@@ -185,7 +185,7 @@ async fn start_checkout(
         .and_then(|h| h.to_str().ok());
     let price = pick_price(accept_language);
 
-    let base_url = crate::config::app_url();
+    let base_url = crate::config::public_base_url();
     let success_url = format!(
         "{}/get-verified/return?session_id={{CHECKOUT_SESSION_ID}}",
         base_url
@@ -255,7 +255,7 @@ async fn checkout_return(
     }
 
     // Create the Identity session and redirect.
-    let base_url = crate::config::app_url();
+    let base_url = crate::config::public_base_url();
     let return_url = format!("{}/get-verified/done", base_url);
     let person_id_str = rid.to_raw_string();
     let id_session = stripe
@@ -391,7 +391,7 @@ async fn resume_verification(
     }
 
     // Path 2: create a fresh Identity session against the existing paid row.
-    let base_url = crate::config::app_url();
+    let base_url = crate::config::public_base_url();
     let return_url = format!("{}/get-verified/done", base_url);
     let person_id_str = rid.to_raw_string();
     let id_session = stripe
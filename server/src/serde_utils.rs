@@ -5,9 +5,13 @@
 //! as `Some("")`. Route form structs (e.g. in `routes::locations`) opt into
 //! these helpers with `#[serde(deserialize_with = "...")]` to map blank
 //! submissions to `None` (or an empty list) and to split comma-separated
-//! values into vectors.
+//! values into vectors. Non-blank numeric input that doesn't parse is an
+//! error rather than a silent `None`, so handlers taking
+//! `Result<Form<_>, FormRejection>` can report it as a validation error.
 
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+
+use crate::error::Error;
 
 /// Deserialize an optional i32 from a string that might be empty
 ///
@@ -177,3 +181,15 @@ where
         }
     }
 }
+
+/// Deserialize a urlencoded form body into `T`.
+///
+/// For handlers that take the raw body so they can re-render the form with
+/// the submitted values (see `templates::FormValues`) when parsing fails.
+///
+/// # Errors
+/// `Error::Validation` with the same friendly message a `FormRejection`
+/// would produce (see `Error::parse_form_validation_error`).
+pub fn parse_form<T: DeserializeOwned>(body: &str) -> Result<T, Error> {
+    serde_urlencoded::from_str(body).map_err(|e| Error::parse_form_validation_error(e.to_string()))
+}
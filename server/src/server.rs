@@ -0,0 +1,114 @@
+//! HTTP accept loop with the [`ServerTuning`] knobs applied.
+//!
+//! Equivalent to `axum::serve` (including `ConnectInfo<SocketAddr>` for
+//! handlers) but builds each hyper connection itself, since `axum::serve`
+//! exposes no way to turn HTTP/2 off, cap streams, or set socket options.
+//! Pages that pull dozens of avatars and thumbnails benefit most: HTTP/2
+//! multiplexes them over one connection and keep-alive spares the proxy a
+//! fresh handshake per request.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use hyper_util::service::TowerToHyperService;
+use socket2::{SockRef, TcpKeepalive};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tower::Service;
+use tracing::{debug, error, warn};
+
+use crate::config::ServerTuning;
+
+/// Accept connections on `listener` and serve `app` on each until the
+/// process exits.
+///
+/// With `max_connections` set, the loop stops accepting while that many
+/// connections are open; the kernel backlog queues the rest.
+pub async fn serve(listener: TcpListener, app: Router, tuning: ServerTuning) -> io::Result<()> {
+    let mut make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+    let builder = connection_builder(&tuning);
+    let limit = tuning.max_connections.map(|n| Arc::new(Semaphore::new(n)));
+
+    loop {
+        let permit = match &limit {
+            Some(limit) => Some(
+                Arc::clone(limit)
+                    .acquire_owned()
+                    .await
+                    .expect("connection semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            // The peer gave up before we got to it; nothing to back off from
+            Err(e) if is_connection_error(&e) => continue,
+            // Usually fd exhaustion: pause so the loop doesn't spin
+            Err(e) => {
+                error!("Failed to accept connection: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        configure_socket(&stream, &tuning);
+
+        let service = make_service
+            .call(remote_addr)
+            .await
+            .unwrap_or_else(|never| match never {});
+        let builder = builder.clone();
+        tokio::spawn(async move {
+            let service = TowerToHyperService::new(service);
+            if let Err(e) = builder
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("Connection from {} ended with error: {}", remote_addr, e);
+            }
+            drop(permit);
+        });
+    }
+}
+
+/// Per-connection protocol settings shared by every accepted socket.
+fn connection_builder(tuning: &ServerTuning) -> Builder<TokioExecutor> {
+    let mut builder = Builder::new(TokioExecutor::new());
+    builder.http1().keep_alive(tuning.http1_keep_alive);
+    builder
+        .http2()
+        .max_concurrent_streams(tuning.http2_max_streams);
+    if tuning.http2 {
+        builder
+    } else {
+        builder.http1_only()
+    }
+}
+
+/// Apply the TCP-level options. Failures only cost performance, so they're
+/// logged rather than dropping the connection.
+fn configure_socket(stream: &TcpStream, tuning: &ServerTuning) {
+    if let Err(e) = stream.set_nodelay(tuning.tcp_nodelay) {
+        warn!("Failed to set TCP_NODELAY: {}", e);
+    }
+    if let Some(idle) = tuning.tcp_keepalive {
+        let keepalive = TcpKeepalive::new().with_time(idle);
+        if let Err(e) = SockRef::from(stream).set_tcp_keepalive(&keepalive) {
+            warn!("Failed to enable TCP keep-alive: {}", e);
+        }
+    }
+}
+
+fn is_connection_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    )
+}
@@ -0,0 +1,138 @@
+//! Exportable crew list for a production, as CSV or PDF.
+//!
+//! Both formats take the rows from [`ProductionModel::crew_list`], whose
+//! contact fields are already redacted, so nothing here decides who sees
+//! what. The PDF is a landscape US Letter table laid out with
+//! [`super::pdf::TextDocument`], paginated with the header row repeated;
+//! over-long cells are cut with `...`.
+//!
+//! [`ProductionModel::crew_list`]: crate::models::production::ProductionModel::crew_list
+
+use crate::error::Error;
+use crate::models::production::CrewListEntry;
+use crate::services::pdf::{self, TextDocument};
+
+/// Column headings, shared by both formats.
+pub const COLUMNS: [&str; 5] = ["Name", "Role", "Department", "Email", "Phone"];
+
+/// The printed cells of one entry, in [`COLUMNS`] order.
+fn cells(entry: &CrewListEntry) -> [String; 5] {
+    [
+        entry.name.clone(),
+        entry.production_roles.join(", "),
+        entry.departments.join(", "),
+        entry.email.clone().unwrap_or_default(),
+        entry.phone.clone().unwrap_or_default(),
+    ]
+}
+
+/// Render the crew list as CSV (RFC 4180, CRLF line ends) with a header row.
+pub fn to_csv(crew: &[CrewListEntry]) -> String {
+    let mut out = String::new();
+    push_csv_row(&mut out, COLUMNS.iter().map(|c| c.to_string()));
+    for entry in crew {
+        push_csv_row(&mut out, cells(entry));
+    }
+    out
+}
+
+fn push_csv_row(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&csv_field(&field));
+    }
+    out.push_str("\r\n");
+}
+
+/// Quote a field when it needs it. A value a spreadsheet would evaluate as
+/// a formula (leading `=`, `+`, `-`, `@`, or a tab or carriage return
+/// ahead of one) is prefixed with `'` — names and roles are
+/// member-supplied. Phone numbers like `+44 20 7946 0958` are left alone:
+/// they hold nothing a formula could reach out with.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) && !is_phone_number(value) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Digits with the usual phone punctuation (`+`, `-`, `.`, spaces,
+/// parentheses), and enough digits to be a number rather than arithmetic.
+fn is_phone_number(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | ' ' | '(' | ')'))
+        && value.chars().filter(char::is_ascii_digit).count() >= 7
+}
+
+const MARGIN: f32 = 15.0;
+const BODY_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 5.0;
+/// Left edge (mm) and width (in characters) of each column. Helvetica at
+/// 9pt averages a little under 1.7mm a character.
+const LAYOUT: [(f32, usize); 5] = [
+    (MARGIN, 32),
+    (70.0, 33),
+    (128.0, 25),
+    (173.0, 37),
+    (238.0, 15),
+];
+
+/// Render the crew list as PDF bytes, titled with the production and the
+/// date it was generated.
+pub fn render_pdf(
+    production_title: &str,
+    generated_on: &str,
+    crew: &[CrewListEntry],
+) -> Result<Vec<u8>, Error> {
+    let title = format!("Crew List - {production_title}");
+    let mut doc = TextDocument::new(&title, pdf::LETTER_LANDSCAPE, MARGIN)?;
+    doc.text_at(MARGIN, &title, 16.0, true);
+    doc.y -= LINE_HEIGHT * 1.5;
+    doc.text_at(
+        MARGIN,
+        &format!("Generated {generated_on}"),
+        BODY_SIZE,
+        false,
+    );
+    doc.y -= LINE_HEIGHT * 2.0;
+    header_row(&mut doc);
+
+    if crew.is_empty() {
+        doc.text_at(MARGIN, "No crew yet.", BODY_SIZE, false);
+    }
+    for entry in crew {
+        if doc.ensure_space(LINE_HEIGHT) {
+            header_row(&mut doc);
+        }
+        row(&mut doc, &cells(entry), false);
+    }
+
+    doc.finish()
+}
+
+fn row(doc: &mut TextDocument, cells: &[String], bold: bool) {
+    for ((x, width), cell) in LAYOUT.iter().zip(cells) {
+        doc.text_at(
+            *x,
+            &pdf::truncate(&deunicode::deunicode(cell), *width),
+            BODY_SIZE,
+            bold,
+        );
+    }
+    doc.y -= LINE_HEIGHT;
+}
+
+fn header_row(doc: &mut TextDocument) {
+    let headings = COLUMNS.map(|c| c.to_uppercase());
+    row(doc, &headings, true);
+    doc.y -= LINE_HEIGHT / 2.0;
+}
@@ -3,9 +3,18 @@
 //!
 //! Covers every outbound mail the app sends: email-verification codes,
 //! password resets, org/production invitations, generic notifications
-//! (e.g. new-message alerts), and user feedback forwarding. Bodies are
-//! built inline as paired plain-text + HTML strings; user-supplied content
-//! interpolated into HTML is sanitized with `ammonia` first.
+//! (e.g. new-message alerts), equipment rental reminders, organization
+//! contact-form messages, and user feedback forwarding.
+//!
+//! Verification, password-reset, invitation, rental-reminder, and org-contact
+//! mail is rendered from Askama templates under `templates/emails/`: each
+//! message is an `.html` + `.txt` pair sharing `emails/_layout.*` (branding,
+//! sign-off, footer), built into a [`MultipartEmail`] by a pure function
+//! ([`verification_email`] etc.) and sent with
+//! [`EmailService::send_multipart`]. The welcome and profile-reminder copy is
+//! still built inline as paired strings; user-supplied content interpolated
+//! into that HTML, and into feedback mail, is escaped or sanitized with
+//! `ammonia` first.
 //!
 //! There is no global instance or boot-time init: call sites construct an
 //! [`EmailService`] with [`EmailService::from_env`] right before sending
@@ -15,11 +24,17 @@
 //!
 //! [`EmailService::from_env`] picks a backend once, at construction
 //! ([`select_provider_kind`] holds the precedence):
-//! * `EMAIL_PROVIDER` — optional explicit override, `postmark` or `mailjet`.
+//! * `EMAIL_PROVIDER` — optional explicit override, `postmark`, `mailjet`,
+//!   or `capture`.
 //! * Otherwise auto-detect, preferring Postmark when its token is set and
 //!   falling back to Mailjet. A deployment can keep both configured and
 //!   switch by flipping which credentials (or `EMAIL_PROVIDER`) are present.
 //!
+//! `capture` is for tests and local development: nothing leaves the process.
+//! Each message is recorded in memory (read back with [`captured_emails`] /
+//! [`take_captured_emails`]) and, when `EMAIL_CAPTURE_DIR` is set, also
+//! written there as one JSON file per message. It is never auto-detected.
+//!
 //! ## Env vars
 //!
 //! Postmark:
@@ -29,6 +44,9 @@
 //! Mailjet:
 //! * `MAILJET_API_KEY` / `MAILJET_API_SECRET` — basic-auth credentials.
 //!
+//! Capture:
+//! * `EMAIL_CAPTURE_DIR` — optional directory for the per-message JSON files.
+//!
 //! Shared sender identity (the `EMAIL_FROM_*` names are preferred; the
 //! `MAILJET_FROM_*` names are still honored for backward compatibility):
 //! * `EMAIL_FROM_ADDRESS` / `MAILJET_FROM_EMAIL` — default `noreply@slatehub.com`.
@@ -37,10 +55,14 @@
 //!   [`EmailService::send_feedback_email`] delivers (defaults to the from
 //!   address).
 
+use askama::Template;
+use chrono::Datelike;
 use reqwest;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
 use thiserror::Error;
 use tracing::{debug, error, info};
 
@@ -62,6 +84,9 @@ pub enum EmailError {
     /// static payload shapes).
     #[error("JSON serialization failed: {0}")]
     SerializationError(#[from] serde_json::Error),
+    /// An email template failed to render.
+    #[error("Template rendering failed: {0}")]
+    TemplateError(#[from] askama::Error),
 }
 
 type Result<T> = std::result::Result<T, EmailError>;
@@ -78,6 +103,9 @@ enum Provider {
         server_token: String,
         message_stream: String,
     },
+    /// In-process capture for tests/dev; see [`captured_emails`]. `dir`
+    /// additionally receives one JSON file per message.
+    Capture { dir: Option<PathBuf> },
 }
 
 impl Provider {
@@ -86,6 +114,7 @@ impl Provider {
         match self {
             Provider::Mailjet { .. } => "mailjet",
             Provider::Postmark { .. } => "postmark",
+            Provider::Capture { .. } => "capture",
         }
     }
 
@@ -112,6 +141,36 @@ pub enum ProviderKind {
     Postmark,
     /// Mailjet (`MAILJET_API_KEY` + `MAILJET_API_SECRET`).
     Mailjet,
+    /// In-process capture (`EMAIL_PROVIDER=capture` only).
+    Capture,
+}
+
+/// A message recorded by the `capture` provider instead of being sent, with
+/// the sender identity and headers resolved as a real provider would see them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapturedEmail {
+    pub from: String,
+    pub to: String,
+    pub cc: Option<String>,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub text_body: Option<String>,
+    pub html_body: Option<String>,
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Everything the `capture` provider has recorded in this process, oldest
+/// first.
+static CAPTURED: LazyLock<Mutex<Vec<CapturedEmail>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Messages captured so far (oldest first), leaving the log intact.
+pub fn captured_emails() -> Vec<CapturedEmail> {
+    CAPTURED.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Drain and return the captured messages, so a test only sees what it sent.
+pub fn take_captured_emails() -> Vec<CapturedEmail> {
+    std::mem::take(&mut *CAPTURED.lock().unwrap_or_else(|e| e.into_inner()))
 }
 
 /// A configured email backend plus the sender identity. Cheap to build per
@@ -219,6 +278,216 @@ fn escape_html(s: &str) -> String {
         .replace('"', "&quot;")
 }
 
+/// A rendered transactional email: subject plus the plain-text and HTML
+/// alternatives, sent together by [`EmailService::send_multipart`].
+#[derive(Debug, Clone)]
+pub struct MultipartEmail {
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+}
+
+/// Declare the `.html` + `.txt` template pair for one email. Both structs get
+/// the listed fields plus the `year` and `app_url` the shared
+/// `emails/_layout.*` templates read.
+macro_rules! email_templates {
+    ($html:ident, $text:ident, $html_path:literal, $text_path:literal, { $($field:ident: $ty:ty),* $(,)? }) => {
+        #[derive(Template)]
+        #[template(path = $html_path)]
+        struct $html<'a> {
+            year: i32,
+            app_url: &'a str,
+            $($field: $ty),*
+        }
+
+        #[derive(Template)]
+        #[template(path = $text_path)]
+        struct $text<'a> {
+            year: i32,
+            app_url: &'a str,
+            $($field: $ty),*
+        }
+    };
+}
+
+/// Render both halves of an [`email_templates!`] pair from one field list.
+macro_rules! render_email {
+    ($subject:expr, $html:ident, $text:ident, { $($field:ident $(: $value:expr)?),* $(,)? }) => {{
+        let app_url = crate::config::public_base_url();
+        let year = chrono::Utc::now().year();
+        Ok(MultipartEmail {
+            subject: $subject,
+            text_body: $text { year, app_url: &app_url, $($field $(: $value)?),* }.render()?,
+            html_body: $html { year, app_url: &app_url, $($field $(: $value)?),* }.render()?,
+        })
+    }};
+}
+
+email_templates!(VerificationHtml, VerificationText, "emails/verification.html", "emails/verification.txt", {
+    verify_url: &'a str,
+    code: &'a str,
+});
+
+email_templates!(PasswordResetHtml, PasswordResetText, "emails/password_reset.html", "emails/password_reset.txt", {
+    name: &'a str,
+    reset_url: &'a str,
+    code: &'a str,
+});
+
+email_templates!(InvitationHtml, InvitationText, "emails/invitation.html", "emails/invitation.txt", {
+    inviter_name: &'a str,
+    org_name: &'a str,
+    signup_url: &'a str,
+    message: Option<&'a str>,
+});
+
+email_templates!(RentalReminderHtml, RentalReminderText, "emails/rental_reminder.html", "emails/rental_reminder.txt", {
+    name: &'a str,
+    item_name: &'a str,
+    owner_name: &'a str,
+    due_date: &'a str,
+    overdue: bool,
+    item_url: &'a str,
+});
+
+email_templates!(OrgContactHtml, OrgContactText, "emails/org_contact.html", "emails/org_contact.txt", {
+    org_name: &'a str,
+    sender_name: &'a str,
+    sender_email: &'a str,
+    message: &'a str,
+    profile_url: &'a str,
+});
+
+/// Build the email-verification message: a confirm link plus the bare
+/// 6-digit `code` for manual entry.
+///
+/// # Errors
+///
+/// [`EmailError::TemplateError`] if a template fails to render.
+pub fn verification_email(verify_url: &str, code: &str) -> Result<MultipartEmail> {
+    render_email!(
+        "Verify your SlateHub email address".to_string(),
+        VerificationHtml,
+        VerificationText,
+        { verify_url, code }
+    )
+}
+
+/// Build the password-reset message: the reset `code` plus a link to the
+/// reset page. `name` falls back to "there" when the account has none.
+///
+/// # Errors
+///
+/// [`EmailError::TemplateError`] if a template fails to render.
+pub fn password_reset_email(
+    name: Option<&str>,
+    reset_url: &str,
+    code: &str,
+) -> Result<MultipartEmail> {
+    render_email!(
+        "Reset your SlateHub password".to_string(),
+        PasswordResetHtml,
+        PasswordResetText,
+        { name: name.unwrap_or("there"), reset_url, code }
+    )
+}
+
+/// Build an invitation for someone without an account. A blank personal
+/// `message` is omitted.
+///
+/// # Errors
+///
+/// [`EmailError::TemplateError`] if a template fails to render.
+pub fn invitation_email(
+    org_name: &str,
+    inviter_name: &str,
+    signup_url: &str,
+    message: Option<&str>,
+) -> Result<MultipartEmail> {
+    render_email!(
+        format!("You've been invited to join {org_name} on SlateHub"),
+        InvitationHtml,
+        InvitationText,
+        {
+            inviter_name,
+            org_name,
+            signup_url,
+            message: message.map(str::trim).filter(|m| !m.is_empty()),
+        }
+    )
+}
+
+/// A message sent to an organization through its profile's contact form.
+pub struct OrgInquiry<'a> {
+    pub org_name: &'a str,
+    pub sender_name: &'a str,
+    /// Where the organization's reply goes (the message's `Reply-To`).
+    pub sender_email: &'a str,
+    pub message: &'a str,
+    /// Absolute URL of the organization's profile.
+    pub profile_url: &'a str,
+}
+
+/// Build the message forwarding an [`OrgInquiry`] to the organization.
+///
+/// # Errors
+///
+/// [`EmailError::TemplateError`] if a template fails to render.
+pub fn org_contact_email(inquiry: &OrgInquiry<'_>) -> Result<MultipartEmail> {
+    render_email!(
+        format!("Message for {} from {}", inquiry.org_name, inquiry.sender_name),
+        OrgContactHtml,
+        OrgContactText,
+        {
+            org_name: inquiry.org_name,
+            sender_name: inquiry.sender_name,
+            sender_email: inquiry.sender_email,
+            message: inquiry.message.trim(),
+            profile_url: inquiry.profile_url,
+        }
+    )
+}
+
+/// What a rental reminder is about. Dates arrive preformatted so the caller
+/// controls the timezone and wording.
+pub struct RentalReminder<'a> {
+    /// Renter's name if known; the greeting falls back to "there".
+    pub renter_name: Option<&'a str>,
+    /// Equipment or kit name.
+    pub item_name: &'a str,
+    pub owner_name: &'a str,
+    /// Expected return date, e.g. `"Oct 17, 2026"`.
+    pub due_date: &'a str,
+    /// Past due (a firmer "please return it" message) rather than upcoming.
+    pub overdue: bool,
+    /// Absolute URL of the rented equipment or kit page.
+    pub item_url: &'a str,
+}
+
+/// Build a due-soon or overdue reminder for an equipment rental.
+///
+/// # Errors
+///
+/// [`EmailError::TemplateError`] if a template fails to render.
+pub fn rental_reminder_email(reminder: &RentalReminder<'_>) -> Result<MultipartEmail> {
+    let subject = if reminder.overdue {
+        format!("Overdue: please return {}", reminder.item_name)
+    } else {
+        format!(
+            "Reminder: {} is due back {}",
+            reminder.item_name, reminder.due_date
+        )
+    };
+    render_email!(subject, RentalReminderHtml, RentalReminderText, {
+        name: reminder.renter_name.unwrap_or("there"),
+        item_name: reminder.item_name,
+        owner_name: reminder.owner_name,
+        due_date: reminder.due_date,
+        overdue: reminder.overdue,
+        item_url: reminder.item_url,
+    })
+}
+
 /// Build a profile-completion reminder email: `(subject, text, html)`.
 ///
 /// `reminder_number` (1, 2, or 3) drives the tone — playful, then pointed, then
@@ -503,7 +772,9 @@ pub fn welcome_email_bodies(ctx: &WelcomeEmail<'_>) -> (String, String, String)
 ///
 /// * An explicit value (`EMAIL_PROVIDER`) wins, but errors when that
 ///   provider's credentials are absent or the value is unrecognized.
-/// * Otherwise Postmark is preferred when available, then Mailjet.
+///   `capture` needs no credentials.
+/// * Otherwise Postmark is preferred when available, then Mailjet. Capture
+///   is never auto-detected.
 ///
 /// # Errors
 ///
@@ -528,8 +799,9 @@ pub fn select_provider_kind(
                     .to_string(),
             )
         }),
+        Some("capture") => Ok(ProviderKind::Capture),
         Some(other) if !other.is_empty() => Err(EmailError::ConfigError(format!(
-            "Unknown EMAIL_PROVIDER '{other}' (expected 'postmark' or 'mailjet')"
+            "Unknown EMAIL_PROVIDER '{other}' (expected 'postmark', 'mailjet', or 'capture')"
         ))),
         // No (or empty) explicit choice: auto-detect, Postmark first.
         _ if has_postmark => Ok(ProviderKind::Postmark),
@@ -599,6 +871,9 @@ impl EmailService {
                 api_secret: mailjet_secret
                     .expect("secret present when ProviderKind::Mailjet is selected"),
             }),
+            ProviderKind::Capture => Ok(Provider::Capture {
+                dir: non_empty("EMAIL_CAPTURE_DIR").map(PathBuf::from),
+            }),
         }
     }
 
@@ -631,6 +906,28 @@ impl EmailService {
         .await
     }
 
+    /// Send a rendered [`MultipartEmail`] (plain-text and HTML alternatives)
+    /// from the default sender.
+    ///
+    /// # Errors
+    ///
+    /// Same failure modes as [`Self::send_email`].
+    pub async fn send_multipart(
+        &self,
+        to_email: &str,
+        to_name: Option<&str>,
+        email: &MultipartEmail,
+    ) -> Result<()> {
+        self.send_email(
+            to_email,
+            to_name,
+            &email.subject,
+            Some(&email.text_body),
+            Some(&email.html_body),
+        )
+        .await
+    }
+
     /// Send a fully-built message through the configured provider.
     async fn dispatch(&self, email: OutgoingEmail<'_>) -> Result<()> {
         debug!(
@@ -652,7 +949,64 @@ impl EmailService {
                 self.send_via_postmark(server_token, message_stream, &email)
                     .await
             }
+            Provider::Capture { dir } => self.capture(dir.as_deref(), &email).await,
+        }
+    }
+
+    /// Record a message for [`captured_emails`] (and, with a capture `dir`,
+    /// write it there as JSON) instead of sending it.
+    async fn capture(
+        &self,
+        dir: Option<&std::path::Path>,
+        email: &OutgoingEmail<'_>,
+    ) -> Result<()> {
+        let captured = CapturedEmail {
+            from: format_address(
+                email.from_email.unwrap_or(&self.from_email),
+                Some(email.from_name.unwrap_or(&self.from_name)),
+            ),
+            to: format_address(email.to_email, email.to_name),
+            cc: email.cc.map(|c| c.to_string()),
+            reply_to: email.reply_to.map(|r| r.to_string()),
+            subject: email.subject.to_string(),
+            text_body: email.text_body.map(|t| t.to_string()),
+            html_body: email.html_body.map(|h| h.to_string()),
+            sent_at: chrono::Utc::now(),
+        };
+
+        if let Some(dir) = dir {
+            let path = dir.join(format!(
+                "{}-{}.json",
+                captured.sent_at.format("%Y%m%dT%H%M%S"),
+                ulid::Ulid::new()
+            ));
+            let json = serde_json::to_vec_pretty(&captured)?;
+            let written = match tokio::fs::create_dir_all(dir).await {
+                Ok(()) => tokio::fs::write(&path, json).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = written {
+                error!(
+                    "Failed to write captured email to {}: {}",
+                    path.display(),
+                    e
+                );
+                return Err(EmailError::SendError(format!(
+                    "capture write to {} failed: {e}",
+                    path.display()
+                )));
+            }
         }
+
+        info!(
+            "Captured email to {} (not sent): {}",
+            email.to_email, email.subject
+        );
+        CAPTURED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(captured);
+        Ok(())
     }
 
     /// POST a message to Mailjet's `/v3.1/send` endpoint.
@@ -889,177 +1243,50 @@ impl EmailService {
     }
 
     /// Send the email-verification message: a confirm link
-    /// (`/verify-email/confirm?code=…&email=…` on [`crate::config::app_url`])
+    /// (`/verify-email/confirm?code=…&email=…` on [`crate::config::public_base_url`])
     /// plus the bare 6-digit code for manual entry. Tells the user the code
-    /// expires in 24 hours (the TTL set by `services::verification`).
+    /// expires in 24 hours (the TTL set by `services::verification`). Copy is
+    /// built by [`verification_email`].
     pub async fn send_verification_email(
         &self,
         to_email: &str,
         to_name: Option<&str>,
         verification_code: &str,
     ) -> Result<()> {
-        let subject = "Verify your SlateHub email address";
-        let base_url = crate::config::app_url();
         let verify_url = format!(
             "{}/verify-email/confirm?code={}&email={}",
-            base_url,
+            crate::config::public_base_url(),
             urlencoding::encode(verification_code),
             urlencoding::encode(to_email)
         );
-
-        let text_body = format!(
-            "Welcome to SlateHub!\n\n\
-            Click the link below to verify your email:\n\
-            {}\n\n\
-            Or enter this code on the verification page:\n\
-            {}\n\n\
-            This code will expire in 24 hours.\n\n\
-            If you didn't create an account on SlateHub, please ignore this email.\n\n\
-            Best regards,\n\
-            The SlateHub Team",
-            verify_url, verification_code
-        );
-
-        let html_body = format!(
-            r#"<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-</head>
-<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Helvetica, Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
-    <div style="background-color: #f8f9fa; border-radius: 8px; padding: 30px; margin-bottom: 20px;">
-        <h1 style="color: #2c3e50; margin-top: 0;">Welcome to SlateHub!</h1>
-        <p style="font-size: 16px; color: #555;">Thank you for joining our creative community.</p>
-    </div>
-
-    <div style="background-color: #ffffff; border: 1px solid #e0e0e0; border-radius: 8px; padding: 30px;">
-        <div style="text-align: center; margin: 20px 0 30px 0;">
-            <a href="{}" style="display: inline-block; background-color: #eb5437; color: white; padding: 14px 36px; text-decoration: none; border-radius: 6px; font-weight: bold; font-size: 16px;">Verify My Email</a>
-        </div>
-
-        <div style="border-top: 1px solid #e0e0e0; padding-top: 20px; margin-top: 10px;">
-            <p style="font-size: 14px; color: #666; margin-bottom: 10px;">Or enter this code on the verification page:</p>
-
-            <div style="background-color: #f0f4f8; border: 2px dashed #4a90e2; border-radius: 6px; padding: 20px; text-align: center; margin: 10px 0;">
-                <code style="font-size: 32px; font-weight: bold; color: #4a90e2; letter-spacing: 4px;">{}</code>
-            </div>
-        </div>
-
-        <p style="font-size: 14px; color: #999; margin-top: 20px;">
-            This code will expire in 24 hours. If you didn't create an account on SlateHub, please ignore this email.
-        </p>
-    </div>
-
-    <div style="margin-top: 30px; padding-top: 20px; border-top: 1px solid #e0e0e0; text-align: center; color: #999; font-size: 12px;">
-        <p>&copy; 2024 SlateHub. All rights reserved.</p>
-    </div>
-</body>
-</html>"#,
-            verify_url, verification_code
-        );
-
-        self.send_email(
-            to_email,
-            to_name,
-            subject,
-            Some(&text_body),
-            Some(&html_body),
-        )
-        .await
+        let email = verification_email(&verify_url, verification_code)?;
+        self.send_multipart(to_email, to_name, &email).await
     }
 
     /// Send the password-reset message: the 6-digit reset code plus a link
     /// to `/reset-password?email=…`. Tells the user the code expires in
-    /// 1 hour (the TTL set by `services::verification`).
+    /// 1 hour (the TTL set by `services::verification`). Copy is built by
+    /// [`password_reset_email`].
     pub async fn send_password_reset_email(
         &self,
         to_email: &str,
         to_name: Option<&str>,
         reset_code: &str,
     ) -> Result<()> {
-        let subject = "Reset your SlateHub password";
-        let base_url = crate::config::app_url();
-        let encoded_email = urlencoding::encode(to_email);
-
-        let text_body = format!(
-            "Hello {},\n\n\
-            We received a request to reset your SlateHub password.\n\n\
-            Your password reset code is: {}\n\n\
-            To reset your password:\n\
-            1. Go to: {}/reset-password?email={}\n\
-            2. Enter the code above\n\
-            3. Create your new password\n\n\
-            This code will expire in 1 hour.\n\n\
-            If you didn't request a password reset, please ignore this email. Your password will remain unchanged.\n\n\
-            Best regards,\n\
-            The SlateHub Team",
-            to_name.unwrap_or("there"),
-            reset_code,
-            base_url,
-            encoded_email
-        );
-
-        let html_body = format!(
-            r#"<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-</head>
-<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Helvetica, Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
-    <div style="background-color: #fff3cd; border: 1px solid #ffc107; border-radius: 8px; padding: 20px; margin-bottom: 20px;">
-        <h2 style="color: #856404; margin-top: 0;">Password Reset Request</h2>
-        <p style="color: #856404; margin-bottom: 0;">We received a request to reset your SlateHub password.</p>
-    </div>
-
-    <div style="background-color: #ffffff; border: 1px solid #e0e0e0; border-radius: 8px; padding: 30px;">
-        <p style="font-size: 16px; margin-bottom: 20px;">Your password reset code is:</p>
-
-        <div style="background-color: #f0f4f8; border: 2px dashed #dc3545; border-radius: 6px; padding: 20px; text-align: center; margin: 20px 0;">
-            <code style="font-size: 32px; font-weight: bold; color: #dc3545; letter-spacing: 4px;">{}</code>
-        </div>
-
-        <div style="text-align: center; margin: 30px 0;">
-            <a href="{}/reset-password?email={}" style="display: inline-block; background-color: #dc3545; color: white; padding: 12px 30px; text-decoration: none; border-radius: 6px; font-weight: bold; font-size: 16px;">Reset Your Password</a>
-        </div>
-
-        <p style="font-size: 14px; color: #666; margin-top: 20px;">
-            Click the button above or enter the code on the password reset page to create a new password.
-        </p>
-
-        <p style="font-size: 14px; color: #dc3545; font-weight: bold; margin-top: 20px;">
-            This code will expire in 1 hour.
-        </p>
-
-        <p style="font-size: 14px; color: #999; margin-top: 20px;">
-            If you didn't request a password reset, please ignore this email. Your password will remain unchanged.
-        </p>
-    </div>
-
-    <div style="margin-top: 30px; padding-top: 20px; border-top: 1px solid #e0e0e0; text-align: center; color: #999; font-size: 12px;">
-        <p>© 2024 SlateHub. All rights reserved.</p>
-    </div>
-</body>
-</html>"#,
-            reset_code, base_url, encoded_email
+        let reset_url = format!(
+            "{}/reset-password?email={}",
+            crate::config::public_base_url(),
+            urlencoding::encode(to_email)
         );
-
-        self.send_email(
-            to_email,
-            to_name,
-            subject,
-            Some(&text_body),
-            Some(&html_body),
-        )
-        .await
+        let email = password_reset_email(to_name, &reset_url, reset_code)?;
+        self.send_multipart(to_email, to_name, &email).await
     }
 
     /// Send an invitation email to someone with no SlateHub account yet
     /// (used for both org and production invites — `org_name` is whichever
-    /// the target is). The optional personal `message` is sanitized with
-    /// `ammonia` before being embedded in the HTML body; `signup_url` should
-    /// carry the `ref=invite&email=…` query so signup auto-joins them.
+    /// the target is). Copy is built by [`invitation_email`], which escapes
+    /// the optional personal `message`; `signup_url` should carry the
+    /// `ref=invite&email=…` query so signup auto-joins them.
     pub async fn send_invitation_email(
         &self,
         to_email: &str,
@@ -1068,82 +1295,56 @@ impl EmailService {
         signup_url: &str,
         message: Option<&str>,
     ) -> Result<()> {
-        let subject = format!("You've been invited to join {} on SlateHub", org_name);
-
-        let message_text = match message {
-            Some(msg) if !msg.is_empty() => format!("\n\n{} says: \"{}\"\n", inviter_name, msg),
-            _ => String::new(),
-        };
-
-        let text_body = format!(
-            "Hi there!\n\n\
-            {} has invited you to join {} on SlateHub — the production networking platform.{}\n\n\
-            To accept this invitation, create your free account:\n\
-            {}\n\n\
-            Once you sign up and verify your email, you'll automatically be added to {}.\n\n\
-            If you weren't expecting this invitation, you can safely ignore this email.\n\n\
-            Best regards,\n\
-            The SlateHub Team",
-            inviter_name, org_name, message_text, signup_url, org_name
-        );
-
-        let message_html = match message {
-            Some(msg) if !msg.is_empty() => format!(
-                r#"<div style="background-color: #f5f5f5; border-left: 3px solid #eb5437; padding: 15px 20px; margin: 20px 0; border-radius: 4px;">
-            <p style="font-size: 14px; color: #666; margin: 0 0 5px 0; font-weight: 600;">{} says:</p>
-            <p style="font-size: 15px; color: #333; margin: 0; font-style: italic;">"{}"</p>
-        </div>"#,
-                inviter_name,
-                ammonia::clean(msg)
-            ),
-            _ => String::new(),
-        };
-
-        let html_body = format!(
-            r#"<!DOCTYPE html>
-<html>
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-</head>
-<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Helvetica, Arial, sans-serif; line-height: 1.6; color: #333; max-width: 600px; margin: 0 auto; padding: 20px;">
-    <div style="background-color: #171717; border-radius: 8px; padding: 30px; margin-bottom: 20px;">
-        <h1 style="color: #d6d8ca; margin-top: 0;">You're Invited!</h1>
-        <p style="font-size: 16px; color: #d6d8ca;">{} has invited you to join <strong>{}</strong> on SlateHub.</p>
-    </div>
-
-    <div style="background-color: #ffffff; border: 1px solid #e0e0e0; border-radius: 8px; padding: 30px;">
-        <p style="font-size: 16px; margin-bottom: 20px;">
-            SlateHub is the production networking platform for film, TV, and media professionals.
-        </p>
-
-        {}
-
-        <div style="text-align: center; margin: 30px 0;">
-            <a href="{}" style="display: inline-block; background-color: #eb5437; color: white; padding: 14px 36px; text-decoration: none; border-radius: 6px; font-weight: bold; font-size: 16px;">Create Your Account</a>
-        </div>
-
-        <p style="font-size: 14px; color: #666; margin-top: 20px;">
-            Once you sign up and verify your email, you'll automatically be added to {}.
-        </p>
-
-        <p style="font-size: 14px; color: #999; margin-top: 20px;">
-            If you weren't expecting this invitation, you can safely ignore this email.
-        </p>
-    </div>
-
-    <div style="margin-top: 30px; padding-top: 20px; border-top: 1px solid #e0e0e0; text-align: center; color: #999; font-size: 12px;">
-        <p>&copy; 2024 SlateHub. All rights reserved.</p>
-    </div>
-</body>
-</html>"#,
-            inviter_name, org_name, message_html, signup_url, org_name
-        );
+        let email = invitation_email(org_name, inviter_name, signup_url, message)?;
+        self.send_multipart(to_email, None, &email).await
+    }
 
-        self.send_email(to_email, None, &subject, Some(&text_body), Some(&html_body))
+    /// Send a due-soon or overdue reminder to an equipment renter. Copy is
+    /// built by [`rental_reminder_email`].
+    ///
+    /// # Errors
+    ///
+    /// Same failure modes as the other senders (see [`Self::send_email`]),
+    /// plus [`EmailError::TemplateError`].
+    pub async fn send_rental_reminder_email(
+        &self,
+        to_email: &str,
+        reminder: &RentalReminder<'_>,
+    ) -> Result<()> {
+        let email = rental_reminder_email(reminder)?;
+        self.send_multipart(to_email, reminder.renter_name, &email)
             .await
     }
 
+    /// Forward a contact-form [`OrgInquiry`] to the organization's contact
+    /// address, with `Reply-To` set to the sender so the address itself is
+    /// only revealed if the organization replies. Copy is built by
+    /// [`org_contact_email`].
+    ///
+    /// # Errors
+    ///
+    /// Same failure modes as the other senders (see [`Self::send_email`]),
+    /// plus [`EmailError::TemplateError`].
+    pub async fn send_org_contact_email(
+        &self,
+        to_email: &str,
+        inquiry: &OrgInquiry<'_>,
+    ) -> Result<()> {
+        let email = org_contact_email(inquiry)?;
+        self.dispatch(OutgoingEmail {
+            to_email,
+            to_name: Some(inquiry.org_name),
+            subject: &email.subject,
+            text_body: Some(&email.text_body),
+            html_body: Some(&email.html_body),
+            from_email: None,
+            from_name: None,
+            cc: None,
+            reply_to: Some(inquiry.sender_email),
+        })
+        .await
+    }
+
     /// Send a generic notification email (e.g., new message notification).
     /// The caller supplies ready-made text and HTML bodies; this just
     /// forwards them to Mailjet unchanged.
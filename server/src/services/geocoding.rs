@@ -0,0 +1,402 @@
+//! Forward geocoding of free-text profile locations.
+//!
+//! Resolves strings like "LA" or "Atlanta, GA" to a [`GeoLocation`]
+//! (city / region / country plus coordinates) through a Nominatim-compatible
+//! `/search` endpoint. Reads `GEOCODING_URL` once into a process-wide
+//! `LazyLock`; when it is unset every lookup returns `None` and profiles keep
+//! only their display string. Lookups go through a [`GeocodeCache`] (misses
+//! included) and a process-wide throttle of one outbound request per
+//! [`REQUEST_INTERVAL`], the public Nominatim usage policy, so user-driven
+//! lookups and the startup passes share one budget. Also holds the great-circle helpers behind
+//! [`crate::models::person::Person::find_near`] and
+//! [`crate::models::location::LocationModel::find_near`], and the startup
+//! passes that geocode profiles and filming locations saved before they had
+//! coordinates.
+
+use reqwest;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use surrealdb::types::{RecordId, SurrealValue};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::db::DB;
+use crate::models::location::{Location, LocationModel};
+use crate::models::person::GeoLocation;
+
+#[derive(Error, Debug)]
+pub enum GeocodingError {
+    #[error("Geocoding request failed: {0}")]
+    HttpError(#[from] reqwest::Error),
+    #[error("Geocoding not configured")]
+    NotConfigured,
+    #[error("Geocoder returned unusable coordinates: {0}")]
+    BadCoordinates(String),
+}
+
+type Result<T> = std::result::Result<T, GeocodingError>;
+
+/// Mean Earth radius used for great-circle distances.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Least time between outbound geocoder requests; the public Nominatim
+/// usage policy allows one per second.
+pub const REQUEST_INTERVAL: Duration = Duration::from_millis(1100);
+
+/// How long a lookup result, found or not, is reused.
+const CACHE_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// Cached queries kept before expired ones are swept.
+const CACHE_CAPACITY: usize = 10_000;
+
+static GEOCODER: LazyLock<Option<GeocodingService>> =
+    LazyLock::new(|| match GeocodingService::from_env() {
+        Ok(svc) => {
+            info!("Geocoding service initialized ({})", svc.base_url);
+            Some(svc)
+        }
+        Err(e) => {
+            warn!("Geocoding service not available: {}", e);
+            None
+        }
+    });
+
+/// Get the global geocoding service instance, if configured.
+pub fn get_service() -> Result<&'static GeocodingService> {
+    GEOCODER.as_ref().ok_or(GeocodingError::NotConfigured)
+}
+
+pub struct GeocodingService {
+    base_url: String,
+    client: reqwest::Client,
+    cache: GeocodeCache,
+    /// When the last outbound request went out; held while waiting out
+    /// [`REQUEST_INTERVAL`], so concurrent lookups queue behind it.
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+}
+
+/// Geocoder results by query, each reused for a fixed TTL. Queries are
+/// matched case- and whitespace-insensitively; "no match" is cached too,
+/// so repeated junk queries don't reach the geocoder.
+pub struct GeocodeCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<String, (Instant, Option<GeoLocation>)>>,
+}
+
+impl GeocodeCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The cached result for `query` at `now`: `None` when absent or
+    /// expired, `Some(None)` for a cached miss.
+    pub fn get_at(&self, query: &str, now: Instant) -> Option<Option<GeoLocation>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&cache_key(query))
+            .filter(|(stored, _)| now.duration_since(*stored) < self.ttl)
+            .map(|(_, geo)| geo.clone())
+    }
+
+    /// Remember `result` for `query` as of `now`. Past capacity, expired
+    /// entries are swept first, and everything if that isn't enough.
+    pub fn insert_at(&self, query: &str, result: Option<GeoLocation>, now: Instant) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            entries.retain(|_, (stored, _)| now.duration_since(*stored) < ttl);
+            if entries.len() >= self.capacity {
+                entries.clear();
+            }
+        }
+        entries.insert(cache_key(query), (now, result));
+    }
+}
+
+fn cache_key(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// --- Nominatim response types ---
+
+#[derive(Debug, Deserialize)]
+struct NominatimPlace {
+    lat: String,
+    lon: String,
+    #[serde(default)]
+    address: NominatimAddress,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NominatimAddress {
+    city: Option<String>,
+    town: Option<String>,
+    village: Option<String>,
+    municipality: Option<String>,
+    state: Option<String>,
+    province: Option<String>,
+    region: Option<String>,
+    country: Option<String>,
+    country_code: Option<String>,
+}
+
+impl GeocodingService {
+    fn from_env() -> Result<Self> {
+        let base_url = env::var("GEOCODING_URL")
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .ok_or(GeocodingError::NotConfigured)?;
+
+        // Nominatim rejects requests without an identifying User-Agent.
+        let client = reqwest::Client::builder()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .timeout(Duration::from_secs(5))
+            .build()?;
+
+        Ok(Self {
+            base_url,
+            client,
+            cache: GeocodeCache::new(CACHE_TTL, CACHE_CAPACITY),
+            last_request: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    /// Resolve `query` to its best match, or `None` when nothing matches.
+    /// Served from the cache when possible; otherwise waits its turn under
+    /// the request throttle. Failed lookups aren't cached.
+    pub async fn geocode(&self, query: &str) -> Result<Option<GeoLocation>> {
+        if let Some(cached) = self.cache.get_at(query, Instant::now()) {
+            return Ok(cached);
+        }
+
+        {
+            let mut last = self.last_request.lock().await;
+            // Another lookup may have resolved the same query while we queued.
+            if let Some(cached) = self.cache.get_at(query, Instant::now()) {
+                return Ok(cached);
+            }
+            if let Some(previous) = *last {
+                tokio::time::sleep(REQUEST_INTERVAL.saturating_sub(previous.elapsed())).await;
+            }
+            *last = Some(Instant::now());
+        }
+
+        debug!("Geocoding location: {}", query);
+
+        let places: Vec<NominatimPlace> = self
+            .client
+            .get(format!("{}/search", self.base_url))
+            .query(&[
+                ("q", query),
+                ("format", "jsonv2"),
+                ("addressdetails", "1"),
+                ("limit", "1"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let geo = places.into_iter().next().map(to_geo_location).transpose()?;
+        self.cache.insert_at(query, geo.clone(), Instant::now());
+        Ok(geo)
+    }
+}
+
+fn to_geo_location(place: NominatimPlace) -> Result<GeoLocation> {
+    let lat: f64 = place
+        .lat
+        .parse()
+        .map_err(|_| GeocodingError::BadCoordinates(place.lat.clone()))?;
+    let lng: f64 = place
+        .lon
+        .parse()
+        .map_err(|_| GeocodingError::BadCoordinates(place.lon.clone()))?;
+    let a = place.address;
+
+    Ok(GeoLocation {
+        city: a.city.or(a.town).or(a.village).or(a.municipality),
+        region: a.state.or(a.province).or(a.region),
+        country: a.country,
+        country_code: a.country_code.map(|c| c.to_lowercase()),
+        lat,
+        lng,
+    })
+}
+
+/// Geocode `location` if a geocoder is configured, logging (not returning)
+/// failures so profile saves never fail on a flaky lookup.
+pub async fn geocode(location: &str) -> Option<GeoLocation> {
+    let location = location.trim();
+    if location.is_empty() {
+        return None;
+    }
+    let service = get_service().ok()?;
+    match service.geocode(location).await {
+        Ok(geo) => geo,
+        Err(e) => {
+            warn!("Failed to geocode {:?}: {}", location, e);
+            None
+        }
+    }
+}
+
+/// Great-circle (haversine) distance in kilometres.
+pub fn distance_km(a_lat: f64, a_lng: f64, b_lat: f64, b_lng: f64) -> f64 {
+    let d_lat = (b_lat - a_lat).to_radians();
+    let d_lng = (b_lng - a_lng).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2)
+        + a_lat.to_radians().cos() * b_lat.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+/// Latitude/longitude bounds enclosing every point within `radius_km` of
+/// the centre, as `(min_lat, max_lat, min_lng, max_lng)`. Longitude spans
+/// the whole globe near the poles or when the box would cross the
+/// antimeridian, so callers can always use plain range comparisons.
+pub fn bounding_box(lat: f64, lng: f64, radius_km: f64) -> (f64, f64, f64, f64) {
+    let d_lat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+    let min_lat = (lat - d_lat).max(-90.0);
+    let max_lat = (lat + d_lat).min(90.0);
+
+    let cos_lat = lat.to_radians().cos();
+    if max_lat >= 90.0 || min_lat <= -90.0 || cos_lat <= f64::EPSILON {
+        return (min_lat, max_lat, -180.0, 180.0);
+    }
+    let d_lng = (radius_km / (EARTH_RADIUS_KM * cos_lat)).to_degrees();
+    if lng - d_lng < -180.0 || lng + d_lng > 180.0 {
+        return (min_lat, max_lat, -180.0, 180.0);
+    }
+    (min_lat, max_lat, lng - d_lng, lng + d_lng)
+}
+
+/// Geocode every profile that has a display location but no structured
+/// one (profiles saved before `profile.geo` existed, or while the geocoder
+/// was down). Paced by the request throttle; a no-op without a geocoder.
+pub async fn backfill_profile_locations() {
+    if get_service().is_err() {
+        return;
+    }
+
+    #[derive(Debug, Deserialize, SurrealValue)]
+    struct PendingRow {
+        id: RecordId,
+        location: String,
+    }
+
+    let rows: Vec<PendingRow> = match DB
+        .query(
+            "SELECT id, profile.location AS location FROM person
+             WHERE profile.location IS NOT NONE AND profile.location != '' AND profile.geo IS NONE",
+        )
+        .await
+        .and_then(|mut r| r.take(0))
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!(error = %e, "Failed to query profiles pending geocoding");
+            return;
+        }
+    };
+
+    if rows.is_empty() {
+        info!("No profile locations to geocode");
+        return;
+    }
+
+    info!("Geocoding {} profile locations", rows.len());
+    let mut resolved = 0usize;
+    for row in rows {
+        if let Some(geo) = geocode(&row.location).await {
+            // Only write if the location is still the one we geocoded.
+            match DB
+                .query(
+                    "UPDATE $id SET profile.geo = $geo
+                     WHERE profile.location = $location AND profile.geo IS NONE",
+                )
+                .bind(("id", row.id.clone()))
+                .bind(("geo", geo))
+                .bind(("location", row.location))
+                .await
+            {
+                Ok(_) => resolved += 1,
+                Err(e) => warn!(error = %e, id = ?row.id, "Failed to store geocoded location"),
+            }
+        }
+    }
+    info!(
+        "Profile location geocoding complete ({} resolved)",
+        resolved
+    );
+}
+
+/// Geocode every filming location without coordinates (rows saved before
+/// migration 027, or while the geocoder was down), after parsing their
+/// structured address. Paced by the request throttle; the geocoding half
+/// is a no-op without a geocoder.
+pub async fn backfill_location_coordinates() {
+    match LocationModel::backfill_address_components().await {
+        Ok(0) => {}
+        Ok(n) => info!("Parsed structured addresses for {} locations", n),
+        Err(e) => warn!(error = %e, "Failed to backfill location addresses"),
+    }
+    if get_service().is_err() {
+        return;
+    }
+
+    let locations: Vec<Location> = match DB
+        .query("SELECT * FROM location WHERE geo IS NONE")
+        .await
+        .and_then(|mut r| r.take(0))
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!(error = %e, "Failed to query locations pending geocoding");
+            return;
+        }
+    };
+
+    if locations.is_empty() {
+        return;
+    }
+
+    info!("Geocoding {} filming locations", locations.len());
+    let mut resolved = 0usize;
+    for location in locations {
+        let query = location.geocode_query();
+        if let Some(geo) = geocode(&query).await {
+            // Only write if the address is still the one we geocoded.
+            match DB
+                .query("UPDATE $id SET geo = $geo WHERE address = $address AND geo IS NONE")
+                .bind(("id", location.id.clone()))
+                .bind(("geo", geo))
+                .bind(("address", location.address))
+                .await
+            {
+                Ok(_) => resolved += 1,
+                Err(e) => {
+                    warn!(error = %e, id = ?location.id, "Failed to store location coordinates")
+                }
+            }
+        }
+    }
+    info!("Location geocoding complete ({} resolved)", resolved);
+}
@@ -0,0 +1,155 @@
+//! Bounded pool for CPU-bound image work (decode, crop, resize, encode).
+//!
+//! Upload handlers hand their processing closures to [`run`], which runs
+//! each on tokio's blocking pool while holding one of
+//! `IMAGE_PROCESSING_CONCURRENCY` permits (default: the number of CPUs).
+//! Up to `IMAGE_PROCESSING_QUEUE` further jobs (default 32) wait for a
+//! permit; beyond that the request is turned away with a 429 and
+//! `Retry-After` instead of piling up, so a burst of uploads can't starve
+//! every core and stall the async runtime. Both variables are read once at
+//! first use.
+
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::rate_limit::RateLimitStatus;
+
+/// Jobs allowed to wait for a permit when `IMAGE_PROCESSING_QUEUE` is unset.
+const DEFAULT_QUEUE: usize = 32;
+
+/// How long a turned-away client is told to wait before retrying.
+const RETRY_AFTER: Duration = Duration::from_secs(5);
+
+static POOL: LazyLock<ImagePool> = LazyLock::new(|| {
+    let config = ImagePoolConfig::from_env();
+    info!(
+        "Image processing pool: {} concurrent, {} queued",
+        config.concurrency, config.queue
+    );
+    ImagePool::new(config)
+});
+
+/// Run `job` on the shared image pool.
+///
+/// # Errors
+///
+/// [`Error::RateLimited`] when the queue is full, `Error::Internal` if the
+/// blocking task panics, otherwise whatever `job` returns.
+pub async fn run<T, F>(job: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    POOL.run(job).await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImagePoolConfig {
+    /// Jobs processed at once
+    pub concurrency: usize,
+    /// Jobs allowed to wait for a free slot
+    pub queue: usize,
+}
+
+impl ImagePoolConfig {
+    /// `IMAGE_PROCESSING_CONCURRENCY` (at least 1) and
+    /// `IMAGE_PROCESSING_QUEUE`; unparseable values fall back to defaults.
+    pub fn from_env() -> Self {
+        let number = |var: &str| {
+            env::var(var)
+                .ok()
+                .and_then(|v| match v.trim().parse::<usize>() {
+                    Ok(n) => Some(n),
+                    Err(_) => {
+                        warn!("Ignoring invalid {}={:?}", var, v);
+                        None
+                    }
+                })
+        };
+        let cpus = thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            concurrency: number("IMAGE_PROCESSING_CONCURRENCY")
+                .unwrap_or(cpus)
+                .max(1),
+            queue: number("IMAGE_PROCESSING_QUEUE").unwrap_or(DEFAULT_QUEUE),
+        }
+    }
+}
+
+/// A concurrency cap plus a bounded wait queue in front of
+/// `spawn_blocking`.
+pub struct ImagePool {
+    config: ImagePoolConfig,
+    permits: Arc<Semaphore>,
+    waiting: AtomicUsize,
+}
+
+/// Leaves the wait queue when dropped, including when the waiting request
+/// is cancelled.
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ImagePool {
+    pub fn new(config: ImagePoolConfig) -> Self {
+        Self {
+            config,
+            permits: Arc::new(Semaphore::new(config.concurrency)),
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// See [`run`].
+    pub async fn run<T, F>(&self, job: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = match self.permits.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                if self.waiting.fetch_add(1, Ordering::SeqCst) >= self.config.queue {
+                    self.waiting.fetch_sub(1, Ordering::SeqCst);
+                    return Err(self.busy());
+                }
+                let _slot = QueueSlot(&self.waiting);
+                self.permits
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|e| Error::Internal(format!("image pool closed: {e}")))?
+            }
+        };
+
+        // The permit moves into the task: a cancelled request can't free its
+        // slot while the work it started is still running.
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            job()
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("image task join error: {e}")))?
+    }
+
+    fn busy(&self) -> Error {
+        Error::RateLimited {
+            message: "The server is busy processing images. Please try again in a few seconds."
+                .to_string(),
+            status: RateLimitStatus {
+                limit: self.config.concurrency + self.config.queue,
+                remaining: 0,
+                reset_after: RETRY_AFTER,
+            },
+        }
+    }
+}
@@ -130,7 +130,7 @@ impl InvitationService {
                     .await?;
 
                 // Send invitation email
-                let base_url = crate::config::app_url();
+                let base_url = crate::config::public_base_url();
                 let signup_url = format!(
                     "{}/signup?ref=invite&email={}",
                     base_url,
@@ -288,7 +288,7 @@ impl InvitationService {
                     .await?;
 
                 // Send invitation email
-                let base_url = crate::config::app_url();
+                let base_url = crate::config::public_base_url();
                 let signup_url = format!(
                     "{}/signup?ref=invite&email={}",
                     base_url,
@@ -184,7 +184,7 @@ pub async fn verified_profiles(limit: usize) -> Vec<VerifiedProfile> {
     let q = format!(
         "SELECT username, profile.name AS name, profile.headline AS headline, profile.avatar AS avatar \
          FROM person WHERE profile.avatar IS NOT NONE \
-         AND verification_status = 'identity' ORDER BY rand() LIMIT {};",
+         AND verification_status = 'identity' AND profile.is_private != true ORDER BY rand() LIMIT {};",
         limit
     );
     let rows: Vec<Row> = DB
@@ -18,10 +18,13 @@
 //! |---|---|
 //! | [`activity`] | Fire-and-forget `activity_event` rows for page views (spawned, never blocks) |
 //! | [`aristotle_runner`] | Concurrency-capped wrapper running the in-crate aristotle script-breakdown pipeline |
+//! | [`crew_list`] | Production crew list export as CSV or a paginated PDF table |
 //! | [`email`] | Transactional email (verification, password reset, invitations, feedback) via Postmark or Mailjet |
 //! | [`embedding`] | In-process fastembed (BGE-Large-EN-v1.5) vectors + embedding-text builders for semantic search |
 //! | [`feature_flag`] | Code-registered, DB-configured feature flags with four visibility states |
+//! | [`geocoding`] | Nominatim-compatible forward geocoding of profile locations (cached, throttled), distance helpers, startup backfill |
 //! | [`geodata`] | Static city → region/country lookup used to enrich embedding text |
+//! | [`image_pool`] | Bounded concurrency + wait queue for CPU-bound upload image processing |
 //! | [`invitation`] | Org/production invites for existing users (membership + notification) and unknown emails (pending row + email) |
 //! | [`landing`] | `/a/{campaign}` ad landing-page registry + fire-and-forget `landing_event` funnel writes + signup attribution |
 //! | [`listmonk`] | Best-effort newsletter subscription fan-out to a self-hosted Listmonk instance |
@@ -29,20 +32,28 @@
 //! | [`oidc_events`] | Outbound SSF/CAEP/RISC Security Event Tokens with a retrying background delivery worker |
 //! | [`oidc_keys`] | ed25519 OIDC signing keypair: generation, JWKS publication, id_token signing, rotation |
 //! | [`oidc_tokens`] | OIDC authorization codes + access/refresh tokens: issuance, hashing, lookup, revocation |
+//! | [`pdf`] | Paginated top-down text layout over printpdf's built-in Helvetica, shared by the PDF exports |
+//! | [`rental_agreement`] | PDF rental agreement / receipt for an equipment checkout |
 //! | [`s3`] | S3-compatible object storage (RustFS/MinIO/AWS) for uploads, downloads, presigned URLs |
 //! | [`search`] | Canonical layered search queries (people/orgs/locations/productions/jobs) shared by web + MCP |
 //! | [`search_log`] | Fire-and-forget `search_log` rows recording query + result counts |
 //! | [`search_utils`] | Query normalization and natural-language filter parsing for people search |
+//! | [`sitemap`] | Paged XML sitemap generation over public profiles, productions, orgs, locations, jobs |
+//! | [`storage_cleanup`] | Periodic sweep deleting (or dry-run logging) S3 objects no database row references |
 //! | [`stripe`] | Stripe Checkout + Identity + refunds over raw REST, with manual webhook signature verification |
+//! | [`svg`] | Script-free rasterization of uploaded SVG logos via resvg |
 //! | [`tmdb`] | TMDB person search + combined credits for profile credit import |
 //! | [`verification`] | Six-digit email-verification / password-reset codes in `verification_codes` |
 
 pub mod activity;
 pub mod aristotle_runner;
+pub mod crew_list;
 pub mod email;
 pub mod embedding;
 pub mod feature_flag;
+pub mod geocoding;
 pub mod geodata;
+pub mod image_pool;
 pub mod invitation;
 pub mod landing;
 pub mod listmonk;
@@ -50,12 +61,17 @@ pub mod notification_stream;
 pub mod oidc_events;
 pub mod oidc_keys;
 pub mod oidc_tokens;
+pub mod pdf;
 pub mod profile_completeness;
 pub mod profile_reminders;
+pub mod rental_agreement;
 pub mod s3;
 pub mod search;
 pub mod search_log;
 pub mod search_utils;
+pub mod sitemap;
+pub mod storage_cleanup;
 pub mod stripe;
+pub mod svg;
 pub mod tmdb;
 pub mod verification;
@@ -286,7 +286,7 @@ async fn build_set_jwt(client: &OauthClient, evt: &SecurityEventRow) -> Result<S
         events: Value,
     }
     let claims = SetClaims {
-        iss: crate::config::app_url(),
+        iss: crate::config::public_base_url(),
         aud: &client.client_id,
         iat: now,
         jti,
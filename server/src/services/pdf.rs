@@ -0,0 +1,130 @@
+//! Top-down text layout over printpdf, shared by the generated documents
+//! (the rental agreement and the crew list).
+//!
+//! A [`TextDocument`] owns the PDF and a cursor at the current baseline;
+//! writers place text at an x offset and move the cursor down, and
+//! [`TextDocument::ensure_space`] starts a new page when the next block
+//! wouldn't fit above the bottom margin. Only the built-in Helvetica faces
+//! are used, so no font assets ship with the server; they only cover
+//! Latin-1, so every string is transliterated to ASCII first
+//! (`"Zoë"` → `"Zoe"`).
+
+use std::io::BufWriter;
+
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+};
+
+use crate::error::Error;
+
+/// US Letter, portrait, in mm.
+pub const LETTER_PORTRAIT: (f32, f32) = (215.9, 279.4);
+/// US Letter, landscape, in mm.
+pub const LETTER_LANDSCAPE: (f32, f32) = (279.4, 215.9);
+
+/// A PDF being written top to bottom, one page after another.
+pub struct TextDocument {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    size: (f32, f32),
+    margin: f32,
+    pages: usize,
+    /// Baseline of the next line, in mm from the bottom of the page.
+    pub y: f32,
+}
+
+impl TextDocument {
+    /// An empty document titled `title` with one page of `size` (width,
+    /// height in mm) and the cursor at the top margin.
+    pub fn new(title: &str, size: (f32, f32), margin: f32) -> Result<Self, Error> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(size.0), Mm(size.1), "Layer 1");
+        let font_error =
+            |e: printpdf::Error| Error::Internal(format!("Failed to load PDF font: {e}"));
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(font_error)?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(font_error)?;
+        Ok(Self {
+            layer: doc.get_page(page).get_layer(layer),
+            doc,
+            regular,
+            bold,
+            size,
+            margin,
+            pages: 1,
+            y: size.1 - margin,
+        })
+    }
+
+    /// Pages written so far.
+    pub fn page_count(&self) -> usize {
+        self.pages
+    }
+
+    /// Draw `text` at the cursor's baseline, `x` mm from the left edge,
+    /// without moving the cursor.
+    pub fn text_at(&self, x: f32, text: &str, size: f32, bold: bool) {
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer
+            .use_text(deunicode::deunicode(text), size, Mm(x), Mm(self.y), font);
+    }
+
+    /// Start a new page unless `height` mm still fit above the bottom
+    /// margin. Returns whether a page was added, so callers can repeat
+    /// headers.
+    pub fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y - height >= self.margin {
+            return false;
+        }
+        let (page, layer) = self
+            .doc
+            .add_page(Mm(self.size.0), Mm(self.size.1), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.y = self.size.1 - self.margin;
+        self.pages += 1;
+        true
+    }
+
+    /// Write the document out as PDF bytes.
+    pub fn finish(self) -> Result<Vec<u8>, Error> {
+        let mut buf = BufWriter::new(Vec::new());
+        self.doc
+            .save(&mut buf)
+            .map_err(|e| Error::Internal(format!("Failed to write PDF: {e}")))?;
+        buf.into_inner()
+            .map_err(|e| Error::Internal(format!("Failed to write PDF: {e}")))
+    }
+}
+
+/// Greedy word wrap at `width` characters. Words longer than a line are
+/// left whole rather than split.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for raw in text.lines() {
+        let mut line = String::new();
+        for word in raw.split_whitespace() {
+            if !line.is_empty() && line.len() + 1 + word.len() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// `text` cut to at most `width` characters, ending in `...` when cut.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(width.saturating_sub(3)).collect();
+    format!("{}...", kept.trim_end())
+}
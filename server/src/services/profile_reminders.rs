@@ -90,7 +90,7 @@ pub async fn run(cfg: &ReminderConfig) {
     };
     let edit_url = format!(
         "{}/profile/edit",
-        crate::config::app_url().trim_end_matches('/')
+        crate::config::public_base_url().trim_end_matches('/')
     );
 
     for n in 1u8..=3 {
@@ -0,0 +1,148 @@
+//! Printable rental agreement / receipt for an equipment checkout.
+//!
+//! The route gathers a [`RentalAgreement`] (plain strings, already resolved
+//! from the rental, its equipment or kit, and the people involved) and
+//! [`render_pdf`] lays it out on US Letter pages through
+//! [`super::pdf::TextDocument`], continuing onto a new page when a long kit
+//! list or notes run past the bottom margin.
+
+use crate::error::Error;
+use crate::services::pdf::{self, TextDocument};
+
+/// Standard terms printed under the checkout details.
+pub const TERMS: &[&str] = &[
+    "The renter accepts the items listed above in the stated condition and is responsible for them until they are checked back in.",
+    "Items must be returned by the expected return date in the same condition, allowing for normal wear.",
+    "Loss, theft, or damage beyond normal wear must be reported to the owner immediately and is the renter's responsibility.",
+    "Items may not be sublet or lent to anyone else without the owner's written consent.",
+];
+
+/// Everything printed on the agreement, already formatted for display.
+#[derive(Debug, Clone, Default)]
+pub struct RentalAgreement {
+    /// Rental record id, shown as the agreement reference.
+    pub reference: String,
+    /// `"Equipment"` or `"Kit"`.
+    pub item_kind: String,
+    pub item_name: String,
+    /// Label/value lines under the item name (serial number, model, ...).
+    pub item_details: Vec<(String, String)>,
+    /// For kits, the names of the items in the kit.
+    pub kit_items: Vec<String>,
+    pub owner: String,
+    pub renter: String,
+    pub checkout_by: String,
+    pub checkout_date: String,
+    pub expected_return_date: Option<String>,
+    pub checkout_condition: String,
+    pub checkout_notes: Option<String>,
+}
+
+const MARGIN: f32 = 20.0;
+const BODY_SIZE: f32 = 10.0;
+const LINE_HEIGHT: f32 = 5.0;
+/// Helvetica at 10pt fits roughly this many characters across the text
+/// column; used for word wrapping.
+const WRAP_CHARS: usize = 95;
+
+/// Render the agreement as PDF bytes.
+pub fn render_pdf(agreement: &RentalAgreement) -> Result<Vec<u8>, Error> {
+    let title = format!("Rental Agreement {}", agreement.reference);
+    let mut page = Writer {
+        doc: TextDocument::new(&title, pdf::LETTER_PORTRAIT, MARGIN)?,
+    };
+
+    page.line("Equipment Rental Agreement", 16.0, true);
+    page.line(
+        &format!("Reference: {}", agreement.reference),
+        BODY_SIZE,
+        false,
+    );
+    page.gap();
+
+    page.heading(&agreement.item_kind);
+    page.line(&agreement.item_name, 12.0, true);
+    for (label, value) in &agreement.item_details {
+        page.field(label, value);
+    }
+    if !agreement.kit_items.is_empty() {
+        page.field("Includes", &agreement.kit_items.join(", "));
+    }
+    page.gap();
+
+    page.heading("Parties");
+    page.field("Owner", &agreement.owner);
+    page.field("Renter", &agreement.renter);
+    page.field("Checked out by", &agreement.checkout_by);
+    page.gap();
+
+    page.heading("Checkout");
+    page.field("Checkout date", &agreement.checkout_date);
+    page.field(
+        "Expected return",
+        agreement
+            .expected_return_date
+            .as_deref()
+            .unwrap_or("Not specified"),
+    );
+    page.field("Condition", &agreement.checkout_condition);
+    if let Some(notes) = agreement.checkout_notes.as_deref() {
+        page.field("Notes", notes);
+    }
+    page.gap();
+
+    page.heading("Terms");
+    for (i, term) in TERMS.iter().enumerate() {
+        page.paragraph(&format!("{}. {}", i + 1, term));
+    }
+    page.gap();
+    page.gap();
+
+    // Keep both signature lines together on the last page.
+    page.doc.ensure_space(LINE_HEIGHT * 3.5);
+    page.line(
+        "Renter signature: ______________________________    Date: ______________",
+        BODY_SIZE,
+        false,
+    );
+    page.gap();
+    page.line(
+        "Owner signature:  ______________________________    Date: ______________",
+        BODY_SIZE,
+        false,
+    );
+
+    page.doc.finish()
+}
+
+/// Single-column writer over the agreement pages.
+struct Writer {
+    doc: TextDocument,
+}
+
+impl Writer {
+    fn line(&mut self, text: &str, size: f32, bold: bool) {
+        let height = LINE_HEIGHT * size / BODY_SIZE;
+        self.doc.ensure_space(height);
+        self.doc.text_at(MARGIN, text, size, bold);
+        self.doc.y -= height;
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.line(&text.to_uppercase(), BODY_SIZE, true);
+    }
+
+    fn field(&mut self, label: &str, value: &str) {
+        self.paragraph(&format!("{label}: {value}"));
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        for line in pdf::wrap(text, WRAP_CHARS) {
+            self.line(&line, BODY_SIZE, false);
+        }
+    }
+
+    fn gap(&mut self) {
+        self.doc.y -= LINE_HEIGHT / 2.0;
+    }
+}
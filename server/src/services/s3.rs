@@ -14,6 +14,7 @@
 
 use bytes::Bytes;
 use s3::{Bucket, BucketConfiguration, Region, creds::Credentials};
+use std::collections::HashMap;
 use tracing::{debug, info};
 
 use crate::error::{Error, Result};
@@ -54,6 +55,14 @@ impl Default for S3Config {
 // Service
 // ---------------------------------------------------------------------------
 
+/// One object from a bucket listing.
+#[derive(Debug, Clone)]
+pub struct StoredObject {
+    pub key: String,
+    pub size: u64,
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 /// Generic S3-compatible storage service.
 pub struct S3Service {
     bucket: Box<Bucket>,
@@ -199,10 +208,30 @@ impl S3Service {
     }
 
     /// Generate a presigned URL for downloading (expires in 24 hours).
-    pub async fn generate_download_url(&self, key: &str) -> Result<String> {
+    ///
+    /// The URL overrides the response's `Content-Type` and
+    /// `Content-Disposition` (see [`content_disposition`]), so S3 serves the
+    /// file the same way the media proxy would rather than with whatever
+    /// metadata the object happened to be stored with.
+    pub async fn generate_download_url(
+        &self,
+        key: &str,
+        content_type: &str,
+        filename: &str,
+    ) -> Result<String> {
         debug!("Generating presigned download URL for: {}", key);
+        let overrides = HashMap::from([
+            (
+                "response-content-type".to_string(),
+                content_type.to_string(),
+            ),
+            (
+                "response-content-disposition".to_string(),
+                content_disposition(content_type, filename),
+            ),
+        ]);
         self.bucket
-            .presign_get(key, 86400, None)
+            .presign_get(key, 86400, Some(overrides))
             .await
             .map_err(|e| Error::Internal(format!("Failed to generate presigned URL: {e}")))
     }
@@ -235,6 +264,24 @@ impl S3Service {
         Ok(keys)
     }
 
+    /// Object count and total size in bytes across the whole bucket.
+    pub async fn usage(&self) -> Result<(u64, u64)> {
+        let results = self
+            .bucket
+            .list("".to_string(), None)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to list S3 objects: {e}")))?;
+
+        let (mut objects, mut bytes) = (0u64, 0u64);
+        for page in results {
+            for obj in page.contents {
+                objects += 1;
+                bytes += obj.size;
+            }
+        }
+        Ok((objects, bytes))
+    }
+
     /// List all object keys under a given prefix (e.g. `profiles/abc/`).
     pub async fn list_under_prefix(&self, prefix: &str) -> Result<Vec<String>> {
         let results = self
@@ -252,6 +299,28 @@ impl S3Service {
         Ok(keys)
     }
 
+    /// Every object under `prefix` with its size and last-modified time
+    /// (`None` when the backend's timestamp doesn't parse).
+    pub async fn list_objects_under_prefix(&self, prefix: &str) -> Result<Vec<StoredObject>> {
+        let results = self
+            .bucket
+            .list(prefix.to_string(), None)
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to list S3 prefix '{prefix}': {e}")))?;
+
+        Ok(results
+            .into_iter()
+            .flat_map(|page| page.contents)
+            .map(|obj| StoredObject {
+                last_modified: chrono::DateTime::parse_from_rfc3339(&obj.last_modified)
+                    .ok()
+                    .map(|t| t.with_timezone(&chrono::Utc)),
+                key: obj.key,
+                size: obj.size,
+            })
+            .collect())
+    }
+
     /// Delete every object under a given prefix. Returns `(deleted, failed)`.
     /// Best-effort: per-key failures are logged but don't abort the loop.
     pub async fn delete_under_prefix(&self, prefix: &str) -> Result<(usize, usize)> {
@@ -333,6 +402,67 @@ impl S3Service {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Serving headers
+// ---------------------------------------------------------------------------
+
+/// Whether browsers render `content_type` in place (images, audio, video)
+/// rather than needing it downloaded.
+pub fn renders_inline(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    ["image/", "audio/", "video/"]
+        .iter()
+        .any(|prefix| essence.starts_with(prefix))
+}
+
+/// The `Content-Disposition` to serve a stored file with: `inline` when
+/// [`renders_inline`], otherwise `attachment` so documents such as resumes
+/// download instead of being opened — or mis-rendered — in the tab.
+///
+/// `filename` gets an ASCII-only `filename=` fallback, plus the original
+/// percent-encoded in `filename*` when the two differ.
+pub fn content_disposition(content_type: &str, filename: &str) -> String {
+    let disposition = if renders_inline(content_type) {
+        "inline"
+    } else {
+        "attachment"
+    };
+
+    let filename = filename.trim();
+    let fallback: String = filename
+        .chars()
+        .map(|c| match c {
+            ' ' | '!' | '#'..='[' | ']'..='~' => c,
+            _ => '_',
+        })
+        .collect();
+    let fallback = if fallback.trim_matches(['_', '.', ' ']).is_empty() {
+        "download".to_string()
+    } else {
+        fallback
+    };
+
+    if fallback == filename || filename.is_empty() {
+        format!("{disposition}; filename=\"{fallback}\"")
+    } else {
+        format!(
+            "{disposition}; filename=\"{fallback}\"; filename*=UTF-8''{}",
+            urlencoding::encode(filename)
+        )
+    }
+}
+
+/// The last path segment of `key` (`profiles/abc/resume.pdf` →
+/// `resume.pdf`), the filename to offer when nothing better is recorded.
+pub fn key_filename(key: &str) -> &str {
+    key.rsplit('/').next().unwrap_or(key)
+}
+
 // ---------------------------------------------------------------------------
 // Global singleton
 // ---------------------------------------------------------------------------
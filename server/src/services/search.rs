@@ -16,7 +16,7 @@ use tracing::error;
 use crate::config::SearchWeights;
 use crate::db::DB;
 use crate::error::{Error, Result};
-use crate::services::search_utils::ParsedQuery;
+use crate::services::search_utils::{MatchContext, ParsedQuery, match_context};
 
 // ---------------------------------------------------------------------------
 // Result types
@@ -34,7 +34,11 @@ pub struct PersonSearchResult {
     pub avatar_url: Option<String>,
     pub embedding_text: Option<String>,
     pub verification_status: String,
+    /// An [`crate::models::person::AvailabilityStatus`] string.
+    pub availability_status: Option<String>,
     pub score: f64,
+    #[serde(skip)]
+    pub matched: Option<MatchContext>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -48,6 +52,8 @@ pub struct OrganizationSearchResult {
     pub embedding_text: Option<String>,
     pub verified: bool,
     pub score: f64,
+    #[serde(skip)]
+    pub matched: Option<MatchContext>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -62,6 +68,8 @@ pub struct LocationSearchResult {
     pub profile_photo: Option<String>,
     pub embedding_text: Option<String>,
     pub score: f64,
+    #[serde(skip)]
+    pub matched: Option<MatchContext>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -76,6 +84,8 @@ pub struct ProductionSearchResult {
     pub poster_photo: Option<String>,
     pub embedding_text: Option<String>,
     pub score: f64,
+    #[serde(skip)]
+    pub matched: Option<MatchContext>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -89,6 +99,8 @@ pub struct JobSearchResult {
     pub role_count: i64,
     pub embedding_text: Option<String>,
     pub score: f64,
+    #[serde(skip)]
+    pub matched: Option<MatchContext>,
 }
 
 // ---------------------------------------------------------------------------
@@ -170,6 +182,10 @@ pub async fn search_people(
         );
     }
 
+    if parsed.availability.is_some() {
+        hard_parts.push("profile.availability_status = $availability_filter".to_string());
+    }
+
     let has_hard_filters = !hard_parts.is_empty();
     let hard_filter = if has_hard_filters {
         format!("AND {}", hard_parts.join(" AND "))
@@ -209,6 +225,7 @@ pub async fn search_people(
             profile.location AS location,
             profile.skills AS skills,
             profile.avatar AS avatar_url,
+            profile.availability_status AS availability_status,
             embedding_text,
             verification_status ?? 'none' AS verification_status,
             <float> (
@@ -224,6 +241,7 @@ pub async fn search_people(
             ) AS score
         FROM person
         WHERE verification_status != 'unverified'
+            AND profile.is_private != true
             AND {text_vector_gate}
             {hard_filter}
         ORDER BY score DESC
@@ -256,6 +274,10 @@ pub async fn search_people(
         .bind(("hair_filter", parsed.hair_color.clone().unwrap_or_default()))
         .bind(("eye_filter", parsed.eye_color.clone().unwrap_or_default()))
         .bind(("body_filter", parsed.body_type.clone().unwrap_or_default()))
+        .bind((
+            "availability_filter",
+            parsed.availability.clone().unwrap_or_default(),
+        ))
         .await
         .map_err(|e| {
             error!(error = %e, table = "person", "Search query failed");
@@ -267,7 +289,7 @@ pub async fn search_people(
         Error::Database(e.to_string())
     })?;
 
-    let results = rows
+    let mut results: Vec<PersonSearchResult> = rows
         .into_iter()
         .filter(|r| r["score"].as_f64().unwrap_or(0.0) > 0.0)
         .map(|r| PersonSearchResult {
@@ -288,10 +310,26 @@ pub async fn search_people(
             avatar_url: json_opt_str(&r, "avatar_url"),
             embedding_text: json_opt_str(&r, "embedding_text"),
             verification_status: json_str_or(&r, "verification_status", "none"),
+            availability_status: json_opt_str(&r, "availability_status"),
             score: r["score"].as_f64().unwrap_or(0.0),
+            matched: None,
         })
         .collect();
 
+    for result in &mut results {
+        let skills = result.skills.join(", ");
+        result.matched = match_context(
+            &parsed.cleaned,
+            &[
+                ("name", Some(&result.name)),
+                ("headline", result.headline.as_deref()),
+                ("skills", Some(&skills)),
+                ("location", result.location.as_deref()),
+                ("bio", result.bio.as_deref()),
+            ],
+        );
+    }
+
     Ok(results)
 }
 
@@ -388,7 +426,7 @@ pub async fn search_organizations(
         Error::Database(e.to_string())
     })?;
 
-    let results = rows
+    let mut results: Vec<OrganizationSearchResult> = rows
         .into_iter()
         .filter(|r| r["score"].as_f64().unwrap_or(0.0) > 0.0)
         .map(|r| OrganizationSearchResult {
@@ -401,9 +439,21 @@ pub async fn search_organizations(
             embedding_text: json_opt_str(&r, "embedding_text"),
             verified: r["verified"].as_bool().unwrap_or(false),
             score: r["score"].as_f64().unwrap_or(0.0),
+            matched: None,
         })
         .collect();
 
+    for result in &mut results {
+        result.matched = match_context(
+            params.query,
+            &[
+                ("name", Some(&result.name)),
+                ("description", result.description.as_deref()),
+                ("location", result.location.as_deref()),
+            ],
+        );
+    }
+
     Ok(results)
 }
 
@@ -516,7 +566,7 @@ pub async fn search_locations(
         Error::Database(e.to_string())
     })?;
 
-    let results = rows
+    let mut results: Vec<LocationSearchResult> = rows
         .into_iter()
         .filter(|r| r["score"].as_f64().unwrap_or(0.0) > 0.0)
         .map(|r| LocationSearchResult {
@@ -530,9 +580,23 @@ pub async fn search_locations(
             profile_photo: json_opt_str(&r, "profile_photo"),
             embedding_text: json_opt_str(&r, "embedding_text"),
             score: r["score"].as_f64().unwrap_or(0.0),
+            matched: None,
         })
         .collect();
 
+    for result in &mut results {
+        result.matched = match_context(
+            params.query,
+            &[
+                ("name", Some(&result.name)),
+                ("address", Some(&result.address)),
+                ("city", Some(&result.city)),
+                ("state", Some(&result.state)),
+                ("description", result.description.as_deref()),
+            ],
+        );
+    }
+
     Ok(results)
 }
 
@@ -626,7 +690,7 @@ pub async fn search_productions(
         Error::Database(e.to_string())
     })?;
 
-    let results = rows
+    let mut results: Vec<ProductionSearchResult> = rows
         .into_iter()
         .filter(|r| r["score"].as_f64().unwrap_or(0.0) > 0.0)
         .map(|r| ProductionSearchResult {
@@ -640,9 +704,21 @@ pub async fn search_productions(
             poster_photo: json_opt_str(&r, "poster_photo"),
             embedding_text: json_opt_str(&r, "embedding_text"),
             score: r["score"].as_f64().unwrap_or(0.0),
+            matched: None,
         })
         .collect();
 
+    for result in &mut results {
+        result.matched = match_context(
+            params.query,
+            &[
+                ("title", Some(&result.title)),
+                ("description", result.description.as_deref()),
+                ("location", result.location.as_deref()),
+            ],
+        );
+    }
+
     Ok(results)
 }
 
@@ -771,9 +847,21 @@ pub async fn search_jobs(
             role_count: r["role_count"].as_i64().unwrap_or(0),
             embedding_text: json_opt_str(r, "embedding_text"),
             score,
+            matched: None,
         });
     }
 
+    for result in &mut results {
+        result.matched = match_context(
+            params.query,
+            &[
+                ("title", Some(&result.title)),
+                ("description", Some(&result.description)),
+                ("location", result.location.as_deref()),
+            ],
+        );
+    }
+
     Ok(results)
 }
 
@@ -61,6 +61,9 @@ pub struct ParsedQuery {
     pub hair_color: Option<String>,
     pub eye_color: Option<String>,
     pub body_type: Option<String>,
+    /// An [`crate::models::person::AvailabilityStatus`] string. Never parsed
+    /// from the text; set by callers with an explicit availability filter.
+    pub availability: Option<String>,
     pub cleaned: String,
 }
 
@@ -193,3 +196,98 @@ pub fn extract_location(query: &str) -> (Option<String>, String) {
         (None, query.to_string())
     }
 }
+
+/// Characters of context kept on each side of a match in a result snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+/// Which field of a search result matched the query, with a short excerpt
+/// around the first matching term so the UI can highlight it. Every
+/// `services::search` result type carries one as `matched`, `None` for
+/// purely semantic hits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchContext {
+    /// Name of the matched field (`name`, `bio`, `description` …).
+    pub field: String,
+    /// Excerpt of that field around the first matching term.
+    pub snippet: String,
+}
+
+/// Find the first field (in the given priority order) containing one of the
+/// query's terms and cut a snippet around it.
+///
+/// Matching is case-insensitive on whole terms of two or more characters, so
+/// it mirrors the `CONTAINS` gate the search queries use. Returns `None` when
+/// nothing matched textually (e.g. a purely semantic hit).
+pub fn match_context(query: &str, fields: &[(&str, Option<&str>)]) -> Option<MatchContext> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 2)
+        .map(str::to_lowercase)
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    for (field, text) in fields {
+        let Some(text) = text.filter(|t| !t.is_empty()) else {
+            continue;
+        };
+        // Lowercase char by char, remembering where each lowercased byte came
+        // from so offsets map back onto the original text.
+        let mut lower = String::with_capacity(text.len());
+        let mut origin = Vec::with_capacity(text.len());
+        for (i, c) in text.char_indices() {
+            for l in c.to_lowercase() {
+                lower.push(l);
+                origin.resize(lower.len(), i);
+            }
+        }
+
+        let first = terms
+            .iter()
+            .filter_map(|t| lower.find(t.as_str()).map(|pos| (pos, pos + t.len())))
+            .min_by_key(|(start, _)| *start);
+        if let Some((start, end)) = first {
+            let start = origin[start];
+            let end = origin.get(end).copied().unwrap_or(text.len());
+            return Some(MatchContext {
+                field: field.to_string(),
+                snippet: snippet_around(text, start, end),
+            });
+        }
+    }
+    None
+}
+
+/// Excerpt `text` around the byte range `start..end`, trimming to word
+/// boundaries and marking cut ends with an ellipsis.
+fn snippet_around(text: &str, start: usize, end: usize) -> String {
+    let before = text[..start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_CHARS - 1)
+        .map(|(i, _)| i);
+    let after = text[end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_CHARS)
+        .map(|(i, _)| end + i);
+
+    let from = match before {
+        Some(i) => text[i..start].find(' ').map(|s| i + s + 1).unwrap_or(i),
+        None => 0,
+    };
+    let to = match after {
+        Some(i) => text[end..i].rfind(' ').map(|s| end + s).unwrap_or(i),
+        None => text.len(),
+    };
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(text[from..to].trim());
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
@@ -0,0 +1,253 @@
+//! XML sitemap generation for `/sitemap.xml` and `/sitemaps/{file}`.
+//!
+//! Entries come from the DB per [`Section`]: static pages, public profiles,
+//! productions, public organizations, public locations, and open jobs, each
+//! with `lastmod` taken from the row's `updated_at`. Private entities
+//! (private profiles, non-public orgs and locations) are never listed.
+//!
+//! While the whole site fits in one file ([`MAX_URLS_PER_SITEMAP`]) the
+//! route serves a single `<urlset>`; past that it serves a
+//! `<sitemapindex>` pointing at per-section pages like
+//! `/sitemaps/people-2.xml`. Rendering is pure so it can be tested without
+//! a DB; the two async functions do the querying.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use surrealdb::types::SurrealValue;
+
+use crate::{db::DB, error::Error, html::escape_attr};
+
+/// URLs per sitemap file. The protocol allows 50,000; staying well under
+/// keeps each response small enough to build in one query.
+pub const MAX_URLS_PER_SITEMAP: usize = 10_000;
+
+/// Static pages as `(path, priority, changefreq)`.
+const STATIC_PAGES: &[(&str, &str, &str)] = &[
+    ("/", "1.0", "weekly"),
+    ("/about", "0.8", "monthly"),
+    ("/search", "0.9", "daily"),
+    ("/people", "0.9", "daily"),
+    ("/productions", "0.9", "daily"),
+    ("/orgs", "0.8", "daily"),
+    ("/locations", "0.8", "daily"),
+    ("/jobs", "0.9", "daily"),
+    ("/terms", "0.3", "yearly"),
+    ("/privacy", "0.3", "yearly"),
+    ("/impressum", "0.3", "yearly"),
+];
+
+/// One group of sitemap URLs; also the unit of pagination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Pages,
+    People,
+    Productions,
+    Organizations,
+    Locations,
+    Jobs,
+}
+
+impl Section {
+    pub const ALL: [Section; 6] = [
+        Section::Pages,
+        Section::People,
+        Section::Productions,
+        Section::Organizations,
+        Section::Locations,
+        Section::Jobs,
+    ];
+
+    /// Name used in paged file names (`people-1.xml`).
+    pub fn slug(self) -> &'static str {
+        match self {
+            Section::Pages => "pages",
+            Section::People => "people",
+            Section::Productions => "productions",
+            Section::Organizations => "orgs",
+            Section::Locations => "locations",
+            Section::Jobs => "jobs",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.slug() == slug)
+    }
+
+    /// `(path prefix, changefreq, priority)` for DB-backed sections.
+    fn url_shape(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Section::Pages => ("/", "weekly", "0.5"),
+            Section::People => ("/", "weekly", "0.7"),
+            Section::Productions => ("/productions/", "weekly", "0.6"),
+            Section::Organizations => ("/orgs/", "weekly", "0.6"),
+            Section::Locations => ("/locations/", "weekly", "0.5"),
+            Section::Jobs => ("/jobs/", "daily", "0.6"),
+        }
+    }
+
+    /// `(URL key expression, FROM ... WHERE ... clause)` for DB-backed sections.
+    /// Visibility rules here must match the public view routes. Flags are
+    /// compared with their schema default (`DEFAULT false`) as the fallback,
+    /// so rows written before a flag existed are classified the same way
+    /// the view routes classify them.
+    fn source(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Section::Pages => None,
+            Section::People => Some((
+                "username",
+                "FROM person WHERE verification_status != 'unverified' AND profile.is_private != true",
+            )),
+            Section::Productions => Some(("slug", "FROM production")),
+            Section::Organizations => {
+                Some(("slug", "FROM organization WHERE (public ?? false) = true"))
+            }
+            Section::Locations => Some((
+                "<string> meta::id(id)",
+                "FROM location WHERE (is_public ?? false) = true",
+            )),
+            Section::Jobs => Some((
+                "<string> meta::id(id)",
+                "FROM job_posting WHERE status = 'open'",
+            )),
+        }
+    }
+}
+
+/// One `<url>` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapUrl {
+    pub path: String,
+    pub lastmod: Option<DateTime<Utc>>,
+    pub changefreq: &'static str,
+    pub priority: &'static str,
+}
+
+#[derive(Debug, Deserialize, SurrealValue)]
+struct Row {
+    key: String,
+    #[serde(default)]
+    #[surreal(default)]
+    lastmod: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, SurrealValue)]
+struct CountResult {
+    count: u64,
+}
+
+/// Parse a paged file name like `people-2.xml` into its section and
+/// 1-based page number.
+pub fn parse_file_name(name: &str) -> Option<(Section, usize)> {
+    let stem = name.strip_suffix(".xml")?;
+    let (slug, page) = stem.rsplit_once('-')?;
+    let page: usize = page.parse().ok()?;
+    if page == 0 {
+        return None;
+    }
+    Some((Section::from_slug(slug)?, page))
+}
+
+/// Number of files a section with `count` URLs is split into.
+pub fn page_count(count: usize) -> usize {
+    count.div_ceil(MAX_URLS_PER_SITEMAP).max(1)
+}
+
+/// Render a `<urlset>` document.
+pub fn render_urlset(base: &str, urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for url in urls {
+        xml.push_str(&format!(
+            "  <url>\n    <loc>{}{}</loc>\n",
+            base,
+            escape_attr(&url.path)
+        ));
+        if let Some(lastmod) = url.lastmod {
+            xml.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                lastmod.format("%Y-%m-%d")
+            ));
+        }
+        xml.push_str(&format!(
+            "    <changefreq>{}</changefreq>\n    <priority>{}</priority>\n  </url>\n",
+            url.changefreq, url.priority
+        ));
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+/// Render a `<sitemapindex>` listing every page of every section, given
+/// each section's URL count.
+pub fn render_index(base: &str, counts: &[(Section, usize)]) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for &(section, count) in counts {
+        if count == 0 {
+            continue;
+        }
+        for page in 1..=page_count(count) {
+            xml.push_str(&format!(
+                "  <sitemap>\n    <loc>{}/sitemaps/{}-{}.xml</loc>\n  </sitemap>\n",
+                base,
+                section.slug(),
+                page
+            ));
+        }
+    }
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
+
+/// How many URLs a section currently holds.
+pub async fn section_count(section: Section) -> Result<usize, Error> {
+    let Some((_, from)) = section.source() else {
+        return Ok(STATIC_PAGES.len());
+    };
+    let result: Option<CountResult> = DB
+        .query(format!("SELECT count() AS count {from} GROUP ALL"))
+        .await?
+        .take(0)?;
+    Ok(result.map(|r| r.count as usize).unwrap_or(0))
+}
+
+/// One page (1-based) of a section's URLs, ordered by key so pages are
+/// stable between crawls.
+pub async fn section_urls(section: Section, page: usize) -> Result<Vec<SitemapUrl>, Error> {
+    let (prefix, changefreq, priority) = section.url_shape();
+    let Some((key, from)) = section.source() else {
+        let today = Utc::now();
+        return Ok(STATIC_PAGES
+            .iter()
+            .map(|&(path, priority, changefreq)| SitemapUrl {
+                path: path.to_string(),
+                lastmod: Some(today),
+                changefreq,
+                priority,
+            })
+            .collect());
+    };
+
+    let offset = page.saturating_sub(1) * MAX_URLS_PER_SITEMAP;
+    let rows: Vec<Row> = DB
+        .query(format!(
+            "SELECT {key} AS key, updated_at AS lastmod {from}
+             ORDER BY key ASC LIMIT $limit START $offset"
+        ))
+        .bind(("limit", MAX_URLS_PER_SITEMAP as i64))
+        .bind(("offset", offset as i64))
+        .await?
+        .take(0)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SitemapUrl {
+            path: format!("{prefix}{}", row.key),
+            lastmod: row.lastmod,
+            changefreq,
+            priority,
+        })
+        .collect())
+}
@@ -0,0 +1,467 @@
+//! Periodic reconciliation of uploaded files against the database.
+//!
+//! Replacing an avatar, logo, or poster writes a new key and leaves the old
+//! object behind, so the bucket slowly fills with files nothing points to.
+//! [`run`] lists everything under the upload prefixes ([`SWEPT_PREFIXES`]),
+//! collects every key the database still references
+//! ([`collect_referenced_files`], shared with the admin file-integrity page),
+//! and deletes the unreferenced objects older than the grace period — an
+//! upload lands in S3 a moment before the row that references it.
+//!
+//! Configured from the environment ([`StorageCleanupConfig::from_env`]).
+//! Dry-run by default: the sweep only logs what it would delete until
+//! `STORAGE_CLEANUP_DRY_RUN=false`.
+
+use std::collections::HashSet;
+use std::env;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use surrealdb::types::SurrealValue;
+use tracing::{debug, info, warn};
+
+use crate::db::DB;
+use crate::error::{Error, Result};
+use crate::services::s3::{StoredObject, s3};
+
+/// Key prefixes the upload handlers write under. Anything else in the
+/// bucket (test fixtures, manual uploads) is never touched.
+pub const SWEPT_PREFIXES: &[&str] = &["profiles/", "organizations/", "locations/", "productions/"];
+
+/// Schedule and safety configuration for the sweep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageCleanupConfig {
+    /// Hours between sweeps; 0 disables the task.
+    pub interval_hours: u32,
+    /// Minimum age, in hours, before an unreferenced object is deleted.
+    pub grace_hours: u32,
+    /// Log what would be deleted without deleting anything. On by default
+    /// so the first sweeps on an existing bucket can be reviewed.
+    pub dry_run: bool,
+}
+
+impl StorageCleanupConfig {
+    /// Read from the environment. Defaults: daily, 24-hour grace, dry run.
+    pub fn from_env() -> Self {
+        fn num(var: &str, default: u32) -> u32 {
+            env::var(var)
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or(default)
+        }
+        Self {
+            interval_hours: num("STORAGE_CLEANUP_INTERVAL_HOURS", 24),
+            grace_hours: num("STORAGE_CLEANUP_GRACE_HOURS", 24),
+            dry_run: env::var("STORAGE_CLEANUP_DRY_RUN")
+                .map(|v| !v.trim().eq_ignore_ascii_case("false"))
+                .unwrap_or(true),
+        }
+    }
+}
+
+/// What one sweep found and did.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CleanupReport {
+    /// Objects listed under the swept prefixes
+    pub scanned: usize,
+    /// Unreferenced objects past the grace period
+    pub orphaned: usize,
+    /// Their combined size
+    pub orphaned_bytes: u64,
+    pub deleted: usize,
+    pub failed: usize,
+}
+
+/// Run one sweep. Nothing is deleted if S3 isn't configured or the
+/// reference scan fails — a partial view of the database would make live
+/// files look orphaned.
+pub async fn run(cfg: &StorageCleanupConfig) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    let s3 = match s3() {
+        Ok(s3) => s3,
+        Err(e) => {
+            debug!("storage_cleanup: S3 not configured, skipping ({e})");
+            return report;
+        }
+    };
+    let referenced = match collect_referenced_files().await {
+        Ok((keys, _)) => keys,
+        Err(e) => {
+            warn!(error = %e, "storage_cleanup: reference scan failed, skipping");
+            return report;
+        }
+    };
+
+    let mut objects = Vec::new();
+    for prefix in SWEPT_PREFIXES {
+        match s3.list_objects_under_prefix(prefix).await {
+            Ok(listed) => objects.extend(listed),
+            Err(e) => {
+                warn!(prefix, error = %e, "storage_cleanup: listing failed, skipping");
+                return report;
+            }
+        }
+    }
+    report.scanned = objects.len();
+
+    let grace = Duration::hours(i64::from(cfg.grace_hours));
+    let orphans = select_orphans(&objects, &referenced, Utc::now(), grace);
+    report.orphaned = orphans.len();
+    report.orphaned_bytes = orphans.iter().map(|o| o.size).sum();
+
+    for orphan in orphans {
+        if cfg.dry_run {
+            info!(key = %orphan.key, size = orphan.size, "storage_cleanup: would delete orphan");
+            continue;
+        }
+        match s3.delete_file(&orphan.key).await {
+            Ok(()) => report.deleted += 1,
+            Err(e) => {
+                report.failed += 1;
+                warn!(key = %orphan.key, error = %e, "storage_cleanup: delete failed");
+            }
+        }
+    }
+
+    info!(
+        scanned = report.scanned,
+        orphaned = report.orphaned,
+        orphaned_bytes = report.orphaned_bytes,
+        deleted = report.deleted,
+        failed = report.failed,
+        dry_run = cfg.dry_run,
+        "storage_cleanup: sweep complete"
+    );
+    report
+}
+
+/// Objects no key in `referenced` covers and last modified at least `grace`
+/// before `now`. Objects without a parseable timestamp are kept.
+pub fn select_orphans<'a>(
+    objects: &'a [StoredObject],
+    referenced: &HashSet<String>,
+    now: DateTime<Utc>,
+    grace: Duration,
+) -> Vec<&'a StoredObject> {
+    objects
+        .iter()
+        .filter(|o| !referenced.contains(&o.key))
+        .filter(|o| o.last_modified.is_some_and(|t| now - t >= grace))
+        .collect()
+}
+
+/// The S3 keys a stored file URL keeps alive: the key itself (the URL minus
+/// `/api/media/`) plus the thumbnail the upload handlers write beside it,
+/// which no column records for older uploads. Thumbnails are `thumb_` +
+/// the main filename, or `thumb_{id}.jpg` when the main file is
+/// `{kind}_{id}.{ext}` (logos, posters). Empty for an empty URL.
+pub fn referenced_keys(url: &str) -> Vec<String> {
+    let key = url.strip_prefix("/api/media/").unwrap_or(url);
+    if key.is_empty() {
+        return Vec::new();
+    }
+    let mut keys = vec![key.to_string()];
+    let (dir, filename) = key.rsplit_once('/').unwrap_or(("", key));
+    if !filename.starts_with("thumb_") {
+        let dir = if dir.is_empty() {
+            String::new()
+        } else {
+            format!("{dir}/")
+        };
+        keys.push(format!("{dir}thumb_{filename}"));
+        let stem = filename.rsplit_once('.').map_or(filename, |(stem, _)| stem);
+        if let Some((_, id)) = stem.split_once('_') {
+            keys.push(format!("{dir}thumb_{id}.jpg"));
+        }
+    }
+    keys
+}
+
+/// A file reference from the database: S3 key + source info for broken link reporting.
+#[derive(Debug, Clone)]
+pub struct FileRef {
+    pub key: String,
+    pub entity: String, // e.g. "person:abc123"
+    pub field: String,  // e.g. "avatar", "photos[2].url"
+}
+
+/// Collect all referenced S3 keys from every table that stores file URLs.
+/// Returns (set of all keys for orphan detection, vec of all refs for broken link detection).
+pub async fn collect_referenced_files() -> Result<(HashSet<String>, Vec<FileRef>)> {
+    let mut keys: HashSet<String> = HashSet::new();
+    let mut refs: Vec<FileRef> = Vec::new();
+
+    #[derive(Debug, Deserialize, SurrealValue)]
+    struct PhotoRef {
+        url: Option<String>,
+        thumbnail_url: Option<String>,
+    }
+
+    /// Helper: extract key, insert into set, and record the reference.
+    fn track(
+        keys: &mut HashSet<String>,
+        refs: &mut Vec<FileRef>,
+        url: &str,
+        entity: &str,
+        field: &str,
+    ) {
+        let mut referenced = referenced_keys(url).into_iter();
+        let Some(key) = referenced.next() else {
+            return;
+        };
+        keys.insert(key.clone());
+        keys.extend(referenced);
+        refs.push(FileRef {
+            key,
+            entity: entity.to_string(),
+            field: field.to_string(),
+        });
+    }
+
+    // Person: avatar + photo gallery
+    {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct PersonFiles {
+            id: String,
+            name: Option<String>,
+            avatar: Option<String>,
+            photos: Option<Vec<PhotoRef>>,
+        }
+
+        let rows: Vec<PersonFiles> = DB
+            .query("SELECT <string> id AS id, name, profile.avatar AS avatar, profile.photos AS photos FROM person")
+            .await
+            .map_err(|e| Error::Database(e.to_string()))?
+            .take(0)
+            .unwrap_or_default();
+
+        for row in rows {
+            let entity = format!("person {} ({})", row.name.as_deref().unwrap_or("?"), row.id);
+            if let Some(avatar) = row.avatar {
+                track(&mut keys, &mut refs, &avatar, &entity, "avatar");
+            }
+            if let Some(photos) = row.photos {
+                for (i, photo) in photos.iter().enumerate() {
+                    if let Some(ref url) = photo.url {
+                        track(
+                            &mut keys,
+                            &mut refs,
+                            url,
+                            &entity,
+                            &format!("photos[{}]", i),
+                        );
+                    }
+                    if let Some(ref thumb) = photo.thumbnail_url {
+                        track(
+                            &mut keys,
+                            &mut refs,
+                            thumb,
+                            &entity,
+                            &format!("photos[{}].thumb", i),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Organization: logo
+    {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct OrgFiles {
+            id: String,
+            name: Option<String>,
+            logo: Option<String>,
+        }
+
+        let rows: Vec<OrgFiles> = DB
+            .query("SELECT <string> id AS id, name, logo FROM organization")
+            .await
+            .map_err(|e| Error::Database(e.to_string()))?
+            .take(0)
+            .unwrap_or_default();
+
+        for row in rows {
+            let entity = format!("org {} ({})", row.name.as_deref().unwrap_or("?"), row.id);
+            if let Some(logo) = row.logo {
+                track(&mut keys, &mut refs, &logo, &entity, "logo");
+            }
+        }
+    }
+
+    // Production: header_photo, poster_photo, poster_url, photo gallery
+    {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct ProdFiles {
+            id: String,
+            title: Option<String>,
+            header_photo: Option<String>,
+            poster_photo: Option<String>,
+            poster_url: Option<String>,
+            photos: Option<Vec<PhotoRef>>,
+        }
+
+        let rows: Vec<ProdFiles> = DB
+            .query("SELECT <string> id AS id, title, header_photo, poster_photo, poster_url, photos FROM production")
+            .await
+            .map_err(|e| Error::Database(e.to_string()))?
+            .take(0)
+            .unwrap_or_default();
+
+        for row in rows {
+            let entity = format!(
+                "production {} ({})",
+                row.title.as_deref().unwrap_or("?"),
+                row.id
+            );
+            if let Some(v) = row.header_photo {
+                track(&mut keys, &mut refs, &v, &entity, "header_photo");
+            }
+            if let Some(v) = row.poster_photo {
+                track(&mut keys, &mut refs, &v, &entity, "poster_photo");
+            }
+            if let Some(v) = row.poster_url
+                && v.starts_with("/api/media/")
+            {
+                track(&mut keys, &mut refs, &v, &entity, "poster_url");
+            }
+            if let Some(photos) = row.photos {
+                for (i, photo) in photos.iter().enumerate() {
+                    if let Some(ref url) = photo.url {
+                        track(
+                            &mut keys,
+                            &mut refs,
+                            url,
+                            &entity,
+                            &format!("photos[{}]", i),
+                        );
+                    }
+                    if let Some(ref thumb) = photo.thumbnail_url {
+                        track(
+                            &mut keys,
+                            &mut refs,
+                            thumb,
+                            &entity,
+                            &format!("photos[{}].thumb", i),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Location: profile_photo + photo gallery
+    {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct LocFiles {
+            id: String,
+            name: Option<String>,
+            profile_photo: Option<String>,
+            photos: Option<Vec<PhotoRef>>,
+        }
+
+        let rows: Vec<LocFiles> = DB
+            .query("SELECT <string> id AS id, name, profile_photo, photos FROM location")
+            .await
+            .map_err(|e| Error::Database(e.to_string()))?
+            .take(0)
+            .unwrap_or_default();
+
+        for row in rows {
+            let entity = format!(
+                "location {} ({})",
+                row.name.as_deref().unwrap_or("?"),
+                row.id
+            );
+            if let Some(v) = row.profile_photo {
+                track(&mut keys, &mut refs, &v, &entity, "profile_photo");
+            }
+            if let Some(photos) = row.photos {
+                for (i, photo) in photos.iter().enumerate() {
+                    if let Some(ref url) = photo.url {
+                        track(
+                            &mut keys,
+                            &mut refs,
+                            url,
+                            &entity,
+                            &format!("photos[{}]", i),
+                        );
+                    }
+                    if let Some(ref thumb) = photo.thumbnail_url {
+                        track(
+                            &mut keys,
+                            &mut refs,
+                            thumb,
+                            &entity,
+                            &format!("photos[{}].thumb", i),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Production scripts: file_url / file_key
+    {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct ScriptFiles {
+            id: String,
+            file_url: Option<String>,
+            file_key: Option<String>,
+        }
+
+        let rows: Vec<ScriptFiles> = DB
+            .query("SELECT <string> id AS id, file_url, file_key FROM production_script")
+            .await
+            .map_err(|e| Error::Database(e.to_string()))?
+            .take(0)
+            .unwrap_or_default();
+
+        for row in rows {
+            let entity = format!("script ({})", row.id);
+            if let Some(v) = row.file_url {
+                track(&mut keys, &mut refs, &v, &entity, "file_url");
+            }
+            if let Some(v) = row.file_key {
+                keys.insert(v.clone());
+                refs.push(FileRef {
+                    key: v,
+                    entity: entity.clone(),
+                    field: "file_key".to_string(),
+                });
+            }
+        }
+    }
+
+    // Media table: every upload's main file and thumbnail (uri linked from
+    // person.profile.media_other, person.profile.resume)
+    {
+        #[derive(Debug, Deserialize, SurrealValue)]
+        struct MediaFiles {
+            id: String,
+            uri: Option<String>,
+            object_key: Option<String>,
+            thumbnail_key: Option<String>,
+        }
+
+        let rows: Vec<MediaFiles> = DB
+            .query("SELECT <string> id AS id, uri, object_key, thumbnail_key FROM media")
+            .await
+            .map_err(|e| Error::Database(e.to_string()))?
+            .take(0)
+            .unwrap_or_default();
+
+        for row in rows {
+            let entity = format!("media ({})", row.id);
+            if let Some(v) = row.uri {
+                track(&mut keys, &mut refs, &v, &entity, "uri");
+            }
+            // Plain keys: deleting one of these orphans the row, but a
+            // missing one isn't a broken link anyone follows.
+            keys.extend(row.object_key);
+            keys.extend(row.thumbnail_key);
+        }
+    }
+
+    Ok((keys, refs))
+}
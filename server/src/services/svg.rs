@@ -0,0 +1,53 @@
+//! Rasterization of user-supplied SVG (organization logos).
+//!
+//! An SVG is a document, not an image: served from our origin it can carry
+//! `<script>`, event handlers, and `<foreignObject>` HTML that run on
+//! anyone who opens the file. Rather than sanitize markup, uploads are
+//! rendered to pixels with resvg — which never executes scripts — and only
+//! the raster is kept. `<image>` references to files or URLs are refused
+//! so an upload can't pull local files into the rendered output; embedded
+//! `data:` images still render. Text needs fonts, and none are loaded, so
+//! logos should have their text converted to outlines.
+//!
+//! CPU-bound; call from inside `image_pool::run`.
+
+use bytes::Bytes;
+use resvg::usvg;
+
+use crate::error::{Error, Result};
+
+/// Render `data` to a PNG whose longer side is `max_side` pixels, over an
+/// opaque white background (logos are re-encoded as JPEG downstream).
+///
+/// # Errors
+///
+/// `Error::BadRequest` if `data` isn't a parseable SVG or has no area.
+pub fn rasterize(data: &[u8], max_side: u32) -> Result<Bytes> {
+    let options = usvg::Options {
+        image_href_resolver: usvg::ImageHrefResolver {
+            resolve_data: usvg::ImageHrefResolver::default_data_resolver(),
+            resolve_string: Box::new(|_, _| None),
+        },
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_data(data, &options)
+        .map_err(|e| Error::bad_request(format!("Invalid SVG file: {}", e)))?;
+
+    let size = tree.size();
+    let scale = max_side as f32 / size.width().max(size.height());
+    let width = (size.width() * scale).round().max(1.0) as u32;
+    let height = (size.height() * scale).round().max(1.0) as u32;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| Error::bad_request("SVG has no drawable area"))?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    pixmap
+        .encode_png()
+        .map(Bytes::from)
+        .map_err(|e| Error::Internal(format!("Failed to encode rasterized SVG: {}", e)))
+}
@@ -3,25 +3,29 @@
 //! This module is the bridge between route handlers and the files under
 //! `templates/`: every page's `#[derive(Template)]` context struct, the
 //! [`BaseContext`] carrying the fields every page needs (app name, year,
-//! asset-cache-busting version, active nav item, signed-in user), and the
-//! [`filters`] module Askama resolves `{{ x|filter }}` calls against.
+//! asset-cache-busting version, active nav item, signed-in user, CSP
+//! nonce), and the [`filters`] module Askama resolves `{{ x|filter }}`
+//! calls against.
 //!
 //! Handlers build a [`BaseContext`], then construct their template struct
-//! with [`crate::with_base!`] so the five shared fields are spread without
+//! with [`crate::with_base!`] so the six shared fields are spread without
 //! boilerplate.
 
 use askama::Template;
 use chrono::Datelike;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use surrealdb::types::RecordId;
 
 use crate::db::DB;
 use crate::models::likes::{LikedLocation, LikedPerson};
 use crate::models::notification::NotificationModel;
-use crate::models::person::SessionUser;
+use crate::models::person::{AvailabilityStatus, SessionUser};
+use crate::record_id_ext::{RecordIdExt, normalize_record_id};
 
-/// Construct a page-template struct, spreading the five [`BaseContext`]
-/// fields (`app_name`, `year`, `version`, `active_page`, `user`) so call
-/// sites list only what's specific to the page.
+/// Construct a page-template struct, spreading the six [`BaseContext`]
+/// fields (`app_name`, `year`, `version`, `active_page`, `user`, `nonce`)
+/// so call sites list only what's specific to the page.
 ///
 /// ```ignore
 /// let base = BaseContext::new().with_page("productions").with_user(user).await;
@@ -45,6 +49,7 @@ macro_rules! with_base {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             $($field $(: $value)?),*
         }
     }};
@@ -59,10 +64,36 @@ macro_rules! with_base {
 pub(crate) mod filters {
     use askama::Values;
 
-    /// Convert a relative path to an absolute URL using APP_URL
+    /// The current request's translation of a message key
+    /// (`{{ "auth.invalid_credentials"|t }}`), see [`crate::i18n`].
+    #[askama::filter_fn]
+    pub fn t(key: &str, _: &dyn Values) -> askama::Result<String> {
+        Ok(crate::i18n::t(key))
+    }
+
+    /// An instant in the request's timezone, see [`crate::i18n::local_datetime`].
+    #[askama::filter_fn]
+    pub fn local_datetime(
+        at: &chrono::DateTime<chrono::Utc>,
+        _: &dyn Values,
+    ) -> askama::Result<String> {
+        Ok(crate::i18n::local_datetime(at))
+    }
+
+    /// An instant's date in the request's timezone, see
+    /// [`crate::i18n::local_date`].
+    #[askama::filter_fn]
+    pub fn local_date(
+        at: &chrono::DateTime<chrono::Utc>,
+        _: &dyn Values,
+    ) -> askama::Result<String> {
+        Ok(crate::i18n::local_date(at))
+    }
+
+    /// Convert a relative path to an absolute URL using the public base URL
     #[askama::filter_fn]
     pub fn abs_url(path: &str, _: &dyn Values) -> askama::Result<String> {
-        let base = crate::config::app_url();
+        let base = crate::config::public_base_url();
         Ok(format!("{}{}", base, path))
     }
 
@@ -120,17 +151,35 @@ pub(crate) mod filters {
         Ok(crate::text::format_bytes_i64(*bytes))
     }
 
-    /// Format an ISO 8601 date string as relative time: "2 days ago", "in 4 weeks", "now"
+    /// Relative time in the request's language — "2 days ago", "in 4 weeks",
+    /// "just now" — for a `DateTime<Utc>` or an RFC 3339 string. See
+    /// [`crate::i18n::time_ago`].
     #[askama::filter_fn]
-    pub fn time_ago(date_str: &str, _: &dyn Values) -> askama::Result<String> {
-        use chrono::{DateTime, Utc};
-        use chrono_humanize::HumanTime;
+    pub fn time_ago<T: Timestamp + ?Sized>(value: &T, _: &dyn Values) -> askama::Result<String> {
+        Ok(crate::i18n::time_ago(&value.timestamp()?))
+    }
+
+    /// What [`time_ago`] accepts.
+    pub trait Timestamp {
+        fn timestamp(&self) -> askama::Result<chrono::DateTime<chrono::Utc>>;
+    }
 
-        let dt = date_str
-            .parse::<DateTime<Utc>>()
-            .map_err(|_| askama::Error::Fmt)?;
+    impl Timestamp for chrono::DateTime<chrono::Utc> {
+        fn timestamp(&self) -> askama::Result<chrono::DateTime<chrono::Utc>> {
+            Ok(*self)
+        }
+    }
 
-        Ok(HumanTime::from(dt).to_string())
+    impl Timestamp for str {
+        fn timestamp(&self) -> askama::Result<chrono::DateTime<chrono::Utc>> {
+            self.parse().map_err(|_| askama::Error::Fmt)
+        }
+    }
+
+    impl Timestamp for String {
+        fn timestamp(&self) -> askama::Result<chrono::DateTime<chrono::Utc>> {
+            self.as_str().timestamp()
+        }
     }
 }
 
@@ -193,40 +242,82 @@ impl User {
 
     /// Generate initials from a name or username
     fn generate_initials(name: &str) -> String {
-        let parts: Vec<&str> = name.split_whitespace().collect();
-
-        if parts.len() >= 2 {
-            // Use first letter of first and last name
-            let first = parts[0].chars().next().unwrap_or('?');
-            let last = parts[parts.len() - 1].chars().next().unwrap_or('?');
-            format!("{}{}", first, last).to_uppercase()
-        } else if !parts.is_empty() {
-            // Use first two letters of single name
-            let chars: Vec<char> = parts[0].chars().take(2).collect();
-            if chars.len() == 2 {
-                format!("{}{}", chars[0], chars[1]).to_uppercase()
-            } else if chars.len() == 1 {
-                format!("{}", chars[0]).to_uppercase()
-            } else {
-                "??".to_string()
-            }
-        } else {
-            "??".to_string()
+        crate::avatar::initials(name)
+    }
+
+    /// Build `User`s for a batch of people (roster rows, directory cards)
+    /// with one query, rather than one avatar lookup per person.
+    ///
+    /// `ids` may be `person:…` record ids or bare keys. Unknown ids are
+    /// skipped and the rest keep their input order. Viewer-specific fields
+    /// (`notification_count`, `can_manage_productions`) are left at their
+    /// defaults, since none of these people is the one browsing.
+    pub async fn batch_from_profiles(ids: &[String]) -> Vec<Self> {
+        let rids: Vec<RecordId> = ids
+            .iter()
+            .map(|id| normalize_record_id(id, "person"))
+            .collect();
+        if rids.is_empty() {
+            return Vec::new();
         }
+
+        let rows: Vec<serde_json::Value> = match DB
+            .query(
+                "SELECT <string> id AS id, (name ?? profile.name ?? username) AS name, email, \
+                 profile.avatar, verification_status, is_admin FROM person WHERE id IN $ids",
+            )
+            .bind(("ids", rids.clone()))
+            .await
+            .and_then(|mut response| response.take(0))
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::error!("Failed to batch-load avatars: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let by_id: HashMap<&str, &serde_json::Value> = rows
+            .iter()
+            .filter_map(|row| Some((row.get("id")?.as_str()?, row)))
+            .collect();
+
+        rids.iter()
+            .filter_map(|rid| {
+                let id = rid.to_raw_string();
+                let row = by_id.get(id.as_str())?;
+                let name = row
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let (avatar_url, is_identity_verified, is_admin) = avatar_fields(row);
+                let avatar = avatar_url
+                    .clone()
+                    .unwrap_or_else(|| format!("/api/avatar?id={}", id));
+                Some(User {
+                    initials: Self::generate_initials(&name),
+                    email: row
+                        .get("email")
+                        .and_then(|e| e.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    id,
+                    name,
+                    avatar,
+                    avatar_url,
+                    notification_count: 0,
+                    is_identity_verified,
+                    is_admin,
+                    can_manage_productions: false,
+                })
+            })
+            .collect()
     }
 
     /// Fetch avatar URL, verification status, and admin flag from the database
     async fn fetch_avatar_and_verification(person_id: &str) -> (Option<String>, bool, bool) {
-        // Ensure we have full record ID
-        let person_rid = if person_id.starts_with("person:") {
-            surrealdb::types::RecordId::parse_simple(person_id).ok()
-        } else {
-            Some(surrealdb::types::RecordId::new("person", person_id))
-        };
-
-        let Some(rid) = person_rid else {
-            return (None, false, false);
-        };
+        let rid = normalize_record_id(person_id, "person");
 
         // Query for the person's avatar URL, verification status, and admin flag
         if let Ok(mut response) = DB
@@ -236,27 +327,33 @@ impl User {
             && let Ok(result) = response.take::<Option<serde_json::Value>>(0)
             && let Some(data) = result
         {
-            let avatar_url = data
-                .get("profile")
-                .and_then(|p| p.get("avatar"))
-                .and_then(|a| a.as_str())
-                .map(|s| s.to_string());
-            let is_verified = data
-                .get("verification_status")
-                .and_then(|v| v.as_str())
-                .map(|s| s == "identity")
-                .unwrap_or(false);
-            let is_admin = data
-                .get("is_admin")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            return (avatar_url, is_verified, is_admin);
+            return avatar_fields(&data);
         }
 
         (None, false, false)
     }
 }
 
+/// Avatar URL, identity verification, and admin flag from a person row
+/// selected with `profile.avatar, verification_status, is_admin`.
+fn avatar_fields(data: &serde_json::Value) -> (Option<String>, bool, bool) {
+    let avatar_url = data
+        .get("profile")
+        .and_then(|p| p.get("avatar"))
+        .and_then(|a| a.as_str())
+        .map(|s| s.to_string());
+    let is_verified = data
+        .get("verification_status")
+        .and_then(|v| v.as_str())
+        .map(|s| s == "identity")
+        .unwrap_or(false);
+    let is_admin = data
+        .get("is_admin")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    (avatar_url, is_verified, is_admin)
+}
+
 /// Common template data
 #[derive(Debug, Clone)]
 pub struct CommonData {
@@ -288,6 +385,7 @@ pub struct IndexTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub production_count: u32,
     pub user_count: u32,
     pub connection_count: u32,
@@ -298,7 +396,8 @@ pub struct IndexTemplate {
 pub struct Activity {
     pub user: String,
     pub action: String,
-    pub time: String,
+    /// When it happened; render with the `time_ago` filter.
+    pub at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Ad landing page "When you're not on set" — served at `/a/not-on-set`.
@@ -312,6 +411,7 @@ pub struct NotOnSetLandingTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     /// Analytics campaign id — hidden in the email form + consumed by the pixel.
     pub campaign_id: String,
     /// YouTube id for the founders video.
@@ -341,6 +441,7 @@ pub struct LoginTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub error: Option<String>,
     pub redirect_to: Option<String>,
 }
@@ -354,6 +455,7 @@ pub struct InviteLandingTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub target_name: String,
     pub target_type: String,
     pub production_roles: Option<Vec<String>>,
@@ -371,6 +473,7 @@ pub struct SignupTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub error: Option<String>,
     pub prefill_email: Option<String>,
     pub redirect: Option<String>,
@@ -391,6 +494,7 @@ pub struct EmailVerificationTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub error: Option<String>,
     pub success: Option<String>,
     pub email: Option<String>,
@@ -410,6 +514,7 @@ pub struct VerifyConversionTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub pixel_id: Option<String>,
     pub redirect: String,
 }
@@ -423,6 +528,7 @@ pub struct ForgotPasswordTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub error: Option<String>,
     pub success: Option<String>,
     pub email: Option<String>,
@@ -437,12 +543,99 @@ pub struct ResetPasswordTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub error: Option<String>,
     pub success: Option<String>,
     pub email: Option<String>,
     pub code: Option<String>,
 }
 
+/// Longest `og:description` / `twitter:description` we emit, in characters.
+pub const SHARE_DESCRIPTION_MAX: usize = 200;
+
+/// OpenGraph / Twitter-card metadata for a shareable entity page, computed
+/// by the handler and rendered into the `_layout.html` meta blocks. Entity
+/// templates hold it as `share: Option<ShareMeta>`; `None` means the page is
+/// private, so only the site-wide defaults (plus `noindex`) are emitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareMeta {
+    /// Page title without the ` | {app_name}` suffix the template adds.
+    pub title: String,
+    /// Plain text, whitespace-collapsed and capped at
+    /// [`SHARE_DESCRIPTION_MAX`] characters.
+    pub description: String,
+    /// Absolute canonical URL.
+    pub url: String,
+    /// Absolute image URL (profile card, logo, poster); the site default
+    /// card is used when `None`.
+    pub image: Option<String>,
+}
+
+impl ShareMeta {
+    /// `path` is root-relative (e.g. `/orgs/acme`).
+    pub fn new(title: impl Into<String>, description: &str, path: &str) -> Self {
+        Self {
+            title: title.into(),
+            description: share_description(description),
+            url: absolute_url(path),
+            image: None,
+        }
+    }
+
+    /// Attach an image, resolving root-relative upload URLs against
+    /// the public base URL — crawlers ignore relative `og:image` values.
+    pub fn with_image(mut self, image: Option<&str>) -> Self {
+        self.image = image.filter(|url| !url.is_empty()).map(absolute_url);
+        self
+    }
+
+    /// Share card for a person's profile page. Call after
+    /// [`ProfileData::redact_for_visitor`] so hidden fields can't leak.
+    pub fn for_profile(profile: &ProfileData, app_name: &str) -> Self {
+        let title = match &profile.headline {
+            Some(headline) => format!("{} — {}", profile.name, headline),
+            None => profile.name.clone(),
+        };
+        let mut description = profile
+            .bio
+            .clone()
+            .or_else(|| profile.headline.clone())
+            .unwrap_or_default();
+        if let Some(location) = &profile.location {
+            description.push_str(&format!(" Based in {}.", location));
+        }
+        description.push_str(&format!(
+            " Connect and collaborate with {} on {}.",
+            profile.name, app_name
+        ));
+
+        Self::new(title, &description, &format!("/{}", profile.username))
+            .with_image(Some(&format!("/api/og/profile/{}", profile.username)))
+    }
+}
+
+/// Resolve a root-relative URL against the public base URL; absolute URLs pass through.
+pub fn absolute_url(url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("{}{}", crate::config::public_base_url(), url)
+    }
+}
+
+/// Collapse whitespace and cap at [`SHARE_DESCRIPTION_MAX`] characters,
+/// ending with an ellipsis when cut.
+fn share_description(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= SHARE_DESCRIPTION_MAX {
+        return collapsed;
+    }
+    let mut cut: String = collapsed.chars().take(SHARE_DESCRIPTION_MAX - 1).collect();
+    cut.truncate(cut.trim_end().len());
+    cut.push('…');
+    cut
+}
+
 /// Profile page template
 #[derive(Template)]
 #[template(path = "persons/profile.html")]
@@ -452,8 +645,11 @@ pub struct ProfileTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub profile: ProfileData,
     pub is_liked: bool,
+    /// Social-share meta; `None` for the owner's view of a private profile.
+    pub share: Option<ShareMeta>,
     /// Owner-only completeness meter (`None` for other viewers).
     pub completeness: Option<crate::services::profile_completeness::ProfileCompleteness>,
 }
@@ -472,7 +668,9 @@ pub struct ProfileData {
     pub website: Option<String>,
     pub skills: Vec<String>,
     pub languages: Vec<String>,
+    /// Free-text availability note.
     pub availability: Option<String>,
+    pub availability_status: Option<AvailabilityStatus>,
     pub involvements: Vec<InvolvementDisplay>,
     pub education: Vec<Education>,
     pub social_links: Vec<SocialLinkDisplay>,
@@ -516,6 +714,7 @@ impl ProfileData {
             && self.location.is_none()
             && self.website.is_none()
             && self.availability.is_none()
+            && self.availability_status.is_none()
             && self.skills.is_empty()
             && self.languages.is_empty()
             && self.involvements.is_empty()
@@ -523,6 +722,48 @@ impl ProfileData {
             && self.reels.is_empty()
             && self.photos.is_empty()
     }
+
+    /// Strip what a visitor (anyone but the owner) may not see: contact
+    /// details unless the owner opted in via `is_public`, plus each field
+    /// named in the owner's `private_fields` (keys from
+    /// [`crate::models::person::PRIVATE_FIELD_OPTIONS`]). Done here rather
+    /// than in the template so hidden values never reach the rendered page.
+    pub fn redact_for_visitor(&mut self, private_fields: &[String]) {
+        if !self.is_public {
+            self.email.clear();
+            self.phone = None;
+        }
+        for field in private_fields {
+            match field.as_str() {
+                "birthday" => self.birthday = None,
+                "gender" => self.gender = None,
+                "measurements" => {
+                    self.height_mm = None;
+                    self.weight_kg = None;
+                    self.body_type = None;
+                }
+                "ethnicity" => self.ethnicity.clear(),
+                "nationality" => self.nationality = None,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Minimal page shown to visitors of a private profile: identity only.
+#[derive(Template)]
+#[template(path = "persons/private_profile.html")]
+pub struct PrivateProfileTemplate {
+    pub app_name: String,
+    pub year: i32,
+    pub version: String,
+    pub active_page: String,
+    pub user: Option<User>,
+    pub nonce: String,
+    pub name: String,
+    pub username: String,
+    pub avatar: Option<String>,
+    pub initials: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -592,6 +833,7 @@ pub struct ProfileEditTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub profile: ProfileData,
     pub platforms: Vec<SocialPlatformOption>,
     pub error: Option<String>,
@@ -622,6 +864,7 @@ pub struct ProductionsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub productions: Vec<Production>,
     pub filter: Option<String>,
     pub sort_by: String,
@@ -691,9 +934,11 @@ pub struct ProductionTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub production: ProductionDetail,
     pub production_roles: Vec<String>,
     pub org_production_roles: Vec<String>,
+    pub share: Option<ShareMeta>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -734,6 +979,30 @@ pub struct ProductionDetail {
     pub budget_level: Option<String>,
     pub production_tier: Option<String>,
     pub pending_email_invites: Vec<PendingEmailInvite>,
+    /// First page of comments (oldest first); the rest load from
+    /// `/productions/{slug}/comments?page=N`.
+    pub comments: Vec<ProductionCommentView>,
+    pub comment_count: u64,
+    pub like_count: u64,
+    /// Whether the signed-in viewer has liked this production.
+    pub viewer_liked: bool,
+}
+
+/// A production comment as rendered on the production page. `id` is the
+/// bare record key (used for `#comment-{id}` anchors and action URLs).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductionCommentView {
+    pub id: String,
+    pub body: String,
+    pub author_username: String,
+    pub author_name: String,
+    pub author_avatar: Option<String>,
+    pub created_at: String,
+    pub edited: bool,
+    pub can_edit: bool,
+    pub can_delete: bool,
+    pub like_count: u64,
+    pub viewer_liked: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -788,6 +1057,7 @@ pub struct ProductionCreateTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub production_types: Vec<String>,
     pub production_statuses: Vec<String>,
     pub budget_levels: Vec<String>,
@@ -796,6 +1066,8 @@ pub struct ProductionCreateTemplate {
     pub production_roles: Vec<String>,
     pub org_production_roles: Vec<String>,
     pub errors: Option<Vec<String>>,
+    /// Submitted field values, kept when re-rendering after an error.
+    pub values: FormValues,
 }
 
 /// Production edit form template
@@ -807,6 +1079,7 @@ pub struct ProductionEditTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub production: ProductionEditData,
     pub production_types: Vec<String>,
     pub production_statuses: Vec<String>,
@@ -847,6 +1120,7 @@ pub struct LocationsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub locations: Vec<LocationView>,
     pub filter: Option<String>,
     pub city: Option<String>,
@@ -879,8 +1153,11 @@ pub struct LocationTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub location: LocationDetail,
     pub is_liked: bool,
+    /// Prefills the add-rate form's currency.
+    pub default_currency: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -899,6 +1176,11 @@ pub struct LocationDetail {
     pub state: String,
     pub country: String,
     pub postal_code: Option<String>,
+    /// ISO 3166-1 alpha-2; preferred over `country` in structured data.
+    pub country_code: Option<String>,
+    /// Geocoded coordinates, for the map link.
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
     pub description: Option<String>,
     pub contact_name: String,
     pub contact_email: String,
@@ -926,6 +1208,13 @@ pub struct RateView {
     pub description: Option<String>,
 }
 
+impl RateView {
+    /// `amount` in `currency` for display, e.g. `$250.00` or `250.00 €`.
+    pub fn formatted_amount(&self) -> String {
+        crate::currency::format_amount(self.amount, &self.currency)
+    }
+}
+
 /// Location create form template
 #[derive(Template)]
 #[template(path = "locations/location_create.html")]
@@ -935,7 +1224,24 @@ pub struct LocationCreateTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub errors: Option<Vec<String>>,
+    /// Submitted field values, kept when re-rendering after an error.
+    pub values: FormValues,
+    /// Organizations the user can list the location under (owner/admin).
+    pub user_organizations: Vec<OrgOption>,
+    /// An existing location of the same owner the submission looks like.
+    pub duplicate_of: Option<DuplicateLocation>,
+}
+
+/// The existing location a create submission probably duplicates.
+#[derive(Debug, Clone)]
+pub struct DuplicateLocation {
+    /// Record key, for the `/locations/{id}` link.
+    pub id: String,
+    pub name: String,
+    pub address: String,
+    pub city: String,
 }
 
 /// Location edit form template
@@ -947,8 +1253,12 @@ pub struct LocationEditTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub location: LocationEditData,
     pub errors: Option<Vec<String>>,
+    /// Field values: the stored location on first load, the submitted body
+    /// when re-rendering after an error.
+    pub values: FormValues,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -982,8 +1292,14 @@ pub struct PeopleTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub people: Vec<PersonCard>,
     pub filter: Option<String>,
+    pub skill: Option<String>,
+    pub location: Option<String>,
+    pub availability: Option<AvailabilityStatus>,
+    /// Active filters as `&key=value` pairs for the infinite-scroll URL.
+    pub more_params: String,
     pub specialties: Vec<String>,
     pub liked_ids: Vec<String>,
     pub current_user_id: String,
@@ -1012,6 +1328,7 @@ pub struct AboutTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub stat_creatives: usize,
     pub stat_organizations: usize,
     pub stat_locations: usize,
@@ -1027,6 +1344,7 @@ pub struct TermsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
 }
 
 #[derive(Template)]
@@ -1037,6 +1355,7 @@ pub struct PrivacyTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
 }
 
 /// Get Verified page template
@@ -1048,6 +1367,7 @@ pub struct GetVerifiedTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub has_pending_request: bool,
     pub already_verified: bool,
     pub paid_flow_enabled: bool,
@@ -1065,6 +1385,7 @@ pub struct GetVerifiedDoneTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     /// "verified" | "processing" | "failed" — what we know right now.
     pub state: String,
 }
@@ -1078,14 +1399,86 @@ pub struct AccountSettingsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub username: String,
     pub email: String,
     pub messaging_preference: String,
+    /// Saved IANA timezone; empty means "use the browser's".
+    pub timezone: String,
     pub show_contact_info: bool,
+    pub profile_private: bool,
+    pub private_field_options: Vec<PrivateFieldOption>,
     pub error: Option<String>,
     pub success: Option<String>,
 }
 
+/// One "hide this field" checkbox on the account settings page.
+#[derive(Debug, Clone)]
+pub struct PrivateFieldOption {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub hidden: bool,
+}
+
+impl PrivateFieldOption {
+    /// Every hideable field, checked where it appears in `private_fields`.
+    pub fn for_selection(private_fields: &[String]) -> Vec<Self> {
+        crate::models::person::PRIVATE_FIELD_OPTIONS
+            .iter()
+            .map(|&(key, label)| Self {
+                key,
+                label,
+                hidden: private_fields.iter().any(|f| f == key),
+            })
+            .collect()
+    }
+}
+
+/// Field values for a create/edit form, so a form re-rendered after a
+/// validation error keeps what the user typed.
+///
+/// Edit pages seed it from the stored record; a failed submit rebuilds it
+/// from the raw request body with [`FormValues::from_body`], which works
+/// even when the body didn't deserialize into the typed form struct.
+/// Templates read `values.get("name")` (empty when absent) and
+/// `values.checked("public")`.
+#[derive(Debug, Clone, Default)]
+pub struct FormValues(Vec<(String, String)>);
+
+impl FormValues {
+    /// Parse a urlencoded body; undecodable input yields no values.
+    pub fn from_body(body: &str) -> Self {
+        Self(serde_urlencoded::from_str(body).unwrap_or_default())
+    }
+
+    /// Add a field; `None` leaves it absent (renders empty).
+    pub fn with(mut self, key: &str, value: Option<impl ToString>) -> Self {
+        if let Some(value) = value {
+            self.0.push((key.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    /// Add a checkbox field, present as `"on"` when set.
+    pub fn with_checked(self, key: &str, checked: bool) -> Self {
+        self.with(key, checked.then_some("on"))
+    }
+
+    /// First value for `key`, or `""`.
+    pub fn get(&self, key: &str) -> &str {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("")
+    }
+
+    /// Whether a checkbox was ticked (`on`/`true`).
+    pub fn checked(&self, key: &str) -> bool {
+        matches!(self.get(key), "on" | "true")
+    }
+}
+
 /// Likes page template
 #[derive(Template)]
 #[template(path = "likes/index.html")]
@@ -1095,6 +1488,7 @@ pub struct LikesTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub liked_people: Vec<LikedPerson>,
     pub liked_locations: Vec<LikedLocation>,
 }
@@ -1108,6 +1502,7 @@ pub struct ProfileAnalyticsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub profile_name: String,
     pub profile_username: String,
     pub total_views: u64,
@@ -1216,6 +1611,7 @@ pub struct JobsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub jobs: Vec<JobListView>,
     pub search_query: Option<String>,
     pub has_more: bool,
@@ -1230,6 +1626,7 @@ pub struct JobTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub job: JobDetailView,
 }
 
@@ -1242,6 +1639,7 @@ pub struct JobCreateTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub pay_rate_types: Vec<String>,
     pub user_organizations: Vec<JobOrgOption>,
     pub errors: Option<Vec<String>>,
@@ -1256,6 +1654,7 @@ pub struct JobEditTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub job_id: String,
     pub title: String,
     pub description: String,
@@ -1279,6 +1678,7 @@ pub struct MyJobsTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
     pub postings: Vec<JobListView>,
     pub applications: Vec<UserApplicationView>,
 }
@@ -1290,8 +1690,10 @@ pub struct MyJobsTemplate {
 pub mod equipment {
     use crate::models::equipment::{
         Equipment, EquipmentCategory, EquipmentCondition, EquipmentKit, EquipmentRental,
+        KitCompleteness, OwnerDashboard, RenterHistory, SerialDuplicate,
     };
     use crate::models::person::SessionUser;
+    use crate::record_id_ext::RecordIdExt;
     use askama::Template;
 
     /// Equipment-specific Askama filters; shared filters re-exported so the
@@ -1301,7 +1703,7 @@ pub mod equipment {
         use askama::Values;
         use surrealdb::types::RecordId;
 
-        pub use crate::templates::filters::abs_url;
+        pub use crate::templates::filters::{abs_url, local_date, local_datetime, t};
 
         /// Render a RecordId as "table:key" string for use in templates
         #[askama::filter_fn]
@@ -1319,9 +1721,51 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub equipment: Vec<Equipment>,
         pub kits: Vec<EquipmentKit>,
+        /// Serial numbers used by more than one item in the inventory.
+        pub serial_duplicates: Vec<SerialDuplicate>,
+        pub owner_type: String,
+        pub owner_id: String,
+        /// Active `?sort=` value (an `EquipmentSort::as_str`).
+        pub sort_by: String,
+        pub page_title: String,
+        pub error_message: Option<String>,
+    }
+
+    /// Operational overview of an owner's gear
+    #[derive(Template)]
+    #[template(path = "equipment/dashboard.html")]
+    pub struct EquipmentDashboardTemplate {
+        pub app_name: String,
+        pub year: i32,
+        pub version: String,
+        pub active_page: String,
+        pub user: Option<super::User>,
+        pub nonce: String,
+        pub current_user: Option<SessionUser>,
+        pub dashboard: OwnerDashboard,
+        pub owner_type: String,
+        pub owner_id: String,
+        pub page_title: String,
+    }
+
+    /// Equipment lookup results, shown when `/equipment/find` matches
+    /// several items or none
+    #[derive(Template)]
+    #[template(path = "equipment/find.html")]
+    pub struct EquipmentFindTemplate {
+        pub app_name: String,
+        pub year: i32,
+        pub version: String,
+        pub active_page: String,
+        pub user: Option<super::User>,
+        pub nonce: String,
+        pub current_user: Option<SessionUser>,
+        pub query: String,
+        pub matches: Vec<Equipment>,
         pub owner_type: String,
         pub owner_id: String,
         pub page_title: String,
@@ -1337,6 +1781,7 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub equipment: Option<Equipment>,
         pub categories: Vec<EquipmentCategory>,
@@ -1345,6 +1790,9 @@ pub mod equipment {
         pub owner_id: String,
         pub page_title: String,
         pub error_message: Option<String>,
+        /// Field values: the stored item when editing, the submitted body
+        /// when re-rendering after an error.
+        pub values: super::FormValues,
     }
 
     /// Equipment detail page template
@@ -1356,9 +1804,12 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub equipment: Equipment,
         pub rentals: Vec<EquipmentRental>,
+        /// The owner's other items with this serial number (editors only).
+        pub serial_duplicates: Vec<Equipment>,
         pub can_edit: bool,
         pub page_title: String,
         pub error_message: Option<String>,
@@ -1373,6 +1824,7 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub kit: Option<EquipmentKit>,
         pub available_equipment: Vec<Equipment>,
@@ -1393,9 +1845,10 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub kit: EquipmentKit,
-        pub kit_items: Vec<Equipment>,
+        pub completeness: KitCompleteness,
         pub rentals: Vec<EquipmentRental>,
         pub can_edit: bool,
         pub page_title: String,
@@ -1411,10 +1864,18 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub equipment: Option<Equipment>,
         pub kit: Option<EquipmentKit>,
+        /// Set when checking out a kit, to warn about unavailable items.
+        pub completeness: Option<KitCompleteness>,
         pub conditions: Vec<EquipmentCondition>,
+        /// Renter entered so far (prefilled after "Check history").
+        pub renter_type: String,
+        pub renter_id: String,
+        /// The renter's return record; only ever set for the item's owner.
+        pub renter_history: Option<RenterHistory>,
         pub page_title: String,
         pub error_message: Option<String>,
     }
@@ -1428,6 +1889,7 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub rental: EquipmentRental,
         pub conditions: Vec<EquipmentCondition>,
@@ -1444,6 +1906,7 @@ pub mod equipment {
         pub version: String,
         pub active_page: String,
         pub user: Option<super::User>,
+        pub nonce: String,
         pub current_user: Option<SessionUser>,
         pub rentals: Vec<EquipmentRental>,
         pub page_title: String,
@@ -1458,6 +1921,9 @@ pub struct BaseContext {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    /// The request's CSP nonce, stamped on inline tags as
+    /// `<script nonce="{{ nonce }}">`. Empty outside a request.
+    pub nonce: String,
 }
 
 impl Default for BaseContext {
@@ -1468,6 +1934,7 @@ impl Default for BaseContext {
             version: crate::version::asset_version().to_string(),
             active_page: String::new(),
             user: None,
+            nonce: crate::middleware::csp::nonce(),
         }
     }
 }
@@ -1497,6 +1964,7 @@ impl IndexTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             production_count: 0,
             user_count: 0,
             connection_count: 0,
@@ -1513,6 +1981,7 @@ impl LoginTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             error: None,
             redirect_to: None,
         }
@@ -1527,6 +1996,7 @@ impl SignupTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             error: None,
             prefill_email: None,
             redirect: None,
@@ -1546,6 +2016,7 @@ impl EmailVerificationTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             error: None,
             success: None,
             email: None,
@@ -1563,6 +2034,7 @@ impl ForgotPasswordTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             error: None,
             success: None,
             email: None,
@@ -1578,6 +2050,7 @@ impl ResetPasswordTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             error: None,
             success: None,
             email: None,
@@ -1594,6 +2067,7 @@ impl ProductionsTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             productions: vec![],
             filter: None,
             sort_by: "recent".to_string(),
@@ -1610,8 +2084,13 @@ impl PeopleTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             people: vec![],
             filter: None,
+            skill: None,
+            location: None,
+            availability: None,
+            more_params: String::new(),
             specialties: vec![],
             liked_ids: vec![],
             current_user_id: String::new(),
@@ -1635,6 +2114,7 @@ impl AboutTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             stat_creatives,
             stat_organizations,
             stat_locations,
@@ -1652,6 +2132,7 @@ impl TermsTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
         }
     }
 }
@@ -1664,6 +2145,7 @@ impl PrivacyTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
         }
     }
 }
@@ -1676,6 +2158,7 @@ pub struct ImpressumTemplate {
     pub version: String,
     pub active_page: String,
     pub user: Option<User>,
+    pub nonce: String,
 }
 
 impl ImpressumTemplate {
@@ -1686,6 +2169,7 @@ impl ImpressumTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
         }
     }
 }
@@ -1698,10 +2182,14 @@ impl AccountSettingsTemplate {
             version: base.version,
             active_page: base.active_page,
             user: base.user,
+            nonce: base.nonce,
             username: String::new(),
             email: String::new(),
             messaging_preference: "anyone".to_string(),
+            timezone: String::new(),
             show_contact_info: false,
+            profile_private: false,
+            private_field_options: PrivateFieldOption::for_selection(&[]),
             error: None,
             success: None,
         }
@@ -1,29 +1,9 @@
 //! Dependency-free text formatting helpers shared across layers.
 //!
 //! Lives at the crate root (rather than in `templates` or a model) because
-//! both data-layer code (slug generation in models) and presentation code
-//! (Askama filters, the stats endpoint, request logging) need these, and
-//! none of those layers should depend on another just for a string helper.
-
-/// Derive a URL-safe slug from free-form text.
-///
-/// Lowercases, replaces every non-alphanumeric run with a single `-`, and
-/// trims leading/trailing dashes: `"The Last Deposit!"` → `"the-last-deposit"`.
-///
-/// This is the canonical implementation — `production`, `location`, and the
-/// script-upload file-key builder all previously carried byte-identical
-/// copies. Uniqueness (e.g. `-2` suffixes on collision) remains the caller's
-/// concern; this function is purely lexical.
-pub fn slugify(text: &str) -> String {
-    text.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|segment| !segment.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
-}
+//! presentation code (Askama filters, the stats endpoint, request logging)
+//! needs these and none of those layers should depend on another just for
+//! a string helper. Slug generation lives in [`crate::util::slug`].
 
 /// Format a byte count as a human-readable label: `1.5 MB`, `820 KB`, `42 B`.
 ///
@@ -0,0 +1,3 @@
+//! Small, dependency-light helpers that don't belong to any one layer.
+
+pub mod slug;
@@ -0,0 +1,51 @@
+//! URL slug generation shared by the slug-owning models (organizations,
+//! locations, productions) and the org-create form.
+
+/// Derive a URL-safe slug from free-form text.
+///
+/// Transliterates to ASCII (`"Café Zürich"` → `"cafe-zurich"`), lowercases,
+/// replaces every non-alphanumeric run with a single `-`, and trims
+/// leading/trailing dashes: `"The Last Deposit!"` → `"the-last-deposit"`.
+///
+/// Used by `production`, `location`, and the script-upload file-key
+/// builder. It is purely lexical; [`slugify_unique`] adds the collision
+/// suffix.
+pub fn slugify(text: &str) -> String {
+    deunicode::deunicode(text)
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Slug used when [`slugify`] leaves nothing (`"!!!"`, an emoji-only name).
+pub const FALLBACK_SLUG: &str = "untitled";
+
+/// [`slugify`] `base`, then append `-2`, `-3`, … until `exists` reports a
+/// free candidate. The unsuffixed slug is tried first.
+///
+/// Callers supply the lookup (slug column, reserved names, …) so this module
+/// stays free of database code; organizations, locations, and productions
+/// all derive their slugs through here.
+pub async fn slugify_unique<F, Fut, E>(base: &str, mut exists: F) -> Result<String, E>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<bool, E>>,
+{
+    let mut slug = slugify(base);
+    if slug.is_empty() {
+        slug = FALLBACK_SLUG.to_string();
+    }
+    if !exists(slug.clone()).await? {
+        return Ok(slug);
+    }
+    let mut suffix = 2u32;
+    loop {
+        let candidate = format!("{slug}-{suffix}");
+        if !exists(candidate.clone()).await? {
+            return Ok(candidate);
+        }
+        suffix += 1;
+    }
+}
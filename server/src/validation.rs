@@ -0,0 +1,186 @@
+//! Field-level validation for create/edit forms.
+//!
+//! Handlers run every check against the submitted values, collecting
+//! failures into a [`ValidationErrors`] instead of bailing on the first, so
+//! the re-rendered form can list everything the user needs to fix in one
+//! pass. Templates take the messages as `errors: Option<Vec<String>>`
+//! (see [`ValidationErrors::into_messages`]); JSON callers get the field
+//! names too, via [`Error::ValidationFields`]. Messages are in the
+//! request's language ([`crate::i18n`]); field labels are the caller's.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::Datelike;
+
+use crate::error::Error;
+use crate::i18n::tf;
+
+/// Earliest year accepted by [`ValidationErrors::year`].
+pub const MIN_YEAR: i32 = 1800;
+
+/// Field → message pairs, in the order the checks ran.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationErrors(Vec<(String, String)>);
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failure for `field`.
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.0.push((field.to_string(), message.into()));
+    }
+
+    /// `value` must contain something other than whitespace.
+    pub fn required(&mut self, field: &str, label: &str, value: &str) -> &mut Self {
+        if value.trim().is_empty() {
+            self.add(field, tf("validation.required", &[("label", &label)]));
+        }
+        self
+    }
+
+    /// `value` must be at most `max` characters (not bytes).
+    pub fn max_len(&mut self, field: &str, label: &str, value: &str, max: usize) -> &mut Self {
+        if value.chars().count() > max {
+            self.add(
+                field,
+                tf("validation.max_len", &[("label", &label), ("max", &max)]),
+            );
+        }
+        self
+    }
+
+    /// A blank value passes; anything else must look like an email address.
+    pub fn email(&mut self, field: &str, label: &str, value: &str) -> &mut Self {
+        let value = value.trim();
+        if !value.is_empty() && !is_plausible_email(value) {
+            self.add(field, tf("validation.email", &[("label", &label)]));
+        }
+        self
+    }
+
+    /// A blank value passes; anything else must be a year between
+    /// [`MIN_YEAR`] and the current year.
+    pub fn year(&mut self, field: &str, label: &str, value: &str) -> &mut Self {
+        let value = value.trim();
+        if value.is_empty() {
+            return self;
+        }
+        let current = chrono::Utc::now().year();
+        match value.parse::<i32>() {
+            Ok(year) if (MIN_YEAR..=current).contains(&year) => {}
+            _ => self.add(
+                field,
+                tf(
+                    "validation.year",
+                    &[("label", &label), ("min", &MIN_YEAR), ("max", &current)],
+                ),
+            ),
+        }
+        self
+    }
+
+    /// A blank value passes; anything else must be a whole number ≥ `min`.
+    pub fn integer_at_least(
+        &mut self,
+        field: &str,
+        label: &str,
+        value: &str,
+        min: i64,
+    ) -> &mut Self {
+        let value = value.trim();
+        if value.is_empty() {
+            return self;
+        }
+        match value.parse::<i64>() {
+            Ok(n) if n >= min => {}
+            _ => self.add(
+                field,
+                tf(
+                    "validation.integer_at_least",
+                    &[("label", &label), ("min", &min)],
+                ),
+            ),
+        }
+        self
+    }
+
+    /// A blank value passes (the configured default applies); anything
+    /// else must be a three-letter currency code.
+    pub fn currency(&mut self, field: &str, value: &str) -> &mut Self {
+        if !value.trim().is_empty() && crate::currency::normalize(value).is_none() {
+            self.add(field, crate::currency::INVALID_CODE_MESSAGE);
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The message recorded for `field`, if any.
+    pub fn for_field(&self, field: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(f, _)| f == field)
+            .map(|(_, m)| m.as_str())
+    }
+
+    /// `Ok(())` when nothing failed, otherwise `Err(self)`.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// The messages alone, for a template's `errors` list.
+    pub fn into_messages(self) -> Vec<String> {
+        self.0.into_iter().map(|(_, m)| m).collect()
+    }
+
+    /// Messages grouped by field, for the `fields` object of a JSON error
+    /// body. Each field keeps its messages in check order.
+    pub fn by_field(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut fields: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (field, message) in &self.0 {
+            fields.entry(field).or_default().push(message);
+        }
+        fields
+    }
+}
+
+/// Every message, `; `-separated — the one-line summary shown when there is
+/// nowhere to put per-field messages.
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (_, message)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            f.write_str(message)?;
+        }
+        Ok(())
+    }
+}
+
+/// For callers without a form to re-render (JSON APIs): one
+/// `Error::ValidationFields` carrying every message and its field.
+impl From<ValidationErrors> for Error {
+    fn from(errors: ValidationErrors) -> Self {
+        Error::ValidationFields(errors)
+    }
+}
+
+/// Cheap shape check — one `@`, a non-empty local part, and a dotted
+/// domain with no spaces. Deliverability is proven by the verification
+/// email, not here.
+fn is_plausible_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.contains(char::is_whitespace)
+        && domain.split('.').all(|part| !part.is_empty())
+        && domain.contains('.')
+}
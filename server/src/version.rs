@@ -13,8 +13,8 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Cache-busting version for static-asset URLs: `"<semver>.<boot-epoch>"`.
 ///
-/// Static files are served with `Cache-Control: immutable, max-age=1y`
-/// (see `routes::app()`), so the `?v=` value MUST change whenever CSS/JS
+/// Static URLs carrying `?v=` are served `immutable` for a year (see
+/// `config::StaticCachePolicy`), so the value MUST change whenever CSS/JS
 /// changes or browsers keep stale assets forever. The crate version alone
 /// isn't enough — assets routinely change without a semver bump — so the
 /// process boot time is appended: every deploy/restart invalidates.
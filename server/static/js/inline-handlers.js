@@ -0,0 +1,35 @@
+// Inline event handlers under the nonce-based Content-Security-Policy.
+//
+// A `script-src` carrying a nonce makes browsers ignore 'unsafe-inline',
+// which also blocks `onclick="…"` attributes. Templates write those
+// handlers as `data-handler-click="…"` instead, and this file runs them
+// from one delegated listener per event type, with the attribute's
+// semantics: `this` is the element, `event` the event, and returning false
+// cancels the default action. Compiling the code needs 'unsafe-eval', which
+// the policy already grants for Datastar expressions. Loaded from <head>,
+// so it also covers elements added later by Datastar patches.
+(function () {
+    const EVENTS = ["click", "change", "input", "submit", "mouseover", "mouseout", "error"];
+
+    function run(el, type, event) {
+        const code = el.getAttribute("data-handler-" + type);
+        if (code === null) return;
+        if (new Function("event", code).call(el, event) === false) {
+            event.preventDefault();
+        }
+    }
+
+    function dispatch(event) {
+        let el = event.target instanceof Element ? event.target : null;
+        while (el) {
+            run(el, event.type, event);
+            if (!event.bubbles || event.cancelBubble) break;
+            el = el.parentElement;
+        }
+    }
+
+    for (const type of EVENTS) {
+        // An image's `error` doesn't bubble, so it is caught on the way down.
+        document.addEventListener(type, dispatch, type === "error");
+    }
+})();
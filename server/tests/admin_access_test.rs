@@ -0,0 +1,128 @@
+//! Tests for the platform-admin guard: every `/admin` route (and
+//! `/api/admin/stats`) goes through the `AdminUser` extractor, so anonymous
+//! requests get 401 and signed-in non-admins get 403 before any handler
+//! logic runs.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::auth::create_jwt;
+use slatehub::db::DB;
+use slatehub::record_id_ext::RecordIdExt;
+use surrealdb::types::{RecordId, SurrealValue};
+use tower::ServiceExt;
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+/// Create a person and return a bearer token for them.
+async fn person_token(username: &str, is_admin: bool) -> String {
+    let rows: Vec<R> = DB
+        .query(
+            "CREATE person CONTENT {
+                username: $username, email: $email, password: 'h', name: $username,
+                is_admin: $is_admin, verification_status: 'email',
+                profile: { name: $username, skills: [], social_links: [], ethnicity: [], unions: [], languages: [], experience: [], education: [], reels: [], media_other: [], awards: [] }
+            } RETURN id",
+        )
+        .bind(("username", username.to_string()))
+        .bind(("email", format!("{username}@example.com")))
+        .bind(("is_admin", is_admin))
+        .await
+        .expect("create person")
+        .take(0)
+        .expect("take person");
+    let id = rows.into_iter().next().expect("one").id;
+    create_jwt(
+        &id.to_raw_string(),
+        username,
+        &format!("{username}@example.com"),
+    )
+    .expect("jwt")
+}
+
+async fn status(method: &str, uri: &str, token: Option<&str>) -> StatusCode {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::ACCEPT, "application/json")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let body = if method == "POST" {
+        Body::from("name=Hacked")
+    } else {
+        Body::empty()
+    };
+    slatehub::routes::app()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+const ADMIN_ROUTES: &[(&str, &str)] = &[
+    ("GET", "/admin"),
+    ("GET", "/admin/people"),
+    ("GET", "/admin/org-types"),
+    ("POST", "/admin/org-types"),
+    ("POST", "/admin/org-types/anything/rename"),
+    ("POST", "/admin/org-types/anything/delete"),
+    ("GET", "/admin/feature-flags"),
+    ("GET", "/admin/cleanup-files"),
+    ("POST", "/admin/backup"),
+    ("GET", "/api/admin/stats"),
+];
+
+#[test]
+fn non_admins_are_forbidden_from_admin_routes() {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("JWT_SECRET", "test_secret_for_admin_access_test");
+    }
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        let token = person_token("regular", false).await;
+
+        for &(method, uri) in ADMIN_ROUTES {
+            assert_eq!(
+                status(method, uri, None).await,
+                StatusCode::UNAUTHORIZED,
+                "anonymous {method} {uri}"
+            );
+            assert_eq!(
+                status(method, uri, Some(&token)).await,
+                StatusCode::FORBIDDEN,
+                "non-admin {method} {uri}"
+            );
+        }
+    });
+}
+
+#[test]
+fn admins_reach_admin_pages() {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("JWT_SECRET", "test_secret_for_admin_access_test");
+    }
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        let token = person_token("operator", true).await;
+        assert_eq!(
+            status("GET", "/admin/org-types", Some(&token)).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status("GET", "/api/admin/stats", Some(&token)).await,
+            StatusCode::OK
+        );
+    });
+}
@@ -538,7 +538,7 @@ fn full_fixture_no_unexpected_props_from_outside_action() {
 // ── PDF round-trip ──
 //
 // Render the Fountain fixture into a screenplay-formatted PDF with
-// `printpdf` (dev-dep only), then run the same parser + breakdown
+// `printpdf`, then run the same parser + breakdown
 // pipeline against the PDF bytes. This is the *only* path that
 // exercises `pdf_extract` end-to-end in our test suite — the rest of
 // the integration tests work on Fountain or PDF-style text constants.
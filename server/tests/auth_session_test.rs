@@ -2,12 +2,13 @@
 //! default and 30 days when remembered, the JWT `exp`/`remember` claims match
 //! what was requested, tokens minted before the `remember` claim existed still
 //! decode, and `should_refresh_session` only slides remembered sessions once
-//! the token is a day old. The same duration sizes the cookie Max-Age in the
-//! login handler and middleware refresh, so these constants are the contract.
+//! the token is a day old. The same duration sizes the `session_cookie`
+//! Max-Age used by the login handler and middleware refresh, so these
+//! constants are the contract; a standard login's cookie has no Max-Age.
 
 use slatehub::auth::{
     Claims, JwtConfig, SESSION_REFRESH_AFTER_SECS, create_jwt, create_jwt_with_duration,
-    create_session_jwt, decode_jwt, session_duration, should_refresh_session,
+    create_session_jwt, decode_jwt, session_cookie, session_duration, should_refresh_session,
 };
 
 /// Tests that mint real tokens need the signing secret. Same value every time,
@@ -129,6 +130,8 @@ fn claims(remember: bool, iat: u64) -> Claims {
         iat,
         exp: iat + session_duration(remember),
         remember,
+        iss: None,
+        aud: None,
     }
 }
 
@@ -152,3 +155,23 @@ fn refresh_policy_slides_only_remembered_day_old_sessions() {
     // Clock skew (iat in the future) must not underflow or refresh.
     assert!(!should_refresh_session(&claims(true, now + 500), now));
 }
+
+#[test]
+fn remembered_cookie_persists_for_the_token_lifetime() {
+    let cookie = session_cookie("token".to_string(), true);
+    assert_eq!(cookie.name(), "auth_token");
+    assert_eq!(
+        cookie.max_age(),
+        Some(cookie::time::Duration::seconds(2_592_000))
+    );
+    assert_eq!(cookie.http_only(), Some(true));
+}
+
+#[test]
+fn standard_cookie_is_a_browser_session_cookie() {
+    let cookie = session_cookie("token".to_string(), false);
+    assert_eq!(cookie.value(), "token");
+    assert_eq!(cookie.max_age(), None);
+    assert_eq!(cookie.expires(), None);
+    assert_eq!(cookie.path(), Some("/"));
+}
@@ -1,4 +1,12 @@
-use slatehub::auth::{create_jwt, decode_jwt, hash_password_sync, verify_password_sync};
+mod common;
+
+use slatehub::auth::{
+    create_jwt, decode_jwt, dummy_verify_password, hash_password_sync, verify_password_sync,
+};
+use slatehub::db::DB;
+use slatehub::error::Error;
+use slatehub::models::person::Person;
+use surrealdb::types::RecordId;
 
 #[test]
 fn test_password_hashing() {
@@ -33,3 +41,32 @@ fn test_jwt_creation_and_validation() {
     assert_eq!(claims.username, username);
     assert_eq!(claims.email, email);
 }
+
+#[test]
+fn test_signin_unknown_and_wrong_password_fail_alike() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        // The throwaway verification must never error or panic.
+        dummy_verify_password("anything").await;
+
+        DB.query(
+            "CREATE $id CONTENT {
+                username: 'known',
+                email: 'known@example.com',
+                password: $password,
+                verification_status: 'email'
+            }",
+        )
+        .bind(("id", RecordId::new("person", "known")))
+        .bind(("password", hash_password_sync("right password").unwrap()))
+        .await
+        .expect("create person");
+
+        let unknown = Person::signin("nobody".into(), "right password".into(), false).await;
+        let wrong = Person::signin("known".into(), "wrong password".into(), false).await;
+        assert!(matches!(unknown, Err(Error::Unauthorized)));
+        assert!(matches!(wrong, Err(Error::Unauthorized)));
+    });
+}
@@ -0,0 +1,62 @@
+//! `AvailabilityStatus` round-trips through its stored strings and maps
+//! legacy free-text availability notes best-effort. Pure logic — no test DB
+//! required.
+
+use slatehub::models::person::{AvailabilityStatus, Profile};
+
+#[test]
+fn stored_strings_round_trip() {
+    for status in AvailabilityStatus::ALL {
+        assert_eq!(AvailabilityStatus::parse(status.as_str()), Some(status));
+        assert!(!status.label().is_empty());
+    }
+    assert_eq!(AvailabilityStatus::parse("Available"), None);
+    assert_eq!(AvailabilityStatus::parse(""), None);
+}
+
+#[test]
+fn free_text_maps_to_a_status() {
+    let cases = [
+        ("available", Some(AvailabilityStatus::Available)),
+        ("Available now", Some(AvailabilityStatus::Available)),
+        (
+            "Available from April 2026",
+            Some(AvailabilityStatus::Available),
+        ),
+        ("free", Some(AvailabilityStatus::Available)),
+        ("Freelance, full-time", Some(AvailabilityStatus::Available)),
+        ("busy", Some(AvailabilityStatus::Busy)),
+        ("Booked through May", Some(AvailabilityStatus::Busy)),
+        ("Open to offers", Some(AvailabilityStatus::OpenToOffers)),
+        ("not_available", Some(AvailabilityStatus::NotLooking)),
+        ("Not available", Some(AvailabilityStatus::NotLooking)),
+        (
+            "Unavailable until fall",
+            Some(AvailabilityStatus::NotLooking),
+        ),
+        ("Weekends", None),
+        ("", None),
+    ];
+    for (note, expected) in cases {
+        assert_eq!(
+            AvailabilityStatus::from_free_text(note),
+            expected,
+            "{note:?} should map to {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn profile_ignores_unknown_stored_status() {
+    let mut profile = Profile {
+        availability_status: Some("open_to_offers".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        profile.availability_status(),
+        Some(AvailabilityStatus::OpenToOffers)
+    );
+
+    profile.availability_status = Some("sometimes".to_string());
+    assert_eq!(profile.availability_status(), None);
+}
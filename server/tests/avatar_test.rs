@@ -0,0 +1,78 @@
+//! Generated initials placeholders for avatars without an upload.
+
+mod common;
+
+use slatehub::avatar::{background_color, initials, placeholder_svg};
+use slatehub::db::DB;
+use slatehub::record_id_ext::RecordIdExt;
+use slatehub::templates::User;
+
+#[test]
+fn test_same_id_always_yields_the_same_color() {
+    let color = background_color("person:ab12cd34");
+    for _ in 0..3 {
+        assert_eq!(background_color("person:ab12cd34"), color);
+    }
+    assert_eq!(background_color("ab12cd34"), color);
+    assert!(color.starts_with('#') && color.len() == 7);
+
+    let distinct: std::collections::HashSet<_> = (0..50)
+        .map(|i| background_color(&format!("organization:org{i}")))
+        .collect();
+    assert!(distinct.len() > 4, "colors barely vary: {distinct:?}");
+}
+
+#[test]
+fn test_initials_from_names() {
+    assert_eq!(initials("Jane Q. Doe"), "JD");
+    assert_eq!(initials("cher"), "CH");
+    assert_eq!(initials("X"), "X");
+    assert_eq!(initials("  "), "??");
+    assert_eq!(initials("émile zola"), "ÉZ");
+}
+
+#[test]
+fn test_placeholder_svg_renders_escaped_initials_on_the_id_color() {
+    let svg = placeholder_svg("organization:acme", "<Acme> & \"Sons\"");
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains(&format!("fill=\"{}\"", background_color("acme"))));
+    assert!(svg.contains(">&lt;&quot;</text>"));
+    assert!(svg.contains("aria-label=\"&lt;Acme&gt; &amp; &quot;Sons&quot;\""));
+}
+
+#[test]
+fn test_batch_from_profiles_keeps_order_and_falls_back_to_placeholder() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        let ann = common::create_test_user("batch_ann").await;
+        let bob = common::create_test_user("batch_bob").await;
+        DB.query("UPDATE $id SET profile.avatar = 'https://cdn.example.com/ann.jpg'")
+            .bind(("id", ann.id.clone()))
+            .await
+            .expect("set avatar");
+
+        let ids = vec![
+            bob.id.key_string(),
+            "person:does_not_exist".to_string(),
+            ann.id.to_raw_string(),
+        ];
+        let users = User::batch_from_profiles(&ids).await;
+
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].id, bob.id.to_raw_string());
+        assert_eq!(users[0].avatar_url, None);
+        assert_eq!(
+            users[0].avatar,
+            format!("/api/avatar?id={}", bob.id.to_raw_string())
+        );
+        assert_eq!(users[0].initials, "BA");
+        assert_eq!(users[1].name, "batch_ann");
+        assert_eq!(
+            users[1].avatar_url.as_deref(),
+            Some("https://cdn.example.com/ann.jpg")
+        );
+        assert!(User::batch_from_profiles(&[]).await.is_empty());
+    });
+}
@@ -0,0 +1,98 @@
+use slatehub::captcha::{self, CaptchaAction, CaptchaConfig, CaptchaProvider};
+use slatehub::error::Error;
+
+fn clear_captcha_env() {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        for name in [
+            "CAPTCHA_PROVIDER",
+            "CAPTCHA_SITE_KEY",
+            "CAPTCHA_SECRET_KEY",
+            "CAPTCHA_ACTIONS",
+        ] {
+            std::env::remove_var(name);
+        }
+    }
+}
+
+fn configure_turnstile(actions: Option<&str>) {
+    clear_captcha_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("CAPTCHA_PROVIDER", "turnstile");
+        std::env::set_var("CAPTCHA_SITE_KEY", "site-key");
+        std::env::set_var("CAPTCHA_SECRET_KEY", "secret-key");
+        if let Some(actions) = actions {
+            std::env::set_var("CAPTCHA_ACTIONS", actions);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_captcha_is_a_noop_when_unconfigured() {
+    clear_captcha_env();
+
+    assert!(CaptchaConfig::from_env().is_none());
+    assert_eq!(captcha::widget(CaptchaAction::Signup, "n0nce"), "");
+    assert!(
+        captcha::verify(CaptchaAction::Signup, None, "203.0.113.7")
+            .await
+            .is_ok()
+    );
+    assert_eq!(
+        captcha::extend_csp("script-src 'self'"),
+        "script-src 'self'"
+    );
+
+    // A provider without keys is still off.
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("CAPTCHA_PROVIDER", "hcaptcha") };
+    assert!(CaptchaConfig::from_env().is_none());
+    clear_captcha_env();
+}
+
+#[tokio::test]
+async fn test_configured_captcha_guards_only_listed_actions() {
+    configure_turnstile(Some("contact, bogus"));
+
+    let config = CaptchaConfig::from_env().expect("configured");
+    assert_eq!(config.provider, CaptchaProvider::Turnstile);
+    assert_eq!(config.actions, vec![CaptchaAction::Contact]);
+
+    let widget = captcha::widget(CaptchaAction::Contact, "n0nce");
+    assert!(widget.contains(r#"nonce="n0nce""#));
+    assert!(widget.contains(r#"class="cf-turnstile captcha-widget""#));
+    assert!(widget.contains(r#"data-sitekey="site-key""#));
+    assert_eq!(captcha::widget(CaptchaAction::Signup, "n0nce"), "");
+
+    // Missing token fails before any call to the provider.
+    let err = captcha::verify(CaptchaAction::Contact, Some("  "), "203.0.113.7")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+    assert!(
+        captcha::verify(CaptchaAction::Signup, None, "203.0.113.7")
+            .await
+            .is_ok()
+    );
+
+    assert_eq!(
+        captcha::extend_csp("default-src 'self'; script-src 'self'"),
+        "default-src 'self'; script-src 'self' https://challenges.cloudflare.com"
+    );
+    clear_captcha_env();
+}
+
+#[test]
+fn test_captcha_actions_default_to_all() {
+    configure_turnstile(None);
+    assert_eq!(
+        CaptchaConfig::from_env().expect("configured").actions,
+        CaptchaAction::ALL.to_vec()
+    );
+    assert_eq!(
+        CaptchaAction::parse("Join_Request"),
+        Some(CaptchaAction::JoinRequest)
+    );
+    clear_captcha_env();
+}
@@ -0,0 +1,53 @@
+//! `common::clean_database` must leave every schema table empty of test
+//! data while restoring the lookup seeds tests resolve against.
+
+mod common;
+
+use slatehub::db::DB;
+
+async fn count(table: &str) -> i64 {
+    let n: Option<i64> = DB
+        .query(format!("SELECT VALUE count() FROM {table} GROUP ALL"))
+        .await
+        .expect("count query")
+        .take(0)
+        .expect("take count");
+    n.unwrap_or(0)
+}
+
+#[test]
+fn test_clean_database_removes_data_and_restores_seeds() {
+    common::setup_test_db();
+    common::run(async {
+        DB.query(
+            "CREATE person CONTENT {
+                email: 'clean-db@example.com',
+                password: 'hashed',
+                username: 'clean-db-user',
+                profile: { name: 'clean-db-user', skills: [], social_links: [], ethnicity: [], unions: [], languages: [], experience: [], education: [], reels: [], media_other: [], awards: [] }
+            };
+            CREATE reserved_names CONTENT { name: 'clean-db-extra' };",
+        )
+        .await
+        .and_then(|r| r.check())
+        .expect("seed rows");
+    });
+
+    common::clean_database();
+
+    common::run(async {
+        assert_eq!(count("person").await, 0);
+        assert!(count("organization_type").await > 0);
+
+        let extra: Option<i64> = DB
+            .query(
+                "SELECT VALUE count() FROM reserved_names WHERE name = 'clean-db-extra' GROUP ALL",
+            )
+            .await
+            .expect("query reserved_names")
+            .take(0)
+            .expect("take reserved_names");
+        assert_eq!(extra.unwrap_or(0), 0);
+        assert!(count("reserved_names").await > 0);
+    });
+}
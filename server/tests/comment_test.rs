@@ -0,0 +1,100 @@
+//! Tests for production comments: body normalization (pure) and the
+//! create → list → edit → delete lifecycle against the test DB.
+
+mod common;
+
+use slatehub::db::DB;
+use slatehub::models::comment::{CommentModel, MAX_COMMENT_LENGTH};
+use surrealdb::types::{RecordId, SurrealValue};
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+#[test]
+fn body_is_trimmed() {
+    assert_eq!(
+        CommentModel::normalize_body("  great cast!\n").unwrap(),
+        "great cast!"
+    );
+}
+
+#[test]
+fn blank_body_is_rejected() {
+    assert!(CommentModel::normalize_body("   \n\t").is_err());
+}
+
+#[test]
+fn body_length_is_capped_in_characters() {
+    let at_limit = "é".repeat(MAX_COMMENT_LENGTH);
+    assert!(CommentModel::normalize_body(&at_limit).is_ok());
+
+    let over_limit = "é".repeat(MAX_COMMENT_LENGTH + 1);
+    assert!(CommentModel::normalize_body(&over_limit).is_err());
+}
+
+#[test]
+fn comment_lifecycle() {
+    common::setup_test_db();
+    common::clean_table("comment");
+    common::clean_table("production");
+    common::clean_table("person");
+
+    common::run(async {
+        let person: Vec<R> = DB
+            .query(
+                "CREATE person CONTENT {
+                    username: 'commenter',
+                    email: 'commenter@example.com',
+                    password: 'x'
+                } RETURN id",
+            )
+            .await
+            .expect("create person")
+            .take(0)
+            .expect("take person");
+        let author = person.into_iter().next().expect("one person").id;
+
+        let production: Vec<R> = DB
+            .query(
+                "CREATE production CONTENT {
+                    title: 'Comment Target',
+                    slug: 'comment-target',
+                    type: 'Feature Film',
+                    status: 'in_development'
+                } RETURN id",
+            )
+            .await
+            .expect("create production")
+            .take(0)
+            .expect("take production");
+        let target = production.into_iter().next().expect("one production").id;
+
+        let first = CommentModel::create(&target, &author, "  first!  ")
+            .await
+            .expect("create first comment");
+        assert_eq!(first.body, "first!");
+        assert!(first.updated_at.is_none());
+        CommentModel::create(&target, &author, "second")
+            .await
+            .expect("create second comment");
+
+        assert_eq!(CommentModel::count_for_target(&target).await.unwrap(), 2);
+        let page = CommentModel::list_for_target(&target, 1, 0).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].body, "first!");
+        assert_eq!(page[0].author_username, "commenter");
+
+        let edited = CommentModel::update_body(&first.id, "first, edited")
+            .await
+            .expect("edit comment");
+        assert_eq!(edited.body, "first, edited");
+        assert!(edited.updated_at.is_some());
+
+        CommentModel::delete(&first.id)
+            .await
+            .expect("delete comment");
+        assert_eq!(CommentModel::count_for_target(&target).await.unwrap(), 1);
+    });
+}
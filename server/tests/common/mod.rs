@@ -13,11 +13,18 @@
 //! grounds to fail a test; we re-connect and re-authenticate up to
 //! [`CONNECT_ATTEMPTS`] times.
 
+use axum::body::Body;
+use axum::http::{Request, Response, header};
 use once_cell::sync::OnceCell;
+use slatehub::auth::create_jwt;
 use slatehub::db::DB;
+use slatehub::models::organization;
+use slatehub::record_id_ext::RecordIdExt;
 use std::sync::LazyLock;
+use surrealdb::types::{RecordId, SurrealValue};
 use surrealdb::{engine::remote::ws::Ws, opt::auth::Root};
 use tokio::runtime::Runtime;
+use tower::ServiceExt;
 
 static RT: OnceCell<Runtime> = OnceCell::new();
 
@@ -113,6 +120,161 @@ pub fn clean_table(table: &str) {
     });
 }
 
+/// The schema the test DB is initialised from (`make test-db-init`). It is
+/// a superset of `db/migrations/`, so it is the source of truth for which
+/// tables exist and which lookup tables ship with seed rows.
+const SCHEMA: &str = include_str!("../../../db/schema.surql");
+
+/// Every table defined in [`SCHEMA`], in definition order.
+fn schema_tables() -> Vec<&'static str> {
+    SCHEMA
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("DEFINE TABLE "))
+        .filter_map(|rest| {
+            rest.split_whitespace()
+                .find(|word| !matches!(*word, "OVERWRITE" | "IF" | "NOT" | "EXISTS"))
+        })
+        .map(|name| name.trim_end_matches(';'))
+        .collect()
+}
+
+/// The `INSERT INTO` statements that seed the lookup tables
+/// (organization types, reserved names, departments, …). Each spans from
+/// its `INSERT INTO` line to the first line ending in `;`.
+fn seed_statements() -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current: Option<String> = None;
+    for line in SCHEMA.lines() {
+        let trimmed = line.trim();
+        if current.is_none() && trimmed.starts_with("INSERT INTO ") {
+            current = Some(String::new());
+        }
+        if let Some(statement) = current.as_mut() {
+            statement.push_str(line);
+            statement.push('\n');
+            if trimmed.ends_with(';') {
+                statements.extend(current.take());
+            }
+        }
+    }
+    statements
+}
+
+/// Reset the test DB to its freshly-initialised state.
+///
+/// Deletes every row from every table in the schema, asserts that nothing
+/// survived, then re-runs the schema's seed inserts so lookup tables that
+/// tests resolve against (e.g. `organization_type`) are populated again.
+#[allow(dead_code)] // not every test binary resets the whole database
+pub fn clean_database() {
+    let tables = schema_tables();
+    for table in &tables {
+        clean_table(table);
+    }
+
+    runtime().block_on(async {
+        for table in &tables {
+            let remaining: Option<i64> = DB
+                .query(format!("SELECT VALUE count() FROM {table} GROUP ALL"))
+                .await
+                .unwrap_or_else(|e| panic!("Failed to count rows in {table}: {e}"))
+                .take(0)
+                .unwrap_or_else(|e| panic!("Failed to read row count for {table}: {e}"));
+            assert_eq!(
+                remaining.unwrap_or(0),
+                0,
+                "clean_database left rows behind in {table}"
+            );
+        }
+
+        for statement in seed_statements() {
+            DB.query(&statement)
+                .await
+                .and_then(|response| response.check())
+                .unwrap_or_else(|e| panic!("Failed to re-seed lookup table: {e}\n{statement}"));
+        }
+    });
+
+    // Re-seeding mints new ids, so drop anything cached from the old rows
+    organization::invalidate_organization_types();
+    organization::invalidate_reserved_names();
+}
+
+/// A person created for an HTTP-level test, with a session token for them.
+#[allow(dead_code)] // only the route-level test binaries sign users in
+pub struct TestUser {
+    pub id: RecordId,
+    pub username: String,
+    pub token: String,
+}
+
+/// Create an email-verified person named `username` and sign a JWT for
+/// them with [`create_jwt`], exactly as login would.
+///
+/// Falls back to a fixed `JWT_SECRET` when the binary hasn't set one, so
+/// the token and the router's auth middleware agree on the key.
+#[allow(dead_code)]
+pub async fn create_test_user(username: &str) -> TestUser {
+    #[derive(serde::Deserialize, SurrealValue)]
+    struct Created {
+        id: RecordId,
+    }
+
+    if std::env::var("JWT_SECRET").is_err() {
+        // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+        unsafe { std::env::set_var("JWT_SECRET", "test-secret-for-integration-tests") }
+    }
+
+    let email = format!("{username}@example.com");
+    let rows: Vec<Created> = DB
+        .query(
+            "CREATE person CONTENT {
+                username: $username, email: $email, password: 'h', name: $username,
+                verification_status: 'email',
+                profile: { name: $username, skills: [], social_links: [], ethnicity: [], unions: [], languages: [], experience: [], education: [], reels: [], media_other: [], awards: [] }
+            } RETURN id",
+        )
+        .bind(("username", username.to_string()))
+        .bind(("email", email.clone()))
+        .await
+        .expect("create test user")
+        .take(0)
+        .expect("take test user");
+    let id = rows.into_iter().next().expect("one test user").id;
+    let token = create_jwt(&id.to_raw_string(), username, &email).expect("sign test user JWT");
+    TestUser {
+        id,
+        username: username.to_string(),
+        token,
+    }
+}
+
+/// Build a request carrying `user`'s session in the `auth_token` cookie,
+/// the way a signed-in browser sends it. A non-empty `form` body is sent
+/// as `application/x-www-form-urlencoded`.
+#[allow(dead_code)]
+pub fn authed_request(user: &TestUser, method: &str, uri: &str, form: &str) -> Request<Body> {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::COOKIE, format!("auth_token={}", user.token));
+    if !form.is_empty() {
+        request = request.header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+    }
+    request
+        .body(Body::from(form.to_string()))
+        .expect("build test request")
+}
+
+/// Run `request` through the full application router, middleware included.
+#[allow(dead_code)]
+pub async fn send(request: Request<Body>) -> Response<Body> {
+    slatehub::routes::app()
+        .oneshot(request)
+        .await
+        .expect("router is infallible")
+}
+
 /// Run an async test closure on the shared runtime
 pub fn run<F: std::future::Future<Output = ()>>(f: F) {
     runtime().block_on(f);
@@ -0,0 +1,76 @@
+//! Response compression: text is gzipped, images go out as-is, and the
+//! level and size threshold are configurable.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use tower::ServiceExt;
+
+async fn get_gzip(uri: &str) -> axum::response::Response {
+    get_encoded(uri, "gzip").await
+}
+
+async fn get_encoded(uri: &str, accept_encoding: &str) -> axum::response::Response {
+    slatehub::routes::app()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::ACCEPT_ENCODING, accept_encoding)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn compresses_text_but_not_images_or_small_bodies() {
+    let js = get_gzip("/static/js/profile-image-upload.js").await;
+    assert_eq!(js.status(), StatusCode::OK);
+    assert_eq!(js.headers()[header::CONTENT_ENCODING], "gzip");
+
+    let png = get_gzip("/static/images/filmpivot.png").await;
+    assert_eq!(png.status(), StatusCode::OK);
+    assert!(png.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn prefers_brotli_and_skips_precompressed_formats() {
+    let js = get_encoded("/static/js/profile-image-upload.js", "gzip, br").await;
+    assert_eq!(js.headers()[header::CONTENT_ENCODING], "br");
+
+    let font = get_encoded("/static/fonts/landing/inter.woff2", "gzip, br").await;
+    assert_eq!(font.status(), StatusCode::OK);
+    assert!(font.headers().get(header::CONTENT_ENCODING).is_none());
+
+    let missing = get_encoded("/api/definitely-not-a-route", "gzip, br").await;
+    assert!(missing.status().is_client_error());
+    assert!(missing.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[test]
+fn compression_level_and_threshold_come_from_env() {
+    use slatehub::config::CompressionConfig;
+    use tower_http::compression::CompressionLevel;
+
+    // SAFETY: the only test in this binary that touches these variables.
+    unsafe {
+        std::env::set_var("COMPRESSION_LEVEL", "best");
+        std::env::set_var("COMPRESSION_MIN_SIZE", "4096");
+    }
+    let config = CompressionConfig::from_env();
+    assert_eq!(config.level, CompressionLevel::Best);
+    assert_eq!(config.min_size, 4096);
+
+    unsafe {
+        std::env::set_var("COMPRESSION_LEVEL", "6");
+        std::env::set_var("COMPRESSION_MIN_SIZE", "lots");
+    }
+    let config = CompressionConfig::from_env();
+    assert_eq!(config.level, CompressionLevel::Precise(6));
+    assert_eq!(config.min_size, CompressionConfig::default().min_size);
+
+    unsafe {
+        std::env::remove_var("COMPRESSION_LEVEL");
+        std::env::remove_var("COMPRESSION_MIN_SIZE");
+    }
+}
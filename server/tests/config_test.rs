@@ -1,4 +1,4 @@
-use slatehub::config::{DatabaseConfig, ServerConfig};
+use slatehub::config::{DatabaseConfig, ServerConfig, ServerTuning};
 
 #[test]
 fn test_database_connection_url() {
@@ -19,8 +19,150 @@ fn test_server_socket_addr() {
     let config = ServerConfig {
         host: "127.0.0.1".to_string(),
         port: 3000,
+        tuning: ServerTuning::default(),
     };
 
     let addr = config.socket_addr().unwrap();
     assert_eq!(addr.to_string(), "127.0.0.1:3000");
 }
+
+#[test]
+fn test_server_tuning_reads_overrides_and_zero_disables() {
+    // SAFETY: only this test sets these variables, and only to valid values.
+    unsafe {
+        for name in [
+            "SERVER_HTTP2",
+            "SERVER_HTTP2_MAX_STREAMS",
+            "SERVER_HTTP1_KEEPALIVE",
+            "SERVER_TCP_KEEPALIVE_SECS",
+            "SERVER_TCP_NODELAY",
+            "SERVER_MAX_CONNECTIONS",
+        ] {
+            std::env::remove_var(name);
+        }
+        assert_eq!(ServerTuning::from_env().unwrap(), ServerTuning::default());
+
+        std::env::set_var("SERVER_HTTP2", "False");
+        std::env::set_var("SERVER_HTTP2_MAX_STREAMS", "64");
+        std::env::set_var("SERVER_TCP_KEEPALIVE_SECS", "0");
+        std::env::set_var("SERVER_MAX_CONNECTIONS", " 5000 ");
+        let tuning = ServerTuning::from_env().unwrap();
+        assert!(!tuning.http2);
+        assert_eq!(tuning.http2_max_streams, 64);
+        assert_eq!(tuning.tcp_keepalive, None);
+        assert_eq!(tuning.max_connections, Some(5000));
+        assert!(tuning.http1_keep_alive && tuning.tcp_nodelay);
+
+        std::env::set_var("SERVER_TCP_KEEPALIVE_SECS", "30");
+        std::env::set_var("SERVER_MAX_CONNECTIONS", "0");
+        let tuning = ServerTuning::from_env().unwrap();
+        assert_eq!(
+            tuning.tcp_keepalive,
+            Some(std::time::Duration::from_secs(30))
+        );
+        assert_eq!(tuning.max_connections, None);
+
+        for name in [
+            "SERVER_HTTP2",
+            "SERVER_HTTP2_MAX_STREAMS",
+            "SERVER_TCP_KEEPALIVE_SECS",
+            "SERVER_MAX_CONNECTIONS",
+        ] {
+            std::env::remove_var(name);
+        }
+    }
+}
+
+#[test]
+fn test_debug_routes_off_unless_explicitly_enabled() {
+    use slatehub::config::debug_routes_enabled;
+
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::remove_var("DEBUG_ROUTES");
+        assert!(!debug_routes_enabled());
+        std::env::set_var("DEBUG_ROUTES", "1");
+        assert!(!debug_routes_enabled());
+        std::env::set_var("DEBUG_ROUTES", " TRUE ");
+        assert!(debug_routes_enabled());
+        std::env::remove_var("DEBUG_ROUTES");
+    }
+}
+
+#[test]
+fn test_upload_image_formats_are_validated() {
+    use slatehub::config::{Config, SUPPORTED_IMAGE_FORMATS};
+
+    // SAFETY: no other test in this binary reads these variables.
+    unsafe {
+        std::env::set_var("DB_USERNAME", "root");
+        std::env::set_var("DB_PASSWORD", "root");
+
+        std::env::remove_var("UPLOAD_IMAGE_FORMATS");
+        assert_eq!(
+            Config::from_env().unwrap().upload_image_formats,
+            SUPPORTED_IMAGE_FORMATS
+        );
+
+        std::env::set_var("UPLOAD_IMAGE_FORMATS", " Image/PNG, image/jpeg,image/png ");
+        assert_eq!(
+            Config::from_env().unwrap().upload_image_formats,
+            ["image/png", "image/jpeg"]
+        );
+
+        std::env::set_var("UPLOAD_IMAGE_FORMATS", "image/jpeg,image/heic");
+        assert!(Config::from_env().is_err());
+        std::env::set_var("UPLOAD_IMAGE_FORMATS", " , ");
+        assert!(Config::from_env().is_err());
+
+        std::env::remove_var("UPLOAD_IMAGE_FORMATS");
+    }
+}
+
+#[test]
+fn test_public_base_url_must_be_absolute() {
+    use slatehub::config::parse_public_base_url;
+
+    assert_eq!(
+        parse_public_base_url(" https://slatehub.com/ ").unwrap(),
+        "https://slatehub.com"
+    );
+    assert_eq!(
+        parse_public_base_url("http://localhost:3000").unwrap(),
+        "http://localhost:3000"
+    );
+    for bad in [
+        "slatehub.com",
+        "/relative",
+        "ftp://slatehub.com",
+        "https://slatehub.com/?ref=mail",
+        "",
+    ] {
+        assert!(parse_public_base_url(bad).is_err(), "{bad:?} accepted");
+    }
+}
+
+#[test]
+fn test_public_base_url_prefers_new_variable() {
+    use slatehub::config::Config;
+
+    // SAFETY: the other tests here set only valid URLs, if any.
+    unsafe {
+        std::env::set_var("DB_USERNAME", "root");
+        std::env::set_var("DB_PASSWORD", "root");
+
+        std::env::set_var("APP_URL", "https://legacy.example.com/");
+        std::env::remove_var("PUBLIC_BASE_URL");
+        assert_eq!(
+            Config::from_env().unwrap().public_base_url,
+            "https://legacy.example.com"
+        );
+        std::env::set_var("PUBLIC_BASE_URL", "https://slatehub.example.com");
+        assert_eq!(
+            Config::from_env().unwrap().public_base_url,
+            "https://slatehub.example.com"
+        );
+        std::env::remove_var("PUBLIC_BASE_URL");
+        std::env::remove_var("APP_URL");
+    }
+}
@@ -0,0 +1,38 @@
+//! How stored files are presented to browsers: images, audio, and video
+//! render inline; documents (resumes) download under a safe filename.
+
+use slatehub::services::s3::{content_disposition, key_filename, renders_inline};
+
+#[test]
+fn media_types_render_inline_and_documents_download() {
+    assert!(renders_inline("image/jpeg"));
+    assert!(renders_inline("Video/MP4; codecs=avc1"));
+    assert!(!renders_inline("application/pdf"));
+    assert!(!renders_inline("text/html"));
+
+    assert_eq!(
+        content_disposition("image/png", "logo_01H.png"),
+        "inline; filename=\"logo_01H.png\""
+    );
+    assert_eq!(
+        content_disposition("application/pdf", "Jane Doe Resume.pdf"),
+        "attachment; filename=\"Jane Doe Resume.pdf\""
+    );
+}
+
+#[test]
+fn filenames_are_made_header_safe() {
+    assert_eq!(
+        content_disposition("application/pdf", "a\"b\\c\r\n.pdf"),
+        "attachment; filename=\"a_b_c__.pdf\"; filename*=UTF-8''a%22b%5Cc%0D%0A.pdf"
+    );
+    assert_eq!(
+        content_disposition("application/pdf", "Résumé.pdf"),
+        "attachment; filename=\"R_sum_.pdf\"; filename*=UTF-8''R%C3%A9sum%C3%A9.pdf"
+    );
+    assert_eq!(
+        content_disposition("application/pdf", "   "),
+        "attachment; filename=\"download\""
+    );
+    assert_eq!(key_filename("profiles/abc/resume.pdf"), "resume.pdf");
+}
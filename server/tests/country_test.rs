@@ -0,0 +1,29 @@
+//! ISO 3166-1 country code validation and name lookup.
+
+use slatehub::country::{COUNTRIES, code_for, name, normalize};
+
+#[test]
+fn test_normalize_accepts_assigned_codes_only() {
+    assert_eq!(normalize(" us ").as_deref(), Some("US"));
+    assert_eq!(normalize("gb").as_deref(), Some("GB"));
+    for bad in ["", "UK", "XX", "USA", "U5"] {
+        assert_eq!(normalize(bad), None, "{bad:?} accepted");
+    }
+}
+
+#[test]
+fn test_code_for_maps_codes_names_and_aliases() {
+    assert_eq!(code_for("fr"), Some("FR"));
+    assert_eq!(code_for("germany"), Some("DE"));
+    assert_eq!(code_for(" USA "), Some("US"));
+    assert_eq!(code_for("UK"), Some("GB"));
+    assert_eq!(code_for("Atlantis"), None);
+    assert_eq!(code_for(""), None);
+    assert_eq!(name("mx"), Some("Mexico"));
+}
+
+#[test]
+fn test_country_table_is_sorted_and_unique() {
+    assert!(COUNTRIES.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(COUNTRIES.iter().all(|(code, _)| code.len() == 2));
+}
@@ -0,0 +1,258 @@
+//! Production crew list export — the CSV/PDF renderers in
+//! `services::crew_list`, `ProductionModel::crew_list` (contact redaction,
+//! private profiles, accepted members only, department grouping), and the
+//! members-only `/productions/{slug}/crew.csv` and `.pdf` routes.
+
+mod common;
+
+use axum::http::{StatusCode, header};
+use slatehub::db::DB;
+use slatehub::models::production::{CrewListEntry, ProductionModel};
+use slatehub::services::{crew_list, pdf};
+use surrealdb::types::{RecordId, SurrealValue};
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+fn entry(name: &str, roles: &[&str], email: Option<&str>) -> CrewListEntry {
+    CrewListEntry {
+        name: name.to_string(),
+        production_roles: roles.iter().map(|r| r.to_string()).collect(),
+        departments: vec![],
+        email: email.map(str::to_string),
+        phone: None,
+    }
+}
+
+#[test]
+fn test_csv_has_a_header_and_quotes_where_needed() {
+    let csv = crew_list::to_csv(&[
+        entry(
+            "Ada Lovelace",
+            &["Director", "Writer"],
+            Some("ada@example.com"),
+        ),
+        entry("Sam \"Sparks\" Lee", &["Gaffer"], None),
+    ]);
+    let lines: Vec<&str> = csv.split("\r\n").collect();
+    assert_eq!(lines[0], "Name,Role,Department,Email,Phone");
+    assert_eq!(
+        lines[1],
+        "Ada Lovelace,\"Director, Writer\",,ada@example.com,"
+    );
+    assert_eq!(lines[2], "\"Sam \"\"Sparks\"\" Lee\",Gaffer,,,");
+    assert_eq!(lines[3], "");
+}
+
+#[test]
+fn test_csv_neutralizes_formula_cells() {
+    let csv = crew_list::to_csv(&[entry("=HYPERLINK(\"x\")", &["@Grip"], None)]);
+    let row = csv.split("\r\n").nth(1).unwrap();
+    assert_eq!(row, "\"'=HYPERLINK(\"\"x\"\")\",'@Grip,,,");
+
+    // A leading tab or carriage return doesn't hide a formula either.
+    let csv = crew_list::to_csv(&[entry("\t=1+1", &["\r@Grip"], None)]);
+    let row = csv.split("\r\n").nth(1).unwrap();
+    assert_eq!(row, "'\t=1+1,\"'\r@Grip\",,,");
+}
+
+#[test]
+fn test_csv_keeps_international_phone_numbers() {
+    let mut crew = entry("Ada Lovelace", &["Director"], None);
+    crew.phone = Some("+44 20 7946 0958".to_string());
+    let mut trick = entry("Sam Lee", &["Gaffer"], None);
+    trick.phone = Some("+1+cmd|' /C calc'!A0".to_string());
+    let csv = crew_list::to_csv(&[crew, trick]);
+    let rows: Vec<&str> = csv.split("\r\n").collect();
+    assert_eq!(rows[1], "Ada Lovelace,Director,,,+44 20 7946 0958");
+    assert_eq!(rows[2], "Sam Lee,Gaffer,,,'+1+cmd|' /C calc'!A0");
+}
+
+#[test]
+fn test_truncate_cuts_long_cells() {
+    assert_eq!(pdf::truncate("Gaffer", 10), "Gaffer");
+    assert_eq!(pdf::truncate("Second Assistant Camera", 12), "Second As...");
+}
+
+#[test]
+fn test_pdf_renders_empty_and_multi_page_lists() {
+    let empty = crew_list::render_pdf("Night Shift", "March 1, 2026", &[]).unwrap();
+    assert!(empty.starts_with(b"%PDF"));
+
+    let crew: Vec<CrewListEntry> = (0..120)
+        .map(|i| entry(&format!("Crew Member {i}"), &["Grip"], None))
+        .collect();
+    let long = crew_list::render_pdf("Night Shift", "March 1, 2026", &crew).unwrap();
+    assert!(long.starts_with(b"%PDF"));
+    assert!(long.len() > empty.len());
+}
+
+async fn seed_production(slug: &str) -> RecordId {
+    let rows: Vec<R> = DB
+        .query(
+            "CREATE production CONTENT {
+                title: $slug, slug: $slug, type: 'Feature Film',
+                status: 'in_development', source: 'manual'
+            } RETURN id",
+        )
+        .bind(("slug", slug.to_string()))
+        .await
+        .expect("create production")
+        .take(0)
+        .expect("take production");
+    rows.into_iter().next().expect("one").id
+}
+
+async fn join(person: &RecordId, production: &RecordId, roles: &[&str], status: &str) {
+    DB.query(
+        "RELATE $person->member_of->$production CONTENT {
+            role: 'member', production_roles: $roles, invitation_status: $status
+        }",
+    )
+    .bind(("person", person.clone()))
+    .bind(("production", production.clone()))
+    .bind((
+        "roles",
+        roles.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+    ))
+    .bind(("status", status.to_string()))
+    .await
+    .expect("relate member")
+    .check()
+    .expect("relate member statement");
+}
+
+#[test]
+fn test_crew_list_redacts_contacts_and_skips_pending_members() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("production");
+    common::clean_table("member_of");
+    common::clean_table("role");
+    common::clean_table("department");
+    common::run(async {
+        let production = seed_production("crew-list-model").await;
+        let open = common::create_test_user("crew_open").await;
+        let private = common::create_test_user("crew_private").await;
+        let pending = common::create_test_user("crew_pending").await;
+
+        DB.query(
+            "UPDATE $open SET profile.is_public = true, profile.phone = '555-0100';
+             UPDATE $private SET profile.phone = '555-0199';
+             LET $camera = (CREATE ONLY department SET name = 'Camera').id;
+             CREATE role SET name = 'Focus Puller', department = $camera;",
+        )
+        .bind(("open", open.id.clone()))
+        .bind(("private", private.id.clone()))
+        .await
+        .expect("seed profiles and roles")
+        .check()
+        .expect("seed statements");
+
+        join(&open.id, &production, &["Producer"], "accepted").await;
+        join(&private.id, &production, &["Focus Puller"], "accepted").await;
+        join(&pending.id, &production, &["Grip"], "pending").await;
+
+        let crew = ProductionModel::crew_list(&production).await.unwrap();
+        let names: Vec<&str> = crew.iter().map(|c| c.name.as_str()).collect();
+        // Department-less members sort after those with one.
+        assert_eq!(names, vec!["crew_private", "crew_open"]);
+
+        assert_eq!(crew[0].departments, vec!["Camera".to_string()]);
+        assert_eq!(crew[0].email, None);
+        assert_eq!(crew[0].phone, None);
+
+        assert_eq!(crew[1].email.as_deref(), Some("crew_open@example.com"));
+        assert_eq!(crew[1].phone.as_deref(), Some("555-0100"));
+        assert_eq!(crew[1].production_roles, vec!["Producer".to_string()]);
+    });
+}
+
+#[test]
+fn test_crew_list_hides_contacts_of_private_profiles() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("production");
+    common::clean_table("member_of");
+    common::run(async {
+        let production = seed_production("crew-list-private").await;
+        let hidden = common::create_test_user("crew_hidden").await;
+
+        // Opted in to contact sharing, but the profile itself is private.
+        DB.query(
+            "UPDATE $hidden SET profile.is_public = true, profile.is_private = true, \
+             profile.phone = '555-0142'",
+        )
+        .bind(("hidden", hidden.id.clone()))
+        .await
+        .expect("make profile private")
+        .check()
+        .expect("make profile private statement");
+        join(&hidden.id, &production, &["Gaffer"], "accepted").await;
+
+        let crew = ProductionModel::crew_list(&production).await.unwrap();
+        assert_eq!(crew.len(), 1);
+        assert_eq!(crew[0].name, "crew_hidden");
+        assert_eq!(crew[0].email, None);
+        assert_eq!(crew[0].phone, None);
+    });
+}
+
+#[test]
+fn test_crew_exports_are_members_only() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("production");
+    common::clean_table("member_of");
+    common::run(async {
+        let production = seed_production("crew-list-routes").await;
+        let member = common::create_test_user("crew_route_member").await;
+        let outsider = common::create_test_user("crew_route_outsider").await;
+        let invited = common::create_test_user("crew_route_invited").await;
+        join(&member.id, &production, &["Editor"], "accepted").await;
+        join(&invited.id, &production, &["Grip"], "pending").await;
+
+        for user in [&outsider, &invited] {
+            for uri in [
+                "/productions/crew-list-routes/crew.csv",
+                "/productions/crew-list-routes/crew.pdf",
+            ] {
+                let response = common::send(common::authed_request(user, "GET", uri, "")).await;
+                assert_eq!(response.status(), StatusCode::FORBIDDEN, "{uri}");
+            }
+        }
+
+        let response = common::send(common::authed_request(
+            &member,
+            "GET",
+            "/productions/crew-list-routes/crew.csv",
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(
+            response.headers()[header::CONTENT_DISPOSITION]
+                .to_str()
+                .unwrap()
+                .contains("attachment; filename=\"crew-list-routes-crew.csv\"")
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(body.to_vec()).unwrap();
+        assert!(csv.contains("crew_route_member,Editor"));
+        assert!(!csv.contains("crew_route_invited"));
+
+        let response = common::send(common::authed_request(
+            &member,
+            "GET",
+            "/productions/crew-list-routes/crew.pdf",
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+    });
+}
@@ -0,0 +1,147 @@
+//! Crew recommendations match public people by overlapping skills, rank by
+//! match count then proximity to the production, and skip anyone already
+//! attached to it.
+
+mod common;
+
+use axum::http::StatusCode;
+use slatehub::db::DB;
+use slatehub::models::production::{CreateProductionData, Production, ProductionModel};
+use slatehub::record_id_ext::RecordIdExt;
+
+async fn insert_person(username: &str, private: bool, skills: &[&str], location: &str) {
+    DB.query(
+        "CREATE type::record('person', $username) CONTENT {
+            email: string::concat($username, '@example.com'),
+            password: 'hashed',
+            username: $username,
+            verification_status: 'email',
+            profile: {
+                name: $username, location: $location, skills: $skills, is_private: $private,
+                social_links: [], ethnicity: [], unions: [], languages: [], experience: [],
+                education: [], reels: [], media_other: [], awards: []
+            }
+        }",
+    )
+    .bind(("username", username.to_string()))
+    .bind(("private", private))
+    .bind((
+        "skills",
+        skills.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+    ))
+    .bind(("location", location.to_string()))
+    .await
+    .and_then(|r| r.check())
+    .expect("create person");
+}
+
+/// A production in Atlanta owned by `owner_id`, plus a pool of candidates.
+async fn seed(owner_id: &str) -> Production {
+    let production = ProductionModel::create(
+        CreateProductionData {
+            title: "Crew Test Feature".to_string(),
+            production_type: "Feature Film".to_string(),
+            status: "Pre-Production".to_string(),
+            start_date: None,
+            end_date: None,
+            description: None,
+            location: Some("Atlanta".to_string()),
+            budget_level: None,
+            production_tier: None,
+        },
+        owner_id,
+        "person",
+        None,
+    )
+    .await
+    .expect("create production");
+
+    insert_person(
+        "crew_both_la",
+        false,
+        &["Gaffer", "Grip"],
+        "Los Angeles, CA",
+    )
+    .await;
+    insert_person("crew_gaffer_atl", false, &["gaffer"], "Atlanta, GA").await;
+    insert_person("crew_gaffer_la", false, &["Gaffer"], "Los Angeles, CA").await;
+    insert_person("crew_private", true, &["Gaffer", "Grip"], "Atlanta, GA").await;
+    insert_person("crew_attached", false, &["Gaffer", "Grip"], "Atlanta, GA").await;
+    insert_person("crew_editor", false, &["Editor"], "Atlanta, GA").await;
+
+    DB.query("RELATE person:crew_attached->involvement->$production SET relation_type = 'crew'")
+        .bind(("production", production.id.clone()))
+        .await
+        .and_then(|r| r.check())
+        .expect("attach person");
+
+    production
+}
+
+#[test]
+fn test_recommend_crew_ranks_by_matches_then_location() {
+    common::setup_test_db();
+    common::clean_table("involvement");
+    common::clean_table("member_of");
+    common::clean_table("production");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("crew_owner").await;
+        let production = seed(&owner.id.to_raw_string()).await;
+
+        let skills = vec!["Gaffer".to_string(), "GRIP".to_string()];
+        let recs = ProductionModel::recommend_crew(&production.id, &skills, 10)
+            .await
+            .expect("recommend crew");
+
+        let usernames: Vec<&str> = recs.iter().map(|r| r.username.as_str()).collect();
+        assert_eq!(
+            usernames,
+            ["crew_both_la", "crew_gaffer_atl", "crew_gaffer_la"]
+        );
+        assert_eq!(recs[0].match_count, 2);
+        assert_eq!(recs[1].matched_skills, ["gaffer"]);
+
+        let top = ProductionModel::recommend_crew(&production.id, &skills, 1)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+
+        let none = ProductionModel::recommend_crew(&production.id, &[], 10)
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+    });
+}
+
+#[test]
+fn test_recommendations_route_is_limited_to_editors() {
+    common::setup_test_db();
+    common::clean_table("involvement");
+    common::clean_table("member_of");
+    common::clean_table("production");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("crew_route_owner").await;
+        let outsider = common::create_test_user("crew_route_outsider").await;
+        let production = seed(&owner.id.to_raw_string()).await;
+        let uri = format!(
+            "/productions/{}/recommendations?skills=Grip",
+            production.slug
+        );
+
+        let response = common::send(common::authed_request(&outsider, "GET", &uri, "")).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = common::send(common::authed_request(&owner, "GET", &uri, "")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let recs: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(recs.len(), 1);
+        assert_eq!(recs[0]["username"], "crew_both_la");
+    });
+}
@@ -0,0 +1,50 @@
+//! Currency code normalization and per-currency amount formatting.
+
+use slatehub::currency::{INVALID_CODE_MESSAGE, format_amount, minor_digits, normalize, resolve};
+use slatehub::error::Error;
+
+#[test]
+fn test_normalize_accepts_three_letter_codes_only() {
+    assert_eq!(normalize(" eur ").as_deref(), Some("EUR"));
+    assert_eq!(normalize("USD").as_deref(), Some("USD"));
+    for bad in ["", "US", "USDX", "U$D", "€"] {
+        assert_eq!(normalize(bad), None, "{bad:?} accepted");
+    }
+}
+
+#[test]
+fn test_resolve_defaults_blank_and_rejects_malformed_codes() {
+    let default = slatehub::config::default_currency();
+    assert_eq!(resolve(None, "currency").unwrap(), default);
+    assert_eq!(resolve(Some("  "), "currency").unwrap(), default);
+    assert_eq!(resolve(Some(" gbp"), "currency").unwrap(), "GBP");
+
+    match resolve(Some("euro"), "purchase_currency") {
+        Err(Error::ValidationFields(errors)) => {
+            assert_eq!(
+                errors.for_field("purchase_currency"),
+                Some(INVALID_CODE_MESSAGE)
+            );
+        }
+        other => panic!("expected a field error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_decimal_places_follow_the_currency() {
+    assert_eq!(minor_digits("usd"), 2);
+    assert_eq!(minor_digits("JPY"), 0);
+    assert_eq!(minor_digits("KWD"), 3);
+}
+
+#[test]
+fn test_format_amount_places_symbol_per_currency() {
+    assert_eq!(format_amount(1250.0, "USD"), "$1,250.00");
+    assert_eq!(format_amount(1250.5, "eur"), "1,250.50 €");
+    assert_eq!(format_amount(125000.0, "JPY"), "¥125,000");
+    assert_eq!(format_amount(12.3456, "KWD"), "KWD 12.346");
+    assert_eq!(format_amount(999.999, "GBP"), "£1,000.00");
+    assert_eq!(format_amount(1234567.0, "CHF"), "CHF 1,234,567.00");
+    assert_eq!(format_amount(-5.0, "USD"), "-$5.00");
+    assert_eq!(format_amount(0.0, "CAD"), "CA$0.00");
+}
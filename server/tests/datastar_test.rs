@@ -1,7 +1,8 @@
 //! Unit tests for `slatehub::datastar` — SSE patch-event framing.
 //! Pure functions; no test DB required.
 
-use slatehub::datastar::{patch_elements, response};
+use axum::http::HeaderMap;
+use slatehub::datastar::{is_datastar_request, patch_elements, response};
 
 #[test]
 fn patch_event_has_required_framing() {
@@ -34,3 +35,13 @@ fn response_sets_event_stream_headers() {
     );
     assert_eq!(resp.headers().get("cache-control").unwrap(), "no-cache");
 }
+
+#[test]
+fn datastar_requests_are_recognized_by_header() {
+    let mut headers = HeaderMap::new();
+    assert!(!is_datastar_request(&headers));
+    headers.insert("datastar-request", "true".parse().unwrap());
+    assert!(is_datastar_request(&headers));
+    headers.insert("datastar-request", "false".parse().unwrap());
+    assert!(!is_datastar_request(&headers));
+}
@@ -0,0 +1,45 @@
+//! Database outage handling: the health-check interval setting, and the
+//! 503 + `Retry-After` response requests get while the monitor reconnects.
+
+use std::time::Duration;
+
+use axum::http::{StatusCode, header};
+use axum::response::IntoResponse;
+use slatehub::db::health_check_interval;
+use slatehub::error::Error;
+
+#[test]
+fn health_check_interval_comes_from_env() {
+    // SAFETY: this is the only test in this binary touching this variable.
+    unsafe {
+        std::env::remove_var("DB_HEALTH_CHECK_INTERVAL_SECS");
+        assert_eq!(health_check_interval(), Some(Duration::from_secs(10)));
+
+        std::env::set_var("DB_HEALTH_CHECK_INTERVAL_SECS", "30");
+        assert_eq!(health_check_interval(), Some(Duration::from_secs(30)));
+
+        std::env::set_var("DB_HEALTH_CHECK_INTERVAL_SECS", "0");
+        assert_eq!(health_check_interval(), None);
+
+        std::env::remove_var("DB_HEALTH_CHECK_INTERVAL_SECS");
+    }
+}
+
+#[tokio::test]
+async fn unavailable_is_a_503_with_retry_after() {
+    let response = Error::Unavailable {
+        message: "Reconnecting to the database".to_string(),
+        retry_after: Duration::from_secs(5),
+    }
+    .into_response();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()[header::RETRY_AFTER], "5");
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "Reconnecting to the database");
+    assert_eq!(json["retry_after"], 5);
+}
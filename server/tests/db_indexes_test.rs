@@ -0,0 +1,50 @@
+//! Required-index verification run by `ensure_db_initialized`.
+
+mod common;
+
+use serde_json::json;
+use slatehub::db::{REQUIRED_INDEXES, indexes_missing_from, missing_indexes};
+
+#[test]
+fn test_indexes_missing_from_reads_info_for_table() {
+    let info = json!({
+        "fields": {},
+        "indexes": {
+            "idx_equipment_qr": "DEFINE INDEX idx_equipment_qr ON equipment FIELDS qr_code UNIQUE",
+            "idx_equipment_owner_person": "DEFINE INDEX idx_equipment_owner_person ON equipment FIELDS owner_person",
+        }
+    });
+    assert_eq!(
+        indexes_missing_from("equipment", &info),
+        ["idx_equipment_owner_org"]
+    );
+    assert_eq!(
+        indexes_missing_from("person", &json!(null)),
+        ["person_email_unique", "person_username_unique"]
+    );
+    assert!(indexes_missing_from("comment", &json!(null)).is_empty());
+}
+
+#[test]
+fn test_required_indexes_group_tables_and_exist_in_schema() {
+    let schema = include_str!("../../db/schema.surql");
+    let mut seen: Vec<&str> = Vec::new();
+    for (table, index) in REQUIRED_INDEXES {
+        assert!(
+            schema.contains(&format!("DEFINE INDEX {index} ON {table} ")),
+            "{index} on {table} missing from db/schema.surql"
+        );
+        if seen.last() != Some(table) {
+            assert!(!seen.contains(table), "{table} entries not adjacent");
+            seen.push(table);
+        }
+    }
+}
+
+#[test]
+fn test_schema_database_has_every_required_index() {
+    common::setup_test_db();
+    common::run(async {
+        assert_eq!(missing_indexes().await.unwrap(), vec![]);
+    });
+}
@@ -0,0 +1,41 @@
+//! The query-slot guard end to end: with every slot held, a request whose
+//! handler reports its query failure as a plain database error still gets
+//! a 503 with `Retry-After`. Needs the test SurrealDB instance; kept in its
+//! own binary because the slot limits are read once per process.
+
+mod common;
+
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::db::DB;
+
+#[test]
+fn saturated_query_slots_answer_503_with_retry_after() {
+    // SAFETY: set before the first (cached) reads; the only test in this
+    // binary.
+    unsafe {
+        std::env::set_var("DB_MAX_CONCURRENT_QUERIES", "1");
+        std::env::set_var("DB_QUERY_QUEUE_TIMEOUT_MS", "50");
+    }
+    common::setup_test_db();
+
+    common::run(async {
+        // Hold the only slot while the request runs.
+        let holder = tokio::spawn(async { DB.query("SLEEP 2s").await });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let response = common::send(
+            Request::builder()
+                .uri("/productions/busy-slots")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+
+        holder.await.unwrap().expect("slot holder query");
+    });
+}
@@ -0,0 +1,66 @@
+//! The per-query deadline and concurrency guard on `DB.query(...)`:
+//! configuration, and how an expired or turned-away query surfaces as an
+//! application error.
+
+use std::time::Duration;
+
+use slatehub::db::{is_busy_error, max_concurrent_queries, query_queue_timeout, query_timeout};
+use slatehub::error::Error;
+use surrealdb::types::QueryError;
+
+#[test]
+fn query_timeout_comes_from_env() {
+    // SAFETY: set before the first (cached) read; the only test in this
+    // binary touching the variable.
+    unsafe { std::env::set_var("DB_QUERY_TIMEOUT_SECS", "7") };
+    assert_eq!(query_timeout(), Some(Duration::from_secs(7)));
+}
+
+#[test]
+fn timed_out_queries_become_database_errors() {
+    let err: Error = surrealdb::Error::query(
+        "query timed out after 7s (request req-0001)".to_string(),
+        QueryError::TimedOut {
+            duration: Duration::from_secs(7),
+        },
+    )
+    .into();
+    assert!(
+        matches!(&err, Error::Database(msg) if msg.contains("query timed out") && msg.contains("req-0001")),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn query_concurrency_limits_come_from_env() {
+    // SAFETY: set before the first (cached) reads; the only test in this
+    // binary touching these variables.
+    unsafe {
+        std::env::set_var("DB_MAX_CONCURRENT_QUERIES", "8");
+        std::env::set_var("DB_QUERY_QUEUE_TIMEOUT_MS", "0");
+    }
+    assert_eq!(max_concurrent_queries(), Some(8));
+    assert_eq!(query_queue_timeout(), None);
+}
+
+#[test]
+fn busy_queries_become_unavailable() {
+    let busy = surrealdb::Error::query(
+        "database busy: all 8 query slots in use (request req-0002)".to_string(),
+        QueryError::NotExecuted,
+    );
+    assert!(is_busy_error(&busy));
+    let err: Error = busy.into();
+    assert!(
+        matches!(&err, Error::Unavailable { retry_after, .. } if *retry_after == Duration::from_secs(1)),
+        "{err:?}"
+    );
+
+    // Other unexecuted statements are ordinary database errors
+    let skipped = surrealdb::Error::query(
+        "The query was not executed due to a failed transaction".to_string(),
+        QueryError::NotExecuted,
+    );
+    assert!(!is_busy_error(&skipped));
+    assert!(matches!(Error::from(skipped), Error::Database(_)));
+}
@@ -0,0 +1,69 @@
+//! `db::transaction` commits on `Ok` and rolls back on `Err`.
+//!
+//! Writes `search_log` rows (analytics only, no seed data) so no other
+//! fixtures are needed.
+
+mod common;
+
+use slatehub::db::{self, DB};
+use slatehub::error::Error;
+
+async fn logged(query: &str) -> bool {
+    let rows: Vec<String> = DB
+        .query("SELECT VALUE query FROM search_log WHERE query = $query")
+        .bind(("query", query.to_string()))
+        .await
+        .expect("lookup failed")
+        .take(0)
+        .expect("lookup decode failed");
+    !rows.is_empty()
+}
+
+async fn log_search(tx: &db::Tx, query: &str) -> Result<(), Error> {
+    tx.query("CREATE search_log SET query = $query, source = 'test', category = 'all'")
+        .bind(("query", query.to_string()))
+        .await?
+        .check()?;
+    Ok(())
+}
+
+#[test]
+fn test_transaction_commits_on_ok() {
+    common::setup_test_db();
+    common::clean_table("search_log");
+    common::run(async {
+        let query = "tx-commit-probe";
+
+        let result = db::transaction(async |tx| {
+            log_search(tx, query).await?;
+            Ok(42)
+        })
+        .await;
+
+        assert_eq!(result.ok(), Some(42));
+        assert!(logged(query).await);
+    });
+}
+
+#[test]
+fn test_transaction_rolls_back_on_err() {
+    common::setup_test_db();
+    common::clean_table("search_log");
+    common::run(async {
+        let query = "tx-rollback-probe";
+
+        let result: Result<(), Error> = db::transaction(async |tx| {
+            log_search(tx, query).await?;
+            Err(Error::Internal("abort after first write".into()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::Internal(_))));
+        assert!(!logged(query).await, "write inside a failed tx persisted");
+
+        // The shared connection is usable afterwards (no dangling BEGIN).
+        DB.query("RETURN 1")
+            .await
+            .expect("connection wedged after rollback");
+    });
+}
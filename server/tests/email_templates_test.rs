@@ -0,0 +1,158 @@
+//! Guards the Askama-rendered transactional emails in `services::email`:
+//! every message carries both a plain-text and an HTML part, the links and
+//! codes interpolate into both, user-supplied values are HTML-escaped, and
+//! the plain-text part stays free of markup. Pure rendering, so no
+//! network/DB.
+
+use slatehub::services::email::{
+    OrgInquiry, RentalReminder, invitation_email, org_contact_email, password_reset_email,
+    rental_reminder_email, verification_email,
+};
+
+const VERIFY: &str = "https://slatehub.com/verify-email/confirm?code=123456&email=a%40b.com";
+
+#[test]
+fn verification_includes_link_and_code_in_both_parts() {
+    let email = verification_email(VERIFY, "123456").unwrap();
+    assert_eq!(email.subject, "Verify your SlateHub email address");
+    assert!(email.text_body.contains(VERIFY));
+    assert!(email.text_body.contains("123456"));
+    assert!(email.html_body.contains("123456"));
+    // Askama escapes the query string's `&` inside the href.
+    assert!(
+        email
+            .html_body
+            .contains("https://slatehub.com/verify-email/confirm?code=123456&#38;email=a%40b.com")
+    );
+}
+
+#[test]
+fn html_parts_share_the_branded_layout() {
+    let email = verification_email(VERIFY, "123456").unwrap();
+    assert!(email.html_body.starts_with("<!DOCTYPE html>"));
+    assert!(email.html_body.contains("By filmmakers, for filmmakers."));
+    assert!(email.text_body.contains("The SlateHub Team"));
+}
+
+#[test]
+fn text_parts_have_no_markup() {
+    let reminder = RentalReminder {
+        renter_name: Some("Jane"),
+        item_name: "ARRI Alexa Mini",
+        owner_name: "Acme Rentals",
+        due_date: "Oct 17, 2026",
+        overdue: false,
+        item_url: "https://slatehub.com/equipment/abc",
+    };
+    for email in [
+        verification_email(VERIFY, "123456").unwrap(),
+        password_reset_email(
+            Some("Jane"),
+            "https://slatehub.com/reset-password",
+            "654321",
+        )
+        .unwrap(),
+        invitation_email(
+            "Acme",
+            "Tom",
+            "https://slatehub.com/signup",
+            Some("Join us"),
+        )
+        .unwrap(),
+        rental_reminder_email(&reminder).unwrap(),
+    ] {
+        assert!(!email.text_body.contains('<'), "{}", email.text_body);
+    }
+}
+
+#[test]
+fn password_reset_greets_by_name_or_falls_back() {
+    let email = password_reset_email(
+        Some("Jane"),
+        "https://slatehub.com/reset-password",
+        "654321",
+    )
+    .unwrap();
+    assert!(email.text_body.starts_with("Hello Jane,"));
+    assert!(email.html_body.contains("654321"));
+
+    let email =
+        password_reset_email(None, "https://slatehub.com/reset-password", "654321").unwrap();
+    assert!(email.text_body.starts_with("Hello there,"));
+}
+
+#[test]
+fn invitation_escapes_user_supplied_values() {
+    let email = invitation_email(
+        "<b>Acme</b>",
+        "Tom & Chris",
+        "https://slatehub.com/signup",
+        Some("<script>alert(1)</script>"),
+    )
+    .unwrap();
+    assert_eq!(
+        email.subject,
+        "You've been invited to join <b>Acme</b> on SlateHub"
+    );
+    assert!(!email.html_body.contains("<script>"));
+    assert!(email.html_body.contains("&#60;script&#62;"));
+    assert!(email.html_body.contains("Tom &#38; Chris"));
+    assert!(!email.html_body.contains("<b>Acme</b>"));
+    // Plain text is sent verbatim.
+    assert!(email.text_body.contains("Tom & Chris says: \"<script>"));
+}
+
+#[test]
+fn org_contact_escapes_the_senders_message() {
+    let email = org_contact_email(&OrgInquiry {
+        org_name: "Acme",
+        sender_name: "Tom & Chris",
+        sender_email: "tom@example.com",
+        message: "  <script>alert(1)</script>\nAre you hiring?  ",
+        profile_url: "https://slatehub.com/orgs/acme",
+    })
+    .unwrap();
+    assert_eq!(email.subject, "Message for Acme from Tom & Chris");
+    assert!(!email.html_body.contains("<script>"));
+    assert!(email.html_body.contains("Tom &#38; Chris"));
+    assert!(email.html_body.contains("https://slatehub.com/orgs/acme"));
+    assert!(
+        email
+            .text_body
+            .contains("<script>alert(1)</script>\nAre you hiring?")
+    );
+    assert!(email.text_body.contains("tom@example.com"));
+}
+
+#[test]
+fn invitation_omits_blank_message() {
+    let email = invitation_email("Acme", "Tom", "https://slatehub.com/signup", Some("  ")).unwrap();
+    assert!(!email.text_body.contains("says:"));
+    assert!(!email.html_body.contains("says:"));
+}
+
+#[test]
+fn rental_reminder_switches_copy_when_overdue() {
+    let mut reminder = RentalReminder {
+        renter_name: None,
+        item_name: "Lens Kit",
+        owner_name: "Acme Rentals",
+        due_date: "Oct 17, 2026",
+        overdue: false,
+        item_url: "https://slatehub.com/equipment/kit/abc",
+    };
+    let due = rental_reminder_email(&reminder).unwrap();
+    assert_eq!(due.subject, "Reminder: Lens Kit is due back Oct 17, 2026");
+    assert!(due.text_body.starts_with("Hello there,"));
+    assert!(due.text_body.contains("is due back on Oct 17, 2026"));
+    assert!(
+        due.html_body
+            .contains("https://slatehub.com/equipment/kit/abc")
+    );
+
+    reminder.overdue = true;
+    let overdue = rental_reminder_email(&reminder).unwrap();
+    assert_eq!(overdue.subject, "Overdue: please return Lens Kit");
+    assert!(overdue.text_body.contains("was due back on Oct 17, 2026"));
+    assert!(overdue.html_body.contains("Rental Overdue"));
+}
@@ -1,9 +1,13 @@
 //! Unit tests for the provider-selection precedence and Postmark address
 //! formatting in `services::email`. Both are pure functions (no env reads, no
 //! network), so these run fast and deterministically without manipulating
-//! process-wide environment variables.
+//! process-wide environment variables. The capture-provider round trip at the
+//! end is the exception: it sets `EMAIL_PROVIDER=capture` for the test.
 
-use slatehub::services::email::{EmailError, ProviderKind, format_address, select_provider_kind};
+use slatehub::services::email::{
+    EmailError, EmailService, ProviderKind, format_address, select_provider_kind,
+    take_captured_emails,
+};
 
 // ---------------------------------------------------------------------------
 // select_provider_kind — auto-detection (no explicit EMAIL_PROVIDER)
@@ -93,6 +97,18 @@ fn unknown_explicit_provider_errors() {
     ));
 }
 
+#[test]
+fn explicit_capture_needs_no_credentials() {
+    assert_eq!(
+        select_provider_kind(Some("capture"), false, false).unwrap(),
+        ProviderKind::Capture
+    );
+    assert_eq!(
+        select_provider_kind(Some(" CAPTURE "), true, true).unwrap(),
+        ProviderKind::Capture
+    );
+}
+
 #[test]
 fn blank_explicit_falls_back_to_auto_detect() {
     // An empty/whitespace EMAIL_PROVIDER behaves as if unset.
@@ -123,3 +139,62 @@ fn format_address_bare_when_no_name() {
 fn format_address_bare_when_name_blank() {
     assert_eq!(format_address("a@b.com", Some("   ")), "a@b.com");
 }
+
+// ---------------------------------------------------------------------------
+// capture provider — records instead of sending
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn capture_provider_records_sent_messages() {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("EMAIL_PROVIDER", "capture");
+        std::env::remove_var("EMAIL_CAPTURE_DIR");
+    }
+    let service = EmailService::from_env().unwrap();
+    unsafe {
+        std::env::remove_var("EMAIL_PROVIDER");
+    }
+    take_captured_emails();
+
+    service
+        .send_verification_email("jane@example.com", Some("Jane Doe"), "123456")
+        .await
+        .unwrap();
+
+    let captured = take_captured_emails();
+    assert_eq!(captured.len(), 1);
+    let email = &captured[0];
+    assert_eq!(email.to, "Jane Doe <jane@example.com>");
+    assert_eq!(email.subject, "Verify your SlateHub email address");
+    assert!(email.text_body.as_deref().unwrap().contains("123456"));
+    assert!(email.html_body.as_deref().unwrap().contains("123456"));
+    assert!(take_captured_emails().is_empty());
+}
+
+#[tokio::test]
+async fn capture_provider_writes_json_files_when_dir_set() {
+    let dir = std::env::temp_dir().join(format!("slatehub-email-capture-{}", std::process::id()));
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("EMAIL_PROVIDER", "capture");
+        std::env::set_var("EMAIL_CAPTURE_DIR", &dir);
+    }
+    let service = EmailService::from_env().unwrap();
+    unsafe {
+        std::env::remove_var("EMAIL_PROVIDER");
+        std::env::remove_var("EMAIL_CAPTURE_DIR");
+    }
+
+    service
+        .send_notification_email("a@b.com", None, "Hello", "text", "<p>html</p>")
+        .await
+        .unwrap();
+    take_captured_emails();
+
+    let files: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(files.len(), 1);
+    let json = std::fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+    assert!(json.contains("\"subject\": \"Hello\""));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,994 @@
+//! `EquipmentModel` purchase-currency handling, listing sort orders, the
+//! checkout/check-in lifecycle, kit completeness on checkout, duplicate
+//! serial numbers, the `/equipment/find` lookup, bulk check-in, the owner
+//! dashboard, renter reliability, availability on a given (shoot) date, and
+//! the rental agreement PDF's access rules, and the default
+//! categories/conditions seeded at init.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{StatusCode, header};
+use slatehub::db::DB;
+use slatehub::error::Error;
+use slatehub::models::equipment::{
+    CheckinData, CheckoutData, CreateEquipmentData, CreateKitData, EquipmentCondition,
+    EquipmentModel, EquipmentRental, EquipmentSort, Reliability, RentalTiming, RenterHistory,
+};
+use slatehub::record_id_ext::RecordIdExt;
+use surrealdb::types::{RecordId, SurrealValue};
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+async fn seed_lookups() {
+    DB.query(
+        "UPSERT equipment_category:test_camera SET name = 'Test Camera'; \
+         UPSERT equipment_condition:test_good SET name = 'Test Good';",
+    )
+    .await
+    .expect("seed lookups failed");
+}
+
+fn camera(price: Option<f64>, currency: Option<&str>) -> CreateEquipmentData {
+    CreateEquipmentData {
+        name: "Test Body".to_string(),
+        category: "test_camera".to_string(),
+        serial_number: None,
+        model: None,
+        manufacturer: None,
+        description: None,
+        purchase_date: None,
+        purchase_price: price,
+        purchase_currency: currency.map(str::to_string),
+        condition: "test_good".to_string(),
+        notes: None,
+        owner_type: "person".to_string(),
+        owner_person: Some("equipment_tester".to_string()),
+        owner_organization: None,
+        is_kit_item: false,
+        parent_kit: None,
+        current_location: None,
+    }
+}
+
+#[test]
+fn test_purchase_currency_defaults_and_normalizes() {
+    common::setup_test_db();
+    common::clean_table("equipment");
+    common::run(async {
+        seed_lookups().await;
+
+        let default = EquipmentModel::create_equipment(camera(Some(1200.0), None))
+            .await
+            .unwrap();
+        let expected = slatehub::config::default_currency();
+        assert_eq!(default.purchase_currency.as_deref(), Some(expected));
+
+        let euro = EquipmentModel::create_equipment(camera(Some(1250.5), Some(" eur")))
+            .await
+            .unwrap();
+        assert_eq!(euro.purchase_currency.as_deref(), Some("EUR"));
+        assert_eq!(
+            euro.formatted_purchase_price().as_deref(),
+            Some("1,250.50 €")
+        );
+
+        assert!(matches!(
+            EquipmentModel::create_equipment(camera(Some(10.0), Some("euro"))).await,
+            Err(Error::ValidationFields(_))
+        ));
+    });
+}
+
+#[test]
+fn test_backfill_purchase_currency_uses_configured_default() {
+    common::setup_test_db();
+    common::clean_table("equipment");
+    common::run(async {
+        seed_lookups().await;
+        let legacy = EquipmentModel::create_equipment(camera(Some(500.0), Some("EUR")))
+            .await
+            .unwrap();
+        DB.query("UPDATE $id SET purchase_currency = NONE")
+            .bind(("id", legacy.id.clone()))
+            .await
+            .expect("clear currency failed");
+
+        assert_eq!(
+            EquipmentModel::backfill_purchase_currency().await.unwrap(),
+            1
+        );
+        let currency: Option<String> = DB
+            .query("SELECT VALUE purchase_currency FROM ONLY $id")
+            .bind(("id", legacy.id))
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(
+            currency.as_deref(),
+            Some(slatehub::config::default_currency())
+        );
+        assert_eq!(
+            EquipmentModel::backfill_purchase_currency().await.unwrap(),
+            0
+        );
+    });
+}
+
+fn checkout_to(equipment_id: &str, person: &str) -> CheckoutData {
+    CheckoutData {
+        equipment_id: Some(equipment_id.to_string()),
+        kit_id: None,
+        renter_type: "person".to_string(),
+        renter_person: Some(person.to_string()),
+        renter_organization: None,
+        expected_return_date: None,
+        condition: "test_good".to_string(),
+        notes: Some("Out for the shoot".to_string()),
+        checkout_by: person.to_string(),
+        allow_partial: false,
+    }
+}
+
+#[test]
+fn test_list_for_owner_honors_each_sort() {
+    common::setup_test_db();
+    common::clean_table("equipment");
+    common::run(async {
+        seed_lookups().await;
+        for (name, price, available) in [
+            ("Bravo", 300.0, true),
+            ("Charlie", 100.0, false),
+            ("Alpha", 200.0, true),
+        ] {
+            let item = EquipmentModel::create_equipment(CreateEquipmentData {
+                name: name.to_string(),
+                ..camera(Some(price), None)
+            })
+            .await
+            .unwrap();
+            DB.query("UPDATE $id SET is_available = $available")
+                .bind(("id", item.id))
+                .bind(("available", available))
+                .await
+                .expect("set availability failed");
+        }
+
+        let names = |sort| async move {
+            EquipmentModel::list_equipment_for_owner("person", "equipment_tester", sort)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|e| e.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(EquipmentSort::CreatedAt).await,
+            ["Alpha", "Charlie", "Bravo"]
+        );
+        assert_eq!(
+            names(EquipmentSort::Name).await,
+            ["Alpha", "Bravo", "Charlie"]
+        );
+        assert_eq!(
+            names(EquipmentSort::Availability).await,
+            ["Alpha", "Bravo", "Charlie"]
+        );
+        assert_eq!(
+            names(EquipmentSort::PurchasePrice).await,
+            ["Bravo", "Alpha", "Charlie"]
+        );
+    });
+}
+
+#[test]
+fn test_checkout_checkin_lifecycle() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        let owner = common::create_test_user("lifecycle_owner")
+            .await
+            .id
+            .key_string();
+
+        let mut data = camera(None, None);
+        data.owner_person = Some(owner.clone());
+        let equipment = EquipmentModel::create_equipment(data).await.unwrap();
+        let equipment_id = equipment.id.key_string();
+        assert!(equipment.is_available);
+
+        // Checkout flips availability and opens an active rental.
+        let rental = EquipmentModel::checkout_equipment(checkout_to(&equipment_id, &owner))
+            .await
+            .unwrap();
+        assert!(rental.is_active);
+        assert_eq!(rental.checkout_condition.name, "Test Good");
+        assert!(
+            !EquipmentModel::get_equipment(&equipment_id)
+                .await
+                .unwrap()
+                .is_available
+        );
+        let active = EquipmentModel::get_active_rentals_for_equipment(&equipment_id)
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, rental.id);
+
+        // A second checkout of the same item is rejected.
+        assert!(matches!(
+            EquipmentModel::checkout_equipment(checkout_to(&equipment_id, &owner)).await,
+            Err(Error::Validation(_))
+        ));
+
+        // Only the owner (or the owning org's members) can return it.
+        let stranger = CheckinData {
+            return_condition: "test_good".to_string(),
+            return_notes: None,
+            return_by: "lifecycle_stranger".to_string(),
+        };
+        assert!(matches!(
+            EquipmentModel::checkin_equipment(&rental.id.key_string(), stranger).await,
+            Err(Error::Unauthorized)
+        ));
+
+        // Check-in closes the rental and restores availability.
+        let returned = EquipmentModel::checkin_equipment(
+            &rental.id.key_string(),
+            CheckinData {
+                return_condition: "test_good".to_string(),
+                return_notes: Some("All accounted for".to_string()),
+                return_by: owner.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!returned.is_active);
+        assert!(returned.actual_return_date.is_some());
+        assert_eq!(returned.return_notes.as_deref(), Some("All accounted for"));
+
+        // Returning it again is rejected rather than re-freeing anything.
+        let again = CheckinData {
+            return_condition: "test_good".to_string(),
+            return_notes: None,
+            return_by: owner.clone(),
+        };
+        assert!(matches!(
+            EquipmentModel::checkin_equipment(&rental.id.key_string(), again).await,
+            Err(Error::Validation(_))
+        ));
+        assert!(
+            EquipmentModel::get_equipment(&equipment_id)
+                .await
+                .unwrap()
+                .is_available
+        );
+        assert!(
+            EquipmentModel::get_active_rentals_for_equipment(&equipment_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // History keeps the closed rental; a fresh checkout is allowed again
+        // and shows up newest first.
+        let second = EquipmentModel::checkout_equipment(checkout_to(&equipment_id, &owner))
+            .await
+            .unwrap();
+        let history = EquipmentModel::get_rental_history_for_equipment(&equipment_id)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].id, second.id);
+        assert_eq!(history[1].id, rental.id);
+        assert!(history[0].is_active);
+        assert!(!history[1].is_active);
+    });
+}
+
+fn rental_between(checkout: &str, due: Option<&str>, is_active: bool) -> EquipmentRental {
+    let at = |day: &str| {
+        chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc()
+    };
+    EquipmentRental {
+        id: RecordId::new("equipment_rental", "r"),
+        equipment_id: Some(RecordId::new("equipment", "e")),
+        kit_id: None,
+        renter_type: "person".to_string(),
+        renter_person: Some(RecordId::new("person", "p")),
+        renter_organization: None,
+        checkout_date: at(checkout),
+        expected_return_date: due.map(at),
+        actual_return_date: None,
+        checkout_condition: EquipmentCondition {
+            id: RecordId::new("equipment_condition", "good"),
+            name: "Good".to_string(),
+            description: None,
+        },
+        return_condition: None,
+        checkout_notes: None,
+        return_notes: None,
+        checkout_by: RecordId::new("person", "p"),
+        return_by: None,
+        is_active,
+        created_at: at(checkout),
+        updated_at: at(checkout),
+    }
+}
+
+#[test]
+fn test_rental_covers_dates_from_checkout_through_expected_return() {
+    let day = |d: &str| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+
+    let rental = rental_between("2026-03-10", Some("2026-03-12"), true);
+    assert!(!rental.covers_date(day("2026-03-09")));
+    assert!(rental.covers_date(day("2026-03-10")));
+    assert!(rental.covers_date(day("2026-03-12")));
+    assert!(!rental.covers_date(day("2026-03-13")));
+
+    // No expected return: out indefinitely from checkout.
+    let open_ended = rental_between("2026-03-10", None, true);
+    assert!(!open_ended.covers_date(day("2026-03-09")));
+    assert!(open_ended.covers_date(day("2027-01-01")));
+
+    // A returned rental blocks nothing.
+    let returned = rental_between("2026-03-10", Some("2026-03-12"), false);
+    assert!(!returned.covers_date(day("2026-03-11")));
+}
+
+#[test]
+fn test_available_on_checks_the_rental_window() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        let owner = common::create_test_user("shoot_date_owner")
+            .await
+            .id
+            .key_string();
+
+        let mut data = camera(None, None);
+        data.owner_person = Some(owner.clone());
+        let equipment = EquipmentModel::create_equipment(data).await.unwrap();
+        let equipment_id = equipment.id.key_string();
+
+        let today = chrono::Utc::now().date_naive();
+        assert!(
+            EquipmentModel::available_on(&equipment_id, today)
+                .await
+                .unwrap()
+        );
+
+        let mut checkout = checkout_to(&equipment_id, &owner);
+        checkout.expected_return_date = Some(chrono::Utc::now() + chrono::Duration::days(3));
+        EquipmentModel::checkout_equipment(checkout).await.unwrap();
+
+        assert!(
+            !EquipmentModel::available_on(&equipment_id, today)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !EquipmentModel::available_on(&equipment_id, today + chrono::Days::new(3))
+                .await
+                .unwrap()
+        );
+        assert!(
+            EquipmentModel::available_on(&equipment_id, today + chrono::Days::new(10))
+                .await
+                .unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_incomplete_kit_checkout_needs_confirmation() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_kit");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        let owner = common::create_test_user("kit_owner").await.id.key_string();
+
+        let mut ids = Vec::new();
+        for name in ["Kit Body", "Kit Lens"] {
+            let mut data = camera(None, None);
+            data.name = name.to_string();
+            data.owner_person = Some(owner.clone());
+            let item = EquipmentModel::create_equipment(data).await.unwrap();
+            ids.push(item.id.key_string());
+        }
+        let kit = EquipmentModel::create_kit(CreateKitData {
+            name: "Test Kit".to_string(),
+            description: None,
+            category: "test_camera".to_string(),
+            owner_type: "person".to_string(),
+            owner_person: Some(owner.clone()),
+            owner_organization: None,
+            notes: None,
+            equipment_ids: ids.clone(),
+        })
+        .await
+        .unwrap();
+        let kit_id = kit.id.key_string();
+        assert!(
+            EquipmentModel::kit_completeness(&kit_id)
+                .await
+                .unwrap()
+                .is_complete()
+        );
+
+        // The lens goes out on its own, leaving the kit incomplete.
+        let lens_rental = EquipmentModel::checkout_equipment(checkout_to(&ids[1], &owner))
+            .await
+            .unwrap();
+        let completeness = EquipmentModel::kit_completeness(&kit_id).await.unwrap();
+        assert!(!completeness.is_complete());
+        assert_eq!(completeness.unavailable_names(), "Kit Lens");
+
+        let kit_checkout = || {
+            let mut data = checkout_to(&ids[0], &owner);
+            data.equipment_id = None;
+            data.kit_id = Some(kit_id.clone());
+            data
+        };
+        match EquipmentModel::checkout_equipment(kit_checkout()).await {
+            Err(Error::Validation(message)) => assert!(message.contains("Kit Lens")),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+        assert!(EquipmentModel::get_kit(&kit_id).await.unwrap().is_available);
+
+        // Confirming goes ahead and records what was missing.
+        let mut confirmed = kit_checkout();
+        confirmed.allow_partial = true;
+        let kit_rental = EquipmentModel::checkout_equipment(confirmed).await.unwrap();
+        assert!(
+            kit_rental
+                .checkout_notes
+                .as_deref()
+                .unwrap()
+                .ends_with("Checked out without: Kit Lens")
+        );
+
+        // Returning the kit leaves the separately rented lens unavailable.
+        let checkin = || CheckinData {
+            return_condition: "test_good".to_string(),
+            return_notes: None,
+            return_by: owner.clone(),
+        };
+        EquipmentModel::checkin_equipment(&kit_rental.id.key_string(), checkin())
+            .await
+            .unwrap();
+        assert!(
+            EquipmentModel::get_equipment(&ids[0])
+                .await
+                .unwrap()
+                .is_available
+        );
+        assert!(
+            !EquipmentModel::get_equipment(&ids[1])
+                .await
+                .unwrap()
+                .is_available
+        );
+
+        EquipmentModel::checkin_equipment(&lens_rental.id.key_string(), checkin())
+            .await
+            .unwrap();
+        assert!(
+            EquipmentModel::kit_completeness(&kit_id)
+                .await
+                .unwrap()
+                .is_complete()
+        );
+    });
+}
+
+#[test]
+fn test_duplicate_serials_are_flagged() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::run(async {
+        seed_lookups().await;
+        let user = common::create_test_user("serial_owner").await;
+        let owner = user.id.key_string();
+
+        let mut items = Vec::new();
+        for name in ["First Body", "Second Body"] {
+            let mut data = camera(None, None);
+            data.name = name.to_string();
+            data.serial_number = Some("SN-1234".to_string());
+            data.owner_person = Some(owner.clone());
+            items.push(EquipmentModel::create_equipment(data).await.unwrap());
+        }
+
+        let found = EquipmentModel::find_by_serial("person", &owner, " SN-1234 ")
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 2);
+
+        // The second item's page warns about the first, and the inventory
+        // lists the shared serial.
+        let get = |uri: &str| common::send(common::authed_request(&user, "GET", uri, ""));
+        let response = get(&format!("/equipment/{}", items[1].id.key_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8_lossy(&bytes);
+        assert!(html.contains("id=\"duplicate-serial\""));
+        assert!(html.contains("First Body"));
+
+        let response = get("/equipment").await;
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8_lossy(&bytes);
+        assert!(html.contains("section-serial-duplicates"));
+        assert!(html.contains("SN-1234"));
+
+        // Enforcement is opt-in.
+        // SAFETY: no other test in this binary sets a serial number, so
+        // none observes the variable.
+        unsafe { std::env::set_var("EQUIPMENT_UNIQUE_SERIALS", "true") };
+        let mut third = camera(None, None);
+        third.serial_number = Some("SN-1234".to_string());
+        third.owner_person = Some(owner.clone());
+        let result = EquipmentModel::create_equipment(third).await;
+        unsafe { std::env::remove_var("EQUIPMENT_UNIQUE_SERIALS") };
+        assert!(matches!(result, Err(Error::ValidationFields(_))));
+    });
+}
+
+#[test]
+fn test_find_tries_qr_then_serial_then_name() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::run(async {
+        seed_lookups().await;
+        let user = common::create_test_user("find_owner").await;
+        let owner = user.id.key_string();
+
+        let mut items = Vec::new();
+        for (name, serial) in [("Alexa Mini", "AM-1"), ("Alexa LF", "LF-1")] {
+            let mut data = camera(None, None);
+            data.name = name.to_string();
+            data.serial_number = Some(serial.to_string());
+            data.owner_person = Some(owner.clone());
+            items.push(EquipmentModel::create_equipment(data).await.unwrap());
+        }
+
+        let find = |q: &str| {
+            let uri = format!("/equipment/find?q={}", urlencoding::encode(q));
+            common::send(common::authed_request(&user, "GET", &uri, ""))
+        };
+        let detail = |i: usize| format!("/equipment/{}", items[i].id.key_string());
+
+        // A single hit redirects straight to the item.
+        let qr = items[0].qr_code.clone().unwrap();
+        for (q, expected) in [(qr.as_str(), detail(0)), ("LF-1", detail(1))] {
+            let response = find(q).await;
+            assert_eq!(response.status(), StatusCode::SEE_OTHER, "q={q}");
+            assert_eq!(response.headers()[header::LOCATION], expected.as_str());
+        }
+
+        // Several name matches are listed for the user to pick from.
+        let response = find("alexa").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8_lossy(&bytes);
+        assert!(html.contains("Alexa Mini") && html.contains("Alexa LF"));
+
+        let response = find("tripod").await;
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("No equipment matches"));
+    });
+}
+
+/// Check out `equipment` to its owner and return the rental id.
+async fn rent(equipment: &RecordId, by: &str) -> String {
+    let rows: Vec<R> = DB
+        .query(
+            "UPDATE $equipment SET is_available = false;
+             CREATE equipment_rental CONTENT {
+                equipment_id: $equipment,
+                renter_type: 'person',
+                renter_person: type::record('person', $by),
+                checkout_date: time::now(),
+                checkout_condition: equipment_condition:test_good,
+                checkout_by: type::record('person', $by),
+                is_active: true
+             } RETURN id",
+        )
+        .bind(("equipment", equipment.clone()))
+        .bind(("by", by.to_string()))
+        .await
+        .expect("create rental")
+        .take(1)
+        .expect("take rental");
+    rows.into_iter().next().expect("one").id.to_raw_string()
+}
+
+#[test]
+fn test_active_rental_lookups_use_the_composite_indexes() {
+    common::setup_test_db();
+    common::run(async {
+        for (field, target, index) in [
+            (
+                "equipment_id",
+                RecordId::new("equipment", "planned"),
+                "idx_rental_equipment_active",
+            ),
+            (
+                "kit_id",
+                RecordId::new("equipment_kit", "planned"),
+                "idx_rental_kit_active",
+            ),
+        ] {
+            let plan: Vec<serde_json::Value> = DB
+                .query(format!(
+                    "SELECT * FROM equipment_rental WHERE {field} = $target AND is_active = true EXPLAIN"
+                ))
+                .bind(("target", target))
+                .await
+                .expect("explain")
+                .take(0)
+                .expect("take plan");
+            let plan = serde_json::to_string(&plan).unwrap();
+            assert!(
+                plan.contains(index),
+                "{field} lookup not using {index}: {plan}"
+            );
+        }
+    });
+}
+
+#[test]
+fn test_bulk_checkin_reports_each_rental() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        let user = common::create_test_user("bulk_owner").await;
+        let owner = user.id.key_string();
+        let stranger = common::create_test_user("bulk_stranger")
+            .await
+            .id
+            .key_string();
+
+        let mut mine = camera(None, None);
+        mine.owner_person = Some(owner.clone());
+        let mine = EquipmentModel::create_equipment(mine).await.unwrap();
+        let mut theirs = camera(None, None);
+        theirs.owner_person = Some(stranger.clone());
+        let theirs = EquipmentModel::create_equipment(theirs).await.unwrap();
+
+        let mine_rental = rent(&mine.id, &owner).await;
+        let theirs_rental = rent(&theirs.id, &stranger).await;
+
+        let body = serde_json::json!({
+            "rental_ids": [mine_rental, theirs_rental, "equipment_rental:missing"],
+            "return_condition": "test_good",
+            "return_notes": "Returned after the shoot",
+        });
+        let mut request = common::authed_request(&user, "POST", "/equipment/checkin-bulk", "");
+        request.headers_mut().insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/json"),
+        );
+        *request.body_mut() = Body::from(body.to_string());
+        let response = common::send(request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let statuses: Vec<&str> = json["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, ["checked_in", "forbidden", "not_found"]);
+
+        let mine = EquipmentModel::get_equipment(&mine.id.key_string())
+            .await
+            .unwrap();
+        assert!(mine.is_available);
+        let theirs = EquipmentModel::get_equipment(&theirs.id.key_string())
+            .await
+            .unwrap();
+        assert!(!theirs.is_available);
+    });
+}
+
+#[test]
+fn test_owner_dashboard_summarizes_status_and_returns() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        DB.query("UPSERT equipment_condition:test_damaged SET name = 'Damaged'")
+            .await
+            .expect("seed damaged condition");
+        let user = common::create_test_user("dashboard_owner").await;
+        let owner = user.id.key_string();
+
+        let mut items = Vec::new();
+        for name in ["A Cam", "B Cam", "C Cam", "D Cam", "E Cam"] {
+            let mut data = camera(None, None);
+            data.name = name.to_string();
+            data.owner_person = Some(owner.clone());
+            items.push(EquipmentModel::create_equipment(data).await.unwrap());
+        }
+        let mut broken = camera(None, None);
+        broken.condition = "test_damaged".to_string();
+        broken.owner_person = Some(owner.clone());
+        EquipmentModel::create_equipment(broken).await.unwrap();
+        // Someone else's overdue gear doesn't count.
+        let mut theirs = camera(None, None);
+        theirs.owner_person = Some("dashboard_stranger".to_string());
+        let theirs = EquipmentModel::create_equipment(theirs).await.unwrap();
+
+        // Overdue, due in two days, due in a month.
+        for (item, due) in [
+            (&items[0], "time::now() - 1d"),
+            (&items[1], "time::now() + 2d"),
+            (&items[2], "time::now() + 30d"),
+            (&theirs, "time::now() - 1d"),
+        ] {
+            let rental = rent(&item.id, &owner).await;
+            DB.query(format!(
+                "UPDATE type::record($rental) SET expected_return_date = {due}"
+            ))
+            .bind(("rental", rental))
+            .await
+            .expect("set due date");
+        }
+
+        let dashboard = EquipmentModel::owner_dashboard("person", &owner)
+            .await
+            .unwrap();
+        assert_eq!(dashboard.available, 2);
+        assert_eq!(dashboard.rented, 3);
+        assert_eq!(dashboard.maintenance, 1);
+        assert_eq!(dashboard.total(), 6);
+        assert_eq!(dashboard.overdue_rentals, 1);
+        assert_eq!(dashboard.upcoming_returns.len(), 1);
+        assert_eq!(dashboard.upcoming_returns[0].item_name, "B Cam");
+
+        let response = common::send(common::authed_request(
+            &user,
+            "GET",
+            "/equipment/dashboard",
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    });
+}
+
+#[test]
+fn test_renter_history_tallies_on_time_and_late_returns() {
+    use chrono::{Duration, TimeZone, Utc};
+
+    let now = Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+    let due = now - Duration::days(10);
+    let returned = |at| RentalTiming {
+        expected_return_date: Some(due),
+        actual_return_date: Some(at),
+        is_active: false,
+    };
+    let rentals = [
+        // Same day as due (stored as midnight) is still on time.
+        returned(due + Duration::hours(20)),
+        returned(due - Duration::days(1)),
+        // Two days and four days late.
+        returned(due + Duration::hours(48)),
+        returned(due + Duration::hours(96)),
+        // Still out past its date.
+        RentalTiming {
+            expected_return_date: Some(due),
+            actual_return_date: None,
+            is_active: true,
+        },
+        // No due date: counted, not judged.
+        RentalTiming {
+            expected_return_date: None,
+            actual_return_date: Some(now),
+            is_active: false,
+        },
+    ];
+
+    let history = RenterHistory::from_rentals(&rentals, now);
+    assert_eq!(history.total_rentals, 6);
+    assert_eq!(history.on_time_returns, 2);
+    assert_eq!(history.late_returns, 2);
+    assert_eq!(history.currently_overdue, 1);
+    assert_eq!(history.average_lateness_hours, Some(72.0));
+    assert_eq!(history.reliability(), Reliability::Unreliable);
+
+    assert_eq!(
+        RenterHistory::from_rentals(&rentals[..2], now).reliability(),
+        Reliability::Reliable
+    );
+    assert_eq!(
+        RenterHistory::from_rentals(&rentals[5..], now).reliability(),
+        Reliability::New
+    );
+}
+
+#[test]
+fn test_checkout_shows_renter_history_to_the_owner_only() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        let owner_user = common::create_test_user("history_owner").await;
+        let owner = owner_user.id.key_string();
+        let renter = common::create_test_user("history_renter")
+            .await
+            .id
+            .key_string();
+        let stranger = common::create_test_user("history_stranger").await;
+
+        let mut data = camera(None, None);
+        data.owner_person = Some(owner.clone());
+        let item = EquipmentModel::create_equipment(data).await.unwrap();
+        let rental = rent(&item.id, &renter).await;
+        DB.query(
+            "UPDATE type::record($rental) SET expected_return_date = time::now() - 5d, \
+             actual_return_date = time::now(), is_active = false",
+        )
+        .bind(("rental", rental))
+        .await
+        .expect("close rental late");
+
+        let history = EquipmentModel::renter_history("person", &renter)
+            .await
+            .unwrap();
+        assert_eq!(history.total_rentals, 1);
+        assert_eq!(history.late_returns, 1);
+
+        let uri = format!(
+            "/equipment/checkout?equipment_id={}&renter_type=person&renter_id={renter}",
+            item.id.key_string()
+        );
+        for (user, shown) in [(&owner_user, true), (&stranger, false)] {
+            let response = common::send(common::authed_request(user, "GET", &uri, "")).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let html = String::from_utf8_lossy(&bytes);
+            assert_eq!(
+                html.contains(r#"data-reliability="unreliable""#),
+                shown,
+                "history shown: {shown}"
+            );
+        }
+    });
+}
+
+#[test]
+fn test_rental_agreement_pdf_is_for_the_parties_only() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        seed_lookups().await;
+        let owner = common::create_test_user("agreement_owner").await;
+        let renter = common::create_test_user("agreement_renter").await;
+        let stranger = common::create_test_user("agreement_stranger").await;
+
+        let mut data = camera(None, None);
+        data.owner_person = Some(owner.id.key_string());
+        let item = EquipmentModel::create_equipment(data).await.unwrap();
+        let rental = rent(&item.id, &renter.id.key_string()).await;
+        let uri = format!(
+            "/equipment/rental/{}/agreement.pdf",
+            RecordId::parse_simple(&rental).unwrap().key_string()
+        );
+
+        for user in [&owner, &renter] {
+            let response = common::send(common::authed_request(user, "GET", &uri, "")).await;
+            assert_eq!(response.status(), StatusCode::OK, "{}", user.username);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(bytes.starts_with(b"%PDF"));
+        }
+
+        let response = common::send(common::authed_request(&stranger, "GET", &uri, "")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    });
+}
+
+#[test]
+fn test_equipment_can_be_created_right_after_init() {
+    common::setup_test_db();
+    common::run(async {
+        // Start from a database whose default lookups were never seeded;
+        // the `test_*` rows the other tests use are left in place.
+        DB.query(
+            "DELETE equipment_category WHERE name IN $categories; \
+             DELETE equipment_condition WHERE name IN $conditions;",
+        )
+        .bind((
+            "categories",
+            slatehub::db::EQUIPMENT_CATEGORIES
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>(),
+        ))
+        .bind((
+            "conditions",
+            slatehub::db::EQUIPMENT_CONDITIONS
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>(),
+        ))
+        .await
+        .unwrap();
+
+        // Twice: seeding must not duplicate or trip the unique name index.
+        slatehub::db::ensure_db_initialized().await.unwrap();
+        slatehub::db::ensure_db_initialized().await.unwrap();
+
+        let names: Vec<String> = DB
+            .query("SELECT VALUE name FROM equipment_condition WHERE name = 'damaged'")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(names, ["damaged"]);
+
+        // The seeded rows have generated ids; forms submit those keys.
+        let key = |table: &'static str, name: &'static str| async move {
+            let rows: Vec<R> = DB
+                .query(format!("SELECT id FROM {table} WHERE name = $name"))
+                .bind(("name", name))
+                .await
+                .unwrap()
+                .take(0)
+                .unwrap();
+            rows.into_iter().next().expect("seeded row").id.key_string()
+        };
+        let mut data = camera(None, None);
+        data.category = key("equipment_category", "camera").await;
+        data.condition = key("equipment_condition", "good").await;
+        let equipment = EquipmentModel::create_equipment(data).await.unwrap();
+        assert_eq!(equipment.category.name, "camera");
+        assert_eq!(equipment.condition.name, "good");
+    });
+}
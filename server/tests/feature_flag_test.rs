@@ -83,6 +83,7 @@ async fn create_user(
         username: username.to_string(),
         email: email.to_string(),
         name: username.to_string(),
+        timezone: None,
     }
 }
 
@@ -0,0 +1,67 @@
+use serde::Deserialize;
+use slatehub::error::Error;
+use slatehub::serde_utils::{deserialize_optional_i32, parse_form};
+use slatehub::templates::FormValues;
+
+#[derive(Debug, Deserialize)]
+struct YearForm {
+    name: String,
+    #[serde(default, deserialize_with = "deserialize_optional_i32")]
+    founded_year: Option<i32>,
+}
+
+#[test]
+fn test_form_values_from_body_keeps_submitted_input() {
+    let values = FormValues::from_body("name=Acme+%26+Co&founded_year=abc&public=on");
+    assert_eq!(values.get("name"), "Acme & Co");
+    // Values that failed to parse are still echoed back for correction.
+    assert_eq!(values.get("founded_year"), "abc");
+    assert!(values.checked("public"));
+    assert!(!values.checked("allow_join_requests"));
+    assert_eq!(values.get("missing"), "");
+}
+
+#[test]
+fn test_form_values_seeded_from_record() {
+    let values = FormValues::default()
+        .with("name", Some("Acme"))
+        .with("description", None::<String>)
+        .with("founded_year", Some(1999))
+        .with_checked("public", true)
+        .with_checked("allow_join_requests", false);
+    assert_eq!(values.get("name"), "Acme");
+    assert_eq!(values.get("description"), "");
+    assert_eq!(values.get("founded_year"), "1999");
+    assert!(values.checked("public"));
+    assert!(!values.checked("allow_join_requests"));
+}
+
+#[test]
+fn test_parse_form_reports_validation_errors() {
+    let form: YearForm = parse_form("name=Acme&founded_year=1999").unwrap();
+    assert_eq!(form.name, "Acme");
+    assert_eq!(form.founded_year, Some(1999));
+
+    let err = parse_form::<YearForm>("name=Acme&founded_year=abc").unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+    assert!(err.form_message().is_some());
+}
+
+#[test]
+fn test_form_message_only_for_user_fixable_errors() {
+    assert_eq!(
+        Error::Validation("Name is required".into())
+            .form_message()
+            .as_deref(),
+        Some("Name is required")
+    );
+    assert_eq!(
+        Error::Conflict("Slug taken".into())
+            .form_message()
+            .as_deref(),
+        Some("Slug taken")
+    );
+    assert_eq!(Error::Forbidden.form_message(), None);
+    assert_eq!(Error::NotFound.form_message(), None);
+    assert_eq!(Error::Internal("boom".into()).form_message(), None);
+}
@@ -0,0 +1,163 @@
+//! Structured profile locations: great-circle helpers behind radius search,
+//! the geocoder's result cache, the `/api/locations/near?q=` rate limit,
+//! and `Person::find_near` / `find_by_location` over geocoded profiles.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use slatehub::db::DB;
+use slatehub::models::person::{GeoLocation, Person};
+use slatehub::services::geocoding::{GeocodeCache, bounding_box, distance_km};
+use std::time::{Duration, Instant};
+
+const ATLANTA: (f64, f64) = (33.749, -84.388);
+const MARIETTA: (f64, f64) = (33.9526, -84.5499);
+const LOS_ANGELES: (f64, f64) = (34.0522, -118.2437);
+
+#[test]
+fn distance_between_known_cities() {
+    let d = distance_km(ATLANTA.0, ATLANTA.1, LOS_ANGELES.0, LOS_ANGELES.1);
+    assert!((d - 3110.0).abs() < 30.0, "Atlanta to LA was {d} km");
+    assert_eq!(distance_km(ATLANTA.0, ATLANTA.1, ATLANTA.0, ATLANTA.1), 0.0);
+}
+
+#[test]
+fn bounding_box_encloses_the_radius() {
+    let (min_lat, max_lat, min_lng, max_lng) = bounding_box(ATLANTA.0, ATLANTA.1, 50.0);
+    assert!(min_lat < MARIETTA.0 && MARIETTA.0 < max_lat);
+    assert!(min_lng < MARIETTA.1 && MARIETTA.1 < max_lng);
+    assert!(LOS_ANGELES.1 < min_lng);
+
+    // Crossing the antimeridian or reaching a pole spans every longitude.
+    assert_eq!(bounding_box(0.0, 179.9, 50.0).2, -180.0);
+    assert_eq!(bounding_box(89.9, 0.0, 50.0).3, 180.0);
+}
+
+#[test]
+fn geocode_cache_matches_loosely_and_expires() {
+    let cache = GeocodeCache::new(Duration::from_secs(60), 2);
+    let start = Instant::now();
+    let atlanta = GeoLocation {
+        city: Some("Atlanta".to_string()),
+        region: Some("Georgia".to_string()),
+        country: Some("United States".to_string()),
+        country_code: Some("us".to_string()),
+        lat: ATLANTA.0,
+        lng: ATLANTA.1,
+    };
+
+    cache.insert_at("Atlanta, GA", Some(atlanta.clone()), start);
+    cache.insert_at("nowhere at all", None, start);
+    assert_eq!(
+        cache.get_at("  atlanta,   GA ", start + Duration::from_secs(59)),
+        Some(Some(atlanta))
+    );
+    // A cached miss is still an answer.
+    assert_eq!(cache.get_at("Nowhere at all", start), Some(None));
+    assert_eq!(
+        cache.get_at("Atlanta, GA", start + Duration::from_secs(60)),
+        None
+    );
+
+    // At capacity, expired entries make room first.
+    cache.insert_at("Marietta", None, start + Duration::from_secs(61));
+    assert_eq!(
+        cache.get_at("Marietta", start + Duration::from_secs(61)),
+        Some(None)
+    );
+    assert_eq!(cache.get_at("Atlanta, GA", start), None);
+}
+
+#[test]
+fn near_place_lookups_are_rate_limited_per_client() {
+    common::setup_test_db();
+    common::run(async {
+        let request = |ip: &str| {
+            Request::builder()
+                .uri("/api/locations/near?q=Nowhere%20Special")
+                .header("x-forwarded-for", ip)
+                .body(Body::empty())
+                .unwrap()
+        };
+        for _ in 0..30 {
+            let response = common::send(request("203.0.113.77")).await;
+            assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        }
+        let response = common::send(request("203.0.113.77")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response.headers().contains_key("retry-after"));
+
+        // Other clients, and coordinate lookups, aren't affected.
+        let response = common::send(request("203.0.113.78")).await;
+        assert_ne!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let response = common::send(
+            Request::builder()
+                .uri("/api/locations/near?lat=33.7&lng=-84.4")
+                .header("x-forwarded-for", "203.0.113.77")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    });
+}
+
+async fn insert_person(username: &str, private: bool, location: &str, city: &str, at: (f64, f64)) {
+    DB.query(
+        "CREATE type::record('person', $username) CONTENT {
+            email: string::concat($username, '@example.com'),
+            password: 'hashed',
+            username: $username,
+            verification_status: 'email',
+            profile: {
+                name: $username, location: $location, is_private: $private,
+                geo: { city: $city, region: 'Somewhere', country: 'United States', country_code: 'us', lat: $lat, lng: $lng },
+                social_links: [], ethnicity: [], unions: [], languages: [], experience: [],
+                education: [], reels: [], media_other: [], awards: []
+            }
+        }",
+    )
+    .bind(("username", username.to_string()))
+    .bind(("private", private))
+    .bind(("location", location.to_string()))
+    .bind(("city", city.to_string()))
+    .bind(("lat", at.0))
+    .bind(("lng", at.1))
+    .await
+    .and_then(|r| r.check())
+    .expect("create person");
+}
+
+#[test]
+fn test_find_near_and_find_by_location() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        insert_person("geo_atl", false, "ATL", "Atlanta", ATLANTA).await;
+        insert_person("geo_marietta", false, "Marietta, GA", "Marietta", MARIETTA).await;
+        insert_person("geo_private", true, "Atlanta, GA", "Atlanta", ATLANTA).await;
+        insert_person("geo_la", false, "LA", "Los Angeles", LOS_ANGELES).await;
+
+        let near = Person::find_near(ATLANTA.0, ATLANTA.1, 50.0, 10)
+            .await
+            .expect("find near");
+        let usernames: Vec<&str> = near.iter().map(|(p, _)| p.username.as_str()).collect();
+        assert_eq!(usernames, ["geo_atl", "geo_marietta"]);
+        assert!(near[0].1 < near[1].1);
+
+        let nearest = Person::find_near(ATLANTA.0, ATLANTA.1, 50.0, 1)
+            .await
+            .unwrap();
+        assert_eq!(nearest.len(), 1);
+
+        // "los angeles" only appears in the structured city, not the display string.
+        let found = Person::find_by_location("los angeles").await.unwrap();
+        let usernames: Vec<&str> = found.iter().map(|p| p.username.as_str()).collect();
+        assert_eq!(usernames, ["geo_la"]);
+
+        let found = Person::find_by_location("marietta").await.unwrap();
+        assert_eq!(found.len(), 1);
+    });
+}
@@ -0,0 +1,206 @@
+//! Locale negotiation, catalog fallback, and the request-scoped locale:
+//! `Accept-Language` and the `lang` cookie pick the language of messages,
+//! the footer picker, and `Content-Language`. Also the request timezone:
+//! the person's preference, then the `tz` cookie, then UTC — and relative
+//! "3 hours ago" / "in 2 days" phrasing.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::i18n::{self, Locale};
+use slatehub::validation::ValidationErrors;
+use tower::ServiceExt;
+
+#[test]
+fn negotiates_accept_language_by_quality() {
+    assert_eq!(Locale::negotiate(None), Locale::En);
+    assert_eq!(Locale::negotiate(Some("fr-CA,fr;q=0.9")), Locale::Fr);
+    assert_eq!(
+        Locale::negotiate(Some("ja, de;q=0.5, es;q=0.8")),
+        Locale::Es
+    );
+    assert_eq!(Locale::negotiate(Some("pt-BR, it;q=0.9")), Locale::En);
+    assert_eq!(Locale::negotiate(Some("de;q=0, en;q=0.1")), Locale::En);
+    assert_eq!(Locale::negotiate(Some("*")), Locale::En);
+}
+
+#[test]
+fn missing_keys_fall_back_to_english_then_the_key() {
+    for locale in Locale::ALL {
+        assert_ne!(
+            locale.message("auth.invalid_credentials"),
+            "auth.invalid_credentials"
+        );
+    }
+    assert_eq!(Locale::Fr.message("no.such.key"), "no.such.key");
+}
+
+#[tokio::test]
+async fn messages_follow_the_scoped_locale() {
+    assert_eq!(i18n::t("auth.passwords_mismatch"), "Passwords do not match");
+
+    let messages = i18n::scope(Locale::Es, async {
+        let mut errors = ValidationErrors::new();
+        errors
+            .required("name", "Nombre", " ")
+            .max_len("bio", "Biografía", "abcdef", 3);
+        errors.into_messages()
+    })
+    .await;
+    assert_eq!(
+        messages,
+        [
+            "Nombre es obligatorio",
+            "Biografía debe tener como máximo 3 caracteres"
+        ]
+    );
+
+    let reset = i18n::scope(Locale::De, async {
+        i18n::tf("auth.reset_code_sent", &[("email", &"a@example.com")])
+    })
+    .await;
+    assert!(reset.starts_with("Falls ein Konto für a@example.com existiert"));
+}
+
+async fn get(uri: &str, headers: &[(header::HeaderName, &str)]) -> axum::response::Response {
+    let mut request = Request::builder().uri(uri);
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+    slatehub::routes::app()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn cookie_preference_beats_accept_language() {
+    let response = get("/static/js/spow.js", &[(header::ACCEPT_LANGUAGE, "fr")]).await;
+    assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "fr");
+
+    let response = get(
+        "/static/js/spow.js",
+        &[(header::ACCEPT_LANGUAGE, "fr"), (header::COOKIE, "lang=de")],
+    )
+    .await;
+    assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "de");
+}
+
+#[tokio::test]
+async fn language_picker_sets_the_cookie_and_returns_to_the_page() {
+    let response = slatehub::routes::app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/language")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .header(header::REFERER, "http://localhost:3000/people?page=2")
+                .body(Body::from("lang=es"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()[header::LOCATION], "/people?page=2");
+    let cookie = response.headers()[header::SET_COOKIE].to_str().unwrap();
+    assert!(cookie.starts_with("lang=es;"), "{cookie}");
+
+    let response = slatehub::routes::app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/language")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from("lang=klingon"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn instants_render_in_the_scoped_timezone() {
+    use chrono::{TimeZone, Utc};
+
+    let at = Utc.with_ymd_and_hms(2026, 3, 5, 23, 30, 0).unwrap();
+    assert_eq!(i18n::local_datetime(&at), "March 5, 2026 at 11:30 PM UTC");
+
+    let la = i18n::parse_timezone("America/Los_Angeles").unwrap();
+    let shown = i18n::scope_timezone(la, async { i18n::local_datetime(&at) }).await;
+    assert_eq!(shown, "March 5, 2026 at 3:30 PM PST");
+
+    let tokyo = i18n::parse_timezone("Asia/Tokyo").unwrap();
+    let day = i18n::scope_timezone(tokyo, async { i18n::local_date(&at) }).await;
+    assert_eq!(day, "March 6, 2026");
+
+    assert!(i18n::parse_timezone("Mars/Olympus_Mons").is_none());
+}
+
+#[test]
+fn timezone_prefers_the_saved_setting_over_the_cookie() {
+    use slatehub::middleware::CurrentUser;
+    use slatehub::middleware::locale::resolve_timezone;
+    use std::sync::Arc;
+
+    let request = |cookie: Option<&str>, saved: Option<&str>| {
+        let mut builder = Request::builder().uri("/");
+        if let Some(cookie) = cookie {
+            builder = builder.header(header::COOKIE, cookie);
+        }
+        let mut request = builder.body(Body::empty()).unwrap();
+        if let Some(saved) = saved {
+            request.extensions_mut().insert(Arc::new(CurrentUser {
+                id: "person:tz".to_string(),
+                username: "tz".to_string(),
+                email: "tz@example.com".to_string(),
+                name: "TZ".to_string(),
+                timezone: Some(saved.to_string()),
+            }));
+        }
+        request
+    };
+
+    assert_eq!(resolve_timezone(&request(None, None)).name(), "UTC");
+    assert_eq!(
+        resolve_timezone(&request(Some("tz=Europe/Berlin"), None)).name(),
+        "Europe/Berlin"
+    );
+    assert_eq!(
+        resolve_timezone(&request(Some("tz=Europe/Berlin"), Some("Asia/Tokyo"))).name(),
+        "Asia/Tokyo"
+    );
+    assert_eq!(
+        resolve_timezone(&request(Some("tz=nowhere"), None)).name(),
+        "UTC"
+    );
+}
+
+#[tokio::test]
+async fn relative_times_read_naturally_both_ways() {
+    use chrono::{Duration, TimeZone, Utc};
+
+    let now = Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+    let at = |offset: Duration| i18n::relative_time(&(now + offset), &now);
+
+    assert_eq!(at(Duration::seconds(-10)), "just now");
+    assert_eq!(at(Duration::seconds(-70)), "a minute ago");
+    assert_eq!(at(Duration::hours(-3)), "3 hours ago");
+    assert_eq!(at(Duration::days(-1)), "a day ago");
+    assert_eq!(at(Duration::days(-15)), "2 weeks ago");
+    assert_eq!(at(Duration::days(-95)), "3 months ago");
+    assert_eq!(at(Duration::days(-800)), "2 years ago");
+    assert_eq!(at(Duration::days(2)), "in 2 days");
+    assert_eq!(at(Duration::minutes(45)), "in 45 minutes");
+
+    let german = i18n::scope(Locale::De, async {
+        (at(Duration::days(-3)), at(Duration::hours(1)))
+    })
+    .await;
+    assert_eq!(
+        german,
+        ("vor 3 Tagen".to_string(), "in einer Stunde".to_string())
+    );
+
+    let spanish = i18n::scope(Locale::Es, async { at(Duration::days(-14)) }).await;
+    assert_eq!(spanish, "hace 2 semanas");
+}
@@ -0,0 +1,66 @@
+//! The image pool caps concurrent jobs, queues a bounded number more, and
+//! turns the rest away with a 429-style rate-limit error. No test DB needed.
+
+use slatehub::error::Error;
+use slatehub::services::image_pool::{ImagePool, ImagePoolConfig};
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn full_queue_rejects_with_retry_after() {
+    let pool = Arc::new(ImagePool::new(ImagePoolConfig {
+        concurrency: 1,
+        queue: 1,
+    }));
+
+    // Occupy the only permit until released.
+    let (release, hold) = mpsc::channel::<()>();
+    let running = tokio::spawn({
+        let pool = pool.clone();
+        async move {
+            pool.run(move || {
+                hold.recv().ok();
+                Ok(1)
+            })
+            .await
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // One job may wait in the queue...
+    let queued = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.run(|| Ok(2)).await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // ...the next is turned away.
+    match pool.run(|| Ok(3)).await {
+        Err(Error::RateLimited { status, .. }) => {
+            assert_eq!(status.remaining, 0);
+            assert!(status.retry_after_secs() >= 1);
+        }
+        other => panic!("expected RateLimited, got {other:?}"),
+    }
+
+    release.send(()).unwrap();
+    assert_eq!(running.await.unwrap().unwrap(), 1);
+    assert_eq!(queued.await.unwrap().unwrap(), 2);
+
+    // Capacity is back once the backlog drains.
+    assert_eq!(pool.run(|| Ok(4)).await.unwrap(), 4);
+}
+
+#[tokio::test]
+async fn job_errors_pass_through() {
+    let pool = ImagePool::new(ImagePoolConfig {
+        concurrency: 2,
+        queue: 0,
+    });
+    let err = pool
+        .run(|| Err::<(), _>(Error::bad_request("Invalid image file")))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::BadRequest(_)));
+}
@@ -0,0 +1,140 @@
+//! Tests for the JWT lifetime and environment-binding claims: minted tokens
+//! carry the configured `iss`/`aud`, `decode_jwt` (and so the auth
+//! middleware) rejects expired tokens and tokens minted for another
+//! issuer or audience, and `JWT_DURATION` sizes new tokens.
+
+use slatehub::auth::{JwtConfig, create_jwt, decode_jwt};
+use slatehub::config::Config;
+use slatehub::error::Error;
+
+const SECRET: &str = "test-secret-for-jwt-claims-tests";
+
+/// Reset the environment every test here depends on, and install a config
+/// whose base URL the default issuer and audience come from.
+fn reset_env() {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("JWT_SECRET", SECRET);
+        std::env::set_var("APP_URL", "https://slatehub.test");
+        std::env::set_var("DB_USERNAME", "root");
+        std::env::set_var("DB_PASSWORD", "root");
+        std::env::remove_var("JWT_ISSUER");
+        std::env::remove_var("JWT_AUDIENCE");
+        std::env::remove_var("JWT_DURATION");
+    }
+    Config::from_env().expect("config loads").install();
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Sign arbitrary claims with the test secret.
+fn sign(claims: serde_json::Value) -> String {
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::HS256),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .expect("token encodes")
+}
+
+fn claims_with(iss: &str, aud: &str, exp: u64) -> serde_json::Value {
+    serde_json::json!({
+        "sub": "person:test",
+        "username": "tester",
+        "email": "t@example.com",
+        "iat": now(),
+        "exp": exp,
+        "iss": iss,
+        "aud": aud,
+    })
+}
+
+#[test]
+fn issuer_and_audience_default_to_app_url() {
+    reset_env();
+    assert_eq!(JwtConfig::issuer(), "https://slatehub.test");
+    assert_eq!(JwtConfig::audience(), "https://slatehub.test");
+
+    let token = create_jwt("person:test", "tester", "t@example.com").expect("token creation");
+    let claims = decode_jwt(&token).expect("token decodes");
+    assert_eq!(claims.iss.as_deref(), Some("https://slatehub.test"));
+    assert_eq!(claims.aud.as_deref(), Some("https://slatehub.test"));
+}
+
+#[test]
+fn configured_issuer_and_audience_are_minted() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::set_var("JWT_ISSUER", "slatehub-staging");
+        std::env::set_var("JWT_AUDIENCE", "staging");
+    }
+    let token = create_jwt("person:test", "tester", "t@example.com").expect("token creation");
+    let claims = decode_jwt(&token).expect("token decodes");
+    assert_eq!(claims.iss.as_deref(), Some("slatehub-staging"));
+    assert_eq!(claims.aud.as_deref(), Some("staging"));
+    reset_env();
+}
+
+#[test]
+fn token_lifetime_follows_jwt_duration() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("JWT_DURATION", "600") }
+    let token = create_jwt("person:test", "tester", "t@example.com").expect("token creation");
+    let claims = decode_jwt(&token).expect("token decodes");
+    assert_eq!(claims.exp - claims.iat, 600);
+    reset_env();
+}
+
+#[test]
+fn expired_tokens_are_rejected() {
+    reset_env();
+    // Well past jsonwebtoken's default 60s leeway.
+    let token = sign(claims_with(
+        "https://slatehub.test",
+        "https://slatehub.test",
+        now() - 3_600,
+    ));
+    assert!(matches!(decode_jwt(&token), Err(Error::Unauthorized)));
+}
+
+#[test]
+fn tokens_for_another_audience_are_rejected() {
+    reset_env();
+    let token = sign(claims_with(
+        "https://slatehub.test",
+        "https://staging.slatehub.test",
+        now() + 3_600,
+    ));
+    assert!(matches!(decode_jwt(&token), Err(Error::Unauthorized)));
+}
+
+#[test]
+fn tokens_from_another_issuer_are_rejected() {
+    reset_env();
+    let token = sign(claims_with(
+        "https://staging.slatehub.test",
+        "https://slatehub.test",
+        now() + 3_600,
+    ));
+    assert!(matches!(decode_jwt(&token), Err(Error::Unauthorized)));
+}
+
+#[test]
+fn production_rejects_a_staging_minted_token() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("JWT_AUDIENCE", "staging") }
+    let staging = create_jwt("person:test", "tester", "t@example.com").expect("token creation");
+
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("JWT_AUDIENCE", "production") }
+    assert!(matches!(decode_jwt(&staging), Err(Error::Unauthorized)));
+    reset_env();
+}
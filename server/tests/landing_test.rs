@@ -309,6 +309,7 @@ fn sample_template(
         version: "test".into(),
         active_page: "landing".into(),
         user,
+        nonce: "test-nonce".into(),
         campaign_id: "not-on-set".into(),
         video_id: "otrrrEH8wUw".into(),
         pixel_id,
@@ -0,0 +1,75 @@
+//! Tests for `LikesModel`: target validation (pure) and like/unlike
+//! idempotence + counts against the test DB.
+
+mod common;
+
+use slatehub::db::DB;
+use slatehub::models::likes::LikesModel;
+use surrealdb::types::{RecordId, SurrealValue};
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+#[test]
+fn only_likeable_tables_are_accepted() {
+    for table in ["person", "location", "production", "comment", "media"] {
+        assert!(LikesModel::validate_target_id(&RecordId::new(table, "x")).is_ok());
+    }
+    assert!(LikesModel::validate_target_id(&RecordId::new("oauth_client", "x")).is_err());
+}
+
+#[test]
+fn like_is_idempotent_and_counted() {
+    common::setup_test_db();
+    common::clean_table("likes");
+    common::clean_table("production");
+    common::clean_table("person");
+
+    common::run(async {
+        let fan_a = RecordId::new("person", "fan_a");
+        let fan_b = RecordId::new("person", "fan_b");
+        for (id, name) in [(&fan_a, "fan-a"), (&fan_b, "fan-b")] {
+            DB.query("CREATE $id CONTENT { username: $name, email: $email, password: 'x' }")
+                .bind(("id", id.clone()))
+                .bind(("name", name.to_string()))
+                .bind(("email", format!("{name}@example.com")))
+                .await
+                .expect("create person");
+        }
+
+        let production: Vec<R> = DB
+            .query(
+                "CREATE production CONTENT {
+                    title: 'Liked',
+                    slug: 'liked',
+                    type: 'Feature Film',
+                    status: 'in_development'
+                } RETURN id",
+            )
+            .await
+            .expect("create production")
+            .take(0)
+            .expect("take production");
+        let target = production.into_iter().next().expect("one production").id;
+
+        LikesModel::like(&fan_a, &target).await.expect("like");
+        LikesModel::like(&fan_a, &target).await.expect("like again");
+        LikesModel::like(&fan_b, &target).await.expect("second fan");
+        assert_eq!(LikesModel::count(&target).await.unwrap(), 2);
+        assert!(LikesModel::has_liked(&fan_a, &target).await.unwrap());
+
+        LikesModel::unlike(&fan_a, &target).await.expect("unlike");
+        LikesModel::unlike(&fan_a, &target)
+            .await
+            .expect("unlike again");
+        assert_eq!(LikesModel::count(&target).await.unwrap(), 1);
+        assert!(!LikesModel::has_liked(&fan_a, &target).await.unwrap());
+
+        let counts = LikesModel::count_many(std::slice::from_ref(&target))
+            .await
+            .unwrap();
+        assert_eq!(counts.values().copied().sum::<u64>(), 1);
+    });
+}
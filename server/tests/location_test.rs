@@ -0,0 +1,629 @@
+//! `LocationModel` ownership, delete, paging, sort, and rate behavior.
+//!
+//! For the rollback test, a temporary `DEFINE EVENT` makes the location
+//! DELETE throw after the rate cleanup has already run, reproducing the
+//! mid-delete failure that used to leave a `BEGIN TRANSACTION` open on the
+//! shared connection.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use slatehub::db::DB;
+use slatehub::error::Error;
+use slatehub::models::location::{CreateRateData, LocationModel, LocationSort};
+use slatehub::pagination::PageQuery;
+use slatehub::record_id_ext::RecordIdExt;
+use surrealdb::types::RecordId;
+use tower::ServiceExt;
+
+const BLOCK_DELETE: &str = "DEFINE EVENT OVERWRITE block_location_delete ON location \
+     WHEN $event = 'DELETE' THEN { THROW 'location delete blocked by test' }";
+const UNBLOCK_DELETE: &str = "REMOVE EVENT IF EXISTS block_location_delete ON location";
+
+async fn create_location(name: &str) -> RecordId {
+    create_location_by(name, RecordId::new("person", "delete_tester")).await
+}
+
+async fn create_location_by(name: &str, creator: RecordId) -> RecordId {
+    let id: Option<RecordId> = DB
+        .query(
+            "CREATE ONLY location CONTENT { name: $name, address: '1 Test St', \
+             city: 'Testville', state: 'TS', country: 'US', contact_name: 'Tester', \
+             contact_email: 'tester@example.com', created_by: $creator } \
+             RETURN VALUE id",
+        )
+        .bind(("name", name.to_string()))
+        .bind(("creator", creator))
+        .await
+        .expect("create location failed")
+        .take(0)
+        .expect("create location decode failed");
+    id.expect("location not created")
+}
+
+async fn exists(id: &RecordId) -> bool {
+    let found: Option<RecordId> = DB
+        .query("SELECT VALUE id FROM ONLY $id")
+        .bind(("id", id.clone()))
+        .await
+        .expect("lookup failed")
+        .take(0)
+        .unwrap_or(None);
+    found.is_some()
+}
+
+#[test]
+fn test_can_edit_accepts_prefixed_and_bare_person_ids() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::run(async {
+        let id = create_location_by("Owned", RecordId::new("person", "abc")).await;
+        assert!(LocationModel::can_edit(&id, "person:abc").await.unwrap());
+        assert!(LocationModel::can_edit(&id, "abc").await.unwrap());
+        assert!(!LocationModel::can_edit(&id, "person:xyz").await.unwrap());
+
+        // Same key, different table: an org-created location isn't the person's.
+        let org_owned = create_location_by("Org Owned", RecordId::new("organization", "abc")).await;
+        assert!(!LocationModel::can_edit(&org_owned, "abc").await.unwrap());
+    });
+}
+
+#[test]
+fn test_delete_removes_location_and_its_rates() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::clean_table("location_rate");
+    common::run(async {
+        let id = create_location("Delete Me").await;
+        LocationModel::add_rate(
+            &id,
+            CreateRateData {
+                rate_type: "daily".to_string(),
+                amount: 100.0,
+                currency: None,
+                minimum_duration: None,
+                description: None,
+            },
+        )
+        .await
+        .expect("add rate failed");
+
+        LocationModel::delete(&id).await.expect("delete failed");
+        assert!(!exists(&id).await);
+        let rates: Option<i64> = DB
+            .query("RETURN count(SELECT id FROM location_rate WHERE location = $id)")
+            .bind(("id", id.clone()))
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(rates, Some(0), "rates left behind");
+    });
+}
+
+#[test]
+fn test_failed_delete_rolls_back_and_leaves_connection_usable() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::run(async {
+        let id = create_location("Blocked").await;
+        DB.query(BLOCK_DELETE).await.expect("define event failed");
+
+        let result = LocationModel::delete(&id).await;
+
+        DB.query(UNBLOCK_DELETE).await.expect("remove event failed");
+        assert!(result.is_err(), "blocked delete should fail");
+        assert!(exists(&id).await, "location removed despite failure");
+
+        // Were a transaction still open, this write would sit uncommitted
+        // (and vanish); instead it persists and the follow-up delete works.
+        let other = create_location("After Failure").await;
+        assert!(exists(&other).await);
+        LocationModel::delete(&id)
+            .await
+            .expect("retry delete failed");
+        assert!(!exists(&id).await);
+    });
+}
+
+#[test]
+fn test_list_public_page_counts_every_public_location() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::run(async {
+        for name in ["Paged A", "Paged B", "Paged C", "Paged Private"] {
+            create_location(name).await;
+        }
+        DB.query("UPDATE location SET is_public = (name != 'Paged Private')")
+            .await
+            .expect("set visibility failed");
+
+        let first = LocationModel::list_public_page(PageQuery::new(Some(1), Some(2)))
+            .await
+            .unwrap();
+        assert_eq!(first.total, 3);
+        assert_eq!(first.items.len(), 2);
+        assert!(first.has_more());
+
+        let last = LocationModel::list_public_page(PageQuery::new(Some(2), Some(2)))
+            .await
+            .unwrap();
+        assert_eq!(last.items.len(), 1);
+        assert!(!last.has_more());
+        assert!(last.items.iter().all(|l| l.is_public));
+    });
+}
+
+#[test]
+fn test_list_honors_each_sort() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::run(async {
+        for (name, city) in [
+            ("Bravo", "Austin"),
+            ("Charlie", "Boston"),
+            ("Alpha", "Chicago"),
+        ] {
+            let id = create_location(name).await;
+            DB.query("UPDATE $id SET city = $city, is_public = true")
+                .bind(("id", id))
+                .bind(("city", city.to_string()))
+                .await
+                .expect("set city failed");
+        }
+
+        let names = |sort| async move {
+            LocationModel::list(None, true, None, None, None, None, sort, 0)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|l| l.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(LocationSort::Recent).await,
+            ["Alpha", "Charlie", "Bravo"]
+        );
+        assert_eq!(
+            names(LocationSort::Name).await,
+            ["Alpha", "Bravo", "Charlie"]
+        );
+        assert_eq!(
+            names(LocationSort::City).await,
+            ["Bravo", "Charlie", "Alpha"]
+        );
+    });
+}
+
+#[test]
+fn test_list_route_rejects_unknown_sort() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::run(async {
+        let request = Request::builder()
+            .uri("/locations?sort=name%3B%20REMOVE%20TABLE%20location")
+            .body(Body::empty())
+            .unwrap();
+        let response = slatehub::routes::app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    });
+}
+
+#[test]
+fn test_add_rate_defaults_and_normalizes_currency() {
+    common::setup_test_db();
+    common::clean_table("location_rate");
+    common::run(async {
+        let id = create_location("Rated").await;
+        let rate = |currency: Option<&str>| CreateRateData {
+            rate_type: "daily".to_string(),
+            amount: 250.0,
+            currency: currency.map(str::to_string),
+            minimum_duration: None,
+            description: None,
+        };
+
+        let default = LocationModel::add_rate(&id, rate(None)).await.unwrap();
+        assert_eq!(default.currency, slatehub::config::default_currency());
+
+        let euro = LocationModel::add_rate(&id, rate(Some(" eur")))
+            .await
+            .unwrap();
+        assert_eq!(euro.currency, "EUR");
+
+        assert!(matches!(
+            LocationModel::add_rate(&id, rate(Some("euro"))).await,
+            Err(Error::ValidationFields(_))
+        ));
+    });
+}
+
+#[test]
+fn test_private_location_hidden_from_anonymous_viewers() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::clean_table("person");
+    common::run(async {
+        let owner = common::create_test_user("location_owner").await;
+        let outsider = common::create_test_user("location_outsider").await;
+        let id = create_location_by("Private Loft", owner.id.clone()).await;
+        DB.query("UPDATE $id SET is_public = false")
+            .bind(("id", id.clone()))
+            .await
+            .expect("make private");
+        let uri = format!("/locations/{}", id.key_string());
+
+        let anonymous =
+            common::send(Request::builder().uri(&uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(anonymous.status(), StatusCode::FORBIDDEN);
+
+        let rates = common::send(
+            Request::builder()
+                .uri(format!("{uri}/rates"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(rates.status(), StatusCode::FORBIDDEN);
+
+        let outsider_view = common::send(common::authed_request(&outsider, "GET", &uri, "")).await;
+        assert_eq!(outsider_view.status(), StatusCode::FORBIDDEN);
+
+        let owner_view = common::send(common::authed_request(&owner, "GET", &uri, "")).await;
+        assert_eq!(owner_view.status(), StatusCode::OK);
+
+        DB.query("UPDATE $id SET is_public = true")
+            .bind(("id", id.clone()))
+            .await
+            .expect("make public");
+        let public = common::send(Request::builder().uri(&uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(public.status(), StatusCode::OK);
+    });
+}
+
+#[test]
+fn test_org_owned_location_is_editable_by_org_admins() {
+    use slatehub::models::location::{CreateLocationData, LocationOwner};
+    use slatehub::models::organization::{CreateOrganizationData, OrganizationModel};
+
+    common::setup_test_db();
+    common::clean_table("location");
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+    common::run(async {
+        let owner = common::create_test_user("loc_org_owner").await;
+        let admin = common::create_test_user("loc_org_admin").await;
+        let member = common::create_test_user("loc_org_member").await;
+
+        let org_type: Option<RecordId> = DB
+            .query("SELECT VALUE id FROM organization_type LIMIT 1")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        let org = OrganizationModel::new()
+            .create(
+                CreateOrganizationData {
+                    name: "Location Studio".to_string(),
+                    slug: "location-studio".to_string(),
+                    org_type: org_type.expect("seeded org type").to_raw_string(),
+                    description: None,
+                    location: None,
+                    website: None,
+                    contact_email: None,
+                    phone: None,
+                    services: vec![],
+                    founded_year: None,
+                    employees_count: None,
+                    public: true,
+                },
+                &owner.id.to_raw_string(),
+            )
+            .await
+            .expect("create org");
+        for (person, role) in [(&admin, "admin"), (&member, "member")] {
+            DB.query(
+                "RELATE $person->member_of->$org SET role = $role, permissions = [],
+                 invitation_status = 'accepted', joined_at = time::now()",
+            )
+            .bind(("person", person.id.clone()))
+            .bind(("org", org.id.clone()))
+            .bind(("role", role))
+            .await
+            .expect("add member");
+        }
+
+        let location = LocationModel::create(
+            CreateLocationData {
+                name: "Studio Backlot".to_string(),
+                address: "1 Lot Rd".to_string(),
+                city: "Testville".to_string(),
+                state: "TS".to_string(),
+                country: "US".to_string(),
+                postal_code: None,
+                description: None,
+                contact_name: "Lot".to_string(),
+                contact_email: "lot@example.com".to_string(),
+                contact_phone: None,
+                is_public: false,
+                amenities: None,
+                restrictions: None,
+                parking_info: None,
+                max_capacity: None,
+                owner_organization: Some(org.id.to_raw_string()),
+                street: None,
+                unit: None,
+                region_code: None,
+                country_code: None,
+            },
+            &member.id.to_raw_string(),
+        )
+        .await
+        .expect("create location");
+        assert_eq!(
+            location.owner(),
+            LocationOwner::Organization(org.id.clone())
+        );
+
+        let can_edit = |person: &common::TestUser| {
+            let person = person.id.to_raw_string();
+            let id = location.id.clone();
+            async move { LocationModel::can_edit(&id, &person).await.unwrap() }
+        };
+        assert!(can_edit(&owner).await);
+        assert!(can_edit(&admin).await);
+        // Plain members (even the one who listed it) can see but not edit.
+        assert!(!can_edit(&member).await);
+        assert!(
+            LocationModel::can_view(&location, Some(&member.id.to_raw_string()))
+                .await
+                .unwrap()
+        );
+        assert!(!LocationModel::can_view(&location, None).await.unwrap());
+
+        // The "all locations" list applies the same rule: org members who
+        // didn't list it see it, outsiders don't.
+        let outsider = common::create_test_user("loc_org_outsider").await;
+        for (person, shown) in [(&admin, true), (&outsider, false)] {
+            let response = common::send(common::authed_request(
+                person,
+                "GET",
+                "/locations?public_only=false",
+                "",
+            ))
+            .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(
+                String::from_utf8_lossy(&bytes).contains("Studio Backlot"),
+                shown,
+                "{}",
+                person.username
+            );
+        }
+
+        // Rows from before the ownership fields fall back to `created_by`.
+        let legacy = create_location_by("Legacy", owner.id.clone()).await;
+        let legacy = LocationModel::get(&legacy).await.unwrap();
+        assert_eq!(legacy.owner(), LocationOwner::Person(owner.id.clone()));
+
+        // The shoot-day picker offers what a person owns, directly or
+        // through an organization.
+        let names = |person: &common::TestUser| {
+            let person = person.id.to_raw_string();
+            async move {
+                LocationModel::get_for_person(&person)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|l| l.name)
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(names(&admin).await, ["Studio Backlot"]);
+        let mut owned = names(&owner).await;
+        owned.sort();
+        assert_eq!(owned, ["Legacy", "Studio Backlot"]);
+        assert!(names(&outsider).await.is_empty());
+    });
+}
+
+#[test]
+fn test_normalize_location_text_folds_case_punctuation_and_street_types() {
+    use slatehub::models::location::normalize_location_text;
+
+    assert_eq!(normalize_location_text("1 Test Street."), "1 test st");
+    assert_eq!(
+        normalize_location_text("  12 North Main Avenue, Suite 4 "),
+        normalize_location_text("12 n. main ave ste 4")
+    );
+    assert_ne!(
+        normalize_location_text("1 Test St"),
+        normalize_location_text("2 Test St")
+    );
+}
+
+#[test]
+fn test_create_near_identical_location_surfaces_existing_match() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::clean_table("person");
+    common::run(async {
+        let owner = common::create_test_user("loc_dup_owner").await;
+        let existing = create_location_by("Main Stage", owner.id.clone()).await;
+        let form = "name=main+stage+studios&address=1+Test+Street.&city=Testville&state=TS\
+                    &country=US&contact_name=Tester&contact_email=tester%40example.com";
+
+        let response = common::send(common::authed_request(
+            &owner,
+            "POST",
+            "/locations/new",
+            form,
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8_lossy(&body);
+        assert!(html.contains(&format!(r#"data-duplicate-of="{}""#, existing.key_string())));
+        let count: Option<i64> = DB
+            .query("RETURN count(SELECT id FROM location)")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(count, Some(1), "duplicate must not be created");
+
+        // Another owner's location at the same address is not a match.
+        let other = common::create_test_user("loc_dup_other").await;
+        let response = common::send(common::authed_request(
+            &other,
+            "POST",
+            "/locations/new",
+            form,
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        // Confirming creates it anyway.
+        let confirmed = format!("{form}&create_anyway=true");
+        let response = common::send(common::authed_request(
+            &owner,
+            "POST",
+            "/locations/new",
+            &confirmed,
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    });
+}
+
+#[test]
+fn test_address_components_parse_street_unit_and_country() {
+    use slatehub::models::location::AddressComponents;
+
+    let parsed = AddressComponents::parse("1200 Studio Way Suite 4, Burbank", "USA");
+    assert_eq!(parsed.street.as_deref(), Some("1200 Studio Way"));
+    assert_eq!(parsed.unit.as_deref(), Some("Suite 4"));
+    assert_eq!(parsed.country_code.as_deref(), Some("US"));
+    assert_eq!(parsed.region_code, None);
+
+    let parsed = AddressComponents::parse("12 Main St, #4B", "Canada");
+    assert_eq!(parsed.street.as_deref(), Some("12 Main St"));
+    assert_eq!(parsed.unit.as_deref(), Some("#4B"));
+    assert_eq!(parsed.country_code.as_deref(), Some("CA"));
+
+    // A marker with nothing after it is part of the street name.
+    let parsed = AddressComponents::parse("9 Unit", "Narnia");
+    assert_eq!(parsed.street.as_deref(), Some("9 Unit"));
+    assert_eq!(parsed.unit, None);
+    assert_eq!(parsed.country_code, None);
+}
+
+#[test]
+fn test_address_components_resolve_validates_explicit_codes() {
+    use slatehub::models::location::AddressComponents;
+
+    let resolved = AddressComponents::resolve(
+        "1 Lot Rd",
+        "United States",
+        AddressComponents {
+            street: Some("  ".to_string()),
+            unit: Some("Stage 2".to_string()),
+            region_code: Some("us-ca".to_string()),
+            country_code: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(resolved.street.as_deref(), Some("1 Lot Rd"));
+    assert_eq!(resolved.unit.as_deref(), Some("Stage 2"));
+    assert_eq!(resolved.region_code.as_deref(), Some("CA"));
+    assert_eq!(resolved.country_code.as_deref(), Some("US"));
+
+    for (region_code, country_code) in [(None, Some("ZZ")), (Some("FR-IDF"), Some("US"))] {
+        let err = AddressComponents::resolve(
+            "1 Lot Rd",
+            "USA",
+            AddressComponents {
+                region_code: region_code.map(str::to_string),
+                country_code: country_code.map(str::to_string),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::ValidationFields(_)));
+    }
+}
+
+#[test]
+fn test_find_near_orders_public_geocoded_locations_by_distance() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::run(async {
+        // Downtown LA, Burbank (~15 km), San Diego (~180 km).
+        for (name, lat, lng, public) in [
+            ("Burbank Lot", 34.1808, -118.3090, true),
+            ("Downtown Loft", 34.0407, -118.2468, true),
+            ("Hidden Loft", 34.0410, -118.2470, false),
+            ("San Diego Pier", 32.7157, -117.1611, true),
+        ] {
+            let id = create_location_by(name, RecordId::new("person", "near_tester")).await;
+            DB.query(
+                "UPDATE $id SET is_public = $public, country_code = 'US',
+                 geo = { lat: $lat, lng: $lng, city: NONE, region: NONE, country: NONE, country_code: 'us' }",
+            )
+            .bind(("id", id))
+            .bind(("public", public))
+            .bind(("lat", lat))
+            .bind(("lng", lng))
+            .await
+            .expect("set geo");
+        }
+
+        let nearby = LocationModel::find_near(34.0522, -118.2437, 50.0, None, 10)
+            .await
+            .unwrap();
+        let names: Vec<&str> = nearby.iter().map(|(l, _)| l.name.as_str()).collect();
+        assert_eq!(names, ["Downtown Loft", "Burbank Lot"]);
+        assert!(nearby[0].1 < nearby[1].1);
+
+        let elsewhere = LocationModel::find_near(34.0522, -118.2437, 50.0, Some("CA"), 10)
+            .await
+            .unwrap();
+        assert!(elsewhere.is_empty());
+    });
+}
+
+#[test]
+fn test_create_location_without_state_is_accepted() {
+    common::setup_test_db();
+    common::clean_table("location");
+    common::clean_table("person");
+    common::run(async {
+        let owner = common::create_test_user("loc_no_state_owner").await;
+        let form = "name=Harbour+Loft&address=1+Quay+Road&city=Dublin&country=IE\
+                    &contact_name=Tester&contact_email=tester%40example.com";
+
+        let response = common::send(common::authed_request(
+            &owner,
+            "POST",
+            "/locations/new",
+            form,
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        let state: Option<String> = DB
+            .query("RETURN (SELECT VALUE state FROM location WHERE name = 'Harbour Loft')[0]")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(state.as_deref(), Some(""));
+    });
+}
@@ -59,6 +59,7 @@ async fn create_person(
         username: username.to_string(),
         email: email.to_string(),
         name: username.to_string(),
+        timezone: None,
     }
 }
 
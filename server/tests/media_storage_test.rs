@@ -0,0 +1,296 @@
+//! Per-owner storage tracking: quota configuration, usage sums over
+//! recorded uploads, and the quota check that rejects an upload which would
+//! go over, replaced single-slot files staying in the library until deleted
+//! there, plus the metadata each `media` row records and its removal
+//! when the location, production, or organization it is attached to is
+//! deleted. The DB tests need the test SurrealDB instance.
+
+mod common;
+
+use slatehub::db::DB;
+use slatehub::error::Error;
+use slatehub::models::location::LocationModel;
+use slatehub::models::media::{self, Media, NewMedia, kind};
+use slatehub::models::organization::OrganizationModel;
+use slatehub::models::production::ProductionModel;
+use slatehub::pagination::PageQuery;
+use slatehub::record_id_ext::RecordIdExt;
+use surrealdb::types::{RecordId, SurrealValue};
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+#[test]
+fn quota_comes_from_env_per_owner_type() {
+    let person = RecordId::new("person", "quota_env");
+    let org = RecordId::new("organization", "quota_env");
+    // SAFETY: this is the only test in this binary touching these variables.
+    unsafe {
+        std::env::remove_var("STORAGE_QUOTA_MB");
+        std::env::remove_var("ORG_STORAGE_QUOTA_MB");
+        assert_eq!(media::quota_bytes(&person), Some(500 * 1024 * 1024));
+        assert_eq!(media::quota_bytes(&org), Some(2000 * 1024 * 1024));
+
+        std::env::set_var("STORAGE_QUOTA_MB", "3");
+        std::env::set_var("ORG_STORAGE_QUOTA_MB", "0");
+        assert_eq!(media::quota_bytes(&person), Some(3 * 1024 * 1024));
+        assert_eq!(media::quota_bytes(&org), None);
+
+        std::env::remove_var("STORAGE_QUOTA_MB");
+        std::env::remove_var("ORG_STORAGE_QUOTA_MB");
+    }
+}
+
+#[test]
+fn formats_byte_counts() {
+    assert_eq!(media::format_bytes(0), "0 bytes");
+    assert_eq!(media::format_bytes(1023), "1023 bytes");
+    assert_eq!(media::format_bytes(1536), "1.5 KB");
+    assert_eq!(media::format_bytes(500 * 1024 * 1024), "500.0 MB");
+}
+
+fn upload(owner: &RecordId, target: &RecordId, media_type: &'static str, size: u64) -> NewMedia {
+    NewMedia {
+        owner: owner.clone(),
+        uploaded_by: owner.clone(),
+        target: target.clone(),
+        media_type,
+        name: "photo.jpg".to_string(),
+        mime_type: "image/jpeg".to_string(),
+        object_key: format!("profiles/test/{}.jpg", ulid::Ulid::new()),
+        thumbnail_key: None,
+        size,
+        dimensions: Some((400, 400)),
+    }
+}
+
+#[test]
+fn usage_tracks_uploads_replacements_and_deletes() {
+    common::setup_test_db();
+    common::clean_table("media");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("storage_owner").await.id;
+
+        // Gallery uploads accumulate; a replaced avatar is detached but kept.
+        Media::record(upload(&owner, &owner, kind::PROFILE_PHOTO, 1000), false)
+            .await
+            .unwrap();
+        let replaced = Media::record(upload(&owner, &owner, kind::AVATAR, 300), true)
+            .await
+            .unwrap();
+        let mut avatar = upload(&owner, &owner, kind::AVATAR, 200);
+        avatar.thumbnail_key = Some("profiles/test/thumb.jpg".to_string());
+        let avatar_id = Media::record(avatar, true).await.unwrap();
+
+        // The row carries everything needed to serve and list the file.
+        let row = Media::find_by_id(&avatar_id.key_string())
+            .await
+            .unwrap()
+            .expect("avatar media row");
+        assert_eq!(row.owner.as_ref(), Some(&owner));
+        assert!(row.uri.starts_with("/api/media/profiles/test/"));
+        assert_eq!(
+            row.thumbnail_uri.as_deref(),
+            Some("/api/media/profiles/test/thumb.jpg")
+        );
+        assert_eq!((row.width, row.height), (Some(400), Some(400)));
+        assert_eq!(row.size, 200);
+
+        let replaced = Media::find_by_id(&replaced.key_string())
+            .await
+            .unwrap()
+            .expect("replaced avatar kept");
+        assert_eq!(replaced.target, None);
+
+        let usage = Media::storage_usage(&owner).await.unwrap();
+        assert_eq!(usage.used_bytes, 1500);
+        assert_eq!(usage.files, 3);
+
+        let quota = usage.quota_bytes.expect("default quota");
+        let headroom = quota - usage.used_bytes;
+        assert!(Media::ensure_quota(&owner, headroom).await.is_ok());
+        let err = Media::ensure_quota(&owner, headroom + 1).await.unwrap_err();
+        assert!(matches!(err, Error::Validation(ref msg) if msg.contains("quota")));
+
+        // Only the detached copy can be deleted from the library.
+        assert!(matches!(
+            Media::delete_detached(&avatar_id).await,
+            Err(Error::Conflict(_))
+        ));
+        Media::delete_detached(&replaced.id).await.unwrap();
+        let usage = Media::storage_usage(&owner).await.unwrap();
+        assert_eq!(usage.used_bytes, 1200);
+
+        Media::forget(&owner, kind::AVATAR, None).await.unwrap();
+        let usage = Media::storage_usage(&owner).await.unwrap();
+        assert_eq!(usage.used_bytes, 1000);
+        assert_eq!(usage.files, 1);
+    });
+}
+
+#[test]
+fn media_management_requires_login() {
+    common::run(async {
+        for (method, uri) in [
+            ("GET", "/api/media/library"),
+            ("POST", "/api/media/library/abc123/delete"),
+            ("GET", "/api/media/usage"),
+            ("POST", "/api/media/thumbnails/regenerate"),
+            ("POST", "/api/media/thumbnails/abc123/regenerate"),
+        ] {
+            let response = common::send(
+                axum::http::Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await;
+            assert_eq!(
+                response.status(),
+                axum::http::StatusCode::UNAUTHORIZED,
+                "{method} {uri}"
+            );
+        }
+    });
+}
+
+#[test]
+fn library_pages_and_filters_own_uploads() {
+    common::setup_test_db();
+    common::clean_table("media");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("library_owner").await.id;
+        let other = common::create_test_user("library_other").await.id;
+        for _ in 0..3 {
+            Media::record(upload(&owner, &owner, kind::PROFILE_PHOTO, 10), false)
+                .await
+                .unwrap();
+        }
+        Media::record(upload(&owner, &owner, kind::AVATAR, 10), true)
+            .await
+            .unwrap();
+        Media::record(upload(&other, &other, kind::AVATAR, 10), true)
+            .await
+            .unwrap();
+
+        let page = Media::library(&owner, None, PageQuery::new(Some(1), Some(3)))
+            .await
+            .unwrap();
+        assert_eq!(page.total, 4);
+        assert_eq!(page.items.len(), 3);
+        assert!(page.has_more());
+        assert!(
+            page.items
+                .iter()
+                .all(|m| m.uploaded_by.as_ref() == Some(&owner))
+        );
+
+        let avatars = Media::library(&owner, Some(kind::AVATAR), PageQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(avatars.total, 1);
+        assert_eq!(avatars.items[0].media_type.as_deref(), Some(kind::AVATAR));
+    });
+}
+
+/// Create a row with `sql` (which must `RETURN id`) and record a photo on
+/// it uploaded by `owner`.
+async fn seed_target(sql: &str, owner: &RecordId, media_type: &'static str) -> RecordId {
+    let rows: Vec<R> = DB
+        .query(sql)
+        .bind(("owner", owner.clone()))
+        .await
+        .expect("create target")
+        .take(0)
+        .expect("take target");
+    let target = rows.into_iter().next().expect("one").id;
+    Media::record(upload(owner, &target, media_type, 100), false)
+        .await
+        .unwrap();
+    assert_eq!(Media::storage_usage(owner).await.unwrap().files, 1);
+    target
+}
+
+#[test]
+fn deleting_a_location_drops_its_media() {
+    common::setup_test_db();
+    common::clean_table("media");
+    common::clean_table("person");
+    common::clean_table("location");
+
+    common::run(async {
+        let owner = common::create_test_user("media_location_owner").await.id;
+        let location = seed_target(
+            "CREATE location CONTENT {
+                name: 'Stage', address: '1 Main St', city: 'Atlanta', state: 'GA',
+                country: 'US', contact_name: 'Owner',
+                contact_email: 'owner@example.com', created_by: $owner
+             } RETURN id",
+            &owner,
+            kind::LOCATION_PHOTO,
+        )
+        .await;
+
+        LocationModel::delete(&location).await.unwrap();
+        assert_eq!(Media::storage_usage(&owner).await.unwrap().files, 0);
+    });
+}
+
+#[test]
+fn deleting_a_production_drops_its_media() {
+    common::setup_test_db();
+    common::clean_table("media");
+    common::clean_table("person");
+    common::clean_table("production");
+
+    common::run(async {
+        let owner = common::create_test_user("media_production_owner").await.id;
+        let production = seed_target(
+            "CREATE production CONTENT {
+                title: 'Media Prod', slug: 'media-prod', type: 'Feature Film',
+                status: 'in_development', source: 'manual'
+             } RETURN id",
+            &owner,
+            kind::PRODUCTION_POSTER,
+        )
+        .await;
+
+        ProductionModel::delete(&production).await.unwrap();
+        assert_eq!(Media::storage_usage(&owner).await.unwrap().files, 0);
+    });
+}
+
+#[test]
+fn deleting_an_organization_drops_its_media() {
+    common::setup_test_db();
+    common::clean_table("media");
+    common::clean_table("person");
+    common::clean_table("organization");
+
+    common::run(async {
+        let owner = common::create_test_user("media_org_owner").await.id;
+        let org = seed_target(
+            "CREATE organization CONTENT {
+                name: 'Media Org', slug: 'media-org',
+                type: (SELECT VALUE id FROM organization_type LIMIT 1)[0],
+                services: [], social_links: []
+             } RETURN id",
+            &owner,
+            kind::ORG_LOGO,
+        )
+        .await;
+
+        OrganizationModel::new()
+            .delete(&org.to_raw_string())
+            .await
+            .unwrap();
+        assert_eq!(Media::storage_usage(&owner).await.unwrap().files, 0);
+    });
+}
@@ -0,0 +1,123 @@
+//! Upload handlers sniff the file bytes instead of trusting the multipart
+//! `Content-Type`: a text file labeled `image/png` is rejected with 400
+//! before anything is processed or sent to S3, and files over the 10MB cap
+//! are rejected while still streaming in.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::auth::create_jwt;
+use slatehub::db::DB;
+use slatehub::record_id_ext::RecordIdExt;
+use surrealdb::types::{RecordId, SurrealValue};
+use tower::ServiceExt;
+
+const BOUNDARY: &str = "slatehub-test-boundary";
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+async fn person_token(username: &str) -> String {
+    let rows: Vec<R> = DB
+        .query(
+            "CREATE person CONTENT {
+                username: $username, email: $email, password: 'h', name: $username,
+                verification_status: 'email',
+                profile: { name: $username, skills: [], social_links: [], ethnicity: [], unions: [], languages: [], experience: [], education: [], reels: [], media_other: [], awards: [] }
+            } RETURN id",
+        )
+        .bind(("username", username.to_string()))
+        .bind(("email", format!("{username}@example.com")))
+        .await
+        .expect("create person")
+        .take(0)
+        .expect("take person");
+    let id = rows.into_iter().next().expect("one").id;
+    create_jwt(
+        &id.to_raw_string(),
+        username,
+        &format!("{username}@example.com"),
+    )
+    .expect("jwt")
+}
+
+/// POST a single multipart file field and return the response status.
+async fn upload(
+    uri: &str,
+    token: &str,
+    field: &str,
+    content_type: &str,
+    data: &[u8],
+) -> StatusCode {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"upload.png\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {token}"))
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    slatehub::routes::app()
+        .oneshot(request)
+        .await
+        .unwrap()
+        .status()
+}
+
+#[test]
+fn text_labeled_as_png_is_rejected() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::run(async {
+        let token = person_token("sniffer").await;
+        let fake = b"just some text, definitely not a PNG";
+
+        for (uri, field) in [
+            ("/api/media/upload/profile-image", "image"),
+            ("/api/media/upload/profile-photo", "image"),
+            ("/api/media/upload/organization-logo/any-org", "image"),
+        ] {
+            assert_eq!(
+                upload(uri, &token, field, "image/png", fake).await,
+                StatusCode::BAD_REQUEST,
+                "{uri} accepted a spoofed image"
+            );
+        }
+    });
+}
+
+#[test]
+fn oversized_upload_is_rejected() {
+    common::setup_test_db();
+    common::clean_table("person");
+    common::run(async {
+        let token = person_token("oversized").await;
+        // Valid PNG signature so only the size can fail it.
+        let mut big = b"\x89PNG\r\n\x1a\n".to_vec();
+        big.resize(10 * 1024 * 1024 + 1, 0);
+
+        assert_eq!(
+            upload(
+                "/api/media/upload/profile-photo",
+                &token,
+                "image",
+                "image/png",
+                &big
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+    });
+}
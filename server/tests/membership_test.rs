@@ -1,4 +1,6 @@
-use slatehub::models::membership::{InvitationStatus, MembershipModel, MembershipRole, Permission};
+use slatehub::models::membership::{
+    InvitationStatus, MembershipModel, MembershipRole, Permission, PermissionSet,
+};
 
 #[test]
 fn test_role_conversion() {
@@ -47,3 +49,38 @@ fn test_default_permissions() {
     assert!(member_perms.contains(&Permission::CreateProjects));
     assert!(!member_perms.contains(&Permission::InviteMembers));
 }
+
+#[test]
+fn test_permission_strings_round_trip() {
+    for permission in Permission::ALL {
+        assert_eq!(Permission::parse(permission.as_str()), Some(permission));
+        // Must agree with the serde form stored by MembershipModel::update.
+        assert_eq!(
+            serde_json::to_string(&permission).unwrap(),
+            format!("\"{}\"", permission.as_str())
+        );
+    }
+    assert_eq!(Permission::parse("launch_rockets"), None);
+}
+
+#[test]
+fn test_permission_set_for_membership() {
+    let owner = PermissionSet::for_membership("owner", &[]);
+    assert!(Permission::ALL.iter().all(|p| owner.contains(*p)));
+
+    let admin = PermissionSet::for_membership("admin", &[]);
+    assert!(admin.contains(Permission::UpdateOrganization));
+    assert!(!admin.contains(Permission::DeleteOrganization));
+    assert!(!admin.contains(Permission::UpdateMemberRoles));
+
+    // Extra grants stored on the edge add to the role defaults; junk is ignored.
+    let member = PermissionSet::for_membership(
+        "member",
+        &["invite_members".to_string(), "bogus".to_string()],
+    );
+    assert!(member.contains(Permission::InviteMembers));
+    assert!(member.contains(Permission::CreateProjects));
+    assert!(!member.contains(Permission::UpdateOrganization));
+
+    assert!(PermissionSet::for_membership("stranger", &[]).is_empty());
+}
@@ -0,0 +1,143 @@
+//! Tests for `NotificationModel` listing and read state against the test DB,
+//! plus the invitation accept redirect.
+
+mod common;
+
+use axum::http::{StatusCode, header};
+use slatehub::db::DB;
+use slatehub::models::notification::NotificationModel;
+use slatehub::record_id_ext::RecordIdExt;
+use surrealdb::types::RecordId;
+
+#[test]
+fn list_filters_unread_and_mark_read_updates_count() {
+    common::setup_test_db();
+    common::clean_table("notification");
+    common::clean_table("person");
+
+    common::run(async {
+        DB.query(
+            "CREATE $id CONTENT { username: 'bell', email: 'bell@example.com', password: 'x' }",
+        )
+        .bind(("id", RecordId::new("person", "bell")))
+        .await
+        .expect("create person");
+
+        let model = NotificationModel::new();
+        for title in ["first", "second", "third"] {
+            model
+                .create("person:bell", "general", title, "body", None, None)
+                .await
+                .expect("create notification");
+        }
+        assert_eq!(model.get_unread_count("person:bell").await.unwrap(), 3);
+
+        let all = model.list("person:bell", false, 10, 0).await.unwrap();
+        assert_eq!(all.len(), 3);
+        let page = model.list("person:bell", false, 2, 2).await.unwrap();
+        assert_eq!(page.len(), 1);
+
+        let first_id = all[0].id.to_raw_string();
+        model.mark_read(&first_id, "person:bell").await.unwrap();
+        // Someone else's id is a no-op, not an error.
+        model
+            .mark_read(&all[1].id.to_raw_string(), "person:other")
+            .await
+            .unwrap();
+
+        assert_eq!(model.get_unread_count("person:bell").await.unwrap(), 2);
+        let unread = model.list("person:bell", true, 10, 0).await.unwrap();
+        assert_eq!(unread.len(), 2);
+        assert!(unread.iter().all(|n| !n.read));
+
+        model.mark_all_read("person:bell").await.unwrap();
+        assert_eq!(model.get_unread_count("person:bell").await.unwrap(), 0);
+    });
+}
+
+#[test]
+fn accept_invitation_rejects_off_site_redirect() {
+    use slatehub::models::organization::{CreateOrganizationData, OrganizationModel};
+
+    common::setup_test_db();
+    common::clean_table("notification");
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("invite_owner").await;
+        let invitee = common::create_test_user("invite_target").await;
+        let org_type: Option<RecordId> = DB
+            .query("SELECT VALUE id FROM organization_type LIMIT 1")
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        let org = OrganizationModel::new()
+            .create(
+                CreateOrganizationData {
+                    name: "Invite Studio".to_string(),
+                    slug: "invite-studio".to_string(),
+                    org_type: org_type.expect("seeded org type").to_raw_string(),
+                    description: None,
+                    location: None,
+                    website: None,
+                    contact_email: None,
+                    phone: None,
+                    services: vec![],
+                    founded_year: None,
+                    employees_count: None,
+                    public: true,
+                },
+                &owner.id.to_raw_string(),
+            )
+            .await
+            .expect("create org");
+        DB.query(
+            "RELATE $person->member_of->$org SET role = 'member', permissions = [],
+             invitation_status = 'pending', invited_at = time::now()",
+        )
+        .bind(("person", invitee.id.clone()))
+        .bind(("org", org.id.clone()))
+        .await
+        .expect("invite member");
+        let invitee_id = invitee.id.to_raw_string();
+        let model = NotificationModel::new();
+        model
+            .create(
+                &invitee_id,
+                "invitation",
+                "Join Invite Studio",
+                "body",
+                None,
+                None,
+            )
+            .await
+            .expect("create notification");
+        let notification = model
+            .list(&invitee_id, false, 1, 0)
+            .await
+            .unwrap()
+            .remove(0);
+
+        let form = format!(
+            "org_id={}&notification_id={}&redirect=https%3A%2F%2Fevil.example%2F",
+            org.id.to_raw_string(),
+            notification.id.to_raw_string()
+        );
+        let response = common::send(common::authed_request(
+            &invitee,
+            "POST",
+            "/invitations/accept",
+            &form,
+        ))
+        .await;
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION).unwrap(),
+            "/orgs/invite-studio"
+        );
+    });
+}
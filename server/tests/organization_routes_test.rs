@@ -0,0 +1,483 @@
+//! End-to-end tests for the organization pages, driven through the full
+//! router with a signed-in user's session cookie.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::db::DB;
+use slatehub::services::email::take_captured_emails;
+use surrealdb::types::SurrealValue;
+
+async fn first_org_type() -> String {
+    #[derive(serde::Deserialize, SurrealValue)]
+    struct OrgType {
+        id: String,
+    }
+
+    let rows: Vec<OrgType> = DB
+        .query("SELECT string::concat('organization_type:', meta::id(id)) AS id FROM organization_type LIMIT 1")
+        .await
+        .expect("query org types")
+        .take(0)
+        .expect("take org types");
+    rows.into_iter()
+        .next()
+        .expect("No organization_type rows seeded — run `make test-db-init`.")
+        .id
+}
+
+#[test]
+fn test_create_organization_redirects_to_profile() {
+    common::setup_test_db();
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+
+    common::run(async {
+        let user = common::create_test_user("org_founder").await;
+        let org_type = first_org_type().await;
+
+        let form = format!("name=Route+Test+Films&slug=route-test-films&org_type={org_type}");
+        let response =
+            common::send(common::authed_request(&user, "POST", "/orgs/new", &form)).await;
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION).unwrap(),
+            "/orgs/route-test-films"
+        );
+
+        let owners: Option<i64> = DB
+            .query(
+                "SELECT VALUE count() FROM member_of
+                 WHERE in = $person AND out.slug = 'route-test-films' AND role = 'owner'
+                 GROUP ALL",
+            )
+            .bind(("person", user.id.clone()))
+            .await
+            .expect("query membership")
+            .take(0)
+            .expect("take membership");
+        assert_eq!(owners, Some(1), "{} should own the new org", user.username);
+    });
+}
+
+#[test]
+fn test_create_organization_rerenders_form_on_validation_error() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        let user = common::create_test_user("org_sloppy").await;
+
+        let response = common::send(common::authed_request(
+            &user,
+            "POST",
+            "/orgs/new",
+            "name=&org_type=",
+        ))
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::LOCATION).is_none());
+    });
+}
+
+#[test]
+fn test_create_organization_rejects_out_of_range_founded_year() {
+    common::setup_test_db();
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+
+    common::run(async {
+        let user = common::create_test_user("org_time_traveller").await;
+        let org_type = first_org_type().await;
+
+        let form =
+            format!("name=Future+Films&slug=future-films&org_type={org_type}&founded_year=9999");
+        let response =
+            common::send(common::authed_request(&user, "POST", "/orgs/new", &form)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::LOCATION).is_none());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("Founded year must be a year between"));
+
+        let created: Option<i64> = DB
+            .query("SELECT VALUE count() FROM organization WHERE slug = 'future-films' GROUP ALL")
+            .await
+            .expect("query orgs")
+            .take(0)
+            .expect("take orgs");
+        assert_eq!(created, None);
+
+        let form = format!("name=Past+Films&slug=past-films&org_type={org_type}&founded_year=1999");
+        let response =
+            common::send(common::authed_request(&user, "POST", "/orgs/new", &form)).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    });
+}
+
+#[test]
+fn test_contact_form_emails_org_without_revealing_address() {
+    common::setup_test_db();
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("contact_owner").await;
+        let sender = common::create_test_user("contact_sender").await;
+        let org_type = first_org_type().await;
+        let form = format!(
+            "name=Contact+Films&slug=contact-films&org_type={org_type}\
+             &contact_email=press%40contact-films.test&public=on"
+        );
+        let response =
+            common::send(common::authed_request(&owner, "POST", "/orgs/new", &form)).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        let body = |response: axum::response::Response| async move {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+
+        // The profile links to the form instead of showing the address.
+        let profile = body(
+            common::send(
+                Request::builder()
+                    .uri("/orgs/contact-films")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await,
+        )
+        .await;
+        assert!(profile.contains("/orgs/contact-films/contact"));
+        assert!(!profile.contains("press@contact-films.test"));
+
+        // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+        unsafe { std::env::set_var("EMAIL_PROVIDER", "capture") };
+        take_captured_emails();
+
+        // Anonymous senders without the anti-bot tokens are turned away.
+        let anonymous = common::send(
+            Request::builder()
+                .method("POST")
+                .uri("/orgs/contact-films/contact")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(
+                    "name=Bot&email=bot%40example.com&message=Buy+now",
+                ))
+                .unwrap(),
+        )
+        .await;
+        assert_ne!(anonymous.status(), StatusCode::SEE_OTHER);
+        assert!(take_captured_emails().is_empty());
+
+        let response = common::send(common::authed_request(
+            &sender,
+            "POST",
+            "/orgs/contact-films/contact",
+            "message=Are+you+hiring+grips%3F",
+        ))
+        .await;
+        unsafe { std::env::remove_var("EMAIL_PROVIDER") };
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(header::LOCATION).unwrap(),
+            "/orgs/contact-films/contact?sent=true"
+        );
+
+        let sent = take_captured_emails();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].to.contains("press@contact-films.test"));
+        assert_eq!(
+            sent[0].reply_to.as_deref(),
+            Some("contact_sender@example.com")
+        );
+        assert!(
+            sent[0]
+                .text_body
+                .as_deref()
+                .unwrap()
+                .contains("Are you hiring grips?")
+        );
+
+        let confirmation = body(
+            common::send(common::authed_request(
+                &sender,
+                "GET",
+                "/orgs/contact-films/contact?sent=true",
+                "",
+            ))
+            .await,
+        )
+        .await;
+        assert!(!confirmation.contains("press@contact-films.test"));
+    });
+}
+
+#[test]
+fn test_organization_json_respects_visibility() {
+    common::setup_test_db();
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("json_owner").await;
+        let outsider = common::create_test_user("json_outsider").await;
+        let org_type = first_org_type().await;
+        let form = format!(
+            "name=Quiet+Films&slug=quiet-films&org_type={org_type}\
+             &contact_email=hello%40quiet-films.test"
+        );
+        let response =
+            common::send(common::authed_request(&owner, "POST", "/orgs/new", &form)).await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        let json = |response: axum::response::Response| async move {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).expect("JSON body")
+        };
+
+        // Private org: anonymous viewers and non-members get a JSON 403.
+        let anonymous = common::send(
+            Request::builder()
+                .uri("/api/organizations/quiet-films")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(anonymous.status(), StatusCode::FORBIDDEN);
+        assert!(json(anonymous).await.get("error").is_some());
+
+        let outsider_view = common::send(common::authed_request(
+            &outsider,
+            "GET",
+            "/api/organizations/quiet-films",
+            "",
+        ))
+        .await;
+        assert_eq!(outsider_view.status(), StatusCode::FORBIDDEN);
+
+        // Members see the org, its contact address and the member list.
+        let member_view = common::send(common::authed_request(
+            &owner,
+            "GET",
+            "/api/organizations/quiet-films",
+            "",
+        ))
+        .await;
+        assert_eq!(member_view.status(), StatusCode::OK);
+        let org = json(member_view).await;
+        assert_eq!(org["slug"], "quiet-films");
+        assert_eq!(org["public"], false);
+        assert_eq!(org["contact_email"], "hello@quiet-films.test");
+        assert_eq!(org["members"][0]["username"], owner.username.as_str());
+        assert_eq!(org["members"][0]["role"], "owner");
+
+        let missing = common::send(
+            Request::builder()
+                .uri("/api/organizations/no-such-org")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    });
+}
+
+#[test]
+fn test_member_role_change_patches_the_row_for_datastar() {
+    common::setup_test_db();
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+
+    common::run(async {
+        let owner = common::create_test_user("org_patch_owner").await;
+        let crew = common::create_test_user("org_patch_crew").await;
+        let org_type = first_org_type().await;
+
+        let form = format!("name=Patch+Films&slug=patch-films&org_type={org_type}");
+        common::send(common::authed_request(&owner, "POST", "/orgs/new", &form)).await;
+
+        let member_id: Option<String> = DB
+            .query(
+                "LET $org = (SELECT VALUE id FROM ONLY organization WHERE slug = 'patch-films' LIMIT 1);
+                 RELATE $person->member_of->$org SET role = 'member', invitation_status = 'accepted';
+                 SELECT VALUE <string> id FROM ONLY member_of WHERE in = $person LIMIT 1",
+            )
+            .bind(("person", crew.id.clone()))
+            .await
+            .expect("add member")
+            .take(2)
+            .expect("take member id");
+        let member_id = member_id.expect("member_of row");
+        let row_id = format!("#org-member-{}", member_id.trim_start_matches("member_of:"));
+        let uri = format!("/orgs/patch-films/members/{member_id}/role");
+
+        // Without JS: the classic redirect back to the profile
+        let plain = common::send(common::authed_request(&owner, "POST", &uri, "role=admin")).await;
+        assert_eq!(plain.status(), StatusCode::SEE_OTHER);
+
+        // Through Datastar: only the member's row, re-rendered
+        let mut request = common::authed_request(&owner, "POST", &uri, "role=member");
+        request
+            .headers_mut()
+            .insert("datastar-request", "true".parse().unwrap());
+        let response = common::send(request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(
+            body.contains(&format!("data: selector {row_id}\n")),
+            "{body}"
+        );
+        assert!(body.contains("data: mode outer\n"), "{body}");
+        assert!(body.contains(">Promote</button>"), "{body}");
+
+        let mut request = common::authed_request(
+            &owner,
+            "POST",
+            &format!("/orgs/patch-films/members/{member_id}/remove"),
+            "",
+        );
+        request
+            .headers_mut()
+            .insert("datastar-request", "true".parse().unwrap());
+        let response = common::send(request).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("data: mode remove\n"), "{body}");
+    });
+}
+
+#[test]
+fn test_member_management_follows_permissions_not_roles() {
+    common::setup_test_db();
+    common::clean_table("member_of");
+    common::clean_table("organization");
+    common::clean_table("person");
+    common::clean_table("notification");
+
+    common::run(async {
+        let owner = common::create_test_user("org_perm_owner").await;
+        let admin = common::create_test_user("org_perm_admin").await;
+        let inviter = common::create_test_user("org_perm_inviter").await;
+        let crew = common::create_test_user("org_perm_crew").await;
+        common::create_test_user("org_perm_guest").await;
+        let org_type = first_org_type().await;
+
+        let form = format!("name=Perm+Films&slug=perm-films&org_type={org_type}");
+        common::send(common::authed_request(&owner, "POST", "/orgs/new", &form)).await;
+
+        let join = |person: &common::TestUser, role: &'static str, grants: Vec<&'static str>| {
+            let person = person.id.clone();
+            async move {
+                let id: Option<String> = DB
+                    .query(
+                        "LET $org = (SELECT VALUE id FROM ONLY organization WHERE slug = 'perm-films' LIMIT 1);
+                         RELATE $person->member_of->$org
+                             SET role = $role, permissions = $grants, invitation_status = 'accepted';
+                         SELECT VALUE <string> id FROM ONLY member_of WHERE in = $person LIMIT 1",
+                    )
+                    .bind(("person", person))
+                    .bind(("role", role))
+                    .bind(("grants", grants))
+                    .await
+                    .expect("add member")
+                    .take(2)
+                    .expect("take member id");
+                id.expect("member_of row")
+            }
+        };
+        join(&admin, "admin", vec![]).await;
+        join(&inviter, "member", vec!["invite_members"]).await;
+        let crew_member = join(&crew, "member", vec![]).await;
+        let owner_member: Option<String> = DB
+            .query("SELECT VALUE <string> id FROM ONLY member_of WHERE in = $person LIMIT 1")
+            .bind(("person", owner.id.clone()))
+            .await
+            .expect("owner membership")
+            .take(0)
+            .expect("take owner membership");
+        let owner_member = owner_member.expect("owner member_of row");
+
+        // A stored invite grant lets a plain member invite
+        let invite = "username=org_perm_guest&role=member";
+        let response = common::send(common::authed_request(
+            &inviter,
+            "POST",
+            "/orgs/perm-films/members/invite",
+            invite,
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let response = common::send(common::authed_request(
+            &crew,
+            "POST",
+            "/orgs/perm-films/members/invite",
+            invite,
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // Admins may remove members, but neither change roles nor remove
+        // the owner
+        let role_uri = format!("/orgs/perm-films/members/{crew_member}/role");
+        let response = common::send(common::authed_request(
+            &admin,
+            "POST",
+            &role_uri,
+            "role=admin",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let remove = |member: &str| format!("/orgs/perm-films/members/{member}/remove");
+        let response = common::send(common::authed_request(
+            &admin,
+            "POST",
+            &remove(&owner_member),
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = common::send(common::authed_request(
+            &admin,
+            "POST",
+            &remove(&crew_member),
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+        // Only the owner may delete the organization
+        let response = common::send(common::authed_request(
+            &admin,
+            "POST",
+            "/orgs/perm-films/delete",
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    });
+}
@@ -1,6 +1,7 @@
 mod common;
 
 use slatehub::db::DB;
+use slatehub::models::membership::{MembershipModel, Permission};
 use slatehub::models::organization::{CreateOrganizationData, OrganizationModel};
 use slatehub::record_id_ext::RecordIdExt;
 use surrealdb::types::SurrealValue;
@@ -278,3 +279,362 @@ fn test_invite_member_to_org() {
             .expect("Failed to invite member");
     });
 }
+
+/// create → get_members → permission checks all read the same `member_of`
+/// edges, whichever id form (prefixed or bare) the caller passes.
+#[test]
+fn test_membership_consistent_across_model_paths() {
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let org_type = seed_org_type().await;
+        let owner_id = seed_test_person().await;
+        let invitee_id = seed_test_person_with("member", "member@example.com").await;
+
+        let model = OrganizationModel::new();
+        let memberships = MembershipModel::new();
+        let org = model
+            .create(make_org_data("membership-e2e", &org_type), &owner_id)
+            .await
+            .expect("Failed to create org");
+        let org_id = org.id.to_raw_string();
+
+        let members = model.get_members(&org_id).await.expect("get_members");
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].person_id.to_raw_string(), owner_id);
+        assert_eq!(members[0].role, "owner");
+
+        let owner_key = owner_id.strip_prefix("person:").unwrap();
+        for id in [owner_id.as_str(), owner_key] {
+            assert_eq!(
+                model.get_member_role(&org_id, id).await.unwrap().as_deref(),
+                Some("owner")
+            );
+            assert!(
+                memberships
+                    .has_permission(id, &org_id, Permission::UpdateOrganization)
+                    .await
+                    .unwrap()
+            );
+        }
+
+        // A pending invite is listed but grants nothing until accepted.
+        model
+            .add_member(&org_id, &invitee_id, "member", Some(&owner_id))
+            .await
+            .expect("Failed to invite member");
+        assert_eq!(model.get_members(&org_id).await.unwrap().len(), 2);
+        assert_eq!(
+            model.get_member_role(&org_id, &invitee_id).await.unwrap(),
+            None
+        );
+        assert!(
+            memberships
+                .permissions_for(&invitee_id, &org_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let invite = memberships
+            .find_by_person_and_org(&invitee_id, &org_id)
+            .await
+            .unwrap()
+            .expect("invite edge");
+        memberships
+            .accept_invitation(&invite.id.to_raw_string())
+            .await
+            .expect("accept");
+
+        assert_eq!(
+            model
+                .get_member_role(&org_id, &invitee_id)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("member")
+        );
+        let permissions = memberships
+            .permissions_for(&invitee_id, &org_id)
+            .await
+            .unwrap();
+        assert!(permissions.contains(Permission::CreateProjects));
+        assert!(!permissions.contains(Permission::UpdateOrganization));
+    });
+}
+
+/// Logo writes bind the slug, so a quote in it can't break (or extend) the
+/// query. Slugs typed before normalization may still contain one.
+#[test]
+fn test_set_logo_with_apostrophe_in_slug() {
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let org_type = seed_org_type().await;
+        let person_id = seed_test_person().await;
+        let model = OrganizationModel::new();
+        let slug = "o'brien-films";
+        model
+            .create(make_org_data(slug, &org_type), &person_id)
+            .await
+            .expect("Failed to create org");
+
+        let logo = "/api/media/organizations/o'brien-films/logo_1.jpg".to_string();
+        model
+            .set_logo(slug, Some(logo.clone()))
+            .await
+            .expect("set_logo failed");
+        assert_eq!(model.get_by_slug(slug).await.unwrap().logo, Some(logo));
+
+        model.set_logo(slug, None).await.expect("clear logo failed");
+        assert_eq!(model.get_by_slug(slug).await.unwrap().logo, None);
+    });
+}
+
+#[test]
+fn test_organization_type_management() {
+    use slatehub::error::Error;
+
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let model = OrganizationModel::new();
+        // Leftovers from an aborted run would trip the duplicate check.
+        DB.query("DELETE organization_type WHERE name CONTAINS 'Test Taxonomy'")
+            .await
+            .unwrap();
+
+        let created = model
+            .create_organization_type("  Test   Taxonomy ")
+            .await
+            .expect("create type");
+        assert_eq!(created.name, "Test Taxonomy");
+
+        // Case-insensitive duplicates and blank names are rejected.
+        assert!(matches!(
+            model.create_organization_type("test taxonomy").await,
+            Err(Error::Conflict(_))
+        ));
+        assert!(matches!(
+            model.create_organization_type("   ").await,
+            Err(Error::Validation(_))
+        ));
+
+        // Prime the cached list; the admin edits below must refresh it.
+        model.get_organization_types().await.unwrap();
+        model
+            .rename_organization_type(&created.id, "Test Taxonomy Renamed")
+            .await
+            .expect("rename type");
+        let created_id = created.id.to_raw_string();
+        let types = model.get_organization_types().await.unwrap();
+        assert!(types.contains(&(created_id.clone(), "Test Taxonomy Renamed".to_string())));
+
+        // In use: delete is blocked until the org goes away.
+        let person_id = seed_test_person().await;
+        let org = model
+            .create(
+                make_org_data("taxonomy-org", &created.id.to_raw_string()),
+                &person_id,
+            )
+            .await
+            .expect("create org");
+        assert_eq!(org.org_type.name, "Test Taxonomy Renamed");
+
+        let usage = model.get_organization_types_with_usage().await.unwrap();
+        let row = usage.iter().find(|t| t.id == created.id).expect("listed");
+        assert_eq!(row.usage, 1);
+
+        assert!(matches!(
+            model.delete_organization_type(&created.id).await,
+            Err(Error::Conflict(_))
+        ));
+
+        DB.query("DELETE member_of; DELETE $org")
+            .bind(("org", org.id.clone()))
+            .await
+            .unwrap();
+        model
+            .delete_organization_type(&created.id)
+            .await
+            .expect("delete unused type");
+        let types = model.get_organization_types().await.unwrap();
+        assert!(!types.iter().any(|(id, _)| *id == created_id));
+        assert!(matches!(
+            model.delete_organization_type(&created.id).await,
+            Err(Error::NotFound)
+        ));
+    });
+}
+
+#[test]
+fn test_private_org_hidden_from_search_except_for_members() {
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let org_type = seed_org_type().await;
+        let member = seed_test_person().await;
+        let outsider = seed_test_person_with("outsider", "outsider@example.com").await;
+
+        let model = OrganizationModel::new();
+        let mut data = make_org_data("hidden-org", &org_type);
+        data.public = false;
+        model
+            .create(data, &member)
+            .await
+            .expect("create private org");
+        model
+            .create(make_org_data("open-org", &org_type), &member)
+            .await
+            .expect("create public org");
+
+        let slugs = |orgs: Vec<slatehub::models::organization::Organization>| {
+            orgs.into_iter().map(|o| o.slug).collect::<Vec<_>>()
+        };
+        let search = |viewer: Option<&str>| {
+            let viewer = viewer.map(str::to_string);
+            async move {
+                OrganizationModel::new()
+                    .search(
+                        Some("Test Org"),
+                        None,
+                        None,
+                        None,
+                        None,
+                        viewer.as_deref(),
+                        50,
+                        0,
+                    )
+                    .await
+                    .expect("search")
+            }
+        };
+
+        let anonymous = slugs(search(None).await);
+        assert_eq!(anonymous, vec!["open-org".to_string()]);
+
+        let as_member = slugs(search(Some(&member)).await);
+        assert!(as_member.contains(&"hidden-org".to_string()));
+        assert!(as_member.contains(&"open-org".to_string()));
+
+        let as_outsider = slugs(search(Some(&outsider)).await);
+        assert_eq!(as_outsider, vec!["open-org".to_string()]);
+    });
+}
+
+#[test]
+fn test_org_picker_lists_private_orgs_to_members_only() {
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let org_type = seed_org_type().await;
+        let member = common::create_test_user("picker_member").await;
+        let outsider = common::create_test_user("picker_outsider").await;
+
+        let mut data = make_org_data("picker-hidden", &org_type);
+        data.public = false;
+        OrganizationModel::new()
+            .create(data, &member.id.to_raw_string())
+            .await
+            .expect("create private org");
+
+        let picker = |user: common::TestUser| async move {
+            let response = common::send(common::authed_request(
+                &user,
+                "GET",
+                "/api/orgs/search-sse?q=picker",
+                "",
+            ))
+            .await;
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+
+        assert!(picker(member).await.contains("picker-hidden"));
+        assert!(!picker(outsider).await.contains("picker-hidden"));
+    });
+}
+
+#[test]
+fn test_services_are_normalized_and_filterable() {
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let org_type = seed_org_type().await;
+        let person_id = seed_test_person().await;
+        let model = OrganizationModel::new();
+
+        let mut data = make_org_data("post-house", &org_type);
+        data.services = vec!["Post Production".to_string(), "VFX".to_string()];
+        let org = model.create(data, &person_id).await.expect("create");
+        assert_eq!(org.services, vec!["post-production", "visual effects"]);
+
+        let mut data = make_org_data("finishing", &org_type);
+        data.services = vec!["post-production".to_string()];
+        model.create(data, &person_id).await.expect("create");
+        model
+            .create(make_org_data("no-services", &org_type), &person_id)
+            .await
+            .expect("create");
+
+        let found = model
+            .search(None, None, None, Some("postproduction"), None, None, 50, 0)
+            .await
+            .expect("search");
+        let mut slugs: Vec<_> = found.into_iter().map(|o| o.slug).collect();
+        slugs.sort();
+        assert_eq!(slugs, vec!["finishing", "post-house"]);
+
+        let facets = model.service_facets().await.expect("facets");
+        assert_eq!(facets[0].name, "post-production");
+        assert_eq!(facets[0].count, 2);
+        assert!(
+            facets
+                .iter()
+                .any(|f| f.name == "visual effects" && f.count == 1)
+        );
+    });
+}
+
+#[test]
+fn test_backfill_normalizes_service_tags_saved_before_normalization() {
+    common::setup_test_db();
+    clean_all();
+
+    common::run(async {
+        let org_type = seed_org_type().await;
+        let person_id = seed_test_person().await;
+        let model = OrganizationModel::new();
+
+        let org = model
+            .create(make_org_data("legacy-post", &org_type), &person_id)
+            .await
+            .expect("create");
+        // Written straight to the table, as rows from before normalization were
+        DB.query("UPDATE $id SET services = ['Post Production', 'VFX', 'vfx']")
+            .bind(("id", org.id.clone()))
+            .await
+            .and_then(|r| r.check())
+            .expect("seed legacy services");
+
+        assert_eq!(model.backfill_service_tags().await.expect("backfill"), 1);
+        let org = model.get_by_slug("legacy-post").await.expect("reload");
+        assert_eq!(org.services, vec!["post-production", "visual effects"]);
+        // Already canonical: nothing left to rewrite
+        assert_eq!(model.backfill_service_tags().await.expect("backfill"), 0);
+
+        let found = model
+            .search(None, None, None, Some("Post Production"), None, None, 50, 0)
+            .await
+            .expect("search");
+        assert_eq!(found.len(), 1);
+    });
+}
@@ -1,7 +1,7 @@
 use chrono::Utc;
 use slatehub::models::organization::{
     CreateOrganizationData, OrganizationMember, OrganizationModel, SocialLink,
-    UpdateOrganizationData,
+    UpdateOrganizationData, normalize_service, normalize_services, validate_org_numbers,
 };
 use surrealdb::types::RecordId;
 
@@ -180,3 +180,58 @@ fn test_organization_type_variations() {
         assert_eq!(org_data.org_type, org_type);
     }
 }
+
+#[test]
+fn test_service_tags_normalize_to_one_facet() {
+    for raw in [
+        "post-production",
+        "Post Production",
+        "  postproduction ",
+        "POST_PRODUCTION",
+    ] {
+        assert_eq!(normalize_service(raw).as_deref(), Some("post-production"));
+    }
+    assert_eq!(normalize_service("VFX").as_deref(), Some("visual effects"));
+    assert_eq!(
+        normalize_service("  Drone   Cinematography ").as_deref(),
+        Some("drone cinematography")
+    );
+    assert_eq!(normalize_service("   "), None);
+
+    let raw: Vec<String> = ["Colour Grading", "", "color correction", "Casting"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(normalize_services(&raw), vec!["color grading", "casting"]);
+}
+
+#[test]
+fn test_org_numbers_reject_out_of_range_values() {
+    use slatehub::error::Error;
+
+    let fields = |result: Result<(), Error>| match result {
+        Err(Error::ValidationFields(errors)) => errors
+            .by_field()
+            .keys()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>(),
+        other => panic!("expected field errors, got {other:?}"),
+    };
+
+    assert_eq!(
+        fields(validate_org_numbers(Some(9999), None)),
+        vec!["founded_year"]
+    );
+    assert_eq!(
+        fields(validate_org_numbers(Some(1799), None)),
+        vec!["founded_year"]
+    );
+    assert_eq!(
+        fields(validate_org_numbers(None, Some(-1))),
+        vec!["employees_count"]
+    );
+
+    assert!(validate_org_numbers(Some(1800), Some(0)).is_ok());
+    assert!(validate_org_numbers(Some(2020), Some(50)).is_ok());
+    assert!(validate_org_numbers(None, None).is_ok());
+}
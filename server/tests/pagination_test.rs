@@ -0,0 +1,64 @@
+//! `PageQuery` clamping/extraction and `Page` arithmetic.
+
+use axum::extract::FromRequestParts;
+use axum::http::Request;
+use slatehub::pagination::{DEFAULT_PER_PAGE, MAX_PER_PAGE, Page, PageQuery};
+
+async fn extract(uri: &str) -> PageQuery {
+    let (mut parts, ()) = Request::builder().uri(uri).body(()).unwrap().into_parts();
+    PageQuery::from_request_parts(&mut parts, &())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn test_page_query_clamps() {
+    assert_eq!(
+        PageQuery::default(),
+        PageQuery::new(Some(1), Some(DEFAULT_PER_PAGE))
+    );
+    assert_eq!(
+        PageQuery::new(Some(0), Some(0)),
+        PageQuery::new(Some(1), Some(1))
+    );
+    assert_eq!(PageQuery::new(None, Some(10_000)).per_page, MAX_PER_PAGE);
+
+    let query = PageQuery::new(Some(3), Some(25));
+    assert_eq!(query.offset(), 50);
+    assert_eq!(query.limit(), 25);
+}
+
+#[tokio::test]
+async fn test_extractor_reads_query_string_and_ignores_garbage() {
+    assert_eq!(
+        extract("/list?page=4&per_page=10").await,
+        PageQuery::new(Some(4), Some(10))
+    );
+    assert_eq!(extract("/list").await, PageQuery::default());
+    assert_eq!(
+        extract("/list?page=abc&per_page=-5").await,
+        PageQuery::default()
+    );
+    assert_eq!(extract("/list?per_page=500").await.per_page, MAX_PER_PAGE);
+}
+
+#[test]
+fn test_page_totals_and_map() {
+    let page = Page::new(vec![1, 2], 5, PageQuery::new(Some(2), Some(2)));
+    assert_eq!(page.total_pages(), 3);
+    assert!(page.has_more());
+
+    let page = page.map(|n| n * 10);
+    assert_eq!(page.items, vec![10, 20]);
+    assert_eq!((page.total, page.page, page.per_page), (5, 2, 2));
+
+    let empty: Page<u8> = Page::new(vec![], 0, PageQuery::default());
+    assert_eq!(empty.total_pages(), 1);
+    assert!(!empty.has_more());
+
+    let json = serde_json::to_value(Page::new(vec!["a"], 1, PageQuery::default())).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "items": ["a"], "total": 1, "page": 1, "per_page": DEFAULT_PER_PAGE })
+    );
+}
@@ -0,0 +1,97 @@
+//! Tests for configurable Argon2 cost and rehash-on-login: stale-parameter
+//! detection (pure) and the upgrade performed by a successful
+//! `Person::authenticate` against the test DB.
+
+mod common;
+
+use slatehub::auth::{hash_password_sync, hash_password_with_sync, needs_rehash};
+use slatehub::config::{PasswordHashParams, password_hash_params};
+use slatehub::db::DB;
+use slatehub::models::person::Person;
+use surrealdb::types::RecordId;
+
+/// Deliberately cheap parameters standing in for "last year's" settings.
+const OLD_COST: PasswordHashParams = PasswordHashParams {
+    memory_kib: 64,
+    iterations: 1,
+    parallelism: 1,
+};
+
+#[test]
+fn current_hashes_do_not_need_rehash() {
+    let hash = hash_password_sync("hunter22").unwrap();
+    assert!(!needs_rehash(&hash));
+}
+
+#[test]
+fn weaker_hashes_need_rehash() {
+    assert!(OLD_COST.is_weaker_than(password_hash_params()));
+    let hash = hash_password_with_sync("hunter22", &OLD_COST).unwrap();
+    assert!(needs_rehash(&hash));
+}
+
+#[test]
+fn unparseable_hashes_are_left_alone() {
+    assert!(!needs_rehash("not-a-phc-string"));
+}
+
+#[test]
+fn successful_login_upgrades_weak_hash() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        let id = RecordId::new("person", "rehash");
+        let old_hash = hash_password_with_sync("correct horse", &OLD_COST).unwrap();
+        DB.query(
+            "CREATE $id CONTENT {
+                username: 'rehash',
+                email: 'rehash@example.com',
+                password: $password,
+                verification_status: 'email'
+            }",
+        )
+        .bind(("id", id.clone()))
+        .bind(("password", old_hash.clone()))
+        .await
+        .expect("create person");
+
+        let read_hash = || async {
+            let hash: Option<String> = DB
+                .query("SELECT VALUE password FROM ONLY $id")
+                .bind(("id", id.clone()))
+                .await
+                .unwrap()
+                .take(0)
+                .unwrap();
+            hash.expect("password")
+        };
+
+        // A failed login must not touch the stored hash.
+        assert!(
+            Person::authenticate("rehash", "wrong")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(read_hash().await, old_hash);
+
+        let person = Person::authenticate("rehash", "correct horse")
+            .await
+            .expect("authenticate")
+            .expect("valid credentials");
+        assert_eq!(person.username, "rehash");
+
+        let new_hash = read_hash().await;
+        assert_ne!(new_hash, old_hash);
+        assert!(!needs_rehash(&new_hash));
+
+        // The upgraded hash still logs in.
+        assert!(
+            Person::authenticate("rehash", "correct horse")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    });
+}
@@ -0,0 +1,276 @@
+//! The `/people` directory lists only public, verified profiles, filters
+//! by exact skill and location substring, and aggregates specialties from
+//! the same public set; free-text search applies the same filters before
+//! paging. The page's cards take their avatars from one batched lookup.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use slatehub::db::DB;
+use slatehub::models::person::{AvailabilityStatus, Person};
+
+/// Insert a person with a minimal profile.
+async fn insert_person(
+    username: &str,
+    status: &str,
+    private: bool,
+    skills: &[&str],
+    location: &str,
+) {
+    DB.query(
+        "CREATE person CONTENT {
+            email: string::concat($username, '@example.com'),
+            password: 'hashed',
+            username: $username,
+            verification_status: $status,
+            profile: {
+                name: $username, location: $location, skills: $skills, is_private: $private,
+                social_links: [], ethnicity: [], unions: [], languages: [], experience: [],
+                education: [], reels: [], media_other: [], awards: []
+            }
+        }",
+    )
+    .bind(("username", username.to_string()))
+    .bind(("status", status.to_string()))
+    .bind(("private", private))
+    .bind((
+        "skills",
+        skills.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+    ))
+    .bind(("location", location.to_string()))
+    .await
+    .and_then(|r| r.check())
+    .expect("create person");
+}
+
+async fn seed_directory() {
+    insert_person(
+        "dir_editor",
+        "email",
+        false,
+        &["Editor", "Colorist"],
+        "Los Angeles, CA",
+    )
+    .await;
+    insert_person(
+        "dir_grip",
+        "email",
+        false,
+        &["Grip", "Editor"],
+        "Atlanta, GA",
+    )
+    .await;
+    insert_person(
+        "dir_hidden",
+        "email",
+        true,
+        &["Editor", "Gaffer"],
+        "Los Angeles, CA",
+    )
+    .await;
+    insert_person(
+        "dir_pending",
+        "unverified",
+        false,
+        &["Editor"],
+        "Los Angeles, CA",
+    )
+    .await;
+}
+
+fn usernames(persons: Vec<Person>) -> Vec<String> {
+    let mut names: Vec<String> = persons.into_iter().map(|p| p.username).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_directory_excludes_private_and_unverified_profiles() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        seed_directory().await;
+        let page = Person::directory_page(None, None, None, 20, 0)
+            .await
+            .expect("directory page");
+        assert_eq!(usernames(page), ["dir_editor", "dir_grip"]);
+    });
+}
+
+#[test]
+fn test_directory_filters_by_skill_and_location() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        seed_directory().await;
+
+        let editors = Person::directory_page(Some("Editor"), None, None, 20, 0)
+            .await
+            .expect("skill filter");
+        assert_eq!(usernames(editors), ["dir_editor", "dir_grip"]);
+
+        let in_la = Person::directory_page(None, Some("los angeles"), None, 20, 0)
+            .await
+            .expect("location filter");
+        assert_eq!(usernames(in_la), ["dir_editor"]);
+
+        let grips_in_la = Person::directory_page(Some("Grip"), Some("Los Angeles"), None, 20, 0)
+            .await
+            .expect("combined filter");
+        assert!(grips_in_la.is_empty());
+    });
+}
+
+#[test]
+fn test_directory_filters_by_availability_status() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        seed_directory().await;
+        DB.query("UPDATE person SET profile.availability_status = 'open_to_offers' WHERE username = 'dir_grip'")
+            .await
+            .and_then(|r| r.check())
+            .expect("set status");
+
+        let open =
+            Person::directory_page(None, None, Some(AvailabilityStatus::OpenToOffers), 20, 0)
+                .await
+                .expect("availability filter");
+        assert_eq!(usernames(open), ["dir_grip"]);
+
+        let available =
+            Person::directory_page(None, None, Some(AvailabilityStatus::Available), 20, 0)
+                .await
+                .expect("availability filter");
+        assert!(available.is_empty());
+    });
+}
+
+#[test]
+fn test_directory_paginates() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        seed_directory().await;
+        let first = Person::directory_page(None, None, None, 1, 0)
+            .await
+            .unwrap();
+        let second = Person::directory_page(None, None, None, 1, 1)
+            .await
+            .unwrap();
+        let third = Person::directory_page(None, None, None, 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 1);
+        assert_ne!(first[0].username, second[0].username);
+        assert!(third.is_empty());
+    });
+}
+
+#[test]
+fn test_distinct_skills_counts_public_profiles_only() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        seed_directory().await;
+        let skills = Person::distinct_skills(10).await.expect("distinct skills");
+        assert_eq!(
+            skills,
+            [
+                ("Editor".to_string(), 2),
+                ("Colorist".to_string(), 1),
+                ("Grip".to_string(), 1),
+            ]
+        );
+
+        let top = Person::distinct_skills(1).await.expect("limited skills");
+        assert_eq!(top, [("Editor".to_string(), 2)]);
+    });
+}
+
+#[test]
+fn test_people_page_cards_use_batched_avatars() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        seed_directory().await;
+        DB.query(
+            "UPDATE person SET profile.avatar = 'https://cdn.example.com/grip.jpg' \
+             WHERE username = 'dir_grip'",
+        )
+        .await
+        .and_then(|r| r.check())
+        .expect("set avatar");
+
+        let response = common::send(Request::get("/people").body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8_lossy(&bytes);
+        assert!(html.contains("https://cdn.example.com/grip.jpg"));
+        // No upload: the generated initials placeholder, not the static
+        // default.
+        assert!(html.contains("/api/avatar?id=person:"));
+    });
+}
+
+#[test]
+fn test_search_applies_skill_and_location_before_paging() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        // More text matches than a page, with the few grips ranked anywhere.
+        for i in 0..25 {
+            insert_person(
+                &format!("crewmate_editor_{i}"),
+                "email",
+                false,
+                &["Editor"],
+                "Atlanta, GA",
+            )
+            .await;
+        }
+        for (i, location) in ["Atlanta, GA", "Atlanta, GA", "Los Angeles, CA"]
+            .into_iter()
+            .enumerate()
+        {
+            insert_person(
+                &format!("crewmate_grip_{i}"),
+                "email",
+                false,
+                &["Grip"],
+                location,
+            )
+            .await;
+        }
+
+        let page = |uri: &'static str| async move {
+            let response = common::send(Request::get(uri).body(Body::empty()).unwrap()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+
+        let html = page("/people?filter=crewmate&skill=Grip").await;
+        for i in 0..3 {
+            assert!(html.contains(&format!("crewmate_grip_{i}")), "grip {i}");
+        }
+        assert!(!html.contains("crewmate_editor_"));
+
+        let html = page("/people?filter=crewmate&skill=Grip&location=atlanta").await;
+        assert!(html.contains("crewmate_grip_0") && html.contains("crewmate_grip_1"));
+        assert!(!html.contains("crewmate_grip_2"));
+    });
+}
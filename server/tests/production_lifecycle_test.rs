@@ -1,7 +1,11 @@
 //! Unit tests for the production lifecycle phase mapping that drives the
-//! management-overview stepper. Pure logic — no test DB required.
+//! management-overview stepper, and the status transitions it permits.
+//! Pure logic — no test DB required.
 
-use slatehub::models::production::{LifecyclePhase, LifecycleView};
+use slatehub::error::Error;
+use slatehub::models::production::{
+    LifecyclePhase, LifecycleView, allowed_statuses, validate_status_transition,
+};
 
 #[test]
 fn maps_canonical_status_strings_to_phases() {
@@ -120,3 +124,77 @@ fn step_numbers_are_one_based_and_sequential() {
     let numbers: Vec<usize> = view.steps.iter().map(|s| s.number).collect();
     assert_eq!(numbers, vec![1, 2, 3, 4, 5, 6]);
 }
+
+#[test]
+fn transitions_follow_the_lifecycle() {
+    for (from, to) in [
+        ("Development", "Pre-Production"),
+        ("Pre-Production", "Development"),
+        ("Production", "Post-Production"),
+        ("Post-Production", "Released"),
+        ("Completed", "Festival"),
+        ("Released", "Festival"),
+        ("Production", "Canceled"),
+        ("Canceled", "Development"),
+    ] {
+        assert!(
+            validate_status_transition(from, to).is_ok(),
+            "{from} -> {to} should be allowed"
+        );
+    }
+
+    for (from, to) in [
+        ("Development", "Released"),
+        ("Development", "Production"),
+        ("Released", "Development"),
+        ("Released", "Canceled"),
+        ("Canceled", "Production"),
+    ] {
+        let err = validate_status_transition(from, to).unwrap_err();
+        assert!(
+            matches!(&err, Error::Validation(msg) if msg.contains(from) && msg.contains(to)),
+            "{from} -> {to} should be rejected, got {err:?}"
+        );
+    }
+}
+
+#[test]
+fn every_phase_can_stay_put() {
+    for phase in LifecyclePhase::ORDERED
+        .into_iter()
+        .chain([LifecyclePhase::Canceled])
+    {
+        assert!(phase.can_move_to(phase), "{phase:?}");
+    }
+}
+
+#[test]
+fn allowed_statuses_keep_table_order_and_the_current_status() {
+    let statuses: Vec<String> = [
+        "Development",
+        "Pre-Production",
+        "Production",
+        "Post-Production",
+        "Completed",
+        "Festival",
+        "Pre-Sales",
+        "Released",
+        "Canceled",
+    ]
+    .map(String::from)
+    .to_vec();
+
+    assert_eq!(
+        allowed_statuses("Pre-Production", &statuses),
+        ["Development", "Pre-Production", "Production", "Canceled"]
+    );
+    assert_eq!(
+        allowed_statuses("Released", &statuses),
+        ["Completed", "Festival", "Pre-Sales", "Released"]
+    );
+    // A legacy free-form status stays selectable so saving doesn't change it
+    assert_eq!(
+        allowed_statuses("in_development", &statuses)[0],
+        "in_development"
+    );
+}
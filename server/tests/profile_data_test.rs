@@ -25,6 +25,7 @@ fn blank() -> ProfileData {
         skills: vec![],
         languages: vec![],
         availability: None,
+        availability_status: None,
         involvements: vec![],
         education: vec![],
         social_links: vec![],
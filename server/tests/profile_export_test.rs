@@ -0,0 +1,77 @@
+//! `/profile/export.json` downloads the signed-in user's own data, without
+//! credentials or other users' details.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::db::DB;
+
+#[test]
+fn test_export_requires_login() {
+    common::run(async {
+        let response = common::send(
+            Request::builder()
+                .uri("/profile/export.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_ne!(response.status(), StatusCode::OK);
+    });
+}
+
+#[test]
+fn test_export_downloads_own_data() {
+    common::setup_test_db();
+    common::clean_table("likes");
+    common::clean_table("location");
+    common::clean_table("person");
+
+    common::run(async {
+        let user = common::create_test_user("export_user").await;
+        let other = common::create_test_user("export_other").await;
+
+        DB.query(
+            "RELATE $user->likes->$other;
+             RELATE $other->likes->$user;
+             CREATE location CONTENT {
+                name: 'Warehouse', address: '1 Main St', city: 'Atlanta', state: 'GA',
+                country: 'US', contact_name: 'Export User',
+                contact_email: 'export_user@example.com', created_by: $user
+             };",
+        )
+        .bind(("user", user.id.clone()))
+        .bind(("other", other.id.clone()))
+        .await
+        .and_then(|r| r.check())
+        .expect("seed export data");
+
+        let response = common::send(common::authed_request(
+            &user,
+            "GET",
+            "/profile/export.json",
+            "",
+        ))
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let disposition = response.headers()[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(disposition.starts_with("attachment;"));
+        assert!(disposition.contains("export_user"));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let export: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(export["person"]["username"], "export_user");
+        assert!(export["person"].get("password").is_none());
+        assert_eq!(export["follows"].as_array().unwrap().len(), 1);
+        assert_eq!(export["follower_count"], 1);
+        assert_eq!(export["locations"][0]["name"], "Warehouse");
+        assert!(!String::from_utf8_lossy(&body).contains("export_other@"));
+    });
+}
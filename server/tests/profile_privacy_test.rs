@@ -0,0 +1,134 @@
+//! Tests for privacy-aware profile rendering: who gets which
+//! `ProfileAccess`, what `ProfileData::redact_for_visitor` strips, and that
+//! the private-profile page carries identity only. Pure logic, no DB.
+
+use askama::Template;
+use slatehub::models::person::{Person, Profile, ProfileAccess};
+use slatehub::templates::{BaseContext, PrivateProfileTemplate, ProfileData};
+use surrealdb::types::RecordId;
+
+fn person(profile: Profile) -> Person {
+    Person {
+        id: RecordId::new("person", "priv"),
+        username: "priv".to_string(),
+        email: "priv@example.com".to_string(),
+        name: Some("Private Person".to_string()),
+        verification_status: "email".to_string(),
+        profile: Some(profile),
+        messaging_preference: "anyone".to_string(),
+        timezone: None,
+    }
+}
+
+/// A fully filled-in profile as the route builds it, before redaction.
+fn full_profile_data(is_public: bool) -> ProfileData {
+    ProfileData {
+        id: "person:priv".to_string(),
+        name: "Private Person".to_string(),
+        username: "priv".to_string(),
+        email: "priv@example.com".to_string(),
+        avatar: None,
+        initials: "PP".to_string(),
+        headline: Some("Gaffer".to_string()),
+        bio: Some("Lights things.".to_string()),
+        location: Some("Atlanta, GA".to_string()),
+        website: None,
+        skills: vec!["Lighting".to_string()],
+        languages: vec![],
+        availability: None,
+        availability_status: None,
+        involvements: vec![],
+        education: vec![],
+        social_links: vec![],
+        reels: vec![],
+        photos: vec![],
+        is_own_profile: false,
+        is_public,
+        verification_status: "email".to_string(),
+        gender: Some("Female".to_string()),
+        birthday: Some("1990-01-01".to_string()),
+        height_mm: Some(1700),
+        weight_kg: Some(60),
+        body_type: Some("Athletic".to_string()),
+        hair_color: Some("Brown".to_string()),
+        eye_color: None,
+        ethnicity: vec!["Irish".to_string()],
+        acting_age_range_min: Some(25),
+        acting_age_range_max: Some(35),
+        acting_ethnicities: vec![],
+        nationality: Some("US".to_string()),
+        messaging_preference: "anyone".to_string(),
+        phone: Some("555-0100".to_string()),
+    }
+}
+
+#[test]
+fn owner_sees_all() {
+    let private = person(Profile {
+        is_private: true,
+        private_fields: vec!["birthday".to_string()],
+        ..Default::default()
+    });
+    assert_eq!(private.profile_access(true), ProfileAccess::Owner);
+}
+
+#[test]
+fn anonymous_sees_public_fields_only() {
+    let public = person(Profile {
+        private_fields: vec!["birthday".to_string(), "measurements".to_string()],
+        ..Default::default()
+    });
+    assert_eq!(public.profile_access(false), ProfileAccess::Visitor);
+
+    let mut data = full_profile_data(false);
+    data.redact_for_visitor(public.private_fields());
+
+    // Contact details need the owner's opt-in (`is_public`).
+    assert!(data.email.is_empty());
+    assert!(data.phone.is_none());
+    // Hidden fields are gone.
+    assert!(data.birthday.is_none());
+    assert!(data.height_mm.is_none());
+    assert!(data.weight_kg.is_none());
+    assert!(data.body_type.is_none());
+    // Everything else survives.
+    assert_eq!(data.headline.as_deref(), Some("Gaffer"));
+    assert_eq!(data.gender.as_deref(), Some("Female"));
+    assert_eq!(data.nationality.as_deref(), Some("US"));
+    assert_eq!(data.ethnicity, vec!["Irish".to_string()]);
+}
+
+#[test]
+fn contact_details_survive_when_owner_opted_in() {
+    let mut data = full_profile_data(true);
+    data.redact_for_visitor(&[]);
+    assert_eq!(data.email, "priv@example.com");
+    assert_eq!(data.phone.as_deref(), Some("555-0100"));
+}
+
+#[test]
+fn private_profile_renders_minimal_page() {
+    let private = person(Profile {
+        is_private: true,
+        headline: Some("Secret headline".to_string()),
+        bio: Some("Secret bio".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(private.profile_access(false), ProfileAccess::Private);
+
+    let base = BaseContext::new().with_page("profile");
+    let html = slatehub::with_base!(PrivateProfileTemplate, base, {
+        name: private.get_display_name(),
+        username: private.username.clone(),
+        avatar: private.get_avatar_url(),
+        initials: private.get_initials(),
+    })
+    .render()
+    .expect("render private profile");
+
+    assert!(html.contains("This profile is private"));
+    assert!(html.contains("Private Person"));
+    assert!(!html.contains("Secret headline"));
+    assert!(!html.contains("Secret bio"));
+    assert!(!html.contains("priv@example.com"));
+}
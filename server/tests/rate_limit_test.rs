@@ -0,0 +1,72 @@
+//! Sliding-window limiter decisions and the 429 response they produce:
+//! `Retry-After`, `X-RateLimit-*` headers, and `retry_after` in the JSON
+//! body. Pure logic — no test DB required.
+
+use axum::http::{StatusCode, header};
+use axum::response::IntoResponse;
+use slatehub::error::Error;
+use slatehub::rate_limit::{RateLimitStatus, RateLimiter};
+use std::time::{Duration, Instant};
+
+#[test]
+fn allows_up_to_the_limit_then_rejects_until_the_window_passes() {
+    let limiter = RateLimiter::new(2, Duration::from_secs(60));
+    let start = Instant::now();
+
+    let first = limiter.check_at("1.2.3.4", start);
+    assert!(first.allowed);
+    assert_eq!(first.status.remaining, 1);
+
+    let second = limiter.check_at("1.2.3.4", start + Duration::from_secs(10));
+    assert!(second.allowed);
+    assert_eq!(second.status.remaining, 0);
+
+    let third = limiter.check_at("1.2.3.4", start + Duration::from_secs(20));
+    assert!(!third.allowed);
+    assert_eq!(third.status.reset_after, Duration::from_secs(40));
+
+    // Other keys have their own budget.
+    assert!(limiter.check_at("5.6.7.8", start).allowed);
+
+    // Once the first hit ages out a slot frees up.
+    let later = limiter.check_at("1.2.3.4", start + Duration::from_secs(61));
+    assert!(later.allowed);
+    assert_eq!(later.status.remaining, 0);
+}
+
+#[test]
+fn rate_limited_error_carries_backoff_headers_and_body() {
+    let status = RateLimitStatus {
+        limit: 3,
+        remaining: 0,
+        reset_after: Duration::from_millis(90_500),
+    };
+    let response = Error::RateLimited {
+        message: "Slow down".to_string(),
+        status,
+    }
+    .into_response();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let headers = response.headers();
+    assert_eq!(headers[header::RETRY_AFTER], "91");
+    assert_eq!(headers["x-ratelimit-limit"], "3");
+    assert_eq!(headers["x-ratelimit-remaining"], "0");
+    assert_eq!(headers["x-ratelimit-reset"], "91");
+
+    let parsed = RateLimitStatus::from_headers(headers).unwrap();
+    assert_eq!(parsed.limit, 3);
+    assert_eq!(parsed.retry_after_secs(), 91);
+
+    let body = json_body(response);
+    assert_eq!(body["error"], "Slow down");
+    assert_eq!(body["retry_after"], 91);
+}
+
+fn json_body(response: axum::response::Response) -> serde_json::Value {
+    let bytes = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(axum::body::to_bytes(response.into_body(), usize::MAX))
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
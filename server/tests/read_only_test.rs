@@ -0,0 +1,59 @@
+//! Read-only maintenance mode: writes get a 503 with `Retry-After` before
+//! reaching any handler, reads and the exempt endpoints pass through, and
+//! turning the mode off restores writes.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::middleware::read_only::{is_read_only, set_read_only};
+use tower::ServiceExt;
+
+async fn send(method: &str, uri: &str) -> axum::response::Response {
+    slatehub::routes::app()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn read_only_mode_rejects_writes_only() {
+    // SAFETY: the only test in this binary; read once when the flag is
+    // first touched.
+    unsafe { std::env::remove_var("READ_ONLY_MODE") };
+    assert!(!is_read_only());
+
+    set_read_only(true);
+    for (method, uri) in [
+        ("POST", "/api/feedback"),
+        ("DELETE", "/api/media/profile-image"),
+        ("PUT", "/profile"),
+    ] {
+        let response = send(method, uri).await;
+        assert_eq!(
+            response.status(),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "{method} {uri}"
+        );
+        assert_eq!(response.headers()[header::RETRY_AFTER], "60");
+    }
+
+    assert_eq!(
+        send("GET", "/static/js/spow.js").await.status(),
+        StatusCode::OK
+    );
+    assert_ne!(
+        send("POST", "/admin/read-only").await.status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    set_read_only(false);
+    assert_ne!(
+        send("POST", "/api/feedback").await.status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
@@ -0,0 +1,44 @@
+//! Unit tests for `slatehub::record_id_ext` — prefix-insensitive record id
+//! normalization and comparison. Pure functions; no test DB required.
+
+use slatehub::record_id_ext::{RecordIdExt, normalize_record_id};
+use surrealdb::types::RecordId;
+
+#[test]
+fn normalize_accepts_prefixed_and_bare_ids() {
+    let expected = RecordId::new("person", "abc123");
+    assert_eq!(normalize_record_id("person:abc123", "person"), expected);
+    assert_eq!(normalize_record_id("abc123", "person"), expected);
+    assert_eq!(normalize_record_id(" person:abc123 ", "person"), expected);
+}
+
+#[test]
+fn normalize_keeps_an_explicit_table() {
+    let id = normalize_record_id("organization:acme", "person");
+    assert_eq!(id.to_raw_string(), "organization:acme");
+}
+
+#[test]
+fn normalize_strips_surreal_key_escaping() {
+    assert_eq!(
+        normalize_record_id("person:⟨a-b-c⟩", "person"),
+        RecordId::new("person", "a-b-c")
+    );
+    assert_eq!(
+        normalize_record_id("person:`a-b-c`", "person"),
+        RecordId::new("person", "a-b-c")
+    );
+}
+
+#[test]
+fn matches_id_ignores_prefix_but_not_table() {
+    let creator = RecordId::new("person", "abc123");
+    assert!(creator.matches_id("person:abc123", "person"));
+    assert!(creator.matches_id("abc123", "person"));
+    assert!(!creator.matches_id("person:other", "person"));
+
+    // An org-created record never matches a person id with the same key.
+    let org = RecordId::new("organization", "abc123");
+    assert!(!org.matches_id("abc123", "person"));
+    assert!(!org.matches_id("person:abc123", "person"));
+}
@@ -0,0 +1,71 @@
+//! Unit tests for post-login redirect vetting in `slatehub::response`.
+//! Pure functions; no test DB required.
+
+use axum::http::header;
+use slatehub::response::{is_safe_redirect, redirect_to_target, safe_redirect_target};
+
+fn hosts() -> Vec<String> {
+    vec!["slatehub.com".to_string()]
+}
+
+#[test]
+fn allows_same_site_paths() {
+    for target in ["/profile", "/orgs/acme?tab=members", "/a/b#frag"] {
+        assert!(is_safe_redirect(target, &hosts()), "{target}");
+    }
+}
+
+#[test]
+fn rejects_offsite_and_protocol_relative_targets() {
+    for target in [
+        "//evil.com",
+        "https://evil.com",
+        "http://evil.com/profile",
+        "/\\evil.com",
+        "/\t/evil.com",
+        "javascript:alert(1)",
+        "evil.com",
+        "",
+    ] {
+        assert!(!is_safe_redirect(target, &hosts()), "{target:?}");
+    }
+}
+
+#[test]
+fn allows_allowlisted_absolute_urls() {
+    assert!(is_safe_redirect("https://slatehub.com/profile", &hosts()));
+    assert!(is_safe_redirect("https://SlateHub.com/", &hosts()));
+    assert!(!is_safe_redirect(
+        "https://slatehub.com.evil.com/",
+        &hosts()
+    ));
+    assert!(!is_safe_redirect("ftp://slatehub.com/", &hosts()));
+}
+
+#[test]
+fn unsafe_targets_fall_back() {
+    assert_eq!(
+        safe_redirect_target(Some("//evil.com"), "/profile"),
+        "/profile"
+    );
+    assert_eq!(
+        safe_redirect_target(Some("https://evil.com"), "/profile"),
+        "/profile"
+    );
+    assert_eq!(safe_redirect_target(None, "/profile"), "/profile");
+    assert_eq!(
+        safe_redirect_target(Some("/productions/new"), "/profile"),
+        "/productions/new"
+    );
+}
+
+#[test]
+fn redirect_to_target_keeps_vetted_location() {
+    let response = redirect_to_target("/profile");
+    assert_eq!(response.headers()[header::LOCATION], "/profile");
+    let response = redirect_to_target("https://slatehub.com/jobs");
+    assert_eq!(
+        response.headers()[header::LOCATION],
+        "https://slatehub.com/jobs"
+    );
+}
@@ -0,0 +1,43 @@
+//! Rental agreement PDF rendering: transliteration-safe output and
+//! continuation onto further pages when the kit list runs long.
+
+use slatehub::services::rental_agreement::{self, RentalAgreement};
+
+fn agreement(kit_items: usize) -> RentalAgreement {
+    RentalAgreement {
+        reference: "r1".to_string(),
+        item_kind: "Kit".to_string(),
+        item_name: "Camera Package".to_string(),
+        item_details: vec![("Category".to_string(), "Camera".to_string())],
+        kit_items: (0..kit_items)
+            .map(|i| format!("Zoë's Lens Number {i}"))
+            .collect(),
+        owner: "Owner".to_string(),
+        renter: "Renter".to_string(),
+        checkout_by: "Owner".to_string(),
+        checkout_date: "Mar 01, 2026 09:00 UTC".to_string(),
+        expected_return_date: None,
+        checkout_condition: "Good".to_string(),
+        checkout_notes: Some("Out for the shoot".to_string()),
+    }
+}
+
+/// Number of page objects in the rendered document.
+fn pages(pdf: &[u8]) -> usize {
+    let pdf = String::from_utf8_lossy(pdf);
+    pdf.matches("/Type/Page").count() - pdf.matches("/Type/Pages").count()
+}
+
+#[test]
+fn test_short_agreement_fits_on_one_page() {
+    let pdf = rental_agreement::render_pdf(&agreement(3)).unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+    assert_eq!(pages(&pdf), 1);
+}
+
+#[test]
+fn test_long_kit_list_continues_onto_further_pages() {
+    let pdf = rental_agreement::render_pdf(&agreement(400)).unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+    assert!(pages(&pdf) > 1, "expected more than one page");
+}
@@ -57,3 +57,30 @@ fn test_request_id_display() {
     let id = RequestId::from_string("display-test".to_string());
     assert_eq!(format!("{}", id), "display-test");
 }
+
+#[tokio::test]
+async fn test_current_request_id_is_scoped_to_the_request() {
+    use axum::{Router, body::Body, http::Request, middleware, routing::get};
+    use slatehub::middleware::request_id::{current, request_id_middleware};
+    use tower::ServiceExt;
+
+    let app = Router::new()
+        .route("/", get(|| async { current().unwrap_or_default() }))
+        .layer(middleware::from_fn(request_id_middleware));
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/")
+                .header("X-Request-Id", "req-scoped-0001")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"req-scoped-0001");
+
+    assert_eq!(current(), None);
+}
@@ -91,7 +91,7 @@ async fn test_s3_full_roundtrip() {
 
     // ---- presigned GET ----
     let get_url = s3
-        .generate_download_url(TEST_KEY)
+        .generate_download_url(TEST_KEY, TEST_CT, "hello notes.txt")
         .await
         .expect("presign_get");
     assert!(
@@ -104,6 +104,13 @@ async fn test_s3_full_roundtrip() {
         "presigned GET status: {}",
         resp.status()
     );
+    assert_eq!(
+        resp.headers()
+            .get("content-disposition")
+            .and_then(|v| v.to_str().ok()),
+        Some("attachment; filename=\"hello notes.txt\""),
+        "presigned GET should override the disposition"
+    );
     let body = resp.bytes().await.expect("presigned GET body");
     assert_eq!(body.as_ref(), TEST_BODY, "presigned GET bytes should match");
 
@@ -0,0 +1,321 @@
+//! Shoot days — `ShootDayInput` form validation and the `ScheduleModel`
+//! CRUD behind the production-management Schedule tab.
+//!
+//! Covers:
+//!   - Parsing: required date, optional call time (HH:MM or HH:MM:SS),
+//!     location id shape, blank-to-unset fields, status allowlist
+//!   - Stored values: midnight-UTC date, call time on the shoot date
+//!   - `list` ordering by date then call time, and per-production isolation
+//!   - `update`/`delete` refusing a day from another production
+//!   - `delete` removing call times, and refusing once a call sheet exists
+//!   - Equipment bookings: refused while a rental covers the day, listed
+//!     once, removable
+
+mod common;
+
+use chrono::{NaiveDate, NaiveTime};
+use slatehub::db::DB;
+use slatehub::error::Error;
+use slatehub::models::schedule::{ScheduleModel, ShootDayForm, ShootDayInput};
+use surrealdb::types::{RecordId, SurrealValue};
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct R {
+    id: RecordId,
+}
+
+fn form(date: &str, call_time: &str) -> ShootDayForm {
+    ShootDayForm {
+        date: date.to_string(),
+        call_time: call_time.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_requires_a_valid_date() {
+    assert!(matches!(
+        ShootDayInput::parse(&form("", "")),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        ShootDayInput::parse(&form("2026-02-30", "")),
+        Err(Error::Validation(_))
+    ));
+
+    let input = ShootDayInput::parse(&form(" 2026-03-14 ", "")).unwrap();
+    assert_eq!(input.date, NaiveDate::from_ymd_opt(2026, 3, 14).unwrap());
+    assert_eq!(input.call_time, None);
+    assert_eq!(input.location, None);
+    assert_eq!(input.location_label, None);
+    assert_eq!(input.notes, None);
+    assert_eq!(input.status, "planned");
+}
+
+#[test]
+fn test_parse_call_time_and_stored_values() {
+    let input = ShootDayInput::parse(&form("2026-03-14", "06:30")).unwrap();
+    assert_eq!(input.call_time, NaiveTime::from_hms_opt(6, 30, 0));
+    assert_eq!(input.date_value().to_rfc3339(), "2026-03-14T00:00:00+00:00");
+    assert_eq!(
+        input.general_call_value().unwrap().to_rfc3339(),
+        "2026-03-14T06:30:00+00:00"
+    );
+
+    let seconds = ShootDayInput::parse(&form("2026-03-14", "06:30:15")).unwrap();
+    assert_eq!(seconds.call_time, NaiveTime::from_hms_opt(6, 30, 15));
+
+    assert!(matches!(
+        ShootDayInput::parse(&form("2026-03-14", "6.30am")),
+        Err(Error::Validation(_))
+    ));
+}
+
+#[test]
+fn test_parse_location_label_notes_and_status() {
+    let input = ShootDayInput::parse(&ShootDayForm {
+        date: "2026-03-14".to_string(),
+        location: "abc123".to_string(),
+        location_label: "  Stage 4 ".to_string(),
+        notes: "   ".to_string(),
+        status: "confirmed".to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(input.location, Some(RecordId::new("location", "abc123")));
+    assert_eq!(input.location_label.as_deref(), Some("Stage 4"));
+    assert_eq!(input.notes, None);
+    assert_eq!(input.status, "confirmed");
+
+    let prefixed = ShootDayInput::parse(&ShootDayForm {
+        date: "2026-03-14".to_string(),
+        location: "location:abc123".to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(prefixed.location, Some(RecordId::new("location", "abc123")));
+
+    // Ids from other tables and unknown statuses are rejected.
+    for bad in [
+        ShootDayForm {
+            date: "2026-03-14".to_string(),
+            location: "person:abc123".to_string(),
+            ..Default::default()
+        },
+        ShootDayForm {
+            date: "2026-03-14".to_string(),
+            status: "someday".to_string(),
+            ..Default::default()
+        },
+    ] {
+        assert!(matches!(
+            ShootDayInput::parse(&bad),
+            Err(Error::Validation(_))
+        ));
+    }
+}
+
+async fn seed_production(slug: &str) -> RecordId {
+    let rows: Vec<R> = DB
+        .query(
+            "CREATE production CONTENT {
+                title: $slug, slug: $slug, type: 'Feature Film',
+                status: 'in_development', source: 'manual'
+            } RETURN id",
+        )
+        .bind(("slug", slug.to_string()))
+        .await
+        .expect("create production")
+        .take(0)
+        .expect("take production");
+    rows.into_iter().next().expect("one").id
+}
+
+async fn add_day(production: &RecordId, date: &str, call_time: &str) -> RecordId {
+    let input = ShootDayInput::parse(&form(date, call_time)).unwrap();
+    ScheduleModel::create(production, &input).await.unwrap()
+}
+
+#[test]
+fn test_list_is_chronological_and_per_production() {
+    common::setup_test_db();
+    common::clean_table("production");
+    common::clean_table("schedule_day");
+    common::run(async {
+        let prod = seed_production("sched-order").await;
+        let other = seed_production("sched-other").await;
+
+        let late = add_day(&prod, "2026-05-02", "07:00").await;
+        let first = add_day(&prod, "2026-05-01", "09:00").await;
+        let early_call = add_day(&prod, "2026-05-02", "05:30").await;
+        add_day(&other, "2026-04-01", "").await;
+
+        let days = ScheduleModel::list(&prod).await.unwrap();
+        let ids: Vec<RecordId> = days.iter().map(|d| d.id.clone()).collect();
+        assert_eq!(ids, vec![first, early_call, late]);
+        assert_eq!(days[0].day(), NaiveDate::from_ymd_opt(2026, 5, 1).unwrap());
+        assert_eq!(days[1].call_time(), NaiveTime::from_hms_opt(5, 30, 0));
+        assert_eq!(days[0].status, "planned");
+
+        assert_eq!(ScheduleModel::list(&other).await.unwrap().len(), 1);
+    });
+}
+
+#[test]
+fn test_update_round_trips_and_is_scoped_to_the_production() {
+    common::setup_test_db();
+    common::clean_table("production");
+    common::clean_table("schedule_day");
+    common::run(async {
+        let prod = seed_production("sched-update").await;
+        let other = seed_production("sched-update-other").await;
+        let day = add_day(&prod, "2026-06-01", "08:00").await;
+
+        let changed = ShootDayInput::parse(&ShootDayForm {
+            date: "2026-06-03".to_string(),
+            call_time: String::new(),
+            location_label: "Pier 9".to_string(),
+            notes: "Night exteriors".to_string(),
+            status: "confirmed".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        ScheduleModel::update(&prod, &day, &changed).await.unwrap();
+
+        let stored = ScheduleModel::get(&prod, &day).await.unwrap();
+        assert_eq!(stored.day(), NaiveDate::from_ymd_opt(2026, 6, 3).unwrap());
+        assert_eq!(stored.call_time(), None);
+        assert_eq!(stored.place(), Some("Pier 9"));
+        assert_eq!(stored.notes.as_deref(), Some("Night exteriors"));
+        assert_eq!(stored.status, "confirmed");
+
+        // Another production can neither read, edit, nor delete the day.
+        assert!(matches!(
+            ScheduleModel::get(&other, &day).await,
+            Err(Error::NotFound)
+        ));
+        assert!(matches!(
+            ScheduleModel::update(&other, &day, &changed).await,
+            Err(Error::NotFound)
+        ));
+        assert!(matches!(
+            ScheduleModel::delete(&other, &day).await,
+            Err(Error::NotFound)
+        ));
+        assert!(ScheduleModel::get(&prod, &day).await.is_ok());
+    });
+}
+
+#[test]
+fn test_delete_removes_call_times_unless_a_call_sheet_exists() {
+    common::setup_test_db();
+    common::clean_table("production");
+    common::clean_table("person");
+    common::clean_table("schedule_day");
+    common::clean_table("call_time");
+    common::clean_table("call_sheet");
+    common::run(async {
+        let prod = seed_production("sched-delete").await;
+        let user = common::create_test_user("sched_delete_crew").await;
+        let day = add_day(&prod, "2026-07-01", "07:00").await;
+        let sheeted = add_day(&prod, "2026-07-02", "07:00").await;
+
+        DB.query(
+            "CREATE call_time CONTENT { schedule_day: $day, person: $person, call_at: d'2026-07-01T06:00:00Z' };
+             CREATE call_sheet CONTENT { schedule_day: $sheeted, pdf_key: 'sheets/day2.pdf' };",
+        )
+        .bind(("day", day.clone()))
+        .bind(("sheeted", sheeted.clone()))
+        .bind(("person", user.id.clone()))
+        .await
+        .expect("seed call time and sheet")
+        .check()
+        .expect("seed statements");
+
+        ScheduleModel::delete(&prod, &day).await.unwrap();
+        assert!(matches!(
+            ScheduleModel::get(&prod, &day).await,
+            Err(Error::NotFound)
+        ));
+        let call_times: Option<i64> = DB
+            .query("SELECT VALUE count() FROM call_time WHERE schedule_day = $day GROUP ALL")
+            .bind(("day", day.clone()))
+            .await
+            .unwrap()
+            .take(0)
+            .unwrap();
+        assert_eq!(call_times.unwrap_or(0), 0);
+
+        assert!(matches!(
+            ScheduleModel::delete(&prod, &sheeted).await,
+            Err(Error::Conflict(_))
+        ));
+        assert!(ScheduleModel::get(&prod, &sheeted).await.is_ok());
+    });
+}
+
+#[test]
+fn test_booking_equipment_is_refused_on_days_it_is_rented_out() {
+    common::setup_test_db();
+    common::clean_table("production");
+    common::clean_table("person");
+    common::clean_table("schedule_day");
+    common::clean_table("equipment");
+    common::clean_table("equipment_rental");
+    common::run(async {
+        let prod = seed_production("sched-gear").await;
+        let owner = common::create_test_user("sched_gear_owner").await;
+        let rented_day = add_day(&prod, "2026-03-11", "07:00").await;
+        let free_day = add_day(&prod, "2026-03-20", "07:00").await;
+
+        // Out from Mar 10 through Mar 12.
+        let equipment: Option<RecordId> = DB
+            .query(
+                "UPSERT equipment_category:test_camera SET name = 'Test Camera';
+                 UPSERT equipment_condition:test_good SET name = 'Test Good';
+                 LET $item = (CREATE ONLY equipment CONTENT {
+                    name: 'Test Body', category: equipment_category:test_camera,
+                    condition: equipment_condition:test_good,
+                    owner_type: 'person', owner_person: $owner, is_available: false
+                 }).id;
+                 CREATE equipment_rental CONTENT {
+                    equipment_id: $item, renter_type: 'person', renter_person: $owner,
+                    checkout_date: d'2026-03-10T09:00:00Z',
+                    expected_return_date: d'2026-03-12T18:00:00Z',
+                    checkout_condition: equipment_condition:test_good,
+                    checkout_by: $owner, is_active: true
+                 };
+                 RETURN $item;",
+            )
+            .bind(("owner", owner.id.clone()))
+            .await
+            .expect("seed rental")
+            .take(5)
+            .expect("take equipment");
+        let equipment = equipment.expect("equipment id");
+
+        assert!(matches!(
+            ScheduleModel::attach_equipment(&prod, &rented_day, &equipment).await,
+            Err(Error::Conflict(_))
+        ));
+        let day = ScheduleModel::get(&prod, &rented_day).await.unwrap();
+        assert!(day.equipment.is_empty());
+
+        ScheduleModel::attach_equipment(&prod, &free_day, &equipment)
+            .await
+            .unwrap();
+        // Booking again doesn't list the item twice.
+        ScheduleModel::attach_equipment(&prod, &free_day, &equipment)
+            .await
+            .unwrap();
+        let day = ScheduleModel::get(&prod, &free_day).await.unwrap();
+        let names: Vec<_> = day.equipment.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Test Body"]);
+
+        ScheduleModel::detach_equipment(&prod, &free_day, &equipment)
+            .await
+            .unwrap();
+        let day = ScheduleModel::get(&prod, &free_day).await.unwrap();
+        assert!(day.equipment.is_empty());
+    });
+}
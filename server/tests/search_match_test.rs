@@ -0,0 +1,55 @@
+//! Match context (matched field + snippet) attached to search results.
+
+use slatehub::services::search_utils::match_context;
+
+#[test]
+fn test_match_context_picks_first_matching_field_in_priority_order() {
+    let m = match_context(
+        "Steadicam operator",
+        &[
+            ("name", Some("Jane Doe")),
+            ("headline", None),
+            (
+                "bio",
+                Some("Certified STEADICAM operator based in Atlanta."),
+            ),
+        ],
+    )
+    .unwrap();
+    assert_eq!(m.field, "bio");
+    assert_eq!(m.snippet, "Certified STEADICAM operator based in Atlanta.");
+
+    let m = match_context("jane", &[("name", Some("Jane Doe")), ("bio", Some("jane"))]).unwrap();
+    assert_eq!(m.field, "name");
+}
+
+#[test]
+fn test_match_context_trims_long_text_around_the_match() {
+    let bio = format!(
+        "{} gaffer with twenty years on features {}",
+        "word ".repeat(40),
+        "tail ".repeat(40)
+    );
+    let snippet = match_context("gaffer", &[("bio", Some(&bio))])
+        .unwrap()
+        .snippet;
+    assert!(
+        snippet.starts_with('…') && snippet.ends_with('…'),
+        "{snippet}"
+    );
+    assert!(snippet.contains("gaffer with twenty years"));
+    assert!(snippet.chars().count() < bio.chars().count());
+    assert!(!snippet.contains("wor…") && !snippet.contains("…ord"));
+}
+
+#[test]
+fn test_match_context_handles_non_ascii_and_misses() {
+    let m = match_context("café", &[("name", Some("Le CAFÉ Crème — Montréal"))]).unwrap();
+    assert_eq!(m.snippet, "Le CAFÉ Crème — Montréal");
+
+    assert_eq!(match_context("a", &[("name", Some("a band"))]), None);
+    assert_eq!(
+        match_context("drone", &[("name", Some("Jane Doe")), ("bio", None)]),
+        None
+    );
+}
@@ -0,0 +1,171 @@
+//! Every response carries the baseline security headers from the layer
+//! stack in `routes::app`, including a `Content-Security-Policy` that
+//! `CONTENT_SECURITY_POLICY` can replace or (when empty) switch off, and
+//! whose `{nonce}` placeholder matches the nonce stamped on inline tags.
+//! No template uses an inline `on*` handler, which the nonce would block.
+
+use axum::body::Body;
+use axum::http::{HeaderMap, Request, header};
+use slatehub::config::DEFAULT_CONTENT_SECURITY_POLICY;
+use slatehub::templates::BaseContext;
+use tower::ServiceExt;
+
+/// Headers and body of a `GET` through the full router.
+fn get(uri: &str) -> (HeaderMap, String) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let response = slatehub::routes::app()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (headers, String::from_utf8_lossy(&body).into_owned())
+    })
+}
+
+/// Headers of a static-file response; no DB or session involved.
+fn response_headers() -> HeaderMap {
+    get("/static/js/spow.js").0
+}
+
+/// The nonce a `'nonce-…'` source in `policy` carries.
+fn policy_nonce(policy: &str) -> String {
+    let start = policy.find("'nonce-").expect("policy carries a nonce") + "'nonce-".len();
+    let end = start + policy[start..].find('\'').unwrap();
+    policy[start..end].to_string()
+}
+
+/// `policy` with its nonce put back to the `{nonce}` placeholder.
+fn without_nonce(policy: &str) -> String {
+    policy.replace(&policy_nonce(policy), "{nonce}")
+}
+
+fn set_csp(value: Option<&str>) {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        match value {
+            Some(v) => std::env::set_var("CONTENT_SECURITY_POLICY", v),
+            None => std::env::remove_var("CONTENT_SECURITY_POLICY"),
+        }
+    }
+}
+
+#[test]
+fn responses_carry_the_baseline_security_headers() {
+    set_csp(None);
+    let headers = response_headers();
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+    assert_eq!(
+        headers[header::REFERRER_POLICY],
+        "strict-origin-when-cross-origin"
+    );
+    assert_eq!(
+        without_nonce(headers[header::CONTENT_SECURITY_POLICY].to_str().unwrap()),
+        DEFAULT_CONTENT_SECURITY_POLICY
+    );
+}
+
+#[test]
+fn default_policy_forbids_framing_and_plugins() {
+    assert!(DEFAULT_CONTENT_SECURITY_POLICY.starts_with("default-src 'self';"));
+    assert!(DEFAULT_CONTENT_SECURITY_POLICY.contains("script-src 'self' 'nonce-{nonce}'"));
+    assert!(DEFAULT_CONTENT_SECURITY_POLICY.contains("frame-ancestors 'none'"));
+    assert!(DEFAULT_CONTENT_SECURITY_POLICY.contains("object-src 'none'"));
+    assert!(!DEFAULT_CONTENT_SECURITY_POLICY.contains('\n'));
+}
+
+#[test]
+fn policy_can_be_overridden() {
+    set_csp(Some("default-src 'self' https://static.example.com"));
+    let headers = response_headers();
+    assert_eq!(
+        headers[header::CONTENT_SECURITY_POLICY],
+        "default-src 'self' https://static.example.com"
+    );
+    set_csp(None);
+}
+
+#[test]
+fn empty_policy_disables_the_header() {
+    set_csp(Some(""));
+    let headers = response_headers();
+    assert!(headers.get(header::CONTENT_SECURITY_POLICY).is_none());
+    assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    set_csp(None);
+}
+
+#[test]
+fn invalid_policy_falls_back_to_the_default() {
+    set_csp(Some("default-src 'self'\u{7f}"));
+    let headers = response_headers();
+    assert_eq!(
+        without_nonce(headers[header::CONTENT_SECURITY_POLICY].to_str().unwrap()),
+        DEFAULT_CONTENT_SECURITY_POLICY
+    );
+    set_csp(None);
+}
+
+#[test]
+fn nonce_placeholder_is_filled_per_request_and_stamped_on_inline_scripts() {
+    set_csp(None);
+
+    let (headers, body) = get("/login");
+    let policy = headers[header::CONTENT_SECURITY_POLICY].to_str().unwrap();
+    assert!(!policy.contains("{nonce}"), "{policy}");
+    let nonce = policy_nonce(policy);
+    assert!(nonce.len() >= 22, "128-bit nonce expected, got {nonce}");
+    assert!(
+        body.contains(&format!("<script nonce=\"{nonce}\">")),
+        "inline scripts carry the header's nonce"
+    );
+
+    let (headers, _) = get("/login");
+    let second = policy_nonce(headers[header::CONTENT_SECURITY_POLICY].to_str().unwrap());
+    assert_ne!(nonce, second, "every response gets a fresh nonce");
+    set_csp(None);
+}
+
+#[test]
+fn nonce_is_empty_outside_a_request() {
+    assert_eq!(BaseContext::new().nonce, "");
+}
+
+#[test]
+fn templates_have_no_inline_event_handlers() {
+    fn visit(dir: &std::path::Path, offenders: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(&path, offenders);
+            } else if path.extension().is_some_and(|e| e == "html") {
+                let html = std::fs::read_to_string(&path).unwrap();
+                for event in [
+                    "click",
+                    "change",
+                    "input",
+                    "submit",
+                    "error",
+                    "mouseover",
+                    "mouseout",
+                ] {
+                    if html.contains(&format!(" on{event}=\"")) {
+                        offenders.push(format!("{} (on{event})", path.display()));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut offenders = Vec::new();
+    visit(
+        &std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("templates"),
+        &mut offenders,
+    );
+    assert!(
+        offenders.is_empty(),
+        "use data-handler-* instead: {offenders:?}"
+    );
+}
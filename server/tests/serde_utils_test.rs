@@ -1,7 +1,8 @@
 use serde::Deserialize;
 use serde_json::json;
 use slatehub::serde_utils::{
-    deserialize_optional_i32, deserialize_optional_string, deserialize_string_list,
+    deserialize_optional_f64, deserialize_optional_i32, deserialize_optional_string,
+    deserialize_string_list,
 };
 
 #[derive(Deserialize, Debug, PartialEq)]
@@ -32,6 +33,32 @@ fn test_deserialize_optional_i32() {
     let json = json!({ "max_capacity": null, "description": "", "tags": "" });
     let form: TestForm = serde_json::from_value(json).unwrap();
     assert_eq!(form.max_capacity, None);
+
+    // Test with invalid input
+    let json = json!({ "max_capacity": "abc", "description": "", "tags": "" });
+    let err = serde_json::from_value::<TestForm>(json).unwrap_err();
+    assert!(err.to_string().contains("Invalid integer"));
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct PriceForm {
+    #[serde(default, deserialize_with = "deserialize_optional_f64")]
+    purchase_price: Option<f64>,
+}
+
+#[test]
+fn test_deserialize_optional_f64() {
+    let form: PriceForm = serde_json::from_value(json!({ "purchase_price": "" })).unwrap();
+    assert_eq!(form.purchase_price, None);
+
+    let form: PriceForm = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(form.purchase_price, None);
+
+    let form: PriceForm = serde_json::from_value(json!({ "purchase_price": " 1299.99 " })).unwrap();
+    assert_eq!(form.purchase_price, Some(1299.99));
+
+    let err = serde_json::from_value::<PriceForm>(json!({ "purchase_price": "abc" })).unwrap_err();
+    assert!(err.to_string().contains("Invalid decimal number"));
 }
 
 #[test]
@@ -0,0 +1,56 @@
+//! The tuned accept loop: protocol negotiation, `ConnectInfo`, and the
+//! HTTP/2 switch.
+
+use std::net::SocketAddr;
+
+use axum::{Router, extract::ConnectInfo, routing::get};
+use slatehub::config::ServerTuning;
+use tokio::net::TcpListener;
+
+async fn spawn_server(tuning: ServerTuning) -> SocketAddr {
+    let app = Router::new().route(
+        "/peer",
+        get(|ConnectInfo(peer): ConnectInfo<SocketAddr>| async move { peer.ip().to_string() }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(slatehub::server::serve(listener, app, tuning));
+    addr
+}
+
+#[tokio::test]
+async fn test_serves_http1_and_h2c_with_connect_info() {
+    let addr = spawn_server(ServerTuning::default()).await;
+    let url = format!("http://{addr}/peer");
+
+    let response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.version(), reqwest::Version::HTTP_11);
+    assert_eq!(response.text().await.unwrap(), "127.0.0.1");
+
+    let h2 = reqwest::Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .unwrap();
+    let response = h2.get(&url).send().await.unwrap();
+    assert_eq!(response.version(), reqwest::Version::HTTP_2);
+    assert_eq!(response.text().await.unwrap(), "127.0.0.1");
+}
+
+#[tokio::test]
+async fn test_http2_can_be_disabled() {
+    let addr = spawn_server(ServerTuning {
+        http2: false,
+        max_connections: Some(4),
+        ..ServerTuning::default()
+    })
+    .await;
+    let url = format!("http://{addr}/peer");
+
+    assert!(reqwest::get(&url).await.unwrap().status().is_success());
+
+    let h2 = reqwest::Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .unwrap();
+    assert!(h2.get(&url).send().await.is_err());
+}
@@ -0,0 +1,102 @@
+//! The `auth_token` cookie is always `HttpOnly`, `Secure` unless
+//! `COOKIE_SECURE=false`, and `SameSite` per `COOKIE_SAMESITE` (Lax by
+//! default, Strict on request) — both when minted and when cleared.
+
+use axum::body::Body;
+use axum::http::{Request, header};
+use slatehub::auth::{cleared_session_cookie, session_cookie};
+use tower::ServiceExt;
+
+/// Reset the cookie settings to their unset defaults.
+fn reset_env() {
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe {
+        std::env::remove_var("COOKIE_SECURE");
+        std::env::remove_var("COOKIE_SAMESITE");
+    }
+}
+
+#[test]
+fn session_cookie_defaults_to_httponly_secure_lax() {
+    reset_env();
+    let header = session_cookie("token".to_string(), false).to_string();
+    assert!(header.starts_with("auth_token=token"), "{header}");
+    assert!(header.contains("HttpOnly"), "{header}");
+    assert!(header.contains("Secure"), "{header}");
+    assert!(header.contains("SameSite=Lax"), "{header}");
+}
+
+#[test]
+fn same_site_strict_is_configurable() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("COOKIE_SAMESITE", "Strict") }
+    let header = session_cookie("token".to_string(), true).to_string();
+    assert!(header.contains("SameSite=Strict"), "{header}");
+    assert!(header.contains("HttpOnly"), "{header}");
+    reset_env();
+}
+
+#[test]
+fn unknown_same_site_falls_back_to_lax() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("COOKIE_SAMESITE", "none") }
+    let header = session_cookie("token".to_string(), false).to_string();
+    assert!(header.contains("SameSite=Lax"), "{header}");
+    reset_env();
+}
+
+#[test]
+fn secure_can_be_disabled_for_plain_http_development() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("COOKIE_SECURE", "false") }
+    let header = session_cookie("token".to_string(), false).to_string();
+    assert!(!header.contains("Secure"), "{header}");
+    assert!(header.contains("HttpOnly"), "{header}");
+    reset_env();
+}
+
+#[test]
+fn cleared_cookie_keeps_the_configured_attributes() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("COOKIE_SAMESITE", "strict") }
+    let header = cleared_session_cookie().to_string();
+    assert!(header.starts_with("auth_token=;"), "{header}");
+    assert!(header.contains("Max-Age=0"), "{header}");
+    assert!(header.contains("HttpOnly"), "{header}");
+    assert!(header.contains("SameSite=Strict"), "{header}");
+    reset_env();
+}
+
+#[test]
+fn logout_sets_the_configured_cookie_attributes() {
+    reset_env();
+    // SAFETY: tests run with --test-threads=1, so env mutation is safe.
+    unsafe { std::env::set_var("COOKIE_SAMESITE", "strict") }
+    let response = tokio::runtime::Runtime::new().unwrap().block_on(
+        slatehub::routes::app().oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/logout")
+                // The jar only emits a removal for a cookie the request sent.
+                .header(header::COOKIE, "auth_token=stale")
+                .body(Body::empty())
+                .unwrap(),
+        ),
+    );
+    let response = response.unwrap();
+    let set_cookie = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find(|v| v.starts_with("auth_token="))
+        .expect("logout clears the auth cookie")
+        .to_string();
+    assert!(set_cookie.contains("HttpOnly"), "{set_cookie}");
+    assert!(set_cookie.contains("SameSite=Strict"), "{set_cookie}");
+    reset_env();
+}
@@ -13,6 +13,7 @@ fn make_session(id: &str) -> SessionUser {
         username: "test".to_string(),
         email: "t@t.test".to_string(),
         name: "Test".to_string(),
+        timezone: None,
     }
 }
 
@@ -0,0 +1,38 @@
+//! Tests for `ShareMeta`, the OpenGraph/Twitter-card data entity handlers
+//! compute for profile, organization, and production pages. Pure logic.
+
+use slatehub::templates::{SHARE_DESCRIPTION_MAX, ShareMeta};
+
+#[test]
+fn relative_paths_and_images_become_absolute() {
+    let meta = ShareMeta::new("Acme — Production Company", "Makes films.", "/orgs/acme")
+        .with_image(Some("/api/media/logo.png"));
+
+    assert!(meta.url.starts_with("http"));
+    assert!(meta.url.ends_with("/orgs/acme"));
+    let image = meta.image.expect("image");
+    assert!(image.starts_with("http"));
+    assert!(image.ends_with("/api/media/logo.png"));
+}
+
+#[test]
+fn absolute_images_pass_through_and_empty_ones_are_dropped() {
+    let tmdb = "https://image.tmdb.org/t/p/w500/poster.jpg";
+    let meta = ShareMeta::new("Film", "", "/productions/film").with_image(Some(tmdb));
+    assert_eq!(meta.image.as_deref(), Some(tmdb));
+
+    let meta = ShareMeta::new("Film", "", "/productions/film").with_image(Some(""));
+    assert!(meta.image.is_none());
+}
+
+#[test]
+fn descriptions_are_collapsed_and_capped() {
+    let meta = ShareMeta::new("T", "  line one\n\n  line   two ", "/x");
+    assert_eq!(meta.description, "line one line two");
+
+    let long = "word ".repeat(100);
+    let meta = ShareMeta::new("T", &long, "/x");
+    assert!(meta.description.chars().count() <= SHARE_DESCRIPTION_MAX);
+    assert!(meta.description.ends_with('…'));
+    assert!(!meta.description.ends_with(" …"));
+}
@@ -0,0 +1,81 @@
+//! Tests for sitemap rendering and paged file-name parsing. Pure logic —
+//! the DB-backed queries are exercised by hitting `/sitemap.xml`.
+
+use chrono::{TimeZone, Utc};
+use slatehub::services::sitemap::{
+    MAX_URLS_PER_SITEMAP, Section, SitemapUrl, page_count, parse_file_name, render_index,
+    render_urlset,
+};
+
+#[test]
+fn file_names_round_trip_through_section_slugs() {
+    for section in Section::ALL {
+        let name = format!("{}-3.xml", section.slug());
+        assert_eq!(parse_file_name(&name), Some((section, 3)));
+    }
+}
+
+#[test]
+fn malformed_file_names_are_rejected() {
+    for name in [
+        "people.xml",
+        "people-0.xml",
+        "people-x.xml",
+        "people-1.txt",
+        "users-1.xml",
+    ] {
+        assert_eq!(parse_file_name(name), None, "{name}");
+    }
+}
+
+#[test]
+fn page_count_rounds_up_and_is_never_zero() {
+    assert_eq!(page_count(0), 1);
+    assert_eq!(page_count(MAX_URLS_PER_SITEMAP), 1);
+    assert_eq!(page_count(MAX_URLS_PER_SITEMAP + 1), 2);
+}
+
+#[test]
+fn urlset_includes_lastmod_and_escapes_paths() {
+    let urls = vec![
+        SitemapUrl {
+            path: "/jane".to_string(),
+            lastmod: Some(Utc.with_ymd_and_hms(2026, 3, 4, 12, 0, 0).unwrap()),
+            changefreq: "weekly",
+            priority: "0.7",
+        },
+        SitemapUrl {
+            path: "/productions/a&b".to_string(),
+            lastmod: None,
+            changefreq: "weekly",
+            priority: "0.6",
+        },
+    ];
+    let xml = render_urlset("https://example.com", &urls);
+
+    assert!(xml.contains("<loc>https://example.com/jane</loc>"));
+    assert!(xml.contains("<lastmod>2026-03-04</lastmod>"));
+    assert!(xml.contains("<loc>https://example.com/productions/a&amp;b</loc>"));
+    assert_eq!(xml.matches("<lastmod>").count(), 1);
+}
+
+#[test]
+fn index_lists_every_page_and_skips_empty_sections() {
+    let xml = render_index(
+        "https://example.com",
+        &[
+            (Section::Pages, 11),
+            (Section::People, MAX_URLS_PER_SITEMAP * 2 + 5),
+            (Section::Jobs, 0),
+        ],
+    );
+
+    assert!(xml.starts_with("<?xml"));
+    assert!(xml.contains("<sitemapindex"));
+    assert!(xml.contains("https://example.com/sitemaps/pages-1.xml"));
+    for page in 1..=3 {
+        assert!(xml.contains(&format!("https://example.com/sitemaps/people-{page}.xml")));
+    }
+    assert!(!xml.contains("people-4.xml"));
+    assert!(!xml.contains("jobs-"));
+}
@@ -0,0 +1,102 @@
+//! Skill typeahead: prefix matching over the aggregated skill list, and the
+//! `/api/skills` endpoint serving it from public profiles.
+
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use slatehub::db::DB;
+use slatehub::models::person::skills_matching;
+
+fn known() -> Vec<(String, u64)> {
+    vec![
+        ("Editor".to_string(), 9),
+        ("Sound Designer".to_string(), 5),
+        ("Production Designer".to_string(), 4),
+        ("Director".to_string(), 3),
+        ("Assistant Editor".to_string(), 2),
+    ]
+}
+
+fn names(matches: Vec<(String, u64)>) -> Vec<String> {
+    matches.into_iter().map(|(skill, _)| skill).collect()
+}
+
+#[test]
+fn prefix_matches_whole_skill_or_any_word_case_insensitively() {
+    assert_eq!(
+        names(skills_matching(&known(), "ed", 10)),
+        ["Editor", "Assistant Editor"]
+    );
+    assert_eq!(
+        names(skills_matching(&known(), "DES", 10)),
+        ["Sound Designer", "Production Designer"]
+    );
+    assert!(skills_matching(&known(), "gaffer", 10).is_empty());
+}
+
+#[test]
+fn matches_keep_usage_order_and_respect_the_limit() {
+    let matches = skills_matching(&known(), "d", 2);
+    assert_eq!(
+        matches,
+        [
+            ("Sound Designer".to_string(), 5),
+            ("Production Designer".to_string(), 4)
+        ]
+    );
+}
+
+#[test]
+fn empty_prefix_returns_the_most_used() {
+    assert_eq!(
+        names(skills_matching(&known(), "  ", 3)),
+        ["Editor", "Sound Designer", "Production Designer"]
+    );
+}
+
+#[test]
+fn test_skills_endpoint_suggests_public_skills() {
+    common::setup_test_db();
+    common::clean_table("person");
+
+    common::run(async {
+        DB.query(
+            "CREATE person CONTENT {
+                email: 'skills-public@example.com', password: 'hashed', username: 'skills_public',
+                verification_status: 'email',
+                profile: { name: 'Public', skills: ['Editor', 'Colorist'], is_private: false,
+                    social_links: [], ethnicity: [], unions: [], languages: [], experience: [],
+                    education: [], reels: [], media_other: [], awards: [] }
+            };
+            CREATE person CONTENT {
+                email: 'skills-private@example.com', password: 'hashed', username: 'skills_private',
+                verification_status: 'email',
+                profile: { name: 'Private', skills: ['Editor', 'Embroiderer'], is_private: true,
+                    social_links: [], ethnicity: [], unions: [], languages: [], experience: [],
+                    education: [], reels: [], media_other: [], awards: [] }
+            };",
+        )
+        .await
+        .and_then(|r| r.check())
+        .expect("seed people");
+
+        let response = common::send(
+            Request::builder()
+                .uri("/api/skills?prefix=e")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            json["results"],
+            serde_json::json!([{ "skill": "Editor", "count": 1 }])
+        );
+    });
+}
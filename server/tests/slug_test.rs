@@ -0,0 +1,49 @@
+//! Unit tests for `slatehub::util::slug` — slug generation and collision
+//! suffixes. Pure functions; no test DB required.
+
+use slatehub::util::slug::{FALLBACK_SLUG, slugify, slugify_unique};
+
+#[test]
+fn slugify_collapses_punctuation_runs() {
+    assert_eq!(slugify("The Last Deposit!"), "the-last-deposit");
+    assert_eq!(slugify("  spaced   out  "), "spaced-out");
+    assert_eq!(slugify("--Acme -- & -- Co.--"), "acme-co");
+    assert_eq!(
+        slugify("Rock'n'Roll: Part II (2024)"),
+        "rock-n-roll-part-ii-2024"
+    );
+}
+
+#[test]
+fn slugify_transliterates_unicode() {
+    assert_eq!(slugify("Émile's Café #2"), "emile-s-cafe-2");
+    assert_eq!(slugify("Straße Zürich"), "strasse-zurich");
+    assert_eq!(slugify("Ørsted Æble"), "orsted-aeble");
+}
+
+#[test]
+fn slugify_of_only_punctuation_is_empty() {
+    assert_eq!(slugify("!!!"), "");
+}
+
+#[tokio::test]
+async fn slugify_unique_appends_first_free_suffix() {
+    let taken = ["acme", "acme-2"];
+    let slug = slugify_unique("Acme", |candidate| async move {
+        Ok::<_, ()>(taken.contains(&candidate.as_str()))
+    })
+    .await;
+    assert_eq!(slug, Ok("acme-3".to_string()));
+
+    let slug = slugify_unique("Fresh Start", |_| async { Ok::<_, ()>(false) }).await;
+    assert_eq!(slug, Ok("fresh-start".to_string()));
+}
+
+#[tokio::test]
+async fn slugify_unique_falls_back_when_nothing_is_left() {
+    let slug = slugify_unique("!!!", |_| async { Ok::<_, ()>(false) }).await;
+    assert_eq!(slug, Ok(FALLBACK_SLUG.to_string()));
+
+    let err = slugify_unique("acme", |_| async { Err::<bool, _>("db down") }).await;
+    assert_eq!(err, Err("db down"));
+}
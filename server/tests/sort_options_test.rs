@@ -0,0 +1,42 @@
+//! `?sort=` allowlists for equipment and location listings: every listed
+//! option round-trips, anything else is a 400 before it can reach SQL.
+
+use slatehub::error::Error;
+use slatehub::models::equipment::EquipmentSort;
+use slatehub::models::location::LocationSort;
+
+#[test]
+fn test_every_equipment_sort_parses() {
+    for sort in EquipmentSort::ALL {
+        assert_eq!(sort.as_str().parse::<EquipmentSort>().unwrap(), sort);
+    }
+    assert_eq!(EquipmentSort::default(), EquipmentSort::CreatedAt);
+}
+
+#[test]
+fn test_every_location_sort_parses() {
+    for sort in LocationSort::ALL {
+        assert_eq!(sort.as_str().parse::<LocationSort>().unwrap(), sort);
+    }
+    assert_eq!(LocationSort::default(), LocationSort::Recent);
+}
+
+#[test]
+fn test_unknown_sorts_are_rejected() {
+    for bad in [
+        "bogus",
+        "NAME",
+        "owner_person",
+        "name DESC",
+        "created_at; REMOVE TABLE equipment",
+    ] {
+        assert!(
+            matches!(bad.parse::<EquipmentSort>(), Err(Error::BadRequest(_))),
+            "equipment accepted {bad:?}"
+        );
+        assert!(
+            matches!(bad.parse::<LocationSort>(), Err(Error::BadRequest(_))),
+            "location accepted {bad:?}"
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! `/static` cache policy: versioned URLs are immutable, bare paths expire.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use slatehub::config::StaticCachePolicy;
+use tower::ServiceExt;
+
+async fn get(uri: &str) -> axum::response::Response {
+    slatehub::routes::app()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn test_versioned_assets_are_immutable() {
+    let policy = StaticCachePolicy::default();
+    let immutable = "public, max-age=31536000, immutable";
+    let short = "public, max-age=3600";
+
+    assert_eq!(
+        policy.cache_control("/css/main.css", Some("v=1.2.6.1700000000")),
+        immutable
+    );
+    assert_eq!(policy.cache_control("/js/app.3f9a1c2e.js", None), immutable);
+    assert_eq!(
+        policy.cache_control("/js/app.0123456789abcdef.min.js", None),
+        immutable
+    );
+
+    assert_eq!(policy.cache_control("/images/logo.svg", None), short);
+    assert_eq!(policy.cache_control("/css/main.css", Some("v=")), short);
+    assert_eq!(policy.cache_control("/css/main.css", Some("nv=3")), short);
+    assert_eq!(policy.cache_control("/images/deadbeef12.png", None), short);
+    assert_eq!(policy.cache_control("/js/jquery.min.js", None), short);
+}
+
+#[test]
+fn test_static_cache_lifetimes_come_from_env() {
+    // SAFETY: the only test in this binary that touches these variables.
+    unsafe {
+        std::env::set_var("STATIC_CACHE_MAX_AGE", "60");
+        std::env::set_var("STATIC_CACHE_VERSIONED_MAX_AGE", "forever");
+    }
+    let policy = StaticCachePolicy::from_env();
+    assert_eq!(policy.max_age, 60);
+    assert_eq!(
+        policy.versioned_max_age,
+        StaticCachePolicy::default().versioned_max_age
+    );
+    unsafe {
+        std::env::remove_var("STATIC_CACHE_MAX_AGE");
+        std::env::remove_var("STATIC_CACHE_VERSIONED_MAX_AGE");
+    }
+}
+
+#[tokio::test]
+async fn test_static_responses_carry_the_policy() {
+    let versioned = get("/static/css/main.css?v=abc").await;
+    assert_eq!(versioned.status(), StatusCode::OK);
+    assert!(
+        versioned.headers()[header::CACHE_CONTROL]
+            .to_str()
+            .unwrap()
+            .ends_with("immutable")
+    );
+
+    let bare = get("/static/images/logo.svg").await;
+    assert_eq!(bare.status(), StatusCode::OK);
+    assert!(
+        !bare.headers()[header::CACHE_CONTROL]
+            .to_str()
+            .unwrap()
+            .contains("immutable")
+    );
+
+    let missing = get("/static/css/nope.css?v=abc").await;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    assert!(missing.headers().get(header::CACHE_CONTROL).is_none());
+}
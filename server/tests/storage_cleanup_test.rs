@@ -0,0 +1,77 @@
+//! The orphaned-upload sweep: which keys a stored URL keeps alive, which
+//! listed objects count as orphans, and its env configuration.
+
+use std::collections::HashSet;
+
+use chrono::{Duration, TimeZone, Utc};
+use slatehub::services::s3::StoredObject;
+use slatehub::services::storage_cleanup::{StorageCleanupConfig, referenced_keys, select_orphans};
+
+#[test]
+fn urls_keep_their_thumbnails_alive() {
+    assert_eq!(
+        referenced_keys("/api/media/profiles/abc/01HX.jpg"),
+        vec!["profiles/abc/01HX.jpg", "profiles/abc/thumb_01HX.jpg"]
+    );
+    assert_eq!(
+        referenced_keys("/api/media/organizations/acme/logo_01HX.svg"),
+        vec![
+            "organizations/acme/logo_01HX.svg",
+            "organizations/acme/thumb_logo_01HX.svg",
+            "organizations/acme/thumb_01HX.jpg",
+        ]
+    );
+    assert_eq!(
+        referenced_keys("productions/p1/photos/thumb_01HX.jpg"),
+        vec!["productions/p1/photos/thumb_01HX.jpg"]
+    );
+    assert!(referenced_keys("").is_empty());
+}
+
+#[test]
+fn only_old_unreferenced_objects_are_orphans() {
+    let now = Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+    let object = |key: &str, age_hours: Option<i64>| StoredObject {
+        key: key.to_string(),
+        size: 100,
+        last_modified: age_hours.map(|h| now - Duration::hours(h)),
+    };
+    let objects = vec![
+        object("profiles/a/live.jpg", Some(100)),
+        object("profiles/a/old.jpg", Some(100)),
+        object("profiles/a/fresh.jpg", Some(1)),
+        object("profiles/a/undated.jpg", None),
+    ];
+    let referenced: HashSet<String> = ["profiles/a/live.jpg".to_string()].into();
+
+    let orphans = select_orphans(&objects, &referenced, now, Duration::hours(24));
+    let keys: Vec<&str> = orphans.iter().map(|o| o.key.as_str()).collect();
+    assert_eq!(keys, vec!["profiles/a/old.jpg"]);
+}
+
+#[test]
+fn config_defaults_to_a_daily_dry_run() {
+    // SAFETY: this is the only test in this binary touching these variables.
+    unsafe {
+        std::env::remove_var("STORAGE_CLEANUP_INTERVAL_HOURS");
+        std::env::remove_var("STORAGE_CLEANUP_GRACE_HOURS");
+        std::env::remove_var("STORAGE_CLEANUP_DRY_RUN");
+        assert_eq!(
+            StorageCleanupConfig::from_env(),
+            StorageCleanupConfig {
+                interval_hours: 24,
+                grace_hours: 24,
+                dry_run: true,
+            }
+        );
+
+        std::env::set_var("STORAGE_CLEANUP_GRACE_HOURS", "72");
+        std::env::set_var("STORAGE_CLEANUP_DRY_RUN", "false");
+        let cfg = StorageCleanupConfig::from_env();
+        assert_eq!(cfg.grace_hours, 72);
+        assert!(!cfg.dry_run);
+
+        std::env::remove_var("STORAGE_CLEANUP_GRACE_HOURS");
+        std::env::remove_var("STORAGE_CLEANUP_DRY_RUN");
+    }
+}
@@ -0,0 +1,42 @@
+//! SVG logo uploads are rasterized and the markup discarded: what gets
+//! stored is a PNG of the drawing, with nothing left of a `<script>`,
+//! event handler, or `<foreignObject>` that could run when served.
+
+use slatehub::services::svg;
+
+const MALICIOUS_SVG: &str = r##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"
+     width="200" height="100" onload="alert(document.cookie)">
+  <script>fetch('/api/auth/me').then(r => r.text()).then(alert)</script>
+  <foreignObject width="100" height="100">
+    <body xmlns="http://www.w3.org/1999/xhtml"><iframe src="javascript:alert(1)"></iframe></body>
+  </foreignObject>
+  <image xlink:href="/etc/hostname" width="50" height="50"/>
+  <rect x="0" y="0" width="100" height="100" fill="#ff0000" onclick="alert(2)"/>
+</svg>"##;
+
+#[test]
+fn scripted_svg_is_rasterized_to_a_plain_png() {
+    let png = svg::rasterize(MALICIOUS_SVG.as_bytes(), 400).expect("rasterize");
+
+    assert_eq!(
+        image::guess_format(&png).expect("format"),
+        image::ImageFormat::Png
+    );
+    let text = String::from_utf8_lossy(&png);
+    for marker in ["<svg", "<script", "onload", "alert", "foreignObject"] {
+        assert!(!text.contains(marker), "{marker} survived rasterization");
+    }
+
+    // The drawing itself survives, scaled so the longer side is 400px.
+    let img = image::load_from_memory(&png).expect("decode").to_rgba8();
+    assert_eq!(img.dimensions(), (400, 200));
+    assert_eq!(img.get_pixel(50, 50).0, [255, 0, 0, 255]);
+    assert_eq!(img.get_pixel(350, 150).0, [255, 255, 255, 255]);
+}
+
+#[test]
+fn non_svg_input_is_rejected() {
+    let err = svg::rasterize(b"<html><script>alert(1)</script></html>", 400).unwrap_err();
+    assert!(matches!(err, slatehub::error::Error::BadRequest(_)));
+}
@@ -1,19 +1,7 @@
-//! Unit tests for `slatehub::text` — slug generation and byte formatting.
+//! Unit tests for `slatehub::text` — byte formatting.
 //! Pure functions; no test DB required.
 
-use slatehub::text::{format_bytes, format_bytes_i64, slugify};
-
-#[test]
-fn slugify_collapses_punctuation_runs() {
-    assert_eq!(slugify("The Last Deposit!"), "the-last-deposit");
-    assert_eq!(slugify("  spaced   out  "), "spaced-out");
-    assert_eq!(slugify("Émile's Café #2"), "émile-s-café-2");
-}
-
-#[test]
-fn slugify_of_only_punctuation_is_empty() {
-    assert_eq!(slugify("!!!"), "");
-}
+use slatehub::text::{format_bytes, format_bytes_i64};
 
 #[test]
 fn bytes_scale_with_expected_precision() {
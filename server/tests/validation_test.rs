@@ -0,0 +1,130 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use chrono::Datelike;
+use slatehub::error::Error;
+use slatehub::validation::{MIN_YEAR, ValidationErrors};
+
+#[test]
+fn test_collects_every_failure_in_order() {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Name", "  ")
+        .email("contact_email", "Contact email", "not-an-email")
+        .year("founded_year", "Founded year", "abc")
+        .max_len("bio", "Bio", &"x".repeat(11), 10);
+
+    assert_eq!(
+        errors.clone().into_messages(),
+        vec![
+            "Name is required",
+            "Contact email must be a valid email address",
+            &format!(
+                "Founded year must be a year between {MIN_YEAR} and {}",
+                chrono::Utc::now().year()
+            ),
+            "Bio must be at most 10 characters",
+        ]
+    );
+    assert_eq!(errors.for_field("name"), Some("Name is required"));
+    assert_eq!(errors.for_field("location"), None);
+    assert!(errors.into_result().is_err());
+}
+
+#[test]
+fn test_valid_and_blank_optional_values_pass() {
+    let this_year = chrono::Utc::now().year().to_string();
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Name", "Acme")
+        .email("contact_email", "Contact email", "")
+        .email("backup_email", "Backup email", "ops@acme.example")
+        .year("founded_year", "Founded year", "")
+        .year("renamed_year", "Renamed year", &this_year)
+        .integer_at_least("employees_count", "Employees", "", 1)
+        .integer_at_least("max_capacity", "Capacity", "25", 1)
+        // Length is counted in characters, not bytes.
+        .max_len("bio", "Bio", "éééé", 4);
+
+    assert!(errors.is_empty());
+    assert!(errors.into_result().is_ok());
+}
+
+#[test]
+fn test_rejects_out_of_range_numbers_and_malformed_emails() {
+    let next_year = (chrono::Utc::now().year() + 1).to_string();
+    for email in [
+        "a@b",
+        "@acme.com",
+        "a b@acme.com",
+        "a@acme..com",
+        "a@@acme.com",
+    ] {
+        let mut errors = ValidationErrors::new();
+        errors.email("email", "Email", email);
+        assert!(!errors.is_empty(), "{email} should be rejected");
+    }
+
+    let mut errors = ValidationErrors::new();
+    errors
+        .year("founded_year", "Founded year", "1799")
+        .year("founded_year", "Founded year", &next_year)
+        .integer_at_least("max_capacity", "Capacity", "0", 1)
+        .integer_at_least("max_capacity", "Capacity", "2.5", 1);
+    assert_eq!(errors.into_messages().len(), 4);
+}
+
+#[test]
+fn test_converts_into_field_validation_error() {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Name", "")
+        .required("slug", "Slug", "");
+
+    let error = Error::from(errors);
+    assert_eq!(
+        error.form_message().as_deref(),
+        Some("Name is required; Slug is required")
+    );
+    match error {
+        Error::ValidationFields(errors) => {
+            assert_eq!(errors.for_field("slug"), Some("Slug is required"))
+        }
+        other => panic!("expected ValidationFields, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_field_errors_serialize_under_fields_key() {
+    let mut errors = ValidationErrors::new();
+    errors
+        .required("name", "Name", "")
+        .max_len("name", "Name", "abcdef", 3)
+        .email("contact_email", "Contact email", "nope");
+
+    let response = Error::from(errors).into_response();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        json["fields"],
+        serde_json::json!({
+            "name": ["Name is required", "Name must be at most 3 characters"],
+            "contact_email": ["Contact email must be a valid email address"],
+        })
+    );
+    assert_eq!(json["status"], 422);
+}
+
+#[tokio::test]
+async fn test_plain_validation_error_has_no_fields() {
+    let response = Error::validation("No fields to update").into_response();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json.get("fields").is_none());
+    assert_eq!(json["error"], "No fields to update");
+}